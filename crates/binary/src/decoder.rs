@@ -0,0 +1,452 @@
+//! Binary decoder, the inverse of `BinaryEncoder`.
+//!
+//! Reads an encoded buffer back into rows so the format can be verified from
+//! Rust without a JS runtime. The format carries no row ids; decoded rows are
+//! numbered from 0 in buffer order.
+
+use super::{crc32, flags, BinaryDataType, SchemaLayout, CHECKSUM_SIZE, HEADER_SIZE};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use cynos_core::{JsonbValue, Row, Value};
+
+/// Reasons a buffer cannot be decoded with a given layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The buffer ends before a section the header points at.
+    Truncated,
+    /// The header's row stride does not match the layout.
+    StrideMismatch { expected: usize, actual: usize },
+    /// A string column holds bytes that are not valid UTF-8.
+    InvalidUtf8 { row: usize, column: usize },
+    /// A decimal column holds text that is not a decimal number.
+    InvalidDecimal { row: usize, column: usize },
+    /// The trailing checksum does not match the buffer contents.
+    ChecksumMismatch { expected: u32, actual: u32 },
+}
+
+impl DecodeError {
+    fn at(self, row: usize, column: usize) -> Self {
+        match self {
+            DecodeError::InvalidUtf8 { .. } => DecodeError::InvalidUtf8 { row, column },
+            DecodeError::InvalidDecimal { .. } => DecodeError::InvalidDecimal { row, column },
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Truncated => write!(f, "binary result is truncated"),
+            DecodeError::StrideMismatch { expected, actual } => write!(
+                f,
+                "row stride {} does not match layout stride {}",
+                actual, expected
+            ),
+            DecodeError::InvalidUtf8 { row, column } => {
+                write!(f, "row {} column {} is not valid UTF-8", row, column)
+            }
+            DecodeError::InvalidDecimal { row, column } => {
+                write!(f, "row {} column {} is not a valid decimal", row, column)
+            }
+            DecodeError::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "checksum {:#010x} does not match stored checksum {:#010x}",
+                actual, expected
+            ),
+        }
+    }
+}
+
+/// Checks the trailing CRC-32 of a buffer encoded with a checksum.
+///
+/// Buffers without `flags::HAS_CHECKSUM` pass unchecked.
+pub fn verify_checksum(buffer: &[u8]) -> Result<(), DecodeError> {
+    if read_u32(buffer, 12)? & flags::HAS_CHECKSUM == 0 {
+        return Ok(());
+    }
+    let payload_len = buffer
+        .len()
+        .checked_sub(CHECKSUM_SIZE)
+        .filter(|&len| len >= HEADER_SIZE)
+        .ok_or(DecodeError::Truncated)?;
+    let expected = read_u32(buffer, payload_len)?;
+    let actual = crc32(&buffer[..payload_len]);
+    if actual != expected {
+        return Err(DecodeError::ChecksumMismatch { expected, actual });
+    }
+    Ok(())
+}
+
+/// Decodes a buffer produced by `BinaryEncoder::finish` into rows.
+///
+/// A buffer carrying a checksum is verified first.
+pub fn decode_rows(buffer: &[u8], layout: &SchemaLayout) -> Result<Vec<Row>, DecodeError> {
+    verify_checksum(buffer)?;
+    let header_flags = read_u32(buffer, 12)?;
+    let int64_as_bigint = header_flags & flags::INT64_AS_BIGINT != 0;
+    let buffer = if header_flags & flags::HAS_CHECKSUM != 0 {
+        &buffer[..buffer.len() - CHECKSUM_SIZE]
+    } else {
+        buffer
+    };
+    let row_count = read_u32(buffer, 0)? as usize;
+    let row_stride = read_u32(buffer, 4)? as usize;
+    let var_offset = read_u32(buffer, 8)? as usize;
+
+    if row_stride != layout.row_stride() {
+        return Err(DecodeError::StrideMismatch {
+            expected: layout.row_stride(),
+            actual: row_stride,
+        });
+    }
+    let fixed_end = row_count
+        .checked_mul(row_stride)
+        .and_then(|size| size.checked_add(HEADER_SIZE))
+        .ok_or(DecodeError::Truncated)?;
+    if fixed_end > var_offset || var_offset > buffer.len() {
+        return Err(DecodeError::Truncated);
+    }
+    let var_section = &buffer[var_offset..];
+
+    (0..row_count)
+        .map(|row_idx| {
+            let row_start = HEADER_SIZE + row_idx * row_stride;
+            let null_mask = &buffer[row_start..row_start + layout.null_mask_size()];
+            let data_start = row_start + layout.null_mask_size();
+
+            let values = layout
+                .columns()
+                .iter()
+                .enumerate()
+                .map(|(col_idx, column)| {
+                    if null_mask[col_idx / 8] & (1 << (col_idx % 8)) != 0 {
+                        return Ok(Value::Null);
+                    }
+                    let pos = data_start + column.offset;
+                    let field = &buffer[pos..pos + column.fixed_size];
+                    decode_value(field, column.data_type, var_section, int64_as_bigint)
+                        .map_err(|error| error.at(row_idx, col_idx))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(Row::new(row_idx as u64, values))
+        })
+        .collect()
+}
+
+fn decode_value(
+    field: &[u8],
+    data_type: BinaryDataType,
+    var_section: &[u8],
+    int64_as_bigint: bool,
+) -> Result<Value, DecodeError> {
+    let value = match data_type {
+        BinaryDataType::Boolean => Value::Boolean(field[0] != 0),
+        BinaryDataType::Int32 => Value::Int32(i32::from_le_bytes(fixed(field))),
+        BinaryDataType::Int64 if int64_as_bigint => Value::Int64(i64::from_le_bytes(fixed(field))),
+        // Int64 and DateTime are stored as f64 for JS Number compatibility.
+        BinaryDataType::Int64 => Value::Int64(f64::from_le_bytes(fixed(field)) as i64),
+        BinaryDataType::Float64 => Value::Float64(f64::from_le_bytes(fixed(field))),
+        BinaryDataType::DateTime => Value::DateTime(f64::from_le_bytes(fixed(field)) as i64),
+        BinaryDataType::String => {
+            let bytes = read_varlen(field, var_section)?;
+            let text = core::str::from_utf8(bytes)
+                // Positioned by the caller through `DecodeError::at`.
+                .map_err(|_| DecodeError::InvalidUtf8 { row: 0, column: 0 })?;
+            Value::String(String::from(text))
+        }
+        BinaryDataType::Bytes => Value::Bytes(read_varlen(field, var_section)?.to_vec()),
+        BinaryDataType::Jsonb => {
+            Value::Jsonb(JsonbValue::new(read_varlen(field, var_section)?.to_vec()))
+        }
+        BinaryDataType::Decimal => {
+            let bytes = read_varlen(field, var_section)?;
+            let decimal = core::str::from_utf8(bytes)
+                .ok()
+                .and_then(|text| text.parse().ok())
+                .ok_or(DecodeError::InvalidDecimal { row: 0, column: 0 })?;
+            Value::Decimal(decimal)
+        }
+    };
+    Ok(value)
+}
+
+fn fixed<const N: usize>(field: &[u8]) -> [u8; N] {
+    let mut bytes = [0u8; N];
+    bytes.copy_from_slice(&field[..N]);
+    bytes
+}
+
+/// Follows an (offset, length) pair into the variable section.
+fn read_varlen<'a>(field: &[u8], var_section: &'a [u8]) -> Result<&'a [u8], DecodeError> {
+    let offset = u32::from_le_bytes(fixed(field)) as usize;
+    let len = u32::from_le_bytes(fixed(&field[4..])) as usize;
+    offset
+        .checked_add(len)
+        .and_then(|end| var_section.get(offset..end))
+        .ok_or(DecodeError::Truncated)
+}
+
+fn read_u32(buffer: &[u8], pos: usize) -> Result<u32, DecodeError> {
+    buffer
+        .get(pos..pos + 4)
+        .map(|bytes| u32::from_le_bytes(fixed(bytes)))
+        .ok_or(DecodeError::Truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BinaryEncoder;
+    use alloc::rc::Rc;
+    use alloc::string::ToString;
+    use alloc::vec;
+    use cynos_core::schema::{Column, Table};
+    use cynos_core::DataType;
+
+    fn create_test_schema() -> Table {
+        Table::new(
+            "test",
+            vec![
+                Column::new("id", DataType::Int64),
+                Column::new("name", DataType::String).nullable(true),
+                Column::new("active", DataType::Boolean).nullable(true),
+                Column::new("score", DataType::Float64).nullable(true),
+                Column::new("doc", DataType::Jsonb).nullable(true),
+                Column::new("raw", DataType::Bytes).nullable(true),
+                Column::new("at", DataType::DateTime).nullable(true),
+                Column::new("rank", DataType::Int32).nullable(true),
+            ],
+        )
+    }
+
+    fn encode(layout: &SchemaLayout, rows: &[Row], dedup: bool) -> Vec<u8> {
+        encode_with(layout, rows, dedup, false)
+    }
+
+    fn encode_with(layout: &SchemaLayout, rows: &[Row], dedup: bool, checksum: bool) -> Vec<u8> {
+        let rows: Vec<Rc<Row>> = rows.iter().cloned().map(Rc::new).collect();
+        let mut encoder = BinaryEncoder::new(layout.clone(), rows.len())
+            .with_string_dedup(dedup)
+            .with_checksum(checksum);
+        encoder.encode_rows(&rows);
+        encoder.finish()
+    }
+
+    fn sample_row(id: i64, name: &str) -> Row {
+        Row::new(
+            id as u64,
+            vec![
+                Value::Int64(id),
+                Value::String(name.to_string()),
+                Value::Null,
+                Value::Float64(id as f64 * 1.5),
+                Value::Jsonb(JsonbValue::new(br#"{"k":1}"#.to_vec())),
+                Value::Null,
+                Value::Null,
+                Value::Int32(7),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_int64_bigint_mode_preserves_large_ids() {
+        let layout = SchemaLayout::from_schema(&create_test_schema());
+        let large_id = (1i64 << 53) + 1;
+        let rows = [
+            Rc::new(sample_row(large_id, "big")),
+            Rc::new(sample_row(i64::MIN, "min")),
+        ];
+
+        let mut encoder = BinaryEncoder::new(layout.clone(), rows.len()).with_int64_as_bigint(true);
+        encoder.encode_rows(&rows);
+        let buffer = encoder.finish();
+        assert_ne!(read_u32(&buffer, 12).unwrap() & flags::INT64_AS_BIGINT, 0);
+        let decoded = decode_rows(&buffer, &layout).unwrap();
+        assert_eq!(decoded[0].get(0), Some(&Value::Int64(large_id)));
+        assert_eq!(decoded[1].get(0), Some(&Value::Int64(i64::MIN)));
+
+        // The default f64 encoding rounds the same id.
+        let plain = encode(&layout, &[sample_row(large_id, "big")], false);
+        let decoded = decode_rows(&plain, &layout).unwrap();
+        assert_ne!(decoded[0].get(0), Some(&Value::Int64(large_id)));
+    }
+
+    #[test]
+    fn test_checksum_round_trip() {
+        let layout = SchemaLayout::from_schema(&create_test_schema());
+        let rows = [sample_row(1, "alpha"), sample_row(2, "beta")];
+
+        let plain = encode(&layout, &rows, false);
+        let checked = encode_with(&layout, &rows, false, true);
+        assert_eq!(checked.len(), plain.len() + CHECKSUM_SIZE);
+        assert_ne!(read_u32(&checked, 12).unwrap() & flags::HAS_CHECKSUM, 0);
+
+        assert_eq!(verify_checksum(&checked), Ok(()));
+        assert_eq!(verify_checksum(&plain), Ok(()));
+        let decoded = decode_rows(&checked, &layout).unwrap();
+        assert_eq!(decoded[0].values(), rows[0].values());
+        assert_eq!(decoded[1].values(), rows[1].values());
+    }
+
+    #[test]
+    fn test_checksum_detects_corruption() {
+        let layout = SchemaLayout::from_schema(&create_test_schema());
+        let rows = [sample_row(1, "alpha"), sample_row(2, "beta")];
+        let checked = encode_with(&layout, &rows, false, true);
+
+        // Flip one bit in the fixed section, the variable section, and the
+        // checksum itself.
+        for position in [
+            HEADER_SIZE + 3,
+            checked.len() - CHECKSUM_SIZE - 2,
+            checked.len() - 1,
+        ] {
+            let mut corrupted = checked.clone();
+            corrupted[position] ^= 0x01;
+            assert!(matches!(
+                verify_checksum(&corrupted),
+                Err(DecodeError::ChecksumMismatch { .. })
+            ));
+            assert!(matches!(
+                decode_rows(&corrupted, &layout),
+                Err(DecodeError::ChecksumMismatch { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_round_trip_with_nulls_strings_and_jsonb() {
+        let layout = SchemaLayout::from_schema(&create_test_schema());
+        let rows = vec![
+            Row::new(
+                0,
+                vec![
+                    Value::Int64(1),
+                    Value::String("héllo".to_string()),
+                    Value::Boolean(true),
+                    Value::Float64(-2.5),
+                    Value::Jsonb(JsonbValue::new(br#"{"a":[1,null]}"#.to_vec())),
+                    Value::Bytes(vec![0, 7, 255]),
+                    Value::DateTime(1_700_000_000_000),
+                    Value::Int32(-3),
+                ],
+            ),
+            Row::new(
+                1,
+                vec![
+                    Value::Int64(2),
+                    Value::Null,
+                    Value::Null,
+                    Value::Null,
+                    Value::Null,
+                    Value::Null,
+                    Value::Null,
+                    Value::Null,
+                ],
+            ),
+            Row::new(
+                2,
+                vec![
+                    Value::Int64(3),
+                    Value::String("héllo".to_string()),
+                    Value::Boolean(false),
+                    Value::Null,
+                    Value::Jsonb(JsonbValue::new(b"[]".to_vec())),
+                    Value::Bytes(vec![]),
+                    Value::Null,
+                    Value::Int32(0),
+                ],
+            ),
+        ];
+
+        for dedup in [false, true] {
+            let decoded = decode_rows(&encode(&layout, &rows, dedup), &layout).unwrap();
+            assert_eq!(decoded.len(), rows.len());
+            for (decoded, original) in decoded.iter().zip(&rows) {
+                assert_eq!(decoded.values(), original.values());
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip_decimal_keeps_every_digit() {
+        let schema = Table::new(
+            "prices",
+            vec![
+                Column::new("id", DataType::Int64),
+                Column::new("price", DataType::Decimal).nullable(true),
+            ],
+        );
+        let layout = SchemaLayout::from_schema(&schema);
+        let rows: Vec<Row> = ["12345678901234567890.125", "-0.10", "0"]
+            .iter()
+            .enumerate()
+            .map(|(i, text)| {
+                Row::new(
+                    i as u64,
+                    vec![
+                        Value::Int64(i as i64),
+                        Value::Decimal(text.parse().unwrap()),
+                    ],
+                )
+            })
+            .chain([Row::new(3, vec![Value::Int64(3), Value::Null])])
+            .collect();
+
+        let decoded = decode_rows(&encode(&layout, &rows, false), &layout).unwrap();
+        for (decoded, original) in decoded.iter().zip(&rows) {
+            assert_eq!(decoded.values(), original.values());
+        }
+        assert_eq!(
+            decoded[1]
+                .get(1)
+                .and_then(|v| v.as_decimal())
+                .map(|d| d.scale()),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_round_trip_empty_result() {
+        let layout = SchemaLayout::from_schema(&create_test_schema());
+        let decoded = decode_rows(&encode(&layout, &[], false), &layout).unwrap();
+        assert!(decoded.is_empty());
+    }
+
+    #[test]
+    fn test_decode_rejects_mismatched_or_truncated_buffers() {
+        let schema = create_test_schema();
+        let layout = SchemaLayout::from_schema(&schema);
+        let row = Row::new(
+            0,
+            vec![
+                Value::Int64(1),
+                Value::String("abc".to_string()),
+                Value::Null,
+                Value::Null,
+                Value::Null,
+                Value::Null,
+                Value::Null,
+                Value::Null,
+            ],
+        );
+        let buffer = encode(&layout, &[row], false);
+
+        let narrower = SchemaLayout::from_projection(&schema, &["id".to_string()]);
+        assert!(matches!(
+            decode_rows(&buffer, &narrower),
+            Err(DecodeError::StrideMismatch { .. })
+        ));
+        assert_eq!(
+            decode_rows(&buffer[..buffer.len() - 1], &layout),
+            Err(DecodeError::Truncated)
+        );
+        assert_eq!(
+            decode_rows(&buffer[..8], &layout),
+            Err(DecodeError::Truncated)
+        );
+    }
+}
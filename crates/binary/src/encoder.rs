@@ -3,10 +3,43 @@
 //! Encodes rows into a compact binary format that can be read directly
 //! from WASM linear memory using JS DataView.
 
-use super::{flags, BinaryDataType, SchemaLayout, HEADER_SIZE};
+use super::{crc32, flags, BinaryDataType, SchemaLayout, HEADER_SIZE};
+use alloc::boxed::Box;
 use alloc::rc::Rc;
+use alloc::string::ToString;
 use alloc::vec::Vec;
+use core::fmt;
 use cynos_core::{Row, Value};
+use hashbrown::HashMap;
+
+/// Largest section the header's u32 offsets and lengths can address.
+const MAX_SECTION_SIZE: usize = u32::MAX as usize;
+
+/// Error returned when rows do not fit the binary format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EncodeError {
+    /// The header and fixed section outgrew the u32 `var_offset`.
+    FixedSectionOverflow { size: usize, limit: usize },
+    /// The variable section outgrew its u32 offsets.
+    VariableSectionOverflow { size: usize, limit: usize },
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodeError::FixedSectionOverflow { size, limit } => write!(
+                f,
+                "fixed section of {} bytes exceeds the {} byte limit of the binary format",
+                size, limit
+            ),
+            EncodeError::VariableSectionOverflow { size, limit } => write!(
+                f,
+                "variable section of {} bytes exceeds the {} byte limit of the binary format",
+                size, limit
+            ),
+        }
+    }
+}
 
 /// High-performance binary encoder
 pub struct BinaryEncoder {
@@ -19,10 +52,24 @@ pub struct BinaryEncoder {
     row_count: usize,
     /// Whether any NULL values were encountered
     has_nulls: bool,
+    /// Offsets of strings already written to the variable section, when
+    /// string deduplication is enabled
+    string_offsets: Option<HashMap<Box<str>, u32>>,
+    /// Whether to append a CRC-32 of the buffer
+    checksum: bool,
+    /// Whether Int64 values are written as i64 instead of f64
+    int64_as_bigint: bool,
+    /// Size limit of each section
+    max_section_size: usize,
+    /// Variable section size including values dropped for exceeding the limit
+    var_size_requested: usize,
 }
 
 impl BinaryEncoder {
-    /// Create a new encoder with pre-allocated buffers
+    /// Create a new encoder with pre-allocated buffers.
+    ///
+    /// `estimated_rows` only sizes the initial allocations; rows can be added
+    /// one at a time with `push_row` when the count is not known up front.
     pub fn new(layout: SchemaLayout, estimated_rows: usize) -> Self {
         let fixed_size = layout.calculate_fixed_size(estimated_rows);
         // Estimate variable data: ~32 bytes per row average
@@ -34,22 +81,73 @@ impl BinaryEncoder {
             var_buffer: Vec::with_capacity(var_estimate),
             row_count: 0,
             has_nulls: false,
+            string_offsets: None,
+            checksum: false,
+            int64_as_bigint: false,
+            max_section_size: MAX_SECTION_SIZE,
+            var_size_requested: 0,
         }
     }
 
+    /// Enable or disable string deduplication.
+    ///
+    /// When enabled, identical strings share a single entry in the variable
+    /// section and their offset/length pairs point at the same bytes. This
+    /// shrinks the buffer for low-cardinality string columns; decoders are
+    /// unaffected since they only follow offset and length.
+    pub fn with_string_dedup(mut self, enabled: bool) -> Self {
+        self.string_offsets = if enabled { Some(HashMap::new()) } else { None };
+        self
+    }
+
+    /// Enable or disable the trailing checksum.
+    ///
+    /// When enabled, `finish` sets `flags::HAS_CHECKSUM` and appends a CRC-32
+    /// of the whole buffer so a persisted result can be checked with
+    /// `verify_checksum` before it is decoded.
+    pub fn with_checksum(mut self, enabled: bool) -> Self {
+        self.checksum = enabled;
+        self
+    }
+
+    /// Enable or disable exact 64-bit integers.
+    ///
+    /// By default Int64 values are written as f64 so JS can read them as
+    /// numbers, which rounds values beyond 2^53. When enabled, `finish` sets
+    /// `flags::INT64_AS_BIGINT` and Int64 values are written as little-endian
+    /// i64, for readers to decode as `BigInt`.
+    pub fn with_int64_as_bigint(mut self, enabled: bool) -> Self {
+        self.int64_as_bigint = enabled;
+        self
+    }
+
     /// Encode a batch of rows
     pub fn encode_rows(&mut self, rows: &[Rc<Row>]) {
-        // Reserve space for header (will be written at the end)
-        if self.buffer.is_empty() {
-            self.buffer.resize(HEADER_SIZE, 0);
-        }
-
+        self.reserve_header();
         for row in rows {
             self.encode_row(row);
             self.row_count += 1;
         }
     }
 
+    /// Append a single row, growing the buffers as needed.
+    ///
+    /// The header, including the row count, is written by `finish`, so rows
+    /// can be pushed straight from an iterator of unknown length.
+    pub fn push_row(&mut self, row: &Row) {
+        self.reserve_header();
+        self.encode_row(row);
+        self.row_count += 1;
+    }
+
+    /// Reserve space for the header, which is written at the end
+    #[inline(always)]
+    fn reserve_header(&mut self) {
+        if self.buffer.is_empty() {
+            self.buffer.resize(HEADER_SIZE, 0);
+        }
+    }
+
     /// Encode a single row
     #[inline(always)]
     fn encode_row(&mut self, row: &Row) {
@@ -96,6 +194,9 @@ impl BinaryEncoder {
             (Value::Int32(i), BinaryDataType::Int32) => {
                 self.write_bytes_fast(&i.to_le_bytes());
             }
+            (Value::Int64(i), BinaryDataType::Int64) if self.int64_as_bigint => {
+                self.write_bytes_fast(&i.to_le_bytes());
+            }
             (Value::Int64(i), BinaryDataType::Int64) => {
                 // Store as f64 for JS Number compatibility
                 let f = *i as f64;
@@ -110,7 +211,7 @@ impl BinaryEncoder {
                 self.write_bytes_fast(&f.to_le_bytes());
             }
             (Value::String(s), BinaryDataType::String) => {
-                self.write_varlen_fast(s.as_bytes());
+                self.write_string(s);
             }
             (Value::Bytes(b), BinaryDataType::Bytes) => {
                 self.write_varlen_fast(b);
@@ -119,6 +220,9 @@ impl BinaryEncoder {
                 // JsonbValue stores JSON as bytes already
                 self.write_varlen_fast(&j.0);
             }
+            (Value::Decimal(d), BinaryDataType::Decimal) => {
+                self.write_string(&d.to_string());
+            }
             // Type mismatch - write zeros
             _ => {
                 let size = data_type.fixed_size();
@@ -145,6 +249,14 @@ impl BinaryEncoder {
     /// Write variable-length data and store (offset, length) in fixed section
     #[inline(always)]
     fn write_varlen_fast(&mut self, data: &[u8]) {
+        self.var_size_requested += data.len();
+        if self.var_size_requested > self.max_section_size {
+            // Past the limit an offset would wrap; `try_finish` reports the
+            // overflow instead of returning a buffer with bogus offsets.
+            self.write_bytes_fast(&[0; 8]);
+            return;
+        }
+
         let offset = self.var_buffer.len() as u32;
         let length = data.len() as u32;
 
@@ -164,15 +276,65 @@ impl BinaryEncoder {
         }
     }
 
-    /// Finalize encoding and return the complete buffer
-    pub fn finish(mut self) -> Vec<u8> {
+    /// Write a string, reusing an earlier copy when deduplication is enabled
+    #[inline(always)]
+    fn write_string(&mut self, s: &str) {
+        let Some(offsets) = self.string_offsets.as_mut() else {
+            self.write_varlen_fast(s.as_bytes());
+            return;
+        };
+
+        if let Some(&offset) = offsets.get(s) {
+            self.write_bytes_fast(&offset.to_le_bytes());
+            self.write_bytes_fast(&(s.len() as u32).to_le_bytes());
+            return;
+        }
+
+        offsets.insert(Box::from(s), self.var_buffer.len() as u32);
+        self.write_varlen_fast(s.as_bytes());
+    }
+
+    /// Finalize encoding and return the complete buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a section outgrew the format's u32 offsets; use
+    /// `try_finish` to handle that case.
+    pub fn finish(self) -> Vec<u8> {
+        self.try_finish()
+            .unwrap_or_else(|error| panic!("binary encoding failed: {}", error))
+    }
+
+    /// Finalize encoding, failing if a section outgrew the format's u32
+    /// offsets rather than truncating them.
+    pub fn try_finish(mut self) -> Result<Vec<u8>, EncodeError> {
+        self.reserve_header();
+        if self.buffer.len() > self.max_section_size {
+            return Err(EncodeError::FixedSectionOverflow {
+                size: self.buffer.len(),
+                limit: self.max_section_size,
+            });
+        }
+        if self.var_size_requested > self.max_section_size {
+            return Err(EncodeError::VariableSectionOverflow {
+                size: self.var_size_requested,
+                limit: self.max_section_size,
+            });
+        }
+
         // Calculate var_offset (where variable section starts)
         let var_offset = self.buffer.len() as u32;
 
         // Write header
         let row_count = self.row_count as u32;
         let row_stride = self.layout.row_stride() as u32;
-        let flags = if self.has_nulls { flags::HAS_NULLS } else { 0 };
+        let mut flags = if self.has_nulls { flags::HAS_NULLS } else { 0 };
+        if self.checksum {
+            flags |= flags::HAS_CHECKSUM;
+        }
+        if self.int64_as_bigint {
+            flags |= flags::INT64_AS_BIGINT;
+        }
 
         // Header: row_count (4) + row_stride (4) + var_offset (4) + flags (4)
         self.buffer[0..4].copy_from_slice(&row_count.to_le_bytes());
@@ -183,7 +345,12 @@ impl BinaryEncoder {
         // Append variable section
         self.buffer.append(&mut self.var_buffer);
 
-        self.buffer
+        if self.checksum {
+            let crc = crc32(&self.buffer);
+            self.buffer.extend_from_slice(&crc.to_le_bytes());
+        }
+
+        Ok(self.buffer)
     }
 
     /// Get the schema layout
@@ -195,7 +362,7 @@ impl BinaryEncoder {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloc::string::ToString;
+    use alloc::string::{String, ToString};
     use alloc::vec;
     use cynos_core::schema::{Column, Table};
     use cynos_core::DataType;
@@ -222,6 +389,49 @@ mod tests {
         assert_eq!(layout.row_stride(), 25);
     }
 
+    #[test]
+    fn test_join_projection_layout() {
+        let users = create_test_schema();
+        let orders = Table::new(
+            "orders",
+            vec![
+                Column::new("id", DataType::Int64),
+                Column::new("paid", DataType::Boolean),
+                Column::new("note", DataType::String),
+            ],
+        );
+        let specs: Vec<String> = ["o.id", "name", "paid", "test.id", "missing"]
+            .iter()
+            .map(|spec| spec.to_string())
+            .collect();
+        let layout =
+            SchemaLayout::from_join_projection(&[("test", &users), ("o", &orders)], &specs);
+
+        let columns = layout.columns();
+        let summary: Vec<(&str, usize, usize, bool)> = columns
+            .iter()
+            .map(|c| (c.name.as_str(), c.offset, c.fixed_size, c.is_nullable))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("o.id", 0, 8, true),
+                ("name", 8, 8, true),
+                ("paid", 16, 1, true),
+                ("test.id", 17, 8, false),
+            ]
+        );
+        assert_eq!(columns[2].data_type, BinaryDataType::Boolean);
+        assert_eq!(layout.null_mask_size(), 1);
+        assert_eq!(layout.row_stride(), 1 + 25);
+
+        // Nine resolved columns need a second null-mask byte.
+        let wide: Vec<String> = (0..9).map(|_| "value".to_string()).collect();
+        let layout = SchemaLayout::from_join_projection(&[("test", &users), ("o", &orders)], &wide);
+        assert_eq!(layout.null_mask_size(), 2);
+        assert_eq!(layout.row_stride(), 2 + 9 * 8);
+    }
+
     #[test]
     fn test_encode_simple_row() {
         let schema = create_test_schema();
@@ -247,4 +457,167 @@ mod tests {
         let row_stride = u32::from_le_bytes([buffer[4], buffer[5], buffer[6], buffer[7]]);
         assert_eq!(row_stride, 25);
     }
+
+    fn read_u32(buffer: &[u8], pos: usize) -> u32 {
+        u32::from_le_bytes([
+            buffer[pos],
+            buffer[pos + 1],
+            buffer[pos + 2],
+            buffer[pos + 3],
+        ])
+    }
+
+    /// Decode the `name` column of every row by following offset/len.
+    fn decode_names(buffer: &[u8]) -> Vec<&str> {
+        let row_count = read_u32(buffer, 0) as usize;
+        let row_stride = read_u32(buffer, 4) as usize;
+        let var_offset = read_u32(buffer, 8) as usize;
+        // name follows null_mask(1) + id(8)
+        (0..row_count)
+            .map(|i| {
+                let pos = HEADER_SIZE + i * row_stride + 1 + 8;
+                let offset = read_u32(buffer, pos) as usize;
+                let len = read_u32(buffer, pos + 4) as usize;
+                let start = var_offset + offset;
+                core::str::from_utf8(&buffer[start..start + len]).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_string_dedup_shares_variable_section_entries() {
+        let schema = create_test_schema();
+        let statuses = ["active", "inactive", "pending-review"];
+        let rows: Vec<Rc<Row>> = (0..300)
+            .map(|i| {
+                Rc::new(Row::new(
+                    i as u64,
+                    vec![
+                        Value::Int64(i),
+                        Value::String(statuses[i as usize % 3].to_string()),
+                        Value::Float64(i as f64),
+                    ],
+                ))
+            })
+            .collect();
+
+        let mut naive = BinaryEncoder::new(SchemaLayout::from_schema(&schema), rows.len());
+        naive.encode_rows(&rows);
+        let naive = naive.finish();
+
+        let mut dedup = BinaryEncoder::new(SchemaLayout::from_schema(&schema), rows.len())
+            .with_string_dedup(true);
+        dedup.encode_rows(&rows);
+        let dedup = dedup.finish();
+
+        let naive_var_len = naive.len() - read_u32(&naive, 8) as usize;
+        let dedup_var_len = dedup.len() - read_u32(&dedup, 8) as usize;
+        assert_eq!(
+            dedup_var_len,
+            statuses.iter().map(|s| s.len()).sum::<usize>()
+        );
+        assert!(dedup_var_len < naive_var_len);
+
+        // Fixed sections are identical in size; only the variable section shrinks
+        assert_eq!(read_u32(&naive, 8), read_u32(&dedup, 8));
+        assert_eq!(decode_names(&dedup), decode_names(&naive));
+        assert_eq!(decode_names(&dedup)[4], "inactive");
+    }
+
+    #[test]
+    fn test_section_size_guard() {
+        let schema = create_test_schema();
+        let row = |id: i64, name: &str| {
+            Rc::new(Row::new(
+                id as u64,
+                vec![
+                    Value::Int64(id),
+                    Value::String(name.to_string()),
+                    Value::Float64(0.0),
+                ],
+            ))
+        };
+        let encoder = |limit: usize| {
+            let mut encoder = BinaryEncoder::new(SchemaLayout::from_schema(&schema), 2);
+            // Stand-in for the u32 limit, which a test cannot allocate.
+            encoder.max_section_size = limit;
+            encoder
+        };
+
+        // Two rows fill a fixed section of this size; the limit covers both.
+        let limit = HEADER_SIZE + 2 * SchemaLayout::from_schema(&schema).row_stride();
+        let name = |len: usize| "x".repeat(len);
+
+        let mut fits = encoder(limit);
+        fits.encode_rows(&[row(1, &name(limit / 2)), row(2, &name(limit / 2))]);
+        assert!(fits.try_finish().is_ok());
+
+        let mut overflows = encoder(limit);
+        overflows.encode_rows(&[row(1, &name(limit / 2)), row(2, &name(limit / 2 + 1))]);
+        assert_eq!(
+            overflows.try_finish(),
+            Err(EncodeError::VariableSectionOverflow {
+                size: limit + 1,
+                limit
+            })
+        );
+
+        let fixed_size = HEADER_SIZE + SchemaLayout::from_schema(&schema).row_stride();
+        let mut overflows = encoder(fixed_size - 1);
+        overflows.encode_rows(&[row(1, "")]);
+        assert_eq!(
+            overflows.try_finish(),
+            Err(EncodeError::FixedSectionOverflow {
+                size: fixed_size,
+                limit: fixed_size - 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_push_row_matches_encode_rows() {
+        let schema = create_test_schema();
+        let rows: Vec<Rc<Row>> = (0..50)
+            .map(|i| {
+                Rc::new(Row::new(
+                    i as u64,
+                    vec![
+                        Value::Int64(i),
+                        if i % 7 == 0 {
+                            Value::Null
+                        } else {
+                            Value::String(alloc::format!("row-{}", i))
+                        },
+                        Value::Float64(i as f64 / 4.0),
+                    ],
+                ))
+            })
+            .collect();
+
+        let mut batch = BinaryEncoder::new(SchemaLayout::from_schema(&schema), rows.len());
+        batch.encode_rows(&rows);
+        let batch = batch.finish();
+
+        // No row count hint: every row grows the buffers.
+        let mut streaming = BinaryEncoder::new(SchemaLayout::from_schema(&schema), 0);
+        for row in rows.iter().map(|row| row.as_ref()) {
+            streaming.push_row(row);
+        }
+        let streaming = streaming.finish();
+
+        assert_eq!(read_u32(&streaming, 0), 50);
+        assert_eq!(streaming, batch);
+        let layout = SchemaLayout::from_schema(&schema);
+        let decoded = crate::decode_rows(&streaming, &layout).unwrap();
+        assert_eq!(decoded, crate::decode_rows(&batch, &layout).unwrap());
+        for (decoded, row) in decoded.iter().zip(&rows) {
+            assert_eq!(decoded.values(), row.values());
+        }
+
+        // An encoder that never saw a row still produces a valid header.
+        let empty = BinaryEncoder::new(SchemaLayout::from_schema(&schema), 0).finish();
+        assert_eq!(empty.len(), HEADER_SIZE);
+        assert_eq!(read_u32(&empty, 0), 0);
+        assert_eq!(read_u32(&empty, 8), HEADER_SIZE as u32);
+    }
 }
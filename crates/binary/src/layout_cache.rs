@@ -1,38 +1,100 @@
 //! SchemaLayout cache for avoiding repeated layout computation.
 //!
-//! Full-table layouts are cached by table name.
-//! Projection layouts are created fresh each time (or could be cached by column signature).
+//! Full-table layouts are cached by table name and tagged with the schema
+//! epoch they were built at, so a layout built before DDL is never returned
+//! afterwards. Projection layouts are created fresh each time (or could be
+//! cached by column signature).
 
 use super::SchemaLayout;
 use alloc::string::String;
 use cynos_core::schema::Table;
 use hashbrown::HashMap;
 
+/// Default maximum number of cached full-table layouts.
+const DEFAULT_LAYOUT_CACHE_SIZE: usize = 64;
+
+/// Cache entry with the epoch it was built at and access tracking for LRU eviction.
+struct CacheEntry {
+    layout: SchemaLayout,
+    schema_epoch: u64,
+    last_access: u64,
+}
+
 /// Cache for SchemaLayout instances.
 ///
-/// - Full-table queries: cached by table name
+/// - Full-table queries: cached by table name, rebuilt when the schema epoch changes
 /// - Projection queries: created fresh (column combinations are too varied to cache effectively)
-#[derive(Default)]
+///
+/// When the cache is full, the least recently used layout is evicted.
 pub struct SchemaLayoutCache {
     /// Cache for full-table layouts, keyed by table name
-    full_table_layouts: HashMap<String, SchemaLayout>,
+    full_table_layouts: HashMap<String, CacheEntry>,
+    /// Maximum number of entries
+    max_size: usize,
+    /// Global access counter for LRU tracking
+    access_counter: u64,
+}
+
+impl Default for SchemaLayoutCache {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SchemaLayoutCache {
-    /// Create a new empty cache
+    /// Create a new empty cache holding up to `DEFAULT_LAYOUT_CACHE_SIZE` layouts
     pub fn new() -> Self {
+        Self::with_max_size(DEFAULT_LAYOUT_CACHE_SIZE)
+    }
+
+    /// Create a new empty cache holding up to `max_size` layouts
+    pub fn with_max_size(max_size: usize) -> Self {
         Self {
             full_table_layouts: HashMap::new(),
+            max_size: max_size.max(1),
+            access_counter: 0,
         }
     }
 
     /// Get or create a SchemaLayout for a full-table query.
-    /// The layout is cached and reused for subsequent queries on the same table.
+    ///
+    /// The layout is cached and reused for subsequent queries on the same
+    /// table while `schema_epoch` stays the same; a cached layout from another
+    /// epoch is rebuilt from `schema`.
     #[inline]
-    pub fn get_or_create_full(&mut self, table_name: &str, schema: &Table) -> &SchemaLayout {
-        self.full_table_layouts
-            .entry(table_name.into())
-            .or_insert_with(|| SchemaLayout::from_schema(schema))
+    pub fn get_or_create_full(
+        &mut self,
+        table_name: &str,
+        schema_epoch: u64,
+        schema: &Table,
+    ) -> &SchemaLayout {
+        self.access_counter += 1;
+        let fresh = self
+            .full_table_layouts
+            .get(table_name)
+            .is_some_and(|entry| entry.schema_epoch == schema_epoch);
+        if !fresh {
+            if !self.full_table_layouts.contains_key(table_name)
+                && self.full_table_layouts.len() >= self.max_size
+            {
+                self.evict_lru();
+            }
+            self.full_table_layouts.insert(
+                table_name.into(),
+                CacheEntry {
+                    layout: SchemaLayout::from_schema(schema),
+                    schema_epoch,
+                    last_access: 0,
+                },
+            );
+        }
+
+        let entry = self
+            .full_table_layouts
+            .get_mut(table_name)
+            .expect("layout was just cached");
+        entry.last_access = self.access_counter;
+        &entry.layout
     }
 
     /// Create a SchemaLayout for a projection query.
@@ -51,4 +113,84 @@ impl SchemaLayoutCache {
     pub fn clear(&mut self) {
         self.full_table_layouts.clear();
     }
+
+    /// Returns the number of cached layouts
+    pub fn len(&self) -> usize {
+        self.full_table_layouts.len()
+    }
+
+    /// Returns true if no layouts are cached
+    pub fn is_empty(&self) -> bool {
+        self.full_table_layouts.is_empty()
+    }
+
+    fn evict_lru(&mut self) {
+        let lru = self
+            .full_table_layouts
+            .iter()
+            .min_by_key(|(_, entry)| entry.last_access)
+            .map(|(name, _)| name.clone());
+        if let Some(name) = lru {
+            self.full_table_layouts.remove(&name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cynos_core::schema::TableBuilder;
+    use cynos_core::DataType;
+
+    fn users_schema(with_email: bool) -> Table {
+        let mut builder = TableBuilder::new("users")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("name", DataType::String)
+            .unwrap();
+        if with_email {
+            builder = builder.add_column("email", DataType::String).unwrap();
+        }
+        builder.build().unwrap()
+    }
+
+    fn column_names(layout: &SchemaLayout) -> alloc::vec::Vec<&str> {
+        layout.columns().iter().map(|c| c.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_layout_rebuilt_after_schema_change() {
+        let mut cache = SchemaLayoutCache::new();
+        let original = users_schema(false);
+        let altered = users_schema(true);
+
+        let layout = cache.get_or_create_full("users", 1, &original);
+        assert_eq!(column_names(layout), ["id", "name"]);
+
+        // Same epoch: the cached layout is reused.
+        let layout = cache.get_or_create_full("users", 1, &altered);
+        assert_eq!(column_names(layout), ["id", "name"]);
+
+        // The column was added in a later epoch.
+        let layout = cache.get_or_create_full("users", 2, &altered);
+        assert_eq!(column_names(layout), ["id", "name", "email"]);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used_layout() {
+        let mut cache = SchemaLayoutCache::with_max_size(2);
+        let schema = users_schema(false);
+
+        cache.get_or_create_full("a", 0, &schema);
+        cache.get_or_create_full("b", 0, &schema);
+        cache.get_or_create_full("a", 0, &schema);
+        cache.get_or_create_full("c", 0, &schema);
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.full_table_layouts.contains_key("a"));
+        assert!(!cache.full_table_layouts.contains_key("b"));
+        assert!(cache.full_table_layouts.contains_key("c"));
+    }
 }
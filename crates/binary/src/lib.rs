@@ -19,18 +19,22 @@
 //! ...
 //!
 //! Variable Section:
-//! [string bytes][bytes data][jsonb data]
+//! [string bytes][bytes data][jsonb data][decimal text]
 //! ```
 
 #![no_std]
 
 extern crate alloc;
 
+mod checksum;
+mod decoder;
 mod encoder;
 mod layout_cache;
 mod schema_layout;
 
-pub use encoder::BinaryEncoder;
+pub use checksum::crc32;
+pub use decoder::{decode_rows, verify_checksum, DecodeError};
+pub use encoder::{BinaryEncoder, EncodeError};
 pub use layout_cache::SchemaLayoutCache;
 pub use schema_layout::{ColumnLayout, SchemaLayout};
 
@@ -42,8 +46,15 @@ pub const HEADER_SIZE: usize = 16;
 /// Header flags
 pub mod flags {
     pub const HAS_NULLS: u32 = 1 << 0;
+    /// A little-endian CRC-32 of everything before it trails the buffer.
+    pub const HAS_CHECKSUM: u32 = 1 << 1;
+    /// Int64 columns hold little-endian i64 instead of f64.
+    pub const INT64_AS_BIGINT: u32 = 1 << 2;
 }
 
+/// Size of the trailing checksum, when `flags::HAS_CHECKSUM` is set
+pub const CHECKSUM_SIZE: usize = 4;
+
 /// Data type IDs for binary encoding
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -56,6 +67,8 @@ pub enum BinaryDataType {
     DateTime = 5,
     Bytes = 6,
     Jsonb = 7,
+    /// Stored as its decimal text so JS can keep every digit.
+    Decimal = 8,
 }
 
 impl BinaryDataType {
@@ -68,8 +81,9 @@ impl BinaryDataType {
             BinaryDataType::Float64 => 8,
             BinaryDataType::String => 8, // (offset: u32, len: u32)
             BinaryDataType::DateTime => 8,
-            BinaryDataType::Bytes => 8, // (offset: u32, len: u32)
-            BinaryDataType::Jsonb => 8, // (offset: u32, len: u32)
+            BinaryDataType::Bytes => 8,   // (offset: u32, len: u32)
+            BinaryDataType::Jsonb => 8,   // (offset: u32, len: u32)
+            BinaryDataType::Decimal => 8, // (offset: u32, len: u32)
         }
     }
 
@@ -77,7 +91,10 @@ impl BinaryDataType {
     pub fn is_variable_length(self) -> bool {
         matches!(
             self,
-            BinaryDataType::String | BinaryDataType::Bytes | BinaryDataType::Jsonb
+            BinaryDataType::String
+                | BinaryDataType::Bytes
+                | BinaryDataType::Jsonb
+                | BinaryDataType::Decimal
         )
     }
 }
@@ -93,6 +110,7 @@ impl From<cynos_core::DataType> for BinaryDataType {
             cynos_core::DataType::DateTime => BinaryDataType::DateTime,
             cynos_core::DataType::Bytes => BinaryDataType::Bytes,
             cynos_core::DataType::Jsonb => BinaryDataType::Jsonb,
+            cynos_core::DataType::Decimal => BinaryDataType::Decimal,
         }
     }
 }
@@ -179,4 +197,9 @@ impl BinaryResult {
     pub fn new(buffer: Vec<u8>) -> Self {
         Self { buffer }
     }
+
+    /// Get the encoded bytes, e.g. for `decode_rows`
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer
+    }
 }
@@ -6,6 +6,7 @@ use super::BinaryDataType;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use cynos_core::schema::Table;
+use hashbrown::HashMap;
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
@@ -144,6 +145,73 @@ impl SchemaLayout {
         }
     }
 
+    /// Create a SchemaLayout from columns projected out of joined tables.
+    ///
+    /// `schemas` pairs each table's reference name (alias or table name) with
+    /// its schema, in join order. A column spec is either `table.column` or a
+    /// bare `column`, which resolves to the first table that has it; specs that
+    /// resolve to no table are skipped. Qualified specs are named by their
+    /// column part unless that name is projected more than once. As in
+    /// `from_schemas`, columns of every table after the first are nullable.
+    pub fn from_join_projection(schemas: &[(&str, &Table)], column_specs: &[String]) -> Self {
+        let simple_name = |spec: &str| -> String {
+            spec.split_once('.')
+                .map_or(spec, |(_, column)| column)
+                .to_string()
+        };
+        let mut name_counts: HashMap<String, usize> = HashMap::new();
+        for spec in column_specs {
+            *name_counts.entry(simple_name(spec)).or_insert(0) += 1;
+        }
+
+        let resolve = |spec: &str| {
+            let (table, column) = match spec.split_once('.') {
+                Some((table, column)) => (Some(table), column),
+                None => (None, spec),
+            };
+            schemas
+                .iter()
+                .enumerate()
+                .filter(|(_, (name, _))| table.is_none_or(|table| table == *name))
+                .find_map(|(table_idx, (_, schema))| {
+                    schema.get_column(column).map(|col| (table_idx, col))
+                })
+        };
+
+        let columns: Vec<ColumnLayout> = column_specs
+            .iter()
+            .filter_map(|spec| resolve(spec).map(|resolved| (spec, resolved)))
+            .scan(0usize, |offset, (spec, (table_idx, col))| {
+                let data_type = BinaryDataType::from(col.data_type());
+                let fixed_size = data_type.fixed_size();
+                let name = simple_name(spec);
+                let layout = ColumnLayout {
+                    name: if name_counts[&name] > 1 {
+                        spec.clone()
+                    } else {
+                        name
+                    },
+                    data_type,
+                    fixed_size,
+                    is_nullable: table_idx > 0 || col.is_nullable(),
+                    offset: *offset,
+                };
+                *offset += fixed_size;
+                Some(layout)
+            })
+            .collect();
+
+        let null_mask_size = columns.len().div_ceil(8);
+        let data_size: usize = columns.iter().map(|c| c.fixed_size).sum();
+        let row_stride = null_mask_size + data_size;
+
+        Self {
+            columns,
+            row_stride,
+            null_mask_size,
+        }
+    }
+
     /// Get the columns
     pub fn columns(&self) -> &[ColumnLayout] {
         &self.columns
@@ -0,0 +1,443 @@
+//! Fixed-point decimal numbers.
+//!
+//! A `Decimal` is an `i128` mantissa scaled by a power of ten, so `12.50` is
+//! mantissa 1250 at scale 2. Addition, subtraction, multiplication and
+//! remainder are exact; division rounds half away from zero. Operations that
+//! overflow the mantissa return `None`.
+
+use alloc::format;
+use alloc::string::String;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::str::FromStr;
+
+/// Largest supported scale; `i128` holds at most 38 decimal digits.
+pub const MAX_SCALE: u32 = 38;
+
+/// Digits a quotient keeps beyond the larger scale of its operands.
+const DIVISION_EXTRA_SCALE: u32 = 6;
+
+/// A fixed-point decimal number.
+///
+/// Values compare, and hash, by their numeric value regardless of scale:
+/// `1.5` and `1.50` are equal.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Decimal {
+    // The mantissa is split into two words so the type stays 8-byte aligned
+    // and `Value` does not grow.
+    low: u64,
+    high: i64,
+    scale: u32,
+}
+
+/// Error returned when a string is not a decimal number.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseDecimalError;
+
+impl fmt::Display for ParseDecimalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid decimal literal")
+    }
+}
+
+impl Decimal {
+    /// Zero at scale 0.
+    pub const ZERO: Decimal = Decimal::from_parts(0, 0);
+
+    /// Creates `mantissa * 10^-scale`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale` exceeds `MAX_SCALE`.
+    pub fn new(mantissa: i128, scale: u32) -> Self {
+        assert!(
+            scale <= MAX_SCALE,
+            "decimal scale {} exceeds {}",
+            scale,
+            MAX_SCALE
+        );
+        Self::from_parts(mantissa, scale)
+    }
+
+    const fn from_parts(mantissa: i128, scale: u32) -> Self {
+        Self {
+            low: mantissa as u64,
+            high: (mantissa >> 64) as i64,
+            scale,
+        }
+    }
+
+    /// Returns the unscaled mantissa.
+    pub fn mantissa(&self) -> i128 {
+        ((self.high as i128) << 64) | self.low as i128
+    }
+
+    /// Returns the number of digits after the decimal point.
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    /// Returns true if the value is zero.
+    pub fn is_zero(&self) -> bool {
+        self.mantissa() == 0
+    }
+
+    /// Returns the same value with trailing fractional zeros removed.
+    pub fn normalize(self) -> Self {
+        let (mut mantissa, mut scale) = (self.mantissa(), self.scale);
+        while scale > 0 && mantissa % 10 == 0 {
+            mantissa /= 10;
+            scale -= 1;
+        }
+        Self::from_parts(mantissa, scale)
+    }
+
+    /// Returns the value at `scale`, rounding half away from zero when
+    /// digits are dropped, or `None` if the mantissa overflows.
+    pub fn rescale(self, scale: u32) -> Option<Self> {
+        if scale > MAX_SCALE {
+            return None;
+        }
+        let mantissa = match scale.cmp(&self.scale) {
+            Ordering::Equal => self.mantissa(),
+            Ordering::Greater => self.mantissa().checked_mul(pow10(scale - self.scale)?)?,
+            Ordering::Less => round_div(self.mantissa(), pow10(self.scale - scale)?)?,
+        };
+        Some(Self::from_parts(mantissa, scale))
+    }
+
+    /// Returns `self + other` at the larger of the two scales.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let (a, b, scale) = Self::aligned(self, other)?;
+        Some(Self::new(a.checked_add(b)?, scale))
+    }
+
+    /// Returns `self - other` at the larger of the two scales.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        let (a, b, scale) = Self::aligned(self, other)?;
+        Some(Self::new(a.checked_sub(b)?, scale))
+    }
+
+    /// Returns `self * other` at the sum of the two scales, reduced to
+    /// `MAX_SCALE` by rounding if necessary.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        let mantissa = self.mantissa().checked_mul(other.mantissa())?;
+        let scale = self.scale + other.scale;
+        if scale <= MAX_SCALE {
+            return Some(Self::from_parts(mantissa, scale));
+        }
+        let mantissa = round_div(mantissa, pow10(scale - MAX_SCALE)?)?;
+        Some(Self::new(mantissa, MAX_SCALE))
+    }
+
+    /// Returns `self / other`, rounded half away from zero to six digits
+    /// beyond the larger scale of the operands, or `None` when dividing by
+    /// zero or on overflow.
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        if other.is_zero() {
+            return None;
+        }
+        let scale = (self.scale.max(other.scale) + DIVISION_EXTRA_SCALE).min(MAX_SCALE);
+        // self / other = (a / 10^sa) / (b / 10^sb), so the mantissa at
+        // `scale` is a * 10^(scale - sa + sb) / b.
+        let shift = scale + other.scale - self.scale;
+        let numerator = self.mantissa().checked_mul(pow10(shift)?)?;
+        Some(Self::new(round_div(numerator, other.mantissa())?, scale))
+    }
+
+    /// Returns the remainder of `self / other` truncated toward zero, with
+    /// the sign of `self`, or `None` when dividing by zero.
+    pub fn checked_rem(self, other: Self) -> Option<Self> {
+        let (a, b, scale) = Self::aligned(self, other)?;
+        Some(Self::new(a.checked_rem(b)?, scale))
+    }
+
+    /// Returns `-self`.
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Self::new(self.mantissa().checked_neg()?, self.scale))
+    }
+
+    /// Returns the absolute value.
+    pub fn checked_abs(self) -> Option<Self> {
+        Some(Self::new(self.mantissa().checked_abs()?, self.scale))
+    }
+
+    /// Returns the integer part, truncated toward zero, if it fits in an
+    /// `i64`.
+    pub fn to_i64(self) -> Option<i64> {
+        let integer = self.mantissa() / pow10(self.scale)?;
+        i64::try_from(integer).ok()
+    }
+
+    /// Returns the nearest `f64`.
+    pub fn to_f64(self) -> f64 {
+        // Parsing the decimal text rounds correctly, which dividing by a
+        // power of ten does not.
+        format!("{}", self).parse().unwrap_or(f64::NAN)
+    }
+
+    /// Returns the decimal with the shortest representation that converts
+    /// back to `value`, or `None` if `value` is not finite or out of range.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+        // `{:?}` prints the shortest round-tripping digits, and switches to
+        // exponent notation only for very large or small magnitudes.
+        format!("{:?}", value).parse().ok()
+    }
+
+    /// Returns both mantissas at the larger of the two scales.
+    fn aligned(a: Self, b: Self) -> Option<(i128, i128, u32)> {
+        let scale = a.scale.max(b.scale);
+        Some((
+            a.rescale(scale)?.mantissa(),
+            b.rescale(scale)?.mantissa(),
+            scale,
+        ))
+    }
+}
+
+/// Returns `10^exp`, or `None` if it does not fit in an `i128`.
+fn pow10(exp: u32) -> Option<i128> {
+    10i128.checked_pow(exp)
+}
+
+/// Divides, rounding half away from zero. Returns `None` on overflow.
+fn round_div(numerator: i128, denominator: i128) -> Option<i128> {
+    let quotient = numerator.checked_div(denominator)?;
+    let remainder = numerator % denominator;
+    if remainder.unsigned_abs() < denominator.unsigned_abs() - remainder.unsigned_abs() {
+        Some(quotient)
+    } else if (numerator < 0) == (denominator < 0) {
+        Some(quotient + 1)
+    } else {
+        Some(quotient - 1)
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(value: i64) -> Self {
+        Self::new(value as i128, 0)
+    }
+}
+
+impl From<i32> for Decimal {
+    fn from(value: i32) -> Self {
+        Self::new(value as i128, 0)
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let scale = self.scale.max(other.scale);
+        match (self.rescale(scale), other.rescale(scale)) {
+            (Some(a), Some(b)) => a.mantissa().cmp(&b.mantissa()),
+            // Only the side with the smaller scale is scaled up, and it
+            // overflowed, so its magnitude exceeds the other side's.
+            (None, _) => self.mantissa().cmp(&0),
+            (_, None) => 0.cmp(&other.mantissa()),
+        }
+    }
+}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let normalized = self.normalize();
+        normalized.mantissa().hash(state);
+        normalized.scale.hash(state);
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = format!("{}", self.mantissa().unsigned_abs());
+        let sign = if self.mantissa() < 0 { "-" } else { "" };
+        let scale = self.scale as usize;
+        if scale == 0 {
+            return write!(f, "{}{}", sign, digits);
+        }
+        let digits = if digits.len() <= scale {
+            let mut padded = String::from("0");
+            padded.extend(core::iter::repeat_n('0', scale - digits.len()));
+            padded.push_str(&digits);
+            padded
+        } else {
+            digits
+        };
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+        write!(f, "{}{}.{}", sign, integer, fraction)
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = ParseDecimalError;
+
+    /// Parses `[+-]digits[.digits][e[+-]digits]`, keeping every fractional
+    /// digit as scale.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (number, exponent) = match s.find(['e', 'E']) {
+            Some(pos) => (
+                &s[..pos],
+                s[pos + 1..].parse::<i32>().map_err(|_| ParseDecimalError)?,
+            ),
+            None => (s, 0),
+        };
+        let (negative, number) = match number.as_bytes().first() {
+            Some(b'-') => (true, &number[1..]),
+            Some(b'+') => (false, &number[1..]),
+            _ => (false, number),
+        };
+        let (integer, fraction) = number.split_once('.').unwrap_or((number, ""));
+        if integer.is_empty() && fraction.is_empty() {
+            return Err(ParseDecimalError);
+        }
+
+        let mut mantissa: i128 = 0;
+        for byte in integer.bytes().chain(fraction.bytes()) {
+            if !byte.is_ascii_digit() {
+                return Err(ParseDecimalError);
+            }
+            mantissa = mantissa
+                .checked_mul(10)
+                .and_then(|m| m.checked_add((byte - b'0') as i128))
+                .ok_or(ParseDecimalError)?;
+        }
+        if negative {
+            mantissa = -mantissa;
+        }
+
+        let scale = fraction.len() as i64 - exponent as i64;
+        if scale < 0 {
+            let shift = u32::try_from(-scale).map_err(|_| ParseDecimalError)?;
+            let mantissa = mantissa
+                .checked_mul(pow10(shift).ok_or(ParseDecimalError)?)
+                .ok_or(ParseDecimalError)?;
+            return Ok(Self::new(mantissa, 0));
+        }
+        let scale = u32::try_from(scale).map_err(|_| ParseDecimalError)?;
+        if scale > MAX_SCALE {
+            // Too many digits to hold exactly: round to the finest scale.
+            let dropped = pow10(scale - MAX_SCALE).ok_or(ParseDecimalError)?;
+            let mantissa = round_div(mantissa, dropped).ok_or(ParseDecimalError)?;
+            return Ok(Self::new(mantissa, MAX_SCALE));
+        }
+        Ok(Self::new(mantissa, scale))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    fn dec(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        assert_eq!(dec("12.50").mantissa(), 1250);
+        assert_eq!(dec("12.50").scale(), 2);
+        assert_eq!(dec("12.50").to_string(), "12.50");
+        assert_eq!(dec("-0.05").to_string(), "-0.05");
+        assert_eq!(dec(".5").to_string(), "0.5");
+        assert_eq!(dec("+7").to_string(), "7");
+        assert_eq!(dec("1.5e3").to_string(), "1500");
+        assert_eq!(dec("15e-3").to_string(), "0.015");
+        for bad in ["", ".", "-", "1.2.3", "1e", "abc", "1_000"] {
+            assert_eq!(bad.parse::<Decimal>(), Err(ParseDecimalError), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_exact_arithmetic() {
+        // The classic float failure: 0.1 + 0.2 is exactly 0.3.
+        assert_eq!(dec("0.1").checked_add(dec("0.2")), Some(dec("0.3")));
+        assert_eq!(
+            dec("0.1").checked_add(dec("0.2")).unwrap().to_string(),
+            "0.3"
+        );
+        assert_eq!(dec("1.25").checked_sub(dec("2")), Some(dec("-0.75")));
+        assert_eq!(
+            dec("1.25").checked_mul(dec("-0.4")).unwrap().to_string(),
+            "-0.500"
+        );
+        assert_eq!(dec("7.5").checked_rem(dec("2")), Some(dec("1.5")));
+        assert_eq!(dec("-7.5").checked_rem(dec("2")), Some(dec("-1.5")));
+
+        assert_eq!(
+            dec("1").checked_div(dec("3")).unwrap().to_string(),
+            "0.333333"
+        );
+        assert_eq!(
+            dec("2").checked_div(dec("3")).unwrap().to_string(),
+            "0.666667"
+        );
+        assert_eq!(
+            dec("-2").checked_div(dec("3")).unwrap().to_string(),
+            "-0.666667"
+        );
+        assert_eq!(dec("10.00").checked_div(dec("4")), Some(dec("2.5")));
+        assert_eq!(dec("1").checked_div(Decimal::ZERO), None);
+
+        let max = Decimal::new(i128::MAX, 0);
+        assert_eq!(max.checked_add(dec("1")), None);
+        assert_eq!(max.checked_mul(dec("2")), None);
+    }
+
+    #[test]
+    fn test_comparison_ignores_scale() {
+        assert_eq!(dec("1.5"), dec("1.500"));
+        assert!(dec("1.49") < dec("1.5"));
+        assert!(dec("-2") < dec("-1.99"));
+        assert!(Decimal::new(i128::MAX, 0) > dec("0.5"));
+        assert!(Decimal::new(i128::MIN, 0) < dec("-0.5"));
+
+        #[derive(Default)]
+        struct Bytes(alloc::vec::Vec<u8>);
+        impl Hasher for Bytes {
+            fn finish(&self) -> u64 {
+                0
+            }
+            fn write(&mut self, bytes: &[u8]) {
+                self.0.extend_from_slice(bytes);
+            }
+        }
+        let hash = |d: Decimal| {
+            let mut hasher = Bytes::default();
+            d.hash(&mut hasher);
+            hasher.0
+        };
+        assert_eq!(hash(dec("2.10")), hash(dec("2.1")));
+        assert_ne!(hash(dec("2.1")), hash(dec("21")));
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(dec("-12.9").to_i64(), Some(-12));
+        assert_eq!(Decimal::new(i128::MAX, 0).to_i64(), None);
+        assert_eq!(dec("0.1").to_f64(), 0.1);
+        assert_eq!(Decimal::from_f64(0.1), Some(dec("0.1")));
+        assert_eq!(Decimal::from_f64(1e20), Some(dec("100000000000000000000")));
+        assert_eq!(Decimal::from_f64(f64::NAN), None);
+        assert_eq!(dec("1.25").rescale(1), Some(dec("1.3")));
+        assert_eq!(dec("-1.25").rescale(1), Some(dec("-1.3")));
+    }
+}
@@ -17,6 +17,8 @@ pub enum Error {
     NullConstraint { column: String },
     /// Unique constraint violation.
     UniqueConstraint { column: String, value: Value },
+    /// Value outside a column's allowed domain.
+    DomainConstraint { column: String, value: Value },
     /// Row or record not found.
     NotFound { table: String, key: Value },
     /// Invalid schema definition.
@@ -29,6 +31,8 @@ pub enum Error {
     IndexNotFound { table: String, index: String },
     /// Foreign key constraint violation.
     ForeignKeyViolation { constraint: String, message: String },
+    /// CHECK constraint violation.
+    CheckViolation { table: String, constraint: String },
     /// Invalid operation.
     InvalidOperation { message: String },
 }
@@ -49,6 +53,13 @@ impl fmt::Display for Error {
                     column, value
                 )
             }
+            Error::DomainConstraint { column, value } => {
+                write!(
+                    f,
+                    "Domain constraint violation on column {}: {:?}",
+                    column, value
+                )
+            }
             Error::NotFound { table, key } => {
                 write!(f, "Not found in table {}: {:?}", table, key)
             }
@@ -70,6 +81,13 @@ impl fmt::Display for Error {
             } => {
                 write!(f, "Foreign key violation ({}): {}", constraint, message)
             }
+            Error::CheckViolation { table, constraint } => {
+                write!(
+                    f,
+                    "Check constraint {} violated on table {}",
+                    constraint, table
+                )
+            }
             Error::InvalidOperation { message } => {
                 write!(f, "Invalid operation: {}", message)
             }
@@ -98,6 +116,14 @@ impl Error {
         }
     }
 
+    /// Creates a domain constraint error.
+    pub fn domain_constraint(column: impl Into<String>, value: Value) -> Self {
+        Error::DomainConstraint {
+            column: column.into(),
+            value,
+        }
+    }
+
     /// Creates a not found error.
     pub fn not_found(table: impl Into<String>, key: Value) -> Self {
         Error::NotFound {
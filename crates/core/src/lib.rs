@@ -2,7 +2,8 @@
 //!
 //! This crate provides the foundational types for the Cynos in-memory database:
 //!
-//! - `DataType`: Supported data types (Boolean, Int32, Int64, Float64, String, DateTime, Bytes, Jsonb)
+//! - `DataType`: Supported data types (Boolean, Int32, Int64, Float64, String, DateTime, Bytes, Jsonb, Decimal)
+//! - `Decimal`: Fixed-point numbers with exact arithmetic
 //! - `Value`: Runtime values that can be stored in the database
 //! - `Row`: A row of values with a unique identifier
 //! - `schema`: Schema definitions (Column, Table, Index, Constraints)
@@ -40,6 +41,7 @@
 
 extern crate alloc;
 
+pub mod decimal;
 mod error;
 pub mod pattern_match;
 mod row;
@@ -47,6 +49,7 @@ pub mod schema;
 mod types;
 mod value;
 
+pub use decimal::Decimal;
 pub use error::{Error, Result};
 pub use row::{
     next_row_id, reserve_row_ids, set_next_row_id, set_next_row_id_if_greater, Row, RowId,
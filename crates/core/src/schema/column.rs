@@ -2,7 +2,9 @@
 
 use crate::types::DataType;
 use crate::value::Value;
+use alloc::collections::BTreeMap;
 use alloc::string::String;
+use alloc::vec::Vec;
 
 /// A column definition in a table schema.
 #[derive(Clone, Debug)]
@@ -19,6 +21,10 @@ pub struct Column {
     default_value: Option<Value>,
     /// Column index in the table (0-based).
     index: usize,
+    /// Free-form annotations such as a display label, format, or unit.
+    metadata: BTreeMap<String, String>,
+    /// The values a String column may hold, if restricted to a domain.
+    allowed_values: Option<Vec<String>>,
 }
 
 impl Column {
@@ -33,6 +39,8 @@ impl Column {
             unique: false,
             default_value: None,
             index: 0,
+            metadata: BTreeMap::new(),
+            allowed_values: None,
         }
     }
 
@@ -54,6 +62,18 @@ impl Column {
         self
     }
 
+    /// Sets a metadata entry, replacing any previous value for `key`.
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Restricts the column to the given values.
+    pub fn allowed_values(mut self, values: Vec<String>) -> Self {
+        self.allowed_values = Some(values);
+        self
+    }
+
     /// Sets the column index.
     pub(crate) fn with_index(mut self, index: usize) -> Self {
         self.index = index;
@@ -84,6 +104,13 @@ impl Column {
         self.unique
     }
 
+    /// Returns the value an insert stores when it leaves this column out, if
+    /// one was declared.
+    #[inline]
+    pub fn declared_default(&self) -> Option<&Value> {
+        self.default_value.as_ref()
+    }
+
     /// Returns the default value for this column.
     pub fn get_default_value(&self) -> Value {
         self.default_value.clone().unwrap_or_else(|| {
@@ -101,6 +128,32 @@ impl Column {
         self.index
     }
 
+    /// Returns the column metadata, ordered by key.
+    #[inline]
+    pub fn metadata(&self) -> &BTreeMap<String, String> {
+        &self.metadata
+    }
+
+    /// Returns the metadata value for `key`.
+    pub fn get_metadata(&self, key: &str) -> Option<&str> {
+        self.metadata.get(key).map(String::as_str)
+    }
+
+    /// Returns the values this column is restricted to, if it has a domain.
+    pub fn get_allowed_values(&self) -> Option<&[String]> {
+        self.allowed_values.as_deref()
+    }
+
+    /// Returns whether `value` is in this column's domain. NULL and columns
+    /// without a domain always pass; nullability is checked separately.
+    pub fn allows(&self, value: &Value) -> bool {
+        match (&self.allowed_values, value) {
+            (None, _) | (_, Value::Null) => true,
+            (Some(allowed), Value::String(s)) => allowed.iter().any(|a| a == s),
+            (Some(_), _) => false,
+        }
+    }
+
     /// Returns whether this column can be used as an index key.
     #[inline]
     pub fn is_indexable(&self) -> bool {
@@ -139,6 +192,20 @@ mod tests {
         assert_eq!(col.get_default_value(), Value::String("unknown".into()));
     }
 
+    #[test]
+    fn test_column_metadata() {
+        let col = Column::new("price", DataType::Float64)
+            .with_metadata("label", "Price")
+            .with_metadata("unit", "EUR")
+            .with_metadata("unit", "USD");
+
+        assert_eq!(col.get_metadata("label"), Some("Price"));
+        assert_eq!(col.get_metadata("unit"), Some("USD"));
+        assert_eq!(col.get_metadata("format"), None);
+        let keys: alloc::vec::Vec<&str> = col.metadata().keys().map(String::as_str).collect();
+        assert_eq!(keys, ["label", "unit"]);
+    }
+
     #[test]
     fn test_column_default_nullable() {
         let bytes_col = Column::new("data", DataType::Bytes);
@@ -1,8 +1,11 @@
 //! Constraint definitions for Cynos database schema.
 
 use super::index::IndexDef;
+use crate::row::Row;
+use alloc::rc::Rc;
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::fmt;
 
 /// Foreign key action on update/delete.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
@@ -103,6 +106,31 @@ impl ForeignKey {
     }
 }
 
+/// The row predicate of a CHECK constraint.
+///
+/// The schema cannot depend on the query engine, so predicates are kept
+/// behind this trait; `cynos_query::check::ExprCheck` implements it for a
+/// query expression.
+pub trait CheckPredicate: fmt::Debug {
+    /// Returns the name and position of every column the predicate reads.
+    fn columns(&self) -> Vec<(String, usize)>;
+
+    /// Returns whether `row` satisfies the predicate.
+    ///
+    /// As in SQL, only a false result violates the check; a NULL result
+    /// passes.
+    fn check(&self, row: &Row) -> bool;
+}
+
+/// A named CHECK constraint.
+#[derive(Clone, Debug)]
+pub struct CheckConstraint {
+    /// Constraint name, reported when a row violates it.
+    pub name: String,
+    /// The predicate every row must satisfy.
+    pub predicate: Rc<dyn CheckPredicate>,
+}
+
 /// Table constraints container.
 #[derive(Clone, Debug, Default)]
 pub struct Constraints {
@@ -112,6 +140,8 @@ pub struct Constraints {
     not_nullable: Vec<String>,
     /// Foreign key constraints.
     foreign_keys: Vec<ForeignKey>,
+    /// CHECK constraints.
+    checks: Vec<CheckConstraint>,
 }
 
 impl Constraints {
@@ -144,6 +174,12 @@ impl Constraints {
         self
     }
 
+    /// Adds a CHECK constraint.
+    pub fn add_check(mut self, check: CheckConstraint) -> Self {
+        self.checks.push(check);
+        self
+    }
+
     /// Returns the primary key index.
     pub fn get_primary_key(&self) -> Option<&IndexDef> {
         self.primary_key.as_ref()
@@ -158,6 +194,11 @@ impl Constraints {
     pub fn get_foreign_keys(&self) -> &[ForeignKey] {
         &self.foreign_keys
     }
+
+    /// Returns the CHECK constraints.
+    pub fn get_checks(&self) -> &[CheckConstraint] {
+        &self.checks
+    }
 }
 
 #[cfg(test)]
@@ -72,6 +72,8 @@ pub struct IndexDef {
     unique: bool,
     /// Index type.
     index_type: IndexType,
+    /// Boolean column gating membership; rows are indexed only while it is `true`.
+    condition_column: Option<String>,
 }
 
 impl IndexDef {
@@ -87,6 +89,7 @@ impl IndexDef {
             columns,
             unique: false,
             index_type: IndexType::BTree,
+            condition_column: None,
         }
     }
 
@@ -102,6 +105,12 @@ impl IndexDef {
         self
     }
 
+    /// Restricts the index to rows whose Boolean `column` is `true`.
+    pub fn condition_column(mut self, column: impl Into<String>) -> Self {
+        self.condition_column = Some(column.into());
+        self
+    }
+
     /// Returns the index name.
     #[inline]
     pub fn name(&self) -> &str {
@@ -137,6 +146,21 @@ impl IndexDef {
         self.index_type
     }
 
+    /// Returns the Boolean column gating membership, for a conditional index.
+    #[inline]
+    pub fn get_condition_column(&self) -> Option<&str> {
+        self.condition_column.as_deref()
+    }
+
+    /// Returns whether this index only holds rows with its condition set.
+    ///
+    /// The query planner never picks a conditional index, since a query may
+    /// need rows outside it.
+    #[inline]
+    pub fn is_conditional(&self) -> bool {
+        self.condition_column.is_some()
+    }
+
     /// Returns whether this is a single-column index.
     #[inline]
     pub fn is_single_column(&self) -> bool {
@@ -9,6 +9,8 @@ mod index;
 mod table;
 
 pub use column::Column;
-pub use constraint::{ConstraintAction, ConstraintTiming, Constraints, ForeignKey};
+pub use constraint::{
+    CheckConstraint, CheckPredicate, ConstraintAction, ConstraintTiming, Constraints, ForeignKey,
+};
 pub use index::{IndexDef, IndexType, IndexedColumn, Order};
 pub use table::{Table, TableBuilder};
@@ -1,11 +1,13 @@
 //! Table definition for Cynos database schema.
 
 use super::column::Column;
-use super::constraint::{Constraints, ForeignKey};
+use super::constraint::{CheckConstraint, CheckPredicate, Constraints, ForeignKey};
 use super::index::{IndexDef, IndexType, IndexedColumn};
 use crate::error::{Error, Result};
 use crate::types::DataType;
+use crate::value::Value;
 use alloc::format;
+use alloc::rc::Rc;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 
@@ -22,6 +24,8 @@ pub struct Table {
     constraints: Constraints,
     /// Whether to persist indices.
     persistent_index: bool,
+    /// Index of the column that marks soft-deleted rows.
+    soft_delete_column: Option<usize>,
 }
 
 impl Table {
@@ -40,6 +44,7 @@ impl Table {
             indices: Vec::new(),
             constraints: Constraints::new(),
             persistent_index: false,
+            soft_delete_column: None,
         }
     }
 
@@ -92,6 +97,12 @@ impl Table {
     pub fn primary_key(&self) -> Option<&IndexDef> {
         self.constraints.get_primary_key()
     }
+
+    /// Returns the soft-delete column, if the table has one. A row whose
+    /// soft-delete column is not NULL counts as deleted.
+    pub fn soft_delete_column(&self) -> Option<&Column> {
+        self.soft_delete_column.map(|index| &self.columns[index])
+    }
 }
 
 /// Builder for creating table definitions.
@@ -103,7 +114,9 @@ pub struct TableBuilder {
     pk_columns: Vec<IndexedColumn>,
     unique_columns: Vec<String>,
     foreign_keys: Vec<ForeignKey>,
+    checks: Vec<CheckConstraint>,
     persistent_index: bool,
+    soft_delete_column: Option<String>,
 }
 
 impl TableBuilder {
@@ -119,7 +132,9 @@ impl TableBuilder {
             pk_columns: Vec::new(),
             unique_columns: Vec::new(),
             foreign_keys: Vec::new(),
+            checks: Vec::new(),
             persistent_index: false,
+            soft_delete_column: None,
         })
     }
 
@@ -157,6 +172,45 @@ impl TableBuilder {
         Ok(self)
     }
 
+    /// Adds a column whose value is `default` when an insert leaves it out.
+    pub fn add_column_with_default(
+        self,
+        name: impl Into<String>,
+        data_type: DataType,
+        default: Value,
+    ) -> Result<Self> {
+        let name = name.into();
+        self.add_column(name.clone(), data_type)?
+            .add_column_default(&name, default)
+    }
+
+    /// Sets the value an insert stores in `column` when it leaves it out.
+    ///
+    /// The default must be NULL or of the column's type, and a domain-
+    /// restricted column only accepts one of its allowed values.
+    pub fn add_column_default(mut self, column: &str, default: Value) -> Result<Self> {
+        let Some(col) = self.columns.iter_mut().find(|c| c.name() == column) else {
+            return Err(Error::column_not_found(&self.name, column));
+        };
+        if default
+            .data_type()
+            .is_some_and(|data_type| data_type != col.data_type())
+        {
+            return Err(Error::InvalidSchema {
+                message: format!(
+                    "Default for column {} is not a {:?} value",
+                    column,
+                    col.data_type()
+                ),
+            });
+        }
+        if !col.allows(&default) {
+            return Err(Error::domain_constraint(column, default));
+        }
+        *col = col.clone().default_value(default);
+        Ok(self)
+    }
+
     /// Adds a nullable column.
     pub fn add_nullable(mut self, columns: &[&str]) -> Self {
         for name in columns {
@@ -167,6 +221,42 @@ impl TableBuilder {
         self
     }
 
+    /// Attaches a metadata entry to a column.
+    pub fn add_column_metadata(
+        mut self,
+        column: &str,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Result<Self> {
+        let Some(col) = self.columns.iter_mut().find(|c| c.name() == column) else {
+            return Err(Error::column_not_found(&self.name, column));
+        };
+        *col = col.clone().with_metadata(key, value);
+        Ok(self)
+    }
+
+    /// Adds a String column restricted to the `allowed` values.
+    ///
+    /// Writes of any other non-NULL value are rejected with
+    /// `Error::DomainConstraint`.
+    pub fn add_enum_column(self, name: impl Into<String>, allowed: &[&str]) -> Result<Self> {
+        let name = name.into();
+        if allowed.is_empty() {
+            return Err(Error::InvalidSchema {
+                message: format!("Enum column {} needs at least one allowed value", name),
+            });
+        }
+        let mut builder = self.add_column(name, DataType::String)?;
+        let col = builder
+            .columns
+            .last_mut()
+            .expect("add_column pushes the column");
+        *col = col
+            .clone()
+            .allowed_values(allowed.iter().map(|v| String::from(*v)).collect());
+        Ok(builder)
+    }
+
     /// Sets the primary key.
     pub fn add_primary_key(mut self, columns: &[&str], auto_increment: bool) -> Result<Self> {
         let pk_name = format!("pk{}", capitalize(&self.name));
@@ -286,6 +376,42 @@ impl TableBuilder {
         Ok(self)
     }
 
+    /// Adds an index that only holds rows whose Boolean `flag_column` is `true`.
+    ///
+    /// Setting or clearing the flag moves a row into or out of the index. A
+    /// unique conditional index enforces uniqueness among flagged rows only.
+    pub fn add_conditional_index(
+        self,
+        name: impl Into<String>,
+        columns: &[&str],
+        unique: bool,
+        flag_column: &str,
+    ) -> Result<Self> {
+        match self.columns.iter().find(|c| c.name() == flag_column) {
+            None => {
+                return Err(Error::InvalidSchema {
+                    message: format!("Column not found: {}", flag_column),
+                })
+            }
+            Some(c) if c.data_type() != DataType::Boolean => {
+                return Err(Error::InvalidSchema {
+                    message: format!("Index condition column must be Boolean: {}", flag_column),
+                })
+            }
+            _ => {}
+        }
+
+        let mut builder = self.add_index(name, columns, unique)?;
+        let idx = builder.indices.pop().expect("add_index pushes the index");
+        if idx.get_index_type() == IndexType::Gin {
+            return Err(Error::InvalidSchema {
+                message: format!("Conditional GIN indexes are not supported: {}", idx.name()),
+            });
+        }
+        builder.indices.push(idx.condition_column(flag_column));
+        Ok(builder)
+    }
+
     /// Adds a hash index for point-lookups on scalar columns.
     pub fn add_hash_index(
         mut self,
@@ -378,6 +504,41 @@ impl TableBuilder {
         Ok(self)
     }
 
+    /// Adds a CHECK constraint: every inserted or updated row must satisfy
+    /// `predicate`, or the write fails with `Error::CheckViolation`.
+    ///
+    /// The columns the predicate reads are resolved when `build` runs.
+    pub fn add_check(
+        mut self,
+        name: impl Into<String>,
+        predicate: impl CheckPredicate + 'static,
+    ) -> Result<Self> {
+        let name = name.into();
+        Self::check_naming_rules(&name)?;
+        if self.checks.iter().any(|check| check.name == name) {
+            return Err(Error::InvalidSchema {
+                message: format!("Check constraint already exists: {}", name),
+            });
+        }
+        self.checks.push(CheckConstraint {
+            name,
+            predicate: Rc::new(predicate),
+        });
+        Ok(self)
+    }
+
+    /// Marks `column` as the soft-delete flag: deletes set it instead of
+    /// removing the row, and reads skip rows where it is not NULL.
+    ///
+    /// The column must be a nullable DateTime column by the time `build` runs.
+    pub fn soft_delete(mut self, column: &str) -> Result<Self> {
+        if !self.columns.iter().any(|c| c.name() == column) {
+            return Err(Error::column_not_found(&self.name, column));
+        }
+        self.soft_delete_column = Some(column.to_string());
+        Ok(self)
+    }
+
     /// Sets whether to persist indices.
     pub fn persistent_index(mut self, value: bool) -> Self {
         self.persistent_index = value;
@@ -386,6 +547,51 @@ impl TableBuilder {
 
     /// Builds the table definition.
     pub fn build(self) -> Result<Table> {
+        let soft_delete_column = match &self.soft_delete_column {
+            Some(name) => {
+                let index = self
+                    .columns
+                    .iter()
+                    .position(|c| c.name() == name)
+                    .ok_or_else(|| Error::column_not_found(&self.name, name))?;
+                let column = &self.columns[index];
+                if column.data_type() != DataType::DateTime || !column.is_nullable() {
+                    return Err(Error::InvalidSchema {
+                        message: format!(
+                            "Soft-delete column {} must be a nullable DateTime column",
+                            name
+                        ),
+                    });
+                }
+                Some(index)
+            }
+            None => None,
+        };
+
+        for check in &self.checks {
+            for (column, position) in check.predicate.columns() {
+                match self.columns.iter().position(|c| c.name() == column) {
+                    Some(index) if index == position => {}
+                    Some(index) => {
+                        return Err(Error::InvalidSchema {
+                            message: format!(
+                            "Check constraint {} reads column {} at position {}, but it is at {}",
+                            check.name, column, position, index
+                        ),
+                        })
+                    }
+                    None => {
+                        return Err(Error::InvalidSchema {
+                            message: format!(
+                                "Check constraint {} references unknown column: {}",
+                                check.name, column
+                            ),
+                        })
+                    }
+                }
+            }
+        }
+
         // Build constraints
         let mut constraints = Constraints::new();
 
@@ -414,6 +620,10 @@ impl TableBuilder {
             constraints = constraints.add_foreign_key(fk);
         }
 
+        for check in self.checks {
+            constraints = constraints.add_check(check);
+        }
+
         // Build columns with indices
         let columns: Vec<Column> = self
             .columns
@@ -431,6 +641,7 @@ impl TableBuilder {
             indices: all_indices,
             constraints,
             persistent_index: self.persistent_index,
+            soft_delete_column,
         })
     }
 }
@@ -469,6 +680,43 @@ mod tests {
         assert!(table.primary_key().is_some());
     }
 
+    #[test]
+    fn test_table_soft_delete_column() {
+        let builder = || {
+            TableBuilder::new("posts")
+                .unwrap()
+                .add_column("id", DataType::Int64)
+                .unwrap()
+                .add_column("deleted_at", DataType::DateTime)
+                .unwrap()
+                .add_column("title", DataType::String)
+                .unwrap()
+        };
+
+        let table = builder()
+            .add_nullable(&["deleted_at"])
+            .soft_delete("deleted_at")
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(table.soft_delete_column().map(|c| c.index()), Some(1));
+
+        assert!(builder().build().unwrap().soft_delete_column().is_none());
+        assert!(builder().soft_delete("missing").is_err());
+        // The flag has to be able to hold NULL, and a timestamp.
+        assert!(builder()
+            .soft_delete("deleted_at")
+            .unwrap()
+            .build()
+            .is_err());
+        assert!(builder()
+            .add_nullable(&["title"])
+            .soft_delete("title")
+            .unwrap()
+            .build()
+            .is_err());
+    }
+
     #[test]
     fn test_table_get_column() {
         let table = TableBuilder::new("test")
@@ -522,6 +770,164 @@ mod tests {
         assert_eq!(index.get_index_type(), IndexType::Hash);
         assert!(index.is_unique());
     }
+
+    #[test]
+    fn test_add_conditional_index() {
+        let builder = || {
+            TableBuilder::new("users")
+                .unwrap()
+                .add_column("email", DataType::String)
+                .unwrap()
+                .add_column("active", DataType::Boolean)
+                .unwrap()
+        };
+
+        let table = builder()
+            .add_conditional_index("idx_active_email", &["email"], true, "active")
+            .unwrap()
+            .build()
+            .unwrap();
+        let index = table.get_index("idx_active_email").unwrap();
+        assert_eq!(index.get_condition_column(), Some("active"));
+        assert!(index.is_conditional());
+        assert!(index.is_unique());
+
+        assert!(builder()
+            .add_conditional_index("idx_email", &["email"], false, "email")
+            .is_err());
+        assert!(builder()
+            .add_conditional_index("idx_email", &["email"], false, "missing")
+            .is_err());
+    }
+
+    #[test]
+    fn test_add_enum_column() {
+        let table = TableBuilder::new("users")
+            .unwrap()
+            .add_enum_column("status", &["active", "inactive"])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let status = table.get_column("status").unwrap();
+        assert_eq!(status.data_type(), DataType::String);
+        assert_eq!(
+            status.get_allowed_values(),
+            Some(&["active".to_string(), "inactive".to_string()][..])
+        );
+        assert!(status.allows(&crate::Value::String("active".into())));
+        assert!(!status.allows(&crate::Value::String("deleted".into())));
+        assert!(status.allows(&crate::Value::Null));
+
+        assert!(TableBuilder::new("users")
+            .unwrap()
+            .add_enum_column("status", &[])
+            .is_err());
+    }
+
+    #[test]
+    fn test_add_column_metadata() {
+        let table = TableBuilder::new("products")
+            .unwrap()
+            .add_column("price", DataType::Float64)
+            .unwrap()
+            .add_column_metadata("price", "unit", "USD")
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let price = table.get_column("price").unwrap();
+        assert_eq!(price.get_metadata("unit"), Some("USD"));
+        assert_eq!(price.index(), 0);
+
+        let missing = TableBuilder::new("products")
+            .unwrap()
+            .add_column_metadata("price", "unit", "USD");
+        assert!(missing.is_err());
+    }
+
+    /// Checks that an Int64 column holds a non-negative value.
+    #[derive(Debug)]
+    struct NonNegative(&'static str, usize);
+
+    impl CheckPredicate for NonNegative {
+        fn columns(&self) -> Vec<(String, usize)> {
+            alloc::vec![(self.0.into(), self.1)]
+        }
+
+        fn check(&self, row: &crate::Row) -> bool {
+            !matches!(row.get(self.1), Some(Value::Int64(n)) if *n < 0)
+        }
+    }
+
+    #[test]
+    fn test_add_check() {
+        let builder = || {
+            TableBuilder::new("users")
+                .unwrap()
+                .add_column("id", DataType::Int64)
+                .unwrap()
+                .add_column("age", DataType::Int64)
+                .unwrap()
+        };
+
+        let table = builder()
+            .add_check("age_non_negative", NonNegative("age", 1))
+            .unwrap()
+            .build()
+            .unwrap();
+        let checks = table.constraints().get_checks();
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].name, "age_non_negative");
+        let row = |age| crate::Row::new(1, alloc::vec![Value::Int64(1), Value::Int64(age)]);
+        assert!(checks[0].predicate.check(&row(30)));
+        assert!(!checks[0].predicate.check(&row(-1)));
+
+        // Unknown columns and stale positions fail when the table is built.
+        let unknown = builder().add_check("c", NonNegative("height", 1)).unwrap();
+        assert!(unknown.build().is_err());
+        let misplaced = builder().add_check("c", NonNegative("age", 0)).unwrap();
+        assert!(misplaced.build().is_err());
+        // Names are unique per table.
+        assert!(builder()
+            .add_check("c", NonNegative("age", 1))
+            .unwrap()
+            .add_check("c", NonNegative("age", 1))
+            .is_err());
+    }
+
+    #[test]
+    fn test_add_column_with_default() {
+        let table = TableBuilder::new("users")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column_with_default("status", DataType::String, Value::String("active".into()))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(table.get_column("id").unwrap().declared_default(), None);
+        let status = table.get_column("status").unwrap();
+        assert_eq!(
+            status.declared_default(),
+            Some(&Value::String("active".into()))
+        );
+        assert_eq!(status.index(), 1);
+
+        let builder = || TableBuilder::new("users").unwrap();
+        assert!(builder()
+            .add_column_with_default("status", DataType::String, Value::Int64(1))
+            .is_err());
+        assert!(builder()
+            .add_enum_column("status", &["active", "inactive"])
+            .unwrap()
+            .add_column_default("status", Value::String("deleted".into()))
+            .is_err());
+        assert!(builder()
+            .add_column_default("missing", Value::Null)
+            .is_err());
+    }
 }
 
 #[test]
@@ -6,6 +6,7 @@
 ///
 /// Maps to the original Lovefield `lf.Type` enum with additions for JSONB support.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DataType {
     /// Boolean type (true/false)
     Boolean,
@@ -23,6 +24,8 @@ pub enum DataType {
     Bytes,
     /// JSONB type for structured data
     Jsonb,
+    /// Fixed-point decimal number
+    Decimal,
 }
 
 impl DataType {
@@ -37,6 +40,7 @@ impl DataType {
             DataType::DateTime => "0",
             DataType::Bytes => "[]",
             DataType::Jsonb => "null",
+            DataType::Decimal => "0",
         }
     }
 
@@ -49,12 +53,42 @@ impl DataType {
     pub fn is_indexable(&self) -> bool {
         !matches!(self, DataType::Bytes | DataType::Jsonb)
     }
+
+    /// Returns whether some non-null value of this type converts to `target`
+    /// under `Value::coerce_to`.
+    pub fn can_coerce_to(&self, target: DataType) -> bool {
+        use DataType::*;
+        if *self == target {
+            return true;
+        }
+        match target {
+            Boolean => matches!(self, Int32 | Int64 | String),
+            Int32 | Int64 => matches!(
+                self,
+                Boolean | Int32 | Int64 | Float64 | String | DateTime | Decimal
+            ),
+            Float64 | Decimal => matches!(self, Int32 | Int64 | Float64 | String | Decimal),
+            String => matches!(self, Boolean | Int32 | Int64 | Float64 | Decimal),
+            DateTime => matches!(self, Int32 | Int64),
+            Bytes | Jsonb => false,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_can_coerce_to_follows_value_coercion() {
+        assert!(DataType::Int32.can_coerce_to(DataType::Int32));
+        assert!(DataType::Int32.can_coerce_to(DataType::DateTime));
+        assert!(DataType::String.can_coerce_to(DataType::Decimal));
+        assert!(!DataType::DateTime.can_coerce_to(DataType::String));
+        assert!(!DataType::Bytes.can_coerce_to(DataType::Int64));
+        assert!(!DataType::Int64.can_coerce_to(DataType::Jsonb));
+    }
+
     #[test]
     fn test_data_type_equality() {
         assert_eq!(DataType::Int32, DataType::Int32);
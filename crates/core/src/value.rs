@@ -3,6 +3,7 @@
 //! This module defines the `Value` enum which represents any value that can be stored
 //! in a database cell.
 
+use crate::decimal::Decimal;
 use crate::types::DataType;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
@@ -11,6 +12,7 @@ use core::hash::{Hash, Hasher};
 
 /// Placeholder for JSONB values. Will be implemented by the jsonb crate.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct JsonbValue(pub Vec<u8>);
 
 impl Hash for JsonbValue {
@@ -27,6 +29,7 @@ impl JsonbValue {
 
 /// A value that can be stored in a database cell.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Value {
     /// Null value
     Null,
@@ -46,6 +49,8 @@ pub enum Value {
     Bytes(Vec<u8>),
     /// JSONB structured data
     Jsonb(JsonbValue),
+    /// Fixed-point decimal number
+    Decimal(Decimal),
 }
 
 impl Value {
@@ -61,6 +66,7 @@ impl Value {
             Value::DateTime(_) => Some(DataType::DateTime),
             Value::Bytes(_) => Some(DataType::Bytes),
             Value::Jsonb(_) => Some(DataType::Jsonb),
+            Value::Decimal(_) => Some(DataType::Decimal),
         }
     }
 
@@ -102,6 +108,14 @@ impl Value {
         }
     }
 
+    /// Returns the decimal if this is a Decimal, None otherwise.
+    pub fn as_decimal(&self) -> Option<Decimal> {
+        match self {
+            Value::Decimal(v) => Some(*v),
+            _ => None,
+        }
+    }
+
     /// Returns a reference to the string if this is a String, None otherwise.
     pub fn as_str(&self) -> Option<&str> {
         match self {
@@ -137,6 +151,7 @@ impl Value {
             DataType::DateTime => Value::DateTime(0),
             DataType::Bytes => Value::Null,
             DataType::Jsonb => Value::Null,
+            DataType::Decimal => Value::Decimal(Decimal::ZERO),
         }
     }
 
@@ -145,12 +160,17 @@ impl Value {
     /// This is intentionally more permissive than `PartialEq` for integer widths so
     /// `Int32(42)` and `Int64(42)` can match in predicates and join keys while the
     /// raw `Value` equality/hash semantics still preserve type identity for storage
-    /// keys, grouping, and index internals.
+    /// keys, grouping, and index internals. Decimals likewise match integers of
+    /// the same value.
     #[inline]
     pub fn sql_eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Value::Int32(a), Value::Int64(b)) => (*a as i64) == *b,
             (Value::Int64(a), Value::Int32(b)) => *a == (*b as i64),
+            (Value::Decimal(_), Value::Int32(_) | Value::Int64(_))
+            | (Value::Int32(_) | Value::Int64(_), Value::Decimal(_)) => {
+                self.cmp(other) == Ordering::Equal
+            }
             _ => self == other,
         }
     }
@@ -171,9 +191,102 @@ impl Value {
                 u8::MAX.hash(state);
                 value.hash(state);
             }
+            Value::Decimal(value) => match value.normalize() {
+                integral if integral.scale() == 0 && i64::try_from(integral.mantissa()).is_ok() => {
+                    u8::MAX.hash(state);
+                    (integral.mantissa() as i64).hash(state);
+                }
+                _ => self.hash(state),
+            },
             _ => self.hash(state),
         }
     }
+
+    /// Converts this value to `target`, as an explicit `CAST` does.
+    ///
+    /// NULL casts to NULL. Numbers convert between widths, with Float64 to
+    /// integer truncating toward zero; integers convert to and from Boolean
+    /// (zero is false) and DateTime (milliseconds). Strings parse after
+    /// trimming whitespace, and Boolean and numbers render as strings.
+    /// Decimals convert exactly from integers and strings and to strings;
+    /// conversions to integers truncate toward zero and between Decimal and
+    /// Float64 round to the nearest value.
+    /// Returns `None` when there is no such value: out-of-range or
+    /// non-finite numbers, unparsable strings, and unsupported type pairs.
+    pub fn coerce_to(&self, target: DataType) -> Option<Value> {
+        if self.is_null() || self.data_type() == Some(target) {
+            return Some(self.clone());
+        }
+        match target {
+            DataType::Boolean => match self {
+                Value::Int32(v) => Some(Value::Boolean(*v != 0)),
+                Value::Int64(v) => Some(Value::Boolean(*v != 0)),
+                Value::String(s) => {
+                    let s = s.trim();
+                    if s.eq_ignore_ascii_case("true") {
+                        Some(Value::Boolean(true))
+                    } else if s.eq_ignore_ascii_case("false") {
+                        Some(Value::Boolean(false))
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            },
+            DataType::Int32 => self
+                .coerce_to(DataType::Int64)
+                .and_then(|v| i32::try_from(v.as_i64()?).ok())
+                .map(Value::Int32),
+            DataType::Int64 => match self {
+                Value::Boolean(v) => Some(Value::Int64(*v as i64)),
+                Value::Int32(v) => Some(Value::Int64(*v as i64)),
+                Value::DateTime(v) => Some(Value::Int64(*v)),
+                Value::Decimal(v) => v.to_i64().map(Value::Int64),
+                // `as` truncates toward zero but saturates, so keep only
+                // values inside [-2^63, 2^63); NaN fails both comparisons.
+                Value::Float64(v) => (*v >= i64::MIN as f64 && *v < -(i64::MIN as f64))
+                    .then_some(Value::Int64(*v as i64)),
+                Value::String(s) => match s.trim().parse::<i64>() {
+                    Ok(v) => Some(Value::Int64(v)),
+                    Err(_) => Value::Float64(s.trim().parse().ok()?).coerce_to(DataType::Int64),
+                },
+                _ => None,
+            },
+            DataType::Float64 => match self {
+                Value::Int32(v) => Some(Value::Float64(*v as f64)),
+                Value::Int64(v) => Some(Value::Float64(*v as f64)),
+                Value::Decimal(v) => Some(Value::Float64(v.to_f64())),
+                Value::String(s) => s
+                    .trim()
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|v| v.is_finite())
+                    .map(Value::Float64),
+                _ => None,
+            },
+            DataType::String => match self {
+                Value::Boolean(v) => Some(Value::String(v.to_string())),
+                Value::Int32(v) => Some(Value::String(v.to_string())),
+                Value::Int64(v) => Some(Value::String(v.to_string())),
+                Value::Float64(v) if v.is_finite() => Some(Value::String(v.to_string())),
+                Value::Decimal(v) => Some(Value::String(v.to_string())),
+                _ => None,
+            },
+            DataType::DateTime => match self {
+                Value::Int32(v) => Some(Value::DateTime(*v as i64)),
+                Value::Int64(v) => Some(Value::DateTime(*v)),
+                _ => None,
+            },
+            DataType::Decimal => match self {
+                Value::Int32(v) => Some(Value::Decimal((*v).into())),
+                Value::Int64(v) => Some(Value::Decimal((*v).into())),
+                Value::Float64(v) => Decimal::from_f64(*v).map(Value::Decimal),
+                Value::String(s) => s.trim().parse().ok().map(Value::Decimal),
+                _ => None,
+            },
+            DataType::Bytes | DataType::Jsonb => None,
+        }
+    }
 }
 
 impl PartialEq for Value {
@@ -195,6 +308,7 @@ impl PartialEq for Value {
             (Value::DateTime(a), Value::DateTime(b)) => a == b,
             (Value::Bytes(a), Value::Bytes(b)) => a == b,
             (Value::Jsonb(a), Value::Jsonb(b)) => a == b,
+            (Value::Decimal(a), Value::Decimal(b)) => a == b,
             _ => false,
         }
     }
@@ -221,6 +335,7 @@ impl Hash for Value {
             Value::DateTime(d) => d.hash(state),
             Value::Bytes(b) => b.hash(state),
             Value::Jsonb(j) => j.hash(state),
+            Value::Decimal(d) => d.hash(state),
         }
     }
 }
@@ -282,6 +397,21 @@ impl Ord for Value {
             (Value::DateTime(a), Value::DateTime(b)) => a.cmp(b),
             (Value::Bytes(a), Value::Bytes(b)) => a.cmp(b),
             (Value::Jsonb(a), Value::Jsonb(b)) => a.0.cmp(&b.0),
+            (Value::Decimal(a), Value::Decimal(b)) => a.cmp(b),
+            // Decimals compare exactly with integers and approximately
+            // with floats, which sort NaN last.
+            (Value::Decimal(a), Value::Int32(b)) => a.cmp(&Decimal::from(*b)),
+            (Value::Decimal(a), Value::Int64(b)) => a.cmp(&Decimal::from(*b)),
+            (Value::Int32(_) | Value::Int64(_) | Value::Float64(_), Value::Decimal(_)) => {
+                other.cmp(self).reverse()
+            }
+            (Value::Decimal(a), Value::Float64(b)) => {
+                if b.is_nan() {
+                    Ordering::Less
+                } else {
+                    a.to_f64().partial_cmp(b).unwrap_or(Ordering::Equal)
+                }
+            }
             // Different types: order by type discriminant
             _ => self.type_order().cmp(&other.type_order()),
         }
@@ -301,6 +431,7 @@ impl Value {
             Value::DateTime(_) => 6,
             Value::Bytes(_) => 7,
             Value::Jsonb(_) => 8,
+            Value::Decimal(_) => 9,
         }
     }
 }
@@ -329,6 +460,12 @@ impl From<f64> for Value {
     }
 }
 
+impl From<Decimal> for Value {
+    fn from(v: Decimal) -> Self {
+        Value::Decimal(v)
+    }
+}
+
 impl From<String> for Value {
     fn from(v: String) -> Self {
         Value::String(v)
@@ -469,4 +606,55 @@ mod tests {
             Value::String(String::new())
         );
     }
+
+    #[test]
+    fn test_coerce_to() {
+        let cast = |value: Value, target| value.coerce_to(target);
+
+        assert_eq!(
+            cast(Value::String(" 42 ".into()), DataType::Int64),
+            Some(Value::Int64(42))
+        );
+        assert_eq!(
+            cast(Value::String("2.9".into()), DataType::Int32),
+            Some(Value::Int32(2))
+        );
+        assert_eq!(cast(Value::String("abc".into()), DataType::Int64), None);
+
+        // Float64 to integer truncates toward zero.
+        assert_eq!(
+            cast(Value::Float64(2.9), DataType::Int64),
+            Some(Value::Int64(2))
+        );
+        assert_eq!(
+            cast(Value::Float64(-2.9), DataType::Int32),
+            Some(Value::Int32(-2))
+        );
+        assert_eq!(cast(Value::Float64(f64::NAN), DataType::Int64), None);
+        assert_eq!(cast(Value::Float64(1e19), DataType::Int64), None);
+        assert_eq!(cast(Value::Int64(1 << 40), DataType::Int32), None);
+
+        assert_eq!(
+            cast(Value::Int32(7), DataType::String),
+            Some(Value::String("7".into()))
+        );
+        assert_eq!(
+            cast(Value::Float64(1.5), DataType::String),
+            Some(Value::String("1.5".into()))
+        );
+        assert_eq!(
+            cast(Value::Int64(0), DataType::Boolean),
+            Some(Value::Boolean(false))
+        );
+        assert_eq!(
+            cast(Value::String("TRUE".into()), DataType::Boolean),
+            Some(Value::Boolean(true))
+        );
+        assert_eq!(
+            cast(Value::Boolean(true), DataType::Int32),
+            Some(Value::Int32(1))
+        );
+        assert_eq!(cast(Value::Null, DataType::Int64), Some(Value::Null));
+        assert_eq!(cast(Value::Bytes(vec![1]), DataType::Int64), None);
+    }
 }
@@ -0,0 +1,142 @@
+//! Batch execution of several query builders in one call.
+//!
+//! `Database::batch` accepts an array of `SelectBuilder`, `InsertBuilder`,
+//! `UpdateBuilder` and `DeleteBuilder` instances, runs them in order and
+//! returns one result per statement. With `{ transaction: true }` the data
+//! modifications run inside a single transaction that is rolled back if any
+//! statement fails.
+
+use crate::error::CynosError;
+use crate::query_builder::{DeleteBuilder, InsertBuilder, SelectBuilder, UpdateBuilder};
+use crate::transaction::JsTransaction;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::vec::Vec;
+use wasm_bindgen::convert::TryFromJsValue;
+use wasm_bindgen::prelude::*;
+
+/// A single statement of a batch.
+pub(crate) enum BatchStatement {
+    Select(Box<SelectBuilder>),
+    Insert(InsertBuilder),
+    Update(UpdateBuilder),
+    Delete(DeleteBuilder),
+}
+
+impl BatchStatement {
+    /// Takes ownership of a builder passed from JS.
+    ///
+    /// The JS object is consumed; it cannot be executed again afterwards.
+    fn from_js(position: u32, value: &JsValue) -> Result<Self, JsValue> {
+        if let Some(builder) = SelectBuilder::try_from_js_value_ref(value) {
+            return Ok(BatchStatement::Select(Box::new(builder)));
+        }
+        if let Some(builder) = InsertBuilder::try_from_js_value_ref(value) {
+            return Ok(BatchStatement::Insert(builder));
+        }
+        if let Some(builder) = UpdateBuilder::try_from_js_value_ref(value) {
+            return Ok(BatchStatement::Update(builder));
+        }
+        if let Some(builder) = DeleteBuilder::try_from_js_value_ref(value) {
+            return Ok(BatchStatement::Delete(builder));
+        }
+
+        Err(CynosError::invalid_argument(format!(
+            "Batch statement {} is not a select, insert, update, or delete builder",
+            position
+        ))
+        .into())
+    }
+
+    /// Executes the statement directly against the database.
+    async fn exec(&self) -> Result<JsValue, JsValue> {
+        match self {
+            BatchStatement::Select(builder) => builder.exec().await,
+            BatchStatement::Insert(builder) => builder.exec().await,
+            BatchStatement::Update(builder) => builder.exec().await,
+            BatchStatement::Delete(builder) => builder.exec().await,
+        }
+    }
+
+    /// Executes the statement inside `tx`.
+    ///
+    /// Selects read the current table state, which includes the writes made
+    /// earlier in the same transaction.
+    async fn exec_in(&self, tx: &mut JsTransaction) -> Result<JsValue, JsValue> {
+        match self {
+            BatchStatement::Select(builder) => builder.exec().await,
+            BatchStatement::Insert(builder) => {
+                let values = builder
+                    .values_data()
+                    .ok_or_else(|| CynosError::invalid_argument("No values specified"))?;
+                tx.insert_rows(builder.table_name(), values, builder.is_strict())?;
+                Ok(JsValue::from_f64(
+                    js_sys::Array::from(values).length() as f64
+                ))
+            }
+            BatchStatement::Update(builder) => {
+                let count = tx.update_columns(
+                    builder.table_name(),
+                    builder.set_values(),
+                    builder.reset_columns(),
+                    builder.where_clause(),
+                )?;
+                Ok(JsValue::from_f64(count as f64))
+            }
+            BatchStatement::Delete(builder) => {
+                let count = tx.delete(builder.table_name(), builder.where_clause().cloned())?;
+                Ok(JsValue::from_f64(count as f64))
+            }
+        }
+    }
+}
+
+/// Parses the batch statements, failing before anything runs if one of them
+/// is not a supported builder.
+pub(crate) fn parse_statements(statements: &js_sys::Array) -> Result<Vec<BatchStatement>, JsValue> {
+    statements
+        .iter()
+        .enumerate()
+        .map(|(position, value)| BatchStatement::from_js(position as u32, &value))
+        .collect()
+}
+
+/// Reads the `transaction` flag from the batch options.
+pub(crate) fn wants_transaction(options: Option<&JsValue>) -> bool {
+    options
+        .and_then(|options| js_sys::Reflect::get(options, &JsValue::from_str("transaction")).ok())
+        .and_then(|flag| flag.as_bool())
+        .unwrap_or(false)
+}
+
+/// Runs the statements in order, stopping at the first failure.
+///
+/// Without a transaction, statements that ran before the failure stay applied.
+pub(crate) async fn run(
+    statements: Vec<BatchStatement>,
+    tx: Option<JsTransaction>,
+) -> Result<JsValue, JsValue> {
+    let results = js_sys::Array::new();
+
+    let Some(mut tx) = tx else {
+        for statement in &statements {
+            results.push(&statement.exec().await?);
+        }
+        return Ok(results.into());
+    };
+
+    for statement in &statements {
+        match statement.exec_in(&mut tx).await {
+            Ok(result) => {
+                results.push(&result);
+            }
+            Err(error) => {
+                tx.rollback()?;
+                return Err(error);
+            }
+        }
+    }
+
+    tx.commit()?;
+    Ok(results.into())
+}
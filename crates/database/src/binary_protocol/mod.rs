@@ -1,6 +1,6 @@
 //! Binary protocol re-exports from cynos-binary crate.
 
 pub use cynos_binary::{
-    flags, BinaryDataType, BinaryEncoder, BinaryResult, ColumnLayout, SchemaLayout,
+    flags, BinaryDataType, BinaryEncoder, BinaryResult, ColumnLayout, EncodeError, SchemaLayout,
     SchemaLayoutCache, HEADER_SIZE,
 };
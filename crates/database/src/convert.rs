@@ -3,13 +3,17 @@
 //! This module provides functions to convert between JS values and Cynos's
 //! internal types (Value, Row, etc.).
 
+use crate::error::{ConstraintKind, CynosError};
+use crate::table::ComputedDefault;
 use alloc::collections::BTreeMap;
 use alloc::rc::Rc;
 use alloc::string::String;
 use alloc::vec::Vec;
 use cynos_core::schema::Table;
-use cynos_core::{DataType, Row, Value};
+use cynos_core::{DataType, Decimal, Row, Value};
+use cynos_query::executor::{InMemoryDataSource, PhysicalPlanRunner};
 use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
 
 /// Converts a JavaScript value to an Cynos Value.
 ///
@@ -21,6 +25,7 @@ use wasm_bindgen::prelude::*;
 /// - DateTime: JS number (Unix timestamp in ms) or Date object
 /// - Bytes: JS Uint8Array
 /// - Jsonb: Any JS value (serialized to JSON)
+/// - Decimal: JS string (exact) or number
 pub fn js_to_value(js: &JsValue, expected_type: DataType) -> Result<Value, JsValue> {
     if js.is_null() || js.is_undefined() {
         return Ok(Value::Null);
@@ -31,14 +36,14 @@ pub fn js_to_value(js: &JsValue, expected_type: DataType) -> Result<Value, JsVal
             if let Some(b) = js.as_bool() {
                 Ok(Value::Boolean(b))
             } else {
-                Err(JsValue::from_str("Expected boolean value"))
+                Err(CynosError::type_mismatch("Expected boolean value").into())
             }
         }
         DataType::Int32 => {
             if let Some(n) = js.as_f64() {
                 Ok(Value::Int32(n as i32))
             } else {
-                Err(JsValue::from_str("Expected number value"))
+                Err(CynosError::type_mismatch("Expected number value").into())
             }
         }
         DataType::Int64 => {
@@ -48,27 +53,29 @@ pub fn js_to_value(js: &JsValue, expected_type: DataType) -> Result<Value, JsVal
                 // Handle BigInt
                 let s = js_sys::BigInt::from(js.clone())
                     .to_string(10)
-                    .map_err(|_| JsValue::from_str("Failed to convert BigInt"))?;
-                let n: i64 = String::from(s)
-                    .parse()
-                    .map_err(|_| JsValue::from_str("BigInt out of i64 range"))?;
+                    .map_err(|_| {
+                        JsValue::from(CynosError::type_mismatch("Failed to convert BigInt"))
+                    })?;
+                let n: i64 = String::from(s).parse().map_err(|_| {
+                    JsValue::from(CynosError::type_mismatch("BigInt out of i64 range"))
+                })?;
                 Ok(Value::Int64(n))
             } else {
-                Err(JsValue::from_str("Expected number or BigInt value"))
+                Err(CynosError::type_mismatch("Expected number or BigInt value").into())
             }
         }
         DataType::Float64 => {
             if let Some(n) = js.as_f64() {
                 Ok(Value::Float64(n))
             } else {
-                Err(JsValue::from_str("Expected number value"))
+                Err(CynosError::type_mismatch("Expected number value").into())
             }
         }
         DataType::String => {
             if let Some(s) = js.as_string() {
                 Ok(Value::String(s))
             } else {
-                Err(JsValue::from_str("Expected string value"))
+                Err(CynosError::type_mismatch("Expected string value").into())
             }
         }
         DataType::DateTime => {
@@ -79,7 +86,7 @@ pub fn js_to_value(js: &JsValue, expected_type: DataType) -> Result<Value, JsVal
                 let date = js_sys::Date::from(js.clone());
                 Ok(Value::DateTime(date.get_time() as i64))
             } else {
-                Err(JsValue::from_str("Expected number or Date value"))
+                Err(CynosError::type_mismatch("Expected number or Date value").into())
             }
         }
         DataType::Bytes => {
@@ -87,16 +94,27 @@ pub fn js_to_value(js: &JsValue, expected_type: DataType) -> Result<Value, JsVal
                 let arr = js_sys::Uint8Array::new(js);
                 Ok(Value::Bytes(arr.to_vec()))
             } else {
-                Err(JsValue::from_str("Expected Uint8Array value"))
+                Err(CynosError::type_mismatch("Expected Uint8Array value").into())
             }
         }
         DataType::Jsonb => {
             // Serialize any JS value to JSON bytes
-            let json_str = js_sys::JSON::stringify(js)
-                .map_err(|_| JsValue::from_str("Failed to stringify JSON"))?;
+            let json_str = js_sys::JSON::stringify(js).map_err(|_| {
+                JsValue::from(CynosError::type_mismatch("Failed to stringify JSON"))
+            })?;
             let bytes = String::from(json_str).into_bytes();
             Ok(Value::Jsonb(cynos_core::JsonbValue::new(bytes)))
         }
+        DataType::Decimal => {
+            let decimal = if let Some(s) = js.as_string() {
+                s.trim().parse().ok()
+            } else {
+                js.as_f64().and_then(Decimal::from_f64)
+            };
+            decimal.map(Value::Decimal).ok_or_else(|| {
+                CynosError::type_mismatch("Expected decimal string or number").into()
+            })
+        }
     }
 }
 
@@ -126,75 +144,300 @@ pub fn value_to_js(value: &Value) -> JsValue {
                 JsValue::NULL
             }
         }
+        // A string keeps digits a JS number would round away.
+        Value::Decimal(d) => JsValue::from_str(&d.to_string()),
+    }
+}
+
+/// Sets a property on a result object.
+///
+/// Fails if the property cannot be defined, so a malformed result is reported
+/// instead of silently missing a field.
+pub(crate) fn set_property(target: &JsValue, key: &str, value: &JsValue) -> Result<(), JsValue> {
+    match js_sys::Reflect::set(target, &JsValue::from_str(key), value) {
+        Ok(true) => Ok(()),
+        _ => Err(CynosError::invalid_operation(alloc::format!(
+            "Failed to set property '{}' on result object",
+            key
+        ))
+        .into()),
+    }
+}
+
+/// Converts column metadata to a plain JavaScript object of strings.
+pub fn metadata_to_js(metadata: &BTreeMap<String, String>) -> Result<JsValue, JsValue> {
+    let obj: JsValue = js_sys::Object::new().into();
+    for (key, value) in metadata {
+        set_property(&obj, key, &JsValue::from_str(value))?;
     }
+    Ok(obj)
 }
 
 /// Converts an Cynos Row to a JavaScript object.
 ///
 /// The returned object has properties named after the table columns.
-pub fn row_to_js(row: &Row, schema: &Table) -> JsValue {
-    let obj = js_sys::Object::new();
+pub fn row_to_js(row: &Row, schema: &Table) -> Result<JsValue, JsValue> {
+    let obj: JsValue = js_sys::Object::new().into();
     let columns = schema.columns();
 
     for (i, col) in columns.iter().enumerate() {
         if let Some(value) = row.get(i) {
-            let js_val = value_to_js(value);
-            js_sys::Reflect::set(&obj, &JsValue::from_str(col.name()), &js_val).ok();
+            set_property(&obj, col.name(), &value_to_js(value))?;
         }
     }
 
-    obj.into()
+    Ok(obj)
 }
 
 /// Converts a JavaScript object to an Cynos Row.
 ///
 /// The object properties are matched against the table schema columns.
 pub fn js_to_row(js: &JsValue, schema: &Table, row_id: u64) -> Result<Row, JsValue> {
+    js_to_row_with_defaults(js, schema, row_id, &[])
+}
+
+/// Converts a JavaScript object to an Cynos Row, filling columns the object
+/// leaves undefined from their computed `defaults` or, failing that, from the
+/// column's declared default value. Generated columns are always computed,
+/// and an object that sets one is rejected.
+///
+/// Defaults are evaluated after every supplied column has been converted.
+pub(crate) fn js_to_row_with_defaults(
+    js: &JsValue,
+    schema: &Table,
+    row_id: u64,
+    defaults: &[ComputedDefault],
+) -> Result<Row, JsValue> {
     if !js.is_object() {
-        return Err(JsValue::from_str("Expected object value"));
+        return Err(CynosError::type_mismatch("Expected object value").into());
     }
 
     let columns = schema.columns();
     let mut values = Vec::with_capacity(columns.len());
+    let mut pending = Vec::new();
 
     for col in columns {
-        let prop = js_sys::Reflect::get(js, &JsValue::from_str(col.name()))
-            .map_err(|_| JsValue::from_str(&alloc::format!("Missing column: {}", col.name())))?;
-
-        let value = if prop.is_undefined() || prop.is_null() {
-            if col.is_nullable() {
+        let prop = js_sys::Reflect::get(js, &JsValue::from_str(col.name())).map_err(|_| {
+            CynosError::ColumnNotFound(alloc::format!("Missing column: {}", col.name()))
+        })?;
+
+        let computed = defaults
+            .iter()
+            .find(|default| default.column_index == col.index());
+        let value = match computed {
+            Some(default) if default.generated && !prop.is_undefined() => {
+                return Err(generated_column_error(col.name()));
+            }
+            Some(default) if prop.is_undefined() => {
+                pending.push(default);
                 Value::Null
-            } else {
-                return Err(JsValue::from_str(&alloc::format!(
-                    "Column {} is not nullable",
-                    col.name()
-                )));
             }
-        } else {
-            js_to_value(&prop, col.data_type())?
+            _ if prop.is_undefined() || prop.is_null() => {
+                // An explicit null is stored as given, not replaced.
+                match col.declared_default().filter(|_| prop.is_undefined()) {
+                    Some(default) => default.clone(),
+                    None if col.is_nullable() => Value::Null,
+                    None => return Err(not_null_error(col.name()).into()),
+                }
+            }
+            _ => js_to_value(&prop, col.data_type())?,
         };
 
         values.push(value);
     }
 
+    fill_computed_columns(&mut values, schema, row_id, &pending)?;
     Ok(Row::new(row_id, values))
 }
 
+/// Rejects a set list that assigns or resets a generated column, or resets a
+/// column that does not exist. Updates run this once before looking at any
+/// row, so an update matching no rows fails the same way as one that matches.
+pub(crate) fn check_column_updates(
+    updates: &[(String, JsValue)],
+    reset: &[String],
+    schema: &Table,
+    defaults: &[ComputedDefault],
+) -> Result<(), JsValue> {
+    let is_generated = |index: usize| {
+        defaults
+            .iter()
+            .any(|default| default.generated && default.column_index == index)
+    };
+    for (col_name, _) in updates {
+        if let Some(col) = schema.get_column(col_name) {
+            if is_generated(col.index()) {
+                return Err(generated_column_error(col.name()));
+            }
+        }
+    }
+    for col_name in reset {
+        let col = schema.get_column(col_name).ok_or_else(|| {
+            CynosError::ColumnNotFound(alloc::format!("Column not found: {}", col_name))
+        })?;
+        if is_generated(col.index()) {
+            return Err(generated_column_error(col.name()));
+        }
+    }
+    Ok(())
+}
+
+/// Applies `updates` to a copy of `old`'s values and resets the columns in
+/// `reset` to their defaults, then recomputes the generated columns that read
+/// a column whose value changed. Updates naming unknown columns are ignored.
+/// The set list must have passed [`check_column_updates`].
+///
+/// A reset column takes its default expression, evaluated against the updated
+/// row, or else its declared default value, or else NULL.
+pub(crate) fn apply_column_updates(
+    old: &Row,
+    updates: &[(String, JsValue)],
+    reset: &[String],
+    schema: &Table,
+    defaults: &[ComputedDefault],
+) -> Result<Vec<Value>, JsValue> {
+    let mut values = old.values().to_vec();
+    for (col_name, js_val) in updates {
+        if let Some(col) = schema.get_column(col_name) {
+            let idx = col.index();
+            let value = js_to_value(js_val, col.data_type())?;
+            if idx < values.len() {
+                values[idx] = value;
+            }
+        }
+    }
+
+    let mut recompute = Vec::new();
+    for col_name in reset {
+        let Some(col) = schema.get_column(col_name) else {
+            continue;
+        };
+        match defaults
+            .iter()
+            .find(|default| default.column_index == col.index())
+        {
+            Some(default) => recompute.push(default),
+            None => {
+                let value = col.declared_default().cloned().unwrap_or(Value::Null);
+                if value.is_null() && !col.is_nullable() {
+                    return Err(not_null_error(col.name()).into());
+                }
+                values[col.index()] = value;
+            }
+        }
+    }
+
+    recompute.extend(defaults.iter().filter(|default| {
+        default.generated
+            && default
+                .dependencies
+                .iter()
+                .any(|&idx| values.get(idx) != old.get(idx))
+    }));
+    fill_computed_columns(&mut values, schema, old.id(), &recompute)?;
+    Ok(values)
+}
+
+/// Evaluates each computed column in `computed` against `values` and stores
+/// the result, coerced to the column type.
+pub(crate) fn fill_computed_columns(
+    values: &mut [Value],
+    schema: &Table,
+    row_id: u64,
+    computed: &[&ComputedDefault],
+) -> Result<(), CynosError> {
+    if computed.is_empty() {
+        return Ok(());
+    }
+
+    let columns = schema.columns();
+    let supplied = Rc::new(Row::new(row_id, values.to_vec()));
+    let data_source = InMemoryDataSource::new();
+    let runner = PhysicalPlanRunner::new(&data_source);
+    for default in computed {
+        let col = &columns[default.column_index];
+        let value = runner
+            .eval_row(&default.expr, supplied.clone(), schema.name())
+            .coerce_to(col.data_type())
+            .ok_or_else(|| {
+                CynosError::type_mismatch(alloc::format!(
+                    "{} expression for {} does not produce a {:?} value",
+                    if default.generated {
+                        "Generated"
+                    } else {
+                        "Default"
+                    },
+                    col.name(),
+                    col.data_type()
+                ))
+            })?;
+        if value.is_null() && !col.is_nullable() {
+            return Err(not_null_error(col.name()));
+        }
+        values[default.column_index] = value;
+    }
+    Ok(())
+}
+
+fn not_null_error(column: &str) -> CynosError {
+    CynosError::ConstraintViolation {
+        constraint: ConstraintKind::NotNull,
+        message: alloc::format!("Column {} is not nullable", column),
+    }
+}
+
+fn generated_column_error(column: &str) -> JsValue {
+    CynosError::invalid_argument(alloc::format!(
+        "Column {} is generated and cannot be set",
+        column
+    ))
+    .into()
+}
+
+/// Returns an error naming the keys of `js` that are not columns of `schema`.
+fn reject_unknown_fields(js: &JsValue, schema: &Table) -> Result<(), JsValue> {
+    let Some(obj) = js.dyn_ref::<js_sys::Object>() else {
+        return Ok(());
+    };
+    let unknown: Vec<String> = js_sys::Object::keys(obj)
+        .iter()
+        .filter_map(|key| key.as_string())
+        .filter(|key| schema.get_column(key).is_none())
+        .collect();
+    if unknown.is_empty() {
+        return Ok(());
+    }
+    Err(CynosError::ColumnNotFound(alloc::format!(
+        "Unknown column(s) for table {}: {}",
+        schema.name(),
+        unknown.join(", ")
+    ))
+    .into())
+}
+
 /// Converts a JavaScript array of objects to a vector of Rows.
-pub fn js_array_to_rows(
+///
+/// Object keys that are not columns are ignored, unless `strict` is set, in
+/// which case they are reported as an error.
+pub(crate) fn js_array_to_rows(
     js: &JsValue,
     schema: &Table,
     start_row_id: u64,
+    defaults: &[ComputedDefault],
+    strict: bool,
 ) -> Result<Vec<Row>, JsValue> {
     if !js_sys::Array::is_array(js) {
-        return Err(JsValue::from_str("Expected array value"));
+        return Err(CynosError::type_mismatch("Expected array value").into());
     }
 
     let arr = js_sys::Array::from(js);
     let mut rows = Vec::with_capacity(arr.length() as usize);
 
     for (i, item) in arr.iter().enumerate() {
-        let row = js_to_row(&item, schema, start_row_id + i as u64)?;
+        if strict {
+            reject_unknown_fields(&item, schema)?;
+        }
+        let row = js_to_row_with_defaults(&item, schema, start_row_id + i as u64, defaults)?;
         rows.push(row);
     }
 
@@ -202,15 +445,15 @@ pub fn js_array_to_rows(
 }
 
 /// Converts a vector of Rows to a JavaScript array of objects.
-pub fn rows_to_js_array(rows: &[Rc<Row>], schema: &Table) -> JsValue {
+pub fn rows_to_js_array(rows: &[Rc<Row>], schema: &Table) -> Result<JsValue, JsValue> {
     let arr = js_sys::Array::new_with_length(rows.len() as u32);
 
     for (i, row) in rows.iter().enumerate() {
-        let obj = row_to_js(row, schema);
+        let obj = row_to_js(row, schema)?;
         arr.set(i as u32, obj);
     }
 
-    arr.into()
+    Ok(arr.into())
 }
 
 /// Converts a vector of projected Rows to a JavaScript array of objects.
@@ -218,7 +461,10 @@ pub fn rows_to_js_array(rows: &[Rc<Row>], schema: &Table) -> JsValue {
 /// This function is used when only specific columns are selected (projection).
 /// The `column_names` parameter specifies the names of the projected columns
 /// in the order they appear in the row.
-pub fn projected_rows_to_js_array(rows: &[Rc<Row>], column_names: &[String]) -> JsValue {
+pub fn projected_rows_to_js_array(
+    rows: &[Rc<Row>],
+    column_names: &[String],
+) -> Result<JsValue, JsValue> {
     let arr = js_sys::Array::new_with_length(rows.len() as u32);
 
     // Extract just the column part from qualified names and count occurrences
@@ -252,17 +498,16 @@ pub fn projected_rows_to_js_array(rows: &[Rc<Row>], column_names: &[String]) ->
         .collect();
 
     for (i, row) in rows.iter().enumerate() {
-        let obj = js_sys::Object::new();
+        let obj: JsValue = js_sys::Object::new().into();
         for (col_idx, col_name) in final_names.iter().enumerate() {
             if let Some(value) = row.get(col_idx) {
-                let js_val = value_to_js(value);
-                js_sys::Reflect::set(&obj, &JsValue::from_str(col_name), &js_val).ok();
+                set_property(&obj, col_name, &value_to_js(value))?;
             }
         }
-        arr.set(i as u32, obj.into());
+        arr.set(i as u32, obj);
     }
 
-    arr.into()
+    Ok(arr.into())
 }
 
 /// Converts a vector of Rows to a JavaScript array of objects using multiple schemas.
@@ -270,7 +515,7 @@ pub fn projected_rows_to_js_array(rows: &[Rc<Row>], column_names: &[String]) ->
 /// This function is used for JOIN queries where the result contains columns from multiple tables.
 /// The `schemas` parameter specifies the schemas of all joined tables in order.
 /// For duplicate column names across tables, we use `table.column` format to distinguish them.
-pub fn joined_rows_to_js_array(rows: &[Rc<Row>], schemas: &[&Table]) -> JsValue {
+pub fn joined_rows_to_js_array(rows: &[Rc<Row>], schemas: &[&Table]) -> Result<JsValue, JsValue> {
     let arr = js_sys::Array::new_with_length(rows.len() as u32);
 
     // First pass: count occurrences of each column name
@@ -298,17 +543,16 @@ pub fn joined_rows_to_js_array(rows: &[Rc<Row>], schemas: &[&Table]) -> JsValue
     }
 
     for (i, row) in rows.iter().enumerate() {
-        let obj = js_sys::Object::new();
+        let obj: JsValue = js_sys::Object::new().into();
         for (col_idx, col_name) in column_names.iter().enumerate() {
             if let Some(value) = row.get(col_idx) {
-                let js_val = value_to_js(value);
-                js_sys::Reflect::set(&obj, &JsValue::from_str(col_name), &js_val).ok();
+                set_property(&obj, col_name, &value_to_js(value))?;
             }
         }
-        arr.set(i as u32, obj.into());
+        arr.set(i as u32, obj);
     }
 
-    arr.into()
+    Ok(arr.into())
 }
 
 /// Converts a JS variables object into GraphQL variables.
@@ -322,18 +566,26 @@ pub fn js_to_gql_variables(js: Option<&JsValue>) -> Result<cynos_gql::VariableVa
     }
 
     if !js.is_object() || js_sys::Array::is_array(js) {
-        return Err(JsValue::from_str("GraphQL variables must be an object"));
+        return Err(JsValue::from(CynosError::invalid_argument(
+            "GraphQL variables must be an object",
+        )));
     }
 
-    let keys = js_sys::Reflect::own_keys(js)
-        .map_err(|_| JsValue::from_str("Failed to enumerate GraphQL variables"))?;
+    let keys = js_sys::Reflect::own_keys(js).map_err(|_| {
+        JsValue::from(CynosError::invalid_argument(
+            "Failed to enumerate GraphQL variables",
+        ))
+    })?;
     let mut variables = BTreeMap::new();
     for key in keys.iter() {
         let Some(name) = key.as_string() else {
             continue;
         };
-        let value = js_sys::Reflect::get(js, &key)
-            .map_err(|_| JsValue::from_str("Failed to read GraphQL variable"))?;
+        let value = js_sys::Reflect::get(js, &key).map_err(|_| {
+            JsValue::from(CynosError::invalid_argument(
+                "Failed to read GraphQL variable",
+            ))
+        })?;
         variables.insert(name, js_to_gql_input_value(&value)?);
     }
 
@@ -342,10 +594,10 @@ pub fn js_to_gql_variables(js: Option<&JsValue>) -> Result<cynos_gql::VariableVa
 
 /// Converts a GraphQL execution response into the standard `{ data }` object shape.
 pub fn gql_response_to_js(response: &cynos_gql::GraphqlResponse) -> Result<JsValue, JsValue> {
-    let obj = js_sys::Object::new();
-    let data = gql_value_to_js(&response.data);
-    js_sys::Reflect::set(&obj, &JsValue::from_str("data"), &data)?;
-    Ok(obj.into())
+    let obj: JsValue = js_sys::Object::new().into();
+    let data = gql_value_to_js(&response.data)?;
+    set_property(&obj, "data", &data)?;
+    Ok(obj)
 }
 
 fn js_to_gql_input_value(js: &JsValue) -> Result<cynos_gql::InputValue, JsValue> {
@@ -360,10 +612,10 @@ fn js_to_gql_input_value(js: &JsValue) -> Result<cynos_gql::InputValue, JsValue>
     if js.is_bigint() {
         let string = js_sys::BigInt::from(js.clone())
             .to_string(10)
-            .map_err(|_| JsValue::from_str("Failed to convert BigInt"))?;
+            .map_err(|_| JsValue::from(CynosError::type_mismatch("Failed to convert BigInt")))?;
         let value: i64 = String::from(string)
             .parse()
-            .map_err(|_| JsValue::from_str("BigInt out of i64 range"))?;
+            .map_err(|_| JsValue::from(CynosError::type_mismatch("BigInt out of i64 range")))?;
         return Ok(cynos_gql::InputValue::Int(value));
     }
 
@@ -405,15 +657,21 @@ fn js_to_gql_input_value(js: &JsValue) -> Result<cynos_gql::InputValue, JsValue>
     }
 
     if js.is_object() {
-        let keys = js_sys::Reflect::own_keys(js)
-            .map_err(|_| JsValue::from_str("Failed to enumerate GraphQL input object"))?;
+        let keys = js_sys::Reflect::own_keys(js).map_err(|_| {
+            JsValue::from(CynosError::invalid_argument(
+                "Failed to enumerate GraphQL input object",
+            ))
+        })?;
         let mut fields = Vec::with_capacity(keys.length() as usize);
         for key in keys.iter() {
             let Some(name) = key.as_string() else {
                 continue;
             };
-            let value = js_sys::Reflect::get(js, &key)
-                .map_err(|_| JsValue::from_str("Failed to read GraphQL input field"))?;
+            let value = js_sys::Reflect::get(js, &key).map_err(|_| {
+                JsValue::from(CynosError::invalid_argument(
+                    "Failed to read GraphQL input field",
+                ))
+            })?;
             fields.push(cynos_gql::ast::ObjectField {
                 name,
                 value: js_to_gql_input_value(&value)?,
@@ -422,31 +680,28 @@ fn js_to_gql_input_value(js: &JsValue) -> Result<cynos_gql::InputValue, JsValue>
         return Ok(cynos_gql::InputValue::Object(fields));
     }
 
-    Err(JsValue::from_str("Unsupported GraphQL input value"))
+    Err(JsValue::from(CynosError::invalid_argument(
+        "Unsupported GraphQL input value",
+    )))
 }
 
-fn gql_value_to_js(value: &cynos_gql::ResponseValue) -> JsValue {
+fn gql_value_to_js(value: &cynos_gql::ResponseValue) -> Result<JsValue, JsValue> {
     match value {
-        cynos_gql::ResponseValue::Null => JsValue::NULL,
-        cynos_gql::ResponseValue::Scalar(value) => value_to_js(value),
+        cynos_gql::ResponseValue::Null => Ok(JsValue::NULL),
+        cynos_gql::ResponseValue::Scalar(value) => Ok(value_to_js(value)),
         cynos_gql::ResponseValue::List(values) => {
             let array = js_sys::Array::new_with_length(values.len() as u32);
             for (index, value) in values.iter().enumerate() {
-                array.set(index as u32, gql_value_to_js(value));
+                array.set(index as u32, gql_value_to_js(value)?);
             }
-            array.into()
+            Ok(array.into())
         }
         cynos_gql::ResponseValue::Object(fields) => {
-            let object = js_sys::Object::new();
+            let object: JsValue = js_sys::Object::new().into();
             for field in fields {
-                js_sys::Reflect::set(
-                    &object,
-                    &JsValue::from_str(&field.name),
-                    &gql_value_to_js(&field.value),
-                )
-                .ok();
+                set_property(&object, &field.name, &gql_value_to_js(&field.value)?)?;
             }
-            object.into()
+            Ok(object)
         }
     }
 }
@@ -586,4 +841,14 @@ mod tests {
         let js = JsValue::NULL;
         assert_eq!(infer_type(&js), None);
     }
+
+    #[wasm_bindgen_test]
+    fn test_set_property_on_frozen_object_returns_error() {
+        let obj = js_sys::Object::new();
+        js_sys::Object::freeze(&obj);
+
+        let err = set_property(&obj, "id", &JsValue::from_f64(1.0)).unwrap_err();
+        let code = js_sys::Reflect::get(&err, &JsValue::from_str("code")).unwrap();
+        assert_eq!(code.as_string().as_deref(), Some("INVALID_OPERATION"));
+    }
 }
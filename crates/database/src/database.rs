@@ -3,25 +3,31 @@
 //! This module provides the `Database` struct which is the primary interface
 //! for creating tables, executing queries, and managing data.
 
+use crate::batch;
 use crate::binary_protocol::SchemaLayoutCache;
-use crate::convert::{gql_response_to_js, js_to_gql_variables};
-use crate::dataflow_compiler::compile_to_dataflow;
-use crate::live_runtime::{LiveDependencySet, LivePlan, LiveRegistry};
+use crate::convert::{gql_response_to_js, js_to_gql_variables, js_to_value};
+use crate::dataflow_compiler::{compile_to_dataflow, top_n_input_plan};
+use crate::error::CynosError;
+use crate::live_runtime::{LiveDependencySet, LivePlan, LiveRegistry, SnapshotSubscription};
 use crate::query_builder::{DeleteBuilder, InsertBuilder, SelectBuilder, UpdateBuilder};
-use crate::reactive_bridge::JsGraphqlSubscription;
-use crate::table::{JsTable, JsTableBuilder};
+use crate::reactive_bridge::{JsGraphqlSubscription, JsRowObservable, RowObservable};
+use crate::snapshot::{DatabaseSnapshot, TableSnapshot};
+use crate::table::{ComputedDefaults, JsTable, JsTableBuilder};
+use crate::table_json;
 use crate::transaction::JsTransaction;
+use crate::JsNullsOrder;
 use alloc::rc::Rc;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
-use core::cell::RefCell;
-use cynos_core::Row;
+use core::cell::{Cell, RefCell};
+use cynos_core::{reserve_row_ids, set_next_row_id_if_greater, DataType, Row, Value};
 use cynos_gql::{PreparedQuery as GqlPreparedQuery, SchemaCache as GraphqlSchemaCache};
 use cynos_incremental::Delta;
 use cynos_query::plan_cache::PlanCache;
 use cynos_reactive::TableId;
-use cynos_storage::TableCache;
+use cynos_storage::{RowStore, TableCache};
 use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
 
 /// The main database interface.
 ///
@@ -41,6 +47,9 @@ pub struct Database {
     plan_cache: Rc<RefCell<PlanCache>>,
     graphql_schema_cache: Rc<RefCell<GraphqlSchemaCache>>,
     schema_epoch: Rc<RefCell<u64>>,
+    computed_defaults: Rc<RefCell<ComputedDefaults>>,
+    strict_inserts: Cell<bool>,
+    default_nulls_order: Cell<Option<cynos_index::NullsOrder>>,
 }
 
 /// A prepared GraphQL query that reuses the parsed document across executions.
@@ -75,6 +84,9 @@ impl Database {
             plan_cache: Rc::new(RefCell::new(PlanCache::default_size())),
             graphql_schema_cache: Rc::new(RefCell::new(GraphqlSchemaCache::new())),
             schema_epoch: Rc::new(RefCell::new(0)),
+            computed_defaults: Rc::new(RefCell::new(hashbrown::HashMap::new())),
+            strict_inserts: Cell::new(false),
+            default_nulls_order: Cell::new(None),
         }
     }
 
@@ -101,16 +113,24 @@ impl Database {
     pub fn register_table(&self, builder: &JsTableBuilder) -> Result<(), JsValue> {
         let schema = builder.build_internal()?;
         let table_name = schema.name().to_string();
+        let defaults = builder.computed_defaults(&schema)?;
 
         self.cache
             .borrow_mut()
             .create_table(schema)
-            .map_err(|e| JsValue::from_str(&alloc::format!("{:?}", e)))?;
+            .map_err(CynosError::from)?;
 
         // Assign table ID
         let table_id = *self.next_table_id.borrow();
         *self.next_table_id.borrow_mut() += 1;
-        self.table_id_map.borrow_mut().insert(table_name, table_id);
+        self.table_id_map
+            .borrow_mut()
+            .insert(table_name.clone(), table_id);
+        if !defaults.is_empty() {
+            self.computed_defaults
+                .borrow_mut()
+                .insert(table_name, defaults);
+        }
         *self.schema_epoch.borrow_mut() += 1;
         self.graphql_schema_cache.borrow_mut().clear();
 
@@ -131,9 +151,13 @@ impl Database {
         self.cache
             .borrow_mut()
             .drop_table(name)
-            .map_err(|e| JsValue::from_str(&alloc::format!("{:?}", e)))?;
+            .map_err(CynosError::from)?;
 
-        self.table_id_map.borrow_mut().remove(name);
+        if let Some(table_id) = self.table_id_map.borrow_mut().remove(name) {
+            self.query_registry.borrow_mut().on_table_dropped(table_id);
+        }
+        self.computed_defaults.borrow_mut().remove(name);
+        self.schema_layout_cache.borrow_mut().invalidate(name);
         *self.schema_epoch.borrow_mut() += 1;
         self.graphql_schema_cache.borrow_mut().clear();
         Ok(())
@@ -166,9 +190,11 @@ impl Database {
             self.query_registry.clone(),
             self.table_id_map.clone(),
             self.schema_layout_cache.clone(),
+            self.schema_epoch.clone(),
             self.plan_cache.clone(),
             columns.clone(),
         )
+        .with_default_nulls_order(self.default_nulls_order.get())
     }
 
     /// Starts an INSERT operation.
@@ -177,7 +203,9 @@ impl Database {
             self.cache.clone(),
             self.query_registry.clone(),
             self.table_id_map.clone(),
+            self.computed_defaults.clone(),
             table,
+            self.strict_inserts.get(),
         )
     }
 
@@ -187,6 +215,7 @@ impl Database {
             self.cache.clone(),
             self.query_registry.clone(),
             self.table_id_map.clone(),
+            self.computed_defaults.clone(),
             table,
         )
     }
@@ -207,9 +236,31 @@ impl Database {
             self.cache.clone(),
             self.query_registry.clone(),
             self.table_id_map.clone(),
+            self.computed_defaults.clone(),
+            self.strict_inserts.get(),
         )
     }
 
+    /// Executes several query builders in order and returns their results.
+    ///
+    /// `statements` is an array of select, insert, update, and delete
+    /// builders; the result array holds one entry per statement, in order.
+    /// The builders are consumed and cannot be executed again.
+    ///
+    /// Options:
+    /// - `transaction`: when `true`, runs the batch in a transaction that is
+    ///   rolled back if any statement fails. Otherwise statements that ran
+    ///   before a failure stay applied.
+    pub async fn batch(
+        &self,
+        statements: js_sys::Array,
+        options: Option<JsValue>,
+    ) -> Result<JsValue, JsValue> {
+        let statements = batch::parse_statements(&statements)?;
+        let tx = batch::wants_transaction(options.as_ref()).then(|| self.transaction());
+        batch::run(statements, tx).await
+    }
+
     /// Clears all data from all tables.
     pub fn clear(&self) {
         self.cache.borrow_mut().clear();
@@ -221,7 +272,267 @@ impl Database {
         self.cache
             .borrow_mut()
             .clear_table(name)
-            .map_err(|e| JsValue::from_str(&alloc::format!("{:?}", e)))
+            .map_err(|e| CynosError::from(e).into())
+    }
+
+    /// Turns live query maintenance on or off.
+    ///
+    /// With reactivity off, writes skip all notification bookkeeping, which
+    /// speeds up bulk imports; observables and subscriptions stop updating.
+    /// Turning it back on recomputes every live query from the current data
+    /// and notifies subscribers whose results changed in the meantime.
+    #[wasm_bindgen(js_name = setReactiveEnabled)]
+    pub fn set_reactive_enabled(&self, enabled: bool) {
+        let was_enabled = self.query_registry.borrow().is_enabled();
+        self.query_registry.borrow_mut().set_enabled(enabled);
+        if enabled && !was_enabled {
+            self.resync_live_queries();
+        }
+    }
+
+    /// Makes inserts reject objects with keys that are not columns of the
+    /// table, naming the unknown keys in a `COLUMN_NOT_FOUND` error. Off by
+    /// default, in which case such keys are ignored. A single insert can
+    /// override this with `insert(table).strict(...)`.
+    #[wasm_bindgen(js_name = setStrictInserts)]
+    pub fn set_strict_inserts(&self, strict: bool) {
+        self.strict_inserts.set(strict);
+    }
+
+    /// Sets where NULLs go in ORDER BY keys that do not choose themselves,
+    /// GraphQL `orderBy` entries included, for queries started after the
+    /// call. `undefined` restores the natural placement: first when
+    /// ascending, last when descending.
+    #[wasm_bindgen(js_name = setDefaultNullsOrder)]
+    pub fn set_default_nulls_order(&self, nulls: Option<JsNullsOrder>) {
+        let nulls = nulls.map(Into::into);
+        self.default_nulls_order.set(nulls);
+        self.graphql_schema_cache
+            .borrow_mut()
+            .set_default_nulls_order(nulls);
+    }
+
+    /// Sets the largest estimated fraction of a table that a range predicate
+    /// may match and still be read through an index; wider ranges use a
+    /// filtered scan. `undefined` restores the default of 0.3. Estimates come
+    /// from the histograms kept on single-column B-tree indexes.
+    #[wasm_bindgen(js_name = setMaxIndexSelectivity)]
+    pub fn set_max_index_selectivity(&self, fraction: Option<f64>) {
+        self.cache.borrow_mut().set_max_index_selectivity(fraction);
+        self.plan_cache.borrow_mut().clear();
+    }
+
+    /// Captures the whole database: every table's schema, rows and indexes,
+    /// plus the table id and row id counters.
+    ///
+    /// The snapshot shares unchanged rows with the live tables, so it is cheap
+    /// to take and to keep.
+    pub fn snapshot(&self) -> DatabaseSnapshot {
+        let cache = self.cache.borrow();
+        DatabaseSnapshot {
+            tables: cache
+                .table_names()
+                .into_iter()
+                .filter_map(|name| cache.get_table(name))
+                .map(TableSnapshot::capture)
+                .collect(),
+            table_ids: self.table_id_map.borrow().clone(),
+            next_table_id: *self.next_table_id.borrow(),
+            // Reserving zero ids reads the counter without advancing it.
+            next_row_id: reserve_row_ids(0),
+            computed_defaults: self.computed_defaults.borrow().clone(),
+        }
+    }
+
+    /// Rolls the database back to `snapshot`.
+    ///
+    /// Tables created since are dropped and dropped tables come back; every
+    /// table holds exactly the snapshot's rows, under their original row ids.
+    /// The new state replaces the old one only once it is fully built, so a
+    /// failure leaves the database untouched. Live queries are recomputed and
+    /// notified. The row id counter never moves backwards, so ids handed out
+    /// after the snapshot are not reused.
+    pub fn restore(&self, snapshot: &DatabaseSnapshot) -> Result<(), JsValue> {
+        let mut cache = snapshot.build_cache().map_err(CynosError::from)?;
+        cache.set_max_index_selectivity(self.cache.borrow().max_index_selectivity());
+        *self.cache.borrow_mut() = cache;
+        *self.table_id_map.borrow_mut() = snapshot.table_ids.clone();
+        let next_table_id = (*self.next_table_id.borrow()).max(snapshot.next_table_id);
+        *self.next_table_id.borrow_mut() = next_table_id;
+        *self.computed_defaults.borrow_mut() = snapshot.computed_defaults.clone();
+        set_next_row_id_if_greater(snapshot.next_row_id);
+
+        self.schema_layout_cache.borrow_mut().clear();
+        self.plan_cache.borrow_mut().clear();
+        self.graphql_schema_cache.borrow_mut().clear();
+        *self.schema_epoch.borrow_mut() += 1;
+
+        if self.query_registry.borrow().is_enabled() {
+            self.resync_live_queries();
+        }
+        Ok(())
+    }
+
+    /// Returns false while reactivity is turned off with `setReactiveEnabled`.
+    #[wasm_bindgen(js_name = isReactiveEnabled)]
+    pub fn is_reactive_enabled(&self) -> bool {
+        self.query_registry.borrow().is_enabled()
+    }
+
+    /// Exports all rows of a table as a JSON array of objects.
+    ///
+    /// Int64 values are written as decimal strings so they survive the trip
+    /// through JS numbers; see the `table_json` module for the full encoding.
+    #[wasm_bindgen(js_name = exportTableJson)]
+    pub fn export_table_json(&self, table: &str) -> Result<String, JsValue> {
+        let cache = self.cache.borrow();
+        let store = cache
+            .get_table(table)
+            .ok_or_else(|| CynosError::table_not_found(table))?;
+        let rows: Vec<Rc<Row>> = store.scan().collect();
+        Ok(table_json::export_rows(
+            store.schema(),
+            rows.iter().map(|row| row.as_ref()),
+        )?)
+    }
+
+    /// Inserts the rows of a JSON array in the format of `exportTableJson`.
+    ///
+    /// Rows are filled and checked like `insert(...).values(...)` rows:
+    /// missing keys take column defaults, generated columns are recomputed,
+    /// constraints are enforced and live queries are notified. Either every
+    /// row is inserted or, if one fails to decode or violates a constraint,
+    /// none is. Returns the number of inserted rows.
+    #[wasm_bindgen(js_name = importTableJson)]
+    pub fn import_table_json(&self, table: &str, json: &str) -> Result<usize, JsValue> {
+        let mut cache = self.cache.borrow_mut();
+        let store = cache
+            .get_table_mut(table)
+            .ok_or_else(|| CynosError::table_not_found(table))?;
+
+        let rows = {
+            let defaults = self.computed_defaults.borrow();
+            let defaults = defaults.get(table).map_or(&[][..], Vec::as_slice);
+            table_json::decode_rows(store.schema(), json, defaults)?
+        };
+
+        let reactive = self.query_registry.borrow().is_enabled();
+        let mut inserted_ids = hashbrown::HashSet::with_capacity(rows.len());
+        let mut deltas = Vec::with_capacity(if reactive { rows.len() } else { 0 });
+
+        for row in rows {
+            let row_id = row.id();
+            let delta = reactive.then(|| Delta::insert(row.clone()));
+            if let Err(error) = store.insert(row) {
+                for row_id in &inserted_ids {
+                    let _ = store.delete(*row_id);
+                }
+                return Err(CynosError::from(error).into());
+            }
+            inserted_ids.insert(row_id);
+            deltas.extend(delta);
+        }
+
+        let row_count = inserted_ids.len();
+        let table_id = self.table_id_map.borrow().get(table).copied();
+        if let Some(table_id) = table_id.filter(|_| reactive) {
+            drop(cache);
+            self.query_registry
+                .borrow_mut()
+                .on_table_change_delta(table_id, deltas, &inserted_ids);
+        }
+
+        Ok(row_count)
+    }
+
+    /// Returns a zero-copy view of a row's `Bytes` value.
+    ///
+    /// The row is looked up by primary key; pass an array for a composite key.
+    /// Returns `undefined` when no row has the key or the value is NULL.
+    /// WARNING: the view aliases WASM memory. It becomes invalid if WASM memory
+    /// grows or the row is updated, deleted or cleared, so read or copy it
+    /// before the next database call.
+    #[wasm_bindgen(js_name = getRowBytes)]
+    pub fn get_row_bytes(
+        &self,
+        table: &str,
+        id: &JsValue,
+        column: &str,
+    ) -> Result<Option<js_sys::Uint8Array>, JsValue> {
+        let cache = self.cache.borrow();
+        let store = cache
+            .get_table(table)
+            .ok_or_else(|| CynosError::table_not_found(table))?;
+        let schema = store.schema();
+
+        let col = schema.get_column(column).ok_or_else(|| {
+            CynosError::ColumnNotFound(alloc::format!("Column not found: {}", column))
+        })?;
+        if col.data_type() != DataType::Bytes {
+            return Err(CynosError::type_mismatch(alloc::format!(
+                "Column {} is not a Bytes column",
+                column
+            ))
+            .into());
+        }
+
+        let pk_values = pk_values_from_js(table, store, id)?;
+        let Some(row) = store.get_by_pk_values(&pk_values).into_iter().next() else {
+            return Ok(None);
+        };
+        let Some(Value::Bytes(bytes)) = row.get(col.index()) else {
+            return Ok(None);
+        };
+
+        let memory = wasm_bindgen::memory();
+        let buffer = memory
+            .dyn_ref::<js_sys::WebAssembly::Memory>()
+            .expect("wasm_bindgen::memory() should return WebAssembly.Memory")
+            .buffer();
+        // The row stays alive in the store after `row` is dropped, so the
+        // bytes remain at this address until the row is modified.
+        Ok(Some(js_sys::Uint8Array::new_with_byte_offset_and_length(
+            &buffer,
+            bytes.as_ptr() as u32,
+            bytes.len() as u32,
+        )))
+    }
+
+    /// Observes a single row by primary key.
+    ///
+    /// Pass an array for a composite key. Subscribers are called with the row
+    /// whenever it is inserted or updated, and with `null` when it is deleted.
+    /// Changes to other rows of the table never reach the callback.
+    #[wasm_bindgen(js_name = observeRow)]
+    pub fn observe_row(&self, table: &str, id: &JsValue) -> Result<JsRowObservable, JsValue> {
+        let cache = self.cache.borrow();
+        let store = cache
+            .get_table(table)
+            .ok_or_else(|| CynosError::table_not_found(table))?;
+        let pk_values = pk_values_from_js(table, store, id)?;
+        let initial_row = store.get_by_pk_values(&pk_values).into_iter().next();
+        let schema = store.schema().clone();
+        drop(cache);
+
+        let table_id = self
+            .table_id_map
+            .borrow()
+            .get(table)
+            .copied()
+            .ok_or_else(|| {
+                CynosError::TableNotFound(alloc::format!("Table ID not found: {}", table))
+            })?;
+        let observable = Rc::new(RefCell::new(RowObservable::new(
+            self.cache.clone(),
+            table.to_string(),
+            pk_values,
+            initial_row,
+        )));
+        self.query_registry.borrow_mut().register_snapshot(
+            SnapshotSubscription::Row(observable.clone()),
+            &LiveDependencySet::snapshot(alloc::vec![table_id]),
+        );
+        Ok(JsRowObservable::new(observable, schema))
     }
 
     /// Returns the total row count across all tables.
@@ -256,7 +567,7 @@ impl Database {
     ) -> Result<JsValue, JsValue> {
         let variables = js_to_gql_variables(variables.as_ref())?;
         let prepared = GqlPreparedQuery::parse_with_operation(query, operation_name.as_deref())
-            .map_err(|error| JsValue::from_str(error.message()))?;
+            .map_err(CynosError::from)?;
 
         let cache = self.cache.borrow();
         let (catalog, bound) = bind_graphql_operation(
@@ -287,7 +598,7 @@ impl Database {
     ) -> Result<JsGraphqlSubscription, JsValue> {
         let variables = js_to_gql_variables(variables.as_ref())?;
         let prepared = GqlPreparedQuery::parse_with_operation(query, operation_name.as_deref())
-            .map_err(|error| JsValue::from_str(error.message()))?;
+            .map_err(CynosError::from)?;
 
         let cache = self.cache.borrow();
         let (catalog, bound) = bind_graphql_operation(
@@ -316,7 +627,7 @@ impl Database {
         operation_name: Option<String>,
     ) -> Result<PreparedGraphqlQuery, JsValue> {
         let prepared = GqlPreparedQuery::parse_with_operation(query, operation_name.as_deref())
-            .map_err(|error| JsValue::from_str(error.message()))?;
+            .map_err(CynosError::from)?;
         Ok(PreparedGraphqlQuery {
             cache: self.cache.clone(),
             query_registry: self.query_registry.clone(),
@@ -343,7 +654,7 @@ impl Database {
         let mut cache = self.cache.borrow_mut();
         let store = cache
             .get_table_mut(table)
-            .ok_or_else(|| JsValue::from_str(&alloc::format!("Table not found: {}", table)))?;
+            .ok_or_else(|| CynosError::table_not_found(&table))?;
 
         let schema = store.schema().clone();
         let columns = schema.columns();
@@ -378,9 +689,7 @@ impl Database {
             }
 
             let row = Row::new(row_id, values);
-            store
-                .insert(row)
-                .map_err(|e| JsValue::from_str(&alloc::format!("{:?}", e)))?;
+            store.insert(row).map_err(CynosError::from)?;
         }
 
         let end = js_sys::Date::now();
@@ -443,12 +752,12 @@ impl Database {
         let cache = self.cache.borrow();
         let store = cache
             .get_table(table)
-            .ok_or_else(|| JsValue::from_str(&alloc::format!("Table not found: {}", table)))?;
+            .ok_or_else(|| CynosError::table_not_found(&table))?;
 
         let schema = store.schema().clone();
-        let col = schema
-            .get_column(column)
-            .ok_or_else(|| JsValue::from_str(&alloc::format!("Column not found: {}", column)))?;
+        let col = schema.get_column(column).ok_or_else(|| {
+            CynosError::ColumnNotFound(alloc::format!("Column not found: {}", column))
+        })?;
         let col_idx = col.index();
 
         // Build logical plan: SELECT * FROM table WHERE column > threshold
@@ -469,8 +778,7 @@ impl Database {
 
         // Measure query execution time (no serialization)
         let query_start = js_sys::Date::now();
-        let rows = execute_plan(&cache, table, plan)
-            .map_err(|e| JsValue::from_str(&alloc::format!("Query error: {:?}", e)))?;
+        let rows = execute_plan(&cache, table, plan).map_err(CynosError::from)?;
         let query_end = js_sys::Date::now();
         let query_ms = query_end - query_start;
 
@@ -478,7 +786,7 @@ impl Database {
 
         // Measure serialization time
         let serialize_start = js_sys::Date::now();
-        let _js_result = crate::convert::rows_to_js_array(&rows, &schema);
+        let _js_result = crate::convert::rows_to_js_array(&rows, &schema)?;
         let serialize_end = js_sys::Date::now();
         let serialize_ms = serialize_end - serialize_start;
 
@@ -568,6 +876,41 @@ impl PreparedGraphqlQuery {
     }
 }
 
+/// Converts a JS primary key (a scalar, or an array for a composite key) into
+/// the table's primary key values.
+fn pk_values_from_js(table: &str, store: &RowStore, id: &JsValue) -> Result<Vec<Value>, JsValue> {
+    let schema = store.schema();
+    let pk_columns = store.pk_columns();
+    if pk_columns.is_empty() {
+        return Err(CynosError::invalid_operation(alloc::format!(
+            "Table {} has no primary key",
+            table
+        ))
+        .into());
+    }
+    if pk_columns.len() == 1 {
+        let pk_type = schema.columns()[pk_columns[0]].data_type();
+        return Ok(alloc::vec![js_to_value(id, pk_type)?]);
+    }
+
+    let parts = id.dyn_ref::<js_sys::Array>().ok_or_else(|| {
+        CynosError::invalid_argument("A composite primary key must be passed as an array")
+    })?;
+    if parts.length() as usize != pk_columns.len() {
+        return Err(CynosError::invalid_argument(alloc::format!(
+            "Expected {} primary key values, got {}",
+            pk_columns.len(),
+            parts.length()
+        ))
+        .into());
+    }
+    pk_columns
+        .iter()
+        .zip(parts.iter())
+        .map(|(&idx, part)| js_to_value(&part, schema.columns()[idx].data_type()))
+        .collect()
+}
+
 fn bind_graphql_operation(
     prepared: &GqlPreparedQuery,
     cache: &TableCache,
@@ -579,7 +922,7 @@ fn bind_graphql_operation(
     let catalog = graphql_schema_cache.borrow_mut().catalog(epoch, cache);
     let bound = prepared
         .bind(&catalog, Some(variables))
-        .map_err(|error| JsValue::from_str(error.message()))?;
+        .map_err(CynosError::from)?;
     Ok((catalog, bound))
 }
 
@@ -591,14 +934,15 @@ fn execute_graphql_bound_operation(
     bound: cynos_gql::BoundOperation,
 ) -> Result<JsValue, JsValue> {
     if bound.kind == cynos_gql::OperationType::Subscription {
-        return Err(JsValue::from_str(
+        return Err(CynosError::invalid_operation(
             "subscription operations must use subscribeGraphql() or PreparedGraphqlQuery.subscribe()",
-        ));
+        )
+        .into());
     }
 
     let mut cache_ref = cache.borrow_mut();
     let outcome = cynos_gql::execute::execute_bound_operation_mut(&mut cache_ref, &catalog, &bound)
-        .map_err(|error| JsValue::from_str(error.message()))?;
+        .map_err(CynosError::from)?;
     drop(cache_ref);
 
     notify_graphql_changes(query_registry, table_id_map, &outcome.changes);
@@ -630,29 +974,31 @@ fn compile_graphql_live_plan(
     bound: cynos_gql::BoundOperation,
 ) -> Result<LivePlan, JsValue> {
     if bound.kind != cynos_gql::OperationType::Subscription {
-        return Err(JsValue::from_str(
+        return Err(CynosError::invalid_operation(
             "subscribeGraphql() only accepts subscription operations",
-        ));
+        )
+        .into());
     }
     if bound.fields.len() != 1 {
-        return Err(JsValue::from_str(
-            "GraphQL subscriptions must select exactly one root field",
-        ));
+        return Err(CynosError::Graphql(
+            "GraphQL subscriptions must select exactly one root field".into(),
+        )
+        .into());
     }
 
     let field = bound
         .fields
         .into_iter()
         .next()
-        .ok_or_else(|| JsValue::from_str("subscription is missing a root field"))?;
+        .ok_or_else(|| CynosError::Graphql("subscription is missing a root field".into()))?;
     if matches!(field.kind, cynos_gql::bind::BoundRootFieldKind::Typename) {
-        return Err(JsValue::from_str(
-            "GraphQL subscriptions must select a concrete root field",
-        ));
+        return Err(CynosError::Graphql(
+            "GraphQL subscriptions must select a concrete root field".into(),
+        )
+        .into());
     }
 
-    let root_plan = cynos_gql::build_root_field_plan(&catalog, &field)
-        .map_err(|error| JsValue::from_str(error.message()))?;
+    let root_plan = cynos_gql::build_root_field_plan(&catalog, &field).map_err(CynosError::from)?;
     let mut root_dependency_tables = root_plan.logical_plan.collect_tables();
     if !root_dependency_tables
         .iter()
@@ -701,7 +1047,7 @@ fn compile_graphql_live_plan(
         &cache_borrow,
         &compiled_plan,
     )
-    .map_err(|error| JsValue::from_str(&alloc::format!("Query execution error: {:?}", error)))?;
+    .map_err(CynosError::from)?;
 
     Ok(LivePlan::graphql_snapshot(
         dependency_set,
@@ -725,7 +1071,9 @@ fn build_graphql_dependency_bindings(
                 .get(table)
                 .copied()
                 .map(|table_id| (table_id, table.clone()))
-                .ok_or_else(|| JsValue::from_str(&alloc::format!("Table ID not found: {}", table)))
+                .ok_or_else(|| {
+                    CynosError::TableNotFound(alloc::format!("Table ID not found: {}", table))
+                })
         })
         .collect::<Result<Vec<_>, _>>()?;
     bindings.sort_unstable_by(|(left_id, left_name), (right_id, right_name)| {
@@ -749,10 +1097,9 @@ fn build_graphql_dependency_set(
     let root_table_ids = root_tables
         .iter()
         .map(|table| {
-            table_id_map
-                .get(table)
-                .copied()
-                .ok_or_else(|| JsValue::from_str(&alloc::format!("Table ID not found: {}", table)))
+            table_id_map.get(table).copied().ok_or_else(|| {
+                CynosError::TableNotFound(alloc::format!("Table ID not found: {}", table))
+            })
         })
         .collect::<Result<Vec<_>, _>>()?;
     Ok(LiveDependencySet::graphql(
@@ -770,26 +1117,29 @@ fn build_graphql_delta_live_plan(
     dependency_table_bindings: Vec<(TableId, String)>,
     root_plan: &cynos_gql::RootFieldPlan,
 ) -> Result<Option<LivePlan>, JsValue> {
-    let store = cache.get_table(&root_plan.table_name).ok_or_else(|| {
-        JsValue::from_str(&alloc::format!("Table not found: {}", root_plan.table_name))
-    })?;
+    let store = cache
+        .get_table(&root_plan.table_name)
+        .ok_or_else(|| CynosError::table_not_found(&root_plan.table_name))?;
 
     let physical_plan = crate::query_engine::compile_plan(
         cache,
         &root_plan.table_name,
         root_plan.logical_plan.clone(),
     );
+    // Windowed root fields need their TopN state seeded from the unlimited
+    // input; they stay on the snapshot path.
+    if top_n_input_plan(&physical_plan).is_some() {
+        return Ok(None);
+    }
     let mut table_schemas = hashbrown::HashMap::new();
     table_schemas.insert(root_plan.table_name.clone(), store.schema().clone());
-    let Some(compile_result) = compile_to_dataflow(&physical_plan, table_id_map, &table_schemas)
+    let Ok(compile_result) = compile_to_dataflow(&physical_plan, table_id_map, &table_schemas)
     else {
         return Ok(None);
     };
 
-    let initial_rows =
-        crate::query_engine::execute_physical_plan(cache, &physical_plan).map_err(|error| {
-            JsValue::from_str(&alloc::format!("Query execution error: {:?}", error))
-        })?;
+    let initial_rows = crate::query_engine::execute_physical_plan(cache, &physical_plan)
+        .map_err(CynosError::from)?;
     let initial_owned = initial_rows.iter().map(|row| (**row).clone()).collect();
 
     Ok(Some(LivePlan::graphql_delta(
@@ -845,6 +1195,27 @@ fn notify_graphql_changes(
 
 #[allow(dead_code)]
 impl Database {
+    /// Recomputes every live query from the current table contents and
+    /// notifies subscribers whose results changed.
+    fn resync_live_queries(&self) {
+        let table_names: hashbrown::HashMap<TableId, String> = self
+            .table_id_map
+            .borrow()
+            .iter()
+            .map(|(name, &id)| (id, name.clone()))
+            .collect();
+        let cache = self.cache.clone();
+        let table_rows = move |table_id: TableId| -> Vec<Row> {
+            let cache = cache.borrow();
+            table_names
+                .get(&table_id)
+                .and_then(|name| cache.get_table(name))
+                .map(|store| store.scan().map(|row| (*row).clone()).collect())
+                .unwrap_or_default()
+        };
+        self.query_registry.borrow_mut().resync(&table_rows);
+    }
+
     /// Gets the internal cache (for internal use).
     pub(crate) fn cache(&self) -> Rc<RefCell<TableCache>> {
         self.cache.clone()
@@ -879,6 +1250,8 @@ mod tests {
     use crate::table::{ColumnOptions, ForeignKeyOptions};
     use crate::JsDataType;
     use cynos_core::{Row, Value};
+    use cynos_query::ast::Expr as AstExpr;
+    use cynos_query::planner::{LogicalPlan, PhysicalPlan};
     use wasm_bindgen_test::*;
 
     wasm_bindgen_test_configure!(run_in_browser);
@@ -1317,6 +1690,532 @@ mod tests {
         assert_eq!(name, "Alice");
     }
 
+    #[wasm_bindgen_test]
+    fn test_observe_row_fires_only_for_watched_row() {
+        let db = setup_graphql_users_db();
+        db.graphql(
+            "mutation { insertUsers(input: [{ id: 1, name: \"Alice\" }, { id: 2, name: \"Bob\" }]) { id } }",
+            None,
+            None,
+        )
+        .unwrap();
+        db.query_registry.borrow_mut().flush();
+
+        let mut observable = db.observe_row("users", &JsValue::from_f64(1.0)).unwrap();
+        assert!(observable.exists());
+
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let received_c = received.clone();
+        let callback = Closure::wrap(Box::new(move |row: JsValue| {
+            received_c.borrow_mut().push(row);
+        }) as Box<dyn FnMut(JsValue)>);
+        observable.subscribe(callback.into_js_value().unchecked_into());
+
+        db.graphql(
+            "mutation { updateUsers(where: { id: { eq: 2 } }, set: { name: \"Bobby\" }) { id } }",
+            None,
+            None,
+        )
+        .unwrap();
+        db.query_registry.borrow_mut().flush();
+        assert!(received.borrow().is_empty());
+
+        db.graphql(
+            "mutation { updateUsers(where: { id: { eq: 1 } }, set: { name: \"Alicia\" }) { id } }",
+            None,
+            None,
+        )
+        .unwrap();
+        db.query_registry.borrow_mut().flush();
+        assert_eq!(received.borrow().len(), 1);
+        let name = js_sys::Reflect::get(&received.borrow()[0], &JsValue::from_str("name"))
+            .unwrap()
+            .as_string()
+            .unwrap();
+        assert_eq!(name, "Alicia");
+
+        db.graphql(
+            "mutation { deleteUsers(where: { id: { eq: 1 } }) { id } }",
+            None,
+            None,
+        )
+        .unwrap();
+        db.query_registry.borrow_mut().flush();
+        assert_eq!(received.borrow().len(), 2);
+        assert!(received.borrow()[1].is_null());
+        assert!(!observable.exists());
+    }
+
+    fn setup_people_db(full_name: &crate::expr::Expr) -> Result<Database, JsValue> {
+        let db = Database::new("defaults");
+        let builder = db
+            .create_table("people")
+            .column(
+                "id",
+                JsDataType::Int64,
+                Some(ColumnOptions::new().set_primary_key(true)),
+            )
+            .column("first", JsDataType::String, None)
+            .column("last", JsDataType::String, None)
+            .column(
+                "full_name",
+                JsDataType::String,
+                Some(ColumnOptions::new().set_default_expr(full_name)),
+            );
+        db.register_table(&builder)?;
+        Ok(db)
+    }
+
+    #[wasm_bindgen_test]
+    fn test_computed_default_concatenates_columns() {
+        let full_name = crate::col("first")
+            .concat(&JsValue::from_str(" "))
+            .concat(&crate::col("last").into());
+        let db = setup_people_db(&full_name).unwrap();
+
+        let mut tx = db.transaction();
+        let values = js_sys::JSON::parse(
+            r#"[{"id": 1, "first": "Ada", "last": "Lovelace"},
+                {"id": 2, "first": "Alan", "last": "Turing", "full_name": "A. M. Turing"}]"#,
+        )
+        .unwrap();
+        tx.insert("people", &values).unwrap();
+        tx.commit().unwrap();
+
+        let cache = db.cache.borrow();
+        let store = cache.get_table("people").unwrap();
+        let full_name_of = |id: i64| store.get_by_pk(&Value::Int64(id))[0].get(3).cloned();
+        assert_eq!(full_name_of(1), Some(Value::String("Ada Lovelace".into())));
+        // An explicit value wins over the default.
+        assert_eq!(full_name_of(2), Some(Value::String("A. M. Turing".into())));
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_declared_default_fills_omitted_columns() {
+        let db = Database::new("declared_defaults");
+        let builder = db
+            .create_table("tasks")
+            .column(
+                "id",
+                JsDataType::Int64,
+                Some(ColumnOptions::new().set_primary_key(true)),
+            )
+            .column(
+                "status",
+                JsDataType::String,
+                Some(ColumnOptions::new().set_default(JsValue::from_str("active"))),
+            )
+            .column("priority", JsDataType::Int32, None);
+        db.register_table(&builder).unwrap();
+
+        let mut tx = db.transaction();
+        let values = js_sys::JSON::parse(
+            r#"[{"id": 1, "priority": 1}, {"id": 2, "priority": 2, "status": "done"}]"#,
+        )
+        .unwrap();
+        tx.insert("tasks", &values).unwrap();
+        // NOT NULL columns without a default, and explicit nulls, still fail.
+        let no_priority = js_sys::JSON::parse(r#"[{"id": 3}]"#).unwrap();
+        assert!(tx.insert("tasks", &no_priority).is_err());
+        let null_status =
+            js_sys::JSON::parse(r#"[{"id": 4, "priority": 1, "status": null}]"#).unwrap();
+        assert!(tx.insert("tasks", &null_status).is_err());
+        tx.commit().unwrap();
+
+        let status_of = |id: i64| {
+            db.cache
+                .borrow()
+                .get_table("tasks")
+                .unwrap()
+                .get_by_pk(&Value::Int64(id))[0]
+                .get(1)
+                .cloned()
+        };
+        assert_eq!(status_of(1), Some(Value::String("active".into())));
+        assert_eq!(status_of(2), Some(Value::String("done".into())));
+
+        // Updates leave the column alone unless asked to reset it.
+        db.update("tasks")
+            .set(&JsValue::from_str("priority"), Some(JsValue::from_f64(5.0)))
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(status_of(2), Some(Value::String("done".into())));
+        db.update("tasks")
+            .set_default("status")
+            .where_(&crate::col("id").eq(&JsValue::from_f64(2.0)))
+            .exec()
+            .await
+            .unwrap();
+        assert_eq!(status_of(2), Some(Value::String("active".into())));
+        assert!(db
+            .update("tasks")
+            .set_default("priority")
+            .exec()
+            .await
+            .is_err());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_update_fails_whole_before_writing() {
+        let full_name = crate::col("first").concat(&crate::col("last").into());
+        let db = Database::new("update_atomic");
+        let builder = db
+            .create_table("people")
+            .column(
+                "id",
+                JsDataType::Int64,
+                Some(ColumnOptions::new().set_primary_key(true)),
+            )
+            .column("first", JsDataType::String, None)
+            .column(
+                "last",
+                JsDataType::String,
+                Some(ColumnOptions::new().set_nullable(true)),
+            )
+            .column(
+                "full_name",
+                JsDataType::String,
+                Some(ColumnOptions::new().set_generated_expr(&full_name)),
+            );
+        db.register_table(&builder).unwrap();
+        let mut tx = db.transaction();
+        let values = js_sys::JSON::parse(
+            r#"[{"id": 1, "first": "Ada", "last": "Lovelace"},
+                {"id": 2, "first": "Alan", "last": null}]"#,
+        )
+        .unwrap();
+        tx.insert("people", &values).unwrap();
+        tx.commit().unwrap();
+
+        // Setting a generated column fails even when no row matches.
+        let no_rows = crate::col("id").eq(&JsValue::from_f64(99.0));
+        assert!(db
+            .update("people")
+            .set(
+                &JsValue::from_str("full_name"),
+                Some(JsValue::from_str("x"))
+            )
+            .where_(&no_rows)
+            .exec()
+            .await
+            .is_err());
+
+        // Row 2 cannot produce a full name, so row 1 is not written either.
+        assert!(db
+            .update("people")
+            .set(&JsValue::from_str("first"), Some(JsValue::from_str("Bo")))
+            .exec()
+            .await
+            .is_err());
+        let first_of = |id: i64| {
+            db.cache
+                .borrow()
+                .get_table("people")
+                .unwrap()
+                .get_by_pk(&Value::Int64(id))[0]
+                .get(1)
+                .cloned()
+        };
+        assert_eq!(first_of(1), Some(Value::String("Ada".into())));
+        assert_eq!(first_of(2), Some(Value::String("Alan".into())));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_check_constraint_rejects_violating_writes() {
+        let db = Database::new("checks");
+        let builder = db
+            .create_table("people")
+            .column(
+                "id",
+                JsDataType::Int64,
+                Some(ColumnOptions::new().set_primary_key(true)),
+            )
+            .column("age", JsDataType::Int32, None)
+            .check(
+                "age_non_negative",
+                &crate::col("age").gte(&JsValue::from_f64(0.0)),
+            );
+        db.register_table(&builder).unwrap();
+
+        let mut tx = db.transaction();
+        let valid = js_sys::JSON::parse(r#"[{"id": 1, "age": 30}]"#).unwrap();
+        tx.insert("people", &valid).unwrap();
+        let negative = js_sys::JSON::parse(r#"[{"id": 2, "age": -1}]"#).unwrap();
+        let error = tx.insert("people", &negative).unwrap_err();
+        let message = js_sys::Reflect::get(&error, &JsValue::from_str("message")).unwrap();
+        assert!(message.as_string().unwrap().contains("age_non_negative"));
+
+        let set_negative = js_sys::JSON::parse(r#"{"age": -5}"#).unwrap();
+        assert!(tx.update("people", &set_negative, None).is_err());
+        tx.commit().unwrap();
+
+        let cache = db.cache.borrow();
+        let store = cache.get_table("people").unwrap();
+        assert_eq!(store.len(), 1);
+        assert_eq!(
+            store.get_by_pk(&Value::Int64(1))[0].get(1),
+            Some(&Value::Int32(30))
+        );
+
+        // Unknown columns fail at registration, not on the first insert.
+        let unknown = db
+            .create_table("pets")
+            .column(
+                "id",
+                JsDataType::Int64,
+                Some(ColumnOptions::new().set_primary_key(true)),
+            )
+            .check("legs", &crate::col("legs").gte(&JsValue::from_f64(0.0)));
+        assert!(db.register_table(&unknown).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_computed_default_rejects_chains() {
+        // full_name reading itself is the shortest chain.
+        let full_name = crate::col("first").concat(&crate::col("full_name").into());
+        assert!(setup_people_db(&full_name).is_err());
+
+        let unknown = crate::col("first").concat(&crate::col("middle").into());
+        assert!(setup_people_db(&unknown).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_computed_default_checks_the_typed_null_type() {
+        let text = crate::expr::Expr::typed_null(JsDataType::String);
+        assert!(setup_people_db(&text).is_ok());
+
+        let bytes = crate::expr::Expr::typed_null(JsDataType::Bytes);
+        assert!(setup_people_db(&bytes).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_generated_column_follows_its_dependencies() {
+        let full_name = crate::col("first")
+            .concat(&JsValue::from_str(" "))
+            .concat(&crate::col("last").into());
+        let db = Database::new("generated");
+        let builder = db
+            .create_table("people")
+            .column(
+                "id",
+                JsDataType::Int64,
+                Some(ColumnOptions::new().set_primary_key(true)),
+            )
+            .column("first", JsDataType::String, None)
+            .column("last", JsDataType::String, None)
+            .column(
+                "full_name",
+                JsDataType::String,
+                Some(ColumnOptions::new().set_generated_expr(&full_name)),
+            )
+            .index("idx_full_name", &JsValue::from_str("full_name"));
+        db.register_table(&builder).unwrap();
+
+        let mut tx = db.transaction();
+        let values = js_sys::JSON::parse(
+            r#"[{"id": 1, "first": "Ada", "last": "Lovelace"},
+                {"id": 2, "first": "Alan", "last": "Turing"}]"#,
+        )
+        .unwrap();
+        tx.insert("people", &values).unwrap();
+        // Generated columns cannot be supplied or set.
+        let explicit =
+            js_sys::JSON::parse(r#"[{"id": 3, "first": "A", "last": "B", "full_name": "AB"}]"#)
+                .unwrap();
+        assert!(tx.insert("people", &explicit).is_err());
+        let set_generated = js_sys::JSON::parse(r#"{"full_name": "Countess"}"#).unwrap();
+        assert!(tx.update("people", &set_generated, None).is_err());
+
+        // Changing a dependency recomputes the column and its index entry.
+        let rename = js_sys::JSON::parse(r#"{"last": "King"}"#).unwrap();
+        let id_is_1 = crate::col("id").eq(&JsValue::from_f64(1.0));
+        tx.update("people", &rename, Some(id_is_1)).unwrap();
+        tx.commit().unwrap();
+
+        let cache = db.cache.borrow();
+        let full_name_of = |id: i64| {
+            cache
+                .get_table("people")
+                .unwrap()
+                .get_by_pk(&Value::Int64(id))[0]
+                .get(3)
+                .cloned()
+        };
+        assert_eq!(full_name_of(1), Some(Value::String("Ada King".into())));
+        assert_eq!(full_name_of(2), Some(Value::String("Alan Turing".into())));
+
+        let by_full_name = |name: &str| {
+            LogicalPlan::filter(
+                LogicalPlan::scan("people"),
+                AstExpr::eq(
+                    AstExpr::column("people", "full_name", 3),
+                    AstExpr::literal(Value::String(name.into())),
+                ),
+            )
+        };
+        assert!(matches!(
+            crate::query_engine::compile_plan(&cache, "people", by_full_name("Ada King")),
+            PhysicalPlan::IndexGet { .. }
+        ));
+        let rows =
+            crate::query_engine::execute_plan(&cache, "people", by_full_name("Ada King")).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert!(
+            crate::query_engine::execute_plan(&cache, "people", by_full_name("Ada Lovelace"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_strict_inserts_reject_unknown_fields() {
+        let full_name = crate::col("first").concat(&crate::col("last").into());
+        let db = setup_people_db(&full_name).unwrap();
+        let values = js_sys::JSON::parse(
+            r#"[{"id": 1, "first": "Ada", "last": "Lovelace", "nmae": "Ada"}]"#,
+        )
+        .unwrap();
+
+        // Lenient by default: the typo'd key is dropped.
+        let mut tx = db.transaction();
+        tx.insert("people", &values).unwrap();
+        tx.rollback().unwrap();
+
+        db.set_strict_inserts(true);
+        let mut tx = db.transaction();
+        let err = tx.insert("people", &values).unwrap_err();
+        let err: js_sys::Error = err.dyn_into().unwrap();
+        let message = String::from(err.message());
+        assert!(message.contains("nmae"), "{message}");
+        assert!(!message.contains("first"), "{message}");
+        assert_eq!(
+            js_sys::Reflect::get(&err, &JsValue::from_str("code"))
+                .unwrap()
+                .as_string()
+                .as_deref(),
+            Some("COLUMN_NOT_FOUND")
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_snapshot_restore_round_trips_database_state() {
+        let db = setup_graphql_users_db();
+        let mutate = |mutation: &str| db.graphql(mutation, None, None).unwrap();
+        mutate(
+            r#"mutation { insertUsers(input: [{ id: 1, name: "Ada" }, { id: 2, name: "Bob" }]) { id } }"#,
+        );
+        let row_ids = |db: &Database| -> Vec<u64> {
+            let cache = db.cache.borrow();
+            cache
+                .get_table("users")
+                .unwrap()
+                .scan()
+                .map(|row| row.id())
+                .collect()
+        };
+
+        let snapshot = db.snapshot();
+        let users_json = db.export_table_json("users").unwrap();
+        let users_row_ids = row_ids(&db);
+        assert_eq!(snapshot.row_count(), 2);
+
+        mutate(r#"mutation { insertUsers(input: [{ id: 3, name: "Cy" }]) { id } }"#);
+        mutate(
+            r#"mutation { updateUsers(where: { id: { eq: 1 } }, set: { name: "Eve" }) { id } }"#,
+        );
+        mutate("mutation { deleteUsers(where: { id: { eq: 2 } }) { id } }");
+        let extra = db.create_table("extra").column(
+            "id",
+            JsDataType::Int64,
+            Some(ColumnOptions::new().set_primary_key(true)),
+        );
+        db.register_table(&extra).unwrap();
+
+        db.restore(&snapshot).unwrap();
+
+        assert_eq!(db.table_count(), 1);
+        assert!(db.table("extra").is_none());
+        assert_eq!(db.export_table_json("users").unwrap(), users_json);
+        assert_eq!(row_ids(&db), users_row_ids);
+        // The primary key index was rebuilt along with the rows.
+        let cache = db.cache.borrow();
+        let users = cache.get_table("users").unwrap();
+        assert_eq!(users.get_by_pk(&Value::Int64(2)).len(), 1);
+        assert!(users.get_by_pk(&Value::Int64(3)).is_empty());
+        drop(cache);
+
+        // The snapshot stays usable, and the restored table accepts writes.
+        mutate(r#"mutation { insertUsers(input: [{ id: 3, name: "Cy" }]) { id } }"#);
+        db.restore(&snapshot).unwrap();
+        assert_eq!(db.export_table_json("users").unwrap(), users_json);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_reactive_disabled_skips_bookkeeping_and_resyncs_on_enable() {
+        let db = setup_graphql_users_db();
+        let subscription = db
+            .subscribe_graphql(
+                "subscription { users(orderBy: [{ field: ID, direction: ASC }]) { id name } }",
+                None,
+                None,
+            )
+            .unwrap();
+        let user_count = |subscription: &JsGraphqlSubscription| {
+            let result = subscription.get_result();
+            let data = js_sys::Reflect::get(&result, &JsValue::from_str("data")).unwrap();
+            js_sys::Array::from(&js_sys::Reflect::get(&data, &JsValue::from_str("users")).unwrap())
+                .length()
+        };
+
+        let mut observable = db.observe_row("users", &JsValue::from_f64(2.0)).unwrap();
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let received_c = received.clone();
+        let callback = Closure::wrap(Box::new(move |row: JsValue| {
+            received_c.borrow_mut().push(row);
+        }) as Box<dyn FnMut(JsValue)>);
+        observable.subscribe(callback.into_js_value().unchecked_into());
+
+        db.set_reactive_enabled(false);
+        assert!(!db.is_reactive_enabled());
+        let users: Vec<String> = (1..=50)
+            .map(|id| alloc::format!("{{ id: {}, name: \"user{}\" }}", id, id))
+            .collect();
+        db.graphql(
+            &alloc::format!(
+                "mutation {{ insertUsers(input: [{}]) {{ id }} }}",
+                users.join(", ")
+            ),
+            None,
+            None,
+        )
+        .unwrap();
+
+        // Nothing was queued for the live queries, so they are behind.
+        assert!(!db.query_registry.borrow().has_pending_changes());
+        db.query_registry.borrow_mut().flush();
+        assert_eq!(user_count(&subscription), 0);
+        assert!(received.borrow().is_empty());
+
+        // Re-enabling recomputes them from the current data.
+        db.set_reactive_enabled(true);
+        assert_eq!(user_count(&subscription), 50);
+        assert_eq!(received.borrow().len(), 1);
+        assert!(observable.exists());
+
+        // Later writes are tracked again.
+        db.graphql(
+            "mutation { deleteUsers(where: { id: { eq: 2 } }) { id } }",
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(db.query_registry.borrow().has_pending_changes());
+        db.query_registry.borrow_mut().flush();
+        assert_eq!(user_count(&subscription), 49);
+        assert_eq!(received.borrow().len(), 2);
+    }
+
     #[wasm_bindgen_test]
     fn test_graphql_live_selector_chooses_delta_for_scalar_root_subscription() {
         let db = setup_graphql_users_db();
@@ -8,21 +8,35 @@
 //!   1. Bootstrap: execute once to get initial result set
 //!   2. Compile: produce DataflowNode graph for incremental maintenance
 //!
-//! Non-incrementalizable operators (Sort, Limit, TopN) cause the compiler
-//! to return None, signaling fallback to re-query strategy.
+//! LIMIT/OFFSET (TopN, Limit, and index scans with a limit) compile to a
+//! bounded TopN window over the unlimited input. A bare Sort has no bounded
+//! incremental form and, like other non-incrementalizable operators, makes
+//! the compiler return a `NotIncrementalizable` naming the offending
+//! operator, signaling fallback to re-query strategy.
+//!
+//! Compiled dataflow nodes hold closures and cannot be serialized. A
+//! `DataflowDefinition` instead keeps the physical plan and table ids a
+//! dataflow was compiled from, serializes with serde, and recompiles into an
+//! equivalent dataflow with `DataflowDefinition::restore`.
 
 use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::fmt;
 use cynos_core::{schema::Table, Row, Value};
 use cynos_incremental::{
-    AggregateType, DataflowNode, JoinType as IvmJoinType, KeyExtractorFn, TableId,
+    AggregateType, DataflowNode, JoinType as IvmJoinType, KeyExtractorFn,
+    SortOrder as IvmSortOrder, TableId,
 };
 use cynos_index::KeyRange;
 use cynos_query::ast::JoinType as QueryJoinType;
-use cynos_query::ast::{AggregateFunc, BinaryOp, Expr, UnaryOp};
+use cynos_query::ast::{AggregateFunc, BinaryOp, Expr, SortOrder, UnaryOp};
+use cynos_query::executor::{eval_bitwise, eval_decimal_arithmetic, eval_floor_div, eval_pow};
 use cynos_query::planner::{IndexBounds, PhysicalPlan};
 use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
 
 /// Result of compiling a PhysicalPlan to a DataflowNode.
 pub struct CompileResult {
@@ -32,6 +46,44 @@ pub struct CompileResult {
     pub table_ids: HashMap<String, TableId>,
 }
 
+/// Explains why a plan cannot be compiled to a dataflow.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NotIncrementalizable {
+    /// The SQL operator responsible, e.g. `"ORDER BY"` or `"UNION"`.
+    pub operator: &'static str,
+    /// What prevents incremental maintenance and how to avoid it.
+    pub reason: String,
+}
+
+impl NotIncrementalizable {
+    fn new(operator: &'static str, reason: impl Into<String>) -> Self {
+        Self {
+            operator,
+            reason: reason.into(),
+        }
+    }
+
+    fn unknown_table(table: &str) -> Self {
+        Self::new("FROM", format!("no schema is known for table {}", table))
+    }
+
+    fn unsupported_index(index: &str) -> Self {
+        Self::new(
+            "WHERE",
+            format!(
+                "the lookup on index {} cannot be expressed as a row filter",
+                index
+            ),
+        )
+    }
+}
+
+impl fmt::Display for NotIncrementalizable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.operator, self.reason)
+    }
+}
+
 #[derive(Clone)]
 struct CompileLayout {
     tables: Vec<String>,
@@ -128,33 +180,72 @@ struct IndexedColumnRef {
 
 /// Compiles a PhysicalPlan into a DataflowNode for IVM.
 ///
-/// Returns None if the plan contains non-incrementalizable operators
-/// (Sort, Limit, TopN), signaling that re-query should be used instead.
+/// Fails if the plan contains non-incrementalizable operators (e.g. a Sort
+/// without a limit), signaling that re-query should be used instead. The
+/// error names the first such operator found.
 pub fn compile_to_dataflow(
     plan: &PhysicalPlan,
     table_id_map: &HashMap<String, TableId>,
     table_schemas: &HashMap<String, Table>,
-) -> Option<CompileResult> {
-    if !plan.is_incrementalizable() {
-        return None;
-    }
-
+) -> Result<CompileResult, NotIncrementalizable> {
     let mut table_ids = table_id_map.clone();
     let compiled = compile_node(plan, &mut table_ids, table_schemas)?;
-    Some(CompileResult {
+    Ok(CompileResult {
         dataflow: compiled.dataflow,
         table_ids,
     })
 }
 
+/// A serializable description of a dataflow: the physical plan it compiles
+/// from and the table ids its sources read.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DataflowDefinition {
+    /// The plan the dataflow is compiled from.
+    pub plan: PhysicalPlan,
+    /// Mapping from table name → table ID used in the dataflow.
+    pub table_ids: BTreeMap<String, TableId>,
+}
+
+impl DataflowDefinition {
+    /// Captures the definition of the dataflow compiled from `plan` against
+    /// `table_ids`, e.g. the `table_ids` of its `CompileResult`.
+    pub fn new(plan: &PhysicalPlan, table_ids: &HashMap<String, TableId>) -> Self {
+        Self {
+            plan: plan.clone(),
+            table_ids: table_ids
+                .iter()
+                .map(|(table, id)| (table.clone(), *id))
+                .collect(),
+        }
+    }
+
+    /// Recompiles the dataflow. Sources keep their recorded table ids, so
+    /// the restored dataflow accepts the same deltas as the original.
+    pub fn restore(
+        &self,
+        table_schemas: &HashMap<String, Table>,
+    ) -> Result<CompileResult, NotIncrementalizable> {
+        let table_ids = self
+            .table_ids
+            .iter()
+            .map(|(table, id)| (table.clone(), *id))
+            .collect();
+        compile_to_dataflow(&self.plan, &table_ids, table_schemas)
+    }
+}
+
 fn compile_source_node(
     table: &str,
     table_ids: &mut HashMap<String, TableId>,
     table_schemas: &HashMap<String, Table>,
-) -> Option<CompiledNode> {
+) -> Result<CompiledNode, NotIncrementalizable> {
     let table_id = get_or_assign_table_id(table, table_ids);
-    let column_count = table_schemas.get(table)?.columns().len();
-    Some(CompiledNode {
+    let column_count = table_schemas
+        .get(table)
+        .ok_or_else(|| NotIncrementalizable::unknown_table(table))?
+        .columns()
+        .len();
+    Ok(CompiledNode {
         dataflow: DataflowNode::source(table_id),
         layout: CompileLayout::table(table, column_count),
     })
@@ -165,12 +256,12 @@ fn compile_filtered_source(
     predicate: Option<Expr>,
     table_ids: &mut HashMap<String, TableId>,
     table_schemas: &HashMap<String, Table>,
-) -> Option<CompiledNode> {
+) -> Result<CompiledNode, NotIncrementalizable> {
     let source = compile_source_node(table, table_ids, table_schemas)?;
     if let Some(predicate) = predicate {
         let bound_predicate = bind_expr_to_layout(&predicate, &source.layout);
         let pred_fn = compile_predicate(&bound_predicate);
-        return Some(CompiledNode {
+        return Ok(CompiledNode {
             dataflow: DataflowNode::Filter {
                 input: Box::new(source.dataflow),
                 predicate: pred_fn,
@@ -179,7 +270,7 @@ fn compile_filtered_source(
         });
     }
 
-    Some(source)
+    Ok(source)
 }
 
 fn lookup_index_columns(
@@ -326,7 +417,7 @@ fn compile_node(
     plan: &PhysicalPlan,
     table_ids: &mut HashMap<String, TableId>,
     table_schemas: &HashMap<String, Table>,
-) -> Option<CompiledNode> {
+) -> Result<CompiledNode, NotIncrementalizable> {
     match plan {
         PhysicalPlan::TableScan { table } => compile_source_node(table, table_ids, table_schemas),
 
@@ -338,12 +429,39 @@ fn compile_node(
             offset,
             reverse,
         } => {
-            if *reverse || limit.is_some() || offset.unwrap_or(0) > 0 {
-                return None;
+            let windowed = limit.is_some() || offset.unwrap_or(0) > 0;
+            if *reverse && !windowed {
+                return Err(NotIncrementalizable::new(
+                    "ORDER BY",
+                    "a descending index scan without LIMIT is an unbounded sort; add a LIMIT",
+                ));
             }
-            let indexed_columns = lookup_index_columns(table_schemas, table, index)?;
-            let predicate = build_index_scan_predicate(table, &indexed_columns, bounds).ok()?;
-            compile_filtered_source(table, predicate, table_ids, table_schemas)
+            let indexed_columns = lookup_index_columns(table_schemas, table, index)
+                .ok_or_else(|| NotIncrementalizable::unsupported_index(index))?;
+            let predicate = build_index_scan_predicate(table, &indexed_columns, bounds)
+                .map_err(|()| NotIncrementalizable::unsupported_index(index))?;
+            let filtered = compile_filtered_source(table, predicate, table_ids, table_schemas)?;
+            if !windowed {
+                return Ok(filtered);
+            }
+
+            // The scan returns rows in index order, so the window is ordered by
+            // the indexed columns.
+            let order = if *reverse {
+                IvmSortOrder::Desc
+            } else {
+                IvmSortOrder::Asc
+            };
+            let order_by = indexed_columns
+                .iter()
+                .map(|column| (column.index, order))
+                .collect();
+            Ok(compile_top_n(
+                filtered,
+                order_by,
+                limit.unwrap_or(usize::MAX),
+                offset.unwrap_or(0),
+            ))
         }
 
         PhysicalPlan::IndexGet {
@@ -352,25 +470,24 @@ fn compile_node(
             key,
             limit,
         } => {
-            if limit.is_some() {
-                return None;
-            }
-            let indexed_columns = lookup_index_columns(table_schemas, table, index)?;
-            if indexed_columns.len() != 1 {
-                return None;
-            }
+            let indexed_columns = lookup_index_columns(table_schemas, table, index)
+                .filter(|columns| columns.len() == 1)
+                .ok_or_else(|| NotIncrementalizable::unsupported_index(index))?;
             let predicate = Some(Expr::eq(
                 column_expr(table, &indexed_columns[0]),
                 Expr::Literal(key.clone()),
             ));
-            compile_filtered_source(table, predicate, table_ids, table_schemas)
+            let filtered = compile_filtered_source(table, predicate, table_ids, table_schemas)?;
+            match limit {
+                Some(limit) => Ok(compile_top_n(filtered, Vec::new(), *limit, 0)),
+                None => Ok(filtered),
+            }
         }
 
         PhysicalPlan::IndexInGet { table, index, keys } => {
-            let indexed_columns = lookup_index_columns(table_schemas, table, index)?;
-            if indexed_columns.len() != 1 {
-                return None;
-            }
+            let indexed_columns = lookup_index_columns(table_schemas, table, index)
+                .filter(|columns| columns.len() == 1)
+                .ok_or_else(|| NotIncrementalizable::unsupported_index(index))?;
             let predicate = Some(Expr::In {
                 expr: Box::new(column_expr(table, &indexed_columns[0])),
                 list: keys.iter().cloned().map(Expr::Literal).collect(),
@@ -378,16 +495,34 @@ fn compile_node(
             compile_filtered_source(table, predicate, table_ids, table_schemas)
         }
 
-        PhysicalPlan::GinIndexScan { table, recheck, .. }
-        | PhysicalPlan::GinIndexScanMulti { table, recheck, .. } => {
-            compile_filtered_source(table, Some(recheck.clone()?), table_ids, table_schemas)
+        PhysicalPlan::FetchByIds { .. } => Err(NotIncrementalizable::new(
+            "WHERE",
+            "a row id lookup cannot be expressed as a row filter; use observe() instead",
+        )),
+
+        PhysicalPlan::GinIndexScan {
+            table,
+            index,
+            recheck,
+            ..
+        }
+        | PhysicalPlan::GinIndexScanMulti {
+            table,
+            index,
+            recheck,
+            ..
+        } => {
+            let recheck = recheck
+                .clone()
+                .ok_or_else(|| NotIncrementalizable::unsupported_index(index))?;
+            compile_filtered_source(table, Some(recheck), table_ids, table_schemas)
         }
 
         PhysicalPlan::Filter { input, predicate } => {
             let input_node = compile_node(input, table_ids, table_schemas)?;
             let bound_predicate = bind_expr_to_layout(predicate, &input_node.layout);
             let pred_fn = compile_predicate(&bound_predicate);
-            Some(CompiledNode {
+            Ok(CompiledNode {
                 dataflow: DataflowNode::Filter {
                     input: Box::new(input_node.dataflow),
                     predicate: pred_fn,
@@ -410,14 +545,14 @@ fn compile_node(
 
             if col_indices.len() == columns.len() {
                 // Pure column projection — use Project node
-                Some(CompiledNode {
+                Ok(CompiledNode {
                     dataflow: DataflowNode::project(input_node.dataflow, col_indices),
                     layout: input_node.layout.projected(columns.len()),
                 })
             } else {
                 // Has computed expressions — use Map node
                 let exprs = bound_columns;
-                Some(CompiledNode {
+                Ok(CompiledNode {
                     dataflow: DataflowNode::Map {
                         input: Box::new(input_node.dataflow),
                         mapper: Box::new(move |row: &Row| {
@@ -438,6 +573,7 @@ fn compile_node(
             condition,
             join_type,
             output_tables,
+            ..
         }
         | PhysicalPlan::SortMergeJoin {
             left,
@@ -452,10 +588,11 @@ fn compile_node(
             condition,
             join_type,
             output_tables,
+            ..
         } => {
             let left_node = compile_node(left, table_ids, table_schemas)?;
             let right_node = compile_node(right, table_ids, table_schemas)?;
-            let ivm_join_type = convert_join_type(join_type);
+            let ivm_join_type = convert_join_type(join_type)?;
             let (left_key, right_key) =
                 extract_join_keys(condition, &left_node.layout, &right_node.layout);
             let raw_layout = CompileLayout::combined(&left_node.layout, &right_node.layout);
@@ -466,7 +603,7 @@ fn compile_node(
                 right_key,
                 join_type: ivm_join_type,
             };
-            Some(reorder_join_output(join_node, raw_layout, output_tables))
+            Ok(reorder_join_output(join_node, raw_layout, output_tables))
         }
 
         PhysicalPlan::IndexNestedLoopJoin {
@@ -480,13 +617,17 @@ fn compile_node(
         } => {
             let outer_node = compile_node(outer, table_ids, table_schemas)?;
             let inner_table_id = get_or_assign_table_id(inner_table, table_ids);
-            let inner_column_count = table_schemas.get(inner_table)?.columns().len();
+            let inner_column_count = table_schemas
+                .get(inner_table)
+                .ok_or_else(|| NotIncrementalizable::unknown_table(inner_table))?
+                .columns()
+                .len();
             let inner_layout = CompileLayout::table(inner_table, inner_column_count);
             let inner_node = CompiledNode {
                 dataflow: DataflowNode::source(inner_table_id),
                 layout: inner_layout,
             };
-            let ivm_join_type = convert_join_type(join_type);
+            let ivm_join_type = convert_join_type(join_type)?;
             let (left_node, right_node) = if *outer_is_left {
                 (outer_node, inner_node)
             } else {
@@ -504,7 +645,7 @@ fn compile_node(
                 join_type: ivm_join_type,
             };
 
-            Some(reorder_join_output(join_node, raw_layout, output_tables))
+            Ok(reorder_join_output(join_node, raw_layout, output_tables))
         }
 
         PhysicalPlan::CrossProduct { left, right } => {
@@ -512,7 +653,7 @@ fn compile_node(
             let right_node = compile_node(right, table_ids, table_schemas)?;
             let raw_layout = CompileLayout::combined(&left_node.layout, &right_node.layout);
             // Cross product = join with constant key (everything matches)
-            Some(CompiledNode {
+            Ok(CompiledNode {
                 dataflow: DataflowNode::Join {
                     left: Box::new(left_node.dataflow),
                     right: Box::new(right_node.dataflow),
@@ -543,22 +684,35 @@ fn compile_node(
                 .iter()
                 .filter_map(|expr| extract_column_index(expr))
                 .collect();
+            if group_by_indices.len() != bound_group_by.len() {
+                return Err(NotIncrementalizable::new(
+                    "GROUP BY",
+                    "grouping by a computed expression is not maintained incrementally; use observe() instead",
+                ));
+            }
 
-            let functions: Vec<(usize, AggregateType)> = bound_aggregates
-                .iter()
-                .filter_map(|(func, expr)| {
-                    let col_idx = match expr {
-                        Expr::Aggregate {
-                            expr: Some(inner), ..
-                        } => extract_column_index(inner),
-                        Expr::Column(col_ref) => Some(col_ref.index),
-                        _ => Some(0), // COUNT(*) uses column 0
-                    };
-                    col_idx.map(|idx| (idx, convert_aggregate_func(func)))
-                })
-                .collect();
+            let mut functions: Vec<(usize, AggregateType)> =
+                Vec::with_capacity(bound_aggregates.len());
+            for (func, expr) in &bound_aggregates {
+                let aggregate_type = convert_aggregate_func(func).ok_or_else(|| {
+                    NotIncrementalizable::new(
+                        aggregate_operator(func),
+                        "this aggregate has no incremental form; use observe() instead",
+                    )
+                })?;
+                let col_idx = match expr {
+                    Expr::Aggregate {
+                        expr: Some(inner), ..
+                    } => extract_column_index(inner),
+                    Expr::Column(col_ref) => Some(col_ref.index),
+                    _ => Some(0), // COUNT(*) uses column 0
+                };
+                if let Some(idx) = col_idx {
+                    functions.push((idx, aggregate_type));
+                }
+            }
 
-            Some(CompiledNode {
+            Ok(CompiledNode {
                 dataflow: DataflowNode::Aggregate {
                     input: Box::new(input_node.dataflow),
                     group_by: group_by_indices,
@@ -571,7 +725,7 @@ fn compile_node(
         }
 
         PhysicalPlan::NoOp { input } => compile_node(input, table_ids, table_schemas),
-        PhysicalPlan::Empty => Some(CompiledNode {
+        PhysicalPlan::Empty => Ok(CompiledNode {
             dataflow: DataflowNode::source(u32::MAX),
             layout: CompileLayout {
                 tables: Vec::new(),
@@ -579,11 +733,123 @@ fn compile_node(
             },
         }),
 
-        // Non-incrementalizable — should have been caught by is_incrementalizable()
-        PhysicalPlan::Sort { .. }
-        | PhysicalPlan::Limit { .. }
-        | PhysicalPlan::TopN { .. }
-        | PhysicalPlan::Union { .. } => None,
+        PhysicalPlan::TopN {
+            input,
+            order_by,
+            limit,
+            offset,
+        } => {
+            let input_node = compile_node(input, table_ids, table_schemas)?;
+            let order_by = bind_order_by(order_by, &input_node.layout)?;
+            Ok(compile_top_n(input_node, order_by, *limit, *offset))
+        }
+
+        PhysicalPlan::Limit {
+            input,
+            limit,
+            offset,
+        } => {
+            // LIMIT over a Sort keeps the sort order; a bare LIMIT has no defined
+            // order, so the window falls back to row ID order.
+            let (input, order_by) = match input.as_ref() {
+                PhysicalPlan::Sort { input, order_by } => (input.as_ref(), order_by.as_slice()),
+                other => (other, &[][..]),
+            };
+            let input_node = compile_node(input, table_ids, table_schemas)?;
+            let order_by = bind_order_by(order_by, &input_node.layout)?;
+            Ok(compile_top_n(input_node, order_by, *limit, *offset))
+        }
+
+        PhysicalPlan::Sort { .. } => Err(NotIncrementalizable::new(
+            "ORDER BY",
+            "ORDER BY without LIMIT has no bounded incremental form; add a LIMIT or use observe()",
+        )),
+        PhysicalPlan::Union { .. } => Err(NotIncrementalizable::new(
+            "UNION",
+            "UNION is not maintained incrementally; use observe() instead",
+        )),
+    }
+}
+
+/// Resolves ORDER BY expressions to column indices of the input layout.
+/// Fails if any sort key is not a plain column.
+fn bind_order_by(
+    order_by: &[(Expr, SortOrder)],
+    layout: &CompileLayout,
+) -> Result<Vec<(usize, IvmSortOrder)>, NotIncrementalizable> {
+    order_by
+        .iter()
+        .map(|(expr, order)| {
+            let index = extract_column_index(&bind_expr_to_layout(expr, layout)).ok_or_else(|| {
+                NotIncrementalizable::new(
+                    "LIMIT",
+                    "the LIMIT window can only be ordered by plain columns, not computed expressions",
+                )
+            })?;
+            let order = match order {
+                SortOrder::Asc => IvmSortOrder::Asc,
+                SortOrder::Desc => IvmSortOrder::Desc,
+            };
+            Ok((index, order))
+        })
+        .collect()
+}
+
+fn compile_top_n(
+    input: CompiledNode,
+    order_by: Vec<(usize, IvmSortOrder)>,
+    limit: usize,
+    offset: usize,
+) -> CompiledNode {
+    CompiledNode {
+        dataflow: DataflowNode::top_n(input.dataflow, order_by, limit, offset),
+        layout: input.layout,
+    }
+}
+
+/// Returns the plan producing the full input of the outermost LIMIT window,
+/// i.e. the plan with its LIMIT/OFFSET removed.
+///
+/// The TopN state of an IVM view needs every candidate row, not just the
+/// rows inside the window, so that it can backfill the window on deletes.
+/// Returns None if the plan has no outermost window.
+pub fn top_n_input_plan(plan: &PhysicalPlan) -> Option<PhysicalPlan> {
+    match plan {
+        PhysicalPlan::Project { input, .. } | PhysicalPlan::NoOp { input } => {
+            top_n_input_plan(input)
+        }
+        PhysicalPlan::TopN { input, .. } => Some(input.as_ref().clone()),
+        PhysicalPlan::Limit { input, .. } => match input.as_ref() {
+            PhysicalPlan::Sort { input, .. } => Some(input.as_ref().clone()),
+            other => Some(other.clone()),
+        },
+        PhysicalPlan::IndexScan {
+            table,
+            index,
+            bounds,
+            limit,
+            offset,
+            ..
+        } if limit.is_some() || offset.unwrap_or(0) > 0 => Some(PhysicalPlan::IndexScan {
+            table: table.clone(),
+            index: index.clone(),
+            bounds: bounds.clone(),
+            limit: None,
+            offset: None,
+            reverse: false,
+        }),
+        PhysicalPlan::IndexGet {
+            table,
+            index,
+            key,
+            limit: Some(_),
+        } => Some(PhysicalPlan::IndexGet {
+            table: table.clone(),
+            index: index.clone(),
+            key: key.clone(),
+            limit: None,
+        }),
+        _ => None,
     }
 }
 
@@ -592,12 +858,19 @@ fn compile_node(
 // ---------------------------------------------------------------------------
 
 /// Compiles an Expr predicate into a closure for DataflowNode::Filter.
+///
+/// Predicates follow SQL three-valued logic, as in the batch executor: NULL
+/// operands propagate through comparisons, AND/OR/NOT treat NULL as
+/// "unknown", and a row is only kept when the predicate is TRUE. A NULL (or
+/// non-boolean) result excludes the row.
 fn compile_predicate(expr: &Expr) -> Box<dyn Fn(&Row) -> bool + Send + Sync> {
     let expr = expr.clone();
-    Box::new(move |row: &Row| match eval_expr(&expr, row) {
-        Value::Boolean(b) => b,
-        _ => false,
-    })
+    Box::new(move |row: &Row| predicate_holds(&eval_expr(&expr, row)))
+}
+
+/// Returns true only for a TRUE predicate result; FALSE and NULL exclude the row.
+fn predicate_holds(value: &Value) -> bool {
+    matches!(value, Value::Boolean(true))
 }
 
 /// Evaluates an expression against a row.
@@ -614,6 +887,9 @@ fn eval_expr(expr: &Expr, row: &Row) -> Value {
             let val = eval_expr(inner, row);
             eval_unary_op(op, &val)
         }
+        Expr::Cast { expr, target } => eval_expr(expr, row)
+            .coerce_to(*target)
+            .unwrap_or(Value::Null),
         Expr::In { expr, list } => {
             let val = eval_expr(expr, row);
             let found = list.iter().any(|item| eval_expr(item, row) == val);
@@ -674,9 +950,34 @@ fn eval_expr(expr: &Expr, row: &Row) -> Value {
 }
 
 fn eval_binary_op(left: &Value, op: &BinaryOp, right: &Value) -> Value {
+    // NULL propagation, matching the batch executor.
+    if left.is_null() || right.is_null() {
+        return match op {
+            // NULL AND FALSE = FALSE, NULL AND TRUE = NULL
+            BinaryOp::And
+                if matches!(left, Value::Boolean(false))
+                    || matches!(right, Value::Boolean(false)) =>
+            {
+                Value::Boolean(false)
+            }
+            // NULL OR TRUE = TRUE, NULL OR FALSE = NULL
+            BinaryOp::Or
+                if matches!(left, Value::Boolean(true))
+                    || matches!(right, Value::Boolean(true)) =>
+            {
+                Value::Boolean(true)
+            }
+            BinaryOp::IsNotDistinctFrom => Value::Boolean(left.is_null() && right.is_null()),
+            _ => Value::Null,
+        };
+    }
+    if let Some(result) = eval_decimal_arithmetic(*op, left, right) {
+        return result;
+    }
+
     match op {
-        BinaryOp::Eq => Value::Boolean(left == right),
-        BinaryOp::Ne => Value::Boolean(left != right),
+        BinaryOp::Eq | BinaryOp::IsNotDistinctFrom => Value::Boolean(left.sql_eq(right)),
+        BinaryOp::Ne => Value::Boolean(!left.sql_eq(right)),
         BinaryOp::Lt => Value::Boolean(left < right),
         BinaryOp::Le => Value::Boolean(left <= right),
         BinaryOp::Gt => Value::Boolean(left > right),
@@ -694,8 +995,21 @@ fn eval_binary_op(left: &Value, op: &BinaryOp, right: &Value) -> Value {
         BinaryOp::Add => numeric_op(left, right, |a, b| a + b),
         BinaryOp::Sub => numeric_op(left, right, |a, b| a - b),
         BinaryOp::Mul => numeric_op(left, right, |a, b| a * b),
-        BinaryOp::Div => numeric_op(left, right, |a, b| if b != 0.0 { a / b } else { 0.0 }),
-        BinaryOp::Mod => numeric_op(left, right, |a, b| if b != 0.0 { a % b } else { 0.0 }),
+        BinaryOp::Div => match right {
+            Value::Int32(0) | Value::Int64(0) => Value::Null,
+            Value::Float64(f) if *f == 0.0 => Value::Null,
+            _ => numeric_op(left, right, |a, b| a / b),
+        },
+        BinaryOp::Mod => match right {
+            Value::Int32(0) | Value::Int64(0) => Value::Null,
+            Value::Float64(f) if *f == 0.0 => Value::Null,
+            _ => numeric_op(left, right, |a, b| a % b),
+        },
+        BinaryOp::Pow => eval_pow(left, right),
+        BinaryOp::FloorDiv => eval_floor_div(left, right),
+        BinaryOp::BitAnd | BinaryOp::BitOr | BinaryOp::BitXor | BinaryOp::Shl | BinaryOp::Shr => {
+            eval_bitwise(*op, left, right)
+        }
         _ => Value::Null,
     }
 }
@@ -710,6 +1024,7 @@ fn eval_unary_op(op: &UnaryOp, val: &Value) -> Value {
             Value::Int32(v) => Value::Int32(-v),
             Value::Int64(v) => Value::Int64(-v),
             Value::Float64(v) => Value::Float64(-v),
+            Value::Decimal(v) => v.checked_neg().map_or(Value::Null, Value::Decimal),
             _ => Value::Null,
         },
         UnaryOp::IsNull => Value::Boolean(matches!(val, Value::Null)),
@@ -818,7 +1133,7 @@ fn collect_equi_join_keys(
     match expr {
         Expr::BinaryOp {
             left,
-            op: BinaryOp::Eq,
+            op: BinaryOp::Eq | BinaryOp::IsNotDistinctFrom,
             right,
         } => {
             if let (Some(left_col), Some(right_col)) =
@@ -871,6 +1186,7 @@ fn bind_expr_to_layout(expr: &Expr, layout: &CompileLayout) -> Expr {
             layout.resolve_column_index(&col_ref.table, col_ref.index),
         ),
         Expr::Literal(value) => Expr::Literal(value.clone()),
+        Expr::TypedNull(data_type) => Expr::TypedNull(*data_type),
         Expr::BinaryOp { left, op, right } => Expr::BinaryOp {
             left: Box::new(bind_expr_to_layout(left, layout)),
             op: *op,
@@ -887,6 +1203,10 @@ fn bind_expr_to_layout(expr: &Expr, layout: &CompileLayout) -> Expr {
                 .map(|arg| bind_expr_to_layout(arg, layout))
                 .collect(),
         },
+        Expr::Cast { expr, target } => Expr::Cast {
+            expr: Box::new(bind_expr_to_layout(expr, layout)),
+            target: *target,
+        },
         Expr::Aggregate {
             func,
             expr,
@@ -960,24 +1280,55 @@ fn get_or_assign_table_id(table: &str, table_ids: &mut HashMap<String, TableId>)
     *table_ids.entry(table.into()).or_insert(next_id)
 }
 
-fn convert_join_type(jt: &QueryJoinType) -> IvmJoinType {
-    match jt {
+fn convert_join_type(jt: &QueryJoinType) -> Result<IvmJoinType, NotIncrementalizable> {
+    Ok(match jt {
         QueryJoinType::Inner | QueryJoinType::Cross => IvmJoinType::Inner,
         QueryJoinType::LeftOuter => IvmJoinType::LeftOuter,
         QueryJoinType::RightOuter => IvmJoinType::RightOuter,
         QueryJoinType::FullOuter => IvmJoinType::FullOuter,
-    }
+        QueryJoinType::Semi => {
+            return Err(NotIncrementalizable::new(
+                "EXISTS",
+                "semi-joins have no incremental form yet; use observe()",
+            ))
+        }
+        QueryJoinType::Anti => {
+            return Err(NotIncrementalizable::new(
+                "NOT EXISTS",
+                "anti-joins have no incremental form yet; use observe()",
+            ))
+        }
+    })
 }
 
-fn convert_aggregate_func(func: &AggregateFunc) -> AggregateType {
-    match func {
+/// Returns `None` for aggregates the dataflow cannot maintain.
+fn convert_aggregate_func(func: &AggregateFunc) -> Option<AggregateType> {
+    Some(match func {
         AggregateFunc::Count => AggregateType::Count,
         AggregateFunc::Sum => AggregateType::Sum,
         AggregateFunc::Avg => AggregateType::Avg,
         AggregateFunc::Min => AggregateType::Min,
         AggregateFunc::Max => AggregateType::Max,
-        // Unsupported aggregates fall back to Count
-        _ => AggregateType::Count,
+        AggregateFunc::Distinct => AggregateType::CountDistinct,
+        // ARG_MIN/ARG_MAX return a second column the dataflow does not track.
+        AggregateFunc::ArgMin | AggregateFunc::ArgMax => return None,
+        AggregateFunc::StdDev | AggregateFunc::GeoMean => return None,
+    })
+}
+
+/// Returns the SQL name of an aggregate, for diagnostics.
+fn aggregate_operator(func: &AggregateFunc) -> &'static str {
+    match func {
+        AggregateFunc::Count => "COUNT",
+        AggregateFunc::Sum => "SUM",
+        AggregateFunc::Avg => "AVG",
+        AggregateFunc::Min => "MIN",
+        AggregateFunc::Max => "MAX",
+        AggregateFunc::Distinct => "DISTINCT",
+        AggregateFunc::StdDev => "STDDEV",
+        AggregateFunc::GeoMean => "GEOMEAN",
+        AggregateFunc::ArgMin => "ARG_MIN",
+        AggregateFunc::ArgMax => "ARG_MAX",
     }
 }
 
@@ -1098,7 +1449,195 @@ mod tests {
         let mut table_ids = HashMap::new();
         table_ids.insert("users".into(), 1u32);
 
-        assert!(compile_to_dataflow(&plan, &table_ids, &table_schemas).is_none());
+        let error = compile_to_dataflow(&plan, &table_ids, &table_schemas)
+            .err()
+            .unwrap();
+        assert_eq!(error.operator, "ORDER BY");
+    }
+
+    #[test]
+    fn test_compile_top_n_maintains_bounded_window() {
+        use cynos_incremental::{Delta, MaterializedView};
+        use cynos_query::ast::SortOrder;
+
+        let plan = PhysicalPlan::top_n(
+            PhysicalPlan::filter(
+                PhysicalPlan::table_scan("events"),
+                Expr::gt(Expr::column("events", "ts", 1), Expr::literal(0i64)),
+            ),
+            vec![(Expr::column("events", "ts", 1), SortOrder::Desc)],
+            2,
+            0,
+        );
+        let mut table_ids = HashMap::new();
+        table_ids.insert("events".into(), 1u32);
+        let table_schemas = table_schemas(&[("events", &["id", "ts"])]);
+
+        let result = compile_to_dataflow(&plan, &table_ids, &table_schemas).unwrap();
+        assert!(matches!(
+            &result.dataflow,
+            DataflowNode::TopN {
+                limit: 2,
+                offset: 0,
+                ..
+            }
+        ));
+        assert!(matches!(
+            top_n_input_plan(&plan),
+            Some(PhysicalPlan::Filter { .. })
+        ));
+
+        let event =
+            |id: u64, ts: i64| Row::new(id, vec![Value::Int64(id as i64), Value::Int64(ts)]);
+        let mut view =
+            MaterializedView::with_initial(result.dataflow, vec![event(1, 30), event(2, 20)]);
+        view.initialize_top_n_state(vec![event(1, 30), event(2, 20), event(3, 10)]);
+
+        // A newer event evicts the boundary row.
+        let output = view.on_table_change(1, vec![Delta::insert(event(4, 40))]);
+        assert!(output.contains(&Delta::insert(event(4, 40))));
+        assert!(output.contains(&Delta::delete(event(2, 20))));
+
+        // Deleting from the window backfills it with the next row in order.
+        let output = view.on_table_change(1, vec![Delta::delete(event(4, 40))]);
+        assert!(output.contains(&Delta::delete(event(4, 40))));
+        assert!(output.contains(&Delta::insert(event(2, 20))));
+        let ids: Vec<u64> = view.result().iter().map(|row| row.id()).collect();
+        assert_eq!(ids, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_compile_index_scan_with_limit_lowers_to_top_n() {
+        let users = TableBuilder::new("users")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_primary_key(&["id"], false)
+            .unwrap()
+            .build()
+            .unwrap();
+        let pk_name = users.primary_key().unwrap().name().to_string();
+        let mut table_schemas = HashMap::new();
+        table_schemas.insert("users".into(), users);
+
+        let plan = PhysicalPlan::IndexScan {
+            table: "users".into(),
+            index: pk_name,
+            bounds: IndexBounds::Unbounded,
+            limit: Some(5),
+            offset: None,
+            reverse: true,
+        };
+        let mut table_ids = HashMap::new();
+        table_ids.insert("users".into(), 1u32);
+
+        let result = compile_to_dataflow(&plan, &table_ids, &table_schemas).unwrap();
+        match &result.dataflow {
+            DataflowNode::TopN {
+                order_by, limit, ..
+            } => {
+                assert_eq!(order_by, &vec![(0, IvmSortOrder::Desc)]);
+                assert_eq!(*limit, 5);
+            }
+            _ => panic!("Expected TopN node"),
+        }
+        assert!(matches!(
+            top_n_input_plan(&plan),
+            Some(PhysicalPlan::IndexScan {
+                limit: None,
+                reverse: false,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_filter_null_semantics_match_batch_executor() {
+        use crate::query_engine::execute_physical_plan;
+        use cynos_incremental::{Delta, MaterializedView};
+        use cynos_storage::TableCache;
+
+        let scores = TableBuilder::new("scores")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("score", DataType::Int64)
+            .unwrap()
+            .add_nullable(&["score"])
+            .add_primary_key(&["id"], false)
+            .unwrap()
+            .build()
+            .unwrap();
+        let rows = vec![
+            Row::new(1, vec![Value::Int64(1), Value::Int64(3)]),
+            Row::new(2, vec![Value::Int64(2), Value::Null]),
+            Row::new(3, vec![Value::Int64(3), Value::Int64(8)]),
+        ];
+
+        let mut cache = TableCache::new();
+        cache.create_table(scores.clone()).unwrap();
+        for row in &rows {
+            cache
+                .get_table_mut("scores")
+                .unwrap()
+                .insert(row.clone())
+                .unwrap();
+        }
+        let mut table_schemas = HashMap::new();
+        table_schemas.insert("scores".into(), scores);
+        let mut table_ids = HashMap::new();
+        table_ids.insert("scores".into(), 1u32);
+
+        let score = || Expr::column("scores", "score", 1);
+        let is_second = || Expr::eq(Expr::column("scores", "id", 0), Expr::literal(2i64));
+        // (predicate, whether the row with the NULL score matches)
+        let predicates = vec![
+            (Expr::lt(score(), Expr::literal(5i64)), false),
+            (Expr::ne(score(), Expr::literal(3i64)), false),
+            (Expr::not(Expr::gt(score(), Expr::literal(5i64))), false),
+            // NULL OR TRUE = TRUE
+            (
+                Expr::or(Expr::lt(score(), Expr::literal(5i64)), is_second()),
+                true,
+            ),
+            // NULL AND TRUE = NULL
+            (
+                Expr::and(Expr::lt(score(), Expr::literal(5i64)), is_second()),
+                false,
+            ),
+            // x % 0 is NULL, so no row matches
+            (
+                Expr::eq(
+                    Expr::modulo(score(), Expr::literal(0i64)),
+                    Expr::literal(0i64),
+                ),
+                false,
+            ),
+        ];
+
+        for (predicate, null_row_matches) in predicates {
+            let plan = PhysicalPlan::filter(PhysicalPlan::table_scan("scores"), predicate.clone());
+            let mut expected: Vec<u64> = execute_physical_plan(&cache, &plan)
+                .unwrap()
+                .iter()
+                .map(|row| row.id())
+                .collect();
+            expected.sort();
+
+            let result = compile_to_dataflow(&plan, &table_ids, &table_schemas).unwrap();
+            let mut view = MaterializedView::new(result.dataflow);
+            view.on_table_change(1, rows.iter().cloned().map(Delta::insert).collect());
+            let mut actual: Vec<u64> = view.result().iter().map(|row| row.id()).collect();
+            actual.sort();
+
+            assert_eq!(actual, expected, "predicate: {:?}", predicate);
+            assert_eq!(
+                actual.contains(&2),
+                null_row_matches,
+                "predicate: {:?}",
+                predicate
+            );
+        }
     }
 
     #[test]
@@ -1112,7 +1651,58 @@ mod tests {
         );
         let table_ids = HashMap::new();
         let table_schemas = table_schemas(&[("users", &["id"])]);
-        assert!(compile_to_dataflow(&plan, &table_ids, &table_schemas).is_none());
+        let error = compile_to_dataflow(&plan, &table_ids, &table_schemas)
+            .err()
+            .unwrap();
+        assert_eq!(error.operator, "ORDER BY");
+        assert_eq!(
+            error.to_string(),
+            "ORDER BY: ORDER BY without LIMIT has no bounded incremental form; add a LIMIT or use observe()"
+        );
+    }
+
+    #[test]
+    fn test_limit_ordered_by_expression_is_not_incrementalizable() {
+        let plan = PhysicalPlan::limit(
+            PhysicalPlan::sort(
+                PhysicalPlan::table_scan("users"),
+                alloc::vec![(
+                    Expr::BinaryOp {
+                        left: Box::new(Expr::column("users", "id", 0)),
+                        op: BinaryOp::Add,
+                        right: Box::new(Expr::literal(1i64)),
+                    },
+                    cynos_query::ast::SortOrder::Asc
+                )],
+            ),
+            10,
+            0,
+        );
+        let table_ids = HashMap::new();
+        let table_schemas = table_schemas(&[("users", &["id"])]);
+        let error = compile_to_dataflow(&plan, &table_ids, &table_schemas)
+            .err()
+            .unwrap();
+        assert_eq!(error.operator, "LIMIT");
+        assert_eq!(
+            error.reason,
+            "the LIMIT window can only be ordered by plain columns, not computed expressions"
+        );
+    }
+
+    #[test]
+    fn test_unsupported_aggregate_is_named() {
+        let plan = PhysicalPlan::hash_aggregate(
+            PhysicalPlan::table_scan("users"),
+            Vec::new(),
+            alloc::vec![(AggregateFunc::StdDev, Expr::column("users", "id", 0))],
+        );
+        let table_ids = HashMap::new();
+        let table_schemas = table_schemas(&[("users", &["id"])]);
+        let error = compile_to_dataflow(&plan, &table_ids, &table_schemas)
+            .err()
+            .unwrap();
+        assert_eq!(error.operator, "STDDEV");
     }
 
     #[test]
@@ -1144,6 +1734,178 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_two_source_join_tracks_changes_on_both_sides() {
+        use crate::query_engine::execute_physical_plan;
+        use cynos_incremental::{Delta, MaterializedView};
+        use cynos_query::ast::JoinType;
+        use cynos_storage::TableCache;
+
+        let employees = TableBuilder::new("employees")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("dept_id", DataType::Int64)
+            .unwrap()
+            .add_primary_key(&["id"], false)
+            .unwrap()
+            .build()
+            .unwrap();
+        let departments = TableBuilder::new("departments")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("name", DataType::String)
+            .unwrap()
+            .add_primary_key(&["id"], false)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut cache = TableCache::new();
+        cache.create_table(employees.clone()).unwrap();
+        cache.create_table(departments.clone()).unwrap();
+        let mut table_schemas = HashMap::new();
+        table_schemas.insert("employees".into(), employees);
+        table_schemas.insert("departments".into(), departments);
+        let mut table_ids = HashMap::new();
+        table_ids.insert("employees".into(), 1u32);
+        table_ids.insert("departments".into(), 2u32);
+
+        let plan = PhysicalPlan::hash_join(
+            PhysicalPlan::table_scan("employees"),
+            PhysicalPlan::table_scan("departments"),
+            Expr::eq(
+                Expr::column("employees", "dept_id", 1),
+                Expr::column("departments", "id", 0),
+            ),
+            JoinType::Inner,
+        );
+        let result = compile_to_dataflow(&plan, &table_ids, &table_schemas).unwrap();
+        let mut view = MaterializedView::new(result.dataflow);
+        assert!(view.depends_on(1) && view.depends_on(2));
+
+        let employee = |id: i64, dept_id: i64| {
+            Row::new(id as u64, vec![Value::Int64(id), Value::Int64(dept_id)])
+        };
+        let department = |id: i64, name: &str| {
+            Row::new(
+                100 + id as u64,
+                vec![Value::Int64(id), Value::String(name.into())],
+            )
+        };
+        let sorted_values = |rows: Vec<Vec<Value>>| {
+            let mut rows = rows;
+            rows.sort();
+            rows
+        };
+
+        // (table, table id, delta), applied in order to both the table and the view.
+        let changes = vec![
+            ("employees", 1u32, Delta::insert(employee(1, 10))),
+            ("departments", 2u32, Delta::insert(department(10, "eng"))),
+            ("employees", 1u32, Delta::insert(employee(2, 10))),
+            ("departments", 2u32, Delta::insert(department(20, "ops"))),
+            ("employees", 1u32, Delta::insert(employee(3, 20))),
+            ("departments", 2u32, Delta::delete(department(10, "eng"))),
+        ];
+        for (table, table_id, delta) in changes {
+            {
+                let store = cache.get_table_mut(table).unwrap();
+                if delta.is_insert() {
+                    store.insert(delta.data.clone()).unwrap();
+                } else {
+                    store.delete(delta.data.id()).unwrap();
+                }
+            }
+            view.on_table_change(table_id, vec![delta]);
+
+            let expected = execute_physical_plan(&cache, &plan)
+                .unwrap()
+                .iter()
+                .map(|row| row.values().to_vec())
+                .collect();
+            let actual = view
+                .result()
+                .iter()
+                .map(|row| row.values().to_vec())
+                .collect();
+            assert_eq!(
+                sorted_values(actual),
+                sorted_values(expected),
+                "after {table}"
+            );
+        }
+
+        assert_eq!(
+            view.result()[0].values(),
+            &[
+                Value::Int64(3),
+                Value::Int64(20),
+                Value::Int64(20),
+                Value::String("ops".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_restored_definition_produces_identical_output() {
+        use cynos_incremental::{Delta, MaterializedView};
+        use cynos_query::ast::JoinType;
+
+        let schemas = table_schemas(&[
+            ("employees", &["id", "dept_id"]),
+            ("departments", &["id", "name"]),
+        ]);
+        let mut table_ids = HashMap::new();
+        table_ids.insert("employees".into(), 1u32);
+        table_ids.insert("departments".into(), 2u32);
+
+        let plan = PhysicalPlan::hash_join(
+            PhysicalPlan::filter(
+                PhysicalPlan::table_scan("employees"),
+                Expr::ne(
+                    Expr::column("employees", "dept_id", 1),
+                    Expr::literal("archive"),
+                ),
+            ),
+            PhysicalPlan::table_scan("departments"),
+            Expr::eq(
+                Expr::column("employees", "dept_id", 1),
+                Expr::column("departments", "id", 0),
+            ),
+            JoinType::Inner,
+        );
+        let original = compile_to_dataflow(&plan, &table_ids, &schemas).unwrap();
+
+        let json =
+            serde_json::to_string(&DataflowDefinition::new(&plan, &original.table_ids)).unwrap();
+        let definition: DataflowDefinition = serde_json::from_str(&json).unwrap();
+        let restored = definition.restore(&schemas).unwrap();
+        assert_eq!(restored.table_ids, original.table_ids);
+
+        let mut original = MaterializedView::new(original.dataflow);
+        let mut restored = MaterializedView::new(restored.dataflow);
+
+        let row = |id: u64, a: &str, b: &str| {
+            Row::new(id, vec![Value::String(a.into()), Value::String(b.into())])
+        };
+        let changes = vec![
+            (1u32, Delta::insert(row(1, "e1", "eng"))),
+            (1u32, Delta::insert(row(2, "e2", "archive"))),
+            (2u32, Delta::insert(row(10, "eng", "Engineering"))),
+            (2u32, Delta::insert(row(11, "archive", "Archive"))),
+            (1u32, Delta::insert(row(3, "e3", "eng"))),
+            (1u32, Delta::delete(row(1, "e1", "eng"))),
+        ];
+        for (table_id, delta) in changes {
+            original.on_table_change(table_id, vec![delta.clone()]);
+            restored.on_table_change(table_id, vec![delta]);
+            assert_eq!(restored.result(), original.result());
+        }
+        assert_eq!(original.len(), 1);
+    }
+
     #[test]
     fn test_compile_reordered_join_wraps_join_with_projection() {
         use cynos_query::ast::JoinType;
@@ -1187,6 +1949,7 @@ mod tests {
             alloc::vec![
                 (AggregateFunc::Count, Expr::column("orders", "id", 1)),
                 (AggregateFunc::Sum, Expr::column("orders", "amount", 2)),
+                (AggregateFunc::Distinct, Expr::column("orders", "amount", 2)),
             ],
         );
         let mut table_ids = HashMap::new();
@@ -1201,14 +1964,76 @@ mod tests {
                 ..
             } => {
                 assert_eq!(group_by, &[0]);
-                assert_eq!(functions.len(), 2);
+                assert_eq!(functions.len(), 3);
                 assert_eq!(functions[0].1, AggregateType::Count);
                 assert_eq!(functions[1].1, AggregateType::Sum);
+                assert_eq!(functions[2], (2, AggregateType::CountDistinct));
             }
             _ => panic!("Expected Aggregate node"),
         }
     }
 
+    #[test]
+    fn test_traced_count_distinct_updates_on_deltas() {
+        use cynos_incremental::Delta;
+        use cynos_reactive::ObservableQuery;
+
+        // SELECT COUNT(DISTINCT status) FROM tickets, as trace() compiles it.
+        let plan = PhysicalPlan::hash_aggregate(
+            PhysicalPlan::table_scan("tickets"),
+            alloc::vec![],
+            alloc::vec![(
+                AggregateFunc::Distinct,
+                Expr::column("tickets", "status", 1)
+            )],
+        );
+        let mut table_ids = HashMap::new();
+        table_ids.insert("tickets".into(), 1u32);
+        let table_schemas = table_schemas(&[("tickets", &["id", "status"])]);
+        let dataflow = compile_to_dataflow(&plan, &table_ids, &table_schemas)
+            .unwrap()
+            .dataflow;
+
+        let mut query = ObservableQuery::new(dataflow);
+        query.subscribe(|_| {});
+        let row = |id: u64, status: &str| {
+            Row::new(
+                id,
+                vec![Value::Int64(id as i64), Value::String(status.into())],
+            )
+        };
+        let cardinality = |query: &ObservableQuery| {
+            let result = query.result();
+            assert_eq!(result.len(), 1);
+            result[0].get(0).cloned()
+        };
+
+        query.on_table_change(
+            1,
+            vec![
+                Delta::insert(row(1, "open")),
+                Delta::insert(row(2, "closed")),
+            ],
+        );
+        assert_eq!(cardinality(&query), Some(Value::Int64(2)));
+
+        // Duplicates of a value already counted leave the cardinality alone.
+        query.on_table_change(
+            1,
+            vec![Delta::insert(row(3, "open")), Delta::insert(row(4, "open"))],
+        );
+        assert_eq!(cardinality(&query), Some(Value::Int64(2)));
+
+        // It only drops once the last "open" row is gone.
+        query.on_table_change(
+            1,
+            vec![Delta::delete(row(1, "open")), Delta::delete(row(3, "open"))],
+        );
+        assert_eq!(cardinality(&query), Some(Value::Int64(2)));
+        query.on_table_change(1, vec![Delta::delete(row(4, "open"))]);
+        assert_eq!(cardinality(&query), Some(Value::Int64(1)));
+    }
+
     #[test]
     fn test_eval_in_expr() {
         let row = Row::new(1, vec![Value::Int64(3), Value::String("Alice".into())]);
@@ -1408,4 +2233,137 @@ mod tests {
         assert_eq!(rows[0].get(2), Some(&Value::Int64(10)));
         assert_eq!(rows[0].get(3), Some(&Value::String("Engineering".into())));
     }
+
+    /// Minimal LCG so the random sequences are reproducible without std.
+    fn next_random(state: &mut u64) -> u64 {
+        *state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        *state >> 33
+    }
+
+    #[test]
+    fn test_ivm_matches_full_execution_after_random_changes() {
+        use crate::query_engine::execute_physical_plan;
+        use cynos_incremental::Delta;
+        use cynos_query::ast::JoinType;
+        use cynos_reactive::ObservableQuery;
+        use cynos_storage::TableCache;
+
+        let employees = TableBuilder::new("employees")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("dept_id", DataType::Int64)
+            .unwrap()
+            .add_column("salary", DataType::Int64)
+            .unwrap()
+            .add_primary_key(&["id"], false)
+            .unwrap()
+            .build()
+            .unwrap();
+        let departments = TableBuilder::new("departments")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("name", DataType::String)
+            .unwrap()
+            .add_primary_key(&["id"], false)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut table_schemas = HashMap::new();
+        table_schemas.insert("employees".into(), employees.clone());
+        table_schemas.insert("departments".into(), departments.clone());
+        let mut table_ids = HashMap::new();
+        table_ids.insert("employees".into(), 1u32);
+        table_ids.insert("departments".into(), 2u32);
+
+        let plans = [
+            PhysicalPlan::filter(
+                PhysicalPlan::table_scan("employees"),
+                Expr::gt(Expr::column("employees", "salary", 2), Expr::literal(50i64)),
+            ),
+            PhysicalPlan::hash_join(
+                PhysicalPlan::table_scan("employees"),
+                PhysicalPlan::table_scan("departments"),
+                Expr::eq(
+                    Expr::column("employees", "dept_id", 1),
+                    Expr::column("departments", "id", 0),
+                ),
+                JoinType::Inner,
+            ),
+            PhysicalPlan::hash_aggregate(
+                PhysicalPlan::table_scan("employees"),
+                alloc::vec![Expr::column("employees", "dept_id", 1)],
+                alloc::vec![
+                    (AggregateFunc::Count, Expr::column("employees", "id", 0)),
+                    (AggregateFunc::Max, Expr::column("employees", "salary", 2)),
+                ],
+            ),
+        ];
+
+        for plan in &plans {
+            let mut cache = TableCache::new();
+            cache.create_table(employees.clone()).unwrap();
+            cache.create_table(departments.clone()).unwrap();
+            let dataflow = compile_to_dataflow(plan, &table_ids, &table_schemas)
+                .unwrap()
+                .dataflow;
+            let mut query = ObservableQuery::new(dataflow);
+            // Changes are only propagated while someone is subscribed.
+            query.subscribe(|_| {});
+
+            let mut state = 42;
+            for step in 0..400 {
+                let (table, table_id, row) = if next_random(&mut state).is_multiple_of(4) {
+                    let id = (next_random(&mut state) % 5) as i64;
+                    let name = Value::String(alloc::format!("dept-{}", id));
+                    let row = Row::new(1000 + id as u64, alloc::vec![Value::Int64(id), name]);
+                    ("departments", 2u32, row)
+                } else {
+                    let id = (next_random(&mut state) % 30) as i64;
+                    let dept_id = (next_random(&mut state) % 6) as i64;
+                    let salary = (next_random(&mut state) % 100) as i64;
+                    let row = Row::new(
+                        id as u64,
+                        alloc::vec![
+                            Value::Int64(id),
+                            Value::Int64(dept_id),
+                            Value::Int64(salary)
+                        ],
+                    );
+                    ("employees", 1u32, row)
+                };
+
+                let store = cache.get_table_mut(table).unwrap();
+                let delta = match store.get(row.id()) {
+                    Some(existing) => {
+                        let existing = (*existing).clone();
+                        store.delete(existing.id()).unwrap();
+                        Delta::delete(existing)
+                    }
+                    None => {
+                        store.insert(row.clone()).unwrap();
+                        Delta::insert(row)
+                    }
+                };
+                query.on_table_change(table_id, alloc::vec![delta]);
+
+                if step % 10 == 9 {
+                    let full: Vec<Row> = execute_physical_plan(&cache, plan)
+                        .unwrap()
+                        .iter()
+                        .map(|row| (**row).clone())
+                        .collect();
+                    assert!(
+                        query.verify_against(&full),
+                        "IVM diverged after step {step}: {:?} vs {:?}",
+                        query.result(),
+                        full
+                    );
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,271 @@
+//! Structured errors surfaced to JavaScript.
+//!
+//! Fallible bindings report a `CynosError`, which is converted into a JS
+//! `Error` object carrying a stable `code` property so callers can branch on
+//! the kind of failure instead of parsing messages.
+
+use alloc::string::{String, ToString};
+use core::fmt;
+use cynos_query::executor::ExecutionError;
+use wasm_bindgen::prelude::*;
+
+/// The constraint that a write operation violated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConstraintKind {
+    Unique,
+    NotNull,
+    ForeignKey,
+    Domain,
+    Check,
+}
+
+impl ConstraintKind {
+    /// Returns the name exposed to JS in the `constraint` property.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConstraintKind::Unique => "unique",
+            ConstraintKind::NotNull => "notNull",
+            ConstraintKind::ForeignKey => "foreignKey",
+            ConstraintKind::Domain => "domain",
+            ConstraintKind::Check => "check",
+        }
+    }
+}
+
+/// Error type for the JS-facing API.
+///
+/// Each variant carries the human-readable message; the variant itself
+/// determines the machine-readable `code`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CynosError {
+    /// A referenced table does not exist.
+    TableNotFound(String),
+    /// A referenced column does not exist.
+    ColumnNotFound(String),
+    /// A referenced index does not exist.
+    IndexNotFound(String),
+    /// A value does not match the expected type.
+    TypeMismatch(String),
+    /// A write violated a schema constraint.
+    ConstraintViolation {
+        constraint: ConstraintKind,
+        message: String,
+    },
+    /// A row or record was not found.
+    NotFound(String),
+    /// The schema definition is invalid.
+    InvalidSchema(String),
+    /// The caller passed an invalid argument.
+    InvalidArgument(String),
+    /// The operation is not valid in the current state.
+    InvalidOperation(String),
+    /// A GraphQL document failed to parse, bind, or execute.
+    Graphql(String),
+    /// Rust code panicked; reported by the panic hook.
+    Panic(String),
+}
+
+impl CynosError {
+    /// Creates a table-not-found error for the given table name.
+    pub fn table_not_found(table: &str) -> Self {
+        CynosError::TableNotFound(alloc::format!("Table not found: {}", table))
+    }
+
+    /// Creates an invalid-argument error.
+    pub fn invalid_argument(message: impl Into<String>) -> Self {
+        CynosError::InvalidArgument(message.into())
+    }
+
+    /// Creates an invalid-operation error.
+    pub fn invalid_operation(message: impl Into<String>) -> Self {
+        CynosError::InvalidOperation(message.into())
+    }
+
+    /// Creates a type-mismatch error.
+    pub fn type_mismatch(message: impl Into<String>) -> Self {
+        CynosError::TypeMismatch(message.into())
+    }
+
+    /// Returns the stable error code exposed to JS.
+    pub fn code(&self) -> &'static str {
+        match self {
+            CynosError::TableNotFound(_) => "TABLE_NOT_FOUND",
+            CynosError::ColumnNotFound(_) => "COLUMN_NOT_FOUND",
+            CynosError::IndexNotFound(_) => "INDEX_NOT_FOUND",
+            CynosError::TypeMismatch(_) => "TYPE_MISMATCH",
+            CynosError::ConstraintViolation { .. } => "CONSTRAINT_VIOLATION",
+            CynosError::NotFound(_) => "NOT_FOUND",
+            CynosError::InvalidSchema(_) => "INVALID_SCHEMA",
+            CynosError::InvalidArgument(_) => "INVALID_ARGUMENT",
+            CynosError::InvalidOperation(_) => "INVALID_OPERATION",
+            CynosError::Graphql(_) => "GRAPHQL_ERROR",
+            CynosError::Panic(_) => "PANIC",
+        }
+    }
+
+    /// Returns the human-readable message.
+    pub fn message(&self) -> &str {
+        match self {
+            CynosError::TableNotFound(message)
+            | CynosError::ColumnNotFound(message)
+            | CynosError::IndexNotFound(message)
+            | CynosError::TypeMismatch(message)
+            | CynosError::ConstraintViolation { message, .. }
+            | CynosError::NotFound(message)
+            | CynosError::InvalidSchema(message)
+            | CynosError::InvalidArgument(message)
+            | CynosError::InvalidOperation(message)
+            | CynosError::Graphql(message)
+            | CynosError::Panic(message) => message,
+        }
+    }
+
+    /// Returns the violated constraint, if this is a constraint violation.
+    pub fn constraint(&self) -> Option<ConstraintKind> {
+        match self {
+            CynosError::ConstraintViolation { constraint, .. } => Some(*constraint),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CynosError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl From<cynos_core::Error> for CynosError {
+    fn from(error: cynos_core::Error) -> Self {
+        use cynos_core::Error;
+
+        let message = error.to_string();
+        match error {
+            Error::TypeMismatch { .. } => CynosError::TypeMismatch(message),
+            Error::NullConstraint { .. } => CynosError::ConstraintViolation {
+                constraint: ConstraintKind::NotNull,
+                message,
+            },
+            Error::UniqueConstraint { .. } => CynosError::ConstraintViolation {
+                constraint: ConstraintKind::Unique,
+                message,
+            },
+            Error::DomainConstraint { .. } => CynosError::ConstraintViolation {
+                constraint: ConstraintKind::Domain,
+                message,
+            },
+            Error::ForeignKeyViolation { .. } => CynosError::ConstraintViolation {
+                constraint: ConstraintKind::ForeignKey,
+                message,
+            },
+            Error::CheckViolation { .. } => CynosError::ConstraintViolation {
+                constraint: ConstraintKind::Check,
+                message,
+            },
+            Error::NotFound { .. } => CynosError::NotFound(message),
+            Error::InvalidSchema { .. } => CynosError::InvalidSchema(message),
+            Error::ColumnNotFound { .. } => CynosError::ColumnNotFound(message),
+            Error::TableNotFound { .. } => CynosError::TableNotFound(message),
+            Error::IndexNotFound { .. } => CynosError::IndexNotFound(message),
+            Error::InvalidOperation { .. } => CynosError::InvalidOperation(message),
+        }
+    }
+}
+
+impl From<ExecutionError> for CynosError {
+    fn from(error: ExecutionError) -> Self {
+        let message = alloc::format!("Query execution error: {}", error);
+        match error {
+            ExecutionError::TableNotFound(_) => CynosError::TableNotFound(message),
+            ExecutionError::IndexNotFound { .. } => CynosError::IndexNotFound(message),
+            ExecutionError::ColumnNotFound { .. } => CynosError::ColumnNotFound(message),
+            ExecutionError::TypeMismatch(_) => CynosError::TypeMismatch(message),
+            ExecutionError::InvalidOperation(_) => CynosError::InvalidOperation(message),
+        }
+    }
+}
+
+impl From<cynos_binary::EncodeError> for CynosError {
+    fn from(error: cynos_binary::EncodeError) -> Self {
+        CynosError::InvalidOperation(alloc::format!("Binary encoding error: {}", error))
+    }
+}
+
+impl From<cynos_gql::GqlError> for CynosError {
+    fn from(error: cynos_gql::GqlError) -> Self {
+        CynosError::Graphql(error.message().to_string())
+    }
+}
+
+impl From<CynosError> for JsValue {
+    fn from(error: CynosError) -> Self {
+        let js_error = js_sys::Error::new(error.message());
+        js_error.set_name("CynosError");
+        js_sys::Reflect::set(
+            &js_error,
+            &JsValue::from_str("code"),
+            &JsValue::from_str(error.code()),
+        )
+        .ok();
+        if let Some(constraint) = error.constraint() {
+            js_sys::Reflect::set(
+                &js_error,
+                &JsValue::from_str("constraint"),
+                &JsValue::from_str(constraint.as_str()),
+            )
+            .ok();
+        }
+        js_error.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cynos_core::Value;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    fn test_core_unique_error_maps_to_constraint_violation() {
+        let error = CynosError::from(cynos_core::Error::unique_constraint(
+            "email",
+            Value::String("a@example.com".into()),
+        ));
+        assert_eq!(error.code(), "CONSTRAINT_VIOLATION");
+        assert_eq!(error.constraint(), Some(ConstraintKind::Unique));
+        assert!(error.message().contains("email"));
+    }
+
+    #[test]
+    fn test_execution_error_maps_to_code() {
+        let error = CynosError::from(ExecutionError::TableNotFound("users".into()));
+        assert_eq!(error.code(), "TABLE_NOT_FOUND");
+        assert_eq!(
+            error.message(),
+            "Query execution error: Table not found: users"
+        );
+    }
+
+    #[test]
+    fn test_constraint_kinds_are_distinct() {
+        let null = CynosError::from(cynos_core::Error::null_constraint("name"));
+        assert_eq!(null.constraint(), Some(ConstraintKind::NotNull));
+        assert_eq!(
+            CynosError::table_not_found("users").message(),
+            "Table not found: users"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_error_converts_to_js_error_with_code() {
+        let js: JsValue =
+            CynosError::from(cynos_core::Error::unique_constraint("id", Value::Int64(1))).into();
+        assert!(js.is_instance_of::<js_sys::Error>());
+        let code = js_sys::Reflect::get(&js, &JsValue::from_str("code")).unwrap();
+        assert_eq!(code.as_string().as_deref(), Some("CONSTRAINT_VIOLATION"));
+        let constraint = js_sys::Reflect::get(&js, &JsValue::from_str("constraint")).unwrap();
+        assert_eq!(constraint.as_string().as_deref(), Some("unique"));
+    }
+}
@@ -18,6 +18,7 @@ pub struct Column {
     table: Option<String>,
     name: String,
     index: Option<usize>,
+    cast: Option<DataType>,
 }
 
 #[wasm_bindgen]
@@ -29,6 +30,7 @@ impl Column {
             table: Some(table.to_string()),
             name: name.to_string(),
             index: None,
+            cast: None,
         }
     }
 
@@ -43,12 +45,14 @@ impl Column {
                 table: Some(table.to_string()),
                 name: col.to_string(),
                 index: None,
+                cast: None,
             }
         } else {
             Self {
                 table: None,
                 name: name.to_string(),
                 index: None,
+                cast: None,
             }
         }
     }
@@ -71,6 +75,23 @@ impl Column {
         self.table.clone()
     }
 
+    /// Returns a copy of this column reference.
+    #[wasm_bindgen(js_name = "clone")]
+    pub fn copy(&self) -> Column {
+        self.clone()
+    }
+
+    /// Casts the column to `target`, as in `CAST(column AS target)`.
+    ///
+    /// Comparisons on the cast column compare the converted values; rows whose
+    /// value cannot be converted see NULL.
+    pub fn cast(&self, target: crate::JsDataType) -> Column {
+        Column {
+            cast: Some(target.into()),
+            ..self.clone()
+        }
+    }
+
     /// Creates an equality expression: column = value
     pub fn eq(&self, value: &JsValue) -> Expr {
         Expr::comparison(self.clone(), ComparisonOp::Eq, value.clone())
@@ -159,6 +180,14 @@ impl Column {
         Expr::is_not_null(self.clone())
     }
 
+    /// Creates a string concatenation expression: column || other
+    ///
+    /// `other` is a column or a literal value; plain strings are always
+    /// literals. A NULL operand makes the result NULL.
+    pub fn concat(&self, other: &JsValue) -> Expr {
+        Expr::column_ref(self.clone()).concat(other)
+    }
+
     /// Creates a JSONB path access expression
     pub fn get(&self, path: &str) -> JsonbColumn {
         JsonbColumn {
@@ -169,11 +198,33 @@ impl Column {
 
     /// Converts to AST expression.
     pub(crate) fn to_ast(&self) -> AstExpr {
-        AstExpr::column(
-            self.table.as_deref().unwrap_or(""),
-            &self.name,
-            self.index.unwrap_or(0),
-        )
+        self.to_ast_at(self.table.as_deref().unwrap_or(""), self.index.unwrap_or(0))
+    }
+
+    /// Converts to AST expression for a column resolved to `table` at `index`,
+    /// applying the cast if one is set.
+    fn to_ast_at(&self, table: &str, index: usize) -> AstExpr {
+        let expr = AstExpr::column(table, &self.name, index);
+        match self.cast {
+            Some(target) => AstExpr::cast(expr, target),
+            None => expr,
+        }
+    }
+
+    /// Returns the type literals compared against this column convert to:
+    /// the cast target if set, otherwise the column's own `data_type`.
+    fn value_type(&self, data_type: DataType) -> DataType {
+        self.cast.unwrap_or(data_type)
+    }
+
+    /// Returns the name used to resolve this column against a query's tables,
+    /// qualified as `table.column` when a table is set.
+    pub(crate) fn lookup_key(&self) -> String {
+        if let Some(ref table) = self.table {
+            alloc::format!("{}.{}", table, self.name)
+        } else {
+            self.name.clone()
+        }
     }
 }
 
@@ -297,9 +348,15 @@ pub(crate) enum ExprInner {
     ColumnRef {
         column: Column,
     },
+    Concat {
+        parts: Vec<Expr>,
+    },
     Literal {
         value: JsValue,
     },
+    TypedNull {
+        data_type: DataType,
+    },
     True,
 }
 
@@ -411,21 +468,18 @@ impl Expr {
         }
     }
 
-    #[allow(dead_code)]
     pub(crate) fn column_ref(column: Column) -> Self {
         Self {
             inner: ExprInner::ColumnRef { column },
         }
     }
 
-    #[allow(dead_code)]
     pub(crate) fn literal(value: JsValue) -> Self {
         Self {
             inner: ExprInner::Literal { value },
         }
     }
 
-    #[allow(dead_code)]
     pub(crate) fn true_expr() -> Self {
         Self {
             inner: ExprInner::True,
@@ -440,6 +494,12 @@ impl Expr {
 
 #[wasm_bindgen]
 impl Expr {
+    /// Returns a copy of this expression.
+    #[wasm_bindgen(js_name = "clone")]
+    pub fn copy(&self) -> Expr {
+        self.clone()
+    }
+
     /// Creates an AND expression: self AND other
     pub fn and(&self, other: &Expr) -> Expr {
         Expr {
@@ -468,9 +528,198 @@ impl Expr {
             },
         }
     }
+
+    /// Creates a NULL of the given type.
+    ///
+    /// Unlike a plain `null` literal, the value keeps `dataType`, so default
+    /// and generated expressions built from it are checked against the
+    /// column type when the table is created.
+    #[wasm_bindgen(js_name = "null")]
+    pub fn typed_null(data_type: crate::JsDataType) -> Expr {
+        Expr {
+            inner: ExprInner::TypedNull {
+                data_type: data_type.into(),
+            },
+        }
+    }
+
+    /// Creates a filter that matches every row, the identity for `and()`.
+    #[wasm_bindgen(js_name = alwaysTrue)]
+    pub fn always_true() -> Expr {
+        Expr::true_expr()
+    }
+
+    /// Creates a filter that matches no rows, the identity for `or()`.
+    #[wasm_bindgen(js_name = alwaysFalse)]
+    pub fn always_false() -> Expr {
+        Expr::true_expr().not()
+    }
+
+    /// Creates a string concatenation expression: self || other
+    ///
+    /// `other` is a column or a literal value; plain strings are always
+    /// literals. A NULL operand makes the result NULL.
+    pub fn concat(&self, other: &JsValue) -> Expr {
+        let mut parts = match &self.inner {
+            ExprInner::Concat { parts } => parts.clone(),
+            _ => alloc::vec![self.clone()],
+        };
+        parts.push(concat_operand(other));
+        Expr {
+            inner: ExprInner::Concat { parts },
+        }
+    }
+}
+
+/// Reads a `Column` or `Expr` passed inside a JS value without taking it
+/// over: the object's `clone()` copy is what gets unwrapped, so the caller's
+/// object stays usable. Columns are recognised by their `name` property.
+pub(crate) fn copy_from_js<T: JsObjectKind>(value: &JsValue) -> Option<T> {
+    if !value.is_object() {
+        return None;
+    }
+    let is_column = js_sys::Reflect::get(value, &JsValue::from_str("name"))
+        .ok()
+        .is_some_and(|name| name.is_string());
+    if is_column != T::IS_COLUMN {
+        return None;
+    }
+    let copy = js_sys::Reflect::get(value, &JsValue::from_str("clone"))
+        .ok()?
+        .dyn_into::<js_sys::Function>()
+        .ok()?
+        .call0(value)
+        .ok()?;
+    T::try_from_js_value(copy).ok()
+}
+
+/// The wrapper types `copy_from_js` can read.
+pub(crate) trait JsObjectKind: wasm_bindgen::convert::TryFromJsValue {
+    const IS_COLUMN: bool;
+}
+
+impl JsObjectKind for Column {
+    const IS_COLUMN: bool = true;
+}
+
+impl JsObjectKind for Expr {
+    const IS_COLUMN: bool = false;
+}
+
+/// Reads a concatenation operand: a `Column` object (recognised by its
+/// `name` property, as comparison values are) or a literal.
+fn concat_operand(value: &JsValue) -> Expr {
+    let property = |key: &str| {
+        js_sys::Reflect::get(value, &JsValue::from_str(key))
+            .ok()
+            .and_then(|v| v.as_string())
+    };
+    if value.is_object() {
+        if let Some(name) = property("name") {
+            let column = match property("tableName") {
+                Some(table) => Column::new(&table, &name),
+                None => Column::new_simple(&name),
+            };
+            return Expr::column_ref(column);
+        }
+    }
+    Expr::literal(value.clone())
 }
 
 impl Expr {
+    /// Returns the first column referenced by this expression that
+    /// `get_column_info` cannot resolve, if any.
+    pub(crate) fn find_unresolved_column(
+        &self,
+        get_column_info: &impl Fn(&str) -> Option<(String, usize, DataType)>,
+    ) -> Option<String> {
+        self.columns()
+            .into_iter()
+            .map(Column::lookup_key)
+            .find(|lookup_key| get_column_info(lookup_key).is_none())
+    }
+
+    /// Returns the columns referenced by this expression, in order.
+    pub(crate) fn columns(&self) -> Vec<&Column> {
+        let mut columns = Vec::new();
+        self.collect_columns(&mut columns);
+        columns
+    }
+
+    fn collect_columns<'a>(&'a self, columns: &mut Vec<&'a Column>) {
+        match &self.inner {
+            ExprInner::Comparison { column, .. }
+            | ExprInner::Between { column, .. }
+            | ExprInner::NotBetween { column, .. }
+            | ExprInner::InList { column, .. }
+            | ExprInner::NotInList { column, .. }
+            | ExprInner::Like { column, .. }
+            | ExprInner::NotLike { column, .. }
+            | ExprInner::Match { column, .. }
+            | ExprInner::NotMatch { column, .. }
+            | ExprInner::IsNull { column }
+            | ExprInner::IsNotNull { column }
+            | ExprInner::JsonbEq { column, .. }
+            | ExprInner::JsonbContains { column, .. }
+            | ExprInner::JsonbExists { column, .. }
+            | ExprInner::ColumnRef { column } => columns.push(column),
+            ExprInner::Concat { parts } => {
+                for part in parts {
+                    part.collect_columns(columns);
+                }
+            }
+            ExprInner::And { left, right } | ExprInner::Or { left, right } => {
+                left.collect_columns(columns);
+                right.collect_columns(columns);
+            }
+            ExprInner::Not { inner } => inner.collect_columns(columns),
+            ExprInner::Literal { .. } | ExprInner::TypedNull { .. } | ExprInner::True => {}
+        }
+    }
+
+    /// Returns a copy of this expression whose unqualified columns are
+    /// qualified with `table`; already qualified columns are kept.
+    pub(crate) fn scoped_to(&self, table: &str) -> Expr {
+        let mut scoped = self.clone();
+        scoped.qualify_columns(table);
+        scoped
+    }
+
+    fn qualify_columns(&mut self, table: &str) {
+        match &mut self.inner {
+            ExprInner::Comparison { column, .. }
+            | ExprInner::Between { column, .. }
+            | ExprInner::NotBetween { column, .. }
+            | ExprInner::InList { column, .. }
+            | ExprInner::NotInList { column, .. }
+            | ExprInner::Like { column, .. }
+            | ExprInner::NotLike { column, .. }
+            | ExprInner::Match { column, .. }
+            | ExprInner::NotMatch { column, .. }
+            | ExprInner::IsNull { column }
+            | ExprInner::IsNotNull { column }
+            | ExprInner::JsonbEq { column, .. }
+            | ExprInner::JsonbContains { column, .. }
+            | ExprInner::JsonbExists { column, .. }
+            | ExprInner::ColumnRef { column } => {
+                if column.table.is_none() {
+                    column.table = Some(table.into());
+                }
+            }
+            ExprInner::Concat { parts } => {
+                for part in parts {
+                    part.qualify_columns(table);
+                }
+            }
+            ExprInner::And { left, right } | ExprInner::Or { left, right } => {
+                left.qualify_columns(table);
+                right.qualify_columns(table);
+            }
+            ExprInner::Not { inner } => inner.qualify_columns(table),
+            ExprInner::Literal { .. } | ExprInner::TypedNull { .. } | ExprInner::True => {}
+        }
+    }
+
     /// Converts to AST expression for JOIN conditions where table names are needed.
     pub(crate) fn to_ast_with_table(
         &self,
@@ -489,7 +738,7 @@ impl Expr {
                 let lookup_key = column_lookup_key(column);
 
                 let col_expr = if let Some((table, idx, _dt)) = get_column_info(&lookup_key) {
-                    AstExpr::column(&table, &column.name, idx)
+                    column.to_ast_at(&table, idx)
                 } else {
                     column.to_ast()
                 };
@@ -508,7 +757,7 @@ impl Expr {
                     } else {
                         // Value is a string literal
                         let val = if let Some((_, _, dt)) = get_column_info(&lookup_key) {
-                            js_to_value(value, dt).unwrap_or(Value::String(s))
+                            js_to_value(value, column.value_type(dt)).unwrap_or(Value::String(s))
                         } else {
                             Value::String(s)
                         };
@@ -547,7 +796,7 @@ impl Expr {
                     }
                 } else {
                     let val = if let Some((_, _, dt)) = get_column_info(&lookup_key) {
-                        js_to_value(value, dt).unwrap_or(Value::Null)
+                        js_to_value(value, column.value_type(dt)).unwrap_or(Value::Null)
                     } else {
                         // Try to infer type
                         if let Some(n) = value.as_f64() {
@@ -578,9 +827,9 @@ impl Expr {
                 let lookup_key = column_lookup_key(column);
                 let (table, idx, dt) =
                     get_column_info(&lookup_key).unwrap_or((String::new(), 0, DataType::Float64));
-                let col_expr = AstExpr::column(&table, &column.name, idx);
-                let low_val = js_to_value(low, dt).unwrap_or(Value::Null);
-                let high_val = js_to_value(high, dt).unwrap_or(Value::Null);
+                let col_expr = column.to_ast_at(&table, idx);
+                let low_val = js_to_value(low, column.value_type(dt)).unwrap_or(Value::Null);
+                let high_val = js_to_value(high, column.value_type(dt)).unwrap_or(Value::Null);
                 AstExpr::between(
                     col_expr,
                     AstExpr::literal(low_val),
@@ -591,9 +840,9 @@ impl Expr {
                 let lookup_key = column_lookup_key(column);
                 let (table, idx, dt) =
                     get_column_info(&lookup_key).unwrap_or((String::new(), 0, DataType::Float64));
-                let col_expr = AstExpr::column(&table, &column.name, idx);
-                let low_val = js_to_value(low, dt).unwrap_or(Value::Null);
-                let high_val = js_to_value(high, dt).unwrap_or(Value::Null);
+                let col_expr = column.to_ast_at(&table, idx);
+                let low_val = js_to_value(low, column.value_type(dt)).unwrap_or(Value::Null);
+                let high_val = js_to_value(high, column.value_type(dt)).unwrap_or(Value::Null);
                 AstExpr::not_between(
                     col_expr,
                     AstExpr::literal(low_val),
@@ -604,12 +853,12 @@ impl Expr {
                 let lookup_key = column_lookup_key(column);
                 let (table, idx, dt) =
                     get_column_info(&lookup_key).unwrap_or((String::new(), 0, DataType::String));
-                let col_expr = AstExpr::column(&table, &column.name, idx);
+                let col_expr = column.to_ast_at(&table, idx);
 
                 let arr = js_sys::Array::from(values);
                 let vals: Vec<Value> = arr
                     .iter()
-                    .filter_map(|v| js_to_value(&v, dt).ok())
+                    .filter_map(|v| js_to_value(&v, column.value_type(dt)).ok())
                     .collect();
 
                 AstExpr::in_list(col_expr, vals)
@@ -618,12 +867,12 @@ impl Expr {
                 let lookup_key = column_lookup_key(column);
                 let (table, idx, dt) =
                     get_column_info(&lookup_key).unwrap_or((String::new(), 0, DataType::String));
-                let col_expr = AstExpr::column(&table, &column.name, idx);
+                let col_expr = column.to_ast_at(&table, idx);
 
                 let arr = js_sys::Array::from(values);
                 let vals: Vec<Value> = arr
                     .iter()
-                    .filter_map(|v| js_to_value(&v, dt).ok())
+                    .filter_map(|v| js_to_value(&v, column.value_type(dt)).ok())
                     .collect();
 
                 AstExpr::not_in_list(col_expr, vals)
@@ -633,7 +882,7 @@ impl Expr {
                 let (table, idx) = get_column_info(&lookup_key)
                     .map(|(table, index, _)| (table, index))
                     .unwrap_or((String::new(), 0));
-                let col_expr = AstExpr::column(&table, &column.name, idx);
+                let col_expr = column.to_ast_at(&table, idx);
                 AstExpr::like(col_expr, pattern)
             }
             ExprInner::NotLike { column, pattern } => {
@@ -641,7 +890,7 @@ impl Expr {
                 let (table, idx) = get_column_info(&lookup_key)
                     .map(|(table, index, _)| (table, index))
                     .unwrap_or((String::new(), 0));
-                let col_expr = AstExpr::column(&table, &column.name, idx);
+                let col_expr = column.to_ast_at(&table, idx);
                 AstExpr::not_like(col_expr, pattern)
             }
             ExprInner::Match { column, pattern } => {
@@ -649,7 +898,7 @@ impl Expr {
                 let (table, idx) = get_column_info(&lookup_key)
                     .map(|(table, index, _)| (table, index))
                     .unwrap_or((String::new(), 0));
-                let col_expr = AstExpr::column(&table, &column.name, idx);
+                let col_expr = column.to_ast_at(&table, idx);
                 AstExpr::regex_match(col_expr, pattern)
             }
             ExprInner::NotMatch { column, pattern } => {
@@ -657,7 +906,7 @@ impl Expr {
                 let (table, idx) = get_column_info(&lookup_key)
                     .map(|(table, index, _)| (table, index))
                     .unwrap_or((String::new(), 0));
-                let col_expr = AstExpr::column(&table, &column.name, idx);
+                let col_expr = column.to_ast_at(&table, idx);
                 AstExpr::not_regex_match(col_expr, pattern)
             }
             ExprInner::IsNull { column } => {
@@ -665,7 +914,7 @@ impl Expr {
                 let (table, idx) = get_column_info(&lookup_key)
                     .map(|(table, index, _)| (table, index))
                     .unwrap_or((String::new(), 0));
-                let col_expr = AstExpr::column(&table, &column.name, idx);
+                let col_expr = column.to_ast_at(&table, idx);
                 AstExpr::is_null(col_expr)
             }
             ExprInner::IsNotNull { column } => {
@@ -673,7 +922,7 @@ impl Expr {
                 let (table, idx) = get_column_info(&lookup_key)
                     .map(|(table, index, _)| (table, index))
                     .unwrap_or((String::new(), 0));
-                let col_expr = AstExpr::column(&table, &column.name, idx);
+                let col_expr = column.to_ast_at(&table, idx);
                 AstExpr::is_not_null(col_expr)
             }
             ExprInner::JsonbEq {
@@ -684,7 +933,7 @@ impl Expr {
                 // JSONB path equality - use get_column_info to get correct index
                 let lookup_key = column_lookup_key(column);
                 let col_expr = if let Some((table, idx, _)) = get_column_info(&lookup_key) {
-                    AstExpr::column(&table, &column.name, idx)
+                    column.to_ast_at(&table, idx)
                 } else {
                     column.to_ast()
                 };
@@ -704,7 +953,7 @@ impl Expr {
             } => {
                 let lookup_key = column_lookup_key(column);
                 let col_expr = if let Some((table, idx, _)) = get_column_info(&lookup_key) {
-                    AstExpr::column(&table, &column.name, idx)
+                    column.to_ast_at(&table, idx)
                 } else {
                     column.to_ast()
                 };
@@ -726,7 +975,7 @@ impl Expr {
             ExprInner::JsonbExists { column, path } => {
                 let lookup_key = column_lookup_key(column);
                 let col_expr = if let Some((table, idx, _)) = get_column_info(&lookup_key) {
-                    AstExpr::column(&table, &column.name, idx)
+                    column.to_ast_at(&table, idx)
                 } else {
                     column.to_ast()
                 };
@@ -746,7 +995,16 @@ impl Expr {
                 let inner_ast = inner.to_ast_with_table(get_column_info);
                 AstExpr::not(inner_ast)
             }
-            ExprInner::ColumnRef { column } => column.to_ast(),
+            ExprInner::ColumnRef { column } => match get_column_info(&column_lookup_key(column)) {
+                Some((table, idx, _dt)) => column.to_ast_at(&table, idx),
+                None => column.to_ast(),
+            },
+            ExprInner::Concat { parts } => AstExpr::concat(
+                parts
+                    .iter()
+                    .map(|part| part.to_ast_with_table(get_column_info))
+                    .collect(),
+            ),
             ExprInner::Literal { value } => {
                 let val = if let Some(n) = value.as_f64() {
                     if n.fract() == 0.0 {
@@ -763,6 +1021,7 @@ impl Expr {
                 };
                 AstExpr::literal(val)
             }
+            ExprInner::TypedNull { data_type } => AstExpr::null(*data_type),
             ExprInner::True => AstExpr::literal(Value::Boolean(true)),
         }
     }
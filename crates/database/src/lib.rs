@@ -37,21 +37,27 @@
 
 extern crate alloc;
 
+mod batch;
 pub mod binary_protocol;
 pub mod convert;
 pub mod database;
 pub mod dataflow_compiler;
+pub mod error;
 pub mod expr;
 pub mod live_runtime;
+pub mod panic_hook;
 pub mod query_builder;
 pub mod query_engine;
 pub mod reactive_bridge;
+pub mod snapshot;
 pub mod table;
+mod table_json;
 pub mod transaction;
 
 pub use binary_protocol::{BinaryResult, SchemaLayout};
 pub use convert::{js_to_row, js_to_value, row_to_js, value_to_js};
 pub use database::{Database, PreparedGraphqlQuery};
+pub use error::{ConstraintKind, CynosError};
 pub use expr::{Column, Expr};
 pub use query_builder::{
     DeleteBuilder, InsertBuilder, PreparedSelectQuery, SelectBuilder, UpdateBuilder,
@@ -59,6 +65,7 @@ pub use query_builder::{
 pub use reactive_bridge::{
     JsChangesStream, JsGraphqlSubscription, JsIvmObservableQuery, JsObservableQuery,
 };
+pub use snapshot::DatabaseSnapshot;
 pub use table::{ForeignKeyOptions, JsTable, JsTableBuilder};
 pub use transaction::JsTransaction;
 
@@ -67,7 +74,8 @@ use wasm_bindgen::prelude::*;
 /// Initialize the WASM module.
 #[wasm_bindgen(start)]
 pub fn init() {
-    // Panic hook initialization removed - feature not configured
+    #[cfg(feature = "panic-hook")]
+    panic_hook::install_panic_hook();
 }
 
 /// Data types supported by Cynos.
@@ -82,6 +90,7 @@ pub enum JsDataType {
     DateTime = 5,
     Bytes = 6,
     Jsonb = 7,
+    Decimal = 8,
 }
 
 impl From<JsDataType> for cynos_core::DataType {
@@ -95,6 +104,7 @@ impl From<JsDataType> for cynos_core::DataType {
             JsDataType::DateTime => cynos_core::DataType::DateTime,
             JsDataType::Bytes => cynos_core::DataType::Bytes,
             JsDataType::Jsonb => cynos_core::DataType::Jsonb,
+            JsDataType::Decimal => cynos_core::DataType::Decimal,
         }
     }
 }
@@ -110,6 +120,7 @@ impl From<cynos_core::DataType> for JsDataType {
             cynos_core::DataType::DateTime => JsDataType::DateTime,
             cynos_core::DataType::Bytes => JsDataType::Bytes,
             cynos_core::DataType::Jsonb => JsDataType::Jsonb,
+            cynos_core::DataType::Decimal => JsDataType::Decimal,
         }
     }
 }
@@ -131,6 +142,23 @@ impl From<JsSortOrder> for cynos_query::ast::SortOrder {
     }
 }
 
+/// Placement of NULLs in an ORDER BY key.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JsNullsOrder {
+    First = 0,
+    Last = 1,
+}
+
+impl From<JsNullsOrder> for cynos_index::NullsOrder {
+    fn from(order: JsNullsOrder) -> Self {
+        match order {
+            JsNullsOrder::First => cynos_index::NullsOrder::First,
+            JsNullsOrder::Last => cynos_index::NullsOrder::Last,
+        }
+    }
+}
+
 /// Helper function to create a column reference.
 #[wasm_bindgen]
 pub fn col(name: &str) -> Column {
@@ -2,7 +2,7 @@ use crate::binary_protocol::SchemaLayout;
 use crate::query_engine::{CompiledPhysicalPlan, QueryResultSummary};
 use crate::reactive_bridge::{
     GraphqlDeltaObservable, GraphqlSubscriptionObservable, JsGraphqlSubscription,
-    JsIvmObservableQuery, JsObservableQuery, ReQueryObservable,
+    JsIvmObservableQuery, JsObservableQuery, ReQueryObservable, RowObservable,
 };
 use alloc::rc::Rc;
 use alloc::string::String;
@@ -102,6 +102,8 @@ pub(crate) struct SnapshotKernelPlan {
 pub(crate) struct DeltaKernelPlan {
     pub dataflow: DataflowNode,
     pub initial_rows: Vec<Row>,
+    /// Full input of the outermost LIMIT window, if the dataflow has one.
+    pub top_n_input_rows: Option<Vec<Row>>,
 }
 
 pub(crate) enum KernelPlan {
@@ -182,6 +184,7 @@ impl LivePlan {
         dependencies: LiveDependencySet,
         dataflow: DataflowNode,
         initial_rows: Vec<Row>,
+        top_n_input_rows: Option<Vec<Row>>,
         projection: RowsProjection,
         binary_layout: SchemaLayout,
     ) -> Self {
@@ -194,6 +197,7 @@ impl LivePlan {
             kernel: KernelPlan::Delta(DeltaKernelPlan {
                 dataflow,
                 initial_rows,
+                top_n_input_rows,
             }),
             adapter: AdapterPlan::RowsDelta(RowsDeltaAdapterPlan {
                 projection,
@@ -247,6 +251,7 @@ impl LivePlan {
             kernel: KernelPlan::Delta(DeltaKernelPlan {
                 dataflow,
                 initial_rows,
+                top_n_input_rows: None,
             }),
             adapter: AdapterPlan::GraphqlDelta(GraphqlDeltaAdapterPlan {
                 catalog,
@@ -310,10 +315,11 @@ impl LivePlan {
             }
         };
 
-        let observable = Rc::new(RefCell::new(ObservableQuery::with_initial(
-            kernel.dataflow,
-            kernel.initial_rows,
-        )));
+        let mut query = ObservableQuery::with_initial(kernel.dataflow, kernel.initial_rows);
+        if let Some(rows) = kernel.top_n_input_rows {
+            query.initialize_top_n_state(rows);
+        }
+        let observable = Rc::new(RefCell::new(query));
         registry
             .borrow_mut()
             .register_delta(DeltaSubscription::Rows(observable.clone()), &dependencies);
@@ -402,15 +408,33 @@ impl LivePlan {
 pub(crate) enum SnapshotSubscription {
     Rows(Rc<RefCell<ReQueryObservable>>),
     Graphql(Rc<RefCell<GraphqlSubscriptionObservable>>),
+    Row(Rc<RefCell<RowObservable>>),
 }
 
 impl SnapshotSubscription {
     fn subscription_count(&self) -> usize {
         match self {
             Self::Rows(query) => query.borrow().subscription_count(),
+            Self::Row(query) => query.borrow().subscription_count(),
             Self::Graphql(query) => query.borrow().subscription_count(),
         }
     }
+
+    fn refresh(&self) {
+        match self {
+            Self::Rows(query) => query.borrow_mut().refresh(),
+            Self::Row(query) => query.borrow_mut().refresh(),
+            Self::Graphql(query) => query.borrow_mut().refresh(),
+        }
+    }
+
+    fn ptr(&self) -> usize {
+        match self {
+            Self::Rows(query) => Rc::as_ptr(query) as *const () as usize,
+            Self::Row(query) => Rc::as_ptr(query) as *const () as usize,
+            Self::Graphql(query) => Rc::as_ptr(query) as *const () as usize,
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -427,12 +451,26 @@ impl DeltaSubscription {
         }
     }
 
+    fn ptr(&self) -> usize {
+        match self {
+            Self::Rows(query) => Rc::as_ptr(query) as *const () as usize,
+            Self::Graphql(query) => Rc::as_ptr(query) as *const () as usize,
+        }
+    }
+
     fn on_table_change(&self, table_id: TableId, deltas: Vec<Delta<Row>>) {
         match self {
             Self::Rows(query) => query.borrow_mut().on_table_change(table_id, deltas),
             Self::Graphql(query) => query.borrow_mut().on_table_change(table_id, deltas),
         }
     }
+
+    fn resync_from(&self, sources: Vec<(TableId, Vec<Row>)>) {
+        match self {
+            Self::Rows(query) => query.borrow_mut().resync_from(sources),
+            Self::Graphql(query) => query.borrow_mut().resync_from(sources),
+        }
+    }
 }
 
 pub(crate) struct LiveRegistry {
@@ -441,6 +479,8 @@ pub(crate) struct LiveRegistry {
     pending_changes: Rc<RefCell<HashMap<TableId, HashSet<u64>>>>,
     pending_deltas: Rc<RefCell<HashMap<TableId, Vec<Delta<Row>>>>>,
     flush_scheduled: Rc<RefCell<bool>>,
+    /// When false, table changes are dropped instead of queued
+    enabled: bool,
     self_ref: Option<Rc<RefCell<LiveRegistry>>>,
     #[cfg(target_arch = "wasm32")]
     flush_closure: Option<Closure<dyn FnMut(JsValue)>>,
@@ -454,6 +494,7 @@ impl LiveRegistry {
             pending_changes: Rc::new(RefCell::new(HashMap::new())),
             pending_deltas: Rc::new(RefCell::new(HashMap::new())),
             flush_scheduled: Rc::new(RefCell::new(false)),
+            enabled: true,
             self_ref: None,
             #[cfg(target_arch = "wasm32")]
             flush_closure: None,
@@ -464,6 +505,76 @@ impl LiveRegistry {
         self.self_ref = Some(self_ref);
     }
 
+    /// Returns false while change notifications are turned off.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Turns change notifications on or off.
+    ///
+    /// While disabled, table changes are dropped, so live queries fall
+    /// behind; call `resync` after re-enabling to bring them up to date.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Recomputes every live query from scratch, notifying subscribers whose
+    /// results changed. `table_rows` returns the current rows of a table.
+    pub fn resync(&mut self, table_rows: &dyn Fn(TableId) -> Vec<Row>) {
+        self.pending_changes.borrow_mut().clear();
+        self.pending_deltas.borrow_mut().clear();
+
+        let mut refreshed = HashSet::new();
+        for query in self.snapshot_queries.values().flatten() {
+            if refreshed.insert(query.ptr()) {
+                query.refresh();
+            }
+        }
+
+        // A delta query is registered under each table it reads; rebuild it
+        // once from all of them.
+        let mut delta_tables: HashMap<usize, (DeltaSubscription, Vec<TableId>)> = HashMap::new();
+        for (&table_id, queries) in &self.delta_queries {
+            for query in queries {
+                delta_tables
+                    .entry(query.ptr())
+                    .or_insert_with(|| (query.clone(), Vec::new()))
+                    .1
+                    .push(table_id);
+            }
+        }
+
+        let mut rows_by_table: HashMap<TableId, Vec<Row>> = HashMap::new();
+        for (_, (query, tables)) in delta_tables {
+            let sources = tables
+                .into_iter()
+                .map(|table_id| {
+                    let rows = rows_by_table
+                        .entry(table_id)
+                        .or_insert_with(|| table_rows(table_id));
+                    (table_id, rows.clone())
+                })
+                .collect();
+            query.resync_from(sources);
+        }
+
+        self.gc_dead_queries();
+    }
+
+    /// Re-runs the snapshot queries that read a dropped table, so their
+    /// subscribers see the failure, and forgets every registration under it.
+    pub fn on_table_dropped(&mut self, table_id: TableId) {
+        self.pending_changes.borrow_mut().remove(&table_id);
+        self.pending_deltas.borrow_mut().remove(&table_id);
+
+        if let Some(queries) = self.snapshot_queries.remove(&table_id) {
+            for query in &queries {
+                query.refresh();
+            }
+        }
+        self.delta_queries.remove(&table_id);
+    }
+
     pub fn register_snapshot(
         &mut self,
         query: SnapshotSubscription,
@@ -496,6 +607,8 @@ impl LiveRegistry {
                 HashMap<TableId, HashSet<u64>>,
             ),
         > = HashMap::new();
+        let mut merged_row: HashMap<usize, (Rc<RefCell<RowObservable>>, HashSet<u64>)> =
+            HashMap::new();
 
         for (table_id, changed_ids) in changes {
             if let Some(queries) = self.snapshot_queries.get(&table_id) {
@@ -513,6 +626,12 @@ impl LiveRegistry {
                                 .or_insert_with(|| (query.clone(), HashMap::new()));
                             entry.1.insert(table_id, changed_ids.clone());
                         }
+                        SnapshotSubscription::Row(query) => {
+                            let entry = merged_row
+                                .entry(Rc::as_ptr(query) as usize)
+                                .or_insert_with(|| (query.clone(), HashSet::new()));
+                            entry.1.extend(changed_ids.iter().copied());
+                        }
                     }
                 }
             }
@@ -525,9 +644,16 @@ impl LiveRegistry {
         for (_, (query, changes)) in merged_graphql {
             query.borrow_mut().on_change(&changes);
         }
+
+        for (_, (query, changed_ids)) in merged_row {
+            query.borrow_mut().on_change(&changed_ids);
+        }
     }
 
     pub fn on_table_change(&mut self, table_id: TableId, changed_ids: &HashSet<u64>) {
+        if !self.enabled {
+            return;
+        }
         {
             let mut pending = self.pending_changes.borrow_mut();
             pending
@@ -550,6 +676,9 @@ impl LiveRegistry {
         deltas: Vec<Delta<Row>>,
         changed_ids: &HashSet<u64>,
     ) {
+        if !self.enabled {
+            return;
+        }
         {
             let mut pending = self.pending_deltas.borrow_mut();
             pending
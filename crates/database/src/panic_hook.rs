@@ -0,0 +1,128 @@
+//! Panic reporting for the WASM build.
+//!
+//! Without a hook, a Rust panic inside the WASM module surfaces in JS as an
+//! opaque `RuntimeError: unreachable executed`. The hook installed here
+//! throws a `CynosError` with code `PANIC` instead, carrying the panic message
+//! and source location.
+//!
+//! The hook is installed automatically by `init()` when the `panic-hook`
+//! feature is enabled, and can also be installed at runtime from JS with
+//! `installPanicHook()`.
+//!
+//! A panic leaves the module in an undefined state: Rust destructors do not
+//! run, so a `RefCell` borrowed at the time of the panic stays borrowed. The
+//! database instance that panicked should be discarded after the error is
+//! reported.
+//!
+//! Manual repro (build with the `panic-hook` and `testing-exports` features):
+//!
+//! ```javascript
+//! installPanicHook();
+//! try {
+//!   triggerPanicForTesting('boom');
+//! } catch (e) {
+//!   console.log(e.code, e.message); // "PANIC", "Rust panic: boom (at src/panic_hook.rs:..)"
+//! }
+//! ```
+
+use crate::error::CynosError;
+use alloc::format;
+use alloc::string::{String, ToString};
+use std::panic::{self, PanicHookInfo};
+use std::sync::Once;
+use wasm_bindgen::prelude::*;
+
+static INSTALL: Once = Once::new();
+
+/// Installs the panic hook. Repeated calls are no-ops.
+#[wasm_bindgen(js_name = installPanicHook)]
+pub fn install_panic_hook() {
+    INSTALL.call_once(|| {
+        panic::set_hook(alloc::boxed::Box::new(|info| {
+            let error = CynosError::Panic(describe_panic(info));
+            web_sys::console::error_1(&JsValue::from_str(error.message()));
+            wasm_bindgen::throw_val(error.into());
+        }));
+    });
+}
+
+/// Panics with the given message. Exists so the panic hook can be exercised
+/// from JS; it is only exported when the `testing-exports` feature is on.
+#[cfg(feature = "testing-exports")]
+#[wasm_bindgen(js_name = triggerPanicForTesting)]
+pub fn trigger_panic_for_testing(message: &str) {
+    panic!("{}", message);
+}
+
+/// Formats a panic payload and its location into a single message.
+pub(crate) fn describe_panic(info: &PanicHookInfo<'_>) -> String {
+    let payload = info.payload();
+    let message = if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".to_string()
+    };
+
+    match info.location() {
+        Some(location) => format!(
+            "Rust panic: {} (at {}:{}:{})",
+            message,
+            location.file(),
+            location.line(),
+            location.column()
+        ),
+        None => format!("Rust panic: {}", message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    /// Serializes the tests that replace the process-wide panic hook.
+    static HOOK_LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn test_describe_panic_includes_message_and_location() {
+        static CAPTURED: Mutex<Option<String>> = Mutex::new(None);
+
+        let _guard = HOOK_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        // Panics from tests running on other threads go to the previous hook
+        // so they neither overwrite the capture nor lose their output.
+        let this_thread = thread::current().id();
+        let previous = Arc::new(panic::take_hook());
+        let fallback = Arc::clone(&previous);
+        panic::set_hook(alloc::boxed::Box::new(move |info| {
+            if thread::current().id() == this_thread {
+                *CAPTURED.lock().unwrap() = Some(describe_panic(info));
+            } else {
+                fallback(info);
+            }
+        }));
+        let result = panic::catch_unwind(|| {
+            let values: alloc::vec::Vec<i32> = alloc::vec::Vec::new();
+            values.first().copied().expect("row value missing")
+        });
+        drop(panic::take_hook());
+        panic::set_hook(Arc::into_inner(previous).unwrap());
+
+        assert!(result.is_err());
+        let message = CAPTURED.lock().unwrap().take().unwrap();
+        assert!(
+            message.starts_with("Rust panic: row value missing (at "),
+            "{message}"
+        );
+        assert!(message.contains("panic_hook.rs"), "{message}");
+    }
+
+    #[test]
+    fn test_panic_error_has_panic_code() {
+        let error = CynosError::Panic("Rust panic: boom".into());
+        assert_eq!(error.code(), "PANIC");
+        assert_eq!(error.message(), "Rust panic: boom");
+    }
+}
@@ -4,9 +4,13 @@
 //! database queries.
 
 use crate::binary_protocol::{SchemaLayout, SchemaLayoutCache};
-use crate::convert::{js_array_to_rows, js_to_value, projected_rows_to_js_array, rows_to_js_array};
-use crate::dataflow_compiler::compile_to_dataflow;
-use crate::expr::{Expr, ExprInner};
+use crate::convert::{
+    apply_column_updates, check_column_updates, js_array_to_rows, js_to_value, metadata_to_js,
+    projected_rows_to_js_array, rows_to_js_array, set_property, value_to_js,
+};
+use crate::dataflow_compiler::{compile_to_dataflow, top_n_input_plan};
+use crate::error::CynosError;
+use crate::expr::{copy_from_js, Column, Expr, ExprInner};
 use crate::live_runtime::{LiveDependencySet, LivePlan, LiveRegistry, RowsProjection};
 use crate::query_engine::{
     compile_cached_plan, compile_plan, execute_compiled_physical_plan,
@@ -14,18 +18,22 @@ use crate::query_engine::{
     CompiledPhysicalPlan,
 };
 use crate::reactive_bridge::{JsChangesStream, JsIvmObservableQuery, JsObservableQuery};
-use crate::JsSortOrder;
+use crate::table::ComputedDefaults;
+use crate::{JsNullsOrder, JsSortOrder};
 use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
 use alloc::rc::Rc;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use core::cell::RefCell;
 use cynos_core::schema::Table;
-use cynos_core::{reserve_row_ids, DataType, Row, Value};
+use cynos_core::{reserve_row_ids, DataType, Row, RowId, Value};
 use cynos_incremental::Delta;
-use cynos_query::ast::{AggregateFunc, SortOrder};
+use cynos_index::NullsOrder;
+use cynos_query::ast::{AggregateFunc, BinaryOp, Expr as AstExpr, SortOrder};
+use cynos_query::optimizer::ImplicitJoinsPass;
 use cynos_query::plan_cache::{compute_plan_fingerprint, PlanCache};
-use cynos_query::planner::LogicalPlan;
+use cynos_query::planner::{JoinAlgorithm, LogicalPlan};
 use cynos_reactive::TableId;
 use cynos_storage::TableCache;
 use wasm_bindgen::prelude::*;
@@ -37,17 +45,26 @@ pub struct SelectBuilder {
     query_registry: Rc<RefCell<LiveRegistry>>,
     table_id_map: Rc<RefCell<hashbrown::HashMap<String, TableId>>>,
     schema_layout_cache: Rc<RefCell<SchemaLayoutCache>>,
+    schema_epoch: Rc<RefCell<u64>>,
     plan_cache: Rc<RefCell<PlanCache>>,
     columns: JsValue,
     from_table: Option<String>,
     where_clause: Option<Expr>,
+    where_ids: Option<Vec<RowId>>,
     order_by: Vec<(String, SortOrder)>,
+    order_by_exprs: Vec<(Expr, SortOrder, Option<NullsOrder>)>,
     limit_val: Option<usize>,
     offset_val: Option<usize>,
     joins: Vec<JoinClause>,
     group_by_cols: Vec<String>,
     aggregates: Vec<(AggregateFunc, Option<String>)>, // (func, column_name or None for COUNT(*))
     frozen_base: Option<FrozenQueryBase>,
+    with_deleted: bool,
+    allow_cross_product: bool,
+    /// NULL placement for ORDER BY keys that do not set their own.
+    default_nulls: Option<NullsOrder>,
+    /// Whether binary results carry Int64 values as exact 64-bit integers.
+    int64_as_bigint: bool,
 }
 
 #[wasm_bindgen]
@@ -56,16 +73,17 @@ pub struct PreparedSelectQuery {
     compiled_plan: CompiledPhysicalPlan,
     result_mapper: QueryResultMapper,
     binary_layout: SchemaLayout,
+    int64_as_bigint: bool,
 }
 
 #[derive(Clone)]
 enum QueryResultMapper {
-    Full { schema: Table },
+    Full { schema: Box<Table> },
     Columns { column_names: Vec<String> },
 }
 
 impl QueryResultMapper {
-    fn map_rows(&self, rows: &[Rc<Row>]) -> JsValue {
+    fn map_rows(&self, rows: &[Rc<Row>]) -> Result<JsValue, JsValue> {
         match self {
             Self::Full { schema } => rows_to_js_array(rows, schema),
             Self::Columns { column_names } => projected_rows_to_js_array(rows, column_names),
@@ -78,6 +96,8 @@ struct OutputColumn {
     name: String,
     data_type: DataType,
     is_nullable: bool,
+    /// Metadata of the source column; empty for computed columns.
+    metadata: BTreeMap<String, String>,
 }
 
 #[derive(Clone)]
@@ -167,6 +187,8 @@ struct JoinClause {
     alias: Option<String>, // Optional alias (for column reference)
     condition: Expr,
     join_type: JoinType,
+    algorithm: Option<String>, // Optional join algorithm hint from the join options
+    null_safe: bool,           // Whether NULL join keys match each other
 }
 
 impl JoinClause {
@@ -174,6 +196,92 @@ impl JoinClause {
     fn reference_name(&self) -> &str {
         self.alias.as_deref().unwrap_or(&self.table)
     }
+
+    /// Resolves the join algorithm hint against the compiled join condition.
+    fn algorithm_hint(&self, condition: &AstExpr) -> Result<Option<JoinAlgorithm>, JsValue> {
+        let Some(name) = self.algorithm.as_deref() else {
+            return Ok(None);
+        };
+
+        let algorithm = match name {
+            "hash" => JoinAlgorithm::Hash,
+            "sortMerge" => JoinAlgorithm::SortMerge,
+            "nestedLoop" => JoinAlgorithm::NestedLoop,
+            _ => {
+                return Err(CynosError::invalid_argument(alloc::format!(
+                    "Unknown join algorithm '{}' (expected 'hash', 'sortMerge', or 'nestedLoop')",
+                    name
+                ))
+                .into())
+            }
+        };
+
+        if !algorithm.supports_condition(condition) {
+            return Err(CynosError::invalid_argument(alloc::format!(
+                "Join algorithm '{}' requires an equi-join condition on '{}'",
+                name,
+                self.reference_name()
+            ))
+            .into());
+        }
+
+        Ok(Some(algorithm))
+    }
+
+    /// Compiles the join condition, turning its column equalities into
+    /// `IS NOT DISTINCT FROM` when the join is NULL-safe.
+    fn compile_condition(
+        &self,
+        get_col_info: &impl Fn(&str) -> Option<(String, usize, DataType)>,
+    ) -> AstExpr {
+        let condition = self.condition.to_ast_with_table(get_col_info);
+        if self.null_safe {
+            Self::null_safe_keys(condition)
+        } else {
+            condition
+        }
+    }
+
+    fn null_safe_keys(condition: AstExpr) -> AstExpr {
+        match condition {
+            AstExpr::BinaryOp {
+                left,
+                op: BinaryOp::Eq,
+                right,
+            } if matches!(*left, AstExpr::Column(_)) && matches!(*right, AstExpr::Column(_)) => {
+                AstExpr::is_not_distinct_from(*left, *right)
+            }
+            AstExpr::BinaryOp {
+                left,
+                op: BinaryOp::And,
+                right,
+            } => AstExpr::and(Self::null_safe_keys(*left), Self::null_safe_keys(*right)),
+            condition => condition,
+        }
+    }
+
+    /// Reads the `nullSafe` flag from a join options object.
+    fn parse_null_safe(options: Option<&JsValue>) -> bool {
+        options
+            .filter(|value| value.is_object())
+            .and_then(|options| js_sys::Reflect::get(options, &JsValue::from_str("nullSafe")).ok())
+            .and_then(|flag| flag.as_bool())
+            .unwrap_or(false)
+    }
+
+    /// Reads the `algorithm` field from a join options object.
+    fn parse_algorithm(options: Option<JsValue>) -> Option<String> {
+        let options = options.filter(|value| value.is_object())?;
+        let algorithm = js_sys::Reflect::get(&options, &JsValue::from_str("algorithm")).ok()?;
+        if algorithm.is_undefined() || algorithm.is_null() {
+            return None;
+        }
+        Some(
+            algorithm
+                .as_string()
+                .unwrap_or_else(|| alloc::format!("{:?}", algorithm)),
+        )
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -190,6 +298,7 @@ impl SelectBuilder {
         query_registry: Rc<RefCell<LiveRegistry>>,
         table_id_map: Rc<RefCell<hashbrown::HashMap<String, TableId>>>,
         schema_layout_cache: Rc<RefCell<SchemaLayoutCache>>,
+        schema_epoch: Rc<RefCell<u64>>,
         plan_cache: Rc<RefCell<PlanCache>>,
         columns: JsValue,
     ) -> Self {
@@ -198,18 +307,87 @@ impl SelectBuilder {
             query_registry,
             table_id_map,
             schema_layout_cache,
+            schema_epoch,
             plan_cache,
             columns,
             from_table: None,
             where_clause: None,
+            where_ids: None,
             order_by: Vec::new(),
+            order_by_exprs: Vec::new(),
             limit_val: None,
             offset_val: None,
             joins: Vec::new(),
             group_by_cols: Vec::new(),
             aggregates: Vec::new(),
             frozen_base: None,
+            with_deleted: false,
+            allow_cross_product: false,
+            default_nulls: None,
+            int64_as_bigint: false,
+        }
+    }
+
+    /// Appends an ORDER BY key placing NULLs as `nulls` asks.
+    ///
+    /// NULL sorts below every value, so it leads an ascending key and trails
+    /// a descending one. Any other placement is a leading IS NULL key, left
+    /// out for keys that cannot be NULL so that an index can still supply
+    /// the order.
+    fn push_order_key(
+        &self,
+        order_exprs: &mut Vec<(AstExpr, SortOrder)>,
+        key: AstExpr,
+        order: SortOrder,
+        nulls: Option<NullsOrder>,
+    ) {
+        let natural = match order {
+            SortOrder::Asc => NullsOrder::First,
+            SortOrder::Desc => NullsOrder::Last,
+        };
+        let nulls = nulls.filter(|_| self.order_key_nullable(&key));
+        match nulls {
+            Some(NullsOrder::First) if natural != NullsOrder::First => {
+                order_exprs.push((AstExpr::is_null(key.clone()), SortOrder::Desc));
+            }
+            Some(NullsOrder::Last) if natural != NullsOrder::Last => {
+                order_exprs.push((AstExpr::is_null(key.clone()), SortOrder::Asc));
+            }
+            _ => {}
+        }
+        order_exprs.push((key, order));
+    }
+
+    /// Returns whether an ORDER BY key can be NULL. Only plain columns of a
+    /// single-table query are known not to be; any other key is assumed to.
+    fn order_key_nullable(&self, key: &AstExpr) -> bool {
+        if self.frozen_base.is_some()
+            || !self.joins.is_empty()
+            || !self.group_by_cols.is_empty()
+            || !self.aggregates.is_empty()
+        {
+            return true;
+        }
+        let (AstExpr::Column(col), Some(from)) = (key, &self.from_table) else {
+            return true;
+        };
+        if &col.table != from {
+            return true;
         }
+        self.cache
+            .borrow()
+            .get_table(from)
+            .and_then(|store| {
+                let column = store.schema().columns().get(col.index)?;
+                Some(column.is_nullable())
+            })
+            .unwrap_or(true)
+    }
+
+    /// Sets the NULL placement used by ORDER BY keys without their own.
+    pub(crate) fn with_default_nulls_order(mut self, nulls: Option<NullsOrder>) -> Self {
+        self.default_nulls = nulls;
+        self
     }
 
     fn get_schema(&self) -> Option<Table> {
@@ -359,6 +537,27 @@ impl SelectBuilder {
         None
     }
 
+    /// Returns the metadata of a source column, given the table reference
+    /// (table name or join alias) and the table-relative column index.
+    fn source_column_metadata(&self, table_ref: &str, index: usize) -> BTreeMap<String, String> {
+        let table_name = self
+            .joins
+            .iter()
+            .find(|join| join.reference_name() == table_ref)
+            .map_or(table_ref, |join| join.table.as_str());
+        self.cache
+            .borrow()
+            .get_table(table_name)
+            .and_then(|store| {
+                store
+                    .schema()
+                    .columns()
+                    .get(index)
+                    .map(|col| col.metadata().clone())
+            })
+            .unwrap_or_default()
+    }
+
     /// Parses the columns JsValue into a list of column names.
     /// Returns None if selecting all columns (empty array, undefined, or contains "*").
     fn parse_columns(&self) -> Option<Vec<String>> {
@@ -407,10 +606,16 @@ impl SelectBuilder {
     }
 
     /// Builds the scan/join root for a non-set-operation query.
-    fn build_source_plan(&self, table_name: &str) -> LogicalPlan {
-        let mut plan = LogicalPlan::Scan {
-            table: table_name.to_string(),
-        };
+    fn build_source_plan(&self, table_name: &str) -> Result<LogicalPlan, JsValue> {
+        let mut plan = self.live_rows(
+            table_name,
+            match &self.where_ids {
+                Some(ids) => LogicalPlan::fetch_by_ids(table_name, ids.clone()),
+                None => LogicalPlan::Scan {
+                    table: table_name.to_string(),
+                },
+            },
+        );
 
         let mut table_offsets: hashbrown::HashMap<String, usize> = hashbrown::HashMap::new();
 
@@ -419,9 +624,12 @@ impl SelectBuilder {
             let mut current_offset = schema.columns().len();
 
             for join in &self.joins {
-                let right_plan = LogicalPlan::Scan {
-                    table: join.table.clone(),
-                };
+                let right_plan = self.live_rows(
+                    &join.table,
+                    LogicalPlan::Scan {
+                        table: join.table.clone(),
+                    },
+                );
 
                 let ref_name = join.reference_name().to_string();
                 table_offsets.insert(ref_name.clone(), current_offset);
@@ -429,13 +637,16 @@ impl SelectBuilder {
                 let get_col_info = |name: &str| {
                     self.get_column_info_for_join_with_offsets_alias(name, join, &table_offsets)
                 };
-                let ast_condition = join.condition.to_ast_with_table(&get_col_info);
+                let ast_condition = join.compile_condition(&get_col_info);
+                let algorithm = join.algorithm_hint(&ast_condition)?;
+                self.check_cross_product(join, &plan, &right_plan, &ast_condition)?;
 
                 plan = match join.join_type {
                     JoinType::Inner => LogicalPlan::inner_join(plan, right_plan, ast_condition),
                     JoinType::Left => LogicalPlan::left_join(plan, right_plan, ast_condition),
                     JoinType::Right => LogicalPlan::left_join(right_plan, plan, ast_condition),
-                };
+                }
+                .with_join_algorithm(algorithm);
 
                 if let Some(store) = self.cache.borrow().get_table(&join.table) {
                     current_offset += store.schema().columns().len();
@@ -443,122 +654,404 @@ impl SelectBuilder {
             }
         } else {
             for join in &self.joins {
-                let right_plan = LogicalPlan::Scan {
-                    table: join.table.clone(),
-                };
+                let right_plan = self.live_rows(
+                    &join.table,
+                    LogicalPlan::Scan {
+                        table: join.table.clone(),
+                    },
+                );
 
                 let get_col_info = |name: &str| self.get_column_info_for_join(name, &join.table);
-                let ast_condition = join.condition.to_ast_with_table(&get_col_info);
+                let ast_condition = join.compile_condition(&get_col_info);
+                let algorithm = join.algorithm_hint(&ast_condition)?;
+                self.check_cross_product(join, &plan, &right_plan, &ast_condition)?;
 
                 plan = match join.join_type {
                     JoinType::Inner => LogicalPlan::inner_join(plan, right_plan, ast_condition),
                     JoinType::Left => LogicalPlan::left_join(plan, right_plan, ast_condition),
                     JoinType::Right => LogicalPlan::left_join(right_plan, plan, ast_condition),
-                };
+                }
+                .with_join_algorithm(algorithm);
             }
         }
 
-        plan
+        Ok(plan)
     }
 
-    /// Applies WHERE / GROUP BY / ORDER BY / LIMIT / projection clauses on top of a root plan.
-    fn apply_query_modifiers(&self, mut plan: LogicalPlan) -> LogicalPlan {
-        if let Some(ref predicate) = self.where_clause {
-            let get_col_info = |name: &str| self.get_modifier_column_info(name);
-            let ast_predicate = predicate.to_ast_with_table(&get_col_info);
-            plan = LogicalPlan::Filter {
-                input: Box::new(plan),
-                predicate: ast_predicate,
-            };
+    /// Rejects an inner join whose condition does not relate the joined table
+    /// to the tables before it, unless `allowCrossProduct()` was called. The
+    /// optimizer runs such a join as a cross product, and it is nearly always
+    /// a mistyped condition. The check looks only at the condition and the
+    /// plan, never at table sizes, so a query that builds today keeps
+    /// building as its tables grow.
+    fn check_cross_product(
+        &self,
+        join: &JoinClause,
+        left: &LogicalPlan,
+        right: &LogicalPlan,
+        condition: &AstExpr,
+    ) -> Result<(), JsValue> {
+        if self.allow_cross_product
+            || join.join_type != JoinType::Inner
+            || ImplicitJoinsPass.is_join_predicate(condition, left, right)
+        {
+            return Ok(());
+        }
+
+        Err(CynosError::invalid_argument(alloc::format!(
+            "Join condition for '{}' does not relate it to {}, so the join would be a cross product; \
+             check the condition or call allowCrossProduct()",
+            join.reference_name(),
+            left.collect_tables().join(", ")
+        ))
+        .into())
+    }
+
+    /// Applies the WHERE clause, if any, on top of a root plan.
+    fn apply_where(&self, plan: LogicalPlan) -> Result<LogicalPlan, JsValue> {
+        let Some(predicate) = &self.where_clause else {
+            return Ok(plan);
+        };
+        let get_col_info = |name: &str| self.get_modifier_column_info(name);
+        if let Some(column) = predicate.find_unresolved_column(&get_col_info) {
+            return Err(self.unknown_column_error(&column));
+        }
+        Ok(LogicalPlan::Filter {
+            input: Box::new(plan),
+            predicate: predicate.to_ast_with_table(&get_col_info),
+        })
+    }
+
+    /// Filters `source`, a read of `table`, down to its live rows. Filtering
+    /// each table where it is read keeps the filter on the right columns
+    /// and leaves the NULL-padded rows of outer joins alone.
+    fn live_rows(&self, table: &str, source: LogicalPlan) -> LogicalPlan {
+        match self.soft_delete_filter(table) {
+            Some(predicate) => LogicalPlan::Filter {
+                input: Box::new(source),
+                predicate,
+            },
+            None => source,
+        }
+    }
+
+    /// Returns `<soft-delete column> IS NULL` for `table`, unless the table
+    /// has no soft-delete column, `withDeleted()` was called, or the query is
+    /// a UNION whose inputs were filtered on their own.
+    fn soft_delete_filter(&self, table: &str) -> Option<AstExpr> {
+        if self.with_deleted || self.frozen_base.is_some() {
+            return None;
         }
+        let cache = self.cache.borrow();
+        let column = cache.get_table(table)?.schema().soft_delete_column()?;
+        Some(AstExpr::is_null(AstExpr::column(
+            table,
+            column.name(),
+            column.index(),
+        )))
+    }
 
+    /// Runs the query projected to `column` and returns that column's values
+    /// as a flat JS array.
+    fn pluck_values(
+        &self,
+        cache: &TableCache,
+        table_name: &str,
+        column: &str,
+    ) -> Result<JsValue, JsValue> {
         if !self.group_by_cols.is_empty() || !self.aggregates.is_empty() {
-            let group_by_exprs: Vec<_> = self
+            return Err(CynosError::invalid_operation(
+                "pluck() cannot be combined with groupBy() or aggregates",
+            )
+            .into());
+        }
+        if let Some(selected) = self.parse_columns() {
+            if selected.len() != 1 || selected[0] != column {
+                return Err(CynosError::invalid_operation(alloc::format!(
+                    "pluck({}) needs a single selected column, but the query selects {}",
+                    column,
+                    selected.join(", ")
+                ))
+                .into());
+            }
+        }
+
+        let root = match &self.frozen_base {
+            Some(base) => base.plan.clone(),
+            None => self.build_source_plan(table_name)?,
+        };
+        let plan = self.apply_query_modifiers_projecting(
+            root,
+            Some(alloc::vec![column.to_string()]),
+            None,
+        )?;
+        let rows = execute_plan(cache, table_name, plan).map_err(CynosError::from)?;
+
+        let values = js_sys::Array::new_with_length(rows.len() as u32);
+        for (i, row) in rows.iter().enumerate() {
+            let value = row.get(0).map_or(JsValue::NULL, value_to_js);
+            values.set(i as u32, value);
+        }
+        Ok(values.into())
+    }
+
+    /// Runs a query with one aggregate and no grouping or projection and
+    /// returns the aggregate's value.
+    fn scalar_value(&self, cache: &TableCache, table_name: &str) -> Result<JsValue, JsValue> {
+        if !self.group_by_cols.is_empty() {
+            return Err(CynosError::invalid_operation(
+                "execScalar() cannot be combined with groupBy()",
+            )
+            .into());
+        }
+        if self.aggregates.len() != 1 {
+            return Err(CynosError::invalid_operation(alloc::format!(
+                "execScalar() needs exactly one aggregate, but the query has {}",
+                self.aggregates.len()
+            ))
+            .into());
+        }
+        if let Some(selected) = self.parse_columns() {
+            return Err(CynosError::invalid_operation(alloc::format!(
+                "execScalar() cannot select columns, but the query selects {}",
+                selected.join(", ")
+            ))
+            .into());
+        }
+
+        let plan = self.build_logical_plan(table_name)?;
+        let rows = execute_plan(cache, table_name, plan).map_err(CynosError::from)?;
+        match rows.as_slice() {
+            [row] if row.len() == 1 => Ok(row.get(0).map_or(JsValue::NULL, value_to_js)),
+            _ => Err(
+                CynosError::invalid_operation("execScalar() expected one row with one value")
+                    .into(),
+            ),
+        }
+    }
+
+    /// Counts the rows matching the query for `scalarCount()`, rejecting the
+    /// clauses a single count cannot honour.
+    fn scalar_count_value(&self, cache: &TableCache, table_name: &str) -> Result<usize, JsValue> {
+        if !self.group_by_cols.is_empty() {
+            return Err(CynosError::invalid_operation(
+                "scalarCount() cannot be combined with groupBy()",
+            )
+            .into());
+        }
+        if self.limit_val.is_some() || self.offset_val.is_some() {
+            return Err(CynosError::invalid_operation(
+                "scalarCount() cannot be combined with limit() or offset()",
+            )
+            .into());
+        }
+        self.count_matching_rows(cache, table_name)
+    }
+
+    /// Runs the query with its LIMIT capped at `cap` and maps the rows to JS
+    /// objects. The cap lets the planner stop early, or read only the first
+    /// entries of an ordered index.
+    fn fetch_at_most(
+        &self,
+        cache: &TableCache,
+        table_name: &str,
+        cap: usize,
+    ) -> Result<js_sys::Array, JsValue> {
+        let store = cache
+            .get_table(table_name)
+            .ok_or_else(|| CynosError::table_not_found(table_name))?;
+        let root = match &self.frozen_base {
+            Some(base) => base.plan.clone(),
+            None => self.build_source_plan(table_name)?,
+        };
+        let plan = self.apply_query_modifiers_projecting(root, self.parse_columns(), Some(cap))?;
+        let rows = execute_plan(cache, table_name, plan).map_err(CynosError::from)?;
+        let schema = store.schema().clone();
+        Ok(js_sys::Array::from(&self.map_rows_to_js(&rows, &schema)?))
+    }
+
+    /// Returns the first row of the query, or `null` when nothing matches.
+    fn first_row(&self, cache: &TableCache, table_name: &str) -> Result<JsValue, JsValue> {
+        let rows = self.fetch_at_most(cache, table_name, 1)?;
+        Ok(if rows.length() == 0 {
+            JsValue::NULL
+        } else {
+            rows.get(0)
+        })
+    }
+
+    /// Returns the only row of the query, or `null` when nothing matches.
+    /// Fails if more than one row matches.
+    fn single_row(&self, cache: &TableCache, table_name: &str) -> Result<JsValue, JsValue> {
+        let rows = self.fetch_at_most(cache, table_name, 2)?;
+        match rows.length() {
+            0 => Ok(JsValue::NULL),
+            1 => Ok(rows.get(0)),
+            _ => Err(CynosError::invalid_operation(
+                "single() expected at most one row, but the query matched several",
+            )
+            .into()),
+        }
+    }
+
+    /// Counts the rows the FROM, JOIN and WHERE clauses match, ignoring
+    /// grouping, ordering, paging and projection. An unfiltered single-table
+    /// query reads the table's row count without scanning.
+    fn count_matching_rows(&self, cache: &TableCache, table_name: &str) -> Result<usize, JsValue> {
+        let store = cache
+            .get_table(table_name)
+            .ok_or_else(|| CynosError::table_not_found(table_name))?;
+        if self.frozen_base.is_none()
+            && self.where_clause.is_none()
+            && self.where_ids.is_none()
+            && self.joins.is_empty()
+            && self.soft_delete_filter(table_name).is_none()
+        {
+            return Ok(store.len());
+        }
+
+        let root = match &self.frozen_base {
+            Some(base) => base.plan.clone(),
+            None => self.build_source_plan(table_name)?,
+        };
+        let plan = LogicalPlan::aggregate(
+            self.apply_where(root)?,
+            Vec::new(),
+            alloc::vec![(AggregateFunc::Count, AstExpr::literal(Value::Int64(1)))],
+        );
+        let rows = execute_plan(cache, table_name, plan).map_err(CynosError::from)?;
+        Ok(match rows.first().and_then(|row| row.get(0)) {
+            Some(Value::Int64(count)) => *count as usize,
+            _ => 0,
+        })
+    }
+
+    /// Applies WHERE / GROUP BY / ORDER BY / LIMIT / projection clauses on top of a root plan.
+    fn apply_query_modifiers(&self, plan: LogicalPlan) -> Result<LogicalPlan, JsValue> {
+        self.apply_query_modifiers_projecting(plan, self.parse_columns(), None)
+    }
+
+    /// Like `apply_query_modifiers`, but projects `columns` instead of the
+    /// builder's own select list, and returns at most `limit_cap` rows when
+    /// it is set.
+    fn apply_query_modifiers_projecting(
+        &self,
+        plan: LogicalPlan,
+        columns: Option<Vec<String>>,
+        limit_cap: Option<usize>,
+    ) -> Result<LogicalPlan, JsValue> {
+        let mut plan = self.apply_where(plan)?;
+
+        let is_aggregate = !self.group_by_cols.is_empty() || !self.aggregates.is_empty();
+        if is_aggregate {
+            let group_by_exprs = self
                 .group_by_cols
                 .iter()
-                .filter_map(|col| {
-                    self.get_column_info_for_projection(col)
-                        .map(|(tbl, idx, _)| {
-                            let col_name = if let Some(dot_pos) = col.find('.') {
-                                &col[dot_pos + 1..]
-                            } else {
-                                col.as_str()
-                            };
-                            cynos_query::ast::Expr::column(&tbl, col_name, idx)
-                        })
+                .map(|col| {
+                    let (tbl, idx, _) = self.resolve_source_column(col)?;
+                    let col_name = if let Some(dot_pos) = col.find('.') {
+                        &col[dot_pos + 1..]
+                    } else {
+                        col.as_str()
+                    };
+                    Ok(cynos_query::ast::Expr::column(&tbl, col_name, idx))
                 })
-                .collect();
+                .collect::<Result<Vec<_>, JsValue>>()?;
 
-            let agg_exprs: Vec<_> = self
+            let agg_exprs = self
                 .aggregates
                 .iter()
-                .filter_map(|(func, col_opt)| {
+                .map(|(func, col_opt)| {
                     if let Some(col) = col_opt {
-                        self.get_column_info_for_projection(col)
-                            .map(|(tbl, idx, _)| {
-                                let col_name = if let Some(dot_pos) = col.find('.') {
-                                    &col[dot_pos + 1..]
-                                } else {
-                                    col.as_str()
-                                };
-                                (*func, cynos_query::ast::Expr::column(&tbl, col_name, idx))
-                            })
+                        let (tbl, idx, _) = self.resolve_source_column(col)?;
+                        let col_name = if let Some(dot_pos) = col.find('.') {
+                            &col[dot_pos + 1..]
+                        } else {
+                            col.as_str()
+                        };
+                        Ok((*func, cynos_query::ast::Expr::column(&tbl, col_name, idx)))
                     } else {
-                        Some((
+                        Ok((
                             *func,
                             cynos_query::ast::Expr::literal(cynos_core::Value::Int64(1)),
                         ))
                     }
                 })
-                .collect();
+                .collect::<Result<Vec<_>, JsValue>>()?;
 
             plan = LogicalPlan::aggregate(plan, group_by_exprs, agg_exprs);
         }
 
-        if !self.order_by.is_empty() {
-            let order_exprs: Vec<_> = self
-                .order_by
-                .iter()
-                .filter_map(|(col, order)| {
-                    self.get_order_column_info(col).map(|(tbl, idx, _)| {
-                        let col_name = if let Some(dot_pos) = col.find('.') {
-                            &col[dot_pos + 1..]
-                        } else {
-                            col.as_str()
-                        };
-                        (cynos_query::ast::Expr::column(&tbl, col_name, idx), *order)
-                    })
-                })
-                .collect();
+        let mut order_exprs = Vec::new();
+        for (col, order) in &self.order_by {
+            let (tbl, idx, _) = self.resolve_order_column(col)?;
+            let col_name = if let Some(dot_pos) = col.find('.') {
+                &col[dot_pos + 1..]
+            } else {
+                col.as_str()
+            };
+            self.push_order_key(
+                &mut order_exprs,
+                AstExpr::column(&tbl, col_name, idx),
+                *order,
+                self.default_nulls,
+            );
+        }
+        for (expr, order, nulls) in &self.order_by_exprs {
+            let get_col_info = |name: &str| self.get_modifier_column_info(name);
+            if let Some(column) = expr.find_unresolved_column(&get_col_info) {
+                return Err(self.unknown_column_error(&column));
+            }
+            let ast_expr = expr.to_ast_with_table(&get_col_info);
+            self.push_order_key(
+                &mut order_exprs,
+                ast_expr,
+                *order,
+                nulls.or(self.default_nulls),
+            );
+        }
+
+        if !order_exprs.is_empty() {
             plan = LogicalPlan::Sort {
                 input: Box::new(plan),
                 order_by: order_exprs,
             };
         }
 
-        if self.limit_val.is_some() || self.offset_val.is_some() {
+        let limit = match (self.limit_val, limit_cap) {
+            (Some(limit), Some(cap)) => Some(limit.min(cap)),
+            (limit, cap) => limit.or(cap),
+        };
+        if limit.is_some() || self.offset_val.is_some() {
             plan = LogicalPlan::Limit {
                 input: Box::new(plan),
-                limit: self.limit_val.unwrap_or(1_000_000_000),
+                limit: limit.unwrap_or(1_000_000_000),
                 offset: self.offset_val.unwrap_or(0),
             };
         }
 
-        if let Some(cols) = self.parse_columns() {
-            let project_exprs: Vec<_> = cols
+        if let Some(cols) = columns {
+            if is_aggregate {
+                // Aggregate output is always the grouping columns followed by the
+                // aggregates, so the select list only has to be consistent with it.
+                for col in &cols {
+                    self.ensure_grouped_column(col)?;
+                }
+                return Ok(plan);
+            }
+
+            let project_exprs = cols
                 .iter()
-                .filter_map(|col| {
-                    self.get_column_info_for_projection(col)
-                        .map(|(tbl, idx, _)| {
-                            let col_name = if let Some(dot_pos) = col.find('.') {
-                                &col[dot_pos + 1..]
-                            } else {
-                                col.as_str()
-                            };
-                            cynos_query::ast::Expr::column(&tbl, col_name, idx)
-                        })
+                .map(|col| {
+                    let (tbl, idx, _) = self.resolve_source_column(col)?;
+                    let col_name = if let Some(dot_pos) = col.find('.') {
+                        &col[dot_pos + 1..]
+                    } else {
+                        col.as_str()
+                    };
+                    Ok(cynos_query::ast::Expr::column(&tbl, col_name, idx))
                 })
-                .collect();
+                .collect::<Result<Vec<_>, JsValue>>()?;
 
             if !project_exprs.is_empty() {
                 plan = LogicalPlan::Project {
@@ -568,16 +1061,19 @@ impl SelectBuilder {
             }
         }
 
-        plan
+        Ok(plan)
     }
 
     /// Builds a LogicalPlan from the query builder state.
-    fn build_logical_plan(&self, table_name: &str) -> LogicalPlan {
-        let root = self
-            .frozen_base
-            .as_ref()
-            .map(|base| base.plan.clone())
-            .unwrap_or_else(|| self.build_source_plan(table_name));
+    ///
+    /// Fails if a WHERE, GROUP BY, ORDER BY, aggregate, or projected column
+    /// cannot be resolved, or if an aggregate query selects or orders by a
+    /// column that is not part of its GROUP BY clause.
+    fn build_logical_plan(&self, table_name: &str) -> Result<LogicalPlan, JsValue> {
+        let root = match &self.frozen_base {
+            Some(base) => base.plan.clone(),
+            None => self.build_source_plan(table_name)?,
+        };
         self.apply_query_modifiers(root)
     }
 
@@ -648,17 +1144,93 @@ impl SelectBuilder {
         self.get_column_info_for_projection(col_name)
     }
 
+    /// Resolves a column against the source tables, failing if it does not exist.
+    fn resolve_source_column(&self, col_name: &str) -> Result<(String, usize, DataType), JsValue> {
+        self.get_column_info_for_projection(col_name)
+            .ok_or_else(|| self.unknown_column_error(col_name))
+    }
+
+    /// Resolves an ORDER BY column.
+    ///
+    /// Aggregate queries can only be ordered by their output columns; a source
+    /// column outside the GROUP BY clause is reported as a grouping error.
+    fn resolve_order_column(&self, col_name: &str) -> Result<(String, usize, DataType), JsValue> {
+        if let Some(info) = self.get_order_column_info(col_name) {
+            return Ok(info);
+        }
+
+        if (!self.group_by_cols.is_empty() || !self.aggregates.is_empty())
+            && self.get_column_info_for_projection(col_name).is_some()
+        {
+            return Err(self.ungrouped_column_error(col_name));
+        }
+
+        Err(self.unknown_column_error(col_name))
+    }
+
+    /// Checks that a column selected by an aggregate query is one of its GROUP BY columns.
+    fn ensure_grouped_column(&self, col_name: &str) -> Result<(), JsValue> {
+        let (table, index, _) = self.resolve_source_column(col_name)?;
+        let grouped = self.group_by_cols.iter().any(|group_col| {
+            self.get_column_info_for_projection(group_col).is_some_and(
+                |(group_table, group_index, _)| group_table == table && group_index == index,
+            )
+        });
+
+        if grouped {
+            Ok(())
+        } else {
+            Err(self.ungrouped_column_error(col_name))
+        }
+    }
+
+    fn ungrouped_column_error(&self, col_name: &str) -> JsValue {
+        CynosError::invalid_argument(alloc::format!(
+            "Column '{}' must appear in the GROUP BY clause or be used in an aggregate function",
+            col_name
+        ))
+        .into()
+    }
+
+    /// Describes the tables a column reference is resolved against, for error messages.
+    fn column_scope_description(&self) -> String {
+        if self.frozen_base.is_some() {
+            return "UNION output".to_string();
+        }
+
+        let mut tables: Vec<&str> = self.from_table.iter().map(String::as_str).collect();
+        tables.extend(self.joins.iter().map(JoinClause::reference_name));
+        let quoted: Vec<String> = tables
+            .iter()
+            .map(|table| alloc::format!("'{}'", table))
+            .collect();
+        if quoted.len() == 1 {
+            alloc::format!("table {}", quoted[0])
+        } else {
+            alloc::format!("tables {}", quoted.join(", "))
+        }
+    }
+
+    fn unknown_column_error(&self, col_name: &str) -> JsValue {
+        CynosError::ColumnNotFound(alloc::format!(
+            "Unknown column '{}' in {}",
+            col_name,
+            self.column_scope_description()
+        ))
+        .into()
+    }
+
     fn representative_schema(&self) -> Result<Table, JsValue> {
         let table_name = self
             .from_table
             .as_ref()
-            .ok_or_else(|| JsValue::from_str("FROM table not specified"))?;
+            .ok_or_else(|| CynosError::invalid_operation("FROM table not specified"))?;
 
         self.cache
             .borrow()
             .get_table(table_name)
             .map(|store| store.schema().clone())
-            .ok_or_else(|| JsValue::from_str(&alloc::format!("Table not found: {}", table_name)))
+            .ok_or_else(|| CynosError::table_not_found(table_name).into())
     }
 
     fn normalize_column_names(column_names: &[String]) -> Vec<String> {
@@ -712,10 +1284,11 @@ impl SelectBuilder {
             .zip(normalized_names.into_iter())
             .filter_map(|(column_name, output_name)| {
                 self.get_column_info_any_table(column_name)
-                    .map(|(_, _, data_type)| OutputColumn {
+                    .map(|(table, index, data_type)| OutputColumn {
                         name: output_name,
                         data_type,
                         is_nullable: true,
+                        metadata: self.source_column_metadata(&table, index),
                     })
             })
             .collect();
@@ -734,7 +1307,10 @@ impl SelectBuilder {
             let cache = self.cache.borrow();
             for join in &self.joins {
                 let join_store = cache.get_table(&join.table).ok_or_else(|| {
-                    JsValue::from_str(&alloc::format!("Join table not found: {}", join.table))
+                    CynosError::TableNotFound(alloc::format!(
+                        "Join table not found: {}",
+                        join.table
+                    ))
                 })?;
                 sources.push((
                     join.reference_name().to_string(),
@@ -763,6 +1339,7 @@ impl SelectBuilder {
                     name: output_name,
                     data_type: col.data_type(),
                     is_nullable: *force_nullable || col.is_nullable(),
+                    metadata: col.metadata().clone(),
                 });
             }
         }
@@ -783,15 +1360,32 @@ impl SelectBuilder {
                 Some(DataType::Float64) => DataType::Float64,
                 _ => DataType::Int64,
             },
-            AggregateFunc::Min | AggregateFunc::Max => input_type.unwrap_or(DataType::Float64),
+            AggregateFunc::Min
+            | AggregateFunc::Max
+            | AggregateFunc::ArgMin
+            | AggregateFunc::ArgMax => input_type.unwrap_or(DataType::Float64),
         }
     }
 
     fn describe_output(&self) -> Result<QueryOutput, JsValue> {
         if !self.group_by_cols.is_empty() || !self.aggregates.is_empty() {
-            let group_columns = self.group_by_cols.iter().filter_map(|col| {
-                self.get_column_info_for_projection(col)
-                    .map(|(_, _, data_type)| OutputColumn {
+            let group_columns = self
+                .group_by_cols
+                .iter()
+                .map(|col| {
+                    let (_, _, data_type) = self.resolve_source_column(col)?;
+                    let metadata = match &self.frozen_base {
+                        Some(base) => base
+                            .output
+                            .resolve_column(col)
+                            .map(|(_, column)| column.metadata.clone())
+                            .unwrap_or_default(),
+                        None => self
+                            .get_column_info_any_table(col)
+                            .map(|(table, index, _)| self.source_column_metadata(&table, index))
+                            .unwrap_or_default(),
+                    };
+                    Ok(OutputColumn {
                         name: if let Some(dot_pos) = col.find('.') {
                             col[dot_pos + 1..].to_string()
                         } else {
@@ -799,8 +1393,10 @@ impl SelectBuilder {
                         },
                         data_type,
                         is_nullable: true,
+                        metadata,
                     })
-            });
+                })
+                .collect::<Result<Vec<_>, JsValue>>()?;
 
             let aggregate_columns = self.aggregates.iter().map(|(func, col_opt)| {
                 let input_type = col_opt
@@ -824,6 +1420,8 @@ impl SelectBuilder {
                             AggregateFunc::Distinct => "distinct",
                             AggregateFunc::StdDev => "stddev",
                             AggregateFunc::GeoMean => "geomean",
+                            AggregateFunc::ArgMin => "arg_min",
+                            AggregateFunc::ArgMax => "arg_max",
                         },
                         simple_name
                     )
@@ -835,12 +1433,13 @@ impl SelectBuilder {
                     name,
                     data_type: Self::aggregate_output_type(*func, input_type),
                     is_nullable: true,
+                    metadata: BTreeMap::new(),
                 }
             });
 
             return Ok(QueryOutput {
                 schema: self.output_schema_context()?,
-                columns: group_columns.chain(aggregate_columns).collect(),
+                columns: group_columns.into_iter().chain(aggregate_columns).collect(),
             });
         }
 
@@ -864,69 +1463,58 @@ impl SelectBuilder {
                 name: col.name().to_string(),
                 data_type: col.data_type(),
                 is_nullable: col.is_nullable(),
+                metadata: col.metadata().clone(),
             })
             .collect();
 
         Ok(QueryOutput { schema, columns })
     }
 
+    /// Chooses how live query rows are converted to JS objects.
+    ///
+    /// Shared by `observe()` and `trace()` so both emit the same object keys;
+    /// aggregate and frozen outputs are keyed by `describe_output()` names.
+    fn rows_projection(&self, output: QueryOutput, schema: Table) -> RowsProjection {
+        if self.frozen_base.is_some()
+            || !self.aggregates.is_empty()
+            || !self.group_by_cols.is_empty()
+        {
+            let columns = output.column_names();
+            RowsProjection::Projection {
+                schema: output.schema,
+                columns,
+            }
+        } else if let Some(cols) = self.parse_columns() {
+            RowsProjection::Projection {
+                schema,
+                columns: cols,
+            }
+        } else {
+            RowsProjection::Full { schema }
+        }
+    }
+
     /// Creates a SchemaLayout for projected columns, supporting multi-table column references.
     fn create_projection_layout(
         &self,
         column_names: &[String],
     ) -> crate::binary_protocol::SchemaLayout {
-        use crate::binary_protocol::{BinaryDataType, ColumnLayout, SchemaLayout};
-
-        // Extract just the column part from qualified names and count occurrences
-        let mut name_counts: hashbrown::HashMap<&str, usize> = hashbrown::HashMap::new();
-        for col_name in column_names {
-            let simple_name = if let Some(dot_pos) = col_name.find('.') {
-                &col_name[dot_pos + 1..]
-            } else {
-                col_name.as_str()
-            };
-            *name_counts.entry(simple_name).or_insert(0) += 1;
+        let cache = self.cache.borrow();
+        let mut sources: Vec<(&str, &Table)> = Vec::with_capacity(1 + self.joins.len());
+        if let Some(store) = self
+            .from_table
+            .as_ref()
+            .and_then(|table| cache.get_table(table))
+        {
+            sources.push((store.schema().name(), store.schema()));
         }
-
-        let mut columns: Vec<ColumnLayout> = Vec::new();
-        let mut offset = 0usize;
-
-        for name in column_names {
-            // Look up column info from any table
-            if let Some((_, _, data_type)) = self.get_column_info_any_table(name) {
-                let binary_type = BinaryDataType::from(data_type);
-                let fixed_size = binary_type.fixed_size();
-
-                // Determine the final column name - use simple name when unique
-                let final_name = if let Some(dot_pos) = name.find('.') {
-                    let simple_name = &name[dot_pos + 1..];
-                    if name_counts.get(simple_name).copied().unwrap_or(0) > 1 {
-                        // Duplicate - keep qualified name
-                        name.clone()
-                    } else {
-                        // Unique - use simple name
-                        simple_name.to_string()
-                    }
-                } else {
-                    name.clone()
-                };
-
-                columns.push(ColumnLayout {
-                    name: final_name,
-                    data_type: binary_type,
-                    fixed_size,
-                    is_nullable: true, // Conservative: assume nullable
-                    offset,
-                });
-                offset += fixed_size;
+        for join in &self.joins {
+            if let Some(store) = cache.get_table(&join.table) {
+                sources.push((join.reference_name(), store.schema()));
             }
         }
 
-        let null_mask_size = (columns.len() + 7) / 8;
-        let data_size: usize = columns.iter().map(|c| c.fixed_size).sum();
-        let row_stride = null_mask_size + data_size;
-
-        SchemaLayout::new(columns, row_stride, null_mask_size)
+        SchemaLayout::from_join_projection(&sources, column_names)
     }
 
     fn binary_output_layout(
@@ -948,7 +1536,7 @@ impl SelectBuilder {
             Ok(self
                 .schema_layout_cache
                 .borrow_mut()
-                .get_or_create_full(table_name, schema)
+                .get_or_create_full(table_name, *self.schema_epoch.borrow(), schema)
                 .clone())
         }
     }
@@ -964,7 +1552,7 @@ impl SelectBuilder {
     fn build_result_mapper(&self, schema: &Table) -> Result<QueryResultMapper, JsValue> {
         if self.uses_full_row_mapping() {
             Ok(QueryResultMapper::Full {
-                schema: schema.clone(),
+                schema: Box::new(schema.clone()),
             })
         } else {
             Ok(QueryResultMapper::Columns {
@@ -974,7 +1562,7 @@ impl SelectBuilder {
     }
 
     fn map_rows_to_js(&self, rows: &[Rc<Row>], schema: &Table) -> Result<JsValue, JsValue> {
-        Ok(self.build_result_mapper(schema)?.map_rows(rows))
+        self.build_result_mapper(schema)?.map_rows(rows)
     }
 
     /// Gets column info for JOIN conditions, checking both the main table and the join table.
@@ -1121,6 +1709,7 @@ impl SelectBuilder {
         self.columns = JsValue::UNDEFINED;
         self.where_clause = None;
         self.order_by.clear();
+        self.order_by_exprs.clear();
         self.limit_val = None;
         self.offset_val = None;
         self.group_by_cols.clear();
@@ -1131,21 +1720,22 @@ impl SelectBuilder {
         let left_table = self
             .from_table
             .clone()
-            .ok_or_else(|| JsValue::from_str("Left side of UNION is missing FROM"))?;
+            .ok_or_else(|| CynosError::invalid_operation("Left side of UNION is missing FROM"))?;
         let right_table = other
             .from_table
             .as_ref()
-            .ok_or_else(|| JsValue::from_str("Right side of UNION is missing FROM"))?;
+            .ok_or_else(|| CynosError::invalid_operation("Right side of UNION is missing FROM"))?;
 
-        let left_plan = self.build_logical_plan(&left_table);
-        let right_plan = other.build_logical_plan(right_table);
+        let left_plan = self.build_logical_plan(&left_table)?;
+        let right_plan = other.build_logical_plan(right_table)?;
         let left_output = self.describe_output()?;
         let right_output = other.describe_output()?;
 
         if !left_output.is_compatible_with(&right_output) {
-            return Err(JsValue::from_str(
+            return Err(CynosError::invalid_operation(
                 "UNION operands must produce the same number of columns with matching types",
-            ));
+            )
+            .into());
         }
 
         self.frozen_base = Some(FrozenQueryBase {
@@ -1176,6 +1766,43 @@ impl SelectBuilder {
         self
     }
 
+    /// Adds a saved filter to the WHERE clause, combined with AND.
+    ///
+    /// The filter's unqualified columns are bound to the FROM table, so one
+    /// filter can be reused across tables that share column names and never
+    /// binds to a joined table's column. Call after `from()`.
+    #[wasm_bindgen(js_name = whereFilter)]
+    pub fn where_filter(self, filter: &Expr) -> Self {
+        let scoped = match (&self.from_table, &self.frozen_base) {
+            (Some(table), None) => filter.scoped_to(table),
+            _ => filter.clone(),
+        };
+        self.where_(&scoped)
+    }
+
+    /// Restricts the query to the rows with the given row ids, looked up
+    /// directly instead of scanning the table.
+    ///
+    /// Rows come back in the order of `ids` unless an ORDER BY is given.
+    /// Unknown ids are skipped and repeated ids return their row once.
+    /// Further `where()` predicates filter the fetched rows.
+    #[wasm_bindgen(js_name = whereIds)]
+    pub fn where_ids(mut self, ids: &JsValue) -> Self {
+        let values: Vec<JsValue> = match ids.dyn_ref::<js_sys::Array>() {
+            Some(arr) => arr.iter().collect(),
+            None => alloc::vec![ids.clone()],
+        };
+        self.where_ids = Some(
+            values
+                .iter()
+                .filter_map(|v| v.as_f64())
+                .filter(|id| *id >= 0.0 && id.fract() == 0.0)
+                .map(|id| id as RowId)
+                .collect(),
+        );
+        self
+    }
+
     /// Adds an ORDER BY clause.
     #[wasm_bindgen(js_name = orderBy)]
     pub fn order_by(mut self, column: &str, order: JsSortOrder) -> Self {
@@ -1183,6 +1810,82 @@ impl SelectBuilder {
         self
     }
 
+    /// Sets the full ORDER BY from `[expr, order, nullsOrder?]` entries, applied
+    /// after any `orderBy` columns. `expr` is an expression, a column or a
+    /// column name; expression and column objects are copied and stay
+    /// usable. NULLs lead ascending keys and trail descending ones unless
+    /// `nullsOrder` says otherwise.
+    #[wasm_bindgen(js_name = orderByExprs)]
+    pub fn order_by_exprs(mut self, keys: &JsValue) -> Result<SelectBuilder, JsValue> {
+        let keys = keys
+            .dyn_ref::<js_sys::Array>()
+            .ok_or_else(|| CynosError::invalid_argument("orderByExprs expects an array"))?;
+        let mut order_by = Vec::with_capacity(keys.length() as usize);
+        for key in keys.iter() {
+            let invalid = || {
+                CynosError::invalid_argument(
+                    "orderByExprs entries must be [expr, order] or [expr, order, nullsOrder]",
+                )
+            };
+            let key = key.dyn_into::<js_sys::Array>().map_err(|_| invalid())?;
+            let target = key.get(0);
+            let expr = if let Some(name) = target.as_string() {
+                Expr::column_ref(Column::new_simple(&name))
+            } else if let Some(column) = copy_from_js::<Column>(&target) {
+                Expr::column_ref(column)
+            } else {
+                copy_from_js::<Expr>(&target).ok_or_else(invalid)?
+            };
+            let order = match key.get(1).as_f64() {
+                Some(order) if order == JsSortOrder::Desc as u8 as f64 => SortOrder::Desc,
+                Some(order) if order == JsSortOrder::Asc as u8 as f64 => SortOrder::Asc,
+                _ => return Err(invalid().into()),
+            };
+            let nulls = match key.get(2) {
+                nulls if nulls.is_undefined() || nulls.is_null() => None,
+                nulls => match nulls.as_f64() {
+                    Some(value) if value == JsNullsOrder::First as u8 as f64 => {
+                        Some(NullsOrder::First)
+                    }
+                    Some(value) if value == JsNullsOrder::Last as u8 as f64 => {
+                        Some(NullsOrder::Last)
+                    }
+                    _ => return Err(invalid().into()),
+                },
+            };
+            order_by.push((expr, order, nulls));
+        }
+        self.order_by_exprs = order_by;
+        Ok(self)
+    }
+
+    /// Includes soft-deleted rows of the FROM table, which are skipped by
+    /// default.
+    #[wasm_bindgen(js_name = withDeleted)]
+    pub fn with_deleted(mut self) -> Self {
+        self.with_deleted = true;
+        self
+    }
+
+    /// Allows inner joins whose condition does not relate the two sides.
+    /// Without it such a join, which would produce a cross product, fails.
+    #[wasm_bindgen(js_name = allowCrossProduct)]
+    pub fn allow_cross_product(mut self) -> Self {
+        self.allow_cross_product = true;
+        self
+    }
+
+    /// Makes `execBinary` write Int64 columns as exact little-endian 64-bit
+    /// integers, flagged with `INT64_AS_BIGINT` in the header, for readers
+    /// to decode as `BigInt`. By default they are written as f64, which
+    /// rounds values beyond 2^53. Queries prepared from this builder keep
+    /// the setting.
+    #[wasm_bindgen(js_name = int64AsBigInt)]
+    pub fn int64_as_bigint(mut self, enabled: bool) -> Self {
+        self.int64_as_bigint = enabled;
+        self
+    }
+
     /// Sets the LIMIT.
     pub fn limit(mut self, n: usize) -> Self {
         self.limit_val = Some(n);
@@ -1221,27 +1924,38 @@ impl SelectBuilder {
     }
 
     /// Adds an INNER JOIN.
+    ///
+    /// `options` may set `algorithm` to `'hash'`, `'sortMerge'`, or `'nestedLoop'`
+    /// to override the planner's join algorithm choice, and `nullSafe: true`
+    /// to make NULL join keys match each other (`IS NOT DISTINCT FROM`).
+    /// NULL-safe keys run as a hash or nested loop join.
     #[wasm_bindgen(js_name = innerJoin)]
-    pub fn inner_join(mut self, table: &str, condition: &Expr) -> Self {
+    pub fn inner_join(mut self, table: &str, condition: &Expr, options: Option<JsValue>) -> Self {
         let (table_name, alias) = Self::parse_table_spec(table);
         self.joins.push(JoinClause {
             table: table_name,
             alias,
             condition: condition.clone(),
             join_type: JoinType::Inner,
+            null_safe: JoinClause::parse_null_safe(options.as_ref()),
+            algorithm: JoinClause::parse_algorithm(options),
         });
         self
     }
 
     /// Adds a LEFT JOIN.
+    ///
+    /// Accepts the same `options` as `innerJoin`.
     #[wasm_bindgen(js_name = leftJoin)]
-    pub fn left_join(mut self, table: &str, condition: &Expr) -> Self {
+    pub fn left_join(mut self, table: &str, condition: &Expr, options: Option<JsValue>) -> Self {
         let (table_name, alias) = Self::parse_table_spec(table);
         self.joins.push(JoinClause {
             table: table_name,
             alias,
             condition: condition.clone(),
             join_type: JoinType::Left,
+            null_safe: JoinClause::parse_null_safe(options.as_ref()),
+            algorithm: JoinClause::parse_algorithm(options),
         });
         self
     }
@@ -1333,37 +2047,102 @@ impl SelectBuilder {
         let table_name = self
             .from_table
             .as_ref()
-            .ok_or_else(|| JsValue::from_str("FROM table not specified"))?;
+            .ok_or_else(|| CynosError::invalid_operation("FROM table not specified"))?;
 
         let cache = self.cache.borrow();
         let store = cache
             .get_table(table_name)
-            .ok_or_else(|| JsValue::from_str(&alloc::format!("Table not found: {}", table_name)))?;
+            .ok_or_else(|| CynosError::table_not_found(table_name))?;
 
         // Build logical plan using query engine
         // ORDER BY, LIMIT, and OFFSET are now handled in the logical plan
-        let plan = self.build_logical_plan(table_name);
+        let plan = self.build_logical_plan(table_name)?;
 
         // Execute using query engine (with index optimization)
-        let rows = execute_plan(&cache, table_name, plan)
-            .map_err(|e| JsValue::from_str(&alloc::format!("Query execution error: {:?}", e)))?;
+        let rows = execute_plan(&cache, table_name, plan).map_err(CynosError::from)?;
         let schema = store.schema().clone();
         self.map_rows_to_js(&rows, &schema)
     }
 
+    /// Executes the query as `COUNT(*)` and returns the count as a plain
+    /// number. The WHERE clause is applied, and can use indexes, while
+    /// ordering and aggregates added to the builder are ignored. Fails if
+    /// the query has `groupBy()`, `limit()` or `offset()`.
+    #[wasm_bindgen(js_name = scalarCount)]
+    pub async fn scalar_count(&self) -> Result<f64, JsValue> {
+        let table_name = self
+            .from_table
+            .as_ref()
+            .ok_or_else(|| CynosError::invalid_operation("FROM table not specified"))?;
+
+        let cache = self.cache.borrow();
+        Ok(self.scalar_count_value(&cache, table_name)? as f64)
+    }
+
+    /// Executes a query with exactly one aggregate and no `groupBy()` or
+    /// selected columns and returns the aggregate's value instead of a
+    /// one-row array. Fails if the query is not shaped that way.
+    #[wasm_bindgen(js_name = execScalar)]
+    pub async fn exec_scalar(&self) -> Result<JsValue, JsValue> {
+        let table_name = self
+            .from_table
+            .as_ref()
+            .ok_or_else(|| CynosError::invalid_operation("FROM table not specified"))?;
+
+        let cache = self.cache.borrow();
+        self.scalar_value(&cache, table_name)
+    }
+
+    /// Executes the query projected to a single column and returns that
+    /// column's values as a flat array, with NULLs as `null`. Fails if the
+    /// builder already selects other columns or aggregates.
+    pub async fn pluck(&self, column: &str) -> Result<JsValue, JsValue> {
+        let table_name = self
+            .from_table
+            .as_ref()
+            .ok_or_else(|| CynosError::invalid_operation("FROM table not specified"))?;
+
+        let cache = self.cache.borrow();
+        self.pluck_values(&cache, table_name, column)
+    }
+
+    /// Executes the query with `LIMIT 1` and returns the first row as an
+    /// object, or `null` when nothing matches.
+    pub async fn first(&self) -> Result<JsValue, JsValue> {
+        let table_name = self
+            .from_table
+            .as_ref()
+            .ok_or_else(|| CynosError::invalid_operation("FROM table not specified"))?;
+
+        let cache = self.cache.borrow();
+        self.first_row(&cache, table_name)
+    }
+
+    /// Executes the query and returns its only row as an object, or `null`
+    /// when nothing matches. Fails if more than one row matches.
+    pub async fn single(&self) -> Result<JsValue, JsValue> {
+        let table_name = self
+            .from_table
+            .as_ref()
+            .ok_or_else(|| CynosError::invalid_operation("FROM table not specified"))?;
+
+        let cache = self.cache.borrow();
+        self.single_row(&cache, table_name)
+    }
+
     /// Compiles the current query into a reusable prepared handle.
     pub fn prepare(&self) -> Result<PreparedSelectQuery, JsValue> {
         let table_name = self
             .from_table
             .as_ref()
-            .ok_or_else(|| JsValue::from_str("FROM table not specified"))?;
+            .ok_or_else(|| CynosError::invalid_operation("FROM table not specified"))?;
 
         let cache = self.cache.borrow();
         let store = cache
             .get_table(table_name)
-            .ok_or_else(|| JsValue::from_str(&alloc::format!("Table not found: {}", table_name)))?;
+            .ok_or_else(|| CynosError::table_not_found(table_name))?;
 
-        let plan = self.build_logical_plan(table_name);
+        let plan = self.build_logical_plan(table_name)?;
         let fingerprint = compute_plan_fingerprint(&plan);
         let result_mapper = self.build_result_mapper(store.schema())?;
         let binary_layout = self.binary_output_layout(table_name, store.schema())?;
@@ -1381,6 +2160,7 @@ impl SelectBuilder {
             compiled_plan,
             result_mapper,
             binary_layout,
+            int64_as_bigint: self.int64_as_bigint,
         })
     }
 
@@ -1394,15 +2174,15 @@ impl SelectBuilder {
         let table_name = self
             .from_table
             .as_ref()
-            .ok_or_else(|| JsValue::from_str("FROM table not specified"))?;
+            .ok_or_else(|| CynosError::invalid_operation("FROM table not specified"))?;
 
         let cache = self.cache.borrow();
         let _ = cache
             .get_table(table_name)
-            .ok_or_else(|| JsValue::from_str(&alloc::format!("Table not found: {}", table_name)))?;
+            .ok_or_else(|| CynosError::table_not_found(table_name))?;
 
         // Build logical plan
-        let plan = self.build_logical_plan(table_name);
+        let plan = self.build_logical_plan(table_name)?;
 
         // Get explain result
         let result = explain_plan(&cache, table_name, plan);
@@ -1416,6 +2196,37 @@ impl SelectBuilder {
         Ok(obj.into())
     }
 
+    /// Returns the indexes the optimized plan reads, without executing it.
+    ///
+    /// Each entry is `{ table, index, scanType }`, where `scanType` is one of
+    /// `range`, `point`, `multiPoint`, `gin`, `ginMulti` or `joinLookup`.
+    /// A query that only scans tables returns an empty array.
+    #[wasm_bindgen(js_name = chosenIndexes)]
+    pub fn chosen_indexes(&self) -> Result<JsValue, JsValue> {
+        let table_name = self
+            .from_table
+            .as_ref()
+            .ok_or_else(|| CynosError::invalid_operation("FROM table not specified"))?;
+
+        let cache = self.cache.borrow();
+        let _ = cache
+            .get_table(table_name)
+            .ok_or_else(|| CynosError::table_not_found(table_name))?;
+
+        let plan = self.build_logical_plan(table_name)?;
+        let physical_plan = compile_plan(&cache, table_name, plan);
+
+        let usages = js_sys::Array::new();
+        for usage in physical_plan.collect_index_usages() {
+            let obj = js_sys::Object::new();
+            js_sys::Reflect::set(&obj, &"table".into(), &usage.table.into())?;
+            js_sys::Reflect::set(&obj, &"index".into(), &usage.index.into())?;
+            js_sys::Reflect::set(&obj, &"scanType".into(), &usage.scan_type.as_str().into())?;
+            usages.push(&obj);
+        }
+        Ok(usages.into())
+    }
+
     /// Creates an observable query using the cached execution path.
     /// When data changes, the engine reuses the compiled plan and can apply
     /// row-local patches for simple single-table pipelines instead of always
@@ -1424,33 +2235,36 @@ impl SelectBuilder {
         let table_name = self
             .from_table
             .as_ref()
-            .ok_or_else(|| JsValue::from_str("FROM table not specified"))?;
+            .ok_or_else(|| CynosError::invalid_operation("FROM table not specified"))?;
 
         let cache_ref = self.cache.clone();
         let cache = cache_ref.borrow();
         let store = cache
             .get_table(table_name)
-            .ok_or_else(|| JsValue::from_str(&alloc::format!("Table not found: {}", table_name)))?;
+            .ok_or_else(|| CynosError::table_not_found(table_name))?;
 
         // Build logical plan and compile to a cached execution artifact for re-execution.
-        let logical_plan = self.build_logical_plan(table_name);
+        let logical_plan = self.build_logical_plan(table_name)?;
         let output = self.describe_output()?;
-        let output_columns = output.column_names();
         let schema = store.schema().clone();
-        let binary_layout = if self.frozen_base.is_some() {
+        let binary_layout = if self.frozen_base.is_some()
+            || !self.aggregates.is_empty()
+            || !self.group_by_cols.is_empty()
+        {
             output.layout()
+        } else if let Some(cols) = self.parse_columns() {
+            self.create_projection_layout(&cols)
         } else if self.joins.is_empty() {
-            if let Some(cols) = self.parse_columns() {
-                SchemaLayout::from_projection(&schema, &cols)
-            } else {
-                SchemaLayout::from_schema(&schema)
-            }
+            SchemaLayout::from_schema(&schema)
         } else {
             let mut schemas: Vec<&Table> = Vec::with_capacity(1 + self.joins.len());
             schemas.push(store.schema());
             for join in &self.joins {
                 let join_store = cache.get_table(&join.table).ok_or_else(|| {
-                    JsValue::from_str(&alloc::format!("Join table not found: {}", join.table))
+                    CynosError::TableNotFound(alloc::format!(
+                        "Join table not found: {}",
+                        join.table
+                    ))
                 })?;
                 schemas.push(join_store.schema());
             }
@@ -1460,7 +2274,7 @@ impl SelectBuilder {
 
         // Get initial result using the compiled plan artifact.
         let initial_output = execute_compiled_physical_plan_with_summary(&cache, &compiled_plan)
-            .map_err(|e| JsValue::from_str(&alloc::format!("Query execution error: {:?}", e)))?;
+            .map_err(CynosError::from)?;
 
         let dependencies = {
             let table_id_map = self.table_id_map.borrow();
@@ -1469,29 +2283,14 @@ impl SelectBuilder {
                 .into_iter()
                 .map(|table| {
                     table_id_map.get(&table).copied().ok_or_else(|| {
-                        JsValue::from_str(&alloc::format!("Table ID not found: {}", table))
+                        CynosError::TableNotFound(alloc::format!("Table ID not found: {}", table))
                     })
                 })
                 .collect::<Result<Vec<_>, _>>()?;
             LiveDependencySet::snapshot(table_ids)
         };
 
-        let projection = if self.frozen_base.is_some()
-            || !self.aggregates.is_empty()
-            || !self.group_by_cols.is_empty()
-        {
-            RowsProjection::Projection {
-                schema: output.schema,
-                columns: output_columns,
-            }
-        } else if let Some(cols) = self.parse_columns() {
-            RowsProjection::Projection {
-                schema,
-                columns: cols,
-            }
-        } else {
-            RowsProjection::Full { schema }
-        };
+        let projection = self.rows_projection(output, schema);
 
         drop(cache); // Release borrow
 
@@ -1518,38 +2317,46 @@ impl SelectBuilder {
     /// Unlike `observe()` which re-executes the full query on every change (O(result_set)),
     /// `trace()` compiles the query into a dataflow graph and propagates only deltas (O(delta)).
     ///
-    /// Returns an error if the query is not incrementalizable (e.g. contains ORDER BY / LIMIT).
+    /// A LIMIT (with or without ORDER BY) is maintained as a bounded window:
+    /// rows entering or leaving the window are reported as added/removed, and
+    /// a deleted row is backfilled by the next row in order.
+    ///
+    /// Returns an error if the query is not incrementalizable (e.g. ORDER BY without LIMIT);
+    /// the message names the offending operator and how to work around it.
     pub fn trace(&self) -> Result<JsIvmObservableQuery, JsValue> {
         let table_name = self
             .from_table
             .as_ref()
-            .ok_or_else(|| JsValue::from_str("FROM table not specified"))?;
+            .ok_or_else(|| CynosError::invalid_operation("FROM table not specified"))?;
 
         let cache_ref = self.cache.clone();
         let cache = cache_ref.borrow();
         let store = cache
             .get_table(table_name)
-            .ok_or_else(|| JsValue::from_str(&alloc::format!("Table not found: {}", table_name)))?;
+            .ok_or_else(|| CynosError::table_not_found(table_name))?;
 
         // Build logical plan and compile to physical plan
-        let logical_plan = self.build_logical_plan(table_name);
+        let logical_plan = self.build_logical_plan(table_name)?;
         let output = self.describe_output()?;
-        let output_columns = output.column_names();
         let schema = store.schema().clone();
-        let binary_layout = if self.frozen_base.is_some() {
+        let binary_layout = if self.frozen_base.is_some()
+            || !self.aggregates.is_empty()
+            || !self.group_by_cols.is_empty()
+        {
             output.layout()
+        } else if let Some(cols) = self.parse_columns() {
+            self.create_projection_layout(&cols)
         } else if self.joins.is_empty() {
-            if let Some(cols) = self.parse_columns() {
-                SchemaLayout::from_projection(&schema, &cols)
-            } else {
-                SchemaLayout::from_schema(&schema)
-            }
+            SchemaLayout::from_schema(&schema)
         } else {
             let mut schemas: Vec<&Table> = Vec::with_capacity(1 + self.joins.len());
             schemas.push(store.schema());
             for join in &self.joins {
                 let join_store = cache.get_table(&join.table).ok_or_else(|| {
-                    JsValue::from_str(&alloc::format!("Join table not found: {}", join.table))
+                    CynosError::TableNotFound(alloc::format!(
+                        "Join table not found: {}",
+                        join.table
+                    ))
                 })?;
                 schemas.push(join_store.schema());
             }
@@ -1560,7 +2367,7 @@ impl SelectBuilder {
         table_schemas.insert(table_name.clone(), store.schema().clone());
         for join in &self.joins {
             let join_store = cache.get_table(&join.table).ok_or_else(|| {
-                JsValue::from_str(&alloc::format!("Join table not found: {}", join.table))
+                CynosError::TableNotFound(alloc::format!("Join table not found: {}", join.table))
             })?;
             table_schemas.insert(join.table.clone(), join_store.schema().clone());
         }
@@ -1568,13 +2375,27 @@ impl SelectBuilder {
         // Compile physical plan to dataflow — errors if not incrementalizable
         let table_id_map = self.table_id_map.borrow();
         let compile_result = compile_to_dataflow(&physical_plan, &table_id_map, &table_schemas)
-            .ok_or_else(|| JsValue::from_str(
-                "Query is not incrementalizable (contains ORDER BY, LIMIT, or other non-streamable operators). Use observe() instead."
-            ))?;
+            .map_err(|error| {
+                CynosError::invalid_operation(alloc::format!(
+                    "Query is not incrementalizable because of {}: {}",
+                    error.operator,
+                    error.reason
+                ))
+            })?;
 
         // Get initial result using the compiled physical plan
-        let initial_rows = execute_physical_plan(&cache, &physical_plan)
-            .map_err(|e| JsValue::from_str(&alloc::format!("Query execution error: {:?}", e)))?;
+        let initial_rows =
+            execute_physical_plan(&cache, &physical_plan).map_err(CynosError::from)?;
+        let top_n_input_rows = match top_n_input_plan(&physical_plan) {
+            Some(input_plan) => Some(
+                execute_physical_plan(&cache, &input_plan)
+                    .map_err(CynosError::from)?
+                    .iter()
+                    .map(|rc| (**rc).clone())
+                    .collect::<Vec<Row>>(),
+            ),
+            None => None,
+        };
 
         let dependencies =
             LiveDependencySet::snapshot(compile_result.table_ids.values().copied().collect());
@@ -1582,27 +2403,13 @@ impl SelectBuilder {
         drop(table_id_map);
 
         let initial_owned: Vec<Row> = initial_rows.iter().map(|rc| (**rc).clone()).collect();
-        let projection = if self.frozen_base.is_some()
-            || !self.aggregates.is_empty()
-            || !self.group_by_cols.is_empty()
-        {
-            RowsProjection::Projection {
-                schema: output.schema,
-                columns: output_columns,
-            }
-        } else if let Some(cols) = self.parse_columns() {
-            RowsProjection::Projection {
-                schema,
-                columns: cols,
-            }
-        } else {
-            RowsProjection::Full { schema }
-        };
+        let projection = self.rows_projection(output, schema);
 
         let live_plan = LivePlan::rows_delta(
             dependencies,
             compile_result.dataflow,
             initial_owned,
+            top_n_input_rows,
             projection,
             binary_layout,
         );
@@ -1617,17 +2424,41 @@ impl SelectBuilder {
         let table_name = self
             .from_table
             .as_ref()
-            .ok_or_else(|| JsValue::from_str("FROM table not specified"))?;
+            .ok_or_else(|| CynosError::invalid_operation("FROM table not specified"))?;
 
         let cache = self.cache.borrow();
         let store = cache
             .get_table(table_name)
-            .ok_or_else(|| JsValue::from_str(&alloc::format!("Table not found: {}", table_name)))?;
+            .ok_or_else(|| CynosError::table_not_found(table_name))?;
 
         let schema = store.schema();
         self.binary_output_layout(table_name, schema)
     }
 
+    /// Describes the columns this query returns.
+    ///
+    /// Returns an array of `{ name, type, nullable, metadata }` objects in
+    /// output order. Columns projected from a table carry that column's
+    /// metadata under their output name; aggregate columns have none.
+    #[wasm_bindgen(js_name = resultSchema)]
+    pub fn result_schema(&self) -> Result<js_sys::Array, JsValue> {
+        let output = self.describe_output()?;
+        let columns = js_sys::Array::new();
+        for column in &output.columns {
+            let obj: JsValue = js_sys::Object::new().into();
+            set_property(&obj, "name", &JsValue::from_str(&column.name))?;
+            set_property(
+                &obj,
+                "type",
+                &JsValue::from(crate::JsDataType::from(column.data_type)),
+            )?;
+            set_property(&obj, "nullable", &JsValue::from_bool(column.is_nullable))?;
+            set_property(&obj, "metadata", &metadata_to_js(&column.metadata)?)?;
+            columns.push(&obj);
+        }
+        Ok(columns)
+    }
+
     /// Executes the query and returns a binary result buffer.
     /// Use with getSchemaLayout() for zero-copy decoding in JS.
     #[wasm_bindgen(js_name = execBinary)]
@@ -1635,15 +2466,15 @@ impl SelectBuilder {
         let table_name = self
             .from_table
             .as_ref()
-            .ok_or_else(|| JsValue::from_str("FROM table not specified"))?;
+            .ok_or_else(|| CynosError::invalid_operation("FROM table not specified"))?;
 
         let cache = self.cache.borrow();
         let store = cache
             .get_table(table_name)
-            .ok_or_else(|| JsValue::from_str(&alloc::format!("Table not found: {}", table_name)))?;
+            .ok_or_else(|| CynosError::table_not_found(table_name))?;
 
         // Build logical plan
-        let plan = self.build_logical_plan(table_name);
+        let plan = self.build_logical_plan(table_name)?;
         let schema = store.schema();
         let layout = self.binary_output_layout(table_name, schema)?;
 
@@ -1658,14 +2489,14 @@ impl SelectBuilder {
             });
 
             // Execute the cached compiled plan
-            execute_compiled_physical_plan(&cache, compiled_plan)
-                .map_err(|e| JsValue::from_str(&alloc::format!("Query execution error: {:?}", e)))?
+            execute_compiled_physical_plan(&cache, compiled_plan).map_err(CynosError::from)?
         };
 
         // Encode to binary
-        let mut encoder = crate::binary_protocol::BinaryEncoder::new(layout, rows.len());
+        let mut encoder = crate::binary_protocol::BinaryEncoder::new(layout, rows.len())
+            .with_int64_as_bigint(self.int64_as_bigint);
         encoder.encode_rows(&rows);
-        let buffer = encoder.finish();
+        let buffer = encoder.try_finish().map_err(CynosError::from)?;
 
         Ok(crate::binary_protocol::BinaryResult::new(buffer))
     }
@@ -1677,8 +2508,8 @@ impl PreparedSelectQuery {
     pub async fn exec(&self) -> Result<JsValue, JsValue> {
         let cache = self.cache.borrow();
         let rows = execute_compiled_physical_plan(&cache, &self.compiled_plan)
-            .map_err(|e| JsValue::from_str(&alloc::format!("Query execution error: {:?}", e)))?;
-        Ok(self.result_mapper.map_rows(&rows))
+            .map_err(CynosError::from)?;
+        self.result_mapper.map_rows(&rows)
     }
 
     /// Executes the prepared query and returns a binary result buffer.
@@ -1686,12 +2517,14 @@ impl PreparedSelectQuery {
     pub async fn exec_binary(&self) -> Result<crate::binary_protocol::BinaryResult, JsValue> {
         let cache = self.cache.borrow();
         let rows = execute_compiled_physical_plan(&cache, &self.compiled_plan)
-            .map_err(|e| JsValue::from_str(&alloc::format!("Query execution error: {:?}", e)))?;
+            .map_err(CynosError::from)?;
 
         let mut encoder =
-            crate::binary_protocol::BinaryEncoder::new(self.binary_layout.clone(), rows.len());
+            crate::binary_protocol::BinaryEncoder::new(self.binary_layout.clone(), rows.len())
+                .with_int64_as_bigint(self.int64_as_bigint);
         encoder.encode_rows(&rows);
-        Ok(crate::binary_protocol::BinaryResult::new(encoder.finish()))
+        let buffer = encoder.try_finish().map_err(CynosError::from)?;
+        Ok(crate::binary_protocol::BinaryResult::new(buffer))
     }
 
     /// Gets the schema layout for binary decoding.
@@ -1707,8 +2540,10 @@ pub struct InsertBuilder {
     cache: Rc<RefCell<TableCache>>,
     query_registry: Rc<RefCell<LiveRegistry>>,
     table_id_map: Rc<RefCell<hashbrown::HashMap<String, TableId>>>,
+    computed_defaults: Rc<RefCell<ComputedDefaults>>,
     table_name: String,
     values_data: Option<JsValue>,
+    strict: bool,
 }
 
 impl InsertBuilder {
@@ -1716,16 +2551,32 @@ impl InsertBuilder {
         cache: Rc<RefCell<TableCache>>,
         query_registry: Rc<RefCell<LiveRegistry>>,
         table_id_map: Rc<RefCell<hashbrown::HashMap<String, TableId>>>,
+        computed_defaults: Rc<RefCell<ComputedDefaults>>,
         table: &str,
+        strict: bool,
     ) -> Self {
         Self {
             cache,
             query_registry,
             table_id_map,
+            computed_defaults,
             table_name: table.to_string(),
             values_data: None,
+            strict,
         }
     }
+
+    pub(crate) fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
+    pub(crate) fn values_data(&self) -> Option<&JsValue> {
+        self.values_data.as_ref()
+    }
+
+    pub(crate) fn is_strict(&self) -> bool {
+        self.strict
+    }
 }
 
 #[wasm_bindgen]
@@ -1736,17 +2587,25 @@ impl InsertBuilder {
         self
     }
 
+    /// Rejects inserted objects with keys that are not columns of the table,
+    /// instead of ignoring those keys. Defaults to the database's
+    /// `setStrictInserts` setting.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
     /// Executes the insert operation.
     pub async fn exec(&self) -> Result<JsValue, JsValue> {
         let values = self
             .values_data
             .as_ref()
-            .ok_or_else(|| JsValue::from_str("No values specified"))?;
+            .ok_or_else(|| CynosError::invalid_argument("No values specified"))?;
 
         let mut cache = self.cache.borrow_mut();
-        let store = cache.get_table_mut(&self.table_name).ok_or_else(|| {
-            JsValue::from_str(&alloc::format!("Table not found: {}", self.table_name))
-        })?;
+        let store = cache
+            .get_table_mut(&self.table_name)
+            .ok_or_else(|| CynosError::table_not_found(&self.table_name))?;
 
         let schema = store.schema().clone();
 
@@ -1757,24 +2616,34 @@ impl InsertBuilder {
         // Reserve row IDs for all rows at once to avoid ID conflicts
         let start_row_id = reserve_row_ids(row_count);
 
-        // Convert JS values to rows
-        let rows = js_array_to_rows(values, &schema, start_row_id)?;
+        // Convert JS values to rows, filling in computed defaults
+        let defaults = self.computed_defaults.borrow();
+        let defaults = defaults
+            .get(&self.table_name)
+            .map_or(&[][..], Vec::as_slice);
+        let rows = js_array_to_rows(values, &schema, start_row_id, defaults, self.strict)?;
         let row_count = rows.len();
 
-        // Build deltas for IVM notification
-        let deltas: Vec<Delta<Row>> = rows.iter().map(|r| Delta::insert(r.clone())).collect();
+        // Build deltas for IVM notification, unless reactivity is off
+        let reactive = self.query_registry.borrow().is_enabled();
+        let deltas: Vec<Delta<Row>> = if reactive {
+            rows.iter().map(|r| Delta::insert(r.clone())).collect()
+        } else {
+            Vec::new()
+        };
 
         // Insert rows and collect their IDs
         let mut inserted_ids = hashbrown::HashSet::new();
         for row in rows {
-            inserted_ids.insert(row.id());
-            store
-                .insert(row)
-                .map_err(|e| JsValue::from_str(&alloc::format!("{:?}", e)))?;
+            if reactive {
+                inserted_ids.insert(row.id());
+            }
+            store.insert(row).map_err(CynosError::from)?;
         }
 
         // Notify query registry with changed IDs and deltas
-        if let Some(table_id) = self.table_id_map.borrow().get(&self.table_name).copied() {
+        let table_id = self.table_id_map.borrow().get(&self.table_name).copied();
+        if let Some(table_id) = table_id.filter(|_| reactive) {
             drop(cache); // Release borrow before notifying
             self.query_registry
                 .borrow_mut()
@@ -1791,8 +2660,10 @@ pub struct UpdateBuilder {
     cache: Rc<RefCell<TableCache>>,
     query_registry: Rc<RefCell<LiveRegistry>>,
     table_id_map: Rc<RefCell<hashbrown::HashMap<String, TableId>>>,
+    computed_defaults: Rc<RefCell<ComputedDefaults>>,
     table_name: String,
     set_values: Vec<(String, JsValue)>,
+    reset_columns: Vec<String>,
     where_clause: Option<Expr>,
 }
 
@@ -1801,17 +2672,36 @@ impl UpdateBuilder {
         cache: Rc<RefCell<TableCache>>,
         query_registry: Rc<RefCell<LiveRegistry>>,
         table_id_map: Rc<RefCell<hashbrown::HashMap<String, TableId>>>,
+        computed_defaults: Rc<RefCell<ComputedDefaults>>,
         table: &str,
     ) -> Self {
         Self {
             cache,
             query_registry,
             table_id_map,
+            computed_defaults,
             table_name: table.to_string(),
             set_values: Vec::new(),
+            reset_columns: Vec::new(),
             where_clause: None,
         }
     }
+
+    pub(crate) fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
+    pub(crate) fn set_values(&self) -> &[(String, JsValue)] {
+        &self.set_values
+    }
+
+    pub(crate) fn reset_columns(&self) -> &[String] {
+        &self.reset_columns
+    }
+
+    pub(crate) fn where_clause(&self) -> Option<&Expr> {
+        self.where_clause.as_ref()
+    }
 }
 
 #[wasm_bindgen]
@@ -1839,6 +2729,17 @@ impl UpdateBuilder {
         self
     }
 
+    /// Resets a column to its default: the default expression evaluated
+    /// against the updated row, else the declared default value, else NULL.
+    ///
+    /// Updates never apply defaults on their own; a column only changes when
+    /// it is set or reset explicitly.
+    #[wasm_bindgen(js_name = setDefault)]
+    pub fn set_default(mut self, column: &str) -> Self {
+        self.reset_columns.push(column.to_string());
+        self
+    }
+
     /// Sets or extends the WHERE clause.
     /// Multiple calls to where_() are combined with AND.
     #[wasm_bindgen(js_name = "where")]
@@ -1854,11 +2755,16 @@ impl UpdateBuilder {
     pub async fn exec(&self) -> Result<JsValue, JsValue> {
         let schema = {
             let cache = self.cache.borrow();
-            let store = cache.get_table(&self.table_name).ok_or_else(|| {
-                JsValue::from_str(&alloc::format!("Table not found: {}", self.table_name))
-            })?;
+            let store = cache
+                .get_table(&self.table_name)
+                .ok_or_else(|| CynosError::table_not_found(&self.table_name))?;
             store.schema().clone()
         };
+        let defaults = self.computed_defaults.borrow();
+        let defaults = defaults
+            .get(&self.table_name)
+            .map_or(&[][..], Vec::as_slice);
+        check_column_updates(&self.set_values, &self.reset_columns, &schema, defaults)?;
 
         // Find rows to update using query engine (with index optimization)
         let rows_to_update: Vec<Row> = if let Some(ref predicate) = self.where_clause {
@@ -1880,69 +2786,78 @@ impl UpdateBuilder {
             // Execute using query engine (with index optimization)
             let cache = self.cache.borrow();
             execute_plan(&cache, &self.table_name, plan)
-                .map_err(|e| JsValue::from_str(&alloc::format!("Query execution error: {:?}", e)))?
+                .map_err(CynosError::from)?
                 .into_iter()
                 .map(|rc| (*rc).clone())
                 .collect()
         } else {
             // No WHERE clause - update all rows (full scan is necessary)
             let cache = self.cache.borrow();
-            let store = cache.get_table(&self.table_name).ok_or_else(|| {
-                JsValue::from_str(&alloc::format!("Table not found: {}", self.table_name))
-            })?;
+            let store = cache
+                .get_table(&self.table_name)
+                .ok_or_else(|| CynosError::table_not_found(&self.table_name))?;
             store.scan().map(|rc| (*rc).clone()).collect()
         };
 
+        // Compute every new row before touching the store, so a failing
+        // expression leaves the table unchanged
+        let mut new_rows = Vec::with_capacity(rows_to_update.len());
+        for old_row in &rows_to_update {
+            let new_values = apply_column_updates(
+                old_row,
+                &self.set_values,
+                &self.reset_columns,
+                &schema,
+                defaults,
+            )?;
+            let new_version = old_row.version().wrapping_add(1);
+            new_rows.push(Row::new_with_version(old_row.id(), new_version, new_values));
+        }
+
         let mut cache = self.cache.borrow_mut();
-        let store = cache.get_table_mut(&self.table_name).ok_or_else(|| {
-            JsValue::from_str(&alloc::format!("Table not found: {}", self.table_name))
-        })?;
+        let store = cache
+            .get_table_mut(&self.table_name)
+            .ok_or_else(|| CynosError::table_not_found(&self.table_name))?;
 
+        let reactive = self.query_registry.borrow().is_enabled();
         let mut deltas = Vec::new();
         let mut update_count = 0;
         let mut updated_ids = hashbrown::HashSet::new();
+        let mut result = Ok(());
+
+        for (old_row, new_row) in rows_to_update.into_iter().zip(new_rows) {
+            let delta = reactive.then(|| {
+                (
+                    Delta::delete(old_row.clone()),
+                    Delta::insert(new_row.clone()),
+                )
+            });
 
-        for old_row in rows_to_update {
-            // Create new row with updated values
-            let mut new_values = old_row.values().to_vec();
-
-            for (col_name, js_val) in &self.set_values {
-                if let Some(col) = schema.get_column(col_name) {
-                    let idx = col.index();
-                    let value = js_to_value(js_val, col.data_type())?;
-                    if idx < new_values.len() {
-                        new_values[idx] = value;
-                    }
-                }
+            // Update in store; rows already written are still reported below
+            if let Err(err) = store.update(old_row.id(), new_row) {
+                result = Err(CynosError::from(err));
+                break;
             }
 
-            // Create new row with incremented version
-            let new_version = old_row.version().wrapping_add(1);
-            let new_row = Row::new_with_version(old_row.id(), new_version, new_values);
-
-            // Build deltas
-            deltas.push(Delta::delete(old_row.clone()));
-            deltas.push(Delta::insert(new_row.clone()));
-
-            // Track updated row ID
-            updated_ids.insert(old_row.id());
-
-            // Update in store
-            store
-                .update(old_row.id(), new_row)
-                .map_err(|e| JsValue::from_str(&alloc::format!("{:?}", e)))?;
-
+            // Build deltas and track the updated row ID, unless reactivity is off
+            if let Some((delete, insert)) = delta {
+                deltas.push(delete);
+                deltas.push(insert);
+                updated_ids.insert(old_row.id());
+            }
             update_count += 1;
         }
 
         // Notify query registry with changed IDs and deltas
-        if let Some(table_id) = self.table_id_map.borrow().get(&self.table_name).copied() {
+        let table_id = self.table_id_map.borrow().get(&self.table_name).copied();
+        if let Some(table_id) = table_id.filter(|_| reactive && update_count > 0) {
             drop(cache);
             self.query_registry
                 .borrow_mut()
                 .on_table_change_delta(table_id, deltas, &updated_ids);
         }
 
+        result?;
         Ok(JsValue::from_f64(update_count as f64))
     }
 }
@@ -1972,6 +2887,82 @@ impl DeleteBuilder {
             where_clause: None,
         }
     }
+
+    pub(crate) fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
+    pub(crate) fn where_clause(&self) -> Option<&Expr> {
+        self.where_clause.as_ref()
+    }
+
+    /// Stamps the soft-delete column of the matching live rows with
+    /// `deleted_at` instead of removing them. Rows that are already
+    /// soft-deleted keep their original stamp and are not counted.
+    fn soft_delete_rows(
+        &self,
+        schema: &Table,
+        column: &cynos_core::schema::Column,
+        deleted_at: Value,
+    ) -> Result<usize, JsValue> {
+        let flag = AstExpr::column(&self.table_name, column.name(), column.index());
+        let mut predicate = AstExpr::is_null(flag);
+        if let Some(where_clause) = &self.where_clause {
+            let get_col_info = |name: &str| -> Option<(String, usize, DataType)> {
+                schema
+                    .get_column(name)
+                    .map(|col| (self.table_name.clone(), col.index(), col.data_type()))
+            };
+            predicate = AstExpr::and(where_clause.to_ast_with_table(&get_col_info), predicate);
+        }
+        let plan = LogicalPlan::Filter {
+            input: Box::new(LogicalPlan::Scan {
+                table: self.table_name.clone(),
+            }),
+            predicate,
+        };
+        let rows_to_stamp: Vec<Row> = {
+            let cache = self.cache.borrow();
+            execute_plan(&cache, &self.table_name, plan)
+                .map_err(CynosError::from)?
+                .into_iter()
+                .map(|rc| (*rc).clone())
+                .collect()
+        };
+
+        let reactive = self.query_registry.borrow().is_enabled();
+        let mut deltas = Vec::new();
+        let mut stamped_ids = hashbrown::HashSet::new();
+        {
+            let mut cache = self.cache.borrow_mut();
+            let store = cache
+                .get_table_mut(&self.table_name)
+                .ok_or_else(|| CynosError::table_not_found(&self.table_name))?;
+            for old_row in &rows_to_stamp {
+                let mut values = old_row.values().to_vec();
+                values[column.index()] = deleted_at.clone();
+                let new_row =
+                    Row::new_with_version(old_row.id(), old_row.version().wrapping_add(1), values);
+                if reactive {
+                    deltas.push(Delta::delete(old_row.clone()));
+                    deltas.push(Delta::insert(new_row.clone()));
+                    stamped_ids.insert(old_row.id());
+                }
+                store
+                    .update(old_row.id(), new_row)
+                    .map_err(CynosError::from)?;
+            }
+        }
+
+        let table_id = self.table_id_map.borrow().get(&self.table_name).copied();
+        if let Some(table_id) = table_id.filter(|_| reactive) {
+            self.query_registry
+                .borrow_mut()
+                .on_table_change_delta(table_id, deltas, &stamped_ids);
+        }
+
+        Ok(rows_to_stamp.len())
+    }
 }
 
 #[wasm_bindgen]
@@ -1988,43 +2979,59 @@ impl DeleteBuilder {
     }
 
     /// Executes the delete operation.
+    ///
+    /// On a table with a soft-delete column, matching rows are stamped with
+    /// the current time instead of being removed.
     pub async fn exec(&self) -> Result<JsValue, JsValue> {
         let schema = {
             let cache = self.cache.borrow();
-            let store = cache.get_table(&self.table_name).ok_or_else(|| {
-                JsValue::from_str(&alloc::format!("Table not found: {}", self.table_name))
-            })?;
+            let store = cache
+                .get_table(&self.table_name)
+                .ok_or_else(|| CynosError::table_not_found(&self.table_name))?;
             store.schema().clone()
         };
 
+        if let Some(column) = schema.soft_delete_column() {
+            let deleted_at = Value::DateTime(js_sys::Date::now() as i64);
+            let count = self.soft_delete_rows(&schema, column, deleted_at)?;
+            return Ok(JsValue::from_f64(count as f64));
+        }
+
+        let reactive = self.query_registry.borrow().is_enabled();
+
         // Fast path: DELETE without WHERE clause - use clear() for O(1) deletion
         if self.where_clause.is_none() {
             // Collect all rows for IVM notification before clearing
             let (delete_count, deltas, deleted_ids) = {
                 let cache = self.cache.borrow();
-                let store = cache.get_table(&self.table_name).ok_or_else(|| {
-                    JsValue::from_str(&alloc::format!("Table not found: {}", self.table_name))
-                })?;
+                let store = cache
+                    .get_table(&self.table_name)
+                    .ok_or_else(|| CynosError::table_not_found(&self.table_name))?;
 
-                let rows: Vec<_> = store.scan().collect();
-                let count = rows.len();
-                let deltas: Vec<Delta<Row>> =
-                    rows.iter().map(|r| Delta::delete((**r).clone())).collect();
-                let ids: hashbrown::HashSet<_> = rows.iter().map(|r| r.id()).collect();
-                (count, deltas, ids)
+                if !reactive {
+                    (store.len(), Vec::new(), hashbrown::HashSet::new())
+                } else {
+                    let rows: Vec<_> = store.scan().collect();
+                    let count = rows.len();
+                    let deltas: Vec<Delta<Row>> =
+                        rows.iter().map(|r| Delta::delete((**r).clone())).collect();
+                    let ids: hashbrown::HashSet<_> = rows.iter().map(|r| r.id()).collect();
+                    (count, deltas, ids)
+                }
             };
 
             // Clear the table (O(1) operation)
             {
                 let mut cache = self.cache.borrow_mut();
-                let store = cache.get_table_mut(&self.table_name).ok_or_else(|| {
-                    JsValue::from_str(&alloc::format!("Table not found: {}", self.table_name))
-                })?;
+                let store = cache
+                    .get_table_mut(&self.table_name)
+                    .ok_or_else(|| CynosError::table_not_found(&self.table_name))?;
                 store.clear();
             }
 
             // Notify query registry
-            if let Some(table_id) = self.table_id_map.borrow().get(&self.table_name).copied() {
+            let table_id = self.table_id_map.borrow().get(&self.table_name).copied();
+            if let Some(table_id) = table_id.filter(|_| reactive) {
                 self.query_registry.borrow_mut().on_table_change_delta(
                     table_id,
                     deltas,
@@ -2056,7 +3063,7 @@ impl DeleteBuilder {
             // Execute using query engine (with index optimization)
             let cache = self.cache.borrow();
             execute_plan(&cache, &self.table_name, plan)
-                .map_err(|e| JsValue::from_str(&alloc::format!("Query execution error: {:?}", e)))?
+                .map_err(CynosError::from)?
                 .into_iter()
                 .map(|rc| (*rc).clone())
                 .collect()
@@ -2067,23 +3074,28 @@ impl DeleteBuilder {
         let deleted_ids: hashbrown::HashSet<_> = row_ids.iter().copied().collect();
         let delete_count = row_ids.len();
 
-        // Build deltas for IVM notification
-        let deltas: Vec<Delta<Row>> = rows_to_delete
-            .iter()
-            .map(|r| Delta::delete(r.clone()))
-            .collect();
+        // Build deltas for IVM notification, unless reactivity is off
+        let deltas: Vec<Delta<Row>> = if reactive {
+            rows_to_delete
+                .iter()
+                .map(|r| Delta::delete(r.clone()))
+                .collect()
+        } else {
+            Vec::new()
+        };
 
         // Use batch delete for better performance
         {
             let mut cache = self.cache.borrow_mut();
-            let store = cache.get_table_mut(&self.table_name).ok_or_else(|| {
-                JsValue::from_str(&alloc::format!("Table not found: {}", self.table_name))
-            })?;
+            let store = cache
+                .get_table_mut(&self.table_name)
+                .ok_or_else(|| CynosError::table_not_found(&self.table_name))?;
             store.delete_batch(&row_ids);
         }
 
         // Notify query registry with changed IDs and deltas
-        if let Some(table_id) = self.table_id_map.borrow().get(&self.table_name).copied() {
+        let table_id = self.table_id_map.borrow().get(&self.table_name).copied();
+        if let Some(table_id) = table_id.filter(|_| reactive) {
             self.query_registry
                 .borrow_mut()
                 .on_table_change_delta(table_id, deltas, &deleted_ids);
@@ -2097,170 +3109,12 @@ impl DeleteBuilder {
 // JSONB helpers for evaluate_predicate
 // ---------------------------------------------------------------------------
 
-/// Minimal JSON text parser producing `cynos_jsonb::JsonbValue`.
+/// Parses JSON text into a `cynos_jsonb::JsonbValue`.
 ///
-/// Handles: null, true, false, numbers, quoted strings, arrays, objects.
-/// This mirrors the logic in `PhysicalPlanRunner::parse_json_str` so that
-/// the re-query filter path and the query executor agree on semantics.
+/// Uses the same depth-limited parser as the query executor so that the
+/// re-query filter path and the executor agree on semantics.
 fn parse_json_text(s: &str) -> Option<cynos_jsonb::JsonbValue> {
-    let s = s.trim();
-    if s == "null" {
-        return Some(cynos_jsonb::JsonbValue::Null);
-    }
-    if s == "true" {
-        return Some(cynos_jsonb::JsonbValue::Bool(true));
-    }
-    if s == "false" {
-        return Some(cynos_jsonb::JsonbValue::Bool(false));
-    }
-    if let Ok(n) = s.parse::<f64>() {
-        return Some(cynos_jsonb::JsonbValue::Number(n));
-    }
-    if s.starts_with('"') && s.ends_with('"') && s.len() >= 2 {
-        let inner = &s[1..s.len() - 1];
-        return Some(cynos_jsonb::JsonbValue::String(unescape_json(inner)));
-    }
-    if s.starts_with('{') {
-        return parse_json_object(s);
-    }
-    if s.starts_with('[') {
-        return parse_json_array(s);
-    }
-    None
-}
-
-fn unescape_json(s: &str) -> String {
-    let mut result = String::new();
-    let mut chars = s.chars();
-    while let Some(c) = chars.next() {
-        if c == '\\' {
-            match chars.next() {
-                Some('n') => result.push('\n'),
-                Some('t') => result.push('\t'),
-                Some('r') => result.push('\r'),
-                Some('"') => result.push('"'),
-                Some('\\') => result.push('\\'),
-                Some('/') => result.push('/'),
-                Some(other) => {
-                    result.push('\\');
-                    result.push(other);
-                }
-                None => result.push('\\'),
-            }
-        } else {
-            result.push(c);
-        }
-    }
-    result
-}
-
-fn parse_json_object(s: &str) -> Option<cynos_jsonb::JsonbValue> {
-    let s = s.trim();
-    if !s.starts_with('{') || !s.ends_with('}') {
-        return None;
-    }
-    let inner = s[1..s.len() - 1].trim();
-    if inner.is_empty() {
-        return Some(cynos_jsonb::JsonbValue::Object(
-            cynos_jsonb::JsonbObject::new(),
-        ));
-    }
-    let mut obj = cynos_jsonb::JsonbObject::new();
-    for pair in split_json_top_level(inner, ',') {
-        let pair = pair.trim();
-        // Find the colon separating key from value
-        let colon_pos = find_json_colon(pair)?;
-        let key_str = pair[..colon_pos].trim();
-        let val_str = pair[colon_pos + 1..].trim();
-        // Key must be a quoted string
-        if key_str.starts_with('"') && key_str.ends_with('"') && key_str.len() >= 2 {
-            let key = unescape_json(&key_str[1..key_str.len() - 1]);
-            let val = parse_json_text(val_str)?;
-            obj.insert(key, val);
-        } else {
-            return None;
-        }
-    }
-    Some(cynos_jsonb::JsonbValue::Object(obj))
-}
-
-fn parse_json_array(s: &str) -> Option<cynos_jsonb::JsonbValue> {
-    let s = s.trim();
-    if !s.starts_with('[') || !s.ends_with(']') {
-        return None;
-    }
-    let inner = s[1..s.len() - 1].trim();
-    if inner.is_empty() {
-        return Some(cynos_jsonb::JsonbValue::Array(Vec::new()));
-    }
-    let mut arr = Vec::new();
-    for elem in split_json_top_level(inner, ',') {
-        arr.push(parse_json_text(elem.trim())?);
-    }
-    Some(cynos_jsonb::JsonbValue::Array(arr))
-}
-
-/// Split a JSON string at top-level occurrences of `sep`,
-/// respecting nested braces, brackets, and quoted strings.
-fn split_json_top_level(s: &str, sep: char) -> Vec<&str> {
-    let mut parts = Vec::new();
-    let mut depth = 0i32;
-    let mut in_string = false;
-    let mut escape = false;
-    let mut start = 0;
-    for (i, c) in s.char_indices() {
-        if escape {
-            escape = false;
-            continue;
-        }
-        if c == '\\' && in_string {
-            escape = true;
-            continue;
-        }
-        if c == '"' {
-            in_string = !in_string;
-            continue;
-        }
-        if in_string {
-            continue;
-        }
-        if c == '{' || c == '[' {
-            depth += 1;
-        } else if c == '}' || c == ']' {
-            depth -= 1;
-        } else if c == sep && depth == 0 {
-            parts.push(&s[start..i]);
-            start = i + c.len_utf8();
-        }
-    }
-    if start <= s.len() {
-        parts.push(&s[start..]);
-    }
-    parts
-}
-
-/// Find the first colon at top level (outside strings and nested structures).
-fn find_json_colon(s: &str) -> Option<usize> {
-    let mut in_string = false;
-    let mut escape = false;
-    for (i, c) in s.char_indices() {
-        if escape {
-            escape = false;
-            continue;
-        }
-        if c == '\\' && in_string {
-            escape = true;
-            continue;
-        }
-        if c == '"' {
-            in_string = !in_string;
-            continue;
-        }
-        if !in_string && c == ':' {
-            return Some(i);
-        }
-    }
-    None
+    cynos_jsonb::parse_json(s).ok()
 }
 
 /// Compare a `cynos_jsonb::JsonbValue` with a `cynos_core::Value`.
@@ -2618,9 +3472,12 @@ pub(crate) fn evaluate_predicate(predicate: &Expr, row: &Row, schema: &Table) ->
         }
         ExprInner::Not { inner } => !evaluate_predicate(inner, row, schema),
         ExprInner::True => true,
-        // ColumnRef / Literal are value expressions, not predicates.
+        // ColumnRef / Concat / Literal are value expressions, not predicates.
         // Treating them as `true` preserves backward compatibility.
-        ExprInner::ColumnRef { .. } | ExprInner::Literal { .. } => true,
+        ExprInner::ColumnRef { .. }
+        | ExprInner::Concat { .. }
+        | ExprInner::Literal { .. }
+        | ExprInner::TypedNull { .. } => true,
     }
 }
 
@@ -2646,6 +3503,7 @@ mod tests {
         query_registry: Rc<RefCell<LiveRegistry>>,
         table_id_map: Rc<RefCell<hashbrown::HashMap<String, TableId>>>,
         schema_layout_cache: Rc<RefCell<SchemaLayoutCache>>,
+        schema_epoch: Rc<RefCell<u64>>,
         plan_cache: Rc<RefCell<PlanCache>>,
     }
 
@@ -2660,6 +3518,7 @@ mod tests {
                 self.query_registry.clone(),
                 self.table_id_map.clone(),
                 self.schema_layout_cache.clone(),
+                self.schema_epoch.clone(),
                 self.plan_cache.clone(),
                 columns,
             )
@@ -2736,6 +3595,7 @@ mod tests {
             query_registry,
             table_id_map,
             schema_layout_cache: Rc::new(RefCell::new(SchemaLayoutCache::new())),
+            schema_epoch: Rc::new(RefCell::new(0)),
             plan_cache: Rc::new(RefCell::new(PlanCache::default_size())),
         }
     }
@@ -2801,10 +3661,300 @@ mod tests {
             query_registry,
             table_id_map,
             schema_layout_cache: Rc::new(RefCell::new(SchemaLayoutCache::new())),
+            schema_epoch: Rc::new(RefCell::new(0)),
             plan_cache: Rc::new(RefCell::new(PlanCache::default_size())),
         }
     }
 
+    fn build_soft_delete_test_context() -> TestSelectContext {
+        let posts = TableBuilder::new("posts")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("title", DataType::String)
+            .unwrap()
+            .add_column("deleted_at", DataType::DateTime)
+            .unwrap()
+            .add_nullable(&["deleted_at"])
+            .add_primary_key(&["id"], false)
+            .unwrap()
+            .soft_delete("deleted_at")
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut cache = TableCache::new();
+        cache.create_table(posts).unwrap();
+        {
+            let store = cache.get_table_mut("posts").unwrap();
+            for (id, title) in [(1, "a"), (2, "b"), (3, "c")] {
+                store
+                    .insert(Row::new(
+                        id as u64,
+                        vec![Value::Int64(id), Value::String(title.into()), Value::Null],
+                    ))
+                    .unwrap();
+            }
+        }
+
+        let query_registry = Rc::new(RefCell::new(LiveRegistry::new()));
+        query_registry
+            .borrow_mut()
+            .set_self_ref(query_registry.clone());
+        let table_id_map = Rc::new(RefCell::new(hashbrown::HashMap::new()));
+        table_id_map.borrow_mut().insert("posts".into(), 1);
+
+        TestSelectContext {
+            cache: Rc::new(RefCell::new(cache)),
+            query_registry,
+            table_id_map,
+            schema_layout_cache: Rc::new(RefCell::new(SchemaLayoutCache::new())),
+            schema_epoch: Rc::new(RefCell::new(0)),
+            plan_cache: Rc::new(RefCell::new(PlanCache::default_size())),
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn test_soft_delete_stamps_rows_and_selects_skip_them() {
+        let ctx = build_soft_delete_test_context();
+        let schema = ctx
+            .cache
+            .borrow()
+            .get_table("posts")
+            .unwrap()
+            .schema()
+            .clone();
+        let flag = schema.soft_delete_column().unwrap();
+        let delete = || {
+            DeleteBuilder::new(
+                ctx.cache.clone(),
+                ctx.query_registry.clone(),
+                ctx.table_id_map.clone(),
+                "posts",
+            )
+            .where_(&crate::col("id").eq(&JsValue::from_f64(2.0)))
+        };
+
+        assert_eq!(
+            delete()
+                .soft_delete_rows(&schema, flag, Value::DateTime(1_000))
+                .unwrap(),
+            1
+        );
+        // A second delete finds no live row and keeps the first stamp.
+        assert_eq!(
+            delete()
+                .soft_delete_rows(&schema, flag, Value::DateTime(2_000))
+                .unwrap(),
+            0
+        );
+
+        let cache = ctx.cache.borrow();
+        let store = cache.get_table("posts").unwrap();
+        assert_eq!(store.len(), 3);
+        assert_eq!(store.get(2).unwrap().get(2), Some(&Value::DateTime(1_000)));
+
+        let ids = |builder: SelectBuilder| -> Vec<u64> {
+            let plan = builder.build_logical_plan("posts").unwrap();
+            execute_plan(&cache, "posts", plan)
+                .unwrap()
+                .iter()
+                .map(|row| row.id())
+                .collect()
+        };
+        assert_eq!(ids(ctx.builder().from("posts")), vec![1, 3]);
+        assert_eq!(
+            ids(ctx
+                .builder()
+                .from("posts")
+                .where_(&crate::col("id").gt(&JsValue::from_f64(1.0)))),
+            vec![3]
+        );
+        assert_eq!(
+            ids(ctx.builder().from("posts").with_deleted()),
+            vec![1, 2, 3]
+        );
+        assert_eq!(
+            ctx.builder()
+                .from("posts")
+                .count_matching_rows(&cache, "posts")
+                .unwrap(),
+            2
+        );
+    }
+
+    /// Adds `comments`, a second soft-delete table, and soft-deletes post 2
+    /// and comments 11 and 13.
+    fn build_soft_delete_join_test_context() -> TestSelectContext {
+        let ctx = build_soft_delete_test_context();
+        let comments = TableBuilder::new("comments")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("post_id", DataType::Int64)
+            .unwrap()
+            .add_column("deleted_at", DataType::DateTime)
+            .unwrap()
+            .add_nullable(&["deleted_at"])
+            .add_primary_key(&["id"], false)
+            .unwrap()
+            .soft_delete("deleted_at")
+            .unwrap()
+            .build()
+            .unwrap();
+        {
+            let mut cache = ctx.cache.borrow_mut();
+            cache.create_table(comments).unwrap();
+            let store = cache.get_table_mut("comments").unwrap();
+            for (id, post_id, deleted) in
+                [(10, 1, false), (11, 1, true), (12, 2, false), (13, 3, true)]
+            {
+                let deleted_at = if deleted {
+                    Value::DateTime(1_000)
+                } else {
+                    Value::Null
+                };
+                store
+                    .insert(Row::new(
+                        id as u64,
+                        vec![Value::Int64(id), Value::Int64(post_id), deleted_at],
+                    ))
+                    .unwrap();
+            }
+            cache
+                .get_table_mut("posts")
+                .unwrap()
+                .update(
+                    2,
+                    Row::new(
+                        2,
+                        vec![
+                            Value::Int64(2),
+                            Value::String("b".into()),
+                            Value::DateTime(1_000),
+                        ],
+                    ),
+                )
+                .unwrap();
+        }
+        ctx.table_id_map.borrow_mut().insert("comments".into(), 2);
+        ctx
+    }
+
+    #[wasm_bindgen_test]
+    fn test_soft_delete_filters_every_joined_table() {
+        let ctx = build_soft_delete_join_test_context();
+        let on = || {
+            crate::expr::Column::new_simple("posts.id").eq(&JsValue::from_str("comments.post_id"))
+        };
+        let pairs = |builder: SelectBuilder| -> Vec<(Value, Value)> {
+            let cache = ctx.cache.borrow();
+            let plan = builder.build_logical_plan("posts").unwrap();
+            let mut pairs: Vec<(Value, Value)> = execute_plan(&cache, "posts", plan)
+                .unwrap()
+                .iter()
+                .map(|row| (row.get(0).unwrap().clone(), row.get(1).unwrap().clone()))
+                .collect();
+            pairs.sort_by_key(|(post, comment)| (format!("{:?}", post), format!("{:?}", comment)));
+            pairs
+        };
+        let select = || {
+            let columns = js_sys::Array::new();
+            columns.push(&JsValue::from_str("posts.id"));
+            columns.push(&JsValue::from_str("comments.id"));
+            ctx.builder_with_columns(columns.into()).from("posts")
+        };
+
+        // Only live posts joined to live comments.
+        assert_eq!(
+            pairs(select().inner_join("comments", &on(), None)),
+            vec![(Value::Int64(1), Value::Int64(10))]
+        );
+
+        // Post 3's only comment is deleted, so it is padded with NULL rather
+        // than dropped.
+        assert_eq!(
+            pairs(select().left_join("comments", &on(), None)),
+            vec![
+                (Value::Int64(1), Value::Int64(10)),
+                (Value::Int64(3), Value::Null),
+            ]
+        );
+
+        // Comment 12 belongs to the deleted post 2, so its post is NULL.
+        let mut right = select().left_join("comments", &on(), None);
+        right.joins.last_mut().unwrap().join_type = JoinType::Right;
+        assert_eq!(
+            pairs(right),
+            vec![
+                (Value::Int64(1), Value::Int64(10)),
+                (Value::Null, Value::Int64(12)),
+            ]
+        );
+
+        // A soft-delete table is filtered when it is only joined, too.
+        let columns = js_sys::Array::new();
+        columns.push(&JsValue::from_str("comments.id"));
+        columns.push(&JsValue::from_str("posts.id"));
+        let from_comments = ctx
+            .builder_with_columns(columns.into())
+            .from("comments")
+            .inner_join("posts", &on(), None);
+        let cache = ctx.cache.borrow();
+        let plan = from_comments.build_logical_plan("comments").unwrap();
+        let rows = execute_plan(&cache, "comments", plan).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get(0), Some(&Value::Int64(10)));
+        drop(cache);
+
+        // withDeleted() reads every row of every table.
+        assert_eq!(
+            pairs(select().inner_join("comments", &on(), None).with_deleted()).len(),
+            4
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_join_without_relating_condition_needs_allow_cross_product() {
+        let ctx = build_union_test_context();
+        // Meant `users.id = orders.id`; as typed, nothing relates `orders`.
+        let mistyped = || {
+            ctx.builder().from("users").inner_join(
+                "orders",
+                &crate::expr::Column::new_simple("users.id").eq(&JsValue::from_str("users.id")),
+                None,
+            )
+        };
+
+        // Rejected from the condition alone, even though `orders` holds a
+        // single row and the product could not grow yet.
+        assert!(mistyped().build_logical_plan("users").is_err());
+
+        ctx.cache
+            .borrow_mut()
+            .get_table_mut("orders")
+            .unwrap()
+            .insert(Row::new(12, vec![Value::Int64(12), Value::Int64(80)]))
+            .unwrap();
+        assert!(mistyped().build_logical_plan("users").is_err());
+
+        let cache = ctx.cache.borrow();
+        assert_eq!(
+            mistyped()
+                .allow_cross_product()
+                .count_matching_rows(&cache, "users")
+                .unwrap(),
+            6
+        );
+
+        let joined = ctx.builder().from("users").inner_join(
+            "orders",
+            &crate::expr::Column::new_simple("users.id").eq(&JsValue::from_str("orders.id")),
+            None,
+        );
+        assert!(joined.build_logical_plan("users").is_ok());
+    }
+
     #[wasm_bindgen_test]
     fn test_select_builder_union_executes_distinct() {
         let ctx = build_union_test_context();
@@ -2812,7 +3962,7 @@ mod tests {
         let right = ctx.builder().from("users");
 
         let union = left.union(&right).unwrap();
-        let plan = union.build_logical_plan("users");
+        let plan = union.build_logical_plan("users").unwrap();
         assert!(matches!(plan, LogicalPlan::Union { all: false, .. }));
 
         let cache = ctx.cache.borrow();
@@ -2820,6 +3970,191 @@ mod tests {
         assert_eq!(rows.len(), 3);
     }
 
+    #[wasm_bindgen_test]
+    fn test_select_builder_where_ids_fetches_rows_in_id_order() {
+        let ctx = build_union_test_context();
+        let ids = js_sys::Array::new();
+        ids.push(&JsValue::from_f64(3.0));
+        ids.push(&JsValue::from_f64(1.0));
+        ids.push(&JsValue::from_f64(42.0));
+
+        let plan = ctx
+            .builder()
+            .from("users")
+            .where_ids(&ids)
+            .build_logical_plan("users")
+            .unwrap();
+        assert!(matches!(plan, LogicalPlan::FetchByIds { ref ids, .. } if ids == &[3, 1, 42]));
+
+        let cache = ctx.cache.borrow();
+        let rows = execute_plan(&cache, "users", plan).unwrap();
+        let names: Vec<_> = rows.iter().map(|row| row.get(1).cloned()).collect();
+        assert_eq!(
+            names,
+            vec![
+                Some(Value::String("Charlie".into())),
+                Some(Value::String("Alice".into())),
+            ]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_select_builder_scalar_count_returns_bare_number() {
+        let ctx = build_union_test_context();
+        let cache = ctx.cache.borrow();
+
+        let all = ctx.builder().from("users").count();
+        assert_eq!(all.scalar_count_value(&cache, "users").unwrap(), 3);
+
+        let filtered = ctx
+            .builder()
+            .from("users")
+            .where_(&crate::expr::Column::new_simple("id").gt(&JsValue::from_f64(1.0)));
+        assert_eq!(filtered.scalar_count_value(&cache, "users").unwrap(), 2);
+
+        let none = ctx
+            .builder()
+            .from("users")
+            .where_(&crate::expr::Column::new_simple("name").eq(&JsValue::from_str("Zed")));
+        assert_eq!(none.scalar_count_value(&cache, "users").unwrap(), 0);
+
+        let limited = ctx.builder().from("users").limit(1);
+        assert!(limited.scalar_count_value(&cache, "users").is_err());
+        let paged = ctx.builder().from("users").offset(1);
+        assert!(paged.scalar_count_value(&cache, "users").is_err());
+        let grouped = ctx
+            .builder()
+            .from("users")
+            .group_by(&JsValue::from_str("name"));
+        assert!(grouped.scalar_count_value(&cache, "users").is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_select_builder_pluck_returns_flat_array_with_nulls() {
+        let ctx = build_self_join_test_context();
+        let cache = ctx.cache.borrow();
+
+        let values = ctx
+            .builder()
+            .from("employees")
+            .order_by("id", JsSortOrder::Asc)
+            .pluck_values(&cache, "employees", "manager_id")
+            .unwrap();
+        let values = js_sys::Array::from(&values);
+        assert_eq!(values.length(), 3);
+        assert!(values.get(0).is_null());
+        assert_eq!(values.get(1).as_f64(), Some(1.0));
+        assert_eq!(values.get(2).as_f64(), Some(2.0));
+
+        let columns = js_sys::Array::new();
+        columns.push(&JsValue::from_str("name"));
+        columns.push(&JsValue::from_str("id"));
+        let result = ctx
+            .builder_with_columns(columns.into())
+            .from("employees")
+            .pluck_values(&cache, "employees", "name");
+        assert!(result.is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_select_builder_first_returns_one_object_or_null() {
+        let ctx = build_self_join_test_context();
+        let cache = ctx.cache.borrow();
+
+        let row = ctx
+            .builder()
+            .from("employees")
+            .order_by("id", JsSortOrder::Desc)
+            .first_row(&cache, "employees")
+            .unwrap();
+        assert!(!js_sys::Array::is_array(&row));
+        let name = js_sys::Reflect::get(&row, &JsValue::from_str("name")).unwrap();
+        assert_eq!(name.as_string().as_deref(), Some("Engineer"));
+
+        let row = ctx
+            .builder()
+            .from("employees")
+            .where_(&crate::col("id").gt(&JsValue::from_f64(10.0)))
+            .first_row(&cache, "employees")
+            .unwrap();
+        assert!(row.is_null());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_select_builder_single_rejects_multiple_matches() {
+        let ctx = build_self_join_test_context();
+        let cache = ctx.cache.borrow();
+
+        let row = ctx
+            .builder()
+            .from("employees")
+            .where_(&crate::col("id").eq(&JsValue::from_f64(2.0)))
+            .single_row(&cache, "employees")
+            .unwrap();
+        let name = js_sys::Reflect::get(&row, &JsValue::from_str("name")).unwrap();
+        assert_eq!(name.as_string().as_deref(), Some("Manager"));
+
+        let result = ctx
+            .builder()
+            .from("employees")
+            .where_(&crate::col("id").gt(&JsValue::from_f64(1.0)))
+            .single_row(&cache, "employees");
+        assert!(result.is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_select_builder_order_by_exprs_mixed_directions() {
+        let ctx = build_self_join_test_context();
+        let key = |expr: JsValue, order: JsSortOrder, nulls: Option<JsNullsOrder>| {
+            let key = js_sys::Array::new();
+            key.push(&expr);
+            key.push(&order.into());
+            if let Some(nulls) = nulls {
+                key.push(&nulls.into());
+            }
+            JsValue::from(key)
+        };
+        let ordered_ids = |keys: js_sys::Array| {
+            let plan = ctx
+                .builder()
+                .from("employees")
+                .order_by_exprs(&keys)
+                .unwrap()
+                .build_logical_plan("employees")
+                .unwrap();
+            let cache = ctx.cache.borrow();
+            execute_plan(&cache, "employees", plan)
+                .unwrap()
+                .iter()
+                .map(|row| row.id())
+                .collect::<Vec<_>>()
+        };
+
+        // Employees with a manager first, then by a computed name descending.
+        let keys = js_sys::Array::new();
+        keys.push(&key(
+            crate::col("manager_id").is_null().into(),
+            JsSortOrder::Asc,
+            None,
+        ));
+        keys.push(&key(
+            crate::col("name").concat(&JsValue::from_str("!")).into(),
+            JsSortOrder::Desc,
+            None,
+        ));
+        assert_eq!(ordered_ids(keys.clone()), vec![2, 3, 1]);
+        // The expression objects are copied, so the same keys work again.
+        assert_eq!(ordered_ids(keys), vec![2, 3, 1]);
+
+        let keys = js_sys::Array::new();
+        keys.push(&key(
+            JsValue::from_str("manager_id"),
+            JsSortOrder::Desc,
+            Some(JsNullsOrder::First),
+        ));
+        assert_eq!(ordered_ids(keys), vec![1, 3, 2]);
+    }
+
     #[wasm_bindgen_test]
     fn test_select_builder_union_all_executes_with_duplicates() {
         let ctx = build_union_test_context();
@@ -2827,7 +4162,7 @@ mod tests {
         let right = ctx.builder().from("users");
 
         let union = left.union_all(&right).unwrap();
-        let plan = union.build_logical_plan("users");
+        let plan = union.build_logical_plan("users").unwrap();
         assert!(matches!(plan, LogicalPlan::Union { all: true, .. }));
 
         let cache = ctx.cache.borrow();
@@ -2852,7 +4187,12 @@ mod tests {
             .where_(&crate::expr::Column::new_simple("name").eq(&JsValue::from_str("Bob")));
 
         let cache = ctx.cache.borrow();
-        let rows = execute_plan(&cache, "users", filtered.build_logical_plan("users")).unwrap();
+        let rows = execute_plan(
+            &cache,
+            "users",
+            filtered.build_logical_plan("users").unwrap(),
+        )
+        .unwrap();
         assert_eq!(rows.len(), 2);
         assert!(rows
             .iter()
@@ -2877,7 +4217,12 @@ mod tests {
             .limit(1);
 
         let cache = ctx.cache.borrow();
-        let rows = execute_plan(&cache, "users", ordered.build_logical_plan("users")).unwrap();
+        let rows = execute_plan(
+            &cache,
+            "users",
+            ordered.build_logical_plan("users").unwrap(),
+        )
+        .unwrap();
         assert_eq!(rows.len(), 1);
         assert_eq!(rows[0].get(0), Some(&Value::String("Charlie".into())));
     }
@@ -2892,12 +4237,80 @@ mod tests {
             Ok(_) => panic!("union should reject incompatible outputs"),
             Err(error) => error,
         };
+        let message = js_sys::Reflect::get(&error, &JsValue::from_str("message")).unwrap();
         assert_eq!(
-            error.as_string().as_deref(),
+            message.as_string().as_deref(),
             Some("UNION operands must produce the same number of columns with matching types")
         );
     }
 
+    #[wasm_bindgen_test]
+    fn test_result_schema_carries_column_metadata_through_projection() {
+        let products = TableBuilder::new("products")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("price", DataType::Float64)
+            .unwrap()
+            .add_column_metadata("price", "label", "Price")
+            .unwrap()
+            .add_column_metadata("price", "unit", "USD")
+            .unwrap()
+            .add_primary_key(&["id"], false)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut cache = TableCache::new();
+        cache.create_table(products).unwrap();
+        let ctx = TestSelectContext {
+            cache: Rc::new(RefCell::new(cache)),
+            query_registry: Rc::new(RefCell::new(LiveRegistry::new())),
+            table_id_map: Rc::new(RefCell::new(hashbrown::HashMap::new())),
+            schema_layout_cache: Rc::new(RefCell::new(SchemaLayoutCache::new())),
+            schema_epoch: Rc::new(RefCell::new(0)),
+            plan_cache: Rc::new(RefCell::new(PlanCache::default_size())),
+        };
+        let metadata_of = |schema: &js_sys::Array, index: u32, key: &str| {
+            let metadata =
+                js_sys::Reflect::get(&schema.get(index), &JsValue::from_str("metadata")).unwrap();
+            js_sys::Reflect::get(&metadata, &JsValue::from_str(key))
+                .unwrap()
+                .as_string()
+        };
+
+        // Full-table output keeps the schema metadata.
+        let schema = ctx.builder().from("products").result_schema().unwrap();
+        assert_eq!(metadata_of(&schema, 1, "unit").as_deref(), Some("USD"));
+        assert_eq!(metadata_of(&schema, 0, "unit"), None);
+
+        // The self-join renames the projected columns to qualified names; each
+        // one still carries the metadata of its source column.
+        let columns = js_sys::Array::new();
+        columns.push(&JsValue::from_str("products.price"));
+        columns.push(&JsValue::from_str("other.price"));
+        let query = ctx
+            .builder_with_columns(columns.into())
+            .from("products")
+            .inner_join(
+                "products as other",
+                &crate::expr::Column::new_simple("products.id").eq(&JsValue::from_str("other.id")),
+                None,
+            );
+        let output = query.describe_output().unwrap();
+        assert_eq!(output.column_names(), vec!["products.price", "other.price"]);
+        for column in &output.columns {
+            assert_eq!(
+                column.metadata.get("label").map(String::as_str),
+                Some("Price")
+            );
+        }
+
+        let schema = query.result_schema().unwrap();
+        let name = js_sys::Reflect::get(&schema.get(1), &JsValue::from_str("name")).unwrap();
+        assert_eq!(name.as_string().as_deref(), Some("other.price"));
+        assert_eq!(metadata_of(&schema, 1, "unit").as_deref(), Some("USD"));
+    }
+
     #[wasm_bindgen_test]
     fn test_select_builder_self_join_projection_uses_joined_row_offsets() {
         let ctx = build_self_join_test_context();
@@ -2912,9 +4325,10 @@ mod tests {
                 "employees as managers",
                 &crate::expr::Column::new_simple("employees.manager_id")
                     .eq(&JsValue::from_str("managers.id")),
+                None,
             );
 
-        let plan = query.build_logical_plan("employees");
+        let plan = query.build_logical_plan("employees").unwrap();
         match &plan {
             LogicalPlan::Project { columns, .. } => {
                 assert_eq!(columns.len(), 2);
@@ -7,7 +7,7 @@
 use alloc::boxed::Box;
 use alloc::rc::Rc;
 use alloc::vec::Vec;
-use cynos_core::{Row, Value, DUMMY_ROW_ID};
+use cynos_core::{Row, RowId, Value, DUMMY_ROW_ID};
 use cynos_index::KeyRange;
 use cynos_query::context::{ExecutionContext, IndexInfo, QueryIndexType, TableStats};
 use cynos_query::executor::{DataSource, ExecutionError, ExecutionResult, PhysicalPlanRunner};
@@ -124,6 +124,12 @@ impl QueryResultSummaryBuilder {
                 self.mix_u64(8);
                 self.mix_bytes(&value.0);
             }
+            Value::Decimal(value) => {
+                let value = value.normalize();
+                self.mix_u64(9);
+                self.mix_bytes(&value.mantissa().to_le_bytes());
+                self.mix_u64(value.scale() as u64);
+            }
         }
     }
 }
@@ -219,6 +225,20 @@ impl<'a> DataSource for TableCacheDataSource<'a> {
         Ok(store.index_scan_with_options(index, range.as_ref(), limit, offset, reverse))
     }
 
+    fn count_index_range(
+        &self,
+        table: &str,
+        index: &str,
+        range: Option<&KeyRange<Value>>,
+    ) -> ExecutionResult<usize> {
+        let store = self
+            .cache
+            .get_table(table)
+            .ok_or_else(|| ExecutionError::TableNotFound(table.into()))?;
+
+        Ok(store.index_range_count(index, range))
+    }
+
     fn get_index_range_composite_with_limit(
         &self,
         table: &str,
@@ -378,6 +398,14 @@ impl<'a> DataSource for TableCacheDataSource<'a> {
         Ok(store.len())
     }
 
+    fn fetch_rows_by_ids(&self, table: &str, ids: &[RowId]) -> ExecutionResult<Vec<Rc<Row>>> {
+        let store = self
+            .cache
+            .get_table(table)
+            .ok_or_else(|| ExecutionError::TableNotFound(table.into()))?;
+        Ok(ids.iter().filter_map(|id| store.get(*id)).collect())
+    }
+
     fn get_gin_index_rows(
         &self,
         table: &str,
@@ -485,7 +513,7 @@ fn register_table_context(cache: &TableCache, ctx: &mut ExecutionContext, table_
         let schema = store.schema();
 
         let mut indexes = Vec::new();
-        for idx in schema.indices() {
+        for idx in schema.indices().iter().filter(|idx| !idx.is_conditional()) {
             let index_type = match idx.get_index_type() {
                 cynos_core::schema::IndexType::Hash => QueryIndexType::Hash,
                 cynos_core::schema::IndexType::BTree => QueryIndexType::BTree,
@@ -499,6 +527,11 @@ fn register_table_context(cache: &TableCache, ctx: &mut ExecutionContext, table_
                 )
                 .with_type(index_type),
             );
+            if let [column] = idx.columns() {
+                if let Some(histogram) = store.index_histogram(idx.name()) {
+                    ctx.register_histogram(table_name, column.name.clone(), histogram);
+                }
+            }
         }
 
         ctx.register_table(
@@ -509,12 +542,16 @@ fn register_table_context(cache: &TableCache, ctx: &mut ExecutionContext, table_
                 indexes,
             },
         );
+        ctx.register_column_count(table_name, schema.columns().len());
     }
 }
 
 /// Builds ExecutionContext from TableCache for optimizer.
 pub fn build_execution_context(cache: &TableCache, table_name: &str) -> ExecutionContext {
     let mut ctx = ExecutionContext::new();
+    if let Some(fraction) = cache.max_index_selectivity() {
+        ctx.set_max_index_selectivity(fraction);
+    }
     register_table_context(cache, &mut ctx, table_name);
     ctx
 }
@@ -529,6 +566,9 @@ pub fn build_execution_context_for_plan(
     plan: &LogicalPlan,
 ) -> ExecutionContext {
     let mut ctx = ExecutionContext::new();
+    if let Some(fraction) = cache.max_index_selectivity() {
+        ctx.set_max_index_selectivity(fraction);
+    }
     let mut tables = plan.collect_tables();
     if !tables.iter().any(|table| table == table_name) {
         tables.push(table_name.into());
@@ -912,6 +952,10 @@ mod tests {
                 3u64,
                 br#"{"tags":["portable","review"],"category":"tech"}"#.as_slice(),
             ),
+            (
+                4u64,
+                br#"{"owner":{"name":"Dana"},"category":"ops"}"#.as_slice(),
+            ),
         ] {
             store
                 .insert(Row::new(
@@ -953,6 +997,31 @@ mod tests {
         assert!(ctx.find_index("orders", &["user_id"]).is_some());
     }
 
+    #[test]
+    fn test_build_execution_context_skips_conditional_indexes() {
+        let schema = TableBuilder::new("tasks")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("owner", DataType::Int64)
+            .unwrap()
+            .add_column("done", DataType::Boolean)
+            .unwrap()
+            .add_primary_key(&["id"], false)
+            .unwrap()
+            .add_conditional_index("idx_done_owner", &["owner"], false, "done")
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut cache = TableCache::new();
+        cache.create_table(schema).unwrap();
+
+        let ctx = build_execution_context(&cache, "tasks");
+
+        assert!(ctx.find_index("tasks", &["id"]).is_some());
+        assert!(ctx.find_index("tasks", &["owner"]).is_none());
+    }
+
     #[test]
     fn test_compile_plan_prefers_index_join_for_small_outer_fk_join() {
         let cache = create_join_test_cache();
@@ -970,6 +1039,216 @@ mod tests {
         assert!(matches!(physical, PhysicalPlan::IndexNestedLoopJoin { .. }));
     }
 
+    #[test]
+    fn test_join_condition_on_one_side_runs_as_filtered_cross_product() {
+        let cache = create_join_test_cache();
+        // A mistyped condition that only looks at `users`.
+        let plan = LogicalPlan::inner_join(
+            LogicalPlan::scan("users"),
+            LogicalPlan::scan("orders"),
+            AstExpr::lt(
+                AstExpr::column("users", "id", 0),
+                AstExpr::literal(Value::Int64(2)),
+            ),
+        );
+
+        // The condition ends up filtering `users` below the cross product.
+        let physical = compile_plan(&cache, "users", plan.clone());
+        let PhysicalPlan::CrossProduct { left, .. } = &physical else {
+            panic!("expected CrossProduct, got {:?}", physical);
+        };
+        assert!(!matches!(left.as_ref(), PhysicalPlan::TableScan { .. }));
+
+        let rows = execute_plan(&cache, "users", plan).unwrap();
+        assert_eq!(rows.len(), 2 * 4096);
+    }
+
+    #[test]
+    fn test_disjoint_or_ranges_scan_index_twice() {
+        let cache = create_join_test_cache();
+        let users_id = || AstExpr::column("users", "id", 0);
+        let filtered_users = LogicalPlan::filter(
+            LogicalPlan::scan("users"),
+            AstExpr::or(
+                AstExpr::lt(users_id(), AstExpr::literal(Value::Int64(2))),
+                AstExpr::gt(users_id(), AstExpr::literal(Value::Int64(29))),
+            ),
+        );
+
+        let physical = compile_plan(&cache, "users", filtered_users.clone());
+        assert!(matches!(physical, PhysicalPlan::Union { all: true, .. }));
+        let rows = execute_plan(&cache, "users", filtered_users.clone()).unwrap();
+        assert_eq!(rows.len(), 4);
+
+        // The union keeps the users layout when joined.
+        let plan = LogicalPlan::inner_join(
+            filtered_users,
+            LogicalPlan::scan("orders"),
+            AstExpr::eq(users_id(), AstExpr::column("orders", "user_id", 1)),
+        );
+        let rows = execute_plan(&cache, "users", plan).unwrap();
+        assert_eq!(rows.len(), 4 * 128);
+    }
+
+    #[test]
+    fn test_contradictory_range_on_outer_side_pads_with_nulls() {
+        let cache = create_join_test_cache();
+        let user_id = || AstExpr::column("orders", "user_id", 1);
+        let no_orders = LogicalPlan::filter(
+            LogicalPlan::scan("orders"),
+            AstExpr::and(
+                AstExpr::gt(user_id(), AstExpr::literal(Value::Int64(10))),
+                AstExpr::lt(user_id(), AstExpr::literal(Value::Int64(5))),
+            ),
+        );
+        assert!(matches!(
+            compile_plan(&cache, "orders", no_orders.clone()),
+            PhysicalPlan::Empty
+        ));
+
+        // An inner join with no orders is empty as a whole.
+        let plan = LogicalPlan::inner_join(
+            LogicalPlan::scan("users"),
+            no_orders.clone(),
+            AstExpr::eq(AstExpr::column("users", "id", 0), user_id()),
+        );
+        assert!(execute_plan(&cache, "users", plan).unwrap().is_empty());
+
+        let plan = LogicalPlan::left_join(
+            LogicalPlan::scan("users"),
+            no_orders,
+            AstExpr::eq(AstExpr::column("users", "id", 0), user_id()),
+        );
+        let rows = execute_plan(&cache, "users", plan).unwrap();
+        assert_eq!(rows.len(), 32);
+        assert!(rows
+            .iter()
+            .all(|row| row.len() == 5 && row.get(4) == Some(&Value::Null)));
+    }
+
+    #[test]
+    fn test_zero_count_filter_under_hinted_join_runs() {
+        let users = TableBuilder::new("users")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("name", DataType::String)
+            .unwrap()
+            .add_primary_key(&["id"], false)
+            .unwrap()
+            .build()
+            .unwrap();
+        let orders = TableBuilder::new("orders")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("user_id", DataType::Int64)
+            .unwrap()
+            .add_primary_key(&["id"], false)
+            .unwrap()
+            .add_index("idx_user_id", &["user_id"], false)
+            .unwrap()
+            .build()
+            .unwrap();
+        let badges = TableBuilder::new("badges")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("user_id", DataType::Int64)
+            .unwrap()
+            .add_primary_key(&["id"], false)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut cache = TableCache::new();
+        for (schema, rows) in [
+            (
+                users,
+                (0..4)
+                    .map(|id| {
+                        alloc::vec![
+                            Value::Int64(id),
+                            Value::String(alloc::format!("user_{}", id)),
+                        ]
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+            (
+                orders,
+                alloc::vec![
+                    alloc::vec![Value::Int64(10), Value::Int64(0)],
+                    alloc::vec![Value::Int64(11), Value::Int64(1)],
+                ],
+            ),
+            (
+                badges,
+                alloc::vec![
+                    alloc::vec![Value::Int64(20), Value::Int64(0)],
+                    alloc::vec![Value::Int64(21), Value::Int64(2)],
+                ],
+            ),
+        ] {
+            let name = schema.name().to_string();
+            cache.create_table(schema).unwrap();
+            let store = cache.get_table_mut(&name).unwrap();
+            for values in rows {
+                let id = match values[0] {
+                    Value::Int64(id) => id as u64,
+                    _ => unreachable!(),
+                };
+                store.insert(Row::new(id, values)).unwrap();
+            }
+        }
+
+        // Users without orders, as the decorrelated form of
+        // `(SELECT COUNT(*) FROM orders WHERE orders.user_id = u.id) = 0`.
+        let without_orders = LogicalPlan::filter(
+            LogicalPlan::aggregate(
+                LogicalPlan::left_join(
+                    LogicalPlan::scan("users"),
+                    LogicalPlan::scan("orders"),
+                    AstExpr::eq(
+                        AstExpr::column("users", "id", 0),
+                        AstExpr::column("orders", "user_id", 1),
+                    ),
+                ),
+                alloc::vec![
+                    AstExpr::column("users", "id", 0),
+                    AstExpr::column("users", "name", 1),
+                ],
+                alloc::vec![(
+                    cynos_query::ast::AggregateFunc::Count,
+                    AstExpr::count(AstExpr::column("orders", "user_id", 1)),
+                )],
+            ),
+            AstExpr::eq(
+                AstExpr::column("users", "count", 2),
+                AstExpr::literal(Value::Int64(0)),
+            ),
+        );
+        // The hint pins the outer join only; the anti-join the zero count
+        // becomes must still get its index.
+        let plan = LogicalPlan::inner_join(
+            without_orders,
+            LogicalPlan::scan("badges"),
+            AstExpr::eq(
+                AstExpr::column("users", "id", 0),
+                AstExpr::column("badges", "user_id", 1),
+            ),
+        )
+        .with_join_algorithm(Some(cynos_query::planner::JoinAlgorithm::Hash));
+
+        let physical = explain_plan(&cache, "users", plan.clone()).physical_plan;
+        assert!(
+            physical.contains("HashJoin") && physical.contains("Anti"),
+            "expected a hash join over an anti-join, got {}",
+            physical
+        );
+        let rows = execute_plan(&cache, "users", plan).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get(0), Some(&Value::Int64(2)));
+    }
+
     #[test]
     fn test_compiled_plan_matches_physical_plan_execution() {
         let cache = create_join_test_cache();
@@ -1030,6 +1309,149 @@ mod tests {
         assert_eq!(actual_ids, alloc::vec![1, 3]);
     }
 
+    #[test]
+    fn test_jsonb_exists_uses_gin_index() {
+        let cache = create_jsonb_test_cache();
+        let exists = |path: &str| {
+            LogicalPlan::filter(
+                LogicalPlan::scan("documents"),
+                AstExpr::jsonb_exists(AstExpr::column("documents", "metadata", 1), path),
+            )
+        };
+
+        let explain = explain_plan(&cache, "documents", exists("$.owner.name"));
+        assert!(
+            explain.physical_plan.contains("GinIndexScan")
+                && explain.physical_plan.contains("\"exists\""),
+            "expected an exists GIN scan, got {}",
+            explain.physical_plan
+        );
+        assert!(
+            !explain.physical_plan.contains("TableScan"),
+            "expected no table scan, got {}",
+            explain.physical_plan
+        );
+
+        for (path, expected) in [
+            ("$.owner.name", alloc::vec![4u64]),
+            ("$.tags", alloc::vec![1, 2, 3]),
+            ("$.tags[1]", alloc::vec![1, 2, 3]),
+            ("$.missing", alloc::vec![]),
+        ] {
+            let physical = compile_plan(&cache, "documents", exists(path));
+            let compiled = compile_cached_plan(&cache, "documents", exists(path));
+            let mut ids: Vec<u64> = execute_physical_plan(&cache, &physical)
+                .unwrap()
+                .into_iter()
+                .map(|row| row.id())
+                .collect();
+            let mut compiled_ids: Vec<u64> = execute_compiled_physical_plan(&cache, &compiled)
+                .unwrap()
+                .into_iter()
+                .map(|row| row.id())
+                .collect();
+            ids.sort_unstable();
+            compiled_ids.sort_unstable();
+            assert_eq!(ids, expected, "path {}", path);
+            assert_eq!(compiled_ids, expected, "path {}", path);
+        }
+
+        // Combined with an unindexed predicate, the GIN scan still drives the
+        // query and the other predicate filters its rows.
+        let combined = LogicalPlan::filter(
+            LogicalPlan::scan("documents"),
+            AstExpr::and(
+                AstExpr::jsonb_exists(AstExpr::column("documents", "metadata", 1), "$.tags"),
+                AstExpr::gt(
+                    AstExpr::column("documents", "id", 0),
+                    AstExpr::literal(Value::Int64(1)),
+                ),
+            ),
+        );
+        let explain = explain_plan(&cache, "documents", combined.clone());
+        assert!(
+            explain.physical_plan.contains("GinIndexScan"),
+            "expected a GIN scan, got {}",
+            explain.physical_plan
+        );
+        let mut ids: Vec<u64> = execute_plan(&cache, "documents", combined)
+            .unwrap()
+            .into_iter()
+            .map(|row| row.id())
+            .collect();
+        ids.sort_unstable();
+        assert_eq!(ids, alloc::vec![2, 3]);
+    }
+
+    #[test]
+    fn test_range_selectivity_chooses_scan_or_index() {
+        let readings = TableBuilder::new("readings")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("value", DataType::Int64)
+            .unwrap()
+            .add_primary_key(&["id"], false)
+            .unwrap()
+            .add_index("idx_value", &["value"], false)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut cache = TableCache::new();
+        cache.create_table(readings).unwrap();
+        {
+            let store = cache.get_table_mut("readings").unwrap();
+            for id in 0..1000i64 {
+                store
+                    .insert(Row::new(
+                        id as u64,
+                        alloc::vec![Value::Int64(id), Value::Int64(id)],
+                    ))
+                    .unwrap();
+            }
+        }
+        let at_least = |value: i64| {
+            LogicalPlan::filter(
+                LogicalPlan::scan("readings"),
+                AstExpr::gte(
+                    AstExpr::column("readings", "value", 1),
+                    AstExpr::literal(Value::Int64(value)),
+                ),
+            )
+        };
+
+        // 90% of the rows: the index would touch nearly everything.
+        let wide = explain_plan(&cache, "readings", at_least(100)).physical_plan;
+        assert!(
+            wide.contains("TableScan") && !wide.contains("IndexScan"),
+            "expected a filtered scan, got {}",
+            wide
+        );
+
+        // 1% of the rows: the index range scan wins.
+        let narrow = explain_plan(&cache, "readings", at_least(990)).physical_plan;
+        assert!(
+            narrow.contains("IndexScan") && !narrow.contains("TableScan"),
+            "expected an index scan, got {}",
+            narrow
+        );
+
+        // Raising the threshold puts the wide range back on the index.
+        cache.set_max_index_selectivity(Some(0.95));
+        let wide = explain_plan(&cache, "readings", at_least(100)).physical_plan;
+        assert!(
+            wide.contains("IndexScan"),
+            "expected an index scan, got {}",
+            wide
+        );
+        assert_eq!(
+            execute_plan(&cache, "readings", at_least(100))
+                .unwrap()
+                .len(),
+            900
+        );
+    }
+
     #[test]
     fn test_execute_physical_plan_matches_legacy_runner_for_join_project_limit() {
         let cache = create_join_test_cache();
@@ -1438,6 +1860,161 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_compiled_plan_reports_point_and_range_index_usages() {
+        use cynos_core::{DataType, Row, Value};
+        use cynos_query::planner::{IndexScanType, IndexUsage};
+
+        let table = TableBuilder::new("scores")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("score", DataType::Int64)
+            .unwrap()
+            .add_primary_key(&["id"], false)
+            .unwrap()
+            .add_index("idx_score", &["score"], false)
+            .unwrap()
+            .build()
+            .unwrap();
+        let pk_index = table.primary_key().unwrap().name().to_string();
+        let mut cache = TableCache::new();
+        cache.create_table(table).unwrap();
+        let store = cache.get_table_mut("scores").unwrap();
+        for i in 1..=100 {
+            store
+                .insert(Row::new(
+                    i as u64,
+                    alloc::vec![Value::Int64(i), Value::Int64(i * 10)],
+                ))
+                .unwrap();
+        }
+
+        // SELECT * FROM scores WHERE id = 7
+        let point = LogicalPlan::filter(
+            LogicalPlan::scan("scores"),
+            AstExpr::eq(
+                AstExpr::column("scores", "id", 0),
+                AstExpr::literal(Value::Int64(7)),
+            ),
+        );
+        assert_eq!(
+            compile_plan(&cache, "scores", point).collect_index_usages(),
+            alloc::vec![IndexUsage {
+                table: "scores".into(),
+                index: pk_index,
+                scan_type: IndexScanType::Point,
+            }]
+        );
+
+        // SELECT * FROM scores WHERE score > 950
+        let range = LogicalPlan::filter(
+            LogicalPlan::scan("scores"),
+            AstExpr::gt(
+                AstExpr::column("scores", "score", 1),
+                AstExpr::literal(Value::Int64(950)),
+            ),
+        );
+        assert_eq!(
+            compile_plan(&cache, "scores", range).collect_index_usages(),
+            alloc::vec![IndexUsage {
+                table: "scores".into(),
+                index: "idx_score".into(),
+                scan_type: IndexScanType::Range,
+            }]
+        );
+
+        assert!(compile_plan(&cache, "scores", LogicalPlan::scan("scores"))
+            .collect_index_usages()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_hash_index_serves_equality_but_not_ranges() {
+        use cynos_core::schema::IndexType;
+        use cynos_core::{DataType, Row, Value};
+        use cynos_query::ast::SortOrder;
+        use cynos_query::planner::{IndexScanType, IndexUsage};
+
+        let table = TableBuilder::new("sessions")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("token", DataType::Int64)
+            .unwrap()
+            .add_primary_key(&["id"], false)
+            .unwrap()
+            .add_hash_index("idx_token", &["token"], false)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut cache = TableCache::new();
+        cache.create_table(table).unwrap();
+        let store = cache.get_table_mut("sessions").unwrap();
+        for i in 1..=100 {
+            store
+                .insert(Row::new(
+                    i as u64,
+                    alloc::vec![Value::Int64(i), Value::Int64(i * 10)],
+                ))
+                .unwrap();
+        }
+        assert_eq!(
+            store
+                .schema()
+                .get_index("idx_token")
+                .unwrap()
+                .get_index_type(),
+            IndexType::Hash
+        );
+
+        let token = || AstExpr::column("sessions", "token", 1);
+        let filtered = |predicate| LogicalPlan::filter(LogicalPlan::scan("sessions"), predicate);
+        let hash_usage = |scan_type| {
+            alloc::vec![IndexUsage {
+                table: "sessions".into(),
+                index: "idx_token".into(),
+                scan_type,
+            }]
+        };
+        let row_count = |plan| execute_plan(&cache, "sessions", plan).unwrap().len();
+
+        // WHERE token = 70
+        let eq = filtered(AstExpr::eq(token(), AstExpr::literal(Value::Int64(70))));
+        assert_eq!(
+            compile_plan(&cache, "sessions", eq.clone()).collect_index_usages(),
+            hash_usage(IndexScanType::Point)
+        );
+        assert_eq!(row_count(eq), 1);
+
+        // WHERE token IN (10, 20, 999)
+        let in_list = filtered(AstExpr::in_list(
+            token(),
+            alloc::vec![Value::Int64(10), Value::Int64(20), Value::Int64(999)],
+        ));
+        assert_eq!(
+            compile_plan(&cache, "sessions", in_list.clone()).collect_index_usages(),
+            hash_usage(IndexScanType::MultiPoint)
+        );
+        assert_eq!(row_count(in_list), 2);
+
+        // WHERE token > 950 has to scan.
+        let range = filtered(AstExpr::gt(token(), AstExpr::literal(Value::Int64(950))));
+        assert!(compile_plan(&cache, "sessions", range.clone())
+            .collect_index_usages()
+            .is_empty());
+        assert_eq!(row_count(range), 5);
+
+        // ORDER BY token cannot read the hash index in order either.
+        let ordered = LogicalPlan::sort(
+            LogicalPlan::scan("sessions"),
+            alloc::vec![(token(), SortOrder::Asc)],
+        );
+        assert!(compile_plan(&cache, "sessions", ordered)
+            .collect_index_usages()
+            .is_empty());
+    }
+
     #[test]
     fn test_order_by_desc_with_index() {
         use cynos_core::schema::TableBuilder;
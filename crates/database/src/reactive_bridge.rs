@@ -8,11 +8,13 @@
 //! 1. Re-query: re-execute the cached physical plan on each change (original)
 //! 2. IVM (DBSP): propagate deltas through a compiled dataflow graph (new)
 //!
-//! The IVM path is used when the query is incrementalizable (no Sort/Limit/TopN).
+//! The IVM path is used when the query is incrementalizable (no Sort without a
+//! LIMIT; a LIMIT window is maintained as a TopN over its input).
 //! Otherwise, falls back to re-query.
 
 use crate::binary_protocol::{BinaryEncoder, BinaryResult, SchemaLayout};
-use crate::convert::{gql_response_to_js, row_to_js, value_to_js};
+use crate::convert::{gql_response_to_js, row_to_js, set_property, value_to_js};
+use crate::error::CynosError;
 use crate::query_engine::{
     execute_compiled_physical_plan_with_summary, CompiledPhysicalPlan, QueryResultSummary,
 };
@@ -24,6 +26,7 @@ use core::cell::RefCell;
 use cynos_core::schema::Table;
 use cynos_core::{Row, Value};
 use cynos_incremental::{DataflowNode, Delta, MaterializedView, TableId};
+use cynos_query::executor::ExecutionError;
 use cynos_reactive::ObservableQuery;
 use cynos_storage::TableCache;
 use hashbrown::{HashMap, HashSet};
@@ -253,9 +256,14 @@ fn build_delta_batch_invalidation(
 }
 
 fn graphql_response_to_js_value(response: &cynos_gql::GraphqlResponse) -> JsValue {
-    gql_response_to_js(response).unwrap_or(JsValue::NULL)
+    gql_response_to_js(response).unwrap_or_else(|error| {
+        report_conversion_error(&error);
+        JsValue::NULL
+    })
 }
 
+type ErrorCallback = Box<dyn Fn(&ExecutionError) + 'static>;
+
 /// A re-query based observable that re-executes the query on each change.
 /// This leverages the query optimizer and indexes for optimal performance.
 /// The physical plan and lowered execution artifact are cached to avoid repeated
@@ -272,6 +280,8 @@ pub struct ReQueryObservable {
     result_summary: QueryResultSummary,
     /// Subscription callbacks
     subscriptions: Vec<(usize, Box<dyn Fn(&[Rc<Row>]) + 'static>)>,
+    /// Error callbacks, keyed by the subscription they belong to
+    error_callbacks: Vec<(usize, ErrorCallback)>,
     /// Next subscription ID
     next_sub_id: usize,
 }
@@ -300,6 +310,7 @@ impl ReQueryObservable {
             result: initial_result,
             result_summary,
             subscriptions: Vec::new(),
+            error_callbacks: Vec::new(),
             next_sub_id: 0,
         }
     }
@@ -327,10 +338,23 @@ impl ReQueryObservable {
         id
     }
 
+    /// Subscribes to changes, with `on_error` called whenever re-executing
+    /// the query fails. The previous result is kept after a failure.
+    pub fn subscribe_with_error<F, E>(&mut self, callback: F, on_error: E) -> usize
+    where
+        F: Fn(&[Rc<Row>]) + 'static,
+        E: Fn(&ExecutionError) + 'static,
+    {
+        let id = self.subscribe(callback);
+        self.error_callbacks.push((id, Box::new(on_error)));
+        id
+    }
+
     /// Unsubscribes by ID.
     pub fn unsubscribe(&mut self, id: usize) -> bool {
         let len_before = self.subscriptions.len();
         self.subscriptions.retain(|(sub_id, _)| *sub_id != id);
+        self.error_callbacks.retain(|(sub_id, _)| *sub_id != id);
         self.subscriptions.len() < len_before
     }
 
@@ -371,6 +395,20 @@ impl ReQueryObservable {
             }
         }
 
+        self.requery();
+    }
+
+    /// Re-executes the query from scratch, ignoring which rows changed, and
+    /// notifies subscribers if the result differs. Used to catch up after
+    /// changes that were never reported.
+    pub fn refresh(&mut self) {
+        if self.subscriptions.is_empty() {
+            return;
+        }
+        self.requery();
+    }
+
+    fn requery(&mut self) {
         // Re-execute the cached compiled plan (no optimization or lowering overhead)
         let cache = self.cache.borrow();
 
@@ -391,8 +429,12 @@ impl ReQueryObservable {
                     }
                 }
             }
-            Err(_) => {
-                // Query execution failed, keep old result
+            Err(error) => {
+                // Keep the old result and let subscribers that asked for
+                // errors know it is stale.
+                for (_, on_error) in &self.error_callbacks {
+                    on_error(&error);
+                }
             }
         }
     }
@@ -522,6 +564,11 @@ impl GraphqlSubscriptionObservable {
             }
         }
         self.response_dirty = true;
+        self.emit_if_changed();
+    }
+
+    /// Materializes the dirty response and sends it to callbacks if it changed.
+    fn emit_if_changed(&mut self) {
         if self.subscribers.callback_count() == 0 {
             return;
         }
@@ -535,6 +582,21 @@ impl GraphqlSubscriptionObservable {
         }
     }
 
+    /// Re-executes the root query and rebuilds the response from scratch,
+    /// notifying subscribers if it differs. Used to catch up after changes
+    /// that were never reported.
+    pub fn refresh(&mut self) {
+        if self.subscribers.total_count() == 0 {
+            return;
+        }
+        if self.requery_root_rows().is_none() {
+            return;
+        }
+        self.batch_state = cynos_gql::GraphqlBatchState::default();
+        self.response_dirty = true;
+        self.emit_if_changed();
+    }
+
     fn refresh_root_rows(&mut self, changed_ids: &HashSet<u64>) -> Option<bool> {
         if let Some(changed_rows) =
             collect_changed_rows(&self.cache, &self.compiled_plan, changed_ids)
@@ -552,6 +614,10 @@ impl GraphqlSubscriptionObservable {
             }
         }
 
+        self.requery_root_rows()
+    }
+
+    fn requery_root_rows(&mut self) -> Option<bool> {
         let cache = self.cache.borrow();
         let output =
             execute_compiled_physical_plan_with_summary(&cache, &self.compiled_plan).ok()?;
@@ -707,6 +773,16 @@ impl GraphqlDeltaObservable {
         self.subscribers.callback_count()
     }
 
+    /// Rebuilds the view from the full contents of its source tables and
+    /// notifies subscribers if the response differs. Used to catch up after
+    /// changes that were never reported.
+    pub fn resync_from(&mut self, sources: impl IntoIterator<Item = (TableId, Vec<Row>)>) {
+        self.view.rebuild_from(sources);
+        self.batch_state = cynos_gql::GraphqlBatchState::default();
+        self.response_dirty = true;
+        self.emit_if_changed();
+    }
+
     pub fn on_table_change(&mut self, table_id: TableId, deltas: Vec<Delta<Row>>) {
         if self.subscribers.total_count() == 0 {
             return;
@@ -739,6 +815,11 @@ impl GraphqlDeltaObservable {
             }
         }
         self.response_dirty = true;
+        self.emit_if_changed();
+    }
+
+    /// Materializes the dirty response and sends it to callbacks if it changed.
+    fn emit_if_changed(&mut self) {
         if self.subscribers.callback_count() == 0 {
             return;
         }
@@ -856,6 +937,118 @@ impl GraphqlSubscriptionInner {
     }
 }
 
+type RowCallback = Box<dyn Fn(Option<&Rc<Row>>) + 'static>;
+
+/// An observable over a single row, addressed by primary key.
+///
+/// Instead of re-running a query, each table change is filtered down to the
+/// watched row: while the row exists only batches that touch its row id are
+/// looked at, and while it is absent a primary-key lookup checks whether the
+/// batch inserted it. Subscribers are notified with the new row, or `None`
+/// once it is deleted, and only when the row's existence or values changed.
+pub struct RowObservable {
+    /// Reference to the table cache
+    cache: Rc<RefCell<TableCache>>,
+    /// Watched table
+    table: String,
+    /// Primary key of the watched row
+    pk_values: Vec<Value>,
+    /// Current row, if it exists
+    row: Option<Rc<Row>>,
+    /// Subscription callbacks
+    subscriptions: Vec<(usize, RowCallback)>,
+    /// Next subscription ID
+    next_sub_id: usize,
+}
+
+impl RowObservable {
+    /// Creates a row observable with the row's current value.
+    pub fn new(
+        cache: Rc<RefCell<TableCache>>,
+        table: String,
+        pk_values: Vec<Value>,
+        initial_row: Option<Rc<Row>>,
+    ) -> Self {
+        Self {
+            cache,
+            table,
+            pk_values,
+            row: initial_row,
+            subscriptions: Vec::new(),
+            next_sub_id: 0,
+        }
+    }
+
+    /// Returns the current row.
+    pub fn row(&self) -> Option<&Rc<Row>> {
+        self.row.as_ref()
+    }
+
+    /// Subscribes to changes.
+    pub fn subscribe<F: Fn(Option<&Rc<Row>>) + 'static>(&mut self, callback: F) -> usize {
+        let id = self.next_sub_id;
+        self.next_sub_id += 1;
+        self.subscriptions.push((id, Box::new(callback)));
+        id
+    }
+
+    /// Unsubscribes by ID.
+    pub fn unsubscribe(&mut self, id: usize) -> bool {
+        let len_before = self.subscriptions.len();
+        self.subscriptions.retain(|(sub_id, _)| *sub_id != id);
+        self.subscriptions.len() < len_before
+    }
+
+    /// Returns subscription count.
+    pub fn subscription_count(&self) -> usize {
+        self.subscriptions.len()
+    }
+
+    /// Called when the table changes. Notifies subscribers only if the
+    /// watched row was inserted, deleted or had its values changed.
+    pub fn on_change(&mut self, changed_ids: &HashSet<u64>) {
+        if self.subscriptions.is_empty() {
+            return;
+        }
+        if let Some(row) = &self.row {
+            if !changed_ids.contains(&row.id()) {
+                return;
+            }
+        }
+        self.reload();
+    }
+
+    /// Looks the row up again regardless of which rows changed. Used to
+    /// catch up after changes that were never reported.
+    pub fn refresh(&mut self) {
+        if self.subscriptions.is_empty() {
+            return;
+        }
+        self.reload();
+    }
+
+    fn reload(&mut self) {
+        let current = {
+            let cache = self.cache.borrow();
+            cache
+                .get_table(&self.table)
+                .and_then(|store| store.get_by_pk_values(&self.pk_values).into_iter().next())
+        };
+        let unchanged = match (&self.row, &current) {
+            (None, None) => true,
+            (Some(old), Some(new)) => Rc::ptr_eq(old, new) || old.values() == new.values(),
+            _ => false,
+        };
+        self.row = current;
+        if unchanged {
+            return;
+        }
+        for (_, callback) in &self.subscriptions {
+            callback(self.row.as_ref());
+        }
+    }
+}
+
 /// JavaScript-friendly observable query wrapper.
 /// Uses re-query strategy for optimal performance with indexes.
 #[wasm_bindgen]
@@ -866,8 +1059,6 @@ pub struct JsObservableQuery {
     projected_columns: Option<Vec<String>>,
     /// Pre-computed binary layout for getResultBinary().
     binary_layout: SchemaLayout,
-    /// Optional aggregate column names. If Some, this is an aggregate query.
-    aggregate_columns: Option<Vec<String>>,
 }
 
 impl JsObservableQuery {
@@ -881,7 +1072,6 @@ impl JsObservableQuery {
             schema,
             projected_columns: None,
             binary_layout,
-            aggregate_columns: None,
         }
     }
 
@@ -896,7 +1086,6 @@ impl JsObservableQuery {
             schema,
             projected_columns: Some(projected_columns),
             binary_layout,
-            aggregate_columns: None,
         }
     }
 
@@ -914,12 +1103,6 @@ impl JsObservableQuery {
     pub(crate) fn projected_columns(&self) -> Option<&Vec<String>> {
         self.projected_columns.as_ref()
     }
-
-    /// Get the aggregate columns.
-    #[allow(dead_code)]
-    pub(crate) fn aggregate_columns(&self) -> Option<&Vec<String>> {
-        self.aggregate_columns.as_ref()
-    }
 }
 
 #[wasm_bindgen]
@@ -928,22 +1111,41 @@ impl JsObservableQuery {
     ///
     /// The callback receives the complete current result set as a JavaScript array.
     /// It is called whenever data changes (not immediately - use getResult for initial data).
+    /// The optional `onError` callback receives the error when re-executing the
+    /// query fails, for example after a table it reads is dropped; the last
+    /// result is kept.
     /// Returns an unsubscribe function.
-    pub fn subscribe(&mut self, callback: js_sys::Function) -> js_sys::Function {
+    pub fn subscribe(
+        &mut self,
+        callback: js_sys::Function,
+        on_error: Option<js_sys::Function>,
+    ) -> js_sys::Function {
         let schema = self.schema.clone();
         let projected_columns = self.projected_columns.clone();
-        let aggregate_columns = self.aggregate_columns.clone();
 
-        let sub_id = self.inner.borrow_mut().subscribe(move |rows| {
-            let current_data = if let Some(ref cols) = aggregate_columns {
-                projected_rows_to_js_array(rows, cols)
-            } else if let Some(ref cols) = projected_columns {
+        let on_rows = move |rows: &[Rc<Row>]| {
+            let current_data = if let Some(ref cols) = projected_columns {
                 projected_rows_to_js_array(rows, cols)
             } else {
                 rows_to_js_array(rows, &schema)
             };
-            callback.call1(&JsValue::NULL, &current_data).ok();
-        });
+            match current_data {
+                Ok(current_data) => {
+                    callback.call1(&JsValue::NULL, &current_data).ok();
+                }
+                Err(error) => report_conversion_error(&error),
+            }
+        };
+        let sub_id = match on_error {
+            Some(on_error) => self.inner.borrow_mut().subscribe_with_error(
+                on_rows,
+                move |error: &ExecutionError| {
+                    let error: JsValue = CynosError::from(error.clone()).into();
+                    on_error.call1(&JsValue::NULL, &error).ok();
+                },
+            ),
+            None => self.inner.borrow_mut().subscribe(on_rows),
+        };
 
         // Create unsubscribe function
         let inner_unsub = self.inner.clone();
@@ -961,11 +1163,9 @@ impl JsObservableQuery {
 
     /// Returns the current result as a JavaScript array.
     #[wasm_bindgen(js_name = getResult)]
-    pub fn get_result(&self) -> JsValue {
+    pub fn get_result(&self) -> Result<JsValue, JsValue> {
         let inner = self.inner.borrow();
-        if let Some(ref cols) = self.aggregate_columns {
-            projected_rows_to_js_array(inner.result(), cols)
-        } else if let Some(ref cols) = self.projected_columns {
+        if let Some(ref cols) = self.projected_columns {
             projected_rows_to_js_array(inner.result(), cols)
         } else {
             rows_to_js_array(inner.result(), &self.schema)
@@ -974,12 +1174,13 @@ impl JsObservableQuery {
 
     /// Returns the current result as a binary buffer for zero-copy access.
     #[wasm_bindgen(js_name = getResultBinary)]
-    pub fn get_result_binary(&self) -> BinaryResult {
+    pub fn get_result_binary(&self) -> Result<BinaryResult, JsValue> {
         let inner = self.inner.borrow();
         let rows = inner.result();
         let mut encoder = BinaryEncoder::new(self.binary_layout.clone(), rows.len());
         encoder.encode_rows(rows);
-        BinaryResult::new(encoder.finish())
+        let buffer = encoder.try_finish().map_err(CynosError::from)?;
+        Ok(BinaryResult::new(buffer))
     }
 
     /// Returns the schema layout for decoding binary results.
@@ -1007,6 +1208,78 @@ impl JsObservableQuery {
     }
 }
 
+/// JavaScript-friendly single-row observable, created by `Database.observeRow`.
+#[wasm_bindgen]
+pub struct JsRowObservable {
+    inner: Rc<RefCell<RowObservable>>,
+    schema: Table,
+}
+
+impl JsRowObservable {
+    pub(crate) fn new(inner: Rc<RefCell<RowObservable>>, schema: Table) -> Self {
+        Self { inner, schema }
+    }
+}
+
+#[wasm_bindgen]
+impl JsRowObservable {
+    /// Subscribes to changes of the watched row.
+    ///
+    /// The callback receives the row as a JavaScript object after it is
+    /// inserted or updated, and `null` after it is deleted. It is not called
+    /// immediately - use getRow for the current value.
+    /// Returns an unsubscribe function.
+    pub fn subscribe(&mut self, callback: js_sys::Function) -> js_sys::Function {
+        let schema = self.schema.clone();
+
+        let sub_id = self.inner.borrow_mut().subscribe(move |row| {
+            let current = match row {
+                Some(row) => row_to_js(row, &schema),
+                None => Ok(JsValue::NULL),
+            };
+            match current {
+                Ok(current) => {
+                    callback.call1(&JsValue::NULL, &current).ok();
+                }
+                Err(error) => report_conversion_error(&error),
+            }
+        });
+
+        let inner_unsub = self.inner.clone();
+        let called = Rc::new(RefCell::new(false));
+        let called_c = called.clone();
+        let unsubscribe = Closure::wrap(Box::new(move || {
+            let mut c = called_c.borrow_mut();
+            if !*c {
+                *c = true;
+                inner_unsub.borrow_mut().unsubscribe(sub_id);
+            }
+        }) as Box<dyn FnMut()>);
+        unsubscribe.into_js_value().unchecked_into()
+    }
+
+    /// Returns the current row as a JavaScript object, or null if it does not exist.
+    #[wasm_bindgen(js_name = getRow)]
+    pub fn get_row(&self) -> Result<JsValue, JsValue> {
+        match self.inner.borrow().row() {
+            Some(row) => row_to_js(row, &self.schema),
+            None => Ok(JsValue::NULL),
+        }
+    }
+
+    /// Returns whether the watched row currently exists.
+    #[wasm_bindgen(getter)]
+    pub fn exists(&self) -> bool {
+        self.inner.borrow().row().is_some()
+    }
+
+    /// Returns the number of active subscriptions.
+    #[wasm_bindgen(js_name = subscriptionCount)]
+    pub fn subscription_count(&self) -> usize {
+        self.inner.borrow().subscription_count()
+    }
+}
+
 /// JavaScript-friendly IVM observable query wrapper.
 /// Uses DBSP-based incremental view maintenance for O(delta) updates.
 #[wasm_bindgen]
@@ -1017,8 +1290,6 @@ pub struct JsIvmObservableQuery {
     projected_columns: Option<Vec<String>>,
     /// Pre-computed binary layout for getResultBinary().
     binary_layout: SchemaLayout,
-    /// Optional aggregate column names.
-    aggregate_columns: Option<Vec<String>>,
 }
 
 impl JsIvmObservableQuery {
@@ -1032,7 +1303,6 @@ impl JsIvmObservableQuery {
             schema,
             projected_columns: None,
             binary_layout,
-            aggregate_columns: None,
         }
     }
 
@@ -1047,7 +1317,6 @@ impl JsIvmObservableQuery {
             schema,
             projected_columns: Some(projected_columns),
             binary_layout,
-            aggregate_columns: None,
         }
     }
 }
@@ -1065,33 +1334,36 @@ impl JsIvmObservableQuery {
     pub fn subscribe(&mut self, callback: js_sys::Function) -> js_sys::Function {
         let schema = self.schema.clone();
         let projected_columns = self.projected_columns.clone();
-        let aggregate_columns = self.aggregate_columns.clone();
 
         let sub_id = self.inner.borrow_mut().subscribe(move |change_set| {
-            let delta_obj = js_sys::Object::new();
-
             // Serialize only added rows
-            let added = if let Some(ref cols) = aggregate_columns {
-                ivm_rows_to_js_array(&change_set.added, cols)
-            } else if let Some(ref cols) = projected_columns {
+            let added = if let Some(ref cols) = projected_columns {
                 ivm_rows_to_js_array(&change_set.added, cols)
             } else {
                 ivm_full_rows_to_js_array(&change_set.added, &schema)
             };
 
             // Serialize only removed rows
-            let removed = if let Some(ref cols) = aggregate_columns {
-                ivm_rows_to_js_array(&change_set.removed, cols)
-            } else if let Some(ref cols) = projected_columns {
+            let removed = if let Some(ref cols) = projected_columns {
                 ivm_rows_to_js_array(&change_set.removed, cols)
             } else {
                 ivm_full_rows_to_js_array(&change_set.removed, &schema)
             };
 
-            js_sys::Reflect::set(&delta_obj, &JsValue::from_str("added"), &added).ok();
-            js_sys::Reflect::set(&delta_obj, &JsValue::from_str("removed"), &removed).ok();
-
-            callback.call1(&JsValue::NULL, &delta_obj).ok();
+            let delivered = added.and_then(|added| {
+                let removed = removed?;
+                let delta_obj: JsValue = js_sys::Object::new().into();
+                set_property(&delta_obj, "added", &added)?;
+                set_property(&delta_obj, "removed", &removed)?;
+                Ok(delta_obj)
+            });
+
+            match delivered {
+                Ok(delta_obj) => {
+                    callback.call1(&JsValue::NULL, &delta_obj).ok();
+                }
+                Err(error) => report_conversion_error(&error),
+            }
         });
 
         let inner_unsub = self.inner.clone();
@@ -1109,12 +1381,10 @@ impl JsIvmObservableQuery {
 
     /// Returns the current result as a JavaScript array.
     #[wasm_bindgen(js_name = getResult)]
-    pub fn get_result(&self) -> JsValue {
+    pub fn get_result(&self) -> Result<JsValue, JsValue> {
         let inner = self.inner.borrow();
         let rows = inner.result();
-        if let Some(ref cols) = self.aggregate_columns {
-            ivm_rows_to_js_array(&rows, cols)
-        } else if let Some(ref cols) = self.projected_columns {
+        if let Some(ref cols) = self.projected_columns {
             ivm_rows_to_js_array(&rows, cols)
         } else {
             ivm_full_rows_to_js_array(&rows, &self.schema)
@@ -1123,13 +1393,14 @@ impl JsIvmObservableQuery {
 
     /// Returns the current result as a binary buffer for zero-copy access.
     #[wasm_bindgen(js_name = getResultBinary)]
-    pub fn get_result_binary(&self) -> BinaryResult {
+    pub fn get_result_binary(&self) -> Result<BinaryResult, JsValue> {
         let inner = self.inner.borrow();
         let rows = inner.result();
         let rc_rows: Vec<Rc<Row>> = rows.into_iter().map(Rc::new).collect();
         let mut encoder = BinaryEncoder::new(self.binary_layout.clone(), rc_rows.len());
         encoder.encode_rows(&rc_rows);
-        BinaryResult::new(encoder.finish())
+        let buffer = encoder.try_finish().map_err(CynosError::from)?;
+        Ok(BinaryResult::new(buffer))
     }
 
     /// Returns the schema layout for decoding binary results.
@@ -1158,35 +1429,33 @@ impl JsIvmObservableQuery {
 }
 
 /// Converts IVM rows (owned Row, not Rc<Row>) to a JavaScript array using projected columns.
-fn ivm_rows_to_js_array(rows: &[Row], column_names: &[String]) -> JsValue {
+fn ivm_rows_to_js_array(rows: &[Row], column_names: &[String]) -> Result<JsValue, JsValue> {
     let arr = js_sys::Array::new_with_length(rows.len() as u32);
     for (i, row) in rows.iter().enumerate() {
-        let obj = js_sys::Object::new();
+        let obj: JsValue = js_sys::Object::new().into();
         for (col_idx, col_name) in column_names.iter().enumerate() {
             if let Some(value) = row.get(col_idx) {
-                let js_val = value_to_js(value);
-                js_sys::Reflect::set(&obj, &JsValue::from_str(col_name), &js_val).ok();
+                set_property(&obj, col_name, &value_to_js(value))?;
             }
         }
-        arr.set(i as u32, obj.into());
+        arr.set(i as u32, obj);
     }
-    arr.into()
+    Ok(arr.into())
 }
 
 /// Converts IVM rows (owned Row, not Rc<Row>) to a JavaScript array using full schema.
-fn ivm_full_rows_to_js_array(rows: &[Row], schema: &Table) -> JsValue {
+fn ivm_full_rows_to_js_array(rows: &[Row], schema: &Table) -> Result<JsValue, JsValue> {
     let arr = js_sys::Array::new_with_length(rows.len() as u32);
     for (i, row) in rows.iter().enumerate() {
-        let obj = js_sys::Object::new();
+        let obj: JsValue = js_sys::Object::new().into();
         for col in schema.columns() {
             if let Some(value) = row.get(col.index()) {
-                let js_val = value_to_js(value);
-                js_sys::Reflect::set(&obj, &JsValue::from_str(col.name()), &js_val).ok();
+                set_property(&obj, col.name(), &value_to_js(value))?;
             }
         }
-        arr.set(i as u32, obj.into());
+        arr.set(i as u32, obj);
     }
-    arr.into()
+    Ok(arr.into())
 }
 
 /// JavaScript-friendly GraphQL subscription wrapper.
@@ -1308,7 +1577,12 @@ impl JsChangesStream {
         } else {
             rows_to_js_array(inner.borrow().result(), &schema)
         };
-        callback.call1(&JsValue::NULL, &initial_data).ok();
+        match initial_data {
+            Ok(initial_data) => {
+                callback.call1(&JsValue::NULL, &initial_data).ok();
+            }
+            Err(error) => report_conversion_error(&error),
+        }
 
         // Subscribe to subsequent changes
         let schema_clone = schema.clone();
@@ -1319,7 +1593,12 @@ impl JsChangesStream {
             } else {
                 rows_to_js_array(rows, &schema_clone)
             };
-            callback.call1(&JsValue::NULL, &current_data).ok();
+            match current_data {
+                Ok(current_data) => {
+                    callback.call1(&JsValue::NULL, &current_data).ok();
+                }
+                Err(error) => report_conversion_error(&error),
+            }
         });
 
         // Create unsubscribe function
@@ -1337,7 +1616,7 @@ impl JsChangesStream {
 
     /// Returns the current result.
     #[wasm_bindgen(js_name = getResult)]
-    pub fn get_result(&self) -> JsValue {
+    pub fn get_result(&self) -> Result<JsValue, JsValue> {
         let inner = self.inner.borrow();
         if let Some(ref cols) = self.projected_columns {
             projected_rows_to_js_array(inner.result(), cols)
@@ -1348,12 +1627,13 @@ impl JsChangesStream {
 
     /// Returns the current result as a binary buffer for zero-copy access.
     #[wasm_bindgen(js_name = getResultBinary)]
-    pub fn get_result_binary(&self) -> BinaryResult {
+    pub fn get_result_binary(&self) -> Result<BinaryResult, JsValue> {
         let inner = self.inner.borrow();
         let rows = inner.result();
         let mut encoder = BinaryEncoder::new(self.binary_layout.clone(), rows.len());
         encoder.encode_rows(rows);
-        BinaryResult::new(encoder.finish())
+        let buffer = encoder.try_finish().map_err(CynosError::from)?;
+        Ok(BinaryResult::new(buffer))
     }
 
     /// Returns the schema layout for decoding binary results.
@@ -1364,29 +1644,40 @@ impl JsChangesStream {
 }
 
 /// Converts rows to a JavaScript array.
-fn rows_to_js_array(rows: &[Rc<Row>], schema: &Table) -> JsValue {
+fn rows_to_js_array(rows: &[Rc<Row>], schema: &Table) -> Result<JsValue, JsValue> {
     let arr = js_sys::Array::new_with_length(rows.len() as u32);
     for (i, row) in rows.iter().enumerate() {
-        arr.set(i as u32, row_to_js(row, schema));
+        arr.set(i as u32, row_to_js(row, schema)?);
     }
-    arr.into()
+    Ok(arr.into())
 }
 
 /// Converts projected rows to a JavaScript array.
 /// Only includes the specified columns in the output.
-fn projected_rows_to_js_array(rows: &[Rc<Row>], column_names: &[String]) -> JsValue {
+fn projected_rows_to_js_array(
+    rows: &[Rc<Row>],
+    column_names: &[String],
+) -> Result<JsValue, JsValue> {
     let arr = js_sys::Array::new_with_length(rows.len() as u32);
     for (i, row) in rows.iter().enumerate() {
-        let obj = js_sys::Object::new();
+        let obj: JsValue = js_sys::Object::new().into();
         for (col_idx, col_name) in column_names.iter().enumerate() {
             if let Some(value) = row.get(col_idx) {
-                let js_val = value_to_js(value);
-                js_sys::Reflect::set(&obj, &JsValue::from_str(col_name), &js_val).ok();
+                set_property(&obj, col_name, &value_to_js(value))?;
             }
         }
-        arr.set(i as u32, obj.into());
+        arr.set(i as u32, obj);
     }
-    arr.into()
+    Ok(arr.into())
+}
+
+/// Reports a result that could not be converted for a subscriber.
+///
+/// Subscription callbacks have no caller to return an error to, so the
+/// emission is skipped and the error is logged instead of delivering a
+/// partially converted result.
+fn report_conversion_error(error: &JsValue) {
+    web_sys::console::error_1(error);
 }
 
 #[cfg(test)]
@@ -1442,7 +1733,7 @@ mod tests {
             Rc::new(make_row(2, "Bob", 30)),
         ];
 
-        let js = rows_to_js_array(&rows, &schema);
+        let js = rows_to_js_array(&rows, &schema).unwrap();
         let arr = js_sys::Array::from(&js);
         assert_eq!(arr.length(), 2);
     }
@@ -1515,6 +1806,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_requery_error_callback_fires_when_dependency_table_is_dropped() {
+        use crate::live_runtime::{LiveDependencySet, SnapshotSubscription};
+        use crate::query_engine::compile_cached_plan;
+        use cynos_query::planner::LogicalPlan;
+
+        let cache = Rc::new(RefCell::new(TableCache::new()));
+        cache.borrow_mut().create_table(test_schema()).unwrap();
+        cache
+            .borrow_mut()
+            .get_table_mut("users")
+            .unwrap()
+            .insert(make_row(1, "Alice", 25))
+            .unwrap();
+        let compiled = compile_cached_plan(&cache.borrow(), "users", LogicalPlan::scan("users"));
+        let initial = execute_compiled_physical_plan_with_summary(&cache.borrow(), &compiled)
+            .unwrap()
+            .rows;
+        let observable = Rc::new(RefCell::new(ReQueryObservable::new(
+            compiled,
+            cache.clone(),
+            initial,
+        )));
+
+        let updates = Rc::new(RefCell::new(0));
+        let errors = Rc::new(RefCell::new(Vec::new()));
+        let (updates_c, errors_c) = (updates.clone(), errors.clone());
+        observable.borrow_mut().subscribe_with_error(
+            move |_| *updates_c.borrow_mut() += 1,
+            move |error| errors_c.borrow_mut().push(alloc::format!("{:?}", error)),
+        );
+
+        let mut registry = LiveRegistry::new();
+        registry.register_snapshot(
+            SnapshotSubscription::Rows(observable.clone()),
+            &LiveDependencySet::snapshot(alloc::vec![1]),
+        );
+
+        cache.borrow_mut().drop_table("users").unwrap();
+        registry.on_table_dropped(1);
+
+        assert_eq!(*updates.borrow(), 0);
+        assert_eq!(errors.borrow().len(), 1);
+        assert!(errors.borrow()[0].contains("users"));
+        // The stale result is kept, and the dropped table no longer routes
+        // changes to the query.
+        assert_eq!(observable.borrow().len(), 1);
+        assert_eq!(registry.query_count(), 0);
+    }
+
     #[test]
     fn test_result_comparison_falls_back_to_exact_rows_when_summary_matches() {
         let old_rows: Vec<Rc<Row>> = alloc::vec![Rc::new(Row::new_with_version(
@@ -0,0 +1,81 @@
+//! Whole-database snapshots.
+//!
+//! `Database::snapshot` captures every table's schema and rows together with
+//! the table id assignments, computed defaults and the row id counter.
+//! `Database::restore` rebuilds the tables, and with them every index, from
+//! that state.
+//!
+//! Stored rows are copy-on-write `Rc`s, so a snapshot shares them with the
+//! live tables instead of copying; taking one costs a pointer per row.
+
+use crate::table::ComputedDefaults;
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::vec::Vec;
+use cynos_core::schema::Table;
+use cynos_core::{Row, RowId};
+use cynos_reactive::TableId;
+use cynos_storage::{RowStore, TableCache};
+use wasm_bindgen::prelude::*;
+
+/// An opaque copy of a database's state, created by `db.snapshot()` and
+/// applied with `db.restore(snapshot)`.
+#[wasm_bindgen]
+pub struct DatabaseSnapshot {
+    pub(crate) tables: Vec<TableSnapshot>,
+    pub(crate) table_ids: hashbrown::HashMap<String, TableId>,
+    pub(crate) next_table_id: TableId,
+    pub(crate) next_row_id: RowId,
+    pub(crate) computed_defaults: ComputedDefaults,
+}
+
+/// One table's schema and rows.
+pub(crate) struct TableSnapshot {
+    pub(crate) schema: Table,
+    pub(crate) rows: Vec<Rc<Row>>,
+}
+
+impl TableSnapshot {
+    pub(crate) fn capture(store: &RowStore) -> Self {
+        Self {
+            schema: store.schema().clone(),
+            rows: store.scan().collect(),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl DatabaseSnapshot {
+    /// Returns the names of the tables in the snapshot.
+    #[wasm_bindgen(js_name = tableNames)]
+    pub fn table_names(&self) -> Vec<String> {
+        self.tables
+            .iter()
+            .map(|table| table.schema.name().into())
+            .collect()
+    }
+
+    /// Returns the total number of rows across all tables in the snapshot.
+    #[wasm_bindgen(js_name = rowCount)]
+    pub fn row_count(&self) -> usize {
+        self.tables.iter().map(|table| table.rows.len()).sum()
+    }
+}
+
+impl DatabaseSnapshot {
+    /// Builds a fresh table cache holding the snapshot's tables. Rows keep
+    /// their ids, and indexes are rebuilt as the rows are inserted.
+    pub(crate) fn build_cache(&self) -> cynos_core::Result<TableCache> {
+        let mut cache = TableCache::new();
+        for table in &self.tables {
+            cache.create_table(table.schema.clone())?;
+            let store = cache
+                .get_table_mut(table.schema.name())
+                .ok_or_else(|| cynos_core::Error::table_not_found(table.schema.name()))?;
+            for row in &table.rows {
+                store.insert(Row::clone(row))?;
+            }
+        }
+        Ok(cache)
+    }
+}
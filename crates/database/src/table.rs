@@ -2,12 +2,16 @@
 //!
 //! This module provides the JavaScript API for creating and managing tables.
 
-use crate::expr::Column;
+use crate::convert::{js_to_value, metadata_to_js};
+use crate::error::CynosError;
+use crate::expr::{Column, Expr};
 use crate::JsDataType;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use cynos_core::schema::{Table, TableBuilder};
 use cynos_core::DataType;
+use cynos_query::ast::Expr as AstExpr;
+use cynos_query::check::ExprCheck;
 use wasm_bindgen::prelude::*;
 
 /// Column options for table creation.
@@ -18,6 +22,11 @@ pub struct ColumnOptions {
     pub nullable: bool,
     pub unique: bool,
     pub auto_increment: bool,
+    metadata: Vec<(String, String)>,
+    allowed_values: Option<Vec<String>>,
+    default_value: Option<JsValue>,
+    default_expr: Option<Expr>,
+    generated_expr: Option<Expr>,
 }
 
 /// Foreign-key options for GraphQL relation naming.
@@ -58,6 +67,55 @@ impl ColumnOptions {
         self.auto_increment = value;
         self
     }
+
+    /// Attaches a metadata entry (e.g. a label, format, or unit) to the column.
+    #[wasm_bindgen(js_name = setMetadata)]
+    pub fn set_metadata(mut self, key: &str, value: &str) -> Self {
+        self.metadata.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Restricts a String column to the given values; other values are
+    /// rejected with a `domain` constraint violation.
+    #[wasm_bindgen(js_name = setAllowedValues)]
+    pub fn set_allowed_values(mut self, values: Vec<String>) -> Self {
+        self.allowed_values = Some(values);
+        self
+    }
+
+    /// Stores `value` in the column when an insert leaves it out, e.g.
+    /// `'active'` for a status column. An explicit `null` is kept as given.
+    ///
+    /// Updates only apply it when asked to with `UpdateBuilder.setDefault`.
+    #[wasm_bindgen(js_name = setDefault)]
+    pub fn set_default(mut self, value: JsValue) -> Self {
+        self.default_value = Some(value);
+        self
+    }
+
+    /// Computes the column's value from other columns of the inserted row
+    /// when an insert leaves it out, e.g. `col('first').concat(' ').concat(col('last'))`.
+    ///
+    /// The expression may not reference columns that have a default
+    /// expression of their own.
+    #[wasm_bindgen(js_name = setDefaultExpr)]
+    pub fn set_default_expr(mut self, expr: &Expr) -> Self {
+        self.default_expr = Some(expr.clone());
+        self
+    }
+
+    /// Makes the column a stored generated column: its value is always
+    /// computed from other columns of the row, on insert and again whenever
+    /// an update changes a column it reads. Inserts and updates may not set
+    /// it directly.
+    ///
+    /// Like a default expression, it may not reference columns that are
+    /// computed themselves.
+    #[wasm_bindgen(js_name = setGeneratedExpr)]
+    pub fn set_generated_expr(mut self, expr: &Expr) -> Self {
+        self.generated_expr = Some(expr.clone());
+        self
+    }
 }
 
 #[wasm_bindgen]
@@ -89,6 +147,8 @@ pub struct JsTableBuilder {
     indices: Vec<IndexDef>,
     foreign_keys: Vec<ForeignKeyDef>,
     auto_increment: bool,
+    soft_delete: Option<String>,
+    checks: Vec<(String, Expr)>,
 }
 
 #[derive(Clone, Debug)]
@@ -98,13 +158,37 @@ struct ColumnDef {
     data_type: DataType,
     nullable: bool,
     unique: bool,
+    metadata: Vec<(String, String)>,
+    allowed_values: Option<Vec<String>>,
+    default_value: Option<JsValue>,
+    default_expr: Option<Expr>,
+    generated_expr: Option<Expr>,
 }
 
+/// A column value computed from the other columns of the row, resolved
+/// against the table schema.
+///
+/// A default only fills the column when an insert leaves it out. A
+/// generated column is always computed and is recomputed by updates that
+/// change one of its `dependencies`.
+#[derive(Clone, Debug)]
+pub(crate) struct ComputedDefault {
+    pub(crate) column_index: usize,
+    pub(crate) expr: AstExpr,
+    pub(crate) generated: bool,
+    /// Indices of the columns `expr` reads.
+    pub(crate) dependencies: Vec<usize>,
+}
+
+/// Computed column defaults keyed by table name.
+pub(crate) type ComputedDefaults = hashbrown::HashMap<String, Vec<ComputedDefault>>;
+
 #[derive(Clone, Debug)]
 struct IndexDef {
     name: String,
     columns: Vec<String>,
     unique: bool,
+    hash: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -129,6 +213,8 @@ impl JsTableBuilder {
             indices: Vec::new(),
             foreign_keys: Vec::new(),
             auto_increment: false,
+            soft_delete: None,
+            checks: Vec::new(),
         }
     }
 
@@ -152,6 +238,11 @@ impl JsTableBuilder {
             data_type: data_type.into(),
             nullable: opts.nullable,
             unique: opts.unique || opts.primary_key,
+            metadata: opts.metadata,
+            allowed_values: opts.allowed_values,
+            default_value: opts.default_value,
+            default_expr: opts.default_expr,
+            generated_expr: opts.generated_expr,
         });
 
         if opts.primary_key {
@@ -188,6 +279,7 @@ impl JsTableBuilder {
             name: name.to_string(),
             columns: cols,
             unique: false,
+            hash: false,
         });
         self
     }
@@ -207,6 +299,28 @@ impl JsTableBuilder {
             name: name.to_string(),
             columns: cols,
             unique: true,
+            hash: false,
+        });
+        self
+    }
+
+    /// Adds a hash index to the table. Hash indexes serve equality and `IN`
+    /// lookups only; range filters and ordering on the column scan instead.
+    #[wasm_bindgen(js_name = hashIndex)]
+    pub fn hash_index(mut self, name: &str, columns: &JsValue, unique: Option<bool>) -> Self {
+        let cols = if let Some(arr) = columns.dyn_ref::<js_sys::Array>() {
+            arr.iter().filter_map(|v| v.as_string()).collect()
+        } else if let Some(s) = columns.as_string() {
+            alloc::vec![s]
+        } else {
+            return self;
+        };
+
+        self.indices.push(IndexDef {
+            name: name.to_string(),
+            columns: cols,
+            unique: unique.unwrap_or(false),
+            hash: true,
         });
         self
     }
@@ -221,6 +335,7 @@ impl JsTableBuilder {
             name,
             columns: alloc::vec![column.to_string()],
             unique: false,
+            hash: false,
         });
         self
     }
@@ -247,20 +362,70 @@ impl JsTableBuilder {
         self
     }
 
+    /// Marks a nullable DateTime column as the soft-delete flag. Deletes then
+    /// stamp it with the current time instead of removing rows, and SELECT
+    /// queries skip stamped rows unless they call `withDeleted()`.
+    #[wasm_bindgen(js_name = softDelete)]
+    pub fn soft_delete(mut self, column: &str) -> Self {
+        self.soft_delete = Some(column.to_string());
+        self
+    }
+
+    /// Adds a CHECK constraint: inserts and updates whose row makes
+    /// `predicate` false are rejected with a `check` constraint violation,
+    /// e.g. `check('age_non_negative', col('age').gte(0))`.
+    ///
+    /// A predicate that references an unknown column fails `registerTable`.
+    pub fn check(mut self, name: &str, predicate: &Expr) -> Self {
+        self.checks.push((name.to_string(), predicate.clone()));
+        self
+    }
+
     /// Builds the table schema (internal use).
     pub(crate) fn build_internal(&self) -> Result<Table, JsValue> {
-        let mut builder = TableBuilder::new(&self.name)
-            .map_err(|e| JsValue::from_str(&alloc::format!("{:?}", e)))?;
+        let mut builder = TableBuilder::new(&self.name).map_err(CynosError::from)?;
 
         // Add columns
         for col in &self.columns {
-            builder = builder
-                .add_column(&col.name, col.data_type)
-                .map_err(|e| JsValue::from_str(&alloc::format!("{:?}", e)))?;
+            builder = match &col.allowed_values {
+                Some(allowed) if col.data_type == DataType::String => {
+                    let allowed: Vec<&str> = allowed.iter().map(|s| s.as_str()).collect();
+                    builder.add_enum_column(&col.name, &allowed)
+                }
+                Some(_) => {
+                    return Err(CynosError::InvalidSchema(alloc::format!(
+                        "Allowed values require a String column: {}",
+                        col.name
+                    ))
+                    .into())
+                }
+                None => builder.add_column(&col.name, col.data_type),
+            }
+            .map_err(CynosError::from)?;
 
             if col.nullable {
                 builder = builder.add_nullable(&[col.name.as_str()]);
             }
+
+            for (key, value) in &col.metadata {
+                builder = builder
+                    .add_column_metadata(&col.name, key.as_str(), value.as_str())
+                    .map_err(CynosError::from)?;
+            }
+
+            if let Some(default) = &col.default_value {
+                if col.default_expr.is_some() || col.generated_expr.is_some() {
+                    return Err(CynosError::InvalidSchema(alloc::format!(
+                        "Column {} cannot have both a default value and a computed expression",
+                        col.name
+                    ))
+                    .into());
+                }
+                let default = js_to_value(default, col.data_type)?;
+                builder = builder
+                    .add_column_default(&col.name, default)
+                    .map_err(CynosError::from)?;
+            }
         }
 
         // Add primary key
@@ -268,15 +433,18 @@ impl JsTableBuilder {
             let pk_refs: Vec<&str> = pk_cols.iter().map(|s| s.as_str()).collect();
             builder = builder
                 .add_primary_key(&pk_refs, self.auto_increment)
-                .map_err(|e| JsValue::from_str(&alloc::format!("{:?}", e)))?;
+                .map_err(CynosError::from)?;
         }
 
         // Add indices
         for idx in &self.indices {
             let col_refs: Vec<&str> = idx.columns.iter().map(|s| s.as_str()).collect();
-            builder = builder
-                .add_index(&idx.name, &col_refs, idx.unique)
-                .map_err(|e| JsValue::from_str(&alloc::format!("{:?}", e)))?;
+            builder = if idx.hash {
+                builder.add_hash_index(&idx.name, &col_refs, idx.unique)
+            } else {
+                builder.add_index(&idx.name, &col_refs, idx.unique)
+            }
+            .map_err(CynosError::from)?;
         }
 
         // Add foreign keys
@@ -290,12 +458,126 @@ impl JsTableBuilder {
                     fk.field_name.clone(),
                     fk.reverse_field_name.clone(),
                 )
-                .map_err(|e| JsValue::from_str(&alloc::format!("{:?}", e)))?;
+                .map_err(CynosError::from)?;
         }
 
-        builder
-            .build()
-            .map_err(|e| JsValue::from_str(&alloc::format!("{:?}", e)))
+        let column_info = |key: &str| {
+            let name = key
+                .strip_prefix(self.name.as_str())
+                .and_then(|rest| rest.strip_prefix('.'))
+                .unwrap_or(key);
+            self.columns
+                .iter()
+                .position(|col| col.name == name)
+                .map(|index| (self.name.clone(), index, self.columns[index].data_type))
+        };
+        for (name, predicate) in &self.checks {
+            let check = ExprCheck::new(predicate.to_ast_with_table(&column_info));
+            builder = builder.add_check(name, check).map_err(CynosError::from)?;
+        }
+
+        if let Some(column) = &self.soft_delete {
+            builder = builder.soft_delete(column).map_err(CynosError::from)?;
+        }
+
+        builder.build().map_err(|e| CynosError::from(e).into())
+    }
+
+    /// Resolves the column default and generated expressions against the
+    /// built `schema`.
+    ///
+    /// Chains are rejected: an expression may only read columns without a
+    /// default or generated expression, so every expression sees
+    /// caller-supplied values and the evaluation order does not matter.
+    pub(crate) fn computed_defaults(
+        &self,
+        schema: &Table,
+    ) -> Result<Vec<ComputedDefault>, JsValue> {
+        let column_info = |key: &str| {
+            let name = key
+                .strip_prefix(schema.name())
+                .and_then(|rest| rest.strip_prefix('.'))
+                .unwrap_or(key);
+            schema
+                .get_column(name)
+                .map(|col| (schema.name().to_string(), col.index(), col.data_type()))
+        };
+        let computed: Vec<usize> = self
+            .columns
+            .iter()
+            .filter(|col| col.default_expr.is_some() || col.generated_expr.is_some())
+            .filter_map(|col| schema.get_column(&col.name))
+            .map(|col| col.index())
+            .collect();
+        let plain_column_info =
+            |key: &str| column_info(key).filter(|(_, index, _)| !computed.contains(index));
+
+        let mut defaults = Vec::new();
+        for col in &self.columns {
+            let (expr, kind, generated) = match (&col.default_expr, &col.generated_expr) {
+                (None, None) => continue,
+                (Some(expr), None) => (expr, "Default", false),
+                (None, Some(expr)) => (expr, "Generated", true),
+                (Some(_), Some(_)) => {
+                    return Err(CynosError::InvalidSchema(alloc::format!(
+                        "Column {} cannot have both a default and a generated expression",
+                        col.name
+                    ))
+                    .into())
+                }
+            };
+            if let Some(missing) = expr.find_unresolved_column(&column_info) {
+                return Err(CynosError::InvalidSchema(alloc::format!(
+                    "{} expression for {} references unknown column: {}",
+                    kind,
+                    col.name,
+                    missing
+                ))
+                .into());
+            }
+            if let Some(chained) = expr.find_unresolved_column(&plain_column_info) {
+                return Err(CynosError::InvalidSchema(alloc::format!(
+                    "{} expression for {} references {}, which is computed itself",
+                    kind,
+                    col.name,
+                    chained
+                ))
+                .into());
+            }
+            let column = schema
+                .get_column(&col.name)
+                .ok_or_else(|| CynosError::ColumnNotFound(col.name.clone()))?;
+            let mut dependencies: Vec<usize> = expr
+                .columns()
+                .into_iter()
+                .filter_map(|dependency| column_info(&dependency.lookup_key()))
+                .map(|(_, index, _)| index)
+                .collect();
+            dependencies.sort_unstable();
+            dependencies.dedup();
+            let expr = expr.to_ast_with_table(&column_info);
+            let produced = expr
+                .infer_type(&|column| schema.get_column(&column.column).map(|col| col.data_type()));
+            if let Some(produced) = produced {
+                if !produced.can_coerce_to(column.data_type()) {
+                    return Err(CynosError::InvalidSchema(alloc::format!(
+                        "{} expression for {} produces {:?}, which cannot be stored as {:?}",
+                        kind,
+                        col.name,
+                        produced,
+                        column.data_type()
+                    ))
+                    .into());
+                }
+            }
+            defaults.push(ComputedDefault {
+                column_index: column.index(),
+                expr,
+                generated,
+                dependencies,
+            });
+        }
+        Ok(defaults)
     }
 
     /// Returns the table name.
@@ -367,6 +649,16 @@ impl JsTable {
             .unwrap_or(false)
     }
 
+    /// Returns the metadata of a column as a plain object, or `undefined` if
+    /// the column does not exist.
+    #[wasm_bindgen(js_name = getColumnMetadata)]
+    pub fn get_column_metadata(&self, name: &str) -> Result<JsValue, JsValue> {
+        match self.schema.get_column(name) {
+            Some(col) => metadata_to_js(col.metadata()),
+            None => Ok(JsValue::UNDEFINED),
+        }
+    }
+
     /// Returns the primary key column names.
     #[wasm_bindgen(js_name = primaryKeyColumns)]
     pub fn primary_key_columns(&self) -> js_sys::Array {
@@ -418,6 +710,23 @@ mod tests {
         assert!(table.indices().iter().any(|i| i.name() == "idx_email"));
     }
 
+    #[wasm_bindgen_test]
+    fn test_table_builder_with_hash_index() {
+        let builder = JsTableBuilder::new("sessions")
+            .column(
+                "id",
+                JsDataType::Int64,
+                Some(ColumnOptions::new().set_primary_key(true)),
+            )
+            .column("token", JsDataType::String, None)
+            .hash_index("idx_token", &JsValue::from_str("token"), Some(true));
+
+        let table = builder.build_internal().unwrap();
+        let index = table.get_index("idx_token").unwrap();
+        assert_eq!(index.get_index_type(), cynos_core::schema::IndexType::Hash);
+        assert!(index.is_unique());
+    }
+
     #[wasm_bindgen_test]
     fn test_table_builder_nullable() {
         let builder = JsTableBuilder::new("users")
@@ -471,4 +780,35 @@ mod tests {
         let names = table.column_names();
         assert_eq!(names.length(), 3);
     }
+
+    #[wasm_bindgen_test]
+    fn test_js_table_column_metadata() {
+        let builder = JsTableBuilder::new("products")
+            .column(
+                "id",
+                JsDataType::Int64,
+                Some(ColumnOptions::new().set_primary_key(true)),
+            )
+            .column(
+                "price",
+                JsDataType::Float64,
+                Some(
+                    ColumnOptions::new()
+                        .set_metadata("label", "Price")
+                        .set_metadata("unit", "USD"),
+                ),
+            );
+
+        let schema = builder.build_internal().unwrap();
+        assert_eq!(
+            schema.get_column("price").unwrap().get_metadata("unit"),
+            Some("USD")
+        );
+
+        let table = JsTable::new(schema);
+        let metadata = table.get_column_metadata("price").unwrap();
+        let label = js_sys::Reflect::get(&metadata, &JsValue::from_str("label")).unwrap();
+        assert_eq!(label.as_string().as_deref(), Some("Price"));
+        assert!(table.get_column_metadata("missing").unwrap().is_undefined());
+    }
 }
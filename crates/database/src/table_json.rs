@@ -0,0 +1,431 @@
+//! JSON export and import of whole tables.
+//!
+//! `Database::exportTableJson` renders every row of a table as one JSON text:
+//! an array of objects keyed by column name. The text is written into a single
+//! Rust `String`, so no JS object is created per row. `importTableJson` reads
+//! the same format back.
+//!
+//! Values are encoded by column type:
+//!
+//! - `Int64`: a decimal string such as `"9007199254740993"`, because JS
+//!   numbers lose precision beyond 2^53. Import also accepts integral numbers.
+//! - `Int32`, `Float64`: JSON numbers. Non-finite floats are written as `null`.
+//! - `DateTime`: milliseconds since the Unix epoch, as a number.
+//! - `Bytes`: an array of byte values.
+//! - `Jsonb`: the document itself, in canonical form (sorted keys).
+//! - `Boolean`, `String`: the corresponding JSON value.
+//!
+//! SQL NULL is written as `null`; on import, `null` reads as NULL, which fails
+//! for non-nullable columns. A missing key takes the column default, or NULL
+//! when there is none. Imported rows get the same computed columns as
+//! `InsertBuilder` rows and go through the same store checks.
+
+use crate::convert::fill_computed_columns;
+use crate::error::{ConstraintKind, CynosError};
+use crate::table::ComputedDefault;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use cynos_core::schema::{Column, Table};
+use cynos_core::{reserve_row_ids, DataType, Row, Value};
+use cynos_jsonb::{
+    parse_json, to_json_string, write_json, write_json_number, write_json_string, JsonbValue,
+};
+
+/// Renders `rows` of a table with the given schema as a JSON array.
+pub(crate) fn export_rows<'a>(
+    schema: &Table,
+    rows: impl IntoIterator<Item = &'a Row>,
+) -> Result<String, CynosError> {
+    let columns = schema.columns();
+    let mut out = String::from("[");
+
+    for (i, row) in rows.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push('{');
+        for (index, column) in columns.iter().enumerate() {
+            if index > 0 {
+                out.push(',');
+            }
+            write_json_string(column.name(), &mut out);
+            out.push(':');
+            write_value(row.get(index).unwrap_or(&Value::Null), column, &mut out)?;
+        }
+        out.push('}');
+    }
+
+    out.push(']');
+    Ok(out)
+}
+
+fn write_value(value: &Value, column: &Column, out: &mut String) -> Result<(), CynosError> {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Boolean(true) => out.push_str("true"),
+        Value::Boolean(false) => out.push_str("false"),
+        Value::Int32(n) => out.push_str(&n.to_string()),
+        Value::Int64(n) => {
+            out.push('"');
+            out.push_str(&n.to_string());
+            out.push('"');
+        }
+        Value::Float64(n) => write_json_number(*n, out),
+        Value::Decimal(d) => {
+            out.push('"');
+            out.push_str(&d.to_string());
+            out.push('"');
+        }
+        Value::String(s) => write_json_string(s, out),
+        Value::DateTime(ms) => out.push_str(&ms.to_string()),
+        Value::Bytes(bytes) => {
+            out.push('[');
+            for (i, byte) in bytes.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&byte.to_string());
+            }
+            out.push(']');
+        }
+        Value::Jsonb(jsonb) => {
+            let document = core::str::from_utf8(&jsonb.0)
+                .ok()
+                .and_then(|text| parse_json(text).ok())
+                .ok_or_else(|| {
+                    CynosError::invalid_operation(format!(
+                        "Column {} holds a JSONB value that is not valid JSON",
+                        column.name()
+                    ))
+                })?;
+            write_json(&document, out);
+        }
+    }
+    Ok(())
+}
+
+/// Parses a JSON array of row objects into rows with freshly reserved ids,
+/// filling computed columns the way `InsertBuilder` does: a missing key takes
+/// the column's default expression, and generated columns are always
+/// recomputed, so the values an export wrote for them are ignored.
+pub(crate) fn decode_rows(
+    schema: &Table,
+    text: &str,
+    defaults: &[ComputedDefault],
+) -> Result<Vec<Row>, CynosError> {
+    let document = parse_json(text)
+        .map_err(|error| CynosError::invalid_argument(format!("Invalid table JSON: {}", error)))?;
+    let JsonbValue::Array(items) = document else {
+        return Err(CynosError::type_mismatch(
+            "Table JSON must be an array of row objects",
+        ));
+    };
+
+    let start_row_id = reserve_row_ids(items.len() as u64);
+    items
+        .iter()
+        .enumerate()
+        .map(|(position, item)| {
+            let JsonbValue::Object(object) = item else {
+                return Err(CynosError::type_mismatch(format!(
+                    "Row {} is not an object",
+                    position
+                )));
+            };
+            let mut pending = Vec::new();
+            let mut values = schema
+                .columns()
+                .iter()
+                .map(|column| {
+                    let json = object.get(column.name());
+                    let computed = defaults
+                        .iter()
+                        .find(|default| default.column_index == column.index());
+                    if let Some(default) =
+                        computed.filter(|default| default.generated || json.is_none())
+                    {
+                        pending.push(default);
+                        return Ok(Value::Null);
+                    }
+                    decode_value(json, column).map_err(|error| match error {
+                        CynosError::TypeMismatch(message) => CynosError::TypeMismatch(format!(
+                            "Row {}, column {}: {}",
+                            position,
+                            column.name(),
+                            message
+                        )),
+                        other => other,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let row_id = start_row_id + position as u64;
+            fill_computed_columns(&mut values, schema, row_id, &pending)?;
+            Ok(Row::new(row_id, values))
+        })
+        .collect()
+}
+
+fn decode_value(json: Option<&JsonbValue>, column: &Column) -> Result<Value, CynosError> {
+    let json = match (json, column.declared_default()) {
+        // A missing key takes the column default; an explicit null does not.
+        (None, Some(default)) => return Ok(default.clone()),
+        (None | Some(JsonbValue::Null), _) => {
+            return if column.is_nullable() {
+                Ok(Value::Null)
+            } else {
+                Err(CynosError::ConstraintViolation {
+                    constraint: ConstraintKind::NotNull,
+                    message: format!("Column {} is not nullable", column.name()),
+                })
+            };
+        }
+        (Some(json), _) => json,
+    };
+
+    let value = match (column.data_type(), json) {
+        (DataType::Boolean, JsonbValue::Bool(b)) => Value::Boolean(*b),
+        (DataType::Int32, JsonbValue::Number(n)) => {
+            Value::Int32(integral(*n, i32::MIN as f64, i32::MAX as f64)? as i32)
+        }
+        (DataType::Int64, JsonbValue::String(s)) => Value::Int64(
+            s.parse()
+                .map_err(|_| CynosError::type_mismatch("expected a 64-bit integer string"))?,
+        ),
+        (DataType::Int64, JsonbValue::Number(n)) => {
+            Value::Int64(integral(*n, i64::MIN as f64, i64::MAX as f64)? as i64)
+        }
+        (DataType::Float64, JsonbValue::Number(n)) => Value::Float64(*n),
+        (DataType::String, JsonbValue::String(s)) => Value::String(s.clone()),
+        (DataType::DateTime, JsonbValue::Number(n)) => {
+            Value::DateTime(integral(*n, i64::MIN as f64, i64::MAX as f64)? as i64)
+        }
+        (DataType::Bytes, JsonbValue::Array(items)) => Value::Bytes(
+            items
+                .iter()
+                .map(|item| match item {
+                    JsonbValue::Number(n) => integral(*n, 0.0, 255.0).map(|byte| byte as u8),
+                    _ => Err(CynosError::type_mismatch("expected an array of bytes")),
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+        (DataType::Jsonb, json) => Value::Jsonb(cynos_core::JsonbValue::new(
+            to_json_string(json).into_bytes(),
+        )),
+        (data_type, _) => {
+            return Err(CynosError::type_mismatch(format!(
+                "expected a {:?} value",
+                data_type
+            )))
+        }
+    };
+    Ok(value)
+}
+
+fn integral(n: f64, min: f64, max: f64) -> Result<f64, CynosError> {
+    if n.fract() == 0.0 && n >= min && n <= max {
+        Ok(n)
+    } else {
+        Err(CynosError::type_mismatch(format!(
+            "{} is not an integer in range",
+            n
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query_engine::execute_physical_plan;
+    use alloc::vec;
+    use cynos_core::schema::TableBuilder;
+    use cynos_query::ast::Expr as AstExpr;
+    use cynos_query::planner::PhysicalPlan;
+    use cynos_storage::TableCache;
+
+    fn events_table() -> Table {
+        TableBuilder::new("events")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("flag", DataType::Boolean)
+            .unwrap()
+            .add_column("count", DataType::Int32)
+            .unwrap()
+            .add_column("score", DataType::Float64)
+            .unwrap()
+            .add_column("label", DataType::String)
+            .unwrap()
+            .add_column("at", DataType::DateTime)
+            .unwrap()
+            .add_column("raw", DataType::Bytes)
+            .unwrap()
+            .add_column("doc", DataType::Jsonb)
+            .unwrap()
+            .add_nullable(&["label"])
+            .add_primary_key(&["id"], false)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    fn event(id: i64, label: Option<&str>, doc: &str) -> Vec<Value> {
+        vec![
+            Value::Int64(id),
+            Value::Boolean(id % 2 == 0),
+            Value::Int32(-(id as i32)),
+            Value::Float64(id as f64 / 4.0),
+            label.map_or(Value::Null, |label| Value::String(label.into())),
+            Value::DateTime(1_700_000_000_000 + id % 1000),
+            Value::Bytes(vec![0, id as u8, 255]),
+            Value::Jsonb(cynos_core::JsonbValue::new(doc.as_bytes().to_vec())),
+        ]
+    }
+
+    fn table_contents(cache: &TableCache) -> Vec<Vec<Value>> {
+        let plan = PhysicalPlan::table_scan("events");
+        let mut rows: Vec<Vec<Value>> = execute_physical_plan(cache, &plan)
+            .unwrap()
+            .iter()
+            .map(|row| row.values().to_vec())
+            .collect();
+        rows.sort();
+        rows
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let mut source = TableCache::new();
+        source.create_table(events_table()).unwrap();
+        let originals = [
+            event(
+                1,
+                Some("quote \" and\nnewline"),
+                r#"{"a":[1,{"b":null}],"z":"é"}"#,
+            ),
+            // Beyond 2^53: must survive the trip exactly.
+            event(9_007_199_254_740_993, None, "[]"),
+            event(-4, Some(""), "3.5"),
+        ];
+        {
+            let store = source.get_table_mut("events").unwrap();
+            for (i, values) in originals.iter().enumerate() {
+                store
+                    .insert(Row::new(i as u64 + 1, values.clone()))
+                    .unwrap();
+            }
+        }
+
+        let store = source.get_table("events").unwrap();
+        let json = export_rows(
+            store.schema(),
+            store.scan().collect::<Vec<_>>().iter().map(|row| &**row),
+        )
+        .unwrap();
+        assert!(json.contains(r#""id":"9007199254740993""#), "{json}");
+        assert!(json.contains(r#""label":null"#), "{json}");
+
+        let mut target = TableCache::new();
+        target.create_table(events_table()).unwrap();
+        let decoded =
+            decode_rows(target.get_table("events").unwrap().schema(), &json, &[]).unwrap();
+        {
+            let store = target.get_table_mut("events").unwrap();
+            for row in decoded {
+                store.insert(row).unwrap();
+            }
+        }
+
+        assert_eq!(table_contents(&target), table_contents(&source));
+    }
+
+    #[test]
+    fn test_decode_rows_reports_bad_input() {
+        let schema = events_table();
+
+        assert!(matches!(
+            decode_rows(&schema, "{", &[]),
+            Err(CynosError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            decode_rows(&schema, r#"{"id":"1"}"#, &[]),
+            Err(CynosError::TypeMismatch(_))
+        ));
+
+        let missing_flag = r#"[{"id":"1","count":1,"score":1,"at":0,"raw":[],"doc":{}}]"#;
+        assert_eq!(
+            decode_rows(&schema, missing_flag, &[])
+                .unwrap_err()
+                .constraint(),
+            Some(ConstraintKind::NotNull)
+        );
+
+        let bad_count =
+            r#"[{"id":"1","flag":true,"count":1.5,"score":1,"at":0,"raw":[],"doc":{}}]"#;
+        let error = decode_rows(&schema, bad_count, &[]).unwrap_err();
+        assert_eq!(error.code(), "TYPE_MISMATCH");
+        assert!(
+            error.message().starts_with("Row 0, column count:"),
+            "{}",
+            error
+        );
+
+        let numeric_id = r#"[{"id":7,"flag":true,"count":1,"score":1,"at":0,"raw":[1],"doc":{}}]"#;
+        let rows = decode_rows(&schema, numeric_id, &[]).unwrap();
+        assert_eq!(rows[0].get(0), Some(&Value::Int64(7)));
+        assert_eq!(rows[0].get(4), Some(&Value::Null));
+    }
+
+    #[test]
+    fn test_decode_rows_fills_computed_columns() {
+        let schema = TableBuilder::new("items")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("price", DataType::Int64)
+            .unwrap()
+            .add_column("qty", DataType::Int64)
+            .unwrap()
+            .add_column("doubled", DataType::Int64)
+            .unwrap()
+            .add_primary_key(&["id"], false)
+            .unwrap()
+            .build()
+            .unwrap();
+        let defaults = [
+            // `qty` defaults to 1, `doubled` is generated as `price * 2`.
+            ComputedDefault {
+                column_index: 2,
+                expr: AstExpr::literal(Value::Int64(1)),
+                generated: false,
+                dependencies: vec![],
+            },
+            ComputedDefault {
+                column_index: 3,
+                expr: AstExpr::multiply(
+                    AstExpr::column("items", "price", 1),
+                    AstExpr::literal(Value::Int64(2)),
+                ),
+                generated: true,
+                dependencies: vec![1],
+            },
+        ];
+
+        // The stale `doubled` of the second row is recomputed, not imported.
+        let json = r#"[{"id":"1","price":"5"},{"id":"2","price":"3","qty":"4","doubled":"0"}]"#;
+        let rows = decode_rows(&schema, json, &defaults).unwrap();
+        assert_eq!(
+            rows[0].values(),
+            &[
+                Value::Int64(1),
+                Value::Int64(5),
+                Value::Int64(1),
+                Value::Int64(10)
+            ]
+        );
+        assert_eq!(rows[1].id(), rows[0].id() + 1);
+        assert_eq!(rows[1].get(2), Some(&Value::Int64(4)));
+        assert_eq!(rows[1].get(3), Some(&Value::Int64(6)));
+    }
+}
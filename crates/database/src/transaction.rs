@@ -2,15 +2,17 @@
 //!
 //! This module provides transaction support with commit and rollback capabilities.
 
-use crate::convert::{js_array_to_rows, js_to_value};
+use crate::convert::{apply_column_updates, check_column_updates, js_array_to_rows};
+use crate::error::CynosError;
 use crate::expr::Expr;
 use crate::live_runtime::LiveRegistry;
 use crate::query_builder::evaluate_predicate;
+use crate::table::ComputedDefaults;
 use alloc::rc::Rc;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use core::cell::RefCell;
-use cynos_core::{reserve_row_ids, Row};
+use cynos_core::{reserve_row_ids, Row, Value};
 use cynos_reactive::TableId;
 use cynos_storage::{TableCache, Transaction, TransactionState};
 use hashbrown::HashSet;
@@ -22,6 +24,8 @@ pub struct JsTransaction {
     cache: Rc<RefCell<TableCache>>,
     query_registry: Rc<RefCell<LiveRegistry>>,
     table_id_map: Rc<RefCell<hashbrown::HashMap<String, TableId>>>,
+    computed_defaults: Rc<RefCell<ComputedDefaults>>,
+    strict_inserts: bool,
     inner: Option<Transaction>,
     /// Pending changes: (table_id, changed_row_ids)
     pending_changes: Vec<(TableId, HashSet<u64>)>,
@@ -32,30 +36,101 @@ impl JsTransaction {
         cache: Rc<RefCell<TableCache>>,
         query_registry: Rc<RefCell<LiveRegistry>>,
         table_id_map: Rc<RefCell<hashbrown::HashMap<String, TableId>>>,
+        computed_defaults: Rc<RefCell<ComputedDefaults>>,
+        strict_inserts: bool,
     ) -> Self {
         Self {
             cache,
             query_registry,
             table_id_map,
+            computed_defaults,
+            strict_inserts,
             inner: Some(Transaction::begin()),
             pending_changes: Vec::new(),
         }
     }
-}
 
-#[wasm_bindgen]
-impl JsTransaction {
-    /// Inserts rows into a table within the transaction.
-    pub fn insert(&mut self, table: &str, values: &JsValue) -> Result<(), JsValue> {
+    /// Applies column updates, and resets the `reset` columns to their
+    /// defaults, in the rows matching `predicate`.
+    pub(crate) fn update_columns(
+        &mut self,
+        table: &str,
+        updates: &[(String, JsValue)],
+        reset: &[String],
+        predicate: Option<&Expr>,
+    ) -> Result<usize, JsValue> {
         let tx = self
             .inner
             .as_mut()
-            .ok_or_else(|| JsValue::from_str("Transaction already completed"))?;
+            .ok_or_else(|| CynosError::invalid_operation("Transaction already completed"))?;
 
         let mut cache = self.cache.borrow_mut();
         let store = cache
             .get_table_mut(table)
-            .ok_or_else(|| JsValue::from_str(&alloc::format!("Table not found: {}", table)))?;
+            .ok_or_else(|| CynosError::table_not_found(table))?;
+
+        let schema = store.schema().clone();
+        let defaults = self.computed_defaults.borrow();
+        let defaults = defaults.get(table).map_or(&[][..], Vec::as_slice);
+        check_column_updates(updates, reset, &schema, defaults)?;
+
+        // Find rows to update
+        let rows_to_update: Vec<Row> = store
+            .scan()
+            .filter(|row| {
+                if let Some(pred) = predicate {
+                    evaluate_predicate(pred, &**row, &schema)
+                } else {
+                    true
+                }
+            })
+            .map(|rc| (*rc).clone())
+            .collect();
+
+        let mut updated_ids = HashSet::new();
+        let mut update_count = 0;
+
+        // Compute every new row before writing any of them
+        let mut new_rows = Vec::with_capacity(rows_to_update.len());
+        for old_row in &rows_to_update {
+            let new_values = apply_column_updates(old_row, updates, reset, &schema, defaults)?;
+            let new_version = old_row.version().wrapping_add(1);
+            new_rows.push(Row::new_with_version(old_row.id(), new_version, new_values));
+        }
+
+        for (old_row, new_row) in rows_to_update.into_iter().zip(new_rows) {
+            updated_ids.insert(old_row.id());
+
+            tx.update(&mut *cache, table, old_row.id(), new_row)
+                .map_err(CynosError::from)?;
+
+            update_count += 1;
+        }
+
+        if let Some(table_id) = self.table_id_map.borrow().get(table).copied() {
+            self.pending_changes.push((table_id, updated_ids));
+        }
+
+        Ok(update_count)
+    }
+
+    /// Inserts rows into a table within the transaction. With `strict`,
+    /// objects carrying keys that are not columns are rejected.
+    pub(crate) fn insert_rows(
+        &mut self,
+        table: &str,
+        values: &JsValue,
+        strict: bool,
+    ) -> Result<(), JsValue> {
+        let tx = self
+            .inner
+            .as_mut()
+            .ok_or_else(|| CynosError::invalid_operation("Transaction already completed"))?;
+
+        let mut cache = self.cache.borrow_mut();
+        let store = cache
+            .get_table_mut(table)
+            .ok_or_else(|| CynosError::table_not_found(table))?;
 
         let schema = store.schema().clone();
 
@@ -66,7 +141,9 @@ impl JsTransaction {
         // Reserve row IDs for all rows at once to avoid ID conflicts
         let start_row_id = reserve_row_ids(row_count);
 
-        let rows = js_array_to_rows(values, &schema, start_row_id)?;
+        let defaults = self.computed_defaults.borrow();
+        let defaults = defaults.get(table).map_or(&[][..], Vec::as_slice);
+        let rows = js_array_to_rows(values, &schema, start_row_id, defaults, strict)?;
 
         // Collect inserted row IDs
         let mut inserted_ids = HashSet::new();
@@ -75,7 +152,7 @@ impl JsTransaction {
         for row in rows {
             inserted_ids.insert(row.id());
             tx.insert(&mut *cache, table, row)
-                .map_err(|e| JsValue::from_str(&alloc::format!("{:?}", e)))?;
+                .map_err(CynosError::from)?;
         }
 
         // Store pending changes
@@ -85,6 +162,14 @@ impl JsTransaction {
 
         Ok(())
     }
+}
+
+#[wasm_bindgen]
+impl JsTransaction {
+    /// Inserts rows into a table within the transaction.
+    pub fn insert(&mut self, table: &str, values: &JsValue) -> Result<(), JsValue> {
+        self.insert_rows(table, values, self.strict_inserts)
+    }
 
     /// Updates rows in a table within the transaction.
     pub fn update(
@@ -93,22 +178,10 @@ impl JsTransaction {
         set_values: &JsValue,
         predicate: Option<Expr>,
     ) -> Result<usize, JsValue> {
-        let tx = self
-            .inner
-            .as_mut()
-            .ok_or_else(|| JsValue::from_str("Transaction already completed"))?;
-
-        let mut cache = self.cache.borrow_mut();
-        let store = cache
-            .get_table_mut(table)
-            .ok_or_else(|| JsValue::from_str(&alloc::format!("Table not found: {}", table)))?;
-
-        let schema = store.schema().clone();
-
         // Parse set values
         let set_obj = set_values
             .dyn_ref::<js_sys::Object>()
-            .ok_or_else(|| JsValue::from_str("set_values must be an object"))?;
+            .ok_or_else(|| CynosError::invalid_argument("set_values must be an object"))?;
 
         let keys = js_sys::Object::keys(set_obj);
         let mut updates: Vec<(String, JsValue)> = Vec::new();
@@ -119,72 +192,32 @@ impl JsTransaction {
             }
         }
 
-        // Find rows to update
-        let rows_to_update: Vec<Row> = store
-            .scan()
-            .filter(|row| {
-                if let Some(ref pred) = predicate {
-                    evaluate_predicate(pred, &**row, &schema)
-                } else {
-                    true
-                }
-            })
-            .map(|rc| (*rc).clone())
-            .collect();
-
-        let mut updated_ids = HashSet::new();
-        let mut update_count = 0;
-
-        for old_row in rows_to_update {
-            let mut new_values = old_row.values().to_vec();
-
-            for (col_name, js_val) in &updates {
-                if let Some(col) = schema.get_column(col_name) {
-                    let idx = col.index();
-                    let value = js_to_value(js_val, col.data_type())?;
-                    if idx < new_values.len() {
-                        new_values[idx] = value;
-                    }
-                }
-            }
-
-            // Create new row with incremented version
-            let new_version = old_row.version().wrapping_add(1);
-            let new_row = Row::new_with_version(old_row.id(), new_version, new_values);
-
-            updated_ids.insert(old_row.id());
-
-            tx.update(&mut *cache, table, old_row.id(), new_row)
-                .map_err(|e| JsValue::from_str(&alloc::format!("{:?}", e)))?;
-
-            update_count += 1;
-        }
-
-        if let Some(table_id) = self.table_id_map.borrow().get(table).copied() {
-            self.pending_changes.push((table_id, updated_ids));
-        }
-
-        Ok(update_count)
+        self.update_columns(table, &updates, &[], predicate.as_ref())
     }
 
-    /// Deletes rows from a table within the transaction.
+    /// Deletes rows from a table within the transaction. On a table with a
+    /// soft-delete column, the rows are stamped with the current time instead.
     pub fn delete(&mut self, table: &str, predicate: Option<Expr>) -> Result<usize, JsValue> {
         let tx = self
             .inner
             .as_mut()
-            .ok_or_else(|| JsValue::from_str("Transaction already completed"))?;
+            .ok_or_else(|| CynosError::invalid_operation("Transaction already completed"))?;
 
         let mut cache = self.cache.borrow_mut();
         let store = cache
             .get_table_mut(table)
-            .ok_or_else(|| JsValue::from_str(&alloc::format!("Table not found: {}", table)))?;
+            .ok_or_else(|| CynosError::table_not_found(table))?;
 
         let schema = store.schema().clone();
+        let soft_delete_index = schema.soft_delete_column().map(|col| col.index());
 
-        // Find rows to delete
+        // Find rows to delete; soft-deleted rows are already gone
         let rows_to_delete: Vec<Row> = store
             .scan()
             .filter(|row| {
+                if soft_delete_index.is_some_and(|idx| !row.get(idx).is_some_and(Value::is_null)) {
+                    return false;
+                }
                 if let Some(ref pred) = predicate {
                     evaluate_predicate(pred, &**row, &schema)
                 } else {
@@ -199,8 +232,17 @@ impl JsTransaction {
         let mut deleted_ids = HashSet::new();
         for row in rows_to_delete {
             deleted_ids.insert(row.id());
-            tx.delete(&mut *cache, table, row.id())
-                .map_err(|e| JsValue::from_str(&alloc::format!("{:?}", e)))?;
+            match soft_delete_index {
+                Some(idx) => {
+                    let mut values = row.values().to_vec();
+                    values[idx] = Value::DateTime(js_sys::Date::now() as i64);
+                    let new_row =
+                        Row::new_with_version(row.id(), row.version().wrapping_add(1), values);
+                    tx.update(&mut cache, table, row.id(), new_row)
+                }
+                None => tx.delete(&mut *cache, table, row.id()).map(|_| ()),
+            }
+            .map_err(CynosError::from)?;
         }
 
         if let Some(table_id) = self.table_id_map.borrow().get(table).copied() {
@@ -215,10 +257,9 @@ impl JsTransaction {
         let tx = self
             .inner
             .take()
-            .ok_or_else(|| JsValue::from_str("Transaction already completed"))?;
+            .ok_or_else(|| CynosError::invalid_operation("Transaction already completed"))?;
 
-        tx.commit()
-            .map_err(|e| JsValue::from_str(&alloc::format!("{:?}", e)))?;
+        tx.commit().map_err(CynosError::from)?;
 
         // Notify query registry of all changes
         for (table_id, changed_ids) in self.pending_changes.drain(..) {
@@ -235,11 +276,10 @@ impl JsTransaction {
         let tx = self
             .inner
             .take()
-            .ok_or_else(|| JsValue::from_str("Transaction already completed"))?;
+            .ok_or_else(|| CynosError::invalid_operation("Transaction already completed"))?;
 
         let mut cache = self.cache.borrow_mut();
-        tx.rollback(&mut *cache)
-            .map_err(|e| JsValue::from_str(&alloc::format!("{:?}", e)))?;
+        tx.rollback(&mut *cache).map_err(CynosError::from)?;
 
         // Notify Live Query of rollback changes (data was restored)
         for (table_id, changed_ids) in self.pending_changes.drain(..) {
@@ -1,6 +1,10 @@
-use cynos_database::binary_protocol::{BinaryDataType, BinaryResult, SchemaLayout, HEADER_SIZE};
+use cynos_database::binary_protocol::{
+    flags, BinaryDataType, BinaryResult, SchemaLayout, HEADER_SIZE,
+};
 use cynos_database::table::ColumnOptions;
-use cynos_database::{col, Database, JsDataType, JsSortOrder, PreparedSelectQuery, SelectBuilder};
+use cynos_database::{
+    col, Database, Expr, JsDataType, JsNullsOrder, JsSortOrder, PreparedSelectQuery, SelectBuilder,
+};
 use js_sys::{Array, Date, Object, Reflect, Uint8Array, JSON};
 use std::convert::TryInto;
 use wasm_bindgen::JsValue;
@@ -159,7 +163,7 @@ fn decode_binary_rows(layout: &SchemaLayout, result: &BinaryResult) -> Vec<Vec<C
                             buffer[data_start..data_start + 8].try_into().unwrap(),
                         )
                             as i64),
-                        BinaryDataType::String => {
+                        BinaryDataType::String | BinaryDataType::Decimal => {
                             let (offset, len) = read_varlen(&buffer, data_start);
                             let bytes = &buffer[var_offset + offset..var_offset + offset + len];
                             Cell::String(String::from_utf8(bytes.to_vec()).unwrap())
@@ -277,12 +281,22 @@ async fn assert_prepared_matches(
     assert_rows_eq(&binary_rows, expected);
 }
 
+fn error_property(error: &JsValue, name: &str) -> Option<String> {
+    Reflect::get(error, &JsValue::from_str(name))
+        .ok()
+        .and_then(|value| value.as_string())
+}
+
 fn assert_error_string(error: JsValue, expected: &str) {
-    assert_eq!(error.as_string().as_deref(), Some(expected));
+    assert_eq!(error_property(&error, "message").as_deref(), Some(expected));
+}
+
+fn assert_error_code(error: &JsValue, expected: &str) {
+    assert_eq!(error_property(error, "code").as_deref(), Some(expected));
 }
 
 fn assert_error_contains(error: JsValue, expected_fragment: &str) {
-    let text = error.as_string().unwrap_or_default();
+    let text = error_property(&error, "message").unwrap_or_default();
     assert!(
         text.contains(expected_fragment),
         "expected error to contain {expected_fragment:?}, got {text:?}"
@@ -843,6 +857,7 @@ async fn join_star_layout_qualifies_duplicate_column_names_and_nullability() {
         .left_join(
             "employees as managers",
             &col("employees.manager_id").eq(&JsValue::from_str("managers.id")),
+            None,
         )
         .order_by("employees.id", JsSortOrder::Asc);
 
@@ -1015,6 +1030,72 @@ async fn filter_null_and_chained_where_with_pagination_are_correct() {
     assert_select_matches(&paged_query, &specs, &paged_expected).await;
 }
 
+#[wasm_bindgen_test(async)]
+async fn database_default_nulls_order_applies_to_unplaced_keys() {
+    let db = Database::new("query_correctness_default_nulls_order");
+    register_filter_users_table(&db);
+    seed_filter_users(&db).await;
+    db.set_default_nulls_order(Some(JsNullsOrder::First));
+
+    let specs = [spec("id", CellKind::I64, true)];
+    let ids =
+        |ids: &[i64]| -> Vec<Vec<Cell>> { ids.iter().map(|&id| vec![Cell::I64(id)]).collect() };
+
+    // User 7 has no city: it now leads a descending key too.
+    let desc_query = db
+        .select(&js_str_array(&["id"]))
+        .from("users")
+        .order_by("city", JsSortOrder::Desc)
+        .order_by("id", JsSortOrder::Asc);
+    assert_select_matches(&desc_query, &specs, &ids(&[7, 6, 2, 4, 5, 1, 3])).await;
+
+    let asc_query = db
+        .select(&js_str_array(&["id"]))
+        .from("users")
+        .order_by("city", JsSortOrder::Asc)
+        .order_by("id", JsSortOrder::Asc);
+    assert_select_matches(&asc_query, &specs, &ids(&[7, 1, 3, 5, 2, 4, 6])).await;
+
+    // A NOT NULL key needs no IS NULL key, so the primary key index still
+    // supplies the order.
+    let pk_query = db
+        .select(&js_str_array(&["id"]))
+        .from("users")
+        .order_by("id", JsSortOrder::Desc)
+        .limit(2);
+    let physical = explain_physical(&pk_query);
+    assert!(
+        physical.contains("IndexScan"),
+        "expected an index-ordered scan, got {physical}"
+    );
+    assert_select_matches(&pk_query, &specs, &ids(&[7, 6])).await;
+
+    // A key's own placement overrides the default.
+    let keys = js_array([
+        js_array([
+            JsValue::from_str("city"),
+            JsValue::from(JsSortOrder::Desc),
+            JsValue::from(JsNullsOrder::Last),
+        ]),
+        js_array([JsValue::from_str("id"), JsValue::from(JsSortOrder::Asc)]),
+    ]);
+    let override_query = db
+        .select(&js_str_array(&["id"]))
+        .from("users")
+        .order_by_exprs(&keys)
+        .unwrap();
+    assert_select_matches(&override_query, &specs, &ids(&[6, 2, 4, 5, 1, 3, 7])).await;
+
+    // Clearing the default restores NULLs last when descending.
+    db.set_default_nulls_order(None);
+    let natural_query = db
+        .select(&js_str_array(&["id"]))
+        .from("users")
+        .order_by("city", JsSortOrder::Desc)
+        .order_by("id", JsSortOrder::Asc);
+    assert_select_matches(&natural_query, &specs, &ids(&[6, 2, 4, 5, 1, 3, 7])).await;
+}
+
 #[wasm_bindgen_test(async)]
 async fn update_object_form_and_delete_paths_are_correct() {
     let db = Database::new("query_correctness_update_delete");
@@ -1117,6 +1198,91 @@ async fn multi_column_group_by_is_correct() {
     assert_select_matches(&query, &specs, &expected).await;
 }
 
+#[wasm_bindgen_test(async)]
+async fn group_by_count_keys_match_between_observe_and_trace() {
+    let db = Database::new("query_correctness_group_by_live_keys");
+    register_filter_users_table(&db);
+    seed_filter_users(&db).await;
+
+    let query = db
+        .select(&JsValue::from_str("*"))
+        .from("users")
+        .group_by(&js_str_array(&["city"]))
+        .count();
+
+    let row_keys = |result: JsValue| -> Vec<Vec<String>> {
+        Array::from(&result)
+            .iter()
+            .map(|row| {
+                Object::keys(&Object::from(row))
+                    .iter()
+                    .map(|key| key.as_string().unwrap())
+                    .collect()
+            })
+            .collect()
+    };
+    let row_texts = |result: JsValue| -> Vec<String> {
+        let mut texts: Vec<String> = Array::from(&result)
+            .iter()
+            .map(|row| JSON::stringify(&row).unwrap().into())
+            .collect();
+        texts.sort();
+        texts
+    };
+
+    let observed = query.observe().unwrap().get_result().unwrap();
+    let traced = query.trace().unwrap().get_result().unwrap();
+
+    let observed_keys = row_keys(observed.clone());
+    assert!(!observed_keys.is_empty());
+    for keys in observed_keys.iter().chain(row_keys(traced.clone()).iter()) {
+        assert_eq!(keys, &["city".to_string(), "count".to_string()]);
+    }
+    assert_eq!(row_texts(traced), row_texts(observed));
+}
+
+#[wasm_bindgen_test(async)]
+async fn live_decimal_sum_and_avg_stay_exact() {
+    let db = Database::new("query_correctness_live_decimal_sum");
+    let builder = db
+        .create_table("prices")
+        .column(
+            "id",
+            JsDataType::Int64,
+            Some(ColumnOptions::new().set_primary_key(true)),
+        )
+        .column("amount", JsDataType::Decimal, None);
+    db.register_table(&builder).unwrap();
+    let insert = |id: f64, amount: &str| {
+        db.insert("prices").values(&js_array([js_object(&[
+            ("id", JsValue::from_f64(id)),
+            ("amount", JsValue::from_str(amount)),
+        ])]))
+    };
+    insert(1.0, "0.10").exec().await.unwrap();
+    insert(2.0, "0.20").exec().await.unwrap();
+
+    let query = db
+        .select(&JsValue::from_str("*"))
+        .from("prices")
+        .sum("amount")
+        .avg("amount");
+    let observed = query.observe().unwrap();
+    let traced = query.trace().unwrap();
+    let text = |result: JsValue| -> String { JSON::stringify(&result).unwrap().into() };
+
+    let initial = text(traced.get_result().unwrap());
+    assert!(initial.contains("\"0.30\""), "{initial}");
+    assert!(initial.contains("\"0.15000000\""), "{initial}");
+    assert_eq!(text(observed.get_result().unwrap()), initial);
+
+    insert(3.0, "0.30").exec().await.unwrap();
+    let updated = text(traced.get_result().unwrap());
+    assert!(updated.contains("\"0.60\""), "{updated}");
+    assert!(updated.contains("\"0.20000000\""), "{updated}");
+    assert_eq!(text(observed.get_result().unwrap()), updated);
+}
+
 #[wasm_bindgen_test]
 fn union_rejects_incompatible_outputs() {
     let db = Database::new("query_correctness_union_incompatible");
@@ -1130,9 +1296,9 @@ fn union_rejects_incompatible_outputs() {
         Ok(_) => panic!("union should reject incompatible outputs"),
         Err(error) => error,
     };
-    assert_eq!(
-        error.as_string().as_deref(),
-        Some("UNION operands must produce the same number of columns with matching types")
+    assert_error_string(
+        error,
+        "UNION operands must produce the same number of columns with matching types",
     );
 }
 
@@ -1516,6 +1682,7 @@ async fn self_join_projection_preserves_qualified_columns_across_exec_paths() {
         .left_join(
             "employees as managers",
             &col("employees.manager_id").eq(&JsValue::from_str("managers.id")),
+            None,
         )
         .order_by("employees.id", JsSortOrder::Asc);
 
@@ -1963,6 +2130,7 @@ async fn inner_join_projection_across_tables_is_correct() {
         .inner_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .order_by("orders.id", JsSortOrder::Asc);
     assert_select_matches(&query, &specs, &expected).await;
@@ -1992,6 +2160,7 @@ async fn left_join_preserves_unmatched_left_rows() {
         .left_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .order_by("customers.id", JsSortOrder::Asc)
         .order_by("orders.id", JsSortOrder::Asc);
@@ -2214,10 +2383,7 @@ async fn select_without_from_returns_error() {
         Ok(_) => panic!("query without FROM should fail"),
         Err(error) => error,
     };
-    assert_eq!(
-        error.as_string().as_deref(),
-        Some("FROM table not specified")
-    );
+    assert_error_string(error, "FROM table not specified");
 }
 
 #[wasm_bindgen_test(async)]
@@ -2229,10 +2395,223 @@ async fn selecting_missing_table_returns_error() {
         Ok(_) => panic!("query against missing table should fail"),
         Err(error) => error,
     };
-    assert_eq!(
-        error.as_string().as_deref(),
-        Some("Table not found: missing_users")
+    assert_error_code(&error, "TABLE_NOT_FOUND");
+    assert_error_string(error, "Table not found: missing_users");
+}
+
+#[wasm_bindgen_test(async)]
+async fn misspelled_where_column_returns_error() {
+    let db = Database::new("query_correctness_unknown_where_column");
+    register_filter_users_table(&db);
+    let query = db
+        .select(&JsValue::UNDEFINED)
+        .from("users")
+        .where_(&col("agee").gt(&JsValue::from_f64(18.0)));
+
+    let error = match query.exec().await {
+        Ok(_) => panic!("WHERE on unknown column should fail"),
+        Err(error) => error,
+    };
+    assert_error_code(&error, "COLUMN_NOT_FOUND");
+    assert_error_string(error, "Unknown column 'agee' in table 'users'");
+}
+
+#[wasm_bindgen_test(async)]
+async fn misspelled_order_by_column_returns_error() {
+    let db = Database::new("query_correctness_unknown_order_column");
+    register_filter_users_table(&db);
+    let query = db
+        .select(&JsValue::UNDEFINED)
+        .from("users")
+        .order_by("scroe", JsSortOrder::Desc);
+
+    let error = match query.exec().await {
+        Ok(_) => panic!("ORDER BY on unknown column should fail"),
+        Err(error) => error,
+    };
+    assert_error_code(&error, "COLUMN_NOT_FOUND");
+    assert_error_string(error, "Unknown column 'scroe' in table 'users'");
+}
+
+#[wasm_bindgen_test(async)]
+async fn misspelled_projection_column_returns_error() {
+    let db = Database::new("query_correctness_unknown_projection_column");
+    register_customers_table(&db);
+    register_orders_table(&db);
+    let query = db
+        .select(&js_str_array(&["customers.name", "orders.amout"]))
+        .from("customers")
+        .inner_join(
+            "orders",
+            &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
+        );
+
+    let error = match query.exec().await {
+        Ok(_) => panic!("projection of unknown column should fail"),
+        Err(error) => error,
+    };
+    assert_error_code(&error, "COLUMN_NOT_FOUND");
+    assert_error_string(
+        error,
+        "Unknown column 'orders.amout' in tables 'customers', 'orders'",
     );
+
+    let error = match query.explain() {
+        Ok(_) => panic!("explain with unknown column should fail"),
+        Err(error) => error,
+    };
+    assert_error_code(&error, "COLUMN_NOT_FOUND");
+}
+
+#[wasm_bindgen_test(async)]
+async fn unknown_group_by_column_returns_error() {
+    let db = Database::new("query_correctness_unknown_group_by_column");
+    register_metrics_table(&db);
+    let query = db
+        .select(&JsValue::from_str("*"))
+        .from("metrics")
+        .group_by(&js_str_array(&["categroy"]))
+        .count();
+
+    let error = match query.exec().await {
+        Ok(_) => panic!("GROUP BY on unknown column should fail"),
+        Err(error) => error,
+    };
+    assert_error_code(&error, "COLUMN_NOT_FOUND");
+    assert_error_string(error, "Unknown column 'categroy' in table 'metrics'");
+
+    let unknown_aggregate = db
+        .select(&JsValue::from_str("*"))
+        .from("metrics")
+        .group_by(&js_str_array(&["category"]))
+        .sum("vaule");
+    let error = match unknown_aggregate.explain() {
+        Ok(_) => panic!("aggregate over unknown column should fail"),
+        Err(error) => error,
+    };
+    assert_error_code(&error, "COLUMN_NOT_FOUND");
+}
+
+#[wasm_bindgen_test(async)]
+async fn aggregate_select_rejects_columns_missing_from_group_by() {
+    let db = Database::new("query_correctness_ungrouped_column");
+    register_metrics_table(&db);
+    seed_metrics(&db).await;
+
+    let query = db
+        .select(&js_str_array(&["category", "value"]))
+        .from("metrics")
+        .group_by(&js_str_array(&["category"]))
+        .count();
+    let error = match query.exec().await {
+        Ok(_) => panic!("selecting an ungrouped column should fail"),
+        Err(error) => error,
+    };
+    assert_error_code(&error, "INVALID_ARGUMENT");
+    assert_error_string(
+        error,
+        "Column 'value' must appear in the GROUP BY clause or be used in an aggregate function",
+    );
+
+    let ordered = db
+        .select(&JsValue::from_str("*"))
+        .from("metrics")
+        .group_by(&js_str_array(&["category"]))
+        .count()
+        .order_by("value", JsSortOrder::Asc);
+    let error = match ordered.exec().await {
+        Ok(_) => panic!("ordering by an ungrouped column should fail"),
+        Err(error) => error,
+    };
+    assert_error_code(&error, "INVALID_ARGUMENT");
+
+    let grouped = db
+        .select(&js_str_array(&["category"]))
+        .from("metrics")
+        .group_by(&js_str_array(&["category"]))
+        .count()
+        .order_by("category", JsSortOrder::Asc);
+    let specs = [
+        spec("category", CellKind::String, true),
+        spec("count", CellKind::I64, true),
+    ];
+    let expected = vec![
+        vec![Cell::String("A".into()), Cell::I64(2)],
+        vec![Cell::String("B".into()), Cell::I64(2)],
+    ];
+    assert_select_matches(&grouped, &specs, &expected).await;
+}
+
+fn metric_row(id: f64, category: &str, value: f64) -> JsValue {
+    js_object(&[
+        ("id", JsValue::from_f64(id)),
+        ("category", JsValue::from_str(category)),
+        ("value", JsValue::from_f64(value)),
+    ])
+}
+
+#[wasm_bindgen_test(async)]
+async fn batch_runs_insert_then_select_and_returns_each_result() {
+    let db = Database::new("query_correctness_batch_insert_select");
+    register_metrics_table(&db);
+
+    let statements = Array::new();
+    statements.push(&JsValue::from(db.insert("metrics").values(&js_array([
+        metric_row(1.0, "A", 2.0),
+        metric_row(2.0, "B", 4.0),
+    ]))));
+    statements.push(&JsValue::from(
+        db.select(&js_str_array(&["id", "category"]))
+            .from("metrics")
+            .order_by("id", JsSortOrder::Asc),
+    ));
+
+    let results = Array::from(&db.batch(statements, None).await.unwrap());
+    assert_eq!(results.length(), 2);
+    assert_eq!(results.get(0).as_f64(), Some(2.0));
+
+    let rows = Array::from(&results.get(1));
+    assert_eq!(rows.length(), 2);
+    let category = |index: u32| {
+        Reflect::get(&rows.get(index), &JsValue::from_str("category"))
+            .unwrap()
+            .as_string()
+    };
+    assert_eq!(category(0).as_deref(), Some("A"));
+    assert_eq!(category(1).as_deref(), Some("B"));
+}
+
+#[wasm_bindgen_test(async)]
+async fn transactional_batch_rolls_back_on_failure() {
+    let db = Database::new("query_correctness_batch_transaction");
+    register_metrics_table(&db);
+
+    let statements = Array::new();
+    statements.push(&JsValue::from(
+        db.insert("metrics")
+            .values(&js_array([metric_row(1.0, "A", 2.0)])),
+    ));
+    statements.push(&JsValue::from(
+        db.insert("metrics")
+            .values(&js_array([metric_row(1.0, "B", 4.0)])),
+    ));
+    let options = js_object(&[("transaction", JsValue::TRUE)]);
+
+    let error = match db.batch(statements, Some(options)).await {
+        Ok(_) => panic!("duplicate primary key should fail the batch"),
+        Err(error) => error,
+    };
+    assert_error_code(&error, "CONSTRAINT_VIOLATION");
+    assert_eq!(db.total_row_count(), 0);
+
+    let invalid = Array::new();
+    invalid.push(&JsValue::from_str("SELECT 1"));
+    let error = match db.batch(invalid, None).await {
+        Ok(_) => panic!("non-builder batch statements should fail"),
+        Err(error) => error,
+    };
+    assert_error_code(&error, "INVALID_ARGUMENT");
 }
 
 #[wasm_bindgen_test(async)]
@@ -2256,10 +2635,12 @@ async fn insert_missing_non_nullable_column_returns_error() {
         Err(error) => error,
     };
 
+    assert_error_code(&error, "CONSTRAINT_VIOLATION");
     assert_eq!(
-        error.as_string().as_deref(),
-        Some("Column name is not nullable")
+        error_property(&error, "constraint").as_deref(),
+        Some("notNull")
     );
+    assert_error_string(error, "Column name is not nullable");
 }
 
 #[wasm_bindgen_test(async)]
@@ -2284,7 +2665,37 @@ async fn insert_wrong_type_returns_error() {
         Err(error) => error,
     };
 
-    assert_eq!(error.as_string().as_deref(), Some("Expected number value"));
+    assert_error_code(&error, "TYPE_MISMATCH");
+    assert_error_string(error, "Expected number value");
+}
+
+#[wasm_bindgen_test(async)]
+async fn insert_duplicate_primary_key_returns_unique_violation() {
+    let db = Database::new("query_correctness_insert_duplicate_pk");
+    register_filter_users_table(&db);
+
+    let row = || {
+        js_array([js_object(&[
+            ("id", JsValue::from_f64(1.0)),
+            ("name", JsValue::from_str("Alice")),
+            ("age", JsValue::from_f64(25.0)),
+            ("active", JsValue::from_bool(true)),
+            ("score", JsValue::from_f64(85.5)),
+            ("city", JsValue::from_str("Beijing")),
+        ])])
+    };
+    db.insert("users").values(&row()).exec().await.unwrap();
+
+    let error = match db.insert("users").values(&row()).exec().await {
+        Ok(_) => panic!("insert with duplicate primary key should fail"),
+        Err(error) => error,
+    };
+
+    assert_error_code(&error, "CONSTRAINT_VIOLATION");
+    assert_eq!(
+        error_property(&error, "constraint").as_deref(),
+        Some("unique")
+    );
 }
 
 #[wasm_bindgen_test]
@@ -2299,6 +2710,7 @@ fn explain_returns_logical_optimized_and_physical_plan_strings() {
         .left_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .order_by("customers.id", JsSortOrder::Asc)
         .limit(2);
@@ -2323,6 +2735,223 @@ fn explain_returns_logical_optimized_and_physical_plan_strings() {
     assert!(physical.contains("Join") || physical.contains("HashJoin"));
 }
 
+fn explain_physical(query: &SelectBuilder) -> String {
+    let explain = query.explain().unwrap();
+    Reflect::get(&explain, &JsValue::from_str("physical"))
+        .unwrap()
+        .as_string()
+        .unwrap()
+}
+
+#[wasm_bindgen_test(async)]
+async fn wide_ranges_scan_and_narrow_ranges_use_the_index() {
+    let db = Database::new("query_correctness_index_selectivity");
+    let builder = db
+        .create_table("readings")
+        .column(
+            "id",
+            JsDataType::Int64,
+            Some(ColumnOptions::new().set_primary_key(true)),
+        )
+        .column("value", JsDataType::Int64, None)
+        .index("idx_value", &JsValue::from_str("value"));
+    db.register_table(&builder).unwrap();
+    db.insert("readings")
+        .values(&js_array((0..1000).map(|id| {
+            js_object(&[
+                ("id", JsValue::from_f64(id as f64)),
+                ("value", JsValue::from_f64(id as f64)),
+            ])
+        })))
+        .exec()
+        .await
+        .unwrap();
+
+    let at_least = |value: f64| {
+        db.select(&JsValue::from_str("*"))
+            .from("readings")
+            .where_(&col("value").gte(&JsValue::from_f64(value)))
+    };
+
+    let wide = explain_physical(&at_least(100.0));
+    assert!(
+        wide.contains("TableScan") && !wide.contains("IndexScan"),
+        "expected a filtered scan for a 90% range, got {wide}"
+    );
+    let narrow = explain_physical(&at_least(990.0));
+    assert!(
+        narrow.contains("IndexScan"),
+        "expected an index scan for a 1% range, got {narrow}"
+    );
+
+    db.set_max_index_selectivity(Some(0.95));
+    let wide = explain_physical(&at_least(100.0));
+    assert!(
+        wide.contains("IndexScan"),
+        "expected an index scan under a raised threshold, got {wide}"
+    );
+}
+
+#[wasm_bindgen_test(async)]
+async fn join_algorithm_hint_is_used_by_physical_plan() {
+    let db = Database::new("query_correctness_join_hint");
+    register_customers_table(&db);
+    register_orders_table(&db);
+    seed_customers_and_orders(&db).await;
+
+    let specs = [
+        spec("name", CellKind::String, true),
+        spec("amount", CellKind::I64, true),
+    ];
+    let expected = vec![
+        vec![Cell::String("Alice".into()), Cell::I64(100)],
+        vec![Cell::String("Alice".into()), Cell::I64(50)],
+        vec![Cell::String("Bob".into()), Cell::I64(80)],
+    ];
+
+    for (algorithm, node) in [
+        ("hash", "HashJoin"),
+        ("sortMerge", "SortMergeJoin"),
+        ("nestedLoop", "NestedLoopJoin"),
+    ] {
+        let query = db
+            .select(&js_str_array(&["customers.name", "orders.amount"]))
+            .from("customers")
+            .inner_join(
+                "orders",
+                &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+                Some(js_object(&[("algorithm", JsValue::from_str(algorithm))])),
+            )
+            .order_by("orders.id", JsSortOrder::Asc);
+
+        let physical = explain_physical(&query);
+        assert!(
+            physical.contains(node),
+            "expected {node} for {algorithm:?} hint, got {physical}"
+        );
+        assert_select_matches(&query, &specs, &expected).await;
+    }
+}
+
+#[wasm_bindgen_test(async)]
+async fn null_safe_join_option_matches_null_keys() {
+    let db = Database::new("query_correctness_null_safe_join");
+    for (table, value_column) in [("parts", "part"), ("bins", "bin")] {
+        let builder = db
+            .create_table(table)
+            .column(
+                "id",
+                JsDataType::Int64,
+                Some(ColumnOptions::new().set_primary_key(true)),
+            )
+            .column(
+                "region",
+                JsDataType::String,
+                Some(ColumnOptions::new().set_nullable(true)),
+            )
+            .column(value_column, JsDataType::String, None);
+        db.register_table(&builder).unwrap();
+    }
+    for (table, value_column, rows) in [
+        (
+            "parts",
+            "part",
+            [(1.0, Some("eu"), "bolt"), (2.0, None, "nut")],
+        ),
+        ("bins", "bin", [(1.0, Some("eu"), "A"), (2.0, None, "B")]),
+    ] {
+        let rows = rows.map(|(id, region, value)| {
+            js_object(&[
+                ("id", JsValue::from_f64(id)),
+                ("region", region.map_or(JsValue::NULL, JsValue::from_str)),
+                (value_column, JsValue::from_str(value)),
+            ])
+        });
+        db.insert(table)
+            .values(&js_array(rows))
+            .exec()
+            .await
+            .unwrap();
+    }
+
+    let specs = [
+        spec("part", CellKind::String, false),
+        spec("bin", CellKind::String, true),
+    ];
+    let query = |options: Option<JsValue>| {
+        db.select(&js_str_array(&["parts.part", "bins.bin"]))
+            .from("parts")
+            .left_join(
+                "bins",
+                &col("parts.region").eq(&JsValue::from_str("bins.region")),
+                options,
+            )
+            .order_by("parts.id", JsSortOrder::Asc)
+    };
+
+    assert_select_matches(
+        &query(None),
+        &specs,
+        &[
+            vec![Cell::String("bolt".into()), Cell::String("A".into())],
+            vec![Cell::String("nut".into()), Cell::Null],
+        ],
+    )
+    .await;
+
+    let null_safe = query(Some(js_object(&[("nullSafe", JsValue::TRUE)])));
+    assert!(explain_physical(&null_safe).contains("HashJoin"));
+    assert_select_matches(
+        &null_safe,
+        &specs,
+        &[
+            vec![Cell::String("bolt".into()), Cell::String("A".into())],
+            vec![Cell::String("nut".into()), Cell::String("B".into())],
+        ],
+    )
+    .await;
+}
+
+#[wasm_bindgen_test]
+fn invalid_join_algorithm_hints_return_errors() {
+    let db = Database::new("query_correctness_invalid_join_hint");
+    register_customers_table(&db);
+    register_orders_table(&db);
+
+    let unknown = db
+        .select(&js_str_array(&["customers.name", "orders.amount"]))
+        .from("customers")
+        .left_join(
+            "orders",
+            &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            Some(js_object(&[("algorithm", JsValue::from_str("merge"))])),
+        );
+    let error = match unknown.explain() {
+        Ok(_) => panic!("unknown join algorithm should fail"),
+        Err(error) => error,
+    };
+    assert_error_code(&error, "INVALID_ARGUMENT");
+    assert_error_contains(error, "Unknown join algorithm 'merge'");
+
+    let non_equi = db
+        .select(&js_str_array(&["customers.name", "orders.amount"]))
+        .from("customers")
+        .inner_join(
+            "orders",
+            &col("customers.id").lt(&JsValue::from_str("orders.customer_id")),
+            Some(js_object(&[("algorithm", JsValue::from_str("hash"))])),
+        );
+    let error = match non_equi.explain() {
+        Ok(_) => panic!("hash hint on a non-equi join should fail"),
+        Err(error) => error,
+    };
+    assert_error_code(&error, "INVALID_ARGUMENT");
+    assert_error_string(
+        error,
+        "Join algorithm 'hash' requires an equi-join condition on 'orders'",
+    );
+}
+
 #[wasm_bindgen_test(async)]
 async fn select_with_undefined_columns_behaves_like_star() {
     let db = Database::new("query_correctness_select_undefined_star");
@@ -2490,6 +3119,50 @@ async fn empty_result_set_roundtrips_exec_and_exec_binary() {
     assert_select_matches(&query, &specs, &expected).await;
 }
 
+#[wasm_bindgen_test(async)]
+async fn exec_binary_int64_as_bigint_preserves_large_ids() {
+    let db = Database::new("query_correctness_int64_as_bigint");
+    let builder = db.create_table("events").column(
+        "id",
+        JsDataType::Int64,
+        Some(ColumnOptions::new().set_primary_key(true)),
+    );
+    db.register_table(&builder).unwrap();
+
+    let large_id = (1i64 << 53) + 1;
+    db.insert("events")
+        .values(&js_array([js_object(&[(
+            "id",
+            js_sys::BigInt::from(large_id).into(),
+        )])]))
+        .exec()
+        .await
+        .unwrap();
+
+    let first_id = |result: &BinaryResult| {
+        let buffer = binary_result_bytes(result);
+        let header_flags = u32::from_le_bytes(buffer[12..16].try_into().unwrap());
+        let start = HEADER_SIZE + 1;
+        (header_flags, buffer[start..start + 8].try_into().unwrap())
+    };
+    let query = db.select(&JsValue::from_str("*")).from("events");
+
+    // Without the option the id is rounded to the nearest f64.
+    let (header_flags, bytes) = first_id(&query.exec_binary().await.unwrap());
+    assert_eq!(header_flags & flags::INT64_AS_BIGINT, 0);
+    assert_ne!(f64::from_le_bytes(bytes) as i64, large_id);
+
+    let query = query.int64_as_bigint(true);
+    let (header_flags, bytes) = first_id(&query.exec_binary().await.unwrap());
+    assert_ne!(header_flags & flags::INT64_AS_BIGINT, 0);
+    assert_eq!(i64::from_le_bytes(bytes), large_id);
+
+    let prepared = query.prepare().unwrap();
+    let (header_flags, bytes) = first_id(&prepared.exec_binary().await.unwrap());
+    assert_ne!(header_flags & flags::INT64_AS_BIGINT, 0);
+    assert_eq!(i64::from_le_bytes(bytes), large_id);
+}
+
 #[wasm_bindgen_test(async)]
 async fn filter_or_predicate_is_correct() {
     let db = Database::new("query_correctness_filter_or");
@@ -2721,7 +3394,7 @@ async fn join_condition_with_column_object_is_correct() {
     let query = db
         .select(&js_str_array(&["customers.name", "orders.amount"]))
         .from("customers")
-        .inner_join("orders", &col("customers.id").eq(&right_col))
+        .inner_join("orders", &col("customers.id").eq(&right_col), None)
         .order_by("orders.id", JsSortOrder::Asc);
 
     let specs = [
@@ -2750,6 +3423,7 @@ async fn join_condition_with_non_ambiguous_unqualified_columns_is_correct() {
         .inner_join(
             "customers",
             &col("customer_id").eq(&JsValue::from_str("id")),
+            None,
         )
         .order_by("orders.id", JsSortOrder::Asc);
 
@@ -2779,6 +3453,7 @@ async fn join_with_alias_projection_and_filter_is_correct() {
         .inner_join(
             "orders AS purchases",
             &col("customers.id").eq(&JsValue::from_str("purchases.customer_id")),
+            None,
         )
         .where_(&col("purchases.amount").gt(&JsValue::from_f64(60.0)))
         .order_by("purchases.amount", JsSortOrder::Desc);
@@ -2808,6 +3483,7 @@ async fn join_then_group_by_count_and_sum_is_correct() {
         .inner_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .group_by(&js_str_array(&["customers.name"]))
         .count()
@@ -2840,6 +3516,7 @@ async fn join_then_limit_and_offset_are_correct() {
         .left_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .order_by("customers.id", JsSortOrder::Asc)
         .order_by("orders.id", JsSortOrder::Asc)
@@ -3301,10 +3978,12 @@ async fn three_way_left_join_projection_preserves_nulls_and_ordering() {
         .left_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .left_join(
             "payments",
             &col("orders.id").eq(&JsValue::from_str("payments.order_id")),
+            None,
         )
         .order_by("customers.id", JsSortOrder::Asc)
         .order_by("orders.id", JsSortOrder::Asc);
@@ -3363,10 +4042,12 @@ async fn three_way_join_with_aliases_filter_and_pagination_is_correct() {
         .inner_join(
             "orders as purchases",
             &col("customers.id").eq(&JsValue::from_str("purchases.customer_id")),
+            None,
         )
         .left_join(
             "payments as receipts",
             &col("purchases.id").eq(&JsValue::from_str("receipts.order_id")),
+            None,
         )
         .where_(&col("purchases.amount").gte(&JsValue::from_f64(50.0)))
         .where_(&col("receipts.method").is_not_null())
@@ -3407,6 +4088,7 @@ async fn left_join_group_by_count_col_preserves_zero_match_groups() {
         .left_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .group_by(&js_str_array(&["customers.name"]))
         .count_col("orders.id")
@@ -3438,6 +4120,7 @@ async fn left_join_group_by_order_by_aggregate_and_pagination_is_correct() {
         .left_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .group_by(&js_str_array(&["customers.name"]))
         .count_col("orders.id")
@@ -3471,6 +4154,7 @@ async fn join_filter_group_by_sum_and_order_by_aggregate_desc_is_correct() {
         .inner_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .where_(&col("orders.amount").gte(&JsValue::from_f64(60.0)))
         .group_by(&js_str_array(&["customers.name"]))
@@ -3506,10 +4190,12 @@ async fn left_join_group_by_count_paid_orders_is_correct() {
         .left_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .left_join(
             "payments",
             &col("orders.id").eq(&JsValue::from_str("payments.order_id")),
+            None,
         )
         .group_by(&js_str_array(&["customers.name"]))
         .count_col("payments.id")
@@ -3541,6 +4227,7 @@ async fn prepared_left_join_group_by_query_reflects_join_side_mutations() {
         .left_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .group_by(&js_str_array(&["customers.name"]))
         .count_col("orders.id")
@@ -3617,10 +4304,12 @@ async fn prepared_three_way_left_join_query_reflects_third_table_mutations() {
         .left_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .left_join(
             "payments",
             &col("orders.id").eq(&JsValue::from_str("payments.order_id")),
+            None,
         )
         .order_by("customers.id", JsSortOrder::Asc)
         .order_by("orders.id", JsSortOrder::Asc);
@@ -3732,10 +4421,12 @@ async fn three_way_left_join_filtering_for_missing_payment_is_correct() {
         .left_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .left_join(
             "payments",
             &col("orders.id").eq(&JsValue::from_str("payments.order_id")),
+            None,
         )
         .where_(&col("payments.id").is_null())
         .order_by("customers.id", JsSortOrder::Asc)
@@ -3767,10 +4458,12 @@ async fn multi_join_aggregate_after_filter_on_third_table_is_correct() {
         .inner_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .inner_join(
             "payments",
             &col("orders.id").eq(&JsValue::from_str("payments.order_id")),
+            None,
         )
         .where_(&col("payments.settled").eq(&JsValue::from_bool(true)))
         .group_by(&js_str_array(&["customers.name"]))
@@ -3807,10 +4500,12 @@ async fn three_way_left_join_group_by_multi_aggregates_and_pagination_is_correct
         .left_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .left_join(
             "payments",
             &col("orders.id").eq(&JsValue::from_str("payments.order_id")),
+            None,
         )
         .group_by(&js_str_array(&["customers.name"]))
         .count_col("orders.amount")
@@ -3853,10 +4548,12 @@ async fn three_way_left_join_or_filter_then_projection_and_order_is_correct() {
         .left_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .left_join(
             "payments",
             &col("orders.id").eq(&JsValue::from_str("payments.order_id")),
+            None,
         )
         .where_(
             &col("payments.settled")
@@ -3903,6 +4600,7 @@ async fn union_all_of_join_queries_then_post_filter_order_and_limit_is_correct()
         .inner_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .where_(&col("orders.amount").gte(&JsValue::from_f64(80.0)));
     let right = db
@@ -3911,6 +4609,7 @@ async fn union_all_of_join_queries_then_post_filter_order_and_limit_is_correct()
         .inner_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .where_(&col("customers.name").eq(&JsValue::from_str("Alice")));
 
@@ -3990,6 +4689,7 @@ async fn prepared_union_all_of_join_queries_reflects_join_side_mutations() {
         .inner_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .where_(&col("orders.amount").gte(&JsValue::from_f64(80.0)));
     let right = db
@@ -3998,6 +4698,7 @@ async fn prepared_union_all_of_join_queries_reflects_join_side_mutations() {
         .inner_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .where_(&col("customers.name").eq(&JsValue::from_str("Alice")));
 
@@ -4058,10 +4759,12 @@ async fn three_way_left_join_group_by_after_or_filter_is_correct() {
         .left_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .left_join(
             "payments",
             &col("orders.id").eq(&JsValue::from_str("payments.order_id")),
+            None,
         )
         .where_(
             &col("payments.settled")
@@ -4102,10 +4805,12 @@ async fn left_join_missing_payment_rows_grouped_with_null_filter_and_order_is_co
         .left_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .left_join(
             "payments",
             &col("orders.id").eq(&JsValue::from_str("payments.order_id")),
+            None,
         )
         .where_(&col("payments.id").is_null())
         .group_by(&js_str_array(&["customers.name"]))
@@ -4143,10 +4848,12 @@ async fn three_way_left_join_alias_or_null_and_not_filter_with_pagination_is_cor
         .left_join(
             "orders as purchases",
             &col("customers.id").eq(&JsValue::from_str("purchases.customer_id")),
+            None,
         )
         .left_join(
             "payments as receipts",
             &col("purchases.id").eq(&JsValue::from_str("receipts.order_id")),
+            None,
         )
         .where_(
             &col("receipts.id")
@@ -4183,10 +4890,12 @@ async fn three_way_left_join_grouped_after_or_null_and_not_filters_is_correct()
         .left_join(
             "orders as purchases",
             &col("customers.id").eq(&JsValue::from_str("purchases.customer_id")),
+            None,
         )
         .left_join(
             "payments as receipts",
             &col("purchases.id").eq(&JsValue::from_str("receipts.order_id")),
+            None,
         )
         .where_(
             &col("receipts.id")
@@ -4231,10 +4940,12 @@ async fn complex_left_join_filters_then_group_sum_and_pagination_is_correct() {
         .left_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .left_join(
             "payments",
             &col("orders.id").eq(&JsValue::from_str("payments.order_id")),
+            None,
         )
         .where_(&col("orders.amount").gte(&JsValue::from_f64(50.0)))
         .where_(
@@ -4283,10 +4994,12 @@ async fn projection_query_with_or_not_between_null_and_not_like_is_correct() {
         .left_join(
             "orders as purchases",
             &col("customers.id").eq(&JsValue::from_str("purchases.customer_id")),
+            None,
         )
         .left_join(
             "payments as receipts",
             &col("purchases.id").eq(&JsValue::from_str("receipts.order_id")),
+            None,
         )
         .where_(&col("purchases.amount").is_not_null())
         .where_(
@@ -4326,10 +5039,12 @@ async fn union_all_of_grouped_join_queries_with_post_filter_order_and_limit_is_c
         .left_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .left_join(
             "payments",
             &col("orders.id").eq(&JsValue::from_str("payments.order_id")),
+            None,
         )
         .where_(
             &col("payments.id")
@@ -4344,10 +5059,12 @@ async fn union_all_of_grouped_join_queries_with_post_filter_order_and_limit_is_c
         .left_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .left_join(
             "payments",
             &col("orders.id").eq(&JsValue::from_str("payments.order_id")),
+            None,
         )
         .where_(&col("customers.name").like("A%"))
         .group_by(&js_str_array(&["customers.name"]))
@@ -4388,10 +5105,12 @@ async fn grouped_join_with_or_not_and_null_filters_orders_by_multiple_aggregates
         .left_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .left_join(
             "payments",
             &col("orders.id").eq(&JsValue::from_str("payments.order_id")),
+            None,
         )
         .where_(&col("orders.amount").is_not_null())
         .where_(
@@ -4440,10 +5159,12 @@ async fn projection_query_with_or_null_false_and_not_like_pagination_is_correct(
         .left_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .left_join(
             "payments",
             &col("orders.id").eq(&JsValue::from_str("payments.order_id")),
+            None,
         )
         .where_(&col("orders.amount").gte(&JsValue::from_f64(50.0)))
         .where_(
@@ -4684,6 +5405,7 @@ async fn prepared_join_query_exec_after_join_table_drop_returns_error() {
         .left_join(
             "orders",
             &col("customers.id").eq(&JsValue::from_str("orders.customer_id")),
+            None,
         )
         .prepare()
         .unwrap();
@@ -4813,3 +5535,191 @@ async fn grouped_aggregate_on_empty_table_returns_no_rows() {
 
     assert_select_matches(&query, &specs, &expected).await;
 }
+
+#[wasm_bindgen_test(async)]
+async fn get_row_bytes_views_stored_bytes() {
+    let db = Database::new("query_correctness_get_row_bytes");
+    register_rich_users_table(&db);
+    seed_rich_users(&db).await;
+
+    let blob: Vec<u8> = (0..64 * 1024).map(|i| (i % 251) as u8).collect();
+    db.update("users")
+        .set(&JsValue::from_str("avatar"), Some(js_bytes(&blob)))
+        .where_(&col("id").eq(&JsValue::from_f64(3.0)))
+        .exec()
+        .await
+        .unwrap();
+
+    let view = db
+        .get_row_bytes("users", &JsValue::from_f64(1.0), "avatar")
+        .unwrap()
+        .expect("Alice has an avatar");
+    assert_eq!(view.to_vec(), vec![1, 2, 3]);
+
+    let view = db
+        .get_row_bytes("users", &JsValue::from_f64(3.0), "avatar")
+        .unwrap()
+        .expect("Cara has an avatar");
+    assert_eq!(view.length() as usize, blob.len());
+    assert_eq!(view.to_vec(), blob);
+
+    assert!(db
+        .get_row_bytes("users", &JsValue::from_f64(2.0), "avatar")
+        .unwrap()
+        .is_none());
+    assert!(db
+        .get_row_bytes("users", &JsValue::from_f64(99.0), "avatar")
+        .unwrap()
+        .is_none());
+
+    let error = db
+        .get_row_bytes("users", &JsValue::from_f64(1.0), "name")
+        .unwrap_err();
+    assert_error_code(&error, "TYPE_MISMATCH");
+}
+
+#[wasm_bindgen_test(async)]
+async fn saved_filters_compose_and_apply_to_different_tables() {
+    let db = Database::new("query_correctness_saved_filters");
+    register_metrics_table(&db);
+    seed_metrics(&db).await;
+    let budgets = db
+        .create_table("budgets")
+        .column(
+            "id",
+            JsDataType::Int64,
+            Some(ColumnOptions::new().set_primary_key(true)),
+        )
+        .column("category", JsDataType::String, None)
+        .column("value", JsDataType::Int64, None);
+    db.register_table(&budgets).unwrap();
+    db.insert("budgets")
+        .values(&js_array([
+            js_object(&[
+                ("id", JsValue::from_f64(10.0)),
+                ("category", JsValue::from_str("A")),
+                ("value", JsValue::from_f64(1.0)),
+            ]),
+            js_object(&[
+                ("id", JsValue::from_f64(11.0)),
+                ("category", JsValue::from_str("B")),
+                ("value", JsValue::from_f64(9.0)),
+            ]),
+        ]))
+        .exec()
+        .await
+        .unwrap();
+
+    let in_a = col("category").eq(&JsValue::from_str("A"));
+    let large = col("value").gt(&JsValue::from_f64(3.0));
+    let both = in_a.and(&large);
+    let either = in_a.or(&large);
+    let specs = [
+        spec("id", CellKind::I64, false),
+        spec("category", CellKind::String, false),
+        spec("value", CellKind::I64, false),
+    ];
+    let row = |id: i64, category: &str, value: i64| {
+        vec![
+            Cell::I64(id),
+            Cell::String(category.into()),
+            Cell::I64(value),
+        ]
+    };
+    let query = |table: &str, filter: &Expr| {
+        db.select(&JsValue::from_str("*"))
+            .from(table)
+            .where_filter(filter)
+            .order_by("id", JsSortOrder::Asc)
+    };
+
+    assert_select_matches(&query("metrics", &both), &specs, &[row(2, "A", 8)]).await;
+    assert_select_matches(&query("budgets", &both), &specs, &[]).await;
+    assert_select_matches(
+        &query("metrics", &either),
+        &specs,
+        &[
+            row(1, "A", 2),
+            row(2, "A", 8),
+            row(3, "B", 4),
+            row(4, "B", 4),
+        ],
+    )
+    .await;
+    assert_select_matches(
+        &query("budgets", &either),
+        &specs,
+        &[row(10, "A", 1), row(11, "B", 9)],
+    )
+    .await;
+
+    // Saved filters stack with plain predicates and with each other.
+    let narrowed = query("budgets", &either)
+        .where_filter(&in_a.not())
+        .where_(&col("id").gt(&JsValue::from_f64(10.0)));
+    assert_select_matches(&narrowed, &specs, &[row(11, "B", 9)]).await;
+
+    assert_select_matches(&query("budgets", &Expr::always_false()), &specs, &[]).await;
+    assert_select_matches(
+        &query("budgets", &Expr::always_true().and(&large)),
+        &specs,
+        &[row(11, "B", 9)],
+    )
+    .await;
+}
+
+#[wasm_bindgen_test(async)]
+async fn exec_scalar_returns_lone_aggregate_value() {
+    let db = Database::new("query_correctness_exec_scalar");
+    register_metrics_table(&db);
+    seed_metrics(&db).await;
+
+    let count = db
+        .select(&JsValue::from_str("*"))
+        .from("metrics")
+        .count()
+        .exec_scalar()
+        .await
+        .unwrap();
+    assert_eq!(count.as_f64(), Some(4.0));
+
+    let sum = db
+        .select(&JsValue::from_str("*"))
+        .from("metrics")
+        .where_(&col("category").eq(&JsValue::from_str("B")))
+        .sum("value")
+        .exec_scalar()
+        .await
+        .unwrap();
+    assert_eq!(sum.as_f64(), Some(8.0));
+
+    let empty_avg = db
+        .select(&JsValue::from_str("*"))
+        .from("metrics")
+        .where_(&col("category").eq(&JsValue::from_str("Z")))
+        .avg("value")
+        .exec_scalar()
+        .await
+        .unwrap();
+    assert!(empty_avg.is_null());
+
+    // Several columns, several rows, or no aggregate at all are not scalar.
+    let not_scalar = [
+        db.select(&JsValue::from_str("*"))
+            .from("metrics")
+            .count()
+            .sum("value"),
+        db.select(&JsValue::from_str("*"))
+            .from("metrics")
+            .group_by(&js_str_array(&["category"]))
+            .count(),
+        db.select(&JsValue::from_str("*")).from("metrics"),
+        db.select(&JsValue::from_str("value"))
+            .from("metrics")
+            .max("value"),
+    ];
+    for query in &not_scalar {
+        let error = query.exec_scalar().await.unwrap_err();
+        assert_error_code(&error, "INVALID_OPERATION");
+    }
+}
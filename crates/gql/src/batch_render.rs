@@ -806,7 +806,11 @@ fn find_single_column_index_name<'a>(store: &'a RowStore, column_name: &str) ->
         .schema()
         .indices()
         .iter()
-        .find(|index| index.columns().len() == 1 && index.columns()[0].name == column_name)
+        .find(|index| {
+            !index.is_conditional()
+                && index.columns().len() == 1
+                && index.columns()[0].name == column_name
+        })
         .map(|index| index.name())
 }
 
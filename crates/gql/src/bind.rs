@@ -4,7 +4,8 @@ use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use core::str::FromStr;
 
-use cynos_core::{DataType, Value};
+use cynos_core::{DataType, Decimal, Value};
+use cynos_index::NullsOrder;
 use cynos_jsonb::{JsonbBinary, JsonbObject, JsonbValue};
 use hashbrown::HashSet;
 
@@ -83,6 +84,8 @@ pub struct BoundCollectionQuery {
 pub struct OrderSpec {
     pub column_index: usize,
     pub descending: bool,
+    /// Where NULLs go, whatever the direction.
+    pub nulls: NullsOrder,
 }
 
 #[derive(Clone, Debug)]
@@ -357,7 +360,7 @@ fn bind_operation(
         let kind = match root_field.kind {
             RootFieldKind::List => BoundRootFieldKind::Collection {
                 table_name: root_field.table_name.clone(),
-                query: bind_collection_arguments(field, table, variables)?,
+                query: bind_collection_arguments(field, table, catalog, variables)?,
                 selection: bind_required_selection_set(field, table, catalog, variables)?,
             },
             RootFieldKind::ByPk => BoundRootFieldKind::ByPk {
@@ -374,14 +377,20 @@ fn bind_operation(
                 let arguments = materialize_argument_map(field, variables)?;
                 BoundRootFieldKind::Update {
                     table_name: root_field.table_name.clone(),
-                    query: bind_collection_arguments_from_map(field, table, &arguments, &["set"])?,
+                    query: bind_collection_arguments_from_map(
+                        field,
+                        table,
+                        catalog,
+                        &arguments,
+                        &["set"],
+                    )?,
                     assignments: bind_assignments_from_map(field, table, &arguments)?,
                     selection: bind_required_selection_set(field, table, catalog, variables)?,
                 }
             }
             RootFieldKind::Delete => BoundRootFieldKind::Delete {
                 table_name: root_field.table_name.clone(),
-                query: bind_collection_arguments(field, table, variables)?,
+                query: bind_collection_arguments(field, table, catalog, variables)?,
                 selection: bind_required_selection_set(field, table, catalog, variables)?,
             },
         };
@@ -477,7 +486,7 @@ fn bind_selection_set(
                     )
                 })?;
                 let nested = bind_required_selection_set(field, target_table, catalog, variables)?;
-                let query = bind_collection_arguments(field, target_table, variables)?;
+                let query = bind_collection_arguments(field, target_table, catalog, variables)?;
                 fields.push(BoundField::ReverseRelation {
                     response_key: field.response_key().to_string(),
                     relation: relation.clone(),
@@ -493,15 +502,17 @@ fn bind_selection_set(
 fn bind_collection_arguments(
     field: &Field,
     table: &TableMeta,
+    catalog: &GraphqlCatalog,
     variables: &VariableValues,
 ) -> GqlResult<BoundCollectionQuery> {
     let arguments = materialize_argument_map(field, variables)?;
-    bind_collection_arguments_from_map(field, table, &arguments, &[])
+    bind_collection_arguments_from_map(field, table, catalog, &arguments, &[])
 }
 
 fn bind_collection_arguments_from_map(
     field: &Field,
     table: &TableMeta,
+    catalog: &GraphqlCatalog,
     arguments: &BTreeMap<String, InputValue>,
     extra_allowed: &[&str],
 ) -> GqlResult<BoundCollectionQuery> {
@@ -516,7 +527,7 @@ fn bind_collection_arguments_from_map(
         .flatten();
     let order_by = arguments
         .get("orderBy")
-        .map(|value| bind_order_by(value, table))
+        .map(|value| bind_order_by(value, table, catalog.default_nulls_order()))
         .transpose()?
         .unwrap_or_default();
     let limit = arguments
@@ -615,13 +626,16 @@ fn bind_insert_row(value: &InputValue, table: &TableMeta) -> GqlResult<BoundInse
     for column in table.columns() {
         let column_value = match find_object_field(fields, &column.name) {
             Some(value) => coerce_column_value(value, column)?,
-            None if column.nullable => Value::Null,
-            None => {
-                return Err(GqlError::new(
-                    GqlErrorKind::Validation,
-                    format!("missing required insert field `{}`", column.name),
-                ))
-            }
+            None => match &column.default {
+                Some(default) => default.clone(),
+                None if column.nullable => Value::Null,
+                None => {
+                    return Err(GqlError::new(
+                        GqlErrorKind::Validation,
+                        format!("missing required insert field `{}`", column.name),
+                    ))
+                }
+            },
         };
         values.push(column_value);
     }
@@ -1008,7 +1022,13 @@ fn bind_column_predicate_object(
     Ok(())
 }
 
-fn bind_order_by(value: &InputValue, table: &TableMeta) -> GqlResult<Vec<OrderSpec>> {
+/// Binds `orderBy` entries. NULLs go where `default_nulls` says, or else
+/// lowest: first ascending, last descending.
+fn bind_order_by(
+    value: &InputValue,
+    table: &TableMeta,
+    default_nulls: Option<NullsOrder>,
+) -> GqlResult<Vec<OrderSpec>> {
     let entries = match value {
         InputValue::List(values) => values.as_slice(),
         other => core::slice::from_ref(other),
@@ -1036,9 +1056,15 @@ fn bind_order_by(value: &InputValue, table: &TableMeta) -> GqlResult<Vec<OrderSp
                 ))
             }
         };
+        let natural = if descending {
+            NullsOrder::Last
+        } else {
+            NullsOrder::First
+        };
         specs.push(OrderSpec {
             column_index: column.index,
             descending,
+            nulls: default_nulls.unwrap_or(natural),
         });
     }
     Ok(specs)
@@ -1282,6 +1308,18 @@ fn coerce_value(value: &InputValue, data_type: DataType) -> GqlResult<Value> {
                 JsonbBinary::encode(&json).into_bytes(),
             )))
         }
+        DataType::Decimal => match value {
+            InputValue::Null => Ok(Value::Null),
+            InputValue::Int(value) => Ok(Value::Decimal((*value).into())),
+            InputValue::Float(value) => Decimal::from_f64(value.as_f64())
+                .map(Value::Decimal)
+                .ok_or_else(|| GqlError::new(GqlErrorKind::Validation, "invalid Decimal value")),
+            InputValue::String(value) => value
+                .parse()
+                .map(Value::Decimal)
+                .map_err(|_| GqlError::new(GqlErrorKind::Validation, "invalid Decimal value")),
+            _ => type_error(data_type),
+        },
     }
 }
 
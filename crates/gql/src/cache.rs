@@ -1,4 +1,5 @@
 use alloc::string::String;
+use cynos_index::NullsOrder;
 use cynos_storage::TableCache;
 
 use crate::catalog::GraphqlCatalog;
@@ -10,6 +11,8 @@ pub struct SchemaCache {
     catalog: Option<GraphqlCatalog>,
     schema: Option<GraphqlSchema>,
     sdl: Option<String>,
+    /// NULL placement given to the catalogs handed out; survives `clear`.
+    default_nulls: Option<NullsOrder>,
 }
 
 impl SchemaCache {
@@ -24,6 +27,11 @@ impl SchemaCache {
         self.sdl = None;
     }
 
+    /// Sets the `orderBy` NULL placement of catalogs returned from now on.
+    pub fn set_default_nulls_order(&mut self, nulls: Option<NullsOrder>) {
+        self.default_nulls = nulls;
+    }
+
     pub fn catalog(&mut self, epoch: u64, cache: &TableCache) -> GraphqlCatalog {
         if self.epoch != Some(epoch) || self.catalog.is_none() {
             self.epoch = Some(epoch);
@@ -35,6 +43,7 @@ impl SchemaCache {
         self.catalog
             .clone()
             .unwrap_or_else(|| GraphqlCatalog::from_table_cache(cache))
+            .with_default_nulls_order(self.default_nulls)
     }
 
     pub fn schema(&mut self, epoch: u64, cache: &TableCache) -> GraphqlSchema {
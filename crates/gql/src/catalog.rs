@@ -4,7 +4,8 @@ use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 
 use cynos_core::schema::{ForeignKey, Table};
-use cynos_core::DataType;
+use cynos_core::{DataType, Value};
+use cynos_index::NullsOrder;
 use cynos_storage::TableCache;
 use hashbrown::HashSet;
 
@@ -20,6 +21,8 @@ pub struct GraphqlCatalog {
     mutation_field_lookup: BTreeMap<String, usize>,
     subscription_fields: Vec<RootFieldMeta>,
     subscription_field_lookup: BTreeMap<String, usize>,
+    /// NULL placement for `orderBy` entries; `None` keeps NULLs lowest.
+    default_nulls: Option<NullsOrder>,
 }
 
 impl GraphqlCatalog {
@@ -116,9 +119,20 @@ impl GraphqlCatalog {
             query_fields,
             mutation_fields,
             subscription_fields,
+            default_nulls: None,
         }
     }
 
+    /// Sets the NULL placement applied to every `orderBy` entry.
+    pub fn with_default_nulls_order(mut self, nulls: Option<NullsOrder>) -> Self {
+        self.default_nulls = nulls;
+        self
+    }
+
+    pub fn default_nulls_order(&self) -> Option<NullsOrder> {
+        self.default_nulls
+    }
+
     pub fn tables(&self) -> &[TableMeta] {
         &self.tables
     }
@@ -221,6 +235,8 @@ pub struct ColumnMeta {
     pub index: usize,
     pub data_type: DataType,
     pub nullable: bool,
+    /// Value stored when an insert leaves the column out.
+    pub default: Option<Value>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -306,6 +322,7 @@ fn build_table_meta(
             index: column.index(),
             data_type: column.data_type(),
             nullable: column.is_nullable(),
+            default: column.declared_default().cloned(),
         };
         used_names.insert(column_meta.name.clone());
         column_lookup.insert(column_meta.name.clone(), columns.len());
@@ -382,6 +399,7 @@ fn build_table_meta(
                 index: column.index(),
                 data_type: column.data_type(),
                 nullable: column.is_nullable(),
+                default: column.declared_default().cloned(),
             })
             .collect(),
     });
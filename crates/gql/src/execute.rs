@@ -7,6 +7,7 @@ use core::cmp::Ordering;
 
 use cynos_core::pattern_match::like;
 use cynos_core::{reserve_row_ids, Row, Value};
+use cynos_index::NullsOrder;
 use cynos_jsonb::{JsonPath, JsonbBinary};
 use cynos_storage::{RowStore, TableCache};
 
@@ -618,7 +619,11 @@ fn find_single_column_index_name<'a>(store: &'a RowStore, column_name: &str) ->
         .schema()
         .indices()
         .iter()
-        .find(|index| index.columns().len() == 1 && index.columns()[0].name == column_name)
+        .find(|index| {
+            !index.is_conditional()
+                && index.columns().len() == 1
+                && index.columns()[0].name == column_name
+        })
         .map(|index| index.name())
 }
 
@@ -646,6 +651,14 @@ fn compare_rows(left: &Row, right: &Row, order_by: &[crate::bind::OrderSpec]) ->
     for spec in order_by {
         let left_value = left.get(spec.column_index).unwrap_or(&Value::Null);
         let right_value = right.get(spec.column_index).unwrap_or(&Value::Null);
+        // NULL placement does not depend on the direction.
+        let nulls = match spec.nulls {
+            NullsOrder::First => right_value.is_null().cmp(&left_value.is_null()),
+            NullsOrder::Last => left_value.is_null().cmp(&right_value.is_null()),
+        };
+        if nulls != Ordering::Equal {
+            return nulls;
+        }
         let ordering = left_value.cmp(right_value);
         if ordering != Ordering::Equal {
             return if spec.descending {
@@ -5,8 +5,8 @@ use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 
 use cynos_core::schema::IndexType;
-use cynos_core::{Row, Value};
-use cynos_index::KeyRange;
+use cynos_core::{Row, RowId, Value};
+use cynos_index::{KeyRange, NullsOrder};
 use cynos_jsonb::{JsonbBinary, JsonbValue};
 use cynos_query::ast::{Expr as AstExpr, SortOrder};
 use cynos_query::context::{ExecutionContext, IndexInfo, QueryIndexType, TableStats};
@@ -97,14 +97,22 @@ pub(crate) fn build_table_query_plan(
                     ),
                 )
             })?;
-            order_by.push((
-                AstExpr::column(table_name, &column.name, column.index),
-                if spec.descending {
-                    SortOrder::Desc
-                } else {
-                    SortOrder::Asc
-                },
-            ));
+            let key = AstExpr::column(table_name, &column.name, column.index);
+            let (order, natural) = if spec.descending {
+                (SortOrder::Desc, NullsOrder::Last)
+            } else {
+                (SortOrder::Asc, NullsOrder::First)
+            };
+            // NULL sorts lowest; other placements need a leading IS NULL key,
+            // which NOT NULL columns can skip.
+            if column.nullable && spec.nulls != natural {
+                let nulls_order = match spec.nulls {
+                    NullsOrder::First => SortOrder::Desc,
+                    NullsOrder::Last => SortOrder::Asc,
+                };
+                order_by.push((AstExpr::is_null(key.clone()), nulls_order));
+            }
+            order_by.push((key, order));
         }
         plan = LogicalPlan::Sort {
             input: Box::new(plan),
@@ -471,6 +479,14 @@ impl<'a> DataSource for TableCacheDataSource<'a> {
         Ok(store.len())
     }
 
+    fn fetch_rows_by_ids(&self, table: &str, ids: &[RowId]) -> ExecutionResult<Vec<Rc<Row>>> {
+        let store = self
+            .cache
+            .get_table(table)
+            .ok_or_else(|| ExecutionError::TableNotFound(table.into()))?;
+        Ok(ids.iter().filter_map(|id| store.get(*id)).collect())
+    }
+
     fn get_gin_index_rows(
         &self,
         table: &str,
@@ -518,6 +534,9 @@ fn build_execution_context_for_plan(
     plan: &LogicalPlan,
 ) -> ExecutionContext {
     let mut ctx = ExecutionContext::new();
+    if let Some(fraction) = cache.max_index_selectivity() {
+        ctx.set_max_index_selectivity(fraction);
+    }
     let mut tables = plan.collect_tables();
     if !tables.iter().any(|table| table == table_name) {
         tables.push(table_name.into());
@@ -535,7 +554,7 @@ fn register_table_context(cache: &TableCache, ctx: &mut ExecutionContext, table_
         let schema = store.schema();
 
         let mut indexes = Vec::new();
-        for idx in schema.indices() {
+        for idx in schema.indices().iter().filter(|idx| !idx.is_conditional()) {
             let index_type = match idx.get_index_type() {
                 IndexType::Hash => QueryIndexType::Hash,
                 IndexType::BTree => QueryIndexType::BTree,
@@ -549,6 +568,11 @@ fn register_table_context(cache: &TableCache, ctx: &mut ExecutionContext, table_
                 )
                 .with_type(index_type),
             );
+            if let [column] = idx.columns() {
+                if let Some(histogram) = store.index_histogram(idx.name()) {
+                    ctx.register_histogram(table_name, column.name.clone(), histogram);
+                }
+            }
         }
 
         ctx.register_table(
@@ -656,6 +680,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn default_nulls_order_keeps_not_null_keys_on_the_index() {
+        let cache = build_cache();
+        let catalog = GraphqlCatalog::from_table_cache(&cache)
+            .with_default_nulls_order(Some(NullsOrder::First));
+        let prepared = PreparedQuery::parse(
+            "{ users(orderBy: [{ field: ID, direction: DESC }], limit: 1) { id } }",
+        )
+        .unwrap();
+        let bound = prepared.bind(&catalog, None).unwrap();
+        let field = &bound.fields[0];
+
+        let plan = build_root_field_plan(&catalog, field).unwrap();
+        let logical = plan.logical_plan.clone();
+        let ctx = build_execution_context_for_plan(&cache, &plan.table_name, &logical);
+        let physical = QueryPlanner::new(ctx).plan(logical);
+        assert!(
+            matches!(physical, PhysicalPlan::IndexScan { reverse: true, .. }),
+            "expected a reverse IndexScan, got {physical:?}"
+        );
+    }
+
     #[test]
     fn root_by_pk_lowers_to_index_get_and_executes() {
         let (cache, catalog, field) =
@@ -353,6 +353,82 @@ mod tests {
         assert_eq!(int64(field(object_fields(&users[1]), "id")), 2);
     }
 
+    #[test]
+    fn order_by_follows_the_default_nulls_order() {
+        let mut cache = TableCache::new();
+        let people = TableBuilder::new("people")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("city", DataType::String)
+            .unwrap()
+            .add_nullable(&["city"])
+            .add_primary_key(&["id"], false)
+            .unwrap()
+            .build()
+            .unwrap();
+        cache.create_table(people).unwrap();
+        let store = cache.get_table_mut("people").unwrap();
+        for (id, city) in [(1, Some("Oslo")), (2, None), (3, Some("Bern"))] {
+            let city = city.map_or(Value::Null, |city| Value::String(city.into()));
+            store
+                .insert(Row::new(id as u64, alloc::vec![Value::Int64(id), city]))
+                .unwrap();
+        }
+
+        let ids = |catalog: &GraphqlCatalog| {
+            let response = execute_query(
+                &cache,
+                catalog,
+                "{ people(orderBy: [{ field: CITY, direction: DESC }]) { id } }",
+                None,
+                None,
+            )
+            .unwrap();
+            list_items(field(object_fields(&response.data), "people"))
+                .iter()
+                .map(|person| int64(field(object_fields(person), "id")))
+                .collect::<alloc::vec::Vec<_>>()
+        };
+        let catalog = GraphqlCatalog::from_table_cache(&cache);
+        assert_eq!(ids(&catalog), alloc::vec![1, 3, 2]);
+        let catalog = catalog.with_default_nulls_order(Some(cynos_index::NullsOrder::First));
+        assert_eq!(ids(&catalog), alloc::vec![2, 1, 3]);
+    }
+
+    #[test]
+    fn insert_fills_declared_defaults_for_omitted_fields() {
+        let mut cache = TableCache::new();
+        let tasks = TableBuilder::new("tasks")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column_with_default("status", DataType::String, Value::String("open".into()))
+            .unwrap()
+            .add_primary_key(&["id"], false)
+            .unwrap()
+            .build()
+            .unwrap();
+        cache.create_table(tasks).unwrap();
+        let catalog = GraphqlCatalog::from_table_cache(&cache);
+
+        let sdl = crate::schema::render_schema_sdl(&cache);
+        assert!(sdl.contains("status: String\n"));
+
+        let inserted = execute_operation(
+            &mut cache,
+            &catalog,
+            "mutation { insertTasks(input: [{ id: 1 }, { id: 2, status: \"done\" }]) { id status } }",
+            None,
+            None,
+        )
+        .unwrap();
+        let root = object_fields(&inserted.response.data);
+        let tasks = list_items(field(root, "insertTasks"));
+        assert_eq!(string(field(object_fields(&tasks[0]), "status")), "open");
+        assert_eq!(string(field(object_fields(&tasks[1]), "status")), "done");
+    }
+
     #[test]
     fn directives_prune_root_and_nested_fields_after_variable_resolution() {
         let cache = build_cache();
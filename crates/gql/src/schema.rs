@@ -110,6 +110,7 @@ enum ScalarFilterKind {
     DateTime,
     Bytes,
     Json,
+    Decimal,
 }
 
 impl GraphqlSchema {
@@ -132,6 +133,7 @@ impl GraphqlSchema {
         scalar_names.insert("DateTime".to_string());
         scalar_names.insert("Bytes".to_string());
         scalar_names.insert("JSON".to_string());
+        scalar_names.insert("Decimal".to_string());
 
         let mut objects = Vec::with_capacity(tables.len());
         let mut input_objects = Vec::new();
@@ -540,7 +542,11 @@ fn build_insert_input(table: &Table, type_names: &BTreeMap<String, String>) -> I
         .iter()
         .map(|column| InputValueDef {
             name: column.name().to_string(),
-            ty: graphql_type_for_column(column.data_type(), !column.is_nullable()),
+            // Columns with a declared default may be left out.
+            ty: graphql_type_for_column(
+                column.data_type(),
+                !column.is_nullable() && column.declared_default().is_none(),
+            ),
         })
         .collect();
     InputObjectTypeDef {
@@ -598,6 +604,7 @@ fn scalar_filter_definitions() -> Vec<InputObjectTypeDef> {
         scalar_filter_definition("DateTimeFilterInput", ScalarFilterKind::DateTime),
         scalar_filter_definition("BytesFilterInput", ScalarFilterKind::Bytes),
         scalar_filter_definition("JsonFilterInput", ScalarFilterKind::Json),
+        scalar_filter_definition("DecimalFilterInput", ScalarFilterKind::Decimal),
     ]
 }
 
@@ -611,6 +618,7 @@ fn scalar_filter_definition(name: &str, kind: ScalarFilterKind) -> InputObjectTy
         ScalarFilterKind::DateTime => "DateTime",
         ScalarFilterKind::Bytes => "Bytes",
         ScalarFilterKind::Json => "JSON",
+        ScalarFilterKind::Decimal => "Decimal",
     };
 
     let mut fields = vec![InputValueDef {
@@ -657,6 +665,7 @@ fn scalar_filter_definition(name: &str, kind: ScalarFilterKind) -> InputObjectTy
         ScalarFilterKind::Int
         | ScalarFilterKind::Long
         | ScalarFilterKind::Float
+        | ScalarFilterKind::Decimal
         | ScalarFilterKind::DateTime => {
             fields.extend(common_comparison_fields(scalar_name));
             fields.push(InputValueDef {
@@ -719,6 +728,7 @@ fn graphql_type_for_column(data_type: DataType, non_null: bool) -> TypeRef {
         DataType::DateTime => "DateTime",
         DataType::Bytes => "Bytes",
         DataType::Jsonb => "JSON",
+        DataType::Decimal => "Decimal",
     };
     TypeRef::named(type_name, non_null)
 }
@@ -733,6 +743,7 @@ fn scalar_filter_name(data_type: DataType) -> &'static str {
         DataType::DateTime => "DateTimeFilterInput",
         DataType::Bytes => "BytesFilterInput",
         DataType::Jsonb => "JsonFilterInput",
+        DataType::Decimal => "DecimalFilterInput",
     }
 }
 
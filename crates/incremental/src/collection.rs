@@ -150,6 +150,19 @@ where
     pub fn is_empty(&self) -> bool {
         self.data.values().all(|(_, diff)| *diff == 0)
     }
+
+    /// Returns the number of stored entries, including those whose diffs
+    /// have cancelled out.
+    pub fn entry_count(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Drops entries whose diffs have cancelled out and releases the
+    /// capacity they used.
+    pub fn consolidate(&mut self) {
+        self.data.retain(|_, (_, diff)| *diff != 0);
+        self.data.shrink_to_fit();
+    }
 }
 
 impl<K, V> ConsolidatedCollection<K, V>
@@ -269,4 +282,19 @@ mod tests {
         // Net diff should be 0, so it's effectively empty
         assert!(coll.is_empty());
     }
+
+    #[test]
+    fn test_consolidated_collection_consolidate() {
+        let mut coll: ConsolidatedCollection<i32, i32> = ConsolidatedCollection::new();
+        for key in 0..100 {
+            coll.apply(key, key, 1);
+            coll.apply(key, key, -1);
+        }
+        coll.apply(7, 7, 1);
+        assert_eq!(coll.entry_count(), 100);
+
+        coll.consolidate();
+        assert_eq!(coll.entry_count(), 1);
+        assert_eq!(coll.iter().collect::<Vec<_>>(), vec![(&7, &7, 1)]);
+    }
 }
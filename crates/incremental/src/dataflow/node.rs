@@ -26,6 +26,8 @@ pub type KeyExtractorFn = Box<dyn Fn(&Row) -> Vec<cynos_core::Value> + Send + Sy
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AggregateType {
     Count,
+    /// COUNT(DISTINCT column); NULL counts as a value.
+    CountDistinct,
     Sum,
     Avg,
     Min,
@@ -41,6 +43,13 @@ pub enum JoinType {
     FullOuter,
 }
 
+/// Sort direction for dataflow TopN nodes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
 /// A node in the dataflow graph.
 ///
 /// Each node represents an operation that can process incremental changes.
@@ -87,6 +96,17 @@ pub enum DataflowNode {
         group_by: Vec<ColumnId>,
         functions: Vec<(ColumnId, AggregateType)>,
     },
+
+    /// TopN operation - keeps the rows at positions `offset..offset + limit`
+    /// of the input ordered by `order_by`.
+    /// The full input is retained so that a row leaving the window can be
+    /// replaced by the next one in order.
+    TopN {
+        input: Box<DataflowNode>,
+        order_by: Vec<(ColumnId, SortOrder)>,
+        limit: usize,
+        offset: usize,
+    },
 }
 
 impl DataflowNode {
@@ -158,6 +178,21 @@ impl DataflowNode {
         }
     }
 
+    /// Creates a TopN node.
+    pub fn top_n(
+        input: DataflowNode,
+        order_by: Vec<(ColumnId, SortOrder)>,
+        limit: usize,
+        offset: usize,
+    ) -> Self {
+        DataflowNode::TopN {
+            input: Box::new(input),
+            order_by,
+            limit,
+            offset,
+        }
+    }
+
     /// Returns the table ID if this is a source node.
     pub fn source_table_id(&self) -> Option<TableId> {
         match self {
@@ -183,7 +218,8 @@ impl DataflowNode {
             DataflowNode::Filter { input, .. }
             | DataflowNode::Project { input, .. }
             | DataflowNode::Map { input, .. }
-            | DataflowNode::Aggregate { input, .. } => {
+            | DataflowNode::Aggregate { input, .. }
+            | DataflowNode::TopN { input, .. } => {
                 input.collect_sources_inner(sources);
             }
             DataflowNode::Join { left, right, .. } => {
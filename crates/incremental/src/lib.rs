@@ -17,7 +17,7 @@
 //! - `map_incremental`: Transforms deltas using a mapper function
 //! - `project_incremental`: Projects specific columns from row deltas
 //! - `IncrementalHashJoin`: Maintains join results incrementally
-//! - `IncrementalCount/Sum/Avg/Min/Max`: Incremental aggregate functions
+//! - `IncrementalCount/CountDistinct/Sum/Avg/Min/Max`: Incremental aggregate functions
 //!
 //! # Example
 //!
@@ -53,13 +53,15 @@ pub mod operators;
 
 pub use collection::{ConsolidatedCollection, DiffCollection};
 pub use dataflow::{
-    AggregateType, ColumnId, DataflowGraph, DataflowNode, JoinType, KeyExtractorFn, NodeId, TableId,
+    AggregateType, ColumnId, DataflowGraph, DataflowNode, JoinType, KeyExtractorFn, NodeId,
+    SortOrder, TableId,
 };
 pub use delta::{Delta, DeltaBatch, DeltaBatchExt};
 pub use materialize::{
     AggregateState, GroupAggregateState, JoinState, MaterializedView, MaterializedViewBuilder,
+    TopNState,
 };
 pub use operators::{
     filter_incremental, map_incremental, project_incremental, IncrementalAvg, IncrementalCount,
-    IncrementalHashJoin, IncrementalMax, IncrementalMin, IncrementalSum,
+    IncrementalCountDistinct, IncrementalHashJoin, IncrementalMax, IncrementalMin, IncrementalSum,
 };
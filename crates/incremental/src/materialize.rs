@@ -4,14 +4,16 @@
 //! (multisets with integer multiplicities). The materialized view maintains
 //! the current result and propagates deltas through the dataflow graph.
 
-use crate::dataflow::node::JoinType;
+use crate::dataflow::node::{JoinType, SortOrder};
 use crate::dataflow::{AggregateType, ColumnId, DataflowNode, TableId};
 use crate::delta::Delta;
 use crate::operators::{filter_incremental, map_incremental, project_incremental};
 use alloc::collections::BTreeMap;
 use alloc::vec::Vec;
-use cynos_core::{Row, RowId, Value};
-use hashbrown::HashMap;
+use core::cmp::Ordering;
+use cynos_core::{Decimal, Row, RowId, Value};
+use cynos_jsonb::JsonSortKey;
+use hashbrown::{HashMap, HashSet};
 
 // ---------------------------------------------------------------------------
 // JoinState — supports Inner, Left, Right, Full Outer joins via DBSP
@@ -297,6 +299,30 @@ impl JoinState {
         }
         output
     }
+
+    /// Drops empty buckets and releases capacity left behind by deletions.
+    fn compact(&mut self) {
+        self.left_index.retain(|_, rows| !rows.is_empty());
+        self.right_index.retain(|_, rows| !rows.is_empty());
+        for rows in self
+            .left_index
+            .values_mut()
+            .chain(self.right_index.values_mut())
+        {
+            rows.shrink_to_fit();
+        }
+        self.left_index.shrink_to_fit();
+        self.right_index.shrink_to_fit();
+        self.left_match_count.shrink_to_fit();
+        self.right_match_count.shrink_to_fit();
+    }
+
+    fn capacity(&self) -> usize {
+        self.left_index.capacity()
+            + self.right_index.capacity()
+            + self.left_match_count.capacity()
+            + self.right_match_count.capacity()
+    }
 }
 
 impl Default for JoinState {
@@ -336,17 +362,24 @@ fn merge_rows_null_left(right: &Row, left_col_count: usize) -> Row {
 /// - COUNT/SUM/AVG: maintain running totals, O(1) per delta
 /// - MIN/MAX: maintain ordered multiset (BTreeMap), O(log n) per delta
 ///   This eliminates the `needs_recompute` fallback entirely.
+/// - COUNT DISTINCT: the same multiset; its size is the distinct count
 pub enum AggregateState {
     Count {
         count: i64,
     },
+    /// BTreeMap<Value, multiplicity> — a value counts while its multiplicity is positive
+    CountDistinct {
+        values: BTreeMap<Value, i32>,
+    },
     Sum {
         sum: f64,
         count: i64,
+        exact: ExactSum,
     },
     Avg {
         sum: f64,
         count: i64,
+        exact: ExactSum,
     },
     /// BTreeMap<Value, multiplicity> — ordered multiset for O(log n) min on delete
     Min {
@@ -362,8 +395,19 @@ impl AggregateState {
     pub fn new(agg_type: AggregateType) -> Self {
         match agg_type {
             AggregateType::Count => AggregateState::Count { count: 0 },
-            AggregateType::Sum => AggregateState::Sum { sum: 0.0, count: 0 },
-            AggregateType::Avg => AggregateState::Avg { sum: 0.0, count: 0 },
+            AggregateType::CountDistinct => AggregateState::CountDistinct {
+                values: BTreeMap::new(),
+            },
+            AggregateType::Sum => AggregateState::Sum {
+                sum: 0.0,
+                count: 0,
+                exact: ExactSum::new(),
+            },
+            AggregateType::Avg => AggregateState::Avg {
+                sum: 0.0,
+                count: 0,
+                exact: ExactSum::new(),
+            },
             AggregateType::Min => AggregateState::Min {
                 values: BTreeMap::new(),
             },
@@ -379,15 +423,15 @@ impl AggregateState {
             AggregateState::Count { count } => {
                 *count += diff as i64;
             }
-            AggregateState::Sum { sum, count } => {
-                *sum += extract_numeric(value) * diff as f64;
-                *count += diff as i64;
-            }
-            AggregateState::Avg { sum, count } => {
+            AggregateState::Sum { sum, count, exact }
+            | AggregateState::Avg { sum, count, exact } => {
                 *sum += extract_numeric(value) * diff as f64;
                 *count += diff as i64;
+                exact.apply(value, diff);
             }
-            AggregateState::Min { values } | AggregateState::Max { values } => {
+            AggregateState::CountDistinct { values }
+            | AggregateState::Min { values }
+            | AggregateState::Max { values } => {
                 let entry = values.entry(value.clone()).or_insert(0);
                 *entry += diff;
                 if *entry <= 0 {
@@ -401,12 +445,21 @@ impl AggregateState {
     pub fn get_value(&self) -> Value {
         match self {
             AggregateState::Count { count } => Value::Int64(*count),
-            AggregateState::Sum { sum, .. } => Value::Float64(*sum),
-            AggregateState::Avg { sum, count } => {
+            AggregateState::CountDistinct { values } => Value::Int64(values.len() as i64),
+            AggregateState::Sum { sum, exact, .. } => match exact.decimal() {
+                Some(exact) => exact.map_or(Value::Null, Value::Decimal),
+                None => Value::Float64(*sum),
+            },
+            AggregateState::Avg { sum, count, exact } => {
                 if *count == 0 {
                     Value::Null
                 } else {
-                    Value::Float64(*sum / *count as f64)
+                    match exact.decimal() {
+                        Some(exact) => exact
+                            .and_then(|exact| exact.checked_div(Decimal::from(*count)))
+                            .map_or(Value::Null, Value::Decimal),
+                        None => Value::Float64(*sum / *count as f64),
+                    }
                 }
             }
             AggregateState::Min { values } => values.keys().next().cloned().unwrap_or(Value::Null),
@@ -422,7 +475,9 @@ impl AggregateState {
             AggregateState::Count { count } => *count == 0,
             AggregateState::Avg { count, .. } => *count == 0,
             AggregateState::Sum { count, .. } => *count == 0,
-            AggregateState::Min { values } | AggregateState::Max { values } => values.is_empty(),
+            AggregateState::CountDistinct { values }
+            | AggregateState::Min { values }
+            | AggregateState::Max { values } => values.is_empty(),
         }
     }
 }
@@ -454,6 +509,16 @@ impl GroupAggregateState {
         }
     }
 
+    /// Releases capacity left behind by groups that emptied out.
+    fn compact(&mut self) {
+        self.groups.shrink_to_fit();
+        self.last_row_ids.shrink_to_fit();
+    }
+
+    fn capacity(&self) -> usize {
+        self.groups.capacity() + self.last_row_ids.capacity()
+    }
+
     /// Process a batch of input deltas and produce output deltas.
     /// For each affected group:
     ///   1. Emit delete(old_aggregate_row) if group existed
@@ -543,10 +608,220 @@ fn extract_numeric(value: &Value) -> f64 {
         Value::Int32(v) => *v as f64,
         Value::Int64(v) => *v as f64,
         Value::Float64(v) => *v,
+        Value::Decimal(v) => v.to_f64(),
         _ => 0.0,
     }
 }
 
+/// Exact running sum of the integer and decimal inputs of a SUM or AVG, so
+/// that a Decimal column aggregates without rounding through f64, as in the
+/// batch executor.
+pub struct ExactSum {
+    /// `None` once the sum overflowed.
+    sum: Option<Decimal>,
+    /// Live Decimal inputs.
+    decimals: i64,
+    /// Live Float64 inputs; while there are any the result is a float.
+    floats: i64,
+}
+
+impl ExactSum {
+    fn new() -> Self {
+        Self {
+            sum: Some(Decimal::ZERO),
+            decimals: 0,
+            floats: 0,
+        }
+    }
+
+    fn apply(&mut self, value: &Value, diff: i32) {
+        let value = match value {
+            Value::Int32(v) => Decimal::from(*v),
+            Value::Int64(v) => Decimal::from(*v),
+            Value::Decimal(v) => {
+                self.decimals += diff as i64;
+                *v
+            }
+            Value::Float64(_) => {
+                self.floats += diff as i64;
+                return;
+            }
+            _ => return,
+        };
+        self.sum = self.sum.and_then(|sum| {
+            let delta = value.checked_mul(Decimal::from(diff))?;
+            sum.checked_add(delta)
+        });
+    }
+
+    /// Returns the exact sum when the inputs call for a Decimal result:
+    /// decimals and no floats. The inner `None` reports an overflow.
+    fn decimal(&self) -> Option<Option<Decimal>> {
+        (self.decimals > 0 && self.floats == 0).then_some(self.sum)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// TopNState — bounded ordered window over the full input
+// ---------------------------------------------------------------------------
+
+/// State for an incremental TopN (ORDER BY ... LIMIT ... OFFSET ...).
+///
+/// Keeps every input row sorted by the ORDER BY columns (ties broken by row
+/// ID) so that when a row leaves the window the next row in order can take
+/// its place. Each batch of input deltas produces the difference between the
+/// window before and after the batch.
+pub struct TopNState {
+    order_by: Vec<(ColumnId, SortOrder)>,
+    limit: usize,
+    offset: usize,
+    /// All input rows, sorted by `compare_top_n_rows`.
+    rows: Vec<TopNRow>,
+}
+
+/// An input row with its JSONB sort keys decoded, so that keeping `rows`
+/// sorted never re-parses a stored document.
+struct TopNRow {
+    row: Row,
+    /// Slot `i` holds the document of ORDER BY key `i`, if it is JSONB.
+    json: Vec<Option<JsonSortKey>>,
+}
+
+impl TopNRow {
+    fn new(order_by: &[(ColumnId, SortOrder)], row: Row) -> Self {
+        let mut json = Vec::new();
+        for (i, (col, _)) in order_by.iter().enumerate() {
+            if let Some(Value::Jsonb(doc)) = row.get(*col) {
+                json.resize_with(i, || None);
+                json.push(Some(JsonSortKey::decode(&doc.0)));
+            }
+        }
+        Self { row, json }
+    }
+
+    fn json_key(&self, key: usize) -> Option<&JsonSortKey> {
+        self.json.get(key).and_then(Option::as_ref)
+    }
+}
+
+impl TopNState {
+    pub fn new(order_by: Vec<(ColumnId, SortOrder)>, limit: usize, offset: usize) -> Self {
+        Self {
+            order_by,
+            limit,
+            offset,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Replaces the tracked input rows.
+    pub fn set_input(&mut self, rows: Vec<Row>) {
+        let order_by = &self.order_by;
+        self.rows = rows
+            .into_iter()
+            .map(|row| TopNRow::new(order_by, row))
+            .collect();
+        self.rows.sort_by(|a, b| compare_top_n_rows(order_by, a, b));
+    }
+
+    /// Returns the rows currently inside the window, in order.
+    pub fn window(&self) -> impl Iterator<Item = &Row> + '_ {
+        let start = self.offset.min(self.rows.len());
+        let end = start.saturating_add(self.limit).min(self.rows.len());
+        self.rows[start..end].iter().map(|entry| &entry.row)
+    }
+
+    /// Returns the number of input rows tracked, inside or outside the window.
+    pub fn input_len(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn compact(&mut self) {
+        self.rows.shrink_to_fit();
+    }
+
+    fn capacity(&self) -> usize {
+        self.rows.capacity()
+    }
+
+    /// Applies a batch of input deltas and returns the window changes.
+    pub fn process_deltas(&mut self, deltas: Vec<Delta<Row>>) -> Vec<Delta<Row>> {
+        let before: HashMap<RowId, Row> =
+            self.window().map(|row| (row.id(), row.clone())).collect();
+
+        for delta in deltas {
+            let is_insert = delta.is_insert();
+            if !is_insert && !delta.is_delete() {
+                continue;
+            }
+            let entry = TopNRow::new(&self.order_by, delta.data);
+            if is_insert {
+                let position = self.position(&entry).unwrap_or_else(|p| p);
+                self.rows.insert(position, entry);
+            } else if let Ok(position) = self.position(&entry) {
+                self.rows.remove(position);
+            } else {
+                let id = entry.row.id();
+                if let Some(position) = self.rows.iter().position(|probe| probe.row.id() == id) {
+                    // The deleted row's values no longer match the stored
+                    // version; fall back to locating it by ID.
+                    self.rows.remove(position);
+                }
+            }
+        }
+
+        // Deletes go first so that a row whose values changed inside the
+        // window is removed and re-added, not the other way round.
+        let after = self.window();
+        let mut output = Vec::new();
+        let mut changed = Vec::new();
+        let mut retained = HashSet::new();
+        for row in after {
+            match before.get(&row.id()) {
+                Some(old) if old.values() == row.values() => {
+                    retained.insert(row.id());
+                }
+                _ => changed.push(Delta::insert(row.clone())),
+            }
+        }
+        for (id, row) in before {
+            if !retained.contains(&id) {
+                output.push(Delta::delete(row));
+            }
+        }
+        output.extend(changed);
+        output
+    }
+
+    fn position(&self, entry: &TopNRow) -> Result<usize, usize> {
+        self.rows
+            .binary_search_by(|probe| compare_top_n_rows(&self.order_by, probe, entry))
+    }
+}
+
+/// Orders rows by the TopN sort columns, breaking ties by row ID so every row
+/// has a stable position. JSONB keys compare by their decoded content.
+fn compare_top_n_rows(order_by: &[(ColumnId, SortOrder)], a: &TopNRow, b: &TopNRow) -> Ordering {
+    for (i, (col, order)) in order_by.iter().enumerate() {
+        let cmp = match (a.json_key(i), b.json_key(i)) {
+            (Some(av), Some(bv)) => av.cmp(bv),
+            _ => match (a.row.get(*col), b.row.get(*col)) {
+                (Some(av), Some(bv)) => av.cmp(bv),
+                (None, Some(_)) => Ordering::Less,
+                (Some(_), None) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+        };
+        if cmp != Ordering::Equal {
+            return match order {
+                SortOrder::Asc => cmp,
+                SortOrder::Desc => cmp.reverse(),
+            };
+        }
+    }
+    a.row.id().cmp(&b.row.id())
+}
+
 // ---------------------------------------------------------------------------
 // MaterializedView — the core DBSP dataflow executor
 // ---------------------------------------------------------------------------
@@ -558,6 +833,10 @@ pub struct MaterializedView {
     dependencies: Vec<TableId>,
     join_states: HashMap<usize, JoinState>,
     aggregate_states: HashMap<usize, GroupAggregateState>,
+    top_n_states: HashMap<usize, TopNState>,
+    /// Batches after which `consolidate` runs automatically, if set.
+    consolidate_interval: Option<usize>,
+    batches_since_consolidate: usize,
 }
 
 impl MaterializedView {
@@ -569,6 +848,9 @@ impl MaterializedView {
             dependencies,
             join_states: HashMap::new(),
             aggregate_states: HashMap::new(),
+            top_n_states: HashMap::new(),
+            consolidate_interval: None,
+            batches_since_consolidate: 0,
         }
     }
 
@@ -584,6 +866,9 @@ impl MaterializedView {
             dependencies,
             join_states: HashMap::new(),
             aggregate_states: HashMap::new(),
+            top_n_states: HashMap::new(),
+            consolidate_interval: None,
+            batches_since_consolidate: 0,
         }
     }
 
@@ -613,8 +898,41 @@ impl MaterializedView {
         }
     }
 
+    /// Initializes the state of the outermost TopN node from its full input.
+    ///
+    /// A TopN view must know the rows outside its window to backfill it when
+    /// a row inside is deleted, so this has to be called for TopN dataflows
+    /// created with `with_initial`. Has no effect if the dataflow has no
+    /// TopN node.
+    pub fn initialize_top_n_state(&mut self, input_rows: Vec<Row>) {
+        let Some(DataflowNode::TopN {
+            order_by,
+            limit,
+            offset,
+            ..
+        }) = outermost_top_n(&self.dataflow)
+        else {
+            return;
+        };
+        let mut state = TopNState::new(order_by.clone(), *limit, *offset);
+        state.set_input(input_rows);
+        self.top_n_states.insert(0, state);
+    }
+
+    /// Returns the current result.
+    ///
+    /// Rows are in window order if the dataflow ends in a TopN (optionally
+    /// followed by projections); otherwise the order is unspecified.
     #[inline]
     pub fn result(&self) -> Vec<Row> {
+        if let Some(state) = self.top_n_states.get(&0) {
+            if outermost_top_n(&self.dataflow).is_some() {
+                return state
+                    .window()
+                    .filter_map(|row| self.result_map.get(&row.id()).cloned())
+                    .collect();
+            }
+        }
         self.result_map.values().cloned().collect()
     }
 
@@ -653,10 +971,12 @@ impl MaterializedView {
             &self.dataflow,
             &mut self.join_states,
             &mut self.aggregate_states,
+            &mut self.top_n_states,
             table_id,
             deltas,
             0,
             0,
+            0,
         )
         .0;
 
@@ -669,13 +989,91 @@ impl MaterializedView {
             }
         }
 
+        if let Some(interval) = self.consolidate_interval {
+            self.batches_since_consolidate += 1;
+            if self.batches_since_consolidate >= interval {
+                self.consolidate();
+            }
+        }
+
         output_deltas
     }
 
+    /// Compacts the view's result and operator state.
+    ///
+    /// Deltas are applied as they arrive, so rows that were inserted and then
+    /// deleted leave nothing behind but the map capacity they grew. This
+    /// drops empty join buckets and shrinks every map and buffer to its live
+    /// contents, so a view that saw heavy churn goes back to the footprint of
+    /// its current state. The result is unchanged.
+    pub fn consolidate(&mut self) {
+        self.result_map.shrink_to_fit();
+        for state in self.join_states.values_mut() {
+            state.compact();
+        }
+        for state in self.aggregate_states.values_mut() {
+            state.compact();
+        }
+        for state in self.top_n_states.values_mut() {
+            state.compact();
+        }
+        self.batches_since_consolidate = 0;
+    }
+
+    /// Runs `consolidate` automatically after every `batches` calls to
+    /// `on_table_change`, or never with `None` (the default).
+    pub fn set_consolidate_interval(&mut self, batches: Option<usize>) {
+        self.consolidate_interval = batches.filter(|&batches| batches > 0);
+        self.batches_since_consolidate = 0;
+    }
+
+    /// Returns the number of entry slots allocated for the result and for
+    /// join, aggregate and TopN state, live or not.
+    pub fn state_capacity(&self) -> usize {
+        self.result_map.capacity()
+            + self
+                .join_states
+                .values()
+                .map(JoinState::capacity)
+                .sum::<usize>()
+            + self
+                .aggregate_states
+                .values()
+                .map(GroupAggregateState::capacity)
+                .sum::<usize>()
+            + self
+                .top_n_states
+                .values()
+                .map(TopNState::capacity)
+                .sum::<usize>()
+    }
+
     pub fn clear(&mut self) {
         self.result_map.clear();
     }
 
+    /// Discards all incremental state and rebuilds the view from the full
+    /// contents of its source tables.
+    ///
+    /// Every row in `sources` is replayed as an insert, which re-derives join
+    /// hash tables, aggregate accumulators and TopN windows exactly as a new
+    /// view fed the same rows would. Use this to recover a view suspected of
+    /// diverging from its sources. Tables the view does not depend on are
+    /// ignored; a dependency missing from `sources` is treated as empty.
+    pub fn rebuild_from(&mut self, sources: impl IntoIterator<Item = (TableId, Vec<Row>)>) {
+        self.result_map.clear();
+        self.join_states.clear();
+        self.aggregate_states.clear();
+        self.top_n_states.clear();
+
+        for (table_id, rows) in sources {
+            if rows.is_empty() {
+                continue;
+            }
+            self.on_table_change(table_id, rows.into_iter().map(Delta::insert).collect());
+        }
+    }
+
     pub fn set_result(&mut self, rows: Vec<Row>) {
         self.result_map.clear();
         for row in rows {
@@ -684,68 +1082,95 @@ impl MaterializedView {
     }
 }
 
+/// Returns the outermost TopN node, looking through the projections and maps
+/// that may sit on top of it.
+fn outermost_top_n(node: &DataflowNode) -> Option<&DataflowNode> {
+    match node {
+        DataflowNode::TopN { .. } => Some(node),
+        DataflowNode::Project { input, .. } | DataflowNode::Map { input, .. } => {
+            outermost_top_n(input)
+        }
+        _ => None,
+    }
+}
+
 /// Propagates deltas through a dataflow node.
 /// This is a free function to allow split borrows: immutable dataflow + mutable states.
-/// Returns (output_deltas, next_join_id, next_agg_id).
+/// Returns (output_deltas, next_join_id, next_agg_id, next_top_n_id).
+#[allow(clippy::too_many_arguments)]
 fn propagate_deltas(
     node: &DataflowNode,
     join_states: &mut HashMap<usize, JoinState>,
     aggregate_states: &mut HashMap<usize, GroupAggregateState>,
+    top_n_states: &mut HashMap<usize, TopNState>,
     source_table: TableId,
     deltas: Vec<Delta<Row>>,
     join_id: usize,
     agg_id: usize,
-) -> (Vec<Delta<Row>>, usize, usize) {
+    top_n_id: usize,
+) -> (Vec<Delta<Row>>, usize, usize, usize) {
     match node {
         DataflowNode::Source { table_id } => {
             if *table_id == source_table {
-                (deltas, join_id, agg_id)
+                (deltas, join_id, agg_id, top_n_id)
             } else {
-                (Vec::new(), join_id, agg_id)
+                (Vec::new(), join_id, agg_id, top_n_id)
             }
         }
 
         DataflowNode::Filter { input, predicate } => {
-            let (input_deltas, jid, aid) = propagate_deltas(
+            let (input_deltas, jid, aid, tid) = propagate_deltas(
                 input,
                 join_states,
                 aggregate_states,
+                top_n_states,
                 source_table,
                 deltas,
                 join_id,
                 agg_id,
+                top_n_id,
             );
             (
                 filter_incremental(&input_deltas, |row| predicate(row)),
                 jid,
                 aid,
+                tid,
             )
         }
 
         DataflowNode::Project { input, columns } => {
-            let (input_deltas, jid, aid) = propagate_deltas(
+            let (input_deltas, jid, aid, tid) = propagate_deltas(
                 input,
                 join_states,
                 aggregate_states,
+                top_n_states,
                 source_table,
                 deltas,
                 join_id,
                 agg_id,
+                top_n_id,
             );
-            (project_incremental(&input_deltas, columns), jid, aid)
+            (project_incremental(&input_deltas, columns), jid, aid, tid)
         }
 
         DataflowNode::Map { input, mapper } => {
-            let (input_deltas, jid, aid) = propagate_deltas(
+            let (input_deltas, jid, aid, tid) = propagate_deltas(
                 input,
                 join_states,
                 aggregate_states,
+                top_n_states,
                 source_table,
                 deltas,
                 join_id,
                 agg_id,
+                top_n_id,
             );
-            (map_incremental(&input_deltas, |row| mapper(row)), jid, aid)
+            (
+                map_incremental(&input_deltas, |row| mapper(row)),
+                jid,
+                aid,
+                tid,
+            )
         }
 
         DataflowNode::Join {
@@ -769,14 +1194,16 @@ fn propagate_deltas(
             let mut output_deltas = Vec::new();
 
             if is_left_side {
-                let (left_deltas, _, _) = propagate_deltas(
+                let (left_deltas, _, _, _) = propagate_deltas(
                     left,
                     join_states,
                     aggregate_states,
+                    top_n_states,
                     source_table,
                     deltas.clone(),
                     current_join_id + 1,
                     agg_id,
+                    top_n_id,
                 );
 
                 let join_state = join_states.get_mut(&current_join_id).unwrap();
@@ -802,14 +1229,16 @@ fn propagate_deltas(
             }
 
             if is_right_side {
-                let (right_deltas, _, _) = propagate_deltas(
+                let (right_deltas, _, _, _) = propagate_deltas(
                     right,
                     join_states,
                     aggregate_states,
+                    top_n_states,
                     source_table,
                     deltas,
                     current_join_id + 1,
                     agg_id,
+                    top_n_id,
                 );
 
                 let join_state = join_states.get_mut(&current_join_id).unwrap();
@@ -837,7 +1266,7 @@ fn propagate_deltas(
                 }
             }
 
-            (output_deltas, current_join_id + 1, agg_id)
+            (output_deltas, current_join_id + 1, agg_id, top_n_id)
         }
 
         DataflowNode::Aggregate {
@@ -846,18 +1275,20 @@ fn propagate_deltas(
             functions,
         } => {
             let current_agg_id = agg_id;
-            let (input_deltas, jid, _) = propagate_deltas(
+            let (input_deltas, jid, _, tid) = propagate_deltas(
                 input,
                 join_states,
                 aggregate_states,
+                top_n_states,
                 source_table,
                 deltas,
                 join_id,
                 current_agg_id + 1,
+                top_n_id,
             );
 
             if input_deltas.is_empty() {
-                return (Vec::new(), jid, current_agg_id + 1);
+                return (Vec::new(), jid, current_agg_id + 1, tid);
             }
 
             // Get or create aggregate state
@@ -871,7 +1302,38 @@ fn propagate_deltas(
             let agg_state = aggregate_states.get_mut(&current_agg_id).unwrap();
             let output = agg_state.process_deltas(&input_deltas);
 
-            (output, jid, current_agg_id + 1)
+            (output, jid, current_agg_id + 1, tid)
+        }
+
+        DataflowNode::TopN {
+            input,
+            order_by,
+            limit,
+            offset,
+        } => {
+            let current_top_n_id = top_n_id;
+            let (input_deltas, jid, aid, _) = propagate_deltas(
+                input,
+                join_states,
+                aggregate_states,
+                top_n_states,
+                source_table,
+                deltas,
+                join_id,
+                agg_id,
+                current_top_n_id + 1,
+            );
+
+            if input_deltas.is_empty() {
+                return (Vec::new(), jid, aid, current_top_n_id + 1);
+            }
+
+            let state = top_n_states
+                .entry(current_top_n_id)
+                .or_insert_with(|| TopNState::new(order_by.clone(), *limit, *offset));
+            let output = state.process_deltas(input_deltas);
+
+            (output, jid, aid, current_top_n_id + 1)
         }
     }
 }
@@ -880,6 +1342,7 @@ fn propagate_deltas(
 pub struct MaterializedViewBuilder {
     dataflow: Option<DataflowNode>,
     initial: Vec<Row>,
+    consolidate_interval: Option<usize>,
 }
 
 impl Default for MaterializedViewBuilder {
@@ -893,6 +1356,7 @@ impl MaterializedViewBuilder {
         Self {
             dataflow: None,
             initial: Vec::new(),
+            consolidate_interval: None,
         }
     }
 
@@ -906,13 +1370,21 @@ impl MaterializedViewBuilder {
         self
     }
 
+    /// See [`MaterializedView::set_consolidate_interval`].
+    pub fn consolidate_every(mut self, batches: usize) -> Self {
+        self.consolidate_interval = Some(batches);
+        self
+    }
+
     pub fn build(self) -> Option<MaterializedView> {
         self.dataflow.map(|df| {
-            if self.initial.is_empty() {
+            let mut view = if self.initial.is_empty() {
                 MaterializedView::new(df)
             } else {
                 MaterializedView::with_initial(df, self.initial)
-            }
+            };
+            view.set_consolidate_interval(self.consolidate_interval);
+            view
         })
     }
 }
@@ -987,6 +1459,60 @@ mod tests {
         assert!(view.is_empty());
     }
 
+    #[test]
+    fn test_consolidate_releases_churned_state() {
+        let dataflow = DataflowNode::Join {
+            left: Box::new(DataflowNode::source(1)),
+            right: Box::new(DataflowNode::source(2)),
+            left_key: Box::new(|row| vec![row.get(2).cloned().unwrap_or(Value::Null)]),
+            right_key: Box::new(|row| vec![row.get(0).cloned().unwrap_or(Value::Null)]),
+            join_type: JoinType::Inner,
+        };
+        let mut view = MaterializedView::new(dataflow);
+        view.on_table_change(2, vec![Delta::insert(make_department(10, 100))]);
+        let baseline = view.state_capacity();
+
+        // Many small batches whose inserts are all deleted again.
+        for id in 1..=500 {
+            view.on_table_change(1, vec![Delta::insert(make_employee(id, 0, 10))]);
+        }
+        for id in 1..=500 {
+            view.on_table_change(1, vec![Delta::delete(make_employee(id, 0, 10))]);
+        }
+        view.on_table_change(1, vec![Delta::insert(make_employee(1000, 7, 10))]);
+        assert!(view.state_capacity() > 500);
+
+        view.consolidate();
+        assert!(view.state_capacity() <= baseline + 8);
+        let result = view.result();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].get(1), Some(&Value::Int64(7)));
+
+        // The view keeps working after consolidation.
+        view.on_table_change(1, vec![Delta::delete(make_employee(1000, 7, 10))]);
+        assert!(view.is_empty());
+    }
+
+    #[test]
+    fn test_consolidate_interval_triggers_automatically() {
+        let mut view = MaterializedViewBuilder::new()
+            .dataflow(DataflowNode::source(1))
+            .consolidate_every(100)
+            .build()
+            .unwrap();
+
+        for id in 1..=1000 {
+            view.on_table_change(1, vec![Delta::insert(make_row(id, 20))]);
+        }
+        for id in 1..=999 {
+            view.on_table_change(1, vec![Delta::delete(make_row(id, 20))]);
+        }
+        // The last automatic run (batch 1900) saw 100 live rows; without it
+        // the result map would still be sized for 1000.
+        assert!(view.state_capacity() < 1000);
+        assert_eq!(view.len(), 1);
+    }
+
     fn make_employee(id: u64, name_hash: i64, dept_id: i64) -> Row {
         Row::new(
             id,
@@ -1075,6 +1601,77 @@ mod tests {
         assert_eq!(inserts[0].data.get(3), Some(&Value::Null));
     }
 
+    #[test]
+    fn test_rebuild_from_matches_fresh_view() {
+        // SUM(name_hash) of employees per department name, via an inner join.
+        let dataflow = || DataflowNode::Aggregate {
+            input: Box::new(DataflowNode::Join {
+                left: Box::new(DataflowNode::source(1)),
+                right: Box::new(DataflowNode::source(2)),
+                left_key: Box::new(|row| vec![row.get(2).cloned().unwrap_or(Value::Null)]),
+                right_key: Box::new(|row| vec![row.get(0).cloned().unwrap_or(Value::Null)]),
+                join_type: JoinType::Inner,
+            }),
+            group_by: vec![4],
+            functions: vec![(1, AggregateType::Sum), (0, AggregateType::Count)],
+        };
+        let employees = vec![
+            make_employee(1, 10, 100),
+            make_employee(2, 20, 100),
+            make_employee(3, 30, 200),
+        ];
+        let departments = vec![make_department(100, 7), make_department(200, 8)];
+        let sources = || {
+            vec![
+                (1 as TableId, employees.clone()),
+                (2 as TableId, departments.clone()),
+            ]
+        };
+        let sorted = |view: &MaterializedView| {
+            let mut rows: Vec<Vec<Value>> = view
+                .result()
+                .iter()
+                .map(|row| row.values().to_vec())
+                .collect();
+            rows.sort();
+            rows
+        };
+
+        let mut fresh = MaterializedView::new(dataflow());
+        for (table_id, rows) in sources() {
+            fresh.on_table_change(table_id, rows.into_iter().map(Delta::insert).collect());
+        }
+
+        // A view whose state has drifted: it missed a department insert and
+        // its result was overwritten.
+        let mut drifted = MaterializedView::new(dataflow());
+        drifted.on_table_change(1, employees.iter().cloned().map(Delta::insert).collect());
+        drifted.on_table_change(2, vec![Delta::insert(make_department(100, 7))]);
+        drifted.set_result(vec![Row::new(99, vec![Value::Int64(-1)])]);
+        assert_ne!(sorted(&drifted), sorted(&fresh));
+
+        drifted.rebuild_from(sources());
+        assert_eq!(sorted(&drifted), sorted(&fresh));
+        assert_eq!(drifted.len(), 2);
+
+        // Later deltas on either side keep both views in step.
+        let changes = vec![
+            (1, Delta::insert(make_employee(4, 40, 200))),
+            (2, Delta::delete(make_department(100, 7))),
+            (1, Delta::delete(make_employee(3, 30, 200))),
+        ];
+        for (table_id, delta) in changes {
+            let expected = fresh.on_table_change(table_id, vec![delta.clone()]);
+            let actual = drifted.on_table_change(table_id, vec![delta]);
+            assert_eq!(actual.len(), expected.len());
+            assert_eq!(sorted(&drifted), sorted(&fresh));
+        }
+        assert_eq!(
+            sorted(&drifted),
+            vec![vec![Value::Int64(8), Value::Float64(40.0), Value::Int64(1)]]
+        );
+    }
+
     #[test]
     fn test_aggregate_count_sum() {
         // GROUP BY column 0, COUNT(*) and SUM(column 1)
@@ -1104,6 +1701,40 @@ mod tests {
         assert_eq!(last_insert.data.get(2), Some(&Value::Float64(30.0)));
     }
 
+    #[test]
+    fn test_aggregate_count_distinct() {
+        // GROUP BY column 0, COUNT(DISTINCT column 1)
+        let dataflow = DataflowNode::Aggregate {
+            input: Box::new(DataflowNode::source(1)),
+            group_by: vec![0],
+            functions: vec![(1, AggregateType::CountDistinct)],
+        };
+        let mut view = MaterializedView::new(dataflow);
+        let row = |id, value| Row::new(id, vec![Value::Int64(1), Value::Int64(value)]);
+        let distinct_count = |view: &MaterializedView| view.result()[0].get(1).cloned();
+
+        view.on_table_change(
+            1,
+            vec![Delta::insert(row(1, 10)), Delta::insert(row(2, 20))],
+        );
+        assert_eq!(distinct_count(&view), Some(Value::Int64(2)));
+
+        // Re-inserting an existing value leaves the distinct count unchanged.
+        view.on_table_change(1, vec![Delta::insert(row(3, 10))]);
+        assert_eq!(distinct_count(&view), Some(Value::Int64(2)));
+
+        // A new value raises it.
+        view.on_table_change(1, vec![Delta::insert(row(4, 30))]);
+        assert_eq!(distinct_count(&view), Some(Value::Int64(3)));
+
+        // Removing one of two rows holding 10 keeps 10 counted.
+        view.on_table_change(1, vec![Delta::delete(row(1, 10))]);
+        assert_eq!(distinct_count(&view), Some(Value::Int64(3)));
+        view.on_table_change(1, vec![Delta::delete(row(3, 10))]);
+        assert_eq!(distinct_count(&view), Some(Value::Int64(2)));
+        assert_eq!(view.len(), 1);
+    }
+
     #[test]
     fn test_aggregate_min_max_delete() {
         // GROUP BY column 0, MIN(column 1), MAX(column 1)
@@ -1215,4 +1846,178 @@ mod tests {
             "Sum group with 2 rows should NOT be empty, even if sum is 0.0"
         );
     }
+
+    #[test]
+    fn test_aggregate_decimal_sum_avg_exact() {
+        let dec = |s: &str| Value::Decimal(s.parse().unwrap());
+        let mut sum = AggregateState::new(AggregateType::Sum);
+        let mut avg = AggregateState::new(AggregateType::Avg);
+        for state in [&mut sum, &mut avg] {
+            state.apply(&dec("0.10"), 1);
+            state.apply(&dec("0.20"), 1);
+            state.apply(&dec("1.05"), 1);
+            state.apply(&dec("1.05"), -1);
+        }
+        assert_eq!(sum.get_value(), dec("0.30"));
+        assert_eq!(avg.get_value(), dec("0.15"));
+
+        // Integers keep the decimal sum exact; a float makes it a float
+        // until it is retracted.
+        sum.apply(&Value::Int64(2), 1);
+        assert_eq!(sum.get_value(), dec("2.30"));
+        sum.apply(&Value::Float64(0.5), 1);
+        assert!(matches!(sum.get_value(), Value::Float64(_)));
+        sum.apply(&Value::Float64(0.5), -1);
+        assert_eq!(sum.get_value(), dec("2.30"));
+
+        // Integer-only sums stay floats, as before.
+        let mut ints = AggregateState::new(AggregateType::Sum);
+        ints.apply(&Value::Int64(3), 1);
+        assert_eq!(ints.get_value(), Value::Float64(3.0));
+    }
+
+    // ==================== TopN ====================
+
+    fn top_n_by_age(limit: usize) -> DataflowNode {
+        DataflowNode::top_n(
+            DataflowNode::source(1),
+            vec![(1, SortOrder::Desc)],
+            limit,
+            0,
+        )
+    }
+
+    fn ids(rows: &[Row]) -> Vec<u64> {
+        rows.iter().map(|row| row.id()).collect()
+    }
+
+    #[test]
+    fn test_top_n_insert_beyond_limit_evicts_boundary_row() {
+        let mut view = MaterializedView::new(top_n_by_age(2));
+        view.on_table_change(
+            1,
+            vec![
+                Delta::insert(make_row(1, 30)),
+                Delta::insert(make_row(2, 20)),
+                Delta::insert(make_row(3, 10)),
+            ],
+        );
+        assert_eq!(ids(&view.result()), vec![1, 2]);
+
+        // Enters at the top: the boundary row (age 20) leaves the window.
+        let output = view.on_table_change(1, vec![Delta::insert(make_row(4, 40))]);
+        assert_eq!(output.len(), 2);
+        assert!(output.contains(&Delta::insert(make_row(4, 40))));
+        assert!(output.contains(&Delta::delete(make_row(2, 20))));
+        assert_eq!(ids(&view.result()), vec![4, 1]);
+
+        // Falls outside the window: nothing is emitted.
+        let output = view.on_table_change(1, vec![Delta::insert(make_row(5, 5))]);
+        assert!(output.is_empty());
+        assert_eq!(view.len(), 2);
+    }
+
+    #[test]
+    fn test_top_n_delete_backfills_from_outside_window() {
+        let mut view =
+            MaterializedView::with_initial(top_n_by_age(2), vec![make_row(1, 30), make_row(2, 20)]);
+        view.initialize_top_n_state(vec![make_row(1, 30), make_row(2, 20), make_row(3, 10)]);
+
+        let output = view.on_table_change(1, vec![Delta::delete(make_row(1, 30))]);
+        assert_eq!(output.len(), 2);
+        assert!(output.contains(&Delta::delete(make_row(1, 30))));
+        assert!(output.contains(&Delta::insert(make_row(3, 10))));
+        assert_eq!(ids(&view.result()), vec![2, 3]);
+
+        // Deleting a row outside the window changes nothing.
+        view.on_table_change(1, vec![Delta::insert(make_row(4, 1))]);
+        let output = view.on_table_change(1, vec![Delta::delete(make_row(4, 1))]);
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn test_top_n_update_moves_row_within_window() {
+        let mut view = MaterializedView::new(top_n_by_age(2));
+        view.on_table_change(
+            1,
+            vec![
+                Delta::insert(make_row(1, 30)),
+                Delta::insert(make_row(2, 20)),
+            ],
+        );
+
+        let output = view.on_table_change(
+            1,
+            vec![
+                Delta::delete(make_row(2, 20)),
+                Delta::insert(make_row(2, 50)),
+            ],
+        );
+        assert_eq!(output.len(), 2);
+        assert!(output.contains(&Delta::delete(make_row(2, 20))));
+        assert!(output.contains(&Delta::insert(make_row(2, 50))));
+        assert_eq!(ids(&view.result()), vec![2, 1]);
+    }
+
+    #[test]
+    fn test_top_n_offset_window() {
+        let dataflow =
+            DataflowNode::top_n(DataflowNode::source(1), vec![(1, SortOrder::Asc)], 2, 1);
+        let mut view = MaterializedView::new(dataflow);
+        view.on_table_change(
+            1,
+            vec![
+                Delta::insert(make_row(1, 10)),
+                Delta::insert(make_row(2, 20)),
+                Delta::insert(make_row(3, 30)),
+                Delta::insert(make_row(4, 40)),
+            ],
+        );
+        assert_eq!(ids(&view.result()), vec![2, 3]);
+
+        // A new first row shifts the window by one.
+        view.on_table_change(1, vec![Delta::insert(make_row(5, 0))]);
+        assert_eq!(ids(&view.result()), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_top_n_orders_jsonb_logically() {
+        let doc_row = |id: u64, doc: &str| {
+            Row::new(
+                id,
+                vec![
+                    Value::Int64(id as i64),
+                    Value::Jsonb(cynos_core::JsonbValue::new(doc.as_bytes().to_vec())),
+                ],
+            )
+        };
+        let mut view = MaterializedView::new(top_n_by_age(3));
+        view.on_table_change(
+            1,
+            vec![
+                Delta::insert(doc_row(1, "9")),
+                Delta::insert(doc_row(2, "10")),
+                Delta::insert(doc_row(3, r#""z""#)),
+                Delta::insert(doc_row(4, "[1]")),
+                Delta::insert(doc_row(5, r#"{"a":1}"#)),
+            ],
+        );
+        // Objects outrank arrays, which outrank strings; 10 outranks 9.
+        assert_eq!(ids(&view.result()), vec![5, 4, 3]);
+
+        view.on_table_change(1, vec![Delta::delete(doc_row(3, r#""z""#))]);
+        assert_eq!(ids(&view.result()), vec![5, 4, 2]);
+
+        // Binary-encoded documents order by content among text ones.
+        let eleven = cynos_jsonb::JsonbBinary::encode(&cynos_jsonb::JsonbValue::Number(11.0));
+        let binary_row = Row::new(
+            6,
+            vec![
+                Value::Int64(6),
+                Value::Jsonb(cynos_core::JsonbValue::new(eleven.into_bytes())),
+            ],
+        );
+        view.on_table_change(1, vec![Delta::insert(binary_row)]);
+        assert_eq!(ids(&view.result()), vec![5, 4, 6]);
+    }
 }
@@ -1,6 +1,7 @@
 //! Incremental aggregate operators.
 
 use crate::delta::Delta;
+use alloc::collections::BTreeMap;
 use cynos_core::{Row, Value};
 
 /// Incremental COUNT aggregate.
@@ -42,6 +43,56 @@ impl IncrementalCount {
     }
 }
 
+/// Incremental COUNT(DISTINCT) aggregate.
+///
+/// Keeps the multiplicity of every value in the column, so the distinct
+/// count only changes when a value's multiplicity moves between zero and
+/// non-zero. NULL counts as a value, matching the batch DISTINCT aggregate.
+#[derive(Clone, Debug)]
+pub struct IncrementalCountDistinct {
+    counts: BTreeMap<Value, i64>,
+    column: usize,
+}
+
+impl IncrementalCountDistinct {
+    /// Creates a new incremental distinct count for the given column.
+    pub fn new(column: usize) -> Self {
+        Self {
+            counts: BTreeMap::new(),
+            column,
+        }
+    }
+
+    /// Applies a batch of row deltas to update the value multiplicities.
+    pub fn apply(&mut self, deltas: &[Delta<Row>]) {
+        for d in deltas {
+            if let Some(value) = d.data.get(self.column) {
+                let count = self.counts.entry(value.clone()).or_insert(0);
+                *count += d.diff as i64;
+                if *count <= 0 {
+                    self.counts.remove(value);
+                }
+            }
+        }
+    }
+
+    /// Returns the current number of distinct values.
+    #[inline]
+    pub fn get(&self) -> i64 {
+        self.counts.len() as i64
+    }
+
+    /// Returns how many rows currently hold `value`.
+    pub fn multiplicity(&self, value: &Value) -> i64 {
+        self.counts.get(value).copied().unwrap_or(0)
+    }
+
+    /// Resets the distinct count.
+    pub fn reset(&mut self) {
+        self.counts.clear();
+    }
+}
+
 /// Incremental SUM aggregate.
 ///
 /// Maintains a running sum that is updated incrementally.
@@ -290,6 +341,7 @@ fn extract_numeric(value: &Value) -> f64 {
         Value::Int32(v) => *v as f64,
         Value::Int64(v) => *v as f64,
         Value::Float64(v) => *v,
+        Value::Decimal(v) => v.to_f64(),
         _ => 0.0,
     }
 }
@@ -314,6 +366,32 @@ mod tests {
         assert_eq!(count.get(), 1);
     }
 
+    #[test]
+    fn test_incremental_count_distinct() {
+        let mut distinct = IncrementalCountDistinct::new(0);
+
+        distinct.apply(&[
+            Delta::insert(make_row(1, 10)),
+            Delta::insert(make_row(2, 20)),
+        ]);
+        assert_eq!(distinct.get(), 2);
+
+        // Another row with an existing value leaves the count alone.
+        distinct.apply(&[Delta::insert(make_row(3, 10))]);
+        assert_eq!(distinct.get(), 2);
+        assert_eq!(distinct.multiplicity(&Value::Int64(10)), 2);
+
+        // A new value raises it.
+        distinct.apply(&[Delta::insert(make_row(4, 30))]);
+        assert_eq!(distinct.get(), 3);
+
+        // The value disappears only when its last row is deleted.
+        distinct.apply(&[Delta::delete(make_row(1, 10))]);
+        assert_eq!(distinct.get(), 3);
+        distinct.apply(&[Delta::delete(make_row(3, 10))]);
+        assert_eq!(distinct.get(), 2);
+    }
+
     #[test]
     fn test_incremental_sum() {
         let mut sum = IncrementalSum::new(0);
@@ -12,7 +12,8 @@ mod join;
 mod map;
 
 pub use aggregate::{
-    IncrementalAvg, IncrementalCount, IncrementalMax, IncrementalMin, IncrementalSum,
+    IncrementalAvg, IncrementalCount, IncrementalCountDistinct, IncrementalMax, IncrementalMin,
+    IncrementalSum,
 };
 pub use filter::filter_incremental;
 pub use join::IncrementalHashJoin;
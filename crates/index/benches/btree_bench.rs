@@ -21,6 +21,32 @@ fn btree_insert_benchmark(c: &mut Criterion) {
     group.finish();
 }
 
+fn btree_bulk_load_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("btree_build_sorted");
+    group.sample_size(10);
+
+    for size in [10000, 1000000].iter() {
+        group.bench_with_input(BenchmarkId::new("add", size), size, |b, &size| {
+            b.iter(|| {
+                let mut tree = BTreeIndex::new(64, false);
+                for i in 0..size {
+                    tree.add(i, i as u64).unwrap();
+                }
+                black_box(tree)
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("bulk_load", size), size, |b, &size| {
+            b.iter(|| {
+                black_box(
+                    BTreeIndex::bulk_load(64, false, (0..size).map(|i| (i, i as u64))).unwrap(),
+                )
+            });
+        });
+    }
+
+    group.finish();
+}
+
 fn btree_get_benchmark(c: &mut Criterion) {
     let mut group = c.benchmark_group("btree_get");
 
@@ -270,6 +296,7 @@ fn btree_remove_batch_non_unique(c: &mut Criterion) {
 criterion_group!(
     benches,
     btree_insert_benchmark,
+    btree_bulk_load_benchmark,
     btree_get_benchmark,
     btree_range_benchmark,
     hash_insert_benchmark,
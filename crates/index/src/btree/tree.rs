@@ -2,9 +2,11 @@
 
 use super::iter::BTreeIterator;
 use super::node::{Node, NodeId};
-use crate::comparator::{Comparator, SimpleComparator};
+use crate::comparator::{CaseInsensitiveKey, Comparator, SimpleComparator};
 use crate::stats::IndexStats;
 use crate::traits::{Index, IndexError, KeyRange, RangeIndex};
+use alloc::format;
+use alloc::string::String;
 use alloc::vec::Vec;
 use cynos_core::RowId;
 
@@ -13,6 +15,15 @@ use cynos_core::RowId;
 #[allow(dead_code)]
 pub const DEFAULT_ORDER: usize = 64;
 
+/// Splits `len` items into the fewest chunks of at most `max` items, with
+/// chunk sizes differing by at most one.
+fn even_chunks(len: usize, max: usize) -> impl Iterator<Item = usize> {
+    let chunks = len.div_ceil(max);
+    let base = len / chunks.max(1);
+    let extra = len % chunks.max(1);
+    (0..chunks).map(move |i| base + usize::from(i < extra))
+}
+
 /// A B+Tree index for efficient range queries.
 #[derive(Debug)]
 pub struct BTreeIndex<K> {
@@ -27,7 +38,17 @@ pub struct BTreeIndex<K> {
     /// Comparator for key ordering.
     comparator: SimpleComparator,
     /// Statistics for this index.
-    stats: IndexStats,
+    stats: IndexStats<K>,
+}
+
+impl BTreeIndex<CaseInsensitiveKey> {
+    /// Creates a B+Tree over `Value` keys whose strings are ordered without
+    /// regard to ASCII case, so "alice" and "Alice" are adjacent. They remain
+    /// distinct keys, so a unique index accepts both; use
+    /// `CaseInsensitiveKey::fold_bounds` to scan every case variant.
+    pub fn case_insensitive(order: usize, unique: bool) -> Self {
+        Self::new(order, unique)
+    }
 }
 
 impl<K: Clone + Ord> BTreeIndex<K> {
@@ -47,15 +68,142 @@ impl<K: Clone + Ord> BTreeIndex<K> {
             order,
             unique,
             comparator,
-            stats: IndexStats::new(),
+            stats: IndexStats::default(),
+        }
+    }
+
+    /// Builds a tree from entries sorted ascending by key, bottom-up.
+    ///
+    /// Leaves are packed with up to `order - 1` keys and linked in one pass,
+    /// then each internal level is built over the one below, which avoids
+    /// the node splits of repeated `add`. Entries with equal keys must be
+    /// adjacent and share one key; in a unique index they are rejected with
+    /// `IndexError::DuplicateKey`. A key smaller than its predecessor returns
+    /// `IndexError::UnsortedInput`.
+    pub fn bulk_load(
+        order: usize,
+        unique: bool,
+        entries: impl IntoIterator<Item = (K, RowId)>,
+    ) -> Result<Self, IndexError> {
+        let mut keys: Vec<K> = Vec::new();
+        let mut values: Vec<Vec<RowId>> = Vec::new();
+        let mut row_count = 0;
+        for (key, row_id) in entries {
+            match keys.last() {
+                Some(last) if key < *last => return Err(IndexError::UnsortedInput),
+                Some(last) if key == *last => {
+                    if unique {
+                        return Err(IndexError::DuplicateKey);
+                    }
+                    values.last_mut().expect("one list per key").push(row_id);
+                }
+                _ => {
+                    keys.push(key);
+                    values.push(alloc::vec![row_id]);
+                }
+            }
+            row_count += 1;
+        }
+
+        let mut tree = Self::new(order, unique);
+        if keys.is_empty() {
+            return Ok(tree);
         }
+        if order < 3 {
+            // Internal nodes of one key cannot always split a level evenly,
+            // so tiny orders take the regular insert path.
+            for (key, row_ids) in keys.into_iter().zip(values) {
+                for row_id in row_ids {
+                    tree.insert(key.clone(), row_id)?;
+                }
+            }
+            return Ok(tree);
+        }
+
+        tree.arena.clear();
+        tree.stats.add_rows(row_count);
+
+        // Each entry is a node and the smallest key below it.
+        let mut level: Vec<(NodeId, K)> = Vec::new();
+        let mut keys = keys.into_iter();
+        let mut values = values.into_iter();
+        for size in even_chunks(keys.len(), order - 1) {
+            let mut leaf = Node::new_leaf();
+            leaf.keys = keys.by_ref().take(size).collect();
+            leaf.values = values.by_ref().take(size).collect();
+            leaf.prev = level.last().map(|&(id, _)| id);
+            let min_key = leaf.keys[0].clone();
+            let leaf_id = Self::alloc_node(&mut tree.arena, leaf);
+            if let Some(prev) = tree.arena[leaf_id].prev {
+                tree.arena[prev].next = Some(leaf_id);
+            }
+            level.push((leaf_id, min_key));
+        }
+
+        while level.len() > 1 {
+            let mut children = level.into_iter();
+            level = Vec::new();
+            for size in even_chunks(children.len(), order) {
+                let mut node = Node::new_internal();
+                let mut min_key = None;
+                for (child_id, child_min) in children.by_ref().take(size) {
+                    node.children.push(child_id);
+                    match min_key {
+                        None => min_key = Some(child_min),
+                        Some(_) => node.keys.push(child_min),
+                    }
+                }
+                let node_id = Self::alloc_node(&mut tree.arena, node);
+                for i in 0..tree.arena[node_id].children.len() {
+                    let child_id = tree.arena[node_id].children[i];
+                    tree.arena[child_id].parent = Some(node_id);
+                }
+                level.push((node_id, min_key.expect("chunks are non-empty")));
+            }
+        }
+
+        tree.root = level[0].0;
+        #[cfg(feature = "debug-invariants")]
+        tree.assert_valid();
+        Ok(tree)
     }
 
     /// Returns the statistics for this index.
-    pub fn stats(&self) -> &IndexStats {
+    pub fn stats(&self) -> &IndexStats<K> {
         &self.stats
     }
 
+    /// Builds an equi-depth histogram of at most `buckets` buckets over the
+    /// current keys and keeps it updated as rows are added and removed.
+    ///
+    /// Updates only adjust bucket counts; call `rebuild_histogram` to
+    /// rebalance the buckets once `KeyHistogram::is_skewed` reports drift.
+    pub fn enable_histogram(&mut self, buckets: usize) {
+        let keys = Self::key_row_counts(&self.arena, self.leftmost_leaf());
+        self.stats.enable_histogram(buckets, keys);
+    }
+
+    /// Rebuilds the histogram from the current keys, if enabled.
+    pub fn rebuild_histogram(&mut self) {
+        if self.stats.histogram().is_some() {
+            let keys = Self::key_row_counts(&self.arena, self.leftmost_leaf());
+            self.stats.rebuild_histogram(keys);
+        }
+    }
+
+    /// Returns each key with its row count, in key order, reading the
+    /// leaves from `first_leaf` on.
+    fn key_row_counts(arena: &[Node<K>], first_leaf: NodeId) -> Vec<(&K, usize)> {
+        let mut keys = Vec::new();
+        let mut leaf = Some(first_leaf);
+        while let Some(leaf_id) = leaf {
+            let node = &arena[leaf_id];
+            keys.extend(node.keys.iter().zip(node.values.iter().map(Vec::len)));
+            leaf = node.next;
+        }
+        keys
+    }
+
     /// Returns whether this is a unique index.
     pub fn is_unique(&self) -> bool {
         self.unique
@@ -68,6 +216,11 @@ impl<K: Clone + Ord> BTreeIndex<K> {
         id
     }
 
+    /// Returns the fewest keys a non-root node may hold, ⌈order/2⌉ − 1.
+    fn min_keys(&self) -> usize {
+        (self.order - 1) / 2
+    }
+
     /// Finds the leaf node that should contain the given key.
     fn find_leaf(&self, key: &K) -> NodeId {
         let mut current = self.root;
@@ -113,7 +266,7 @@ impl<K: Clone + Ord> BTreeIndex<K> {
         // Insert into leaf
         let pos = self.arena[leaf_id].find_key_position(&key);
         self.arena[leaf_id].insert_at(pos, key.clone(), value);
-        self.stats.add_rows(1);
+        self.stats.add_key_rows(&key, 1);
 
         // Check if we need to split
         if self.arena[leaf_id].key_count() >= self.order {
@@ -217,26 +370,24 @@ impl<K: Clone + Ord> BTreeIndex<K> {
 
         if let Some(pos) = leaf.find_key(key) {
             let removed = self.arena[leaf_id].remove_at(pos, value);
-            self.stats.remove_rows(removed);
+            self.stats.remove_key_rows(key, removed);
 
-            // Handle underflow if needed (simplified - just check if empty)
-            if self.arena[leaf_id].is_empty() && leaf_id != self.root {
+            if leaf_id != self.root {
                 self.handle_underflow(leaf_id);
             }
         }
     }
 
-    /// Handles underflow after deletion (simplified version).
+    /// Refills `node_id` from a sibling, or merges it into one, once it
+    /// holds fewer than `min_keys()` keys.
     fn handle_underflow(&mut self, node_id: NodeId) {
         let parent_id = match self.arena[node_id].parent {
             Some(p) => p,
             None => return, // Root node, nothing to do
         };
 
-        let node = &self.arena[node_id];
-        let min_keys = (self.order - 1) / 2;
-
-        if node.key_count() >= min_keys {
+        let min_keys = self.min_keys();
+        if self.arena[node_id].key_count() >= min_keys {
             return; // No underflow
         }
 
@@ -457,6 +608,40 @@ impl<K: Clone + Ord> BTreeIndex<K> {
         }
     }
 
+    /// Finds the leaf node and position for a reverse scan of a key range: the
+    /// last key at or below the range's upper bound, or strictly below it when
+    /// `include_equal` is false.
+    fn find_range_end(&self, range: &KeyRange<K>, include_equal: bool) -> Option<(NodeId, usize)> {
+        let (key, inclusive) = match range {
+            KeyRange::All | KeyRange::LowerBound { .. } => {
+                let leaf = self.rightmost_leaf();
+                let node = &self.arena[leaf];
+                return (!node.is_empty()).then(|| (leaf, node.key_count() - 1));
+            }
+            KeyRange::Only(key) => (key, include_equal),
+            KeyRange::UpperBound { value, exclusive } => (value, include_equal && !exclusive),
+            KeyRange::Bound {
+                upper,
+                upper_exclusive,
+                ..
+            } => (upper, include_equal && !upper_exclusive),
+        };
+
+        let leaf = self.find_leaf(key);
+        let node = &self.arena[leaf];
+        let pos = if inclusive {
+            node.keys.partition_point(|k| k <= key)
+        } else {
+            node.find_key_position(key)
+        };
+        if pos > 0 {
+            return Some((leaf, pos - 1));
+        }
+        let prev = node.prev?;
+        let prev_node = &self.arena[prev];
+        (!prev_node.is_empty()).then(|| (prev, prev_node.key_count() - 1))
+    }
+
     #[inline]
     fn key_past_forward_range(range: &KeyRange<K>, key: &K) -> bool {
         match range {
@@ -509,6 +694,46 @@ impl<K: Clone + Ord> BTreeIndex<K> {
         }
     }
 
+    /// Returns row IDs within the given key range in descending key order,
+    /// walking backwards from the leaf holding the upper bound.
+    ///
+    /// `offset` rows are skipped before up to `limit` rows are returned, and
+    /// the walk stops as soon as the limit is reached. When `include_equal` is
+    /// false, keys equal to the upper bound are left out even if the range
+    /// includes them.
+    pub fn get_range_rev(
+        &self,
+        range: Option<&KeyRange<K>>,
+        include_equal: bool,
+        limit: Option<usize>,
+        offset: usize,
+    ) -> Vec<RowId> {
+        let range = range.cloned().unwrap_or(KeyRange::All);
+        let mut result = Vec::new();
+        let Some((start_node, start_pos)) = self.find_range_end(&range, include_equal) else {
+            return result;
+        };
+
+        let mut skipped = 0usize;
+        for (key, row_id) in BTreeIterator::new_at(&self.arena, start_node, start_pos, true) {
+            if !range.contains(key) {
+                if Self::key_past_reverse_range(&range, key) {
+                    break;
+                }
+                continue;
+            }
+            if skipped < offset {
+                skipped += 1;
+                continue;
+            }
+            if limit.is_some_and(|limit| result.len() >= limit) {
+                break;
+            }
+            result.push(row_id);
+        }
+        result
+    }
+
     /// Visits row IDs within the given key range without first materializing a `Vec<RowId>`.
     /// Return `false` from the visitor to stop early.
     pub fn visit_range<F>(
@@ -524,35 +749,7 @@ impl<K: Clone + Ord> BTreeIndex<K> {
         let range = range.cloned().unwrap_or(KeyRange::All);
 
         let start = if reverse {
-            match &range {
-                KeyRange::All | KeyRange::LowerBound { .. } => {
-                    let leaf = self.rightmost_leaf();
-                    if self.arena[leaf].is_empty() {
-                        None
-                    } else {
-                        Some((leaf, self.arena[leaf].key_count() - 1))
-                    }
-                }
-                KeyRange::Only(key)
-                | KeyRange::UpperBound { value: key, .. }
-                | KeyRange::Bound { upper: key, .. } => {
-                    let leaf = self.find_leaf(key);
-                    let node = &self.arena[leaf];
-                    let pos = node.find_key_position(key);
-                    if pos > 0 {
-                        Some((leaf, pos - 1))
-                    } else if let Some(prev) = node.prev {
-                        let prev_node = &self.arena[prev];
-                        if prev_node.is_empty() {
-                            None
-                        } else {
-                            Some((prev, prev_node.key_count() - 1))
-                        }
-                    } else {
-                        None
-                    }
-                }
-            }
+            self.find_range_end(&range, true)
         } else {
             self.find_range_start(&range)
         };
@@ -591,9 +788,212 @@ impl<K: Clone + Ord> BTreeIndex<K> {
     }
 }
 
+impl<K: Clone + Ord> BTreeIndex<K> {
+    /// Checks the structural invariants of the tree.
+    ///
+    /// Verifies, for every node reachable from the root:
+    /// - parent pointers and node kinds are consistent
+    /// - keys are strictly ascending and within the separator bounds of the parent
+    /// - nodes hold fewer than `order` keys and non-root nodes are not empty
+    /// - all leaves are at the same depth and form a doubly linked list in key order
+    /// - the row count in the stats matches the row IDs stored in the leaves
+    ///
+    /// Returns a description of the first violation found.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.arena[self.root].parent.is_some() {
+            return Err(format!("root node {} has a parent", self.root));
+        }
+
+        let mut leaves = Vec::new();
+        let mut leaf_depth = None;
+        let mut row_count = 0;
+        self.validate_node(
+            self.root,
+            None,
+            None,
+            0,
+            &mut leaf_depth,
+            &mut leaves,
+            &mut row_count,
+        )?;
+
+        for (i, &leaf_id) in leaves.iter().enumerate() {
+            let leaf = &self.arena[leaf_id];
+            let expected_prev = i.checked_sub(1).map(|prev| leaves[prev]);
+            let expected_next = leaves.get(i + 1).copied();
+            if leaf.prev != expected_prev {
+                return Err(format!(
+                    "leaf {} has prev {:?}, expected {:?}",
+                    leaf_id, leaf.prev, expected_prev
+                ));
+            }
+            if leaf.next != expected_next {
+                return Err(format!(
+                    "leaf {} has next {:?}, expected {:?}",
+                    leaf_id, leaf.next, expected_next
+                ));
+            }
+        }
+
+        if row_count != self.stats.total_rows() {
+            return Err(format!(
+                "stats report {} rows but leaves hold {}",
+                self.stats.total_rows(),
+                row_count
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn validate_node(
+        &self,
+        node_id: NodeId,
+        lower: Option<&K>,
+        upper: Option<&K>,
+        depth: usize,
+        leaf_depth: &mut Option<usize>,
+        leaves: &mut Vec<NodeId>,
+        row_count: &mut usize,
+    ) -> Result<(), String> {
+        let node = self
+            .arena
+            .get(node_id)
+            .ok_or_else(|| format!("node {} is outside the arena", node_id))?;
+
+        if node.key_count() >= self.order {
+            return Err(format!(
+                "node {} holds {} keys, order is {}",
+                node_id,
+                node.key_count(),
+                self.order
+            ));
+        }
+        if node_id != self.root && node.is_empty() {
+            return Err(format!("non-root node {} is empty", node_id));
+        }
+        if node_id != self.root && node.key_count() < self.min_keys() {
+            return Err(format!(
+                "non-root node {} holds {} keys, fewer than the minimum of {}",
+                node_id,
+                node.key_count(),
+                self.min_keys()
+            ));
+        }
+        if node.keys.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(format!(
+                "keys of node {} are not strictly ascending",
+                node_id
+            ));
+        }
+        if let (Some(lower), Some(first)) = (lower, node.keys.first()) {
+            if first < lower {
+                return Err(format!(
+                    "node {} has a key below its parent separator",
+                    node_id
+                ));
+            }
+        }
+        if let (Some(upper), Some(last)) = (upper, node.keys.last()) {
+            if last >= upper {
+                return Err(format!(
+                    "node {} has a key at or above its parent separator",
+                    node_id
+                ));
+            }
+        }
+
+        if node.is_leaf {
+            if !node.children.is_empty() {
+                return Err(format!("leaf {} has children", node_id));
+            }
+            if node.values.len() != node.keys.len() {
+                return Err(format!(
+                    "leaf {} has {} keys but {} value lists",
+                    node_id,
+                    node.keys.len(),
+                    node.values.len()
+                ));
+            }
+            for values in &node.values {
+                if values.is_empty() {
+                    return Err(format!("leaf {} has a key without row IDs", node_id));
+                }
+                if self.unique && values.len() > 1 {
+                    return Err(format!(
+                        "unique leaf {} maps a key to {} row IDs",
+                        node_id,
+                        values.len()
+                    ));
+                }
+                *row_count += values.len();
+            }
+            match *leaf_depth {
+                Some(expected) if expected != depth => {
+                    return Err(format!(
+                        "leaf {} is at depth {}, expected {}",
+                        node_id, depth, expected
+                    ));
+                }
+                Some(_) => {}
+                None => *leaf_depth = Some(depth),
+            }
+            leaves.push(node_id);
+            return Ok(());
+        }
+
+        if !node.values.is_empty() {
+            return Err(format!("internal node {} has row IDs", node_id));
+        }
+        if node.children.len() != node.keys.len() + 1 {
+            return Err(format!(
+                "internal node {} has {} keys but {} children",
+                node_id,
+                node.keys.len(),
+                node.children.len()
+            ));
+        }
+
+        for (i, &child_id) in node.children.iter().enumerate() {
+            let child_parent = self.arena.get(child_id).and_then(|child| child.parent);
+            if child_parent != Some(node_id) {
+                return Err(format!(
+                    "child {} of node {} has parent {:?}",
+                    child_id, node_id, child_parent
+                ));
+            }
+            let child_lower = if i == 0 { lower } else { node.keys.get(i - 1) };
+            let child_upper = node.keys.get(i).or(upper);
+            self.validate_node(
+                child_id,
+                child_lower,
+                child_upper,
+                depth + 1,
+                leaf_depth,
+                leaves,
+                row_count,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Panics if the tree violates its invariants.
+    #[cfg(feature = "debug-invariants")]
+    fn assert_valid(&self) {
+        if let Err(error) = self.validate() {
+            panic!("B+Tree invariant violated: {}", error);
+        }
+    }
+}
+
 impl<K: Clone + Ord> Index<K> for BTreeIndex<K> {
     fn add(&mut self, key: K, value: RowId) -> Result<(), IndexError> {
-        self.insert(key, value)
+        let result = self.insert(key, value);
+        #[cfg(feature = "debug-invariants")]
+        self.assert_valid();
+        result
     }
 
     fn set(&mut self, key: K, value: RowId) {
@@ -601,6 +1001,8 @@ impl<K: Clone + Ord> Index<K> for BTreeIndex<K> {
         self.delete(&key, None);
         // Then insert the new value
         let _ = self.insert(key, value);
+        #[cfg(feature = "debug-invariants")]
+        self.assert_valid();
     }
 
     fn get(&self, key: &K) -> Vec<RowId> {
@@ -616,6 +1018,8 @@ impl<K: Clone + Ord> Index<K> for BTreeIndex<K> {
 
     fn remove(&mut self, key: &K, value: Option<RowId>) {
         self.delete(key, value);
+        #[cfg(feature = "debug-invariants")]
+        self.assert_valid();
     }
 
     fn remove_batch(&mut self, entries: &[(K, RowId)]) {
@@ -627,9 +1031,6 @@ impl<K: Clone + Ord> Index<K> for BTreeIndex<K> {
         let mut sorted_entries: Vec<_> = entries.iter().collect();
         sorted_entries.sort_by(|a, b| a.0.cmp(&b.0));
 
-        // Track nodes that need underflow handling (defer to end for efficiency)
-        let mut underflow_nodes: Vec<NodeId> = Vec::new();
-
         // Process entries in sorted order
         let mut i = 0;
         while i < sorted_entries.len() {
@@ -664,34 +1065,22 @@ impl<K: Clone + Ord> Index<K> for BTreeIndex<K> {
                 }
 
                 let removed = original_len - values.len();
-                self.stats.remove_rows(removed);
+                self.stats.remove_key_rows(key, removed);
 
-                // If all values removed, remove the key
+                // If all values removed, remove the key. Rebalancing right
+                // away keeps every leaf found for the next key valid.
                 if values.is_empty() {
                     self.arena[leaf_id].keys.remove(pos);
                     self.arena[leaf_id].values.remove(pos);
-
-                    // Track for deferred underflow handling
-                    if self.arena[leaf_id].is_empty() && leaf_id != self.root {
-                        if !underflow_nodes.contains(&leaf_id) {
-                            underflow_nodes.push(leaf_id);
-                        }
+                    if leaf_id != self.root {
+                        self.handle_underflow(leaf_id);
                     }
                 }
             }
         }
 
-        // Handle underflows at the end (deferred for efficiency)
-        // Process in reverse order to handle children before parents
-        for node_id in underflow_nodes.into_iter().rev() {
-            if !self.arena[node_id].is_empty() {
-                continue; // Node was filled by subsequent operations
-            }
-            if node_id == self.root {
-                continue; // Root doesn't need underflow handling
-            }
-            self.handle_underflow(node_id);
-        }
+        #[cfg(feature = "debug-invariants")]
+        self.assert_valid();
     }
 
     fn contains_key(&self, key: &K) -> bool {
@@ -706,7 +1095,7 @@ impl<K: Clone + Ord> Index<K> for BTreeIndex<K> {
     fn clear(&mut self) {
         self.arena.clear();
         self.root = Self::alloc_node(&mut self.arena, Node::new_leaf());
-        self.stats.clear();
+        self.stats.clear_keys();
     }
 
     fn min(&self) -> Option<(&K, Vec<RowId>)> {
@@ -734,7 +1123,15 @@ impl<K: Clone + Ord> Index<K> for BTreeIndex<K> {
         match range {
             KeyRange::All => self.stats.total_rows(),
             KeyRange::Only(key) => self.get(key).len(),
-            _ => self.stats.total_rows(), // Simplified estimation
+            _ => match self.stats.estimate_selectivity(range) {
+                Some(selectivity) => {
+                    // Rounded up without `f64::ceil`, which needs std.
+                    let estimate = selectivity * self.stats.total_rows() as f64;
+                    let rows = estimate as usize;
+                    rows + usize::from((rows as f64) < estimate)
+                }
+                None => self.stats.total_rows(), // Simplified estimation
+            },
         }
     }
 }
@@ -747,6 +1144,9 @@ impl<K: Clone + Ord> RangeIndex<K> for BTreeIndex<K> {
         limit: Option<usize>,
         skip: usize,
     ) -> Vec<RowId> {
+        if reverse {
+            return self.get_range_rev(range, true, limit, skip);
+        }
         let mut result = Vec::new();
         self.visit_range(range, reverse, limit, skip, |row_id| {
             result.push(row_id);
@@ -754,6 +1154,44 @@ impl<K: Clone + Ord> RangeIndex<K> for BTreeIndex<K> {
         });
         result
     }
+
+    /// Walks the leaves in the range and sums their row ID list lengths, so
+    /// no row IDs are copied.
+    fn count_range(&self, range: Option<&KeyRange<K>>, include_equal: bool) -> usize {
+        let range = range.cloned().unwrap_or(KeyRange::All);
+        if let KeyRange::All = range {
+            return self.stats.total_rows();
+        }
+        let Some((mut node_id, mut pos)) = self.find_range_start(&range) else {
+            return 0;
+        };
+        let upper = match &range {
+            _ if include_equal => None,
+            KeyRange::Only(value) | KeyRange::UpperBound { value, .. } => Some(value),
+            KeyRange::Bound { upper, .. } => Some(upper),
+            _ => None,
+        };
+
+        let mut count = 0;
+        loop {
+            let node = &self.arena[node_id];
+            for (key, values) in node.keys[pos..].iter().zip(&node.values[pos..]) {
+                if Self::key_past_forward_range(&range, key) || upper.is_some_and(|u| key >= u) {
+                    return count;
+                }
+                if range.contains(key) {
+                    count += values.len();
+                }
+            }
+            match node.next {
+                Some(next) => {
+                    node_id = next;
+                    pos = 0;
+                }
+                None => return count,
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -894,6 +1332,47 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_btree_count_range_matches_get_range() {
+        let mut tree: BTreeIndex<i32> = BTreeIndex::new(4, false);
+        for i in 0..200 {
+            tree.add(i % 50, i as u64).unwrap();
+        }
+
+        let ranges = [
+            KeyRange::all(),
+            KeyRange::only(7),
+            KeyRange::only(99),
+            KeyRange::lower_bound(10, false),
+            KeyRange::lower_bound(10, true),
+            KeyRange::upper_bound(20, false),
+            KeyRange::upper_bound(20, true),
+            KeyRange::bound(5, 30, false, false),
+            KeyRange::bound(5, 30, true, true),
+            KeyRange::bound(60, 70, false, false),
+            KeyRange::bound(5, 5, true, false),
+        ];
+        for range in &ranges {
+            assert_eq!(
+                tree.count_range(Some(range), true),
+                tree.get_range(Some(range), false, None, 0).len(),
+                "{:?}",
+                range
+            );
+            assert_eq!(
+                tree.count_range(Some(range), false),
+                tree.get_range_rev(Some(range), false, None, 0).len(),
+                "{:?} without equal",
+                range
+            );
+        }
+        assert_eq!(tree.count_range(None, true), 200);
+        assert_eq!(
+            tree.count_range(Some(&KeyRange::bound(5, 30, false, false)), false),
+            100
+        );
+    }
+
     #[test]
     fn test_btree_set() {
         let mut tree: BTreeIndex<i32> = BTreeIndex::new(5, true);
@@ -958,6 +1437,48 @@ mod tests {
         assert_eq!(result, vec![9, 8, 7]);
     }
 
+    #[test]
+    fn test_btree_get_range_rev() {
+        let mut tree: BTreeIndex<i32> = BTreeIndex::new(4, true);
+        for i in 0..10 {
+            tree.add(i, i as u64).unwrap();
+        }
+
+        // Offset is applied before limit, counting from the top.
+        assert_eq!(tree.get_range_rev(None, true, Some(3), 2), vec![7, 6, 5]);
+
+        let inclusive = KeyRange::bound(2, 6, false, false);
+        assert_eq!(
+            tree.get_range_rev(Some(&inclusive), true, None, 0),
+            vec![6, 5, 4, 3, 2]
+        );
+        assert_eq!(
+            tree.get_range_rev(Some(&inclusive), false, None, 0),
+            vec![5, 4, 3, 2]
+        );
+        assert_eq!(
+            tree.get_range_rev(Some(&KeyRange::upper_bound(4, true)), true, Some(2), 0),
+            vec![3, 2]
+        );
+        assert_eq!(
+            tree.get_range_rev(Some(&KeyRange::only(4)), true, None, 0),
+            vec![4]
+        );
+        assert_eq!(
+            tree.get_range(Some(&inclusive), true, Some(2), 1),
+            vec![5, 4]
+        );
+
+        let empty = KeyRange::bound(7, 3, false, false);
+        assert!(tree.get_range_rev(Some(&empty), true, None, 0).is_empty());
+        assert!(tree
+            .get_range_rev(Some(&KeyRange::only(42)), true, None, 0)
+            .is_empty());
+        assert!(BTreeIndex::<i32>::new(4, true)
+            .get_range_rev(None, true, Some(1), 0)
+            .is_empty());
+    }
+
     #[test]
     fn test_btree_range_lower_bound() {
         let mut tree: BTreeIndex<i32> = BTreeIndex::new(5, true);
@@ -1022,6 +1543,40 @@ mod tests {
         assert_eq!(tree.stats().total_rows(), 0);
     }
 
+    #[test]
+    fn test_btree_histogram_estimates_range_cost() {
+        let mut tree: BTreeIndex<i32> = BTreeIndex::new(8, false);
+        for i in 0..1000 {
+            tree.add(i, i as RowId).unwrap();
+        }
+        // Without a histogram any range is costed as a full scan.
+        assert_eq!(tree.cost(&KeyRange::lower_bound(900, false)), 1000);
+
+        tree.enable_histogram(20);
+        let cost = tree.cost(&KeyRange::lower_bound(900, false));
+        assert!((75..=150).contains(&cost), "{cost}");
+
+        // Appended keys all land in the last bucket until a rebuild.
+        for i in 1000..2000 {
+            tree.add(i, i as RowId).unwrap();
+        }
+        tree.remove(&0, None);
+        let histogram = tree.stats().histogram().unwrap();
+        assert_eq!(histogram.total(), 1999);
+        assert_eq!(histogram.bounds().last(), Some(&1999));
+        assert!(histogram.is_skewed());
+
+        tree.rebuild_histogram();
+        assert!(!tree.stats().histogram().unwrap().is_skewed());
+        let cost = tree.cost(&KeyRange::lower_bound(1000, false));
+        assert!((900..=1100).contains(&cost), "{cost}");
+        let cost = tree.cost(&KeyRange::bound(500, 599, false, false));
+        assert!((50..=200).contains(&cost), "{cost}");
+
+        tree.clear();
+        assert_eq!(tree.stats().histogram().unwrap().total(), 0);
+    }
+
     #[test]
     fn test_btree_sequence() {
         // Test from original JS test
@@ -1531,6 +2086,7 @@ mod tests {
         }
 
         assert_eq!(tree.len(), 50);
+        tree.validate().unwrap();
 
         // Verify only odd keys remain
         for i in 0..100 {
@@ -1789,4 +2345,216 @@ mod tests {
         let result = tree.get_range(None, false, None, 0);
         assert_eq!(result, vec![0, 1, 2, 4, 5, 6, 8, 9]);
     }
+
+    // ==================== Invariant Validation ====================
+
+    /// Minimal LCG so the random sequences are reproducible without std.
+    fn next_random(state: &mut u64) -> u64 {
+        *state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        *state >> 33
+    }
+
+    #[test]
+    fn test_validate_after_random_operations() {
+        for (order, unique, seed) in [
+            (3, true, 5),
+            (4, false, 7),
+            (5, true, 11),
+            (8, false, 3),
+            (64, false, 13),
+        ] {
+            let mut tree: BTreeIndex<i32> = BTreeIndex::new(order, unique);
+            let mut state = seed;
+            let mut next_row = 0;
+
+            for step in 0..3000 {
+                let key = (next_random(&mut state) % 200) as i32;
+                match next_random(&mut state) % 4 {
+                    0 | 1 => {
+                        let _ = tree.add(key, next_row);
+                        next_row += 1;
+                    }
+                    2 => tree.remove(&key, None),
+                    _ => {
+                        let entries: Vec<(i32, RowId)> = tree
+                            .get(&key)
+                            .into_iter()
+                            .take(1)
+                            .map(|row_id| (key, row_id))
+                            .collect();
+                        tree.remove_batch(&entries);
+                    }
+                }
+
+                if let Err(error) = tree.validate() {
+                    panic!("order {} step {}: {}", order, step, error);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_detects_corruption() {
+        let build = || {
+            let mut tree: BTreeIndex<i32> = BTreeIndex::new(4, true);
+            for i in 0..50 {
+                tree.add(i, i as u64).unwrap();
+            }
+            tree.validate().unwrap();
+            tree
+        };
+
+        // Keys out of order within a leaf
+        let mut tree = build();
+        let leaf = tree.leftmost_leaf();
+        tree.arena[leaf].keys.swap(0, 1);
+        assert!(tree.validate().unwrap_err().contains("strictly ascending"));
+
+        // Broken leaf linkage
+        let mut tree = build();
+        let leaf = tree.leftmost_leaf();
+        tree.arena[leaf].next = None;
+        assert!(tree.validate().unwrap_err().contains("has next"));
+
+        // Key outside the parent's separator bounds
+        let mut tree = build();
+        let leaf = tree.rightmost_leaf();
+        tree.arena[leaf].keys[0] = -1;
+        assert!(tree.validate().unwrap_err().contains("separator"));
+
+        // Row count drifting from the stored row IDs
+        let mut tree = build();
+        tree.stats.add_rows(1);
+        assert!(tree.validate().unwrap_err().contains("stats report"));
+
+        // Overfull node
+        let mut tree = build();
+        let leaf = tree.rightmost_leaf();
+        tree.arena[leaf].keys.extend([100, 101, 102, 103]);
+        tree.arena[leaf]
+            .values
+            .extend([vec![1], vec![2], vec![3], vec![4]]);
+        assert!(tree.validate().unwrap_err().contains("order is 4"));
+
+        // Underfull node: order 6 needs at least two keys per non-root node
+        let mut tree: BTreeIndex<i32> = BTreeIndex::new(6, true);
+        for i in 0..50 {
+            tree.add(i, i as u64).unwrap();
+        }
+        tree.validate().unwrap();
+        let leaf = tree.rightmost_leaf();
+        tree.arena[leaf].keys.truncate(1);
+        tree.arena[leaf].values.truncate(1);
+        assert!(tree
+            .validate()
+            .unwrap_err()
+            .contains("fewer than the minimum"));
+    }
+
+    #[test]
+    fn test_case_insensitive_index() {
+        use cynos_core::Value;
+
+        let key = |name: &str| CaseInsensitiveKey(Value::String(name.into()));
+        let mut tree = BTreeIndex::case_insensitive(4, true);
+        for (row_id, name) in ["bob", "Alice", "carol", "alice", "ALICE", "Bob", "dave"]
+            .iter()
+            .enumerate()
+        {
+            tree.add(key(name), row_id as u64).unwrap();
+        }
+        assert!(tree.validate().is_ok());
+
+        // Case variants sit together, uppercase first.
+        assert_eq!(
+            tree.get_range(None, false, None, 0),
+            vec![4, 1, 3, 5, 0, 2, 6]
+        );
+        assert_eq!(tree.get(&key("alice")), vec![3]);
+
+        let (lower, upper) = CaseInsensitiveKey::fold_bounds("aLiCe");
+        let range = KeyRange::bound(lower, upper, false, false);
+        assert_eq!(tree.get_range(Some(&range), false, None, 0), vec![4, 1, 3]);
+
+        let range = KeyRange::lower_bound(key("B"), false);
+        assert_eq!(
+            tree.get_range(Some(&range), false, None, 0),
+            vec![5, 0, 2, 6]
+        );
+    }
+
+    #[test]
+    fn test_bulk_load_matches_repeated_add() {
+        for order in [3, 4, 5, 64] {
+            for size in [0i64, 1, 3, 4, 5, 17, 64, 65, 1000] {
+                // Every third key carries two rows.
+                let entries: Vec<(i64, RowId)> = (0..size)
+                    .flat_map(|k| {
+                        let rows = if k % 3 == 0 { 2 } else { 1 };
+                        (0..rows).map(move |r| (k * 2, (k * 10 + r) as RowId))
+                    })
+                    .collect();
+
+                let bulk = BTreeIndex::bulk_load(order, false, entries.clone()).unwrap();
+                let mut added = BTreeIndex::new(order, false);
+                for (key, row_id) in &entries {
+                    added.add(*key, *row_id).unwrap();
+                }
+
+                assert!(bulk.validate().is_ok(), "order {} size {}", order, size);
+                assert_eq!(bulk.len(), added.len());
+                assert_eq!(
+                    bulk.get_range(None, false, None, 0),
+                    added.get_range(None, false, None, 0)
+                );
+                assert_eq!(
+                    bulk.get_range(None, true, None, 0),
+                    added.get_range(None, true, None, 0)
+                );
+                for range in [
+                    KeyRange::bound(10, 40, false, true),
+                    KeyRange::lower_bound(size, true),
+                    KeyRange::upper_bound(7, false),
+                    KeyRange::only(6),
+                ] {
+                    assert_eq!(
+                        bulk.get_range(Some(&range), false, None, 0),
+                        added.get_range(Some(&range), false, None, 0)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bulk_loaded_tree_accepts_updates() {
+        let mut tree =
+            BTreeIndex::bulk_load(4, true, (0..100i64).map(|k| (k * 2, k as RowId))).unwrap();
+        for k in 0..100i64 {
+            tree.add(k * 2 + 1, 1000 + k as RowId).unwrap();
+        }
+        for k in (0..200i64).step_by(3) {
+            tree.remove(&k, None);
+        }
+        assert!(tree.validate().is_ok());
+        assert_eq!(tree.len(), 200 - 67);
+        assert_eq!(tree.get(&5), vec![1002]);
+    }
+
+    #[test]
+    fn test_bulk_load_rejects_bad_input() {
+        assert_eq!(
+            BTreeIndex::bulk_load(4, false, [(1, 1), (3, 2), (2, 3)]).unwrap_err(),
+            IndexError::UnsortedInput
+        );
+        assert_eq!(
+            BTreeIndex::bulk_load(4, true, [(1, 1), (2, 2), (2, 3)]).unwrap_err(),
+            IndexError::DuplicateKey
+        );
+        assert!(BTreeIndex::bulk_load(4, true, Vec::<(i32, RowId)>::new())
+            .unwrap()
+            .is_empty());
+    }
 }
@@ -4,6 +4,7 @@
 
 use alloc::vec::Vec;
 use core::cmp::Ordering;
+use cynos_core::{Row, Value};
 
 /// Sort order for index keys.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -25,6 +26,16 @@ impl Order {
     }
 }
 
+/// Placement of null keys relative to non-null keys.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NullsOrder {
+    /// Nulls sort before every non-null key, whatever the key order.
+    #[default]
+    First,
+    /// Nulls sort after every non-null key, whatever the key order.
+    Last,
+}
+
 /// Trait for comparing index keys.
 pub trait Comparator<K> {
     /// Compares two keys according to the comparator's ordering.
@@ -90,6 +101,93 @@ impl<K: Ord> Comparator<K> for SimpleComparator {
     }
 }
 
+/// A comparator for `Value` keys that ignores ASCII case in strings.
+///
+/// Strings are compared with ASCII letters lowercased, so "alice" and
+/// "Alice" sort next to each other. Strings that are equal apart from case
+/// are then ordered by their raw bytes, which keeps the order total and the
+/// iteration order of an index reproducible. Non-string values use the
+/// normal `Value` ordering.
+#[derive(Clone, Debug)]
+pub struct CaseInsensitiveComparator {
+    order: Order,
+}
+
+impl CaseInsensitiveComparator {
+    /// Creates a new case-insensitive comparator with the given order.
+    pub fn new(order: Order) -> Self {
+        Self { order }
+    }
+
+    /// Creates an ascending comparator.
+    pub fn asc() -> Self {
+        Self::new(Order::Asc)
+    }
+
+    /// Creates a descending comparator.
+    pub fn desc() -> Self {
+        Self::new(Order::Desc)
+    }
+
+    /// Returns the order of this comparator.
+    pub fn order(&self) -> Order {
+        self.order
+    }
+}
+
+impl Comparator<Value> for CaseInsensitiveComparator {
+    fn compare(&self, a: &Value, b: &Value) -> Ordering {
+        let ord = match (a, b) {
+            (Value::String(a), Value::String(b)) => a
+                .bytes()
+                .map(|c| c.to_ascii_lowercase())
+                .cmp(b.bytes().map(|c| c.to_ascii_lowercase()))
+                .then_with(|| a.as_bytes().cmp(b.as_bytes())),
+            _ => a.cmp(b),
+        };
+        self.order.apply(ord)
+    }
+}
+
+/// A `Value` index key ordered by `CaseInsensitiveComparator::asc()`.
+///
+/// `BTreeIndex` orders keys by their `Ord`, so wrapping keys in this type
+/// gives a case-insensitive index; see `BTreeIndex::case_insensitive`.
+#[derive(Clone, Debug)]
+pub struct CaseInsensitiveKey(pub Value);
+
+impl CaseInsensitiveKey {
+    /// Returns the smallest and largest keys that equal `s` apart from ASCII
+    /// case: the all-uppercase and all-lowercase spellings. An inclusive range
+    /// between them matches every case variant of `s`.
+    pub fn fold_bounds(s: &str) -> (Self, Self) {
+        (
+            Self(Value::String(s.to_ascii_uppercase())),
+            Self(Value::String(s.to_ascii_lowercase())),
+        )
+    }
+}
+
+impl PartialEq for CaseInsensitiveKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for CaseInsensitiveKey {}
+
+impl PartialOrd for CaseInsensitiveKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CaseInsensitiveKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        CaseInsensitiveComparator::asc().compare(&self.0, &other.0)
+    }
+}
+
 /// A comparator for multi-key indexes (composite keys).
 #[derive(Clone, Debug)]
 pub struct MultiKeyComparator {
@@ -135,37 +233,59 @@ impl<K: Ord> Comparator<Vec<K>> for MultiKeyComparator {
 }
 
 /// A comparator for multi-key indexes that handles null values.
-/// Null values are sorted before non-null values.
+/// By default null values are sorted before non-null values; see
+/// `with_nulls_order` to place them per key.
 #[derive(Clone, Debug)]
 pub struct MultiKeyComparatorWithNull {
     orders: Vec<Order>,
+    nulls: Vec<NullsOrder>,
 }
 
 impl MultiKeyComparatorWithNull {
     /// Creates a new multi-key comparator with null handling.
     pub fn new(orders: Vec<Order>) -> Self {
-        Self { orders }
+        let nulls = alloc::vec![NullsOrder::First; orders.len()];
+        Self { orders, nulls }
+    }
+
+    /// Creates a comparator that places nulls per key. Keys beyond the end
+    /// of `nulls` sort nulls first.
+    pub fn with_nulls_order(orders: Vec<Order>, nulls: Vec<NullsOrder>) -> Self {
+        Self { orders, nulls }
     }
 
     /// Returns the orders of this comparator.
     pub fn orders(&self) -> &[Order] {
         &self.orders
     }
+
+    /// Returns the null placement of each key.
+    pub fn nulls_orders(&self) -> &[NullsOrder] {
+        &self.nulls
+    }
+
+    /// Compares the `i`-th key of two composite keys, where `None` is null.
+    #[inline]
+    pub fn compare_key<K: Ord>(&self, i: usize, a: Option<&K>, b: Option<&K>) -> Ordering {
+        let nulls_first = self.nulls.get(i).copied().unwrap_or_default() == NullsOrder::First;
+        match (a, b) {
+            (Some(av), Some(bv)) => self.orders[i].apply(av.cmp(bv)),
+            (None, Some(_)) if nulls_first => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) if nulls_first => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (None, None) => Ordering::Equal,
+        }
+    }
 }
 
 impl<K: Ord> Comparator<Vec<Option<K>>> for MultiKeyComparatorWithNull {
     fn compare(&self, a: &Vec<Option<K>>, b: &Vec<Option<K>>) -> Ordering {
-        for (i, order) in self.orders.iter().enumerate() {
+        for i in 0..self.orders.len() {
             let a_val = a.get(i).and_then(|v| v.as_ref());
             let b_val = b.get(i).and_then(|v| v.as_ref());
 
-            let cmp = match (a_val, b_val) {
-                (Some(av), Some(bv)) => order.apply(av.cmp(bv)),
-                (None, Some(_)) => Ordering::Less,
-                (Some(_), None) => Ordering::Greater,
-                (None, None) => Ordering::Equal,
-            };
-
+            let cmp = self.compare_key(i, a_val, b_val);
             if cmp != Ordering::Equal {
                 return cmp;
             }
@@ -174,6 +294,28 @@ impl<K: Ord> Comparator<Vec<Option<K>>> for MultiKeyComparatorWithNull {
     }
 }
 
+/// Sorts rows by `(column index, order, null placement)` keys, in priority
+/// order, without building a query plan. The sort is stable, and a NULL or
+/// missing column value counts as null.
+pub fn sort_rows(rows: &mut [Row], keys: &[(usize, Order, NullsOrder)]) {
+    let comparator = MultiKeyComparatorWithNull::with_nulls_order(
+        keys.iter().map(|&(_, order, _)| order).collect(),
+        keys.iter().map(|&(_, _, nulls)| nulls).collect(),
+    );
+
+    fn key(row: &Row, column: usize) -> Option<&Value> {
+        row.get(column).filter(|value| !value.is_null())
+    }
+
+    rows.sort_by(|a, b| {
+        keys.iter()
+            .enumerate()
+            .map(|(i, &(column, _, _))| comparator.compare_key(i, key(a, column), key(b, column)))
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -380,6 +522,56 @@ mod tests {
         assert_eq!(cmp.compare(&c, &d), Ordering::Less);
     }
 
+    #[test]
+    fn test_multi_key_nulls_last() {
+        let cmp = MultiKeyComparatorWithNull::with_nulls_order(
+            vec![Order::Desc, Order::Asc],
+            vec![NullsOrder::Last, NullsOrder::First],
+        );
+
+        let a: Vec<Option<i32>> = vec![None, Some(1)];
+        let b: Vec<Option<i32>> = vec![Some(1), Some(1)];
+        assert_eq!(cmp.compare(&a, &b), Ordering::Greater);
+
+        let c: Vec<Option<i32>> = vec![Some(1), None];
+        assert_eq!(cmp.compare(&c, &b), Ordering::Less);
+    }
+
+    #[test]
+    fn test_sort_rows() {
+        let row = |id: u64, dept: Value, score: Value| Row::new(id, vec![dept, score]);
+        let mut rows = vec![
+            row(1, Value::String("b".into()), Value::Int32(10)),
+            row(2, Value::Null, Value::Int32(50)),
+            row(3, Value::String("a".into()), Value::Null),
+            row(4, Value::String("a".into()), Value::Int32(20)),
+            row(5, Value::String("b".into()), Value::Int32(30)),
+            row(6, Value::String("a".into()), Value::Int32(5)),
+        ];
+
+        // dept ASC NULLS LAST, score DESC NULLS FIRST
+        sort_rows(
+            &mut rows,
+            &[
+                (0, Order::Asc, NullsOrder::Last),
+                (1, Order::Desc, NullsOrder::First),
+            ],
+        );
+        let ids: Vec<u64> = rows.iter().map(|r| r.id()).collect();
+        assert_eq!(ids, vec![3, 4, 6, 5, 1, 2]);
+
+        // dept DESC NULLS FIRST, score ASC NULLS LAST
+        sort_rows(
+            &mut rows,
+            &[
+                (0, Order::Desc, NullsOrder::First),
+                (1, Order::Asc, NullsOrder::Last),
+            ],
+        );
+        let ids: Vec<u64> = rows.iter().map(|r| r.id()).collect();
+        assert_eq!(ids, vec![2, 1, 5, 6, 4, 3]);
+    }
+
     /// Test simple comparator with strings
     #[test]
     fn test_simple_comparator_strings() {
@@ -436,4 +628,36 @@ mod tests {
         // Second key differs: 2 < 3
         assert_eq!(cmp.compare(&a, &c), Ordering::Less);
     }
+
+    #[test]
+    fn test_case_insensitive_comparator() {
+        let cmp = CaseInsensitiveComparator::asc();
+        let s = |v: &str| Value::String(v.into());
+
+        assert_eq!(cmp.compare(&s("alice"), &s("Bob")), Ordering::Less);
+        assert_eq!(cmp.compare(&s("ALICE"), &s("bob")), Ordering::Less);
+        // Equal-fold strings are ordered by raw bytes, uppercase first.
+        assert_eq!(cmp.compare(&s("Alice"), &s("alice")), Ordering::Less);
+        assert_eq!(cmp.compare(&s("alice"), &s("alice")), Ordering::Equal);
+        // Non-strings keep the normal ordering.
+        assert_eq!(
+            cmp.compare(&Value::Int64(2), &Value::Int64(10)),
+            Ordering::Less
+        );
+        assert_eq!(cmp.compare(&Value::Null, &s("a")), Value::Null.cmp(&s("a")));
+
+        let desc = CaseInsensitiveComparator::desc();
+        assert_eq!(desc.compare(&s("alice"), &s("Bob")), Ordering::Greater);
+
+        let mut names: Vec<_> = ["bob", "Alice", "carol", "alice", "ALICE", "Bob"]
+            .iter()
+            .map(|n| s(n))
+            .collect();
+        names.sort_by(|a, b| cmp.compare(a, b));
+        let sorted: Vec<_> = names.iter().filter_map(|v| v.as_str()).collect();
+        assert_eq!(
+            sorted,
+            vec!["ALICE", "Alice", "alice", "Bob", "bob", "carol"]
+        );
+    }
 }
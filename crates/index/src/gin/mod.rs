@@ -9,6 +9,7 @@ pub use posting::PostingList;
 
 use crate::stats::IndexStats;
 use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
 use alloc::string::String;
 use alloc::vec::Vec;
 use cynos_core::RowId;
@@ -80,11 +81,16 @@ impl GinIndex {
 
     /// Indexes a key for a given row.
     pub fn add_key(&mut self, key: String, row_id: RowId) {
-        self.key_index
+        let inserted = self
+            .key_index
             .entry(key)
             .or_insert_with(PostingList::new)
             .add(row_id);
-        self.stats.add_rows(1);
+        if inserted {
+            self.stats.add_rows(1);
+        }
+        #[cfg(feature = "debug-invariants")]
+        self.assert_valid();
     }
 
     /// Indexes a key-value pair for a given row.
@@ -123,6 +129,8 @@ impl GinIndex {
                 self.key_index.remove(key);
             }
         }
+        #[cfg(feature = "debug-invariants")]
+        self.assert_valid();
     }
 
     /// Removes a key-value entry for a given row.
@@ -193,6 +201,30 @@ impl GinIndex {
         }
     }
 
+    /// Gets the row IDs in `all_row_ids` that do NOT contain the given
+    /// key-value pair (for `NOT @>` exclusion filters).
+    ///
+    /// `all_row_ids` is the universe to complement against, normally the
+    /// table's live row IDs. The result is sorted and deduplicated; if the
+    /// pair is not indexed it is the whole universe.
+    pub fn get_not_by_key_value(
+        &self,
+        key: &str,
+        value: &str,
+        all_row_ids: &[RowId],
+    ) -> Vec<RowId> {
+        let mut universe = PostingList::new();
+        for &row_id in all_row_ids {
+            universe.add(row_id);
+        }
+
+        let pair = (key.into(), value.into());
+        match self.key_value_index.get(&pair) {
+            Some(posting) => universe.difference(posting).to_vec(),
+            None => universe.to_vec(),
+        }
+    }
+
     /// Gets all row IDs that contain ALL of the given keys (AND query).
     pub fn get_by_keys_all(&self, keys: &[&str]) -> Vec<RowId> {
         if keys.is_empty() {
@@ -233,6 +265,29 @@ impl GinIndex {
         result.to_vec()
     }
 
+    /// Gets all row IDs that contain ANY of the given keys, each with the
+    /// number of distinct keys it contains.
+    ///
+    /// Results are ranked by match count, highest first; rows with equal
+    /// counts are in row ID order.
+    pub fn get_by_keys_ranked(&self, keys: &[&str]) -> Vec<(RowId, usize)> {
+        let keys: BTreeSet<&str> = keys.iter().copied().collect();
+        let mut counts: BTreeMap<RowId, usize> = BTreeMap::new();
+
+        for key in keys {
+            if let Some(posting) = self.key_index.get(key) {
+                for row_id in posting.iter() {
+                    *counts.entry(row_id).or_insert(0) += 1;
+                }
+            }
+        }
+
+        let mut ranked: Vec<(RowId, usize)> = counts.into_iter().collect();
+        // Stable sort keeps row ID order within equal counts.
+        ranked.sort_by_key(|&(_, count)| core::cmp::Reverse(count));
+        ranked
+    }
+
     /// Gets all row IDs that contain ALL of the given key-value pairs (AND query).
     pub fn get_by_key_values_all(&self, pairs: &[(&str, &str)]) -> Vec<RowId> {
         if pairs.is_empty() {
@@ -261,6 +316,39 @@ impl GinIndex {
         result
     }
 
+    /// Gets all row IDs that contain ANY of the given key-value pairs (OR query).
+    ///
+    /// Once a key turns out to have no indexed values at all, the remaining
+    /// pairs on that key are skipped without a lookup.
+    pub fn get_by_key_values_any(&self, pairs: &[(&str, &str)]) -> Vec<RowId> {
+        let mut result = PostingList::new();
+        let mut absent_keys: BTreeSet<&str> = BTreeSet::new();
+
+        for (key, value) in pairs {
+            if absent_keys.contains(key) {
+                continue;
+            }
+            let pair = ((*key).into(), (*value).into());
+            match self.key_value_index.get(&pair) {
+                Some(posting) => result = result.union(posting),
+                None if !self.has_key_values(key) => {
+                    absent_keys.insert(key);
+                }
+                None => {}
+            }
+        }
+
+        result.to_vec()
+    }
+
+    /// Returns whether any key-value pair is indexed under `key`.
+    fn has_key_values(&self, key: &str) -> bool {
+        self.key_value_index
+            .range((key.into(), String::new())..)
+            .next()
+            .is_some_and(|((k, _), _)| k == key)
+    }
+
     /// Visits row IDs that contain all of the given key-value pairs.
     /// Return `false` from the visitor to stop early.
     pub fn visit_by_key_values_all<F>(&self, pairs: &[(&str, &str)], mut visitor: F)
@@ -296,6 +384,49 @@ impl GinIndex {
         self.key_index.is_empty() && self.key_value_index.is_empty()
     }
 
+    /// Checks the invariants of the index.
+    ///
+    /// Posting lists must be non-empty, and the row count in the stats must
+    /// match the number of (key, row) entries in the key index.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut row_count = 0;
+        for (key, posting) in &self.key_index {
+            if posting.is_empty() {
+                return Err(format!("key '{}' has an empty posting list", key));
+            }
+            row_count += posting.len();
+        }
+
+        if let Some(((key, value), _)) = self
+            .key_value_index
+            .iter()
+            .find(|(_, posting)| posting.is_empty())
+        {
+            return Err(format!(
+                "pair ('{}', '{}') has an empty posting list",
+                key, value
+            ));
+        }
+
+        if row_count != self.stats.total_rows() {
+            return Err(format!(
+                "stats report {} rows but the key index holds {}",
+                self.stats.total_rows(),
+                row_count
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Panics if the index violates its invariants.
+    #[cfg(feature = "debug-invariants")]
+    fn assert_valid(&self) {
+        if let Err(error) = self.validate() {
+            panic!("GIN index invariant violated: {}", error);
+        }
+    }
+
     /// Estimates the cost of a key lookup.
     pub fn cost_key(&self, key: &str) -> usize {
         self.key_index.get(key).map(|p| p.len()).unwrap_or(0)
@@ -423,6 +554,25 @@ mod tests {
         assert_eq!(result, vec![3]);
     }
 
+    #[test]
+    fn test_gin_get_by_keys_ranked() {
+        let mut gin = GinIndex::new();
+        gin.add_keys(["rust".into(), "wasm".into(), "db".into()], 1);
+        gin.add_keys(["rust".into()], 2);
+        gin.add_keys(["wasm".into(), "db".into()], 3);
+        gin.add_keys(["go".into()], 4);
+        gin.add_keys(["db".into()], 5);
+
+        let ranked = gin.get_by_keys_ranked(&["rust", "wasm", "db"]);
+        assert_eq!(ranked, vec![(1, 3), (3, 2), (2, 1), (5, 1)]);
+
+        // Repeating a key does not inflate the counts.
+        let ranked = gin.get_by_keys_ranked(&["db", "db", "rust"]);
+        assert_eq!(ranked, vec![(1, 2), (2, 1), (3, 1), (5, 1)]);
+
+        assert!(gin.get_by_keys_ranked(&["missing"]).is_empty());
+    }
+
     #[test]
     fn test_gin_visit_by_key_value_stops_early() {
         let mut gin = GinIndex::new();
@@ -464,6 +614,55 @@ mod tests {
         assert_eq!(result, vec![1, 2]);
     }
 
+    #[test]
+    fn test_gin_get_by_key_values_any() {
+        let mut gin = GinIndex::new();
+        gin.add_key_value("status".into(), "active".into(), 3);
+        gin.add_key_value("status".into(), "active".into(), 1);
+        gin.add_key_value("status".into(), "pending".into(), 2);
+        gin.add_key_value("status".into(), "pending".into(), 3);
+        gin.add_key_value("status".into(), "closed".into(), 4);
+        gin.add_key_value("type".into(), "admin".into(), 5);
+
+        // Row 3 matches both pairs but appears once, in row ID order.
+        let result = gin.get_by_key_values_any(&[("status", "pending"), ("status", "active")]);
+        assert_eq!(result, vec![1, 2, 3]);
+
+        let result = gin.get_by_key_values_any(&[
+            ("missing", "x"),
+            ("status", "archived"),
+            ("missing", "y"),
+            ("type", "admin"),
+        ]);
+        assert_eq!(result, vec![5]);
+
+        assert!(gin
+            .get_by_key_values_any(&[("status", "archived"), ("missing", "x")])
+            .is_empty());
+        assert!(gin.get_by_key_values_any(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_gin_get_not_by_key_value() {
+        let mut gin = GinIndex::new();
+        gin.add_key_value("archived".into(), "true".into(), 2);
+        gin.add_key_value("archived".into(), "true".into(), 4);
+        gin.add_key_value("archived".into(), "false".into(), 1);
+
+        let result = gin.get_not_by_key_value("archived", "true", &[5, 1, 2, 3, 4]);
+        assert_eq!(result, vec![1, 3, 5]);
+
+        // An unindexed pair excludes nothing; duplicates collapse.
+        let result = gin.get_not_by_key_value("archived", "maybe", &[3, 1, 3, 2, 1]);
+        assert_eq!(result, vec![1, 2, 3]);
+
+        // Matches outside the universe are ignored.
+        let result = gin.get_not_by_key_value("archived", "true", &[4, 6]);
+        assert_eq!(result, vec![6]);
+
+        assert!(gin.get_not_by_key_value("archived", "true", &[]).is_empty());
+    }
+
     #[test]
     fn test_gin_get_by_key_values_all_order_independent() {
         let mut gin = GinIndex::new();
@@ -583,4 +782,20 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_gin_validate() {
+        let mut gin = GinIndex::new();
+        gin.add_key("name".into(), 1);
+        gin.add_key("name".into(), 1);
+        gin.add_key("age".into(), 2);
+        gin.add_key_value("name".into(), "alice".into(), 1);
+        gin.remove_key("age", 2);
+        gin.validate().unwrap();
+        assert_eq!(gin.stats().total_rows(), 1);
+
+        gin.key_value_index
+            .insert(("name".into(), "bob".into()), PostingList::new());
+        assert!(gin.validate().unwrap_err().contains("('name', 'bob')"));
+    }
 }
@@ -1,79 +1,428 @@
 //! Posting list implementation for GIN index.
 //!
 //! A posting list is a sorted list of row IDs that contain a particular key.
-
-use alloc::collections::BTreeSet;
+//!
+//! Row IDs are split roaring-style into a high part, which selects a chunk of
+//! 65536 consecutive IDs, and a 16-bit low part stored in that chunk's
+//! container. A chunk holds a sorted `Vec<u16>` while it is sparse and switches
+//! to a fixed 8 KiB bitmap once it passes `ARRAY_MAX` entries, so dense
+//! postings such as `active = true` over sequential row IDs cost about one bit
+//! per row and combine word by word.
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
 use alloc::vec::Vec;
+use core::mem::size_of;
 use cynos_core::RowId;
 
+/// Largest number of entries an array container holds before it becomes a
+/// bitmap. At this size both representations take 8 KiB.
+const ARRAY_MAX: usize = 4096;
+
+/// A bitmap container only turns back into an array below this size, so a
+/// chunk hovering around `ARRAY_MAX` does not flip on every add and remove.
+const ARRAY_SHRINK: usize = ARRAY_MAX / 2;
+
+/// Number of 64-bit words in a bitmap container.
+const BITMAP_WORDS: usize = 1024;
+
+type Bitmap = Box<[u64; BITMAP_WORDS]>;
+
 /// A posting list storing row IDs in sorted order.
 ///
-/// Uses `BTreeSet` for `no_std` compatibility instead of `RoaringBitmap`.
-/// For production use with large datasets, consider evolving this into a
-/// hybrid representation while keeping the current API stable:
-/// - `Small(Vec<RowId>)` for short postings, which improves cache locality and
-///   makes two-pointer intersections cheap.
-/// - `Large(CompressedBitmap)` for wide postings, which can make AND/OR/DIFF
-///   operations much faster on hot terms.
-///
-/// Because `RowId` is a global `u64`, avoid dense bitmaps unless there is a
-/// table-local ID remapping layer; sparse or compressed bitmaps are the safer
-/// direction here.
+/// The representation is chosen per 65536-ID chunk and is not observable
+/// through the API; see the module docs.
 #[derive(Debug, Clone, Default)]
 pub struct PostingList {
-    /// Sorted set of row IDs.
-    rows: BTreeSet<RowId>,
+    /// Containers keyed by the high bits of the row ID (`row_id >> 16`).
+    containers: BTreeMap<u64, Container>,
+    /// Total number of row IDs across all containers.
+    len: usize,
+}
+
+/// The low 16 bits of the row IDs that share one chunk.
+#[derive(Debug, Clone)]
+enum Container {
+    /// Sorted, deduplicated low bits.
+    Array(Vec<u16>),
+    /// One bit per low value, with the number of bits set.
+    Bitmap { words: Bitmap, len: usize },
+}
+
+#[inline]
+fn split(row_id: RowId) -> (u64, u16) {
+    (row_id >> 16, row_id as u16)
+}
+
+#[inline]
+fn join(high: u64, low: u16) -> RowId {
+    (high << 16) | RowId::from(low)
+}
+
+fn empty_bitmap() -> Bitmap {
+    Box::new([0u64; BITMAP_WORDS])
+}
+
+impl Container {
+    fn len(&self) -> usize {
+        match self {
+            Container::Array(values) => values.len(),
+            Container::Bitmap { len, .. } => *len,
+        }
+    }
+
+    fn contains(&self, low: u16) -> bool {
+        match self {
+            Container::Array(values) => values.binary_search(&low).is_ok(),
+            Container::Bitmap { words, .. } => {
+                words[usize::from(low) / 64] & (1 << (low % 64)) != 0
+            }
+        }
+    }
+
+    fn add(&mut self, low: u16) -> bool {
+        match self {
+            Container::Array(values) => match values.binary_search(&low) {
+                Ok(_) => false,
+                Err(pos) => {
+                    values.insert(pos, low);
+                    if values.len() > ARRAY_MAX {
+                        *self = Self::bitmap_from_sorted(values);
+                    }
+                    true
+                }
+            },
+            Container::Bitmap { words, len } => {
+                let word = &mut words[usize::from(low) / 64];
+                let bit = 1 << (low % 64);
+                if *word & bit != 0 {
+                    return false;
+                }
+                *word |= bit;
+                *len += 1;
+                true
+            }
+        }
+    }
+
+    fn remove(&mut self, low: u16) -> bool {
+        match self {
+            Container::Array(values) => match values.binary_search(&low) {
+                Ok(pos) => {
+                    values.remove(pos);
+                    true
+                }
+                Err(_) => false,
+            },
+            Container::Bitmap { words, len } => {
+                let word = &mut words[usize::from(low) / 64];
+                let bit = 1 << (low % 64);
+                if *word & bit == 0 {
+                    return false;
+                }
+                *word &= !bit;
+                *len -= 1;
+                if *len < ARRAY_SHRINK {
+                    let mut values = Vec::with_capacity(*len);
+                    values.extend(self.iter());
+                    *self = Container::Array(values);
+                }
+                true
+            }
+        }
+    }
+
+    fn iter(&self) -> ContainerIter<'_> {
+        match self {
+            Container::Array(values) => ContainerIter::Array(values.iter()),
+            Container::Bitmap { words, .. } => ContainerIter::Bitmap {
+                words,
+                index: 0,
+                current: words[0],
+            },
+        }
+    }
+
+    fn bitmap_from_sorted(values: &[u16]) -> Self {
+        let mut words = empty_bitmap();
+        for &low in values {
+            words[usize::from(low) / 64] |= 1 << (low % 64);
+        }
+        Container::Bitmap {
+            words,
+            len: values.len(),
+        }
+    }
+
+    /// Wraps the result of a word-wise operation, falling back to an array
+    /// when it is sparse. Returns `None` when no bit is set.
+    fn from_words(words: Bitmap) -> Option<Self> {
+        let len = words.iter().map(|w| w.count_ones() as usize).sum();
+        match len {
+            0 => None,
+            len if len <= ARRAY_MAX => {
+                let container = Container::Bitmap { words, len };
+                Some(Container::Array(container.iter().collect()))
+            }
+            len => Some(Container::Bitmap { words, len }),
+        }
+    }
+
+    /// Wraps sorted low bits, switching to a bitmap when they are dense.
+    fn from_sorted(values: Vec<u16>) -> Option<Self> {
+        match values.len() {
+            0 => None,
+            len if len <= ARRAY_MAX => Some(Container::Array(values)),
+            _ => Some(Self::bitmap_from_sorted(&values)),
+        }
+    }
+
+    fn intersect(&self, other: &Container) -> Option<Container> {
+        match (self, other) {
+            (Container::Array(a), Container::Array(b)) => {
+                let mut result = Vec::with_capacity(a.len().min(b.len()));
+                let (mut i, mut j) = (0, 0);
+                while i < a.len() && j < b.len() {
+                    match a[i].cmp(&b[j]) {
+                        core::cmp::Ordering::Less => i += 1,
+                        core::cmp::Ordering::Greater => j += 1,
+                        core::cmp::Ordering::Equal => {
+                            result.push(a[i]);
+                            i += 1;
+                            j += 1;
+                        }
+                    }
+                }
+                Self::from_sorted(result)
+            }
+            (Container::Array(values), bitmap @ Container::Bitmap { .. })
+            | (bitmap @ Container::Bitmap { .. }, Container::Array(values)) => Self::from_sorted(
+                values
+                    .iter()
+                    .copied()
+                    .filter(|&low| bitmap.contains(low))
+                    .collect(),
+            ),
+            (Container::Bitmap { words: a, .. }, Container::Bitmap { words: b, .. }) => {
+                let mut words = empty_bitmap();
+                for (i, word) in words.iter_mut().enumerate() {
+                    *word = a[i] & b[i];
+                }
+                Self::from_words(words)
+            }
+        }
+    }
+
+    fn union(&self, other: &Container) -> Container {
+        match (self, other) {
+            (Container::Array(a), Container::Array(b)) => {
+                let mut result = Vec::with_capacity(a.len() + b.len());
+                let (mut i, mut j) = (0, 0);
+                while i < a.len() && j < b.len() {
+                    match a[i].cmp(&b[j]) {
+                        core::cmp::Ordering::Less => {
+                            result.push(a[i]);
+                            i += 1;
+                        }
+                        core::cmp::Ordering::Greater => {
+                            result.push(b[j]);
+                            j += 1;
+                        }
+                        core::cmp::Ordering::Equal => {
+                            result.push(a[i]);
+                            i += 1;
+                            j += 1;
+                        }
+                    }
+                }
+                result.extend_from_slice(&a[i..]);
+                result.extend_from_slice(&b[j..]);
+                Self::from_sorted(result).expect("union of non-empty containers")
+            }
+            (Container::Array(values), Container::Bitmap { words, .. })
+            | (Container::Bitmap { words, .. }, Container::Array(values)) => {
+                let mut words = words.clone();
+                for &low in values {
+                    words[usize::from(low) / 64] |= 1 << (low % 64);
+                }
+                Self::from_words(words).expect("union of non-empty containers")
+            }
+            (Container::Bitmap { words: a, .. }, Container::Bitmap { words: b, .. }) => {
+                let mut words = a.clone();
+                for (word, other) in words.iter_mut().zip(b.iter()) {
+                    *word |= other;
+                }
+                Self::from_words(words).expect("union of non-empty containers")
+            }
+        }
+    }
+
+    fn difference(&self, other: &Container) -> Option<Container> {
+        match (self, other) {
+            (Container::Array(values), _) => Self::from_sorted(
+                values
+                    .iter()
+                    .copied()
+                    .filter(|&low| !other.contains(low))
+                    .collect(),
+            ),
+            (Container::Bitmap { words, .. }, Container::Array(values)) => {
+                let mut words = words.clone();
+                for &low in values {
+                    words[usize::from(low) / 64] &= !(1 << (low % 64));
+                }
+                Self::from_words(words)
+            }
+            (Container::Bitmap { words: a, .. }, Container::Bitmap { words: b, .. }) => {
+                let mut words = a.clone();
+                for (word, other) in words.iter_mut().zip(b.iter()) {
+                    *word &= !other;
+                }
+                Self::from_words(words)
+            }
+        }
+    }
+
+    /// Heap bytes owned by the container.
+    fn heap_bytes(&self) -> usize {
+        match self {
+            Container::Array(values) => values.capacity() * size_of::<u16>(),
+            Container::Bitmap { .. } => BITMAP_WORDS * size_of::<u64>(),
+        }
+    }
+}
+
+/// Ascending iterator over the low bits of one container.
+enum ContainerIter<'a> {
+    Array(core::slice::Iter<'a, u16>),
+    Bitmap {
+        words: &'a [u64; BITMAP_WORDS],
+        index: usize,
+        current: u64,
+    },
+}
+
+impl Iterator for ContainerIter<'_> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<u16> {
+        match self {
+            ContainerIter::Array(values) => values.next().copied(),
+            ContainerIter::Bitmap {
+                words,
+                index,
+                current,
+            } => loop {
+                if *current != 0 {
+                    let bit = current.trailing_zeros() as usize;
+                    *current &= *current - 1;
+                    return Some((*index * 64 + bit) as u16);
+                }
+                *index += 1;
+                if *index >= BITMAP_WORDS {
+                    return None;
+                }
+                *current = words[*index];
+            },
+        }
+    }
 }
 
 impl PostingList {
     /// Creates a new empty posting list.
     pub fn new() -> Self {
         Self {
-            rows: BTreeSet::new(),
+            containers: BTreeMap::new(),
+            len: 0,
         }
     }
 
     /// Adds a row ID to the posting list.
-    pub fn add(&mut self, row_id: RowId) {
-        self.rows.insert(row_id);
+    /// Returns true if the row was not already present.
+    pub fn add(&mut self, row_id: RowId) -> bool {
+        let (high, low) = split(row_id);
+        let added = self
+            .containers
+            .entry(high)
+            .or_insert_with(|| Container::Array(Vec::new()))
+            .add(low);
+        if added {
+            self.len += 1;
+        }
+        added
     }
 
     /// Removes a row ID from the posting list.
     /// Returns true if the row was present.
     pub fn remove(&mut self, row_id: RowId) -> bool {
-        self.rows.remove(&row_id)
+        let (high, low) = split(row_id);
+        let Some(container) = self.containers.get_mut(&high) else {
+            return false;
+        };
+        if !container.remove(low) {
+            return false;
+        }
+        if container.len() == 0 {
+            self.containers.remove(&high);
+        }
+        self.len -= 1;
+        true
     }
 
     /// Checks if the posting list contains a row ID.
     pub fn contains(&self, row_id: RowId) -> bool {
-        self.rows.contains(&row_id)
+        let (high, low) = split(row_id);
+        self.containers
+            .get(&high)
+            .is_some_and(|container| container.contains(low))
     }
 
     /// Returns the number of row IDs in the posting list.
     pub fn len(&self) -> usize {
-        self.rows.len()
+        self.len
     }
 
     /// Returns true if the posting list is empty.
     pub fn is_empty(&self) -> bool {
-        self.rows.is_empty()
+        self.len == 0
     }
 
     /// Converts the posting list to a vector.
     pub fn to_vec(&self) -> Vec<RowId> {
-        self.rows.iter().copied().collect()
+        let mut result = Vec::with_capacity(self.len);
+        result.extend(self.iter());
+        result
     }
 
     /// Returns an iterator over the row IDs.
     pub fn iter(&self) -> impl Iterator<Item = RowId> + '_ {
-        self.rows.iter().copied()
+        self.containers
+            .iter()
+            .flat_map(|(&high, container)| container.iter().map(move |low| join(high, low)))
+    }
+
+    /// Returns the approximate memory used by the posting list in bytes,
+    /// including its containers.
+    pub fn memory_bytes(&self) -> usize {
+        size_of::<Self>()
+            + self
+                .containers
+                .values()
+                .map(|container| size_of::<(u64, Container)>() + container.heap_bytes())
+                .sum::<usize>()
     }
 
     /// Computes the intersection of two posting lists.
     pub fn intersect(&self, other: &PostingList) -> PostingList {
-        PostingList {
-            rows: self.rows.intersection(&other.rows).copied().collect(),
+        let mut result = PostingList::new();
+        for (high, container) in &self.containers {
+            if let Some(other) = other.containers.get(high) {
+                if let Some(container) = container.intersect(other) {
+                    result.insert_container(*high, container);
+                }
+            }
         }
+        result
     }
 
     /// Intersects this posting list with a sorted list of candidate row IDs.
@@ -86,26 +435,21 @@ impl PostingList {
         }
 
         let mut result = Vec::with_capacity(core::cmp::min(candidates.len(), self.len()));
-        let mut candidate_idx = 0usize;
-        let mut posting_iter = self.rows.iter().copied();
-        let mut posting_row = posting_iter.next();
-
-        while candidate_idx < candidates.len() {
-            let candidate = candidates[candidate_idx];
-
-            match posting_row {
-                Some(current) if current < candidate => {
-                    posting_row = posting_iter.next();
-                }
-                Some(current) if current == candidate => {
-                    result.push(candidate);
-                    candidate_idx += 1;
-                    posting_row = posting_iter.next();
-                }
-                Some(_) => {
-                    candidate_idx += 1;
+        let mut chunk: Option<(u64, Option<&Container>)> = None;
+
+        for &candidate in candidates {
+            let (high, low) = split(candidate);
+            // Candidates are sorted, so each chunk is looked up once.
+            let container = match chunk {
+                Some((current, container)) if current == high => container,
+                _ => {
+                    let container = self.containers.get(&high);
+                    chunk = Some((high, container));
+                    container
                 }
-                None => break,
+            };
+            if container.is_some_and(|container| container.contains(low)) {
+                result.push(candidate);
             }
         }
 
@@ -114,16 +458,38 @@ impl PostingList {
 
     /// Computes the union of two posting lists.
     pub fn union(&self, other: &PostingList) -> PostingList {
-        PostingList {
-            rows: self.rows.union(&other.rows).copied().collect(),
+        let mut result = self.clone();
+        for (high, container) in &other.containers {
+            let merged = match result.containers.get(high) {
+                Some(existing) => {
+                    result.len -= existing.len();
+                    existing.union(container)
+                }
+                None => container.clone(),
+            };
+            result.insert_container(*high, merged);
         }
+        result
     }
 
     /// Computes the difference of two posting lists (self - other).
     pub fn difference(&self, other: &PostingList) -> PostingList {
-        PostingList {
-            rows: self.rows.difference(&other.rows).copied().collect(),
+        let mut result = PostingList::new();
+        for (high, container) in &self.containers {
+            let remaining = match other.containers.get(high) {
+                Some(other) => container.difference(other),
+                None => Some(container.clone()),
+            };
+            if let Some(container) = remaining {
+                result.insert_container(*high, container);
+            }
         }
+        result
+    }
+
+    fn insert_container(&mut self, high: u64, container: Container) {
+        self.len += container.len();
+        self.containers.insert(high, container);
     }
 }
 
@@ -253,4 +619,97 @@ mod tests {
         let result = pl.intersect_sorted_candidates(&[1, 2, 3, 4, 8, 9]);
         assert_eq!(result, vec![2, 4, 8]);
     }
+
+    #[test]
+    fn test_dense_posting_list_is_compressed() {
+        let mut pl = PostingList::new();
+        for row_id in 0..200_000u64 {
+            pl.add(row_id);
+        }
+
+        assert_eq!(pl.len(), 200_000);
+        // Four chunks of 8 KiB bitmaps instead of 1.6 MB of u64s.
+        assert!(pl.memory_bytes() < 40_000, "{}", pl.memory_bytes());
+        assert_eq!(pl.to_vec(), (0..200_000u64).collect::<Vec<_>>());
+
+        for row_id in 1_000..199_000u64 {
+            pl.remove(row_id);
+        }
+        assert_eq!(pl.len(), 2_000);
+        // The two surviving chunks shrank back to arrays.
+        assert!(
+            pl.memory_bytes() < 2 * BITMAP_WORDS * 8,
+            "{}",
+            pl.memory_bytes()
+        );
+        assert_eq!(
+            pl.to_vec(),
+            (0..1_000u64).chain(199_000..200_000).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_posting_list_matches_btreeset_across_representations() {
+        use alloc::collections::BTreeSet;
+
+        // Dense runs in some chunks and scattered ids in others, so every
+        // pair of container kinds meets in the set operations.
+        let build = |seed: u64, dense: core::ops::Range<u64>| {
+            let mut pl = PostingList::new();
+            let mut expected = BTreeSet::new();
+            let mut x = seed;
+            for row_id in dense {
+                pl.add(row_id);
+                expected.insert(row_id);
+            }
+            for _ in 0..3_000 {
+                x = x
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                let row_id = (x >> 33) % 300_000;
+                assert_eq!(pl.add(row_id), expected.insert(row_id));
+            }
+            for _ in 0..1_000 {
+                x = x
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                let row_id = (x >> 33) % 300_000;
+                assert_eq!(pl.remove(row_id), expected.remove(&row_id));
+            }
+            assert_eq!(pl.len(), expected.len());
+            (pl, expected)
+        };
+
+        let (a, a_set) = build(1, 0..70_000);
+        let (b, b_set) = build(2, 60_000..140_000);
+
+        assert_eq!(a.to_vec(), a_set.iter().copied().collect::<Vec<_>>());
+        let intersect = a.intersect(&b);
+        assert_eq!(
+            intersect.to_vec(),
+            a_set.intersection(&b_set).copied().collect::<Vec<_>>()
+        );
+        assert_eq!(intersect.len(), a_set.intersection(&b_set).count());
+        let union = a.union(&b);
+        assert_eq!(
+            union.to_vec(),
+            a_set.union(&b_set).copied().collect::<Vec<_>>()
+        );
+        assert_eq!(union.len(), a_set.union(&b_set).count());
+        let difference = a.difference(&b);
+        assert_eq!(
+            difference.to_vec(),
+            a_set.difference(&b_set).copied().collect::<Vec<_>>()
+        );
+        assert_eq!(difference.len(), a_set.difference(&b_set).count());
+
+        let candidates = b.to_vec();
+        assert_eq!(
+            a.intersect_sorted_candidates(&candidates),
+            intersect.to_vec()
+        );
+        for row_id in [0, 65_535, 65_536, 69_999, 70_000, 299_999] {
+            assert_eq!(a.contains(row_id), a_set.contains(&row_id));
+        }
+    }
 }
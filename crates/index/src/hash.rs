@@ -4,6 +4,8 @@
 
 use crate::stats::IndexStats;
 use crate::traits::{Index, IndexError, KeyRange, RangeIndex};
+use alloc::format;
+use alloc::string::String;
 use alloc::vec::Vec;
 use cynos_core::RowId;
 use hashbrown::HashMap;
@@ -46,6 +48,49 @@ impl<K: Eq + core::hash::Hash + Clone> HashIndex<K> {
     pub fn get_all_row_ids(&self) -> Vec<RowId> {
         self.map.values().flatten().copied().collect()
     }
+
+    /// Checks the invariants of the index.
+    ///
+    /// Every key must map to a non-empty list of distinct row IDs (exactly one
+    /// for unique indexes), and the row count in the stats must match.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut row_count = 0;
+        for values in self.map.values() {
+            if values.is_empty() {
+                return Err("a key maps to no row IDs".into());
+            }
+            if self.unique && values.len() > 1 {
+                return Err(format!(
+                    "unique index maps a key to {} row IDs",
+                    values.len()
+                ));
+            }
+            for (i, row_id) in values.iter().enumerate() {
+                if values[..i].contains(row_id) {
+                    return Err(format!("row ID {} is indexed twice under one key", row_id));
+                }
+            }
+            row_count += values.len();
+        }
+
+        if row_count != self.stats.total_rows() {
+            return Err(format!(
+                "stats report {} rows but the index holds {}",
+                self.stats.total_rows(),
+                row_count
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Panics if the index violates its invariants.
+    #[cfg(feature = "debug-invariants")]
+    fn assert_valid(&self) {
+        if let Err(error) = self.validate() {
+            panic!("Hash index invariant violated: {}", error);
+        }
+    }
 }
 
 impl<K: Eq + core::hash::Hash + Clone + Ord> Index<K> for HashIndex<K> {
@@ -56,6 +101,8 @@ impl<K: Eq + core::hash::Hash + Clone + Ord> Index<K> for HashIndex<K> {
 
         self.map.entry(key).or_insert_with(Vec::new).push(value);
         self.stats.add_rows(1);
+        #[cfg(feature = "debug-invariants")]
+        self.assert_valid();
         Ok(())
     }
 
@@ -67,6 +114,8 @@ impl<K: Eq + core::hash::Hash + Clone + Ord> Index<K> for HashIndex<K> {
             self.stats.remove_rows(old_count);
         }
         self.stats.add_rows(1);
+        #[cfg(feature = "debug-invariants")]
+        self.assert_valid();
     }
 
     fn get(&self, key: &K) -> Vec<RowId> {
@@ -94,6 +143,8 @@ impl<K: Eq + core::hash::Hash + Clone + Ord> Index<K> for HashIndex<K> {
                 }
             }
         }
+        #[cfg(feature = "debug-invariants")]
+        self.assert_valid();
     }
 
     fn contains_key(&self, key: &K) -> bool {
@@ -472,4 +523,52 @@ mod tests {
         assert_eq!(index.get(&1), vec![1000]);
         assert_eq!(index.get(&2), vec![2000]);
     }
+
+    /// Test the default count_range against reverse scans
+    #[test]
+    fn test_hash_index_count_range() {
+        let mut index: HashIndex<i32> = HashIndex::new(false);
+        for i in 0..30u64 {
+            index.add((i % 10) as i32, i).unwrap();
+        }
+
+        assert_eq!(
+            index.count_range(Some(&KeyRange::bound(2, 5, false, false)), true),
+            12
+        );
+        assert_eq!(
+            index.count_range(Some(&KeyRange::bound(2, 5, false, false)), false),
+            9
+        );
+        assert_eq!(index.count_range(Some(&KeyRange::only(5)), false), 0);
+        // Degenerate ranges hold none of their upper bound's rows.
+        for range in [
+            KeyRange::bound(5, 5, true, false),
+            KeyRange::bound(6, 5, false, false),
+        ] {
+            assert_eq!(index.count_range(Some(&range), true), 0, "{:?}", range);
+            assert_eq!(index.count_range(Some(&range), false), 0, "{:?}", range);
+        }
+    }
+
+    #[test]
+    fn test_hash_index_validate() {
+        let mut index: HashIndex<i32> = HashIndex::new(false);
+        for i in 0..100u64 {
+            index.add((i % 7) as i32, i).unwrap();
+        }
+        index.remove(&3, None);
+        index.remove(&4, Some(4));
+        index.set(5, 500);
+        index.validate().unwrap();
+
+        index.map.get_mut(&1).unwrap().push(1);
+        assert!(index.validate().unwrap_err().contains("indexed twice"));
+
+        let mut unique: HashIndex<i32> = HashIndex::new(true);
+        unique.add(1, 10).unwrap();
+        unique.validate().unwrap();
+        unique.stats.add_rows(1);
+        assert!(unique.validate().unwrap_err().contains("stats report"));
+    }
 }
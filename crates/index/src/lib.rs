@@ -45,12 +45,13 @@ pub mod traits;
 
 pub use btree::BTreeIndex;
 pub use comparator::{
-    Comparator, MultiKeyComparator, MultiKeyComparatorWithNull, Order, SimpleComparator,
+    sort_rows, CaseInsensitiveComparator, CaseInsensitiveKey, Comparator, MultiKeyComparator,
+    MultiKeyComparatorWithNull, NullsOrder, Order, SimpleComparator,
 };
 pub use gin::{
     contains_trigram_key, contains_trigram_pairs, contains_trigrams, GinIndex, PostingList,
 };
 pub use hash::HashIndex;
 pub use nullable::NullableIndex;
-pub use stats::IndexStats;
+pub use stats::{IndexStats, KeyHistogram};
 pub use traits::{Index, IndexError, KeyRange, RangeIndex};
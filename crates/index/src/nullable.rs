@@ -1,8 +1,10 @@
 //! Nullable index wrapper implementation.
 //!
 //! This module provides a wrapper around any index that allows null keys.
-//! Null keys are stored separately from the main index.
+//! Null keys are stored separately from the main index. Full scans emit them
+//! before or after the non-null entries, as set by the index's `NullsOrder`.
 
+use crate::comparator::NullsOrder;
 use crate::stats::IndexStats;
 use crate::traits::{Index, IndexError, KeyRange, RangeIndex};
 use alloc::vec::Vec;
@@ -18,6 +20,8 @@ pub struct NullableIndex<K, I: Index<K>> {
     inner: I,
     /// Row IDs associated with null keys.
     null_values: Vec<RowId>,
+    /// Where full scans place the null entries.
+    nulls_order: NullsOrder,
     /// Statistics for this index.
     stats: IndexStats,
     /// Phantom data for the key type.
@@ -25,16 +29,24 @@ pub struct NullableIndex<K, I: Index<K>> {
 }
 
 impl<K: Clone + Ord, I: Index<K>> NullableIndex<K, I> {
-    /// Creates a new nullable index wrapping the given index.
-    pub fn new(inner: I) -> Self {
+    /// Creates a new nullable index wrapping the given index. Full scans
+    /// emit null entries first or last according to `nulls_order`, in both
+    /// scan directions.
+    pub fn new(inner: I, nulls_order: NullsOrder) -> Self {
         Self {
             inner,
             null_values: Vec::new(),
+            nulls_order,
             stats: IndexStats::new(),
             _marker: core::marker::PhantomData,
         }
     }
 
+    /// Returns where full scans place the null entries.
+    pub fn nulls_order(&self) -> NullsOrder {
+        self.nulls_order
+    }
+
     /// Returns the statistics for this index.
     pub fn stats(&self) -> &IndexStats {
         &self.stats
@@ -170,26 +182,49 @@ impl<K: Clone + Ord, I: RangeIndex<K>> RangeIndex<K> for NullableIndex<K, I> {
         limit: Option<usize>,
         skip: usize,
     ) -> Vec<RowId> {
-        // Get results from inner index
-        let mut results = self.inner.get_range(range, reverse, limit, skip);
-
-        // If no range specified (all), include null values at the end
-        if range.is_none() || matches!(range, Some(KeyRange::All)) {
-            // Null values are appended at the end
-            let remaining_limit = limit.map(|l| l.saturating_sub(results.len()));
-            let remaining_skip = skip.saturating_sub(results.len());
-
-            if remaining_limit.map_or(true, |l| l > 0) {
-                let null_iter = self.null_values.iter().skip(remaining_skip);
-                if let Some(lim) = remaining_limit {
-                    results.extend(null_iter.take(lim).copied());
-                } else {
-                    results.extend(null_iter.copied());
+        // Only a full scan covers the null entries
+        if !matches!(range, None | Some(KeyRange::All)) {
+            return self.inner.get_range(range, reverse, limit, skip);
+        }
+
+        // The null entries sit at one end of the scan, whatever its direction
+        match self.nulls_order {
+            NullsOrder::First => {
+                let mut results: Vec<RowId> = self
+                    .null_values
+                    .iter()
+                    .skip(skip)
+                    .take(limit.unwrap_or(usize::MAX))
+                    .copied()
+                    .collect();
+                let remaining_limit = limit.map(|l| l - results.len());
+                if remaining_limit != Some(0) {
+                    let remaining_skip = skip.saturating_sub(self.null_values.len());
+                    results.extend(self.inner.get_range(
+                        range,
+                        reverse,
+                        remaining_limit,
+                        remaining_skip,
+                    ));
+                }
+                results
+            }
+            NullsOrder::Last => {
+                let mut results = self.inner.get_range(range, reverse, limit, skip);
+                let remaining_limit = limit.map(|l| l - results.len());
+                if remaining_limit != Some(0) {
+                    let remaining_skip = skip.saturating_sub(self.inner.len());
+                    results.extend(
+                        self.null_values
+                            .iter()
+                            .skip(remaining_skip)
+                            .take(remaining_limit.unwrap_or(usize::MAX))
+                            .copied(),
+                    );
                 }
+                results
             }
         }
-
-        results
     }
 }
 
@@ -202,7 +237,7 @@ mod tests {
     #[test]
     fn test_nullable_index_basic() {
         let inner: BTreeIndex<i32> = BTreeIndex::new(5, false);
-        let mut index = NullableIndex::new(inner);
+        let mut index = NullableIndex::new(inner, NullsOrder::Last);
 
         index.add(1, 2).unwrap();
         index.add(1, 3).unwrap();
@@ -223,7 +258,7 @@ mod tests {
     #[test]
     fn test_nullable_index_remove() {
         let inner: BTreeIndex<i32> = BTreeIndex::new(5, false);
-        let mut index = NullableIndex::new(inner);
+        let mut index = NullableIndex::new(inner, NullsOrder::Last);
 
         index.add(1, 2).unwrap();
         index.add(1, 3).unwrap();
@@ -243,7 +278,7 @@ mod tests {
     #[test]
     fn test_nullable_index_set() {
         let inner: BTreeIndex<i32> = BTreeIndex::new(5, false);
-        let mut index = NullableIndex::new(inner);
+        let mut index = NullableIndex::new(inner, NullsOrder::Last);
 
         index.add(1, 2).unwrap();
         index.add(1, 3).unwrap();
@@ -262,7 +297,7 @@ mod tests {
     #[test]
     fn test_nullable_index_contains() {
         let inner: BTreeIndex<i32> = BTreeIndex::new(5, false);
-        let mut index = NullableIndex::new(inner);
+        let mut index = NullableIndex::new(inner, NullsOrder::Last);
 
         index.add(1, 2).unwrap();
         index.add_null(7);
@@ -278,7 +313,7 @@ mod tests {
     #[test]
     fn test_nullable_index_clear() {
         let inner: BTreeIndex<i32> = BTreeIndex::new(5, false);
-        let mut index = NullableIndex::new(inner);
+        let mut index = NullableIndex::new(inner, NullsOrder::Last);
 
         index.add(1, 2).unwrap();
         index.add_null(7);
@@ -293,7 +328,7 @@ mod tests {
     #[test]
     fn test_nullable_index_range() {
         let inner: BTreeIndex<i32> = BTreeIndex::new(5, false);
-        let mut index = NullableIndex::new(inner);
+        let mut index = NullableIndex::new(inner, NullsOrder::Last);
 
         index.add(1, 10).unwrap();
         index.add(2, 20).unwrap();
@@ -311,12 +346,57 @@ mod tests {
         assert_eq!(result, vec![10, 20, 30, 100, 200]);
     }
 
+    #[test]
+    fn test_nullable_index_nulls_order() {
+        let build = |nulls_order| {
+            let mut index = NullableIndex::new(BTreeIndex::new(5, false), nulls_order);
+            index.add(1, 10).unwrap();
+            index.add(2, 20).unwrap();
+            index.add(3, 30).unwrap();
+            index.add_null(100);
+            index.add_null(200);
+            index
+        };
+
+        let first = build(NullsOrder::First);
+        assert_eq!(first.nulls_order(), NullsOrder::First);
+        assert_eq!(
+            first.get_range(None, false, None, 0),
+            vec![100, 200, 10, 20, 30]
+        );
+        assert_eq!(
+            first.get_range(None, true, None, 0),
+            vec![100, 200, 30, 20, 10]
+        );
+        // Limit and skip run across the boundary between nulls and keys.
+        assert_eq!(first.get_range(None, false, Some(2), 1), vec![200, 10]);
+        assert_eq!(first.get_range(None, true, Some(2), 3), vec![20, 10]);
+
+        let last = build(NullsOrder::Last);
+        assert_eq!(
+            last.get_range(None, false, None, 0),
+            vec![10, 20, 30, 100, 200]
+        );
+        // Descending with NULLS LAST still ends with the nulls.
+        assert_eq!(
+            last.get_range(Some(&KeyRange::All), true, None, 0),
+            vec![30, 20, 10, 100, 200]
+        );
+        assert_eq!(last.get_range(None, true, Some(2), 2), vec![10, 100]);
+        assert_eq!(last.get_range(None, true, None, 4), vec![200]);
+        assert_eq!(last.get_range(None, false, Some(1), 5), Vec::<RowId>::new());
+
+        // Bounded ranges never include nulls.
+        let range = KeyRange::upper_bound(2, false);
+        assert_eq!(first.get_range(Some(&range), true, None, 0), vec![20, 10]);
+    }
+
     /// Test that unique nullable index allows multiple null keys
     /// (matching SQL behcynos)
     #[test]
     fn test_nullable_index_unique_allows_multiple_nulls() {
         let inner: BTreeIndex<i32> = BTreeIndex::new(5, true);
-        let mut index = NullableIndex::new(inner);
+        let mut index = NullableIndex::new(inner, NullsOrder::Last);
 
         index.add_null(1);
         index.add(1, 2).unwrap();
@@ -328,7 +408,7 @@ mod tests {
     #[test]
     fn test_nullable_index_stats() {
         let inner: BTreeIndex<i32> = BTreeIndex::new(5, false);
-        let mut index = NullableIndex::new(inner);
+        let mut index = NullableIndex::new(inner, NullsOrder::Last);
 
         index.add_null(1);
         index.add_null(2);
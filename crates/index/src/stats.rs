@@ -1,25 +1,30 @@
 //! Index statistics for Cynos database.
 //!
-//! This module provides statistics tracking for indexes.
+//! This module provides statistics tracking for indexes, optionally with an
+//! equi-depth histogram of the indexed keys for selectivity estimation.
 
+use crate::traits::KeyRange;
+use alloc::vec::Vec;
 use core::sync::atomic::{AtomicUsize, Ordering};
 
 /// Statistics for an index.
+///
+/// `K` is the key type the histogram is kept over; indexes without ordered
+/// keys use the default `()` and never enable one.
 #[derive(Debug)]
-pub struct IndexStats {
+pub struct IndexStats<K = ()> {
     /// Total number of rows in the index.
     total_rows: AtomicUsize,
     /// Maximum key encountered (for numeric keys).
     max_key_encountered: AtomicUsize,
+    /// Key histogram, if enabled.
+    histogram: Option<KeyHistogram<K>>,
 }
 
 impl IndexStats {
     /// Creates a new empty stats instance.
     pub fn new() -> Self {
-        Self {
-            total_rows: AtomicUsize::new(0),
-            max_key_encountered: AtomicUsize::new(0),
-        }
+        Self::default()
     }
 
     /// Creates stats with initial values.
@@ -27,9 +32,12 @@ impl IndexStats {
         Self {
             total_rows: AtomicUsize::new(total_rows),
             max_key_encountered: AtomicUsize::new(max_key),
+            histogram: None,
         }
     }
+}
 
+impl<K> IndexStats<K> {
     /// Returns the total number of rows.
     pub fn total_rows(&self) -> usize {
         self.total_rows.load(Ordering::Relaxed)
@@ -64,23 +72,319 @@ impl IndexStats {
     pub fn clear(&self) {
         self.total_rows.store(0, Ordering::Relaxed);
     }
+
+    /// Returns the key histogram, if enabled.
+    pub fn histogram(&self) -> Option<&KeyHistogram<K>> {
+        self.histogram.as_ref()
+    }
+}
+
+impl<K: Clone + Ord> IndexStats<K> {
+    /// Enables a histogram of at most `buckets` buckets, built from
+    /// `keys`: distinct keys in ascending order with their row counts.
+    pub fn enable_histogram<'a>(
+        &mut self,
+        buckets: usize,
+        keys: impl IntoIterator<Item = (&'a K, usize)>,
+    ) where
+        K: 'a,
+    {
+        self.histogram = Some(KeyHistogram::from_sorted(buckets, keys));
+    }
+
+    /// Disables the histogram.
+    pub fn disable_histogram(&mut self) {
+        self.histogram = None;
+    }
+
+    /// Rebuilds an enabled histogram from `keys`, in the form taken by
+    /// `enable_histogram`, rebalancing buckets that inserts and deletes
+    /// have skewed.
+    pub fn rebuild_histogram<'a>(&mut self, keys: impl IntoIterator<Item = (&'a K, usize)>)
+    where
+        K: 'a,
+    {
+        if let Some(histogram) = &self.histogram {
+            self.histogram = Some(KeyHistogram::from_sorted(histogram.max_buckets, keys));
+        }
+    }
+
+    /// Adds `count` rows under `key` to the row count and the histogram.
+    pub fn add_key_rows(&mut self, key: &K, count: usize) {
+        self.add_rows(count);
+        if let Some(histogram) = &mut self.histogram {
+            histogram.add(key, count);
+        }
+    }
+
+    /// Removes `count` rows under `key` from the row count and the
+    /// histogram.
+    pub fn remove_key_rows(&mut self, key: &K, count: usize) {
+        self.remove_rows(count);
+        if let Some(histogram) = &mut self.histogram {
+            histogram.remove(key, count);
+        }
+    }
+
+    /// Resets the row count and empties the histogram, keeping it enabled.
+    pub fn clear_keys(&mut self) {
+        self.clear();
+        if let Some(histogram) = &mut self.histogram {
+            *histogram = KeyHistogram::from_sorted(histogram.max_buckets, []);
+        }
+    }
+
+    /// Estimates the fraction of rows whose key lies in `range`, in
+    /// `[0, 1]`, or `None` without a histogram.
+    pub fn estimate_selectivity(&self, range: &KeyRange<K>) -> Option<f64> {
+        Some(self.histogram.as_ref()?.estimate_selectivity(range))
+    }
 }
 
-impl Default for IndexStats {
+impl<K> Default for IndexStats<K> {
     fn default() -> Self {
-        Self::new()
+        Self {
+            total_rows: AtomicUsize::new(0),
+            max_key_encountered: AtomicUsize::new(0),
+            histogram: None,
+        }
     }
 }
 
-impl Clone for IndexStats {
+impl<K: Clone> Clone for IndexStats<K> {
     fn clone(&self) -> Self {
         Self {
             total_rows: AtomicUsize::new(self.total_rows.load(Ordering::Relaxed)),
             max_key_encountered: AtomicUsize::new(self.max_key_encountered.load(Ordering::Relaxed)),
+            histogram: self.histogram.clone(),
+        }
+    }
+}
+
+/// Equi-depth histogram over index keys.
+///
+/// Bucket `i` holds the rows whose key is above `uppers[i - 1]` and at most
+/// `uppers[i]`; the first bucket starts at `lower`. Each bucket also counts
+/// the rows at its upper key, so a heavily repeated key is estimated
+/// precisely. A build spreads rows evenly over the buckets. Later inserts and deletes only adjust bucket
+/// counts, widening the first or last bucket for keys outside the known
+/// range, so buckets drift apart until the histogram is rebuilt.
+#[derive(Clone, Debug)]
+pub struct KeyHistogram<K> {
+    max_buckets: usize,
+    lower: Option<K>,
+    uppers: Vec<K>,
+    /// Rows in each bucket.
+    counts: Vec<usize>,
+    /// Rows at each bucket's upper key.
+    upper_rows: Vec<usize>,
+    total: usize,
+}
+
+impl<K: Clone + Ord> KeyHistogram<K> {
+    /// Builds a histogram of at most `max_buckets` buckets from distinct
+    /// keys in ascending order with their row counts.
+    pub fn from_sorted<'a>(
+        max_buckets: usize,
+        keys: impl IntoIterator<Item = (&'a K, usize)>,
+    ) -> Self
+    where
+        K: 'a,
+    {
+        let keys: Vec<(&K, usize)> = keys.into_iter().filter(|&(_, n)| n > 0).collect();
+        let max_buckets = max_buckets.max(1);
+        let total: usize = keys.iter().map(|&(_, n)| n).sum();
+        let mut histogram = Self {
+            max_buckets,
+            lower: keys.first().map(|&(key, _)| key.clone()),
+            uppers: Vec::new(),
+            counts: Vec::new(),
+            upper_rows: Vec::new(),
+            total,
+        };
+
+        let mut remaining = total;
+        let mut in_bucket = 0usize;
+        for (i, &(key, count)) in keys.iter().enumerate() {
+            in_bucket += count;
+            // Close the bucket once it holds its share of the rows not yet
+            // bucketed, so a heavy key does not leave later buckets empty.
+            let buckets_left = max_buckets - histogram.uppers.len();
+            if in_bucket * buckets_left >= remaining || i + 1 == keys.len() {
+                histogram.uppers.push(key.clone());
+                histogram.counts.push(in_bucket);
+                histogram.upper_rows.push(count);
+                remaining -= in_bucket;
+                in_bucket = 0;
+            }
+        }
+        histogram
+    }
+
+    /// Returns the number of buckets.
+    pub fn bucket_count(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Returns the number of rows counted.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Returns the bucket boundaries in ascending order: the smallest key
+    /// followed by each bucket's largest key.
+    pub fn bounds(&self) -> Vec<K> {
+        self.lower
+            .iter()
+            .chain(self.uppers.iter())
+            .cloned()
+            .collect()
+    }
+
+    /// Returns a histogram with each key replaced by `f(key)`, keeping the
+    /// bucket counts. `f` must preserve key order.
+    pub fn map_keys<J: Clone + Ord>(&self, mut f: impl FnMut(&K) -> J) -> KeyHistogram<J> {
+        KeyHistogram {
+            max_buckets: self.max_buckets,
+            lower: self.lower.as_ref().map(&mut f),
+            uppers: self.uppers.iter().map(f).collect(),
+            counts: self.counts.clone(),
+            upper_rows: self.upper_rows.clone(),
+            total: self.total,
+        }
+    }
+
+    /// Returns true once a bucket holds over twice the share of rows of one
+    /// of `max_buckets` buckets below its upper key, at which point a
+    /// rebuild can split it and estimates benefit. Rows at the upper key do
+    /// not count, so a single heavily repeated key never reports skew.
+    pub fn is_skewed(&self) -> bool {
+        let splittable = self
+            .counts
+            .iter()
+            .zip(&self.upper_rows)
+            .map(|(&count, &at_upper)| count.saturating_sub(at_upper))
+            .max()
+            .unwrap_or(0);
+        splittable * self.max_buckets > 2 * self.total.max(1)
+    }
+
+    fn add(&mut self, key: &K, count: usize) {
+        if self.counts.is_empty() {
+            self.lower = Some(key.clone());
+            self.uppers.push(key.clone());
+            self.counts.push(0);
+            self.upper_rows.push(0);
+        }
+        if self.lower.as_ref().is_some_and(|lower| key < lower) {
+            self.lower = Some(key.clone());
+        }
+        let mut bucket = self.uppers.partition_point(|upper| upper < key);
+        if bucket == self.uppers.len() {
+            bucket -= 1;
+            self.uppers[bucket] = key.clone();
+            self.upper_rows[bucket] = 0;
         }
+        if &self.uppers[bucket] == key {
+            self.upper_rows[bucket] += count;
+        }
+        self.counts[bucket] += count;
+        self.total += count;
+    }
+
+    fn remove(&mut self, key: &K, count: usize) {
+        let bucket = self.uppers.partition_point(|upper| upper < key);
+        if let Some(in_bucket) = self.counts.get_mut(bucket) {
+            let count = count.min(*in_bucket);
+            *in_bucket -= count;
+            self.total -= count;
+            if &self.uppers[bucket] == key {
+                self.upper_rows[bucket] = self.upper_rows[bucket].saturating_sub(count);
+            }
+        }
+    }
+
+    /// Estimates the fraction of rows whose key lies in `range`, in
+    /// `[0, 1]`. Buckets entirely inside the range count in full. In a
+    /// bucket the range only overlaps, the rows at the upper key count if
+    /// the range holds that key, and half of the other rows count.
+    pub fn estimate_selectivity(&self, range: &KeyRange<K>) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        let mut rows = 0.0;
+        for (bucket, &count) in self.counts.iter().enumerate() {
+            // Keys in the bucket lie in (after, upper], or [lower, upper]
+            // for the first bucket.
+            let upper = &self.uppers[bucket];
+            let (start, start_inclusive) = match bucket {
+                0 => (self.lower.as_ref().unwrap_or(upper), true),
+                _ => (&self.uppers[bucket - 1], false),
+            };
+            if starts_after(range, upper) || ends_before(range, start, start_inclusive) {
+                continue;
+            }
+            let contained = range.contains(upper)
+                && range_lower(range).is_none_or(|(lo, exclusive)| {
+                    lo < start || (lo == start && (!exclusive || !start_inclusive))
+                });
+            rows += if contained {
+                count as f64
+            } else {
+                let at_upper = self.upper_rows[bucket];
+                let below_upper = (count - at_upper) as f64 / 2.0;
+                if range.contains(upper) {
+                    at_upper as f64 + below_upper
+                } else {
+                    below_upper
+                }
+            };
+        }
+        (rows / self.total as f64).clamp(0.0, 1.0)
+    }
+}
+
+/// Returns the lower bound of `range` and whether it is exclusive.
+fn range_lower<K>(range: &KeyRange<K>) -> Option<(&K, bool)> {
+    match range {
+        KeyRange::Only(value) => Some((value, false)),
+        KeyRange::LowerBound { value, exclusive } => Some((value, *exclusive)),
+        KeyRange::Bound {
+            lower,
+            lower_exclusive,
+            ..
+        } => Some((lower, *lower_exclusive)),
+        KeyRange::All | KeyRange::UpperBound { .. } => None,
+    }
+}
+
+/// Returns the upper bound of `range` and whether it is exclusive.
+fn range_upper<K>(range: &KeyRange<K>) -> Option<(&K, bool)> {
+    match range {
+        KeyRange::Only(value) => Some((value, false)),
+        KeyRange::UpperBound { value, exclusive } => Some((value, *exclusive)),
+        KeyRange::Bound {
+            upper,
+            upper_exclusive,
+            ..
+        } => Some((upper, *upper_exclusive)),
+        KeyRange::All | KeyRange::LowerBound { .. } => None,
     }
 }
 
+/// Returns true if every key in `range` is above `key`.
+fn starts_after<K: Ord>(range: &KeyRange<K>, key: &K) -> bool {
+    range_lower(range).is_some_and(|(lo, exclusive)| lo > key || (lo == key && exclusive))
+}
+
+/// Returns true if every key in `range` is below `start`, or at `start`
+/// when `start` itself is excluded.
+fn ends_before<K: Ord>(range: &KeyRange<K>, start: &K, start_inclusive: bool) -> bool {
+    range_upper(range).is_some_and(|(hi, exclusive)| {
+        hi < start || (hi == start && (exclusive || !start_inclusive))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,4 +435,62 @@ mod tests {
         assert_eq!(cloned.total_rows(), 100);
         assert_eq!(cloned.max_key_encountered(), 50);
     }
+
+    #[test]
+    fn test_histogram_estimates_range_selectivity() {
+        // Keys 0..100, one row each, plus 100 rows under key 50.
+        let keys: Vec<(i64, usize)> = (0..100)
+            .map(|k| (k, if k == 50 { 101 } else { 1 }))
+            .collect();
+        let mut stats = IndexStats::default();
+        assert_eq!(stats.estimate_selectivity(&KeyRange::all()), None);
+        stats.enable_histogram(10, keys.iter().map(|(k, n)| (k, *n)));
+        stats.set_total_rows(200);
+
+        let histogram = stats.histogram().unwrap();
+        assert_eq!(histogram.total(), 200);
+        assert!(histogram.bucket_count() <= 10);
+        assert_eq!(histogram.bounds().first(), Some(&0));
+        assert_eq!(histogram.bounds().last(), Some(&99));
+
+        let estimate = |range: KeyRange<i64>| stats.estimate_selectivity(&range).unwrap();
+        assert_eq!(estimate(KeyRange::all()), 1.0);
+        assert_eq!(estimate(KeyRange::lower_bound(100, false)), 0.0);
+        assert_eq!(estimate(KeyRange::upper_bound(0, true)), 0.0);
+        // The heavy key dominates: half the rows are at 50.
+        let heavy = estimate(KeyRange::bound(45, 55, false, false));
+        assert!(heavy > 0.45 && heavy <= 0.6, "{heavy}");
+        let sparse = estimate(KeyRange::upper_bound(20, false));
+        assert!(sparse > 0.05 && sparse < 0.2, "{sparse}");
+    }
+
+    #[test]
+    fn test_histogram_tracks_updates_and_rebuilds() {
+        let keys: Vec<i64> = (0..10).collect();
+        let mut stats = IndexStats::default();
+        stats.enable_histogram(5, keys.iter().map(|k| (k, 1)));
+
+        // Inserts beyond the known keys widen the last bucket.
+        for key in 10..40 {
+            stats.add_key_rows(&key, 1);
+        }
+        let histogram = stats.histogram().unwrap();
+        assert_eq!(histogram.total(), 40);
+        assert_eq!(histogram.bounds().last(), Some(&39));
+        assert!(histogram.is_skewed());
+
+        let keys: Vec<i64> = (0..40).collect();
+        stats.rebuild_histogram(keys.iter().map(|k| (k, 1)));
+        assert!(!stats.histogram().unwrap().is_skewed());
+        let upper_half = stats
+            .estimate_selectivity(&KeyRange::lower_bound(20, false))
+            .unwrap();
+        assert!((upper_half - 0.5).abs() <= 0.15, "{upper_half}");
+
+        for key in 0..40 {
+            stats.remove_key_rows(&key, 1);
+        }
+        assert_eq!(stats.histogram().unwrap().total(), 0);
+        assert_eq!(stats.estimate_selectivity(&KeyRange::all()), Some(0.0));
+    }
 }
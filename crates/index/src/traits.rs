@@ -7,6 +7,7 @@ use cynos_core::RowId;
 
 /// A key range for index queries.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum KeyRange<K> {
     /// All keys
     All,
@@ -181,6 +182,117 @@ impl<K: Clone + Ord> KeyRange<K> {
         }
     }
 
+    /// Returns the range of keys in both `self` and `other`, or `None` if
+    /// the ranges are disjoint and the intersection is empty.
+    pub fn intersect(&self, other: &KeyRange<K>) -> Option<KeyRange<K>> {
+        let lower = match (self.lower(), other.lower()) {
+            (Some(a), Some(b)) => Some(if a.0 == b.0 {
+                (a.0, a.1 || b.1)
+            } else {
+                core::cmp::max_by(a, b, |x, y| x.0.cmp(y.0))
+            }),
+            (bound, None) | (None, bound) => bound,
+        };
+        let upper = match (self.upper(), other.upper()) {
+            (Some(a), Some(b)) => Some(if a.0 == b.0 {
+                (a.0, a.1 || b.1)
+            } else {
+                core::cmp::min_by(a, b, |x, y| x.0.cmp(y.0))
+            }),
+            (bound, None) | (None, bound) => bound,
+        };
+        Self::from_bounds(lower, upper)
+    }
+
+    /// Returns the range of keys in `self` or `other`, or `None` if the
+    /// ranges neither overlap nor touch, so their union is not one range.
+    ///
+    /// Ranges touch when one ends at the key where the other starts and at
+    /// least one of them includes that key, as `[1, 3)` and `[3, 5]` do.
+    pub fn union(&self, other: &KeyRange<K>) -> Option<KeyRange<K>> {
+        let touches = |a: &KeyRange<K>, b: &KeyRange<K>| match (a.upper(), b.lower()) {
+            (Some((end, end_ex)), Some((start, start_ex))) => end == start && !(end_ex && start_ex),
+            _ => false,
+        };
+        if !self.overlaps(other) && !touches(self, other) && !touches(other, self) {
+            return None;
+        }
+
+        let lower = match (self.lower(), other.lower()) {
+            (Some(a), Some(b)) => Some(if a.0 == b.0 {
+                (a.0, a.1 && b.1)
+            } else {
+                core::cmp::min_by(a, b, |x, y| x.0.cmp(y.0))
+            }),
+            _ => None,
+        };
+        let upper = match (self.upper(), other.upper()) {
+            (Some(a), Some(b)) => Some(if a.0 == b.0 {
+                (a.0, a.1 && b.1)
+            } else {
+                core::cmp::max_by(a, b, |x, y| x.0.cmp(y.0))
+            }),
+            _ => None,
+        };
+        Self::from_bounds(lower, upper)
+    }
+
+    /// Returns the lower bound and whether it is exclusive.
+    fn lower(&self) -> Option<(&K, bool)> {
+        match self {
+            KeyRange::All | KeyRange::UpperBound { .. } => None,
+            KeyRange::Only(key) => Some((key, false)),
+            KeyRange::LowerBound { value, exclusive } => Some((value, *exclusive)),
+            KeyRange::Bound {
+                lower,
+                lower_exclusive,
+                ..
+            } => Some((lower, *lower_exclusive)),
+        }
+    }
+
+    /// Returns the upper bound and whether it is exclusive.
+    fn upper(&self) -> Option<(&K, bool)> {
+        match self {
+            KeyRange::All | KeyRange::LowerBound { .. } => None,
+            KeyRange::Only(key) => Some((key, false)),
+            KeyRange::UpperBound { value, exclusive } => Some((value, *exclusive)),
+            KeyRange::Bound {
+                upper,
+                upper_exclusive,
+                ..
+            } => Some((upper, *upper_exclusive)),
+        }
+    }
+
+    /// Builds the range between two optional bounds, or `None` if no key
+    /// lies between them.
+    fn from_bounds(lower: Option<(&K, bool)>, upper: Option<(&K, bool)>) -> Option<KeyRange<K>> {
+        match (lower, upper) {
+            (None, None) => Some(KeyRange::All),
+            (Some((value, exclusive)), None) => {
+                Some(KeyRange::lower_bound(value.clone(), exclusive))
+            }
+            (None, Some((value, exclusive))) => {
+                Some(KeyRange::upper_bound(value.clone(), exclusive))
+            }
+            (Some((lower, lower_ex)), Some((upper, upper_ex))) => {
+                if lower > upper || (lower == upper && (lower_ex || upper_ex)) {
+                    None
+                } else if lower == upper {
+                    Some(KeyRange::only(lower.clone()))
+                } else {
+                    Some(KeyRange::bound(
+                        lower.clone(),
+                        upper.clone(),
+                        lower_ex,
+                        upper_ex,
+                    ))
+                }
+            }
+        }
+    }
+
     /// Checks if a key is within this range.
     pub fn contains(&self, key: &K) -> bool {
         match self {
@@ -284,6 +396,37 @@ pub trait RangeIndex<K>: Index<K> {
         limit: Option<usize>,
         skip: usize,
     ) -> Vec<RowId>;
+
+    /// Counts the row IDs within the given key range without collecting them.
+    ///
+    /// Bounds follow `get_range`. When `include_equal` is false, keys equal
+    /// to the range's upper bound are left out even if that bound is
+    /// inclusive, as in a reverse scan that starts strictly below it.
+    fn count_range(&self, range: Option<&KeyRange<K>>, include_equal: bool) -> usize
+    where
+        K: Clone + Ord,
+    {
+        let upper = match range {
+            _ if include_equal => None,
+            Some(KeyRange::Only(value))
+            | Some(KeyRange::UpperBound {
+                value,
+                exclusive: false,
+            }) => Some(value),
+            Some(KeyRange::Bound {
+                upper,
+                upper_exclusive: false,
+                ..
+            }) => Some(upper),
+            _ => None,
+        };
+        let count = self.get_range(range, false, None, 0).len();
+        // A degenerate range such as (5, 5] leaves out its own upper bound.
+        match (upper, range) {
+            (Some(upper), Some(range)) if range.contains(upper) => count - self.get(upper).len(),
+            _ => count,
+        }
+    }
 }
 
 /// Error type for index operations.
@@ -293,6 +436,8 @@ pub enum IndexError {
     DuplicateKey,
     /// Key not found.
     KeyNotFound,
+    /// Bulk-load input was not sorted ascending by key.
+    UnsortedInput,
 }
 
 impl core::fmt::Display for IndexError {
@@ -300,6 +445,7 @@ impl core::fmt::Display for IndexError {
         match self {
             IndexError::DuplicateKey => write!(f, "Duplicate key in unique index"),
             IndexError::KeyNotFound => write!(f, "Key not found"),
+            IndexError::UnsortedInput => write!(f, "Bulk-load input is not sorted by key"),
         }
     }
 }
@@ -607,4 +753,138 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_key_range_intersect_overlapping() {
+        let cases = [
+            (
+                KeyRange::bound(1, 10, false, false),
+                KeyRange::bound(5, 20, true, false),
+                KeyRange::bound(5, 10, true, false),
+            ),
+            (
+                KeyRange::lower_bound(3, false),
+                KeyRange::upper_bound(8, true),
+                KeyRange::bound(3, 8, false, true),
+            ),
+            (
+                KeyRange::lower_bound(3, false),
+                KeyRange::lower_bound(3, true),
+                KeyRange::lower_bound(3, true),
+            ),
+            (
+                KeyRange::all(),
+                KeyRange::upper_bound(4, false),
+                KeyRange::upper_bound(4, false),
+            ),
+            (
+                KeyRange::bound(1, 5, false, false),
+                KeyRange::bound(5, 9, false, false),
+                KeyRange::only(5),
+            ),
+            (
+                KeyRange::only(7),
+                KeyRange::bound(1, 10, false, false),
+                KeyRange::only(7),
+            ),
+        ];
+        for (a, b, expected) in &cases {
+            assert_eq!(a.intersect(b).as_ref(), Some(expected), "{:?} ∩ {:?}", a, b);
+            assert_eq!(b.intersect(a).as_ref(), Some(expected), "{:?} ∩ {:?}", b, a);
+        }
+    }
+
+    #[test]
+    fn test_key_range_intersect_disjoint() {
+        let cases = [
+            (
+                KeyRange::bound(1, 3, false, false),
+                KeyRange::bound(5, 7, false, false),
+            ),
+            (
+                KeyRange::bound(1, 5, false, true),
+                KeyRange::bound(5, 7, false, false),
+            ),
+            (
+                KeyRange::upper_bound(5, false),
+                KeyRange::lower_bound(5, true),
+            ),
+            (KeyRange::only(1), KeyRange::only(2)),
+            (KeyRange::only(5), KeyRange::lower_bound(5, true)),
+        ];
+        for (a, b) in &cases {
+            assert_eq!(a.intersect(b), None, "{:?} ∩ {:?}", a, b);
+            assert_eq!(b.intersect(a), None, "{:?} ∩ {:?}", b, a);
+        }
+    }
+
+    #[test]
+    fn test_key_range_union() {
+        let cases = [
+            (
+                KeyRange::bound(1, 5, false, false),
+                KeyRange::bound(3, 9, false, true),
+                KeyRange::bound(1, 9, false, true),
+            ),
+            // Touching ranges merge when one side includes the shared key.
+            (
+                KeyRange::bound(1, 3, false, true),
+                KeyRange::bound(3, 5, false, false),
+                KeyRange::bound(1, 5, false, false),
+            ),
+            (
+                KeyRange::upper_bound(3, false),
+                KeyRange::lower_bound(3, true),
+                KeyRange::all(),
+            ),
+            (
+                KeyRange::lower_bound(2, true),
+                KeyRange::lower_bound(2, false),
+                KeyRange::lower_bound(2, false),
+            ),
+            (KeyRange::only(4), KeyRange::only(4), KeyRange::only(4)),
+        ];
+        for (a, b, expected) in &cases {
+            assert_eq!(a.union(b).as_ref(), Some(expected), "{:?} ∪ {:?}", a, b);
+            assert_eq!(b.union(a).as_ref(), Some(expected), "{:?} ∪ {:?}", b, a);
+        }
+
+        // A gap, even of a single excluded key, leaves two ranges.
+        let gaps = [
+            (
+                KeyRange::bound(1, 3, false, false),
+                KeyRange::bound(5, 7, false, false),
+            ),
+            (
+                KeyRange::upper_bound(3, true),
+                KeyRange::lower_bound(3, true),
+            ),
+        ];
+        for (a, b) in &gaps {
+            assert_eq!(a.union(b), None, "{:?} ∪ {:?}", a, b);
+            assert_eq!(b.union(a), None, "{:?} ∪ {:?}", b, a);
+        }
+    }
+
+    #[test]
+    fn test_key_range_contains_boundaries() {
+        for (lower_ex, upper_ex) in [(false, false), (false, true), (true, false), (true, true)] {
+            let range = KeyRange::bound(10, 20, lower_ex, upper_ex);
+            assert!(!range.contains(&9));
+            assert_eq!(range.contains(&10), !lower_ex, "{:?}", range);
+            assert!(range.contains(&15));
+            assert_eq!(range.contains(&20), !upper_ex, "{:?}", range);
+            assert!(!range.contains(&21));
+        }
+        for exclusive in [false, true] {
+            assert_eq!(
+                KeyRange::lower_bound(10, exclusive).contains(&10),
+                !exclusive
+            );
+            assert_eq!(
+                KeyRange::upper_bound(10, exclusive).contains(&10),
+                !exclusive
+            );
+        }
+    }
 }
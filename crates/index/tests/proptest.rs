@@ -191,4 +191,21 @@ proptest! {
 
         prop_assert_eq!(tree.len(), tree.stats().total_rows());
     }
+
+    /// Test that BTree invariants hold after random inserts and deletes.
+    #[test]
+    fn btree_validate_after_random_ops(
+        ops in prop::collection::vec((any::<bool>(), 0i64..300), 1..600),
+        order in 3usize..16,
+    ) {
+        let mut tree = BTreeIndex::new(order, false);
+        for (i, &(insert, key)) in ops.iter().enumerate() {
+            if insert {
+                tree.add(key, i as u64).unwrap();
+            } else {
+                tree.remove(&key, None);
+            }
+            prop_assert_eq!(tree.validate(), Ok(()));
+        }
+    }
 }
@@ -72,6 +72,14 @@ impl JsonbBinary {
         let mut pos = 0;
         decode_value(&self.data, &mut pos)
     }
+
+    /// Decodes binary data, returning `None` unless the bytes are exactly
+    /// one well-formed encoded value.
+    pub fn try_decode(&self) -> Option<JsonbValue> {
+        let mut pos = 0;
+        let value = try_decode_value(&self.data, &mut pos)?;
+        (pos == self.data.len()).then_some(value)
+    }
 }
 
 /// Encodes a varint (variable-length integer).
@@ -95,7 +103,7 @@ fn decode_varint(data: &[u8], pos: &mut usize) -> usize {
     let mut result = 0usize;
     let mut shift = 0;
     loop {
-        if *pos >= data.len() {
+        if *pos >= data.len() || shift >= usize::BITS {
             break;
         }
         let byte = data[*pos];
@@ -206,6 +214,57 @@ fn decode_value(data: &[u8], pos: &mut usize) -> JsonbValue {
     }
 }
 
+/// Strict counterpart of `decode_value`: fails on truncated data, unknown
+/// tags and invalid UTF-8 instead of substituting null.
+fn try_decode_value(data: &[u8], pos: &mut usize) -> Option<JsonbValue> {
+    let tag = *data.get(*pos)?;
+    *pos += 1;
+
+    match tag {
+        TAG_NULL => Some(JsonbValue::Null),
+        TAG_FALSE => Some(JsonbValue::Bool(false)),
+        TAG_TRUE => Some(JsonbValue::Bool(true)),
+        TAG_NUMBER => {
+            let bytes: [u8; 8] = take(data, pos, 8)?.try_into().ok()?;
+            Some(JsonbValue::Number(f64::from_le_bytes(bytes)))
+        }
+        TAG_STRING => {
+            let len = decode_varint(data, pos);
+            let s = core::str::from_utf8(take(data, pos, len)?).ok()?;
+            Some(JsonbValue::String(s.into()))
+        }
+        TAG_ARRAY => {
+            let count = decode_varint(data, pos);
+            let mut arr = Vec::new();
+            for _ in 0..count {
+                arr.push(try_decode_value(data, pos)?);
+            }
+            Some(JsonbValue::Array(arr))
+        }
+        TAG_OBJECT => {
+            let count = decode_varint(data, pos);
+            let mut obj = JsonbObject::new();
+            for _ in 0..count {
+                let key_len = decode_varint(data, pos);
+                let key = core::str::from_utf8(take(data, pos, key_len)?).ok()?.into();
+                let val = try_decode_value(data, pos)?;
+                obj.insert(key, val);
+            }
+            Some(JsonbValue::Object(obj))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the next `len` bytes and advances past them, or `None` if the
+/// buffer is too short.
+fn take<'a>(data: &'a [u8], pos: &mut usize, len: usize) -> Option<&'a [u8]> {
+    let end = pos.checked_add(len).filter(|&end| end <= data.len())?;
+    let bytes = &data[*pos..end];
+    *pos = end;
+    Some(bytes)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
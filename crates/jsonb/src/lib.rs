@@ -6,6 +6,8 @@
 //! - `JsonbBinary`: Binary encoding/decoding for efficient storage
 //! - `JsonPath`: JSONPath query language support
 //! - `JsonbOp`: PostgreSQL-compatible JSONB operators
+//! - `parse_json`: Depth-limited parsing of JSON text
+//! - `write_json`: Canonical serialization back to JSON text
 //! - GIN index support for efficient querying
 //!
 //! # Example
@@ -39,9 +41,14 @@ mod binary;
 mod index;
 mod ops;
 pub mod path;
+mod text;
 mod value;
 
 pub use binary::JsonbBinary;
 pub use ops::JsonbOp;
 pub use path::{CompareOp, JsonPath, JsonPathPredicate, ParseError, PredicateValue};
+pub use text::{
+    compare_json_text, parse_json, parse_json_with_max_depth, to_json_string, write_json,
+    write_json_number, write_json_string, JsonParseError, JsonSortKey, DEFAULT_MAX_DEPTH,
+};
 pub use value::{JsonbObject, JsonbValue};
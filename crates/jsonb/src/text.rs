@@ -0,0 +1,506 @@
+//! JSON text parsing and serialization for Cynos database.
+//!
+//! JSONB columns store their documents as JSON text. This module parses that
+//! text into a `JsonbValue`. Parsing is recursive, so nesting depth is bounded
+//! to keep adversarial documents from overflowing the (small) WASM stack.
+//!
+//! `write_json` is the inverse: it renders a `JsonbValue` as canonical JSON
+//! text, with object keys in sorted order and no insignificant whitespace.
+
+use crate::binary::JsonbBinary;
+use crate::value::{JsonbObject, JsonbValue};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{self, Write};
+
+/// Default maximum nesting depth of arrays and objects.
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// Error type for JSON text parsing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum JsonParseError {
+    /// The input is not valid JSON; `position` is the byte offset of the
+    /// offending character.
+    Invalid { position: usize },
+    /// Arrays and objects are nested deeper than `max_depth`.
+    TooDeep { max_depth: usize },
+}
+
+impl fmt::Display for JsonParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonParseError::Invalid { position } => {
+                write!(f, "invalid JSON at position {}", position)
+            }
+            JsonParseError::TooDeep { max_depth } => {
+                write!(f, "JSON nesting exceeds maximum depth of {}", max_depth)
+            }
+        }
+    }
+}
+
+/// Parses JSON text using `DEFAULT_MAX_DEPTH`.
+pub fn parse_json(input: &str) -> Result<JsonbValue, JsonParseError> {
+    parse_json_with_max_depth(input, DEFAULT_MAX_DEPTH)
+}
+
+/// Parses JSON text, rejecting documents nested deeper than `max_depth`.
+///
+/// A scalar has depth 0; each enclosing array or object adds one.
+pub fn parse_json_with_max_depth(
+    input: &str,
+    max_depth: usize,
+) -> Result<JsonbValue, JsonParseError> {
+    let mut parser = TextParser {
+        input: input.as_bytes(),
+        pos: 0,
+        max_depth,
+    };
+    parser.skip_whitespace();
+    let value = parser.parse_value(0)?;
+    parser.skip_whitespace();
+    if parser.pos != parser.input.len() {
+        return Err(parser.invalid());
+    }
+    Ok(value)
+}
+
+/// Compares two stored JSON documents by their logical content (see
+/// [`JsonSortKey`]). Sorting many documents should decode each one into a
+/// `JsonSortKey` once rather than call this per comparison.
+pub fn compare_json_text(a: &[u8], b: &[u8]) -> Ordering {
+    JsonSortKey::decode(a).cmp(&JsonSortKey::decode(b))
+}
+
+/// A stored JSON document decoded once so it can be ordered repeatedly.
+///
+/// Documents may be JSON text or [`JsonbBinary`] bytes; both decode to the
+/// same value, so they order consistently with each other. Decoded documents
+/// compare with [`JsonbValue::logical_cmp`] and sort before any bytes that
+/// decode as neither, which compare bytewise. The result is a total order.
+#[derive(Clone, Debug)]
+pub enum JsonSortKey {
+    /// A well-formed document.
+    Document(JsonbValue),
+    /// Bytes that are neither JSON text nor a binary-encoded value.
+    Undecodable(Vec<u8>),
+}
+
+impl JsonSortKey {
+    /// Decodes stored document bytes, trying JSON text first.
+    pub fn decode(bytes: &[u8]) -> Self {
+        core::str::from_utf8(bytes)
+            .ok()
+            .and_then(|text| parse_json(text).ok())
+            .or_else(|| JsonbBinary::from_bytes(bytes.to_vec()).try_decode())
+            .map_or_else(|| Self::Undecodable(bytes.to_vec()), Self::Document)
+    }
+}
+
+impl PartialEq for JsonSortKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for JsonSortKey {}
+
+impl PartialOrd for JsonSortKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for JsonSortKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Document(a), Self::Document(b)) => a.logical_cmp(b),
+            (Self::Document(_), Self::Undecodable(_)) => Ordering::Less,
+            (Self::Undecodable(_), Self::Document(_)) => Ordering::Greater,
+            (Self::Undecodable(a), Self::Undecodable(b)) => a.cmp(b),
+        }
+    }
+}
+
+/// Serializes a value to canonical JSON text.
+pub fn to_json_string(value: &JsonbValue) -> String {
+    let mut out = String::new();
+    write_json(value, &mut out);
+    out
+}
+
+/// Appends the canonical JSON text of `value` to `out`.
+///
+/// Object keys come out in sorted order. Numbers use the shortest form that
+/// round-trips; NaN and infinities, which JSON cannot represent, become `null`.
+pub fn write_json(value: &JsonbValue, out: &mut String) {
+    match value {
+        JsonbValue::Null => out.push_str("null"),
+        JsonbValue::Bool(true) => out.push_str("true"),
+        JsonbValue::Bool(false) => out.push_str("false"),
+        JsonbValue::Number(n) => write_json_number(*n, out),
+        JsonbValue::String(s) => write_json_string(s, out),
+        JsonbValue::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_json(item, out);
+            }
+            out.push(']');
+        }
+        JsonbValue::Object(obj) => {
+            out.push('{');
+            for (i, (key, value)) in obj.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_json_string(key, out);
+                out.push(':');
+                write_json(value, out);
+            }
+            out.push('}');
+        }
+    }
+}
+
+/// Appends `n` as a JSON number, or `null` if it is not finite.
+pub fn write_json_number(n: f64, out: &mut String) {
+    if n.is_finite() {
+        // `Display` for f64 never uses exponent notation and prints
+        // integral values without a fraction, both of which are valid JSON.
+        let _ = write!(out, "{}", n);
+    } else {
+        out.push_str("null");
+    }
+}
+
+/// Appends `s` as a quoted, escaped JSON string.
+pub fn write_json_string(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+struct TextParser<'a> {
+    input: &'a [u8],
+    pos: usize,
+    max_depth: usize,
+}
+
+impl TextParser<'_> {
+    fn invalid(&self) -> JsonParseError {
+        JsonParseError::Invalid { position: self.pos }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect_literal(&mut self, literal: &[u8]) -> Result<(), JsonParseError> {
+        if self.input[self.pos..].starts_with(literal) {
+            self.pos += literal.len();
+            Ok(())
+        } else {
+            Err(self.invalid())
+        }
+    }
+
+    fn parse_value(&mut self, depth: usize) -> Result<JsonbValue, JsonParseError> {
+        match self.peek() {
+            Some(b'n') => self.expect_literal(b"null").map(|_| JsonbValue::Null),
+            Some(b't') => self.expect_literal(b"true").map(|_| JsonbValue::Bool(true)),
+            Some(b'f') => self
+                .expect_literal(b"false")
+                .map(|_| JsonbValue::Bool(false)),
+            Some(b'"') => self.parse_string().map(JsonbValue::String),
+            Some(b'[') => self.parse_array(depth + 1),
+            Some(b'{') => self.parse_object(depth + 1),
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            _ => Err(self.invalid()),
+        }
+    }
+
+    fn enter(&self, depth: usize) -> Result<(), JsonParseError> {
+        if depth > self.max_depth {
+            Err(JsonParseError::TooDeep {
+                max_depth: self.max_depth,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    fn parse_array(&mut self, depth: usize) -> Result<JsonbValue, JsonParseError> {
+        self.enter(depth)?;
+        self.pos += 1;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(JsonbValue::Array(items));
+        }
+        loop {
+            self.skip_whitespace();
+            items.push(self.parse_value(depth)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(JsonbValue::Array(items));
+                }
+                _ => return Err(self.invalid()),
+            }
+        }
+    }
+
+    fn parse_object(&mut self, depth: usize) -> Result<JsonbValue, JsonParseError> {
+        self.enter(depth)?;
+        self.pos += 1;
+        let mut obj = JsonbObject::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(JsonbValue::Object(obj));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some(b'"') {
+                return Err(self.invalid());
+            }
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            if self.peek() != Some(b':') {
+                return Err(self.invalid());
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+            let value = self.parse_value(depth)?;
+            obj.insert(key, value);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(JsonbValue::Object(obj));
+                }
+                _ => return Err(self.invalid()),
+            }
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<JsonbValue, JsonParseError> {
+        let start = self.pos;
+        while matches!(
+            self.peek(),
+            Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+        ) {
+            self.pos += 1;
+        }
+        core::str::from_utf8(&self.input[start..self.pos])
+            .ok()
+            .and_then(|s| s.parse::<f64>().ok())
+            .map(JsonbValue::Number)
+            .ok_or(JsonParseError::Invalid { position: start })
+    }
+
+    fn parse_string(&mut self) -> Result<String, JsonParseError> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut bytes = Vec::new();
+        loop {
+            match self.peek() {
+                Some(b'"') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let escaped = match self.peek() {
+                        Some(b'"') => b'"',
+                        Some(b'\\') => b'\\',
+                        Some(b'/') => b'/',
+                        Some(b'n') => b'\n',
+                        Some(b't') => b'\t',
+                        Some(b'r') => b'\r',
+                        Some(b'b') => 0x08,
+                        Some(b'f') => 0x0c,
+                        Some(b'u') => {
+                            self.pos += 1;
+                            let c = self.parse_unicode_escape()?;
+                            let mut buf = [0u8; 4];
+                            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                            continue;
+                        }
+                        _ => return Err(self.invalid()),
+                    };
+                    bytes.push(escaped);
+                    self.pos += 1;
+                }
+                Some(byte) => {
+                    bytes.push(byte);
+                    self.pos += 1;
+                }
+                None => return Err(JsonParseError::Invalid { position: start }),
+            }
+        }
+        // The input is a `&str` and escapes produce valid UTF-8, so this only
+        // fails if a multi-byte sequence was split, which cannot happen here.
+        String::from_utf8(bytes).map_err(|_| JsonParseError::Invalid { position: start })
+    }
+
+    /// Parses the `XXXX` of a `\uXXXX` escape, combining surrogate pairs.
+    fn parse_unicode_escape(&mut self) -> Result<char, JsonParseError> {
+        let high = self.parse_hex4()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high).ok_or(self.invalid());
+        }
+        if !self.input[self.pos..].starts_with(b"\\u") {
+            return Err(self.invalid());
+        }
+        self.pos += 2;
+        let low = self.parse_hex4()?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(self.invalid());
+        }
+        char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)).ok_or(self.invalid())
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, JsonParseError> {
+        let digits = self
+            .input
+            .get(self.pos..self.pos + 4)
+            .and_then(|digits| core::str::from_utf8(digits).ok())
+            .ok_or(self.invalid())?;
+        let value = u32::from_str_radix(digits, 16).map_err(|_| self.invalid())?;
+        self.pos += 4;
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    fn nested_arrays(depth: usize) -> String {
+        let mut text = "[".repeat(depth);
+        text.push_str(&"]".repeat(depth));
+        text
+    }
+
+    #[test]
+    fn test_compare_json_text() {
+        assert_eq!(compare_json_text(b"9", b"10"), Ordering::Less);
+        assert_eq!(compare_json_text(b"[1, 2]", b"[1,2]"), Ordering::Equal);
+        assert_eq!(compare_json_text(br#"{"a":1}"#, b"true"), Ordering::Greater);
+        // Undecodable bytes sort after every document, then bytewise.
+        assert_eq!(compare_json_text(b"{oops", b"9"), Ordering::Greater);
+        assert_eq!(compare_json_text(b"{oops", b"{"), Ordering::Greater);
+        // Binary-encoded documents order by content alongside text ones.
+        let binary = JsonbBinary::encode(&JsonbValue::Number(5.0)).into_bytes();
+        assert_eq!(compare_json_text(&binary, b"10"), Ordering::Less);
+        assert_eq!(compare_json_text(&binary, b"5"), Ordering::Equal);
+        assert_eq!(compare_json_text(b"{oops", &binary), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_parse_scalars_and_containers() {
+        assert_eq!(parse_json(" null ").unwrap(), JsonbValue::Null);
+        assert_eq!(parse_json("true").unwrap(), JsonbValue::Bool(true));
+        assert_eq!(parse_json("-1.5e2").unwrap(), JsonbValue::Number(-150.0));
+        assert_eq!(
+            parse_json(r#""a\"bé""#).unwrap(),
+            JsonbValue::String("a\"bé".to_string())
+        );
+
+        let value = parse_json(r#"{"b": [1, {"c": null}], "a": "x,y"}"#).unwrap();
+        assert_eq!(value.get("a"), Some(&JsonbValue::String("x,y".into())));
+        let items = value.get("b").unwrap().as_array().unwrap();
+        assert_eq!(items[0], JsonbValue::Number(1.0));
+        assert_eq!(items[1].get("c"), Some(&JsonbValue::Null));
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_text() {
+        assert!(parse_json("").is_err());
+        assert!(parse_json("[1, 2").is_err());
+        assert!(parse_json(r#"{"a" 1}"#).is_err());
+        assert!(parse_json(r#"{a: 1}"#).is_err());
+        assert!(parse_json("[1] 2").is_err());
+        assert!(parse_json(r#""unterminated"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_accepts_documents_at_max_depth() {
+        let text = nested_arrays(DEFAULT_MAX_DEPTH);
+        assert!(parse_json(&text).is_ok());
+    }
+
+    #[test]
+    fn test_parse_rejects_documents_beyond_max_depth() {
+        let text = nested_arrays(DEFAULT_MAX_DEPTH + 1);
+        assert_eq!(
+            parse_json(&text),
+            Err(JsonParseError::TooDeep {
+                max_depth: DEFAULT_MAX_DEPTH
+            })
+        );
+
+        // Far beyond the limit: must fail cleanly rather than overflow the stack.
+        let text = "{\"a\":".repeat(100_000) + &"}".repeat(100_000);
+        assert!(matches!(
+            parse_json(&text),
+            Err(JsonParseError::TooDeep { .. })
+        ));
+    }
+
+    #[test]
+    fn test_write_json_is_canonical_and_round_trips() {
+        let value =
+            parse_json(r#" { "b" : [1, 2.5, -0.5e1, true, null], "a" : "q\"\\\n\u0001é" } "#)
+                .unwrap();
+        let text = to_json_string(&value);
+        assert_eq!(text, r#"{"a":"q\"\\\n\u0001é","b":[1,2.5,-5,true,null]}"#);
+        assert_eq!(parse_json(&text).unwrap(), value);
+
+        assert_eq!(to_json_string(&JsonbValue::Number(f64::NAN)), "null");
+        assert_eq!(
+            to_json_string(&JsonbValue::Number(1e21)),
+            "1000000000000000000000"
+        );
+    }
+
+    #[test]
+    fn test_parse_with_custom_max_depth() {
+        assert!(parse_json_with_max_depth("[[1]]", 2).is_ok());
+        assert_eq!(
+            parse_json_with_max_depth("[[1]]", 1),
+            Err(JsonParseError::TooDeep { max_depth: 1 })
+        );
+        assert!(parse_json_with_max_depth("1", 0).is_ok());
+    }
+}
@@ -263,6 +263,51 @@ impl JsonbValue {
     pub fn get_index(&self, index: usize) -> Option<&JsonbValue> {
         self.as_array().and_then(|arr| arr.get(index))
     }
+
+    /// Compares two values by their logical JSON content, giving a total
+    /// order suitable for sorting.
+    ///
+    /// Values of different types order by type precedence:
+    /// `Null < Bool < Number < String < Array < Object`. Within a type,
+    /// booleans order `false < true`, numbers numerically (NaN last), and
+    /// strings by code point. Arrays compare element by element, a shorter
+    /// prefix ordering first. Objects compare their key-sorted entries the
+    /// same way, key before value.
+    pub fn logical_cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (JsonbValue::Bool(a), JsonbValue::Bool(b)) => a.cmp(b),
+            (JsonbValue::Number(a), JsonbValue::Number(b)) => a
+                .partial_cmp(b)
+                .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan())),
+            (JsonbValue::String(a), JsonbValue::String(b)) => a.cmp(b),
+            (JsonbValue::Array(a), JsonbValue::Array(b)) => a
+                .iter()
+                .zip(b)
+                .map(|(x, y)| x.logical_cmp(y))
+                .find(|ord| ord.is_ne())
+                .unwrap_or_else(|| a.len().cmp(&b.len())),
+            (JsonbValue::Object(a), JsonbValue::Object(b)) => a
+                .iter()
+                .zip(b.iter())
+                .map(|((ka, va), (kb, vb))| ka.cmp(kb).then_with(|| va.logical_cmp(vb)))
+                .find(|ord| ord.is_ne())
+                .unwrap_or_else(|| a.len().cmp(&b.len())),
+            _ => self.type_rank().cmp(&other.type_rank()),
+        }
+    }
+
+    /// Position of this value's type in the [`logical_cmp`](Self::logical_cmp)
+    /// precedence order.
+    fn type_rank(&self) -> u8 {
+        match self {
+            JsonbValue::Null => 0,
+            JsonbValue::Bool(_) => 1,
+            JsonbValue::Number(_) => 2,
+            JsonbValue::String(_) => 3,
+            JsonbValue::Array(_) => 4,
+            JsonbValue::Object(_) => 5,
+        }
+    }
 }
 
 impl PartialEq for JsonbValue {
@@ -437,6 +482,38 @@ mod tests {
         assert!(JsonbValue::String("a".into()) < JsonbValue::String("b".into()));
     }
 
+    #[test]
+    fn test_jsonb_value_logical_cmp() {
+        let mut obj_a = JsonbObject::new();
+        obj_a.insert("a".into(), JsonbValue::Number(1.0));
+        let mut obj_b = obj_a.clone();
+        obj_b.insert("b".into(), JsonbValue::Null);
+
+        // Ascending by type precedence, then within each type.
+        let sorted = [
+            JsonbValue::Null,
+            JsonbValue::Bool(false),
+            JsonbValue::Bool(true),
+            JsonbValue::Number(-1.5),
+            JsonbValue::Number(10.0),
+            JsonbValue::Number(f64::NAN),
+            JsonbValue::String("".into()),
+            JsonbValue::String("b".into()),
+            JsonbValue::Array(vec![]),
+            JsonbValue::Array(vec![JsonbValue::Number(1.0)]),
+            JsonbValue::Array(vec![JsonbValue::Number(1.0), JsonbValue::Null]),
+            JsonbValue::Array(vec![JsonbValue::Number(2.0)]),
+            JsonbValue::Object(JsonbObject::new()),
+            JsonbValue::Object(obj_a),
+            JsonbValue::Object(obj_b),
+        ];
+        for (i, a) in sorted.iter().enumerate() {
+            for (j, b) in sorted.iter().enumerate() {
+                assert_eq!(a.logical_cmp(b), i.cmp(&j), "{:?} vs {:?}", a, b);
+            }
+        }
+    }
+
     #[test]
     fn test_jsonb_value_from_impls() {
         let v: JsonbValue = true.into();
@@ -1,4 +1,10 @@
 //! Benchmark modules
+//!
+//! Every group is listed in [`GROUPS`]; adding a benchmark group means
+//! writing a module with a `run(&mut Report)` function and adding one entry
+//! there.
+
+use crate::report::Report;
 
 pub mod e2e;
 pub mod incremental;
@@ -9,3 +15,76 @@ pub mod jsonb;
 pub mod query;
 pub mod reactive;
 pub mod storage;
+
+/// A named group of benchmarks run by the harness.
+pub struct BenchGroup {
+    /// Short identifier used to select the group on the command line.
+    pub id: &'static str,
+    /// Banner title printed before the group runs.
+    pub title: &'static str,
+    /// Runs the group's benchmarks, recording results into the report.
+    pub run: fn(&mut Report),
+}
+
+/// All benchmark groups, in the order they run.
+pub const GROUPS: &[BenchGroup] = &[
+    BenchGroup {
+        id: "index",
+        title: "INDEX PERFORMANCE",
+        run: index::run,
+    },
+    BenchGroup {
+        id: "storage",
+        title: "STORAGE PERFORMANCE",
+        run: storage::run,
+    },
+    BenchGroup {
+        id: "query",
+        title: "QUERY EXECUTION PERFORMANCE",
+        run: query::run,
+    },
+    BenchGroup {
+        id: "join",
+        title: "JOIN PERFORMANCE",
+        run: join::run,
+    },
+    BenchGroup {
+        id: "incremental",
+        title: "INCREMENTAL COMPUTATION PERFORMANCE",
+        run: incremental::run,
+    },
+    BenchGroup {
+        id: "reactive",
+        title: "REACTIVE QUERY PERFORMANCE",
+        run: reactive::run,
+    },
+    BenchGroup {
+        id: "jsonb",
+        title: "JSONB PERFORMANCE",
+        run: jsonb::run,
+    },
+    BenchGroup {
+        id: "e2e",
+        title: "END-TO-END SCENARIOS",
+        run: e2e::run,
+    },
+    BenchGroup {
+        id: "ivm_compare",
+        title: "IVM vs RE-QUERY COMPARISON",
+        run: ivm_compare::run,
+    },
+];
+
+/// Runs the groups whose id is in `only` (or all of them if `only` is empty),
+/// printing a banner before each.
+pub fn run_groups(groups: &[BenchGroup], only: &[String], report: &mut Report) {
+    for group in groups {
+        if !only.is_empty() && !only.iter().any(|id| id == group.id) {
+            continue;
+        }
+        println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        println!("  {}", group.title);
+        println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
+        (group.run)(report);
+    }
+}
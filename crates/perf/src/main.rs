@@ -2,6 +2,9 @@
 //!
 //! Run with: cargo run -p cynos-perf --release
 //! Or from workspace root: cargo run --release -p cynos-perf
+//!
+//! Pass `--group <id>` (repeatable) to run a subset of groups and
+//! `--json <path>` to also write machine-readable results (`-` for stdout).
 
 mod bench;
 mod report;
@@ -12,6 +15,15 @@ use cynos_index::Index;
 use report::Report;
 
 fn main() {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}", message);
+            eprintln!("usage: perf [--group <id>]... [--json <path|->]");
+            std::process::exit(2);
+        }
+    };
+
     let mut report = Report::new();
 
     println!("╔══════════════════════════════════════════════════════════════════╗");
@@ -22,63 +34,61 @@ fn main() {
     println!("Warming up...\n");
     warmup();
 
-    // 1. Index Performance
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("  INDEX PERFORMANCE");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    index::run(&mut report);
-
-    // 2. Storage Performance
-    println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("  STORAGE PERFORMANCE");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    storage::run(&mut report);
-
-    // 3. Query Execution Performance
-    println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("  QUERY EXECUTION PERFORMANCE");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    query::run(&mut report);
-
-    // 4. Join Performance
-    println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("  JOIN PERFORMANCE");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    join::run(&mut report);
-
-    // 5. Incremental Computation Performance
-    println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("  INCREMENTAL COMPUTATION PERFORMANCE");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    incremental::run(&mut report);
-
-    // 6. Reactive Query Performance
-    println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("  REACTIVE QUERY PERFORMANCE");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    reactive::run(&mut report);
-
-    // 7. JSONB Performance
-    println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("  JSONB PERFORMANCE");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    jsonb::run(&mut report);
-
-    // 8. End-to-End Scenarios
-    println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("  END-TO-END SCENARIOS");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    e2e::run(&mut report);
-
-    // 9. IVM vs Re-query Comparison
-    println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("  IVM vs RE-QUERY COMPARISON");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    ivm_compare::run(&mut report);
+    run_groups(GROUPS, &options.groups, &mut report);
 
     // Print Summary
     println!("\n");
     report.print_summary();
+
+    match options.json.as_deref() {
+        None => {}
+        Some("-") => println!("{}", report.to_json()),
+        Some(path) => {
+            if let Err(err) = std::fs::write(path, report.to_json()) {
+                eprintln!("failed to write {}: {}", path, err);
+                std::process::exit(1);
+            }
+            println!("\nJSON results written to {}", path);
+        }
+    }
+}
+
+/// Command-line options.
+struct Options {
+    /// Group ids to run; empty runs every group.
+    groups: Vec<String>,
+    /// Where to write JSON results (`-` for stdout), if anywhere.
+    json: Option<String>,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut options = Options {
+            groups: Vec::new(),
+            json: None,
+        };
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--group" => {
+                    let id = args.next().ok_or("--group needs a group id")?;
+                    if !GROUPS.iter().any(|group| group.id == id) {
+                        let ids: Vec<_> = GROUPS.iter().map(|group| group.id).collect();
+                        return Err(format!(
+                            "unknown group '{}', expected one of: {}",
+                            id,
+                            ids.join(", ")
+                        ));
+                    }
+                    options.groups.push(id);
+                }
+                "--json" => {
+                    options.json = Some(args.next().ok_or("--json needs a path or -")?);
+                }
+                _ => return Err(format!("unknown argument '{}'", arg)),
+            }
+        }
+        Ok(options)
+    }
 }
 
 fn warmup() {
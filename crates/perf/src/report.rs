@@ -1,7 +1,13 @@
 //! Performance report generation
 
 use crate::utils::{format_duration, format_throughput, BenchResult};
+use cynos_jsonb::{JsonbObject, JsonbValue};
 use std::collections::HashMap;
+use std::time::Duration;
+
+/// Version of the JSON layout produced by [`Report::to_json`]. Bump it when
+/// fields are renamed or removed so tooling comparing runs can tell.
+pub const JSON_SCHEMA_VERSION: u32 = 1;
 
 #[derive(Clone)]
 pub struct BenchEntry {
@@ -76,6 +82,29 @@ impl Report {
         });
     }
 
+    /// Serializes every entry, in the order recorded, as one JSON document:
+    ///
+    /// ```json
+    /// {"benchmarks": [{"category": "Index", "name": "BTree insert",
+    ///   "size": 1000, "iterations": 100, "mean_ns": 1234, "median_ns": 1200,
+    ///   "min_ns": 1100, "max_ns": 2000, "std_dev_ns": 80,
+    ///   "throughput_ops_per_sec": 810372.7, "target": null, "passed": null}],
+    ///  "schema_version": 1}
+    /// ```
+    ///
+    /// Optional fields are `null` when a benchmark does not record them.
+    pub fn to_json(&self) -> String {
+        let benchmarks = self.entries.iter().map(BenchEntry::to_json).collect();
+
+        let mut root = JsonbObject::new();
+        root.insert(
+            "schema_version".into(),
+            JsonbValue::Number(JSON_SCHEMA_VERSION as f64),
+        );
+        root.insert("benchmarks".into(), JsonbValue::Array(benchmarks));
+        cynos_jsonb::to_json_string(&JsonbValue::Object(root))
+    }
+
     pub fn print_summary(&self) {
         println!("╔══════════════════════════════════════════════════════════════════╗");
         println!("║                      PERFORMANCE SUMMARY                         ║");
@@ -155,6 +184,44 @@ impl Report {
     }
 }
 
+impl BenchEntry {
+    fn to_json(&self) -> JsonbValue {
+        fn optional<T>(value: Option<T>, f: impl FnOnce(T) -> JsonbValue) -> JsonbValue {
+            value.map(f).unwrap_or(JsonbValue::Null)
+        }
+        fn nanos(d: Duration) -> JsonbValue {
+            JsonbValue::Number(d.as_nanos() as f64)
+        }
+
+        let mut obj = JsonbObject::new();
+        obj.insert("category".into(), JsonbValue::String(self.category.clone()));
+        obj.insert("name".into(), JsonbValue::String(self.name.clone()));
+        obj.insert(
+            "size".into(),
+            optional(self.size, |s| JsonbValue::Number(s as f64)),
+        );
+        obj.insert(
+            "iterations".into(),
+            JsonbValue::Number(self.result.iterations as f64),
+        );
+        obj.insert("mean_ns".into(), nanos(self.result.mean));
+        obj.insert("median_ns".into(), nanos(self.result.median));
+        obj.insert("min_ns".into(), nanos(self.result.min));
+        obj.insert("max_ns".into(), nanos(self.result.max));
+        obj.insert("std_dev_ns".into(), nanos(self.result.std_dev));
+        obj.insert(
+            "throughput_ops_per_sec".into(),
+            optional(self.throughput, JsonbValue::Number),
+        );
+        obj.insert(
+            "target".into(),
+            optional(self.target, |t| JsonbValue::String(t.into())),
+        );
+        obj.insert("passed".into(), optional(self.passed, JsonbValue::Bool));
+        JsonbValue::Object(obj)
+    }
+}
+
 fn format_size(size: usize) -> String {
     if size >= 1_000_000 {
         format!("{}M", size / 1_000_000)
@@ -164,3 +231,73 @@ fn format_size(size: usize) -> String {
         format!("{}", size)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::{run_groups, BenchGroup};
+    use crate::utils::measure;
+
+    fn trivial(report: &mut Report) {
+        let result = measure(5, || std::hint::black_box(1u64) + 1);
+        let throughput = result.throughput(1);
+        report.add_result("Trivial", "add \"one\"", Some(1), result, Some(throughput));
+
+        let result = measure(3, || ());
+        report.add_with_target("Trivial", "noop", None, result, None, "< 1s", true);
+    }
+
+    #[test]
+    fn test_trivial_benchmark_produces_well_formed_json() {
+        let groups = [BenchGroup {
+            id: "trivial",
+            title: "TRIVIAL",
+            run: trivial,
+        }];
+        let mut report = Report::new();
+        run_groups(&groups, &[], &mut report);
+
+        let json = cynos_jsonb::parse_json(&report.to_json()).expect("valid JSON");
+        let JsonbValue::Object(root) = json else {
+            panic!("expected an object, got {:?}", json);
+        };
+        assert_eq!(
+            root.get("schema_version"),
+            Some(&JsonbValue::Number(JSON_SCHEMA_VERSION as f64))
+        );
+        let Some(JsonbValue::Array(benchmarks)) = root.get("benchmarks") else {
+            panic!("missing benchmarks array");
+        };
+        assert_eq!(benchmarks.len(), 2);
+
+        let JsonbValue::Object(first) = &benchmarks[0] else {
+            panic!("expected an object entry");
+        };
+        let str_field = |key| match first.get(key) {
+            Some(JsonbValue::String(s)) => s.as_str(),
+            other => panic!("{}: {:?}", key, other),
+        };
+        assert_eq!(str_field("category"), "Trivial");
+        assert_eq!(str_field("name"), "add \"one\"");
+        assert_eq!(first.get("size"), Some(&JsonbValue::Number(1.0)));
+        assert_eq!(first.get("iterations"), Some(&JsonbValue::Number(5.0)));
+        assert_eq!(first.get("passed"), Some(&JsonbValue::Null));
+        for key in ["mean_ns", "median_ns", "min_ns", "max_ns", "std_dev_ns"] {
+            assert!(matches!(first.get(key), Some(JsonbValue::Number(n)) if *n >= 0.0));
+        }
+        assert!(matches!(
+            first.get("throughput_ops_per_sec"),
+            Some(JsonbValue::Number(_))
+        ));
+
+        let JsonbValue::Object(second) = &benchmarks[1] else {
+            panic!("expected an object entry");
+        };
+        assert_eq!(second.get("size"), Some(&JsonbValue::Null));
+        assert_eq!(
+            second.get("target"),
+            Some(&JsonbValue::String("< 1s".into()))
+        );
+        assert_eq!(second.get("passed"), Some(&JsonbValue::Bool(true)));
+    }
+}
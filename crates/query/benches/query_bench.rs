@@ -14,7 +14,7 @@ use cynos_query::ast::{ColumnRef, Expr, JoinType, SortOrder, ValuePredicate};
 use cynos_query::executor::join::{HashJoin, NestedLoopJoin, SortMergeJoin};
 use cynos_query::executor::{
     FilterExecutor, InMemoryDataSource, LimitExecutor, PhysicalPlanRunner, ProjectExecutor,
-    Relation, SortExecutor,
+    Relation, SortExecutor, TopNExecutor,
 };
 use cynos_query::optimizer::Optimizer;
 use cynos_query::planner::{IndexBounds, LogicalPlan, PhysicalPlan};
@@ -249,6 +249,40 @@ fn bench_limit(c: &mut Criterion) {
     group.finish();
 }
 
+/// TopNExecutor's bounded heap versus a full sort followed by a limit.
+fn bench_topn_executor(c: &mut Criterion) {
+    let mut group = c.benchmark_group("topn_executor");
+
+    for (size, k) in [(10000, 10), (100000, 10), (100000, 100)].iter() {
+        let rows = create_shuffled_rows(*size);
+        let label = format!("n={}_k={}", size, k);
+
+        group.bench_with_input(BenchmarkId::new("topn", &label), &label, |b, _| {
+            b.iter_batched(
+                || Relation::from_rows_owned(rows.clone(), vec!["table".into()]),
+                |relation| {
+                    let executor = TopNExecutor::new(vec![(0, SortOrder::Asc)], *k, 0);
+                    black_box(executor.execute(relation))
+                },
+                BatchSize::LargeInput,
+            )
+        });
+
+        group.bench_with_input(BenchmarkId::new("sort_limit", &label), &label, |b, _| {
+            b.iter_batched(
+                || Relation::from_rows_owned(rows.clone(), vec!["table".into()]),
+                |relation| {
+                    let sorted = SortExecutor::new(vec![(0, SortOrder::Asc)]).execute(relation);
+                    black_box(LimitExecutor::new(*k, 0).execute(sorted))
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
 // ============================================================================
 // Combined Query Benchmarks
 // ============================================================================
@@ -593,6 +627,43 @@ fn bench_e2e_index_scan(c: &mut Criterion) {
     group.finish();
 }
 
+/// End-to-end: SELECT id, name, category FROM users ORDER BY id via index,
+/// against a reordered projection that has to build new rows
+fn bench_e2e_index_scan_projection(c: &mut Criterion) {
+    let mut group = c.benchmark_group("e2e_index_scan_projection");
+
+    for size in [10000, 100000].iter() {
+        let ds = create_data_source(*size);
+        let project = |columns: Vec<Expr>| {
+            PhysicalPlan::project(
+                PhysicalPlan::index_scan("users", "idx_id", None, None),
+                columns,
+            )
+        };
+        let identity = project(vec![
+            Expr::column("users", "id", 0),
+            Expr::column("users", "name", 1),
+            Expr::column("users", "category", 2),
+        ]);
+        let reordered = project(vec![
+            Expr::column("users", "name", 1),
+            Expr::column("users", "id", 0),
+            Expr::column("users", "category", 2),
+        ]);
+
+        group.bench_with_input(BenchmarkId::new("identity", size), size, |b, _| {
+            let runner = PhysicalPlanRunner::new(&ds);
+            b.iter(|| black_box(runner.execute(&identity).unwrap()))
+        });
+        group.bench_with_input(BenchmarkId::new("reordered", size), size, |b, _| {
+            let runner = PhysicalPlanRunner::new(&ds);
+            b.iter(|| black_box(runner.execute(&reordered).unwrap()))
+        });
+    }
+
+    group.finish();
+}
+
 /// End-to-end: Index point lookup
 fn bench_e2e_index_get(c: &mut Criterion) {
     let mut group = c.benchmark_group("e2e_index_get");
@@ -967,6 +1038,7 @@ criterion_group!(
     bench_sort,
     bench_project,
     bench_limit,
+    bench_topn_executor,
     bench_simple_query,
     bench_join_with_filter,
     // End-to-end benchmarks using PhysicalPlanRunner (no optimizer)
@@ -977,6 +1049,7 @@ criterion_group!(
     bench_e2e_sort_merge_join,
     bench_e2e_complex_query,
     bench_e2e_index_scan,
+    bench_e2e_index_scan_projection,
     bench_e2e_index_get,
     // Optimized end-to-end benchmarks (with optimizer)
     bench_optimized_filter,
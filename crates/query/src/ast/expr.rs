@@ -3,10 +3,11 @@
 use alloc::boxed::Box;
 use alloc::string::String;
 use alloc::vec::Vec;
-use cynos_core::Value;
+use cynos_core::{DataType, Value};
 
 /// Reference to a column in a table.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColumnRef {
     /// Table name (or alias).
     pub table: String,
@@ -34,6 +35,7 @@ impl ColumnRef {
 
 /// Binary operators.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BinaryOp {
     // Comparison
     Eq,
@@ -42,6 +44,9 @@ pub enum BinaryOp {
     Le,
     Gt,
     Ge,
+    /// NULL-safe equality: true when both sides are NULL, false when only
+    /// one is, and `=` otherwise. Never NULL.
+    IsNotDistinctFrom,
     // Logical
     And,
     Or,
@@ -51,6 +56,18 @@ pub enum BinaryOp {
     Mul,
     Div,
     Mod,
+    /// Exponentiation (left ^ right); always Float64, unlike `POWER`.
+    Pow,
+    /// Division rounded toward negative infinity (left // right).
+    FloorDiv,
+    // Bitwise, on Int32/Int64 only
+    BitAnd,
+    BitOr,
+    BitXor,
+    /// Left shift; bits shifted past the width are discarded.
+    Shl,
+    /// Arithmetic (sign-extending) right shift.
+    Shr,
     // String/Pattern
     Like,
     // Set
@@ -60,6 +77,7 @@ pub enum BinaryOp {
 
 /// Unary operators.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UnaryOp {
     Not,
     Neg,
@@ -69,6 +87,7 @@ pub enum UnaryOp {
 
 /// Aggregate functions.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AggregateFunc {
     Count,
     Sum,
@@ -78,10 +97,15 @@ pub enum AggregateFunc {
     Distinct,
     StdDev,
     GeoMean,
+    /// Value of a second column from the row with the smallest key.
+    ArgMin,
+    /// Value of a second column from the row with the largest key.
+    ArgMax,
 }
 
 /// Sort order.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SortOrder {
     #[default]
     Asc,
@@ -90,11 +114,17 @@ pub enum SortOrder {
 
 /// Expression AST node.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Expr {
     /// Column reference.
     Column(ColumnRef),
     /// Literal value.
     Literal(Value),
+    /// NULL literal of a known type.
+    ///
+    /// Evaluates like `Literal(Value::Null)`, but lets type inference see
+    /// the intended type, e.g. in `COALESCE(NULL::BIGINT, ...)`.
+    TypedNull(DataType),
     /// Binary operation.
     BinaryOp {
         left: Box<Expr>,
@@ -105,6 +135,9 @@ pub enum Expr {
     UnaryOp { op: UnaryOp, expr: Box<Expr> },
     /// Function call.
     Function { name: String, args: Vec<Expr> },
+    /// Explicit type conversion, `CAST(expr AS target)`. Evaluates to NULL
+    /// when the value has no representation in `target`.
+    Cast { expr: Box<Expr>, target: DataType },
     /// Aggregate function.
     Aggregate {
         func: AggregateFunc,
@@ -148,6 +181,11 @@ impl Expr {
         Expr::Literal(value.into())
     }
 
+    /// Creates a NULL literal of the given type.
+    pub fn null(data_type: DataType) -> Self {
+        Expr::TypedNull(data_type)
+    }
+
     /// Creates an equality expression.
     pub fn eq(left: Expr, right: Expr) -> Self {
         Expr::BinaryOp {
@@ -157,6 +195,15 @@ impl Expr {
         }
     }
 
+    /// Creates a NULL-safe equality expression (`IS NOT DISTINCT FROM`).
+    pub fn is_not_distinct_from(left: Expr, right: Expr) -> Self {
+        Expr::BinaryOp {
+            left: Box::new(left),
+            op: BinaryOp::IsNotDistinctFrom,
+            right: Box::new(right),
+        }
+    }
+
     /// Creates a not-equal expression.
     pub fn ne(left: Expr, right: Expr) -> Self {
         Expr::BinaryOp {
@@ -220,6 +267,198 @@ impl Expr {
         }
     }
 
+    /// Creates an addition expression (left + right).
+    pub fn plus(left: Expr, right: Expr) -> Self {
+        Expr::BinaryOp {
+            left: Box::new(left),
+            op: BinaryOp::Add,
+            right: Box::new(right),
+        }
+    }
+
+    /// Creates a subtraction expression (left - right).
+    pub fn minus(left: Expr, right: Expr) -> Self {
+        Expr::BinaryOp {
+            left: Box::new(left),
+            op: BinaryOp::Sub,
+            right: Box::new(right),
+        }
+    }
+
+    /// Creates a multiplication expression (left * right).
+    pub fn multiply(left: Expr, right: Expr) -> Self {
+        Expr::BinaryOp {
+            left: Box::new(left),
+            op: BinaryOp::Mul,
+            right: Box::new(right),
+        }
+    }
+
+    /// Creates a division expression (left / right).
+    pub fn divide(left: Expr, right: Expr) -> Self {
+        Expr::BinaryOp {
+            left: Box::new(left),
+            op: BinaryOp::Div,
+            right: Box::new(right),
+        }
+    }
+
+    /// Creates a modulo expression (left % right).
+    pub fn modulo(left: Expr, right: Expr) -> Self {
+        Expr::BinaryOp {
+            left: Box::new(left),
+            op: BinaryOp::Mod,
+            right: Box::new(right),
+        }
+    }
+
+    /// Creates a CAST(expr AS target) expression.
+    pub fn cast(expr: Expr, target: DataType) -> Self {
+        Expr::Cast {
+            expr: Box::new(expr),
+            target,
+        }
+    }
+
+    /// Creates a floor division expression (left // right).
+    pub fn floor_div(left: Expr, right: Expr) -> Self {
+        Expr::BinaryOp {
+            left: Box::new(left),
+            op: BinaryOp::FloorDiv,
+            right: Box::new(right),
+        }
+    }
+
+    /// Creates a POWER(base, exponent) expression.
+    ///
+    /// Integer operands give an integer result; the `^` operator
+    /// (`BinaryOp::Pow`) always computes in Float64.
+    pub fn power(base: Expr, exponent: Expr) -> Self {
+        Expr::Function {
+            name: "power".into(),
+            args: alloc::vec![base, exponent],
+        }
+    }
+
+    /// Creates a CONCAT(args...) expression, the function form of `||`.
+    pub fn concat(args: Vec<Expr>) -> Self {
+        Expr::Function {
+            name: "concat".into(),
+            args,
+        }
+    }
+
+    /// Creates a CONTAINS(expr, substring) expression.
+    pub fn contains(expr: Expr, substring: Expr) -> Self {
+        Expr::Function {
+            name: "contains".into(),
+            args: alloc::vec![expr, substring],
+        }
+    }
+
+    /// Creates a STARTS_WITH(expr, prefix) expression.
+    pub fn starts_with(expr: Expr, prefix: Expr) -> Self {
+        Expr::Function {
+            name: "starts_with".into(),
+            args: alloc::vec![expr, prefix],
+        }
+    }
+
+    /// Creates an ENDS_WITH(expr, suffix) expression.
+    pub fn ends_with(expr: Expr, suffix: Expr) -> Self {
+        Expr::Function {
+            name: "ends_with".into(),
+            args: alloc::vec![expr, suffix],
+        }
+    }
+
+    /// Creates a SPLIT_PART(expr, delimiter, n) expression (1-based `n`).
+    pub fn split_part(expr: Expr, delimiter: Expr, n: Expr) -> Self {
+        Expr::Function {
+            name: "split_part".into(),
+            args: alloc::vec![expr, delimiter, n],
+        }
+    }
+
+    /// Creates a DATE_TRUNC(unit, expr) expression, e.g. with unit `'day'`.
+    pub fn date_trunc(unit: Expr, expr: Expr) -> Self {
+        Expr::Function {
+            name: "date_trunc".into(),
+            args: alloc::vec![unit, expr],
+        }
+    }
+
+    /// Creates an EXTRACT(field, expr) expression, e.g. with field `'year'`.
+    pub fn extract(field: Expr, expr: Expr) -> Self {
+        Expr::Function {
+            name: "extract".into(),
+            args: alloc::vec![field, expr],
+        }
+    }
+
+    /// Creates a TO_JSONB(expr) expression converting a value to JSONB.
+    pub fn to_jsonb(expr: Expr) -> Self {
+        Expr::Function {
+            name: "to_jsonb".into(),
+            args: alloc::vec![expr],
+        }
+    }
+
+    /// Creates a ROW_TO_JSONB(exprs...) expression producing a JSONB object.
+    /// Column arguments are keyed by column name, other arguments by
+    /// position (`f1`, `f2`, ...).
+    pub fn row_to_jsonb(exprs: Vec<Expr>) -> Self {
+        Expr::Function {
+            name: "row_to_jsonb".into(),
+            args: exprs,
+        }
+    }
+
+    /// Creates a bitwise AND expression (left & right).
+    pub fn bit_and(left: Expr, right: Expr) -> Self {
+        Expr::BinaryOp {
+            left: Box::new(left),
+            op: BinaryOp::BitAnd,
+            right: Box::new(right),
+        }
+    }
+
+    /// Creates a bitwise OR expression (left | right).
+    pub fn bit_or(left: Expr, right: Expr) -> Self {
+        Expr::BinaryOp {
+            left: Box::new(left),
+            op: BinaryOp::BitOr,
+            right: Box::new(right),
+        }
+    }
+
+    /// Creates a bitwise XOR expression (left XOR right).
+    pub fn bit_xor(left: Expr, right: Expr) -> Self {
+        Expr::BinaryOp {
+            left: Box::new(left),
+            op: BinaryOp::BitXor,
+            right: Box::new(right),
+        }
+    }
+
+    /// Creates a left shift expression (left << right).
+    pub fn shift_left(left: Expr, right: Expr) -> Self {
+        Expr::BinaryOp {
+            left: Box::new(left),
+            op: BinaryOp::Shl,
+            right: Box::new(right),
+        }
+    }
+
+    /// Creates an arithmetic right shift expression (left >> right).
+    pub fn shift_right(left: Expr, right: Expr) -> Self {
+        Expr::BinaryOp {
+            left: Box::new(left),
+            op: BinaryOp::Shr,
+            right: Box::new(right),
+        }
+    }
+
     /// Creates a NOT expression.
     pub fn not(expr: Expr) -> Self {
         Expr::UnaryOp {
@@ -298,6 +537,32 @@ impl Expr {
         }
     }
 
+    /// Creates an ARG_MIN aggregate: `value` from the row with the smallest `key`.
+    ///
+    /// Both arguments are carried as a two-argument `arg_min` function call.
+    pub fn arg_min(key: Expr, value: Expr) -> Self {
+        Expr::Aggregate {
+            func: AggregateFunc::ArgMin,
+            expr: Some(Box::new(Expr::Function {
+                name: "arg_min".into(),
+                args: alloc::vec![key, value],
+            })),
+            distinct: false,
+        }
+    }
+
+    /// Creates an ARG_MAX aggregate: `value` from the row with the largest `key`.
+    pub fn arg_max(key: Expr, value: Expr) -> Self {
+        Expr::Aggregate {
+            func: AggregateFunc::ArgMax,
+            expr: Some(Box::new(Expr::Function {
+                name: "arg_max".into(),
+                args: alloc::vec![key, value],
+            })),
+            distinct: false,
+        }
+    }
+
     /// Creates a greater-than-or-equal expression.
     pub fn gte(left: Expr, right: Expr) -> Self {
         Expr::BinaryOp {
@@ -419,6 +684,19 @@ impl Expr {
         )
     }
 
+    /// Checks if this is a NULL-safe equi-join condition
+    /// (column IS NOT DISTINCT FROM column).
+    pub fn is_null_safe_equi_join(&self) -> bool {
+        matches!(
+            self,
+            Expr::BinaryOp {
+                op: BinaryOp::IsNotDistinctFrom,
+                left,
+                right
+            } if matches!(left.as_ref(), Expr::Column(_)) && matches!(right.as_ref(), Expr::Column(_))
+        )
+    }
+
     /// Checks if this is a range join condition (>, <, >=, <=).
     pub fn is_range_join(&self) -> bool {
         matches!(
@@ -430,6 +708,114 @@ impl Expr {
             } if matches!(left.as_ref(), Expr::Column(_)) && matches!(right.as_ref(), Expr::Column(_))
         )
     }
+
+    /// Infers the type of the value this expression produces.
+    ///
+    /// `column_type` resolves column references. Returns None when the type
+    /// cannot be known, e.g. for an untyped NULL literal or an unknown
+    /// function; use `Expr::null` to give a NULL a type.
+    pub fn infer_type(
+        &self,
+        column_type: &dyn Fn(&ColumnRef) -> Option<DataType>,
+    ) -> Option<DataType> {
+        match self {
+            Expr::Column(column) => column_type(column),
+            Expr::Literal(value) => value.data_type(),
+            Expr::TypedNull(data_type) => Some(*data_type),
+            Expr::BinaryOp { left, op, right } => match op {
+                BinaryOp::Add
+                | BinaryOp::Sub
+                | BinaryOp::Mul
+                | BinaryOp::Div
+                | BinaryOp::Mod
+                | BinaryOp::FloorDiv => {
+                    match (left.infer_type(column_type), right.infer_type(column_type)) {
+                        (Some(left), Some(right)) if left == right => Some(left),
+                        (
+                            Some(DataType::Int32 | DataType::Int64),
+                            Some(DataType::Int32 | DataType::Int64),
+                        ) => Some(DataType::Int64),
+                        (Some(_), Some(_)) => Some(DataType::Float64),
+                        (known, None) | (None, known) => known,
+                    }
+                }
+                BinaryOp::BitAnd | BinaryOp::BitOr | BinaryOp::BitXor => {
+                    match (left.infer_type(column_type), right.infer_type(column_type)) {
+                        (Some(DataType::Int32), Some(DataType::Int32)) => Some(DataType::Int32),
+                        (known, None) | (None, known) => known,
+                        _ => Some(DataType::Int64),
+                    }
+                }
+                BinaryOp::Pow => Some(DataType::Float64),
+                // A shift keeps the width of the value being shifted.
+                BinaryOp::Shl | BinaryOp::Shr => left.infer_type(column_type),
+                _ => Some(DataType::Boolean),
+            },
+            Expr::UnaryOp { op, expr } => match op {
+                UnaryOp::Neg => expr.infer_type(column_type),
+                UnaryOp::Not | UnaryOp::IsNull | UnaryOp::IsNotNull => Some(DataType::Boolean),
+            },
+            Expr::Function { name, args } => match name.to_uppercase().as_str() {
+                // The first argument with a known type decides, so a typed
+                // NULL in front still types the result.
+                "COALESCE" => args.iter().find_map(|arg| arg.infer_type(column_type)),
+                "ABS" => args.first()?.infer_type(column_type),
+                "POWER" | "POW" => match (
+                    args.first()?.infer_type(column_type),
+                    args.get(1)?.infer_type(column_type),
+                ) {
+                    (Some(DataType::Int32), Some(DataType::Int32)) => Some(DataType::Int32),
+                    (
+                        Some(DataType::Int32 | DataType::Int64),
+                        Some(DataType::Int32 | DataType::Int64),
+                    ) => Some(DataType::Int64),
+                    _ => Some(DataType::Float64),
+                },
+                // The winning argument is returned as is, so the type is
+                // only known when every typed argument agrees.
+                "GREATEST" | "LEAST" => {
+                    let mut types = args.iter().filter_map(|arg| arg.infer_type(column_type));
+                    let first = types.next()?;
+                    types.all(|t| t == first).then_some(first)
+                }
+                "UPPER" | "LOWER" | "CONCAT" | "SPLIT_PART" => Some(DataType::String),
+                "LENGTH" | "EXTRACT" => Some(DataType::Int64),
+                "DATE_TRUNC" => Some(DataType::DateTime),
+                "TO_JSONB" | "ROW_TO_JSONB" => Some(DataType::Jsonb),
+                "JSONB_PATH_EQ" | "JSONB_CONTAINS" | "JSONB_EXISTS" | "CONTAINS"
+                | "STARTS_WITH" | "ENDS_WITH" => Some(DataType::Boolean),
+                _ => None,
+            },
+            Expr::Cast { target, .. } => Some(*target),
+            Expr::Aggregate { func, expr, .. } => {
+                let input = expr.as_ref().and_then(|expr| expr.infer_type(column_type));
+                match func {
+                    AggregateFunc::Count | AggregateFunc::Distinct => Some(DataType::Int64),
+                    AggregateFunc::Avg | AggregateFunc::StdDev | AggregateFunc::GeoMean => {
+                        Some(DataType::Float64)
+                    }
+                    AggregateFunc::Sum => match input {
+                        Some(DataType::Float64) => Some(DataType::Float64),
+                        _ => Some(DataType::Int64),
+                    },
+                    AggregateFunc::Min | AggregateFunc::Max => input,
+                    // ARG_MIN/ARG_MAX wrap `arg_min(key, value)` and return the value.
+                    AggregateFunc::ArgMin | AggregateFunc::ArgMax => match expr.as_deref() {
+                        Some(Expr::Function { args, .. }) => args.get(1)?.infer_type(column_type),
+                        _ => None,
+                    },
+                }
+            }
+            Expr::Between { .. }
+            | Expr::NotBetween { .. }
+            | Expr::In { .. }
+            | Expr::NotIn { .. }
+            | Expr::Like { .. }
+            | Expr::NotLike { .. }
+            | Expr::Match { .. }
+            | Expr::NotMatch { .. } => Some(DataType::Boolean),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -475,4 +861,52 @@ mod tests {
         assert!(!range.is_equi_join());
         assert!(range.is_range_join());
     }
+
+    #[test]
+    fn test_typed_null_in_coalesce_infers_type() {
+        let column_type = |column: &ColumnRef| match column.column.as_str() {
+            "age" => Some(DataType::Int32),
+            _ => None,
+        };
+
+        let untyped = Expr::Function {
+            name: "coalesce".into(),
+            args: alloc::vec![Expr::literal(Value::Null), Expr::literal(Value::Null)],
+        };
+        assert_eq!(untyped.infer_type(&column_type), None);
+
+        let typed = Expr::Function {
+            name: "coalesce".into(),
+            args: alloc::vec![Expr::null(DataType::Int64), Expr::literal(Value::Null)],
+        };
+        assert_eq!(typed.infer_type(&column_type), Some(DataType::Int64));
+
+        // The typed NULL leads, so it wins over the column behind it.
+        let with_column = Expr::Function {
+            name: "COALESCE".into(),
+            args: alloc::vec![Expr::null(DataType::Float64), Expr::column("t", "age", 0)],
+        };
+        assert_eq!(
+            with_column.infer_type(&column_type),
+            Some(DataType::Float64)
+        );
+
+        let comparison = Expr::eq(Expr::column("t", "age", 0), Expr::null(DataType::Int32));
+        assert_eq!(comparison.infer_type(&column_type), Some(DataType::Boolean));
+
+        let sum = Expr::BinaryOp {
+            left: Box::new(Expr::column("t", "age", 0)),
+            op: BinaryOp::Add,
+            right: Box::new(Expr::null(DataType::Int32)),
+        };
+        assert_eq!(sum.infer_type(&column_type), Some(DataType::Int32));
+
+        let widened = Expr::plus(Expr::column("t", "age", 0), Expr::literal(Value::Int64(1)));
+        assert_eq!(widened.infer_type(&column_type), Some(DataType::Int64));
+        let float = Expr::plus(
+            Expr::literal(Value::Int64(1)),
+            Expr::literal(Value::Float64(0.5)),
+        );
+        assert_eq!(float.infer_type(&column_type), Some(DataType::Float64));
+    }
 }
@@ -119,12 +119,29 @@ impl Predicate for ValuePredicate {
 
 /// Join type for join predicates.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum JoinType {
     Inner,
     LeftOuter,
     RightOuter,
     FullOuter,
     Cross,
+    /// Left semi-join: each left row is emitted once if it has at least one
+    /// match on the right. The right side contributes no columns, so this is
+    /// the decorrelated form of `WHERE EXISTS (...)`.
+    Semi,
+    /// Left anti-join: each left row is emitted once if it has no match on
+    /// the right. The right side contributes no columns, so this is the
+    /// decorrelated form of `WHERE NOT EXISTS (...)`.
+    Anti,
+}
+
+impl JoinType {
+    /// Returns true for semi- and anti-joins, whose output is the left side
+    /// only.
+    pub fn outputs_left_only(self) -> bool {
+        matches!(self, JoinType::Semi | JoinType::Anti)
+    }
 }
 
 /// A join predicate compares columns from two tables.
@@ -0,0 +1,144 @@
+//! CHECK constraint predicates.
+//!
+//! `ExprCheck` lets a table declare a CHECK constraint as a query expression,
+//! e.g. `age >= 0`, through `TableBuilder::add_check`.
+
+use crate::ast::Expr;
+use crate::executor::{InMemoryDataSource, PhysicalPlanRunner};
+use alloc::string::String;
+use alloc::vec::Vec;
+use cynos_core::schema::CheckPredicate;
+use cynos_core::{Row, Value};
+
+/// A CHECK constraint predicate backed by an expression over one table.
+///
+/// Column references use table-relative indices. A row passes unless the
+/// expression evaluates to false; NULL and non-boolean results pass.
+#[derive(Clone, Debug)]
+pub struct ExprCheck {
+    expr: Expr,
+}
+
+impl ExprCheck {
+    /// Creates a check from a predicate expression.
+    pub fn new(expr: Expr) -> Self {
+        Self { expr }
+    }
+
+    /// Returns the predicate expression.
+    pub fn expr(&self) -> &Expr {
+        &self.expr
+    }
+}
+
+impl CheckPredicate for ExprCheck {
+    fn columns(&self) -> Vec<(String, usize)> {
+        let mut columns = Vec::new();
+        collect_columns(&self.expr, &mut columns);
+        columns
+    }
+
+    fn check(&self, row: &Row) -> bool {
+        // An empty data source and a runner over it cost no allocation, so
+        // the row is evaluated in place.
+        let data_source = InMemoryDataSource::new();
+        let runner = PhysicalPlanRunner::new(&data_source);
+        runner.eval_values(&self.expr, row.values()) != Value::Boolean(false)
+    }
+}
+
+fn collect_columns(expr: &Expr, columns: &mut Vec<(String, usize)>) {
+    match expr {
+        Expr::Column(col) => {
+            if !columns.iter().any(|(_, index)| *index == col.index) {
+                columns.push((col.column.clone(), col.index));
+            }
+        }
+        Expr::BinaryOp { left, right, .. } => {
+            collect_columns(left, columns);
+            collect_columns(right, columns);
+        }
+        Expr::UnaryOp { expr, .. }
+        | Expr::Cast { expr, .. }
+        | Expr::Like { expr, .. }
+        | Expr::NotLike { expr, .. }
+        | Expr::Match { expr, .. }
+        | Expr::NotMatch { expr, .. } => collect_columns(expr, columns),
+        Expr::Function { args, .. } => {
+            for arg in args {
+                collect_columns(arg, columns);
+            }
+        }
+        Expr::Aggregate { expr, .. } => {
+            if let Some(expr) = expr {
+                collect_columns(expr, columns);
+            }
+        }
+        Expr::Between { expr, low, high } | Expr::NotBetween { expr, low, high } => {
+            collect_columns(expr, columns);
+            collect_columns(low, columns);
+            collect_columns(high, columns);
+        }
+        Expr::In { expr, list } | Expr::NotIn { expr, list } => {
+            collect_columns(expr, columns);
+            for item in list {
+                collect_columns(item, columns);
+            }
+        }
+        Expr::Literal(_) | Expr::TypedNull(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use cynos_core::schema::TableBuilder;
+    use cynos_core::DataType;
+
+    fn age_non_negative(index: usize) -> ExprCheck {
+        ExprCheck::new(Expr::ge(
+            Expr::column("users", "age", index),
+            Expr::literal(Value::Int64(0)),
+        ))
+    }
+
+    #[test]
+    fn test_expr_check_evaluates_rows() {
+        let check = age_non_negative(1);
+        let row = |age: Value| Row::new(1, vec![Value::Int64(1), age]);
+
+        assert_eq!(check.columns(), vec![(String::from("age"), 1)]);
+        assert!(check.check(&row(Value::Int64(30))));
+        assert!(!check.check(&row(Value::Int64(-1))));
+        // A NULL comparison is unknown, which passes.
+        assert!(check.check(&row(Value::Null)));
+    }
+
+    #[test]
+    fn test_expr_check_columns_resolve_at_build() {
+        let builder = || {
+            TableBuilder::new("users")
+                .unwrap()
+                .add_column("id", DataType::Int64)
+                .unwrap()
+                .add_column("age", DataType::Int64)
+                .unwrap()
+        };
+        assert!(builder()
+            .add_check("age_non_negative", age_non_negative(1))
+            .unwrap()
+            .build()
+            .is_ok());
+
+        let unknown = ExprCheck::new(Expr::gt(
+            Expr::column("users", "height", 2),
+            Expr::literal(Value::Int64(0)),
+        ));
+        let result = builder()
+            .add_check("height_positive", unknown)
+            .unwrap()
+            .build();
+        assert!(result.is_err());
+    }
+}
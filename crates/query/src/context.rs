@@ -1,7 +1,14 @@
 //! Execution context for query execution.
 
+use alloc::collections::BTreeMap;
 use alloc::string::String;
 use alloc::vec::Vec;
+use cynos_core::Value;
+use cynos_index::{KeyHistogram, KeyRange};
+
+/// Default for [`ExecutionContext::max_index_selectivity`]: a range expected to
+/// match more than 30% of a table is read with a filtered scan instead.
+pub const DEFAULT_MAX_INDEX_SELECTIVITY: f64 = 0.3;
 
 /// Index type enumeration for query optimization.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
@@ -93,20 +100,96 @@ impl IndexInfo {
 }
 
 /// Execution context providing access to table metadata and statistics.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct ExecutionContext {
     /// Table statistics for optimization.
-    table_stats: alloc::collections::BTreeMap<String, TableStats>,
+    table_stats: BTreeMap<String, TableStats>,
+    /// Column histograms keyed by (table, column).
+    histograms: BTreeMap<(String, String), KeyHistogram<Value>>,
+    /// Number of columns in each table's rows.
+    column_counts: BTreeMap<String, usize>,
+    /// Largest estimated fraction of a table an index range scan may cover.
+    max_index_selectivity: f64,
+}
+
+impl Default for ExecutionContext {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ExecutionContext {
     /// Creates a new empty execution context.
     pub fn new() -> Self {
         Self {
-            table_stats: alloc::collections::BTreeMap::new(),
+            table_stats: BTreeMap::new(),
+            histograms: BTreeMap::new(),
+            column_counts: BTreeMap::new(),
+            max_index_selectivity: DEFAULT_MAX_INDEX_SELECTIVITY,
         }
     }
 
+    /// Registers a histogram for `table.column`.
+    pub fn register_histogram(
+        &mut self,
+        table: impl Into<String>,
+        column: impl Into<String>,
+        histogram: KeyHistogram<Value>,
+    ) {
+        self.histograms
+            .insert((table.into(), column.into()), histogram);
+    }
+
+    /// Gets the histogram registered for `table.column`.
+    pub fn histogram(&self, table: &str, column: &str) -> Option<&KeyHistogram<Value>> {
+        self.histograms.get(&(table.into(), column.into()))
+    }
+
+    /// Registers the number of columns in `table`'s rows.
+    pub fn register_column_count(&mut self, table: impl Into<String>, count: usize) {
+        self.column_counts.insert(table.into(), count);
+    }
+
+    /// Gets the number of columns registered for `table`.
+    pub fn column_count(&self, table: &str) -> Option<usize> {
+        self.column_counts.get(table).copied()
+    }
+
+    /// Estimates the fraction of `table` whose `column` lies between the
+    /// inclusive bounds, where `None` leaves that side unbounded. Returns
+    /// `None` without a histogram for the column or with an empty one.
+    pub fn estimate_range_selectivity(
+        &self,
+        table: &str,
+        column: &str,
+        lower: Option<&Value>,
+        upper: Option<&Value>,
+    ) -> Option<f64> {
+        let histogram = self.histogram(table, column)?;
+        if histogram.total() == 0 {
+            return None;
+        }
+        let range = match (lower.cloned(), upper.cloned()) {
+            (None, None) => KeyRange::all(),
+            (Some(lower), None) => KeyRange::lower_bound(lower, false),
+            (None, Some(upper)) => KeyRange::upper_bound(upper, false),
+            (Some(lower), Some(upper)) => KeyRange::bound(lower, upper, false, false),
+        };
+        Some(histogram.estimate_selectivity(&range))
+    }
+
+    /// Returns the largest estimated fraction of a table that index selection
+    /// still reads through an index range scan.
+    pub fn max_index_selectivity(&self) -> f64 {
+        self.max_index_selectivity
+    }
+
+    /// Sets the largest estimated fraction of a table that index selection
+    /// still reads through an index range scan.
+    pub fn set_max_index_selectivity(&mut self, fraction: f64) {
+        self.max_index_selectivity = fraction;
+    }
+
     /// Registers table statistics.
     pub fn register_table(&mut self, table: impl Into<String>, stats: TableStats) {
         self.table_stats.insert(table.into(), stats);
@@ -207,6 +290,42 @@ mod tests {
         assert!(!ctx.has_index("users", &["name"]));
     }
 
+    #[test]
+    fn test_estimate_range_selectivity() {
+        let mut ctx = ExecutionContext::new();
+        let values: Vec<Value> = (0..1000).map(Value::Int64).collect();
+        ctx.register_histogram(
+            "users",
+            "id",
+            KeyHistogram::from_sorted(32, values.iter().map(|v| (v, 1))),
+        );
+
+        let estimate = |lower: Option<i64>, upper: Option<i64>| {
+            ctx.estimate_range_selectivity(
+                "users",
+                "id",
+                lower.map(Value::Int64).as_ref(),
+                upper.map(Value::Int64).as_ref(),
+            )
+            .unwrap()
+        };
+        assert!((estimate(Some(100), None) - 0.9).abs() < 0.03);
+        assert!(estimate(None, Some(10)) < 0.03);
+        assert!((estimate(Some(250), Some(750)) - 0.5).abs() < 0.03);
+        assert_eq!(estimate(Some(2000), None), 0.0);
+        assert_eq!(estimate(None, None), 1.0);
+
+        assert_eq!(
+            ctx.estimate_range_selectivity("users", "name", None, None),
+            None
+        );
+        ctx.register_histogram("users", "name", KeyHistogram::from_sorted(32, []));
+        assert_eq!(
+            ctx.estimate_range_selectivity("users", "name", None, None),
+            None
+        );
+    }
+
     #[test]
     fn test_find_index() {
         let mut ctx = ExecutionContext::new();
@@ -6,15 +6,72 @@ use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::rc::Rc;
 use alloc::string::String;
 use alloc::vec::Vec;
-use cynos_core::{Row, Value};
+use core::cmp::Ordering;
+use cynos_core::{Decimal, Row, Value};
+use cynos_index::Comparator;
 use libm::{exp, log, sqrt};
 
 /// Aggregate executor - computes aggregate functions.
 pub struct AggregateExecutor {
     /// Group by column indices.
     group_by: Vec<usize>,
-    /// Aggregates to compute: (function, column_index).
-    aggregates: Vec<(AggregateFunc, Option<usize>)>,
+    /// Aggregates to compute.
+    aggregates: Vec<AggregateSpec>,
+}
+
+/// A single aggregate computed by an `AggregateExecutor`.
+#[derive(Clone)]
+pub struct AggregateSpec {
+    func: AggregateFunc,
+    /// Input column; for ARG_MIN/ARG_MAX, the key column.
+    column_index: Option<usize>,
+    /// ARG_MIN/ARG_MAX only: the column returned from the extremal row.
+    arg_column_index: Option<usize>,
+    /// Ordering used by MIN, MAX, ARG_MIN and ARG_MAX instead of `Value`'s.
+    comparator: Option<Rc<dyn Comparator<Value>>>,
+}
+
+impl AggregateSpec {
+    /// Creates an aggregate over `column_index` (`None` for COUNT(*)).
+    pub fn new(func: AggregateFunc, column_index: Option<usize>) -> Self {
+        Self {
+            func,
+            column_index,
+            arg_column_index: None,
+            comparator: None,
+        }
+    }
+
+    /// Creates an ARG_MIN aggregate returning `value_column` from the row with
+    /// the smallest `key_column`.
+    pub fn arg_min(key_column: usize, value_column: usize) -> Self {
+        Self {
+            arg_column_index: Some(value_column),
+            ..Self::new(AggregateFunc::ArgMin, Some(key_column))
+        }
+    }
+
+    /// Creates an ARG_MAX aggregate returning `value_column` from the row with
+    /// the largest `key_column`.
+    pub fn arg_max(key_column: usize, value_column: usize) -> Self {
+        Self {
+            arg_column_index: Some(value_column),
+            ..Self::new(AggregateFunc::ArgMax, Some(key_column))
+        }
+    }
+
+    /// Orders the input with `comparator` instead of the natural `Value`
+    /// ordering. Only MIN, MAX, ARG_MIN and ARG_MAX consult it.
+    pub fn with_comparator(mut self, comparator: Rc<dyn Comparator<Value>>) -> Self {
+        self.comparator = Some(comparator);
+        self
+    }
+}
+
+impl From<(AggregateFunc, Option<usize>)> for AggregateSpec {
+    fn from((func, column_index): (AggregateFunc, Option<usize>)) -> Self {
+        Self::new(func, column_index)
+    }
 }
 
 struct GroupState {
@@ -34,11 +91,15 @@ enum GroupKeyValue {
     DateTime(i64),
     Bytes(Vec<u8>),
     Jsonb(Vec<u8>),
+    /// Normalized mantissa and scale, so equal decimals group together.
+    Decimal(i128, u32),
 }
 
 enum SumOutputMode {
     Integer,
     Float,
+    /// Decimal inputs and no floats: the exact sum is the result.
+    Decimal,
 }
 
 enum AggregateState {
@@ -52,20 +113,27 @@ enum AggregateState {
     Sum {
         column_index: usize,
         sum: f64,
+        /// Exact sum of the integer and decimal inputs; `None` on overflow.
+        exact: Option<Decimal>,
         output_mode: SumOutputMode,
     },
     Avg {
         column_index: usize,
         sum: f64,
         count: u64,
+        /// Exact sum while every input is an integer or decimal.
+        exact: Option<Decimal>,
+        has_decimal: bool,
     },
-    Min {
-        column_index: usize,
-        value: Option<Value>,
-    },
-    Max {
+    /// MIN, MAX, ARG_MIN and ARG_MAX: keeps the first row whose key compares
+    /// as `wanted` against every other key.
+    Extremum {
         column_index: usize,
-        value: Option<Value>,
+        arg_column_index: Option<usize>,
+        wanted: Ordering,
+        comparator: Option<Rc<dyn Comparator<Value>>>,
+        key: Option<Value>,
+        arg: Value,
     },
     Distinct {
         column_index: usize,
@@ -87,6 +155,11 @@ enum AggregateState {
 impl AggregateExecutor {
     /// Creates a new aggregate executor.
     pub fn new(group_by: Vec<usize>, aggregates: Vec<(AggregateFunc, Option<usize>)>) -> Self {
+        Self::with_specs(group_by, aggregates.into_iter().map(Into::into).collect())
+    }
+
+    /// Creates a new aggregate executor from full aggregate specifications.
+    pub fn with_specs(group_by: Vec<usize>, aggregates: Vec<AggregateSpec>) -> Self {
         Self {
             group_by,
             aggregates,
@@ -165,10 +238,7 @@ impl AggregateExecutor {
     }
 
     fn init_states(&self) -> Vec<AggregateState> {
-        self.aggregates
-            .iter()
-            .map(|(func, column_index)| AggregateState::new(*func, *column_index))
-            .collect()
+        self.aggregates.iter().map(AggregateState::new).collect()
     }
 
     fn update_states(&self, states: &mut [AggregateState], entry: &RelationEntry) {
@@ -183,8 +253,17 @@ impl AggregateExecutor {
 }
 
 impl AggregateState {
-    fn new(func: AggregateFunc, column_index: Option<usize>) -> Self {
-        match func {
+    fn new(spec: &AggregateSpec) -> Self {
+        let column_index = spec.column_index;
+        let extremum = |wanted| Self::Extremum {
+            column_index: column_index.unwrap_or(0),
+            arg_column_index: spec.arg_column_index,
+            wanted,
+            comparator: spec.comparator.clone(),
+            key: None,
+            arg: Value::Null,
+        };
+        match spec.func {
             AggregateFunc::Count => match column_index {
                 Some(column_index) => Self::CountNonNull {
                     column_index,
@@ -195,21 +274,18 @@ impl AggregateState {
             AggregateFunc::Sum => Self::Sum {
                 column_index: column_index.unwrap_or(0),
                 sum: 0.0,
+                exact: Some(Decimal::ZERO),
                 output_mode: SumOutputMode::Integer,
             },
             AggregateFunc::Avg => Self::Avg {
                 column_index: column_index.unwrap_or(0),
                 sum: 0.0,
                 count: 0,
+                exact: Some(Decimal::ZERO),
+                has_decimal: false,
             },
-            AggregateFunc::Min => Self::Min {
-                column_index: column_index.unwrap_or(0),
-                value: None,
-            },
-            AggregateFunc::Max => Self::Max {
-                column_index: column_index.unwrap_or(0),
-                value: None,
-            },
+            AggregateFunc::Min | AggregateFunc::ArgMin => extremum(Ordering::Less),
+            AggregateFunc::Max | AggregateFunc::ArgMax => extremum(Ordering::Greater),
             AggregateFunc::Distinct => Self::Distinct {
                 column_index: column_index.unwrap_or(0),
                 seen: BTreeSet::new(),
@@ -248,6 +324,7 @@ impl AggregateState {
             Self::Sum {
                 column_index,
                 sum,
+                exact,
                 output_mode,
             } => {
                 let Some(value) = entry.get_field(*column_index) else {
@@ -256,9 +333,16 @@ impl AggregateState {
                 if value.is_null() {
                     return;
                 }
+                *exact = Self::add_exact(*exact, value);
                 match value {
                     Value::Int32(value) => *sum += *value as f64,
                     Value::Int64(value) => *sum += *value as f64,
+                    Value::Decimal(value) => {
+                        *sum += value.to_f64();
+                        if let SumOutputMode::Integer = output_mode {
+                            *output_mode = SumOutputMode::Decimal;
+                        }
+                    }
                     Value::Float64(value) => {
                         *sum += *value;
                         *output_mode = SumOutputMode::Float;
@@ -272,15 +356,26 @@ impl AggregateState {
                 column_index,
                 sum,
                 count,
+                exact,
+                has_decimal,
             } => {
-                if let Some(value) = entry.get_field(*column_index).and_then(Self::numeric_value) {
+                let Some(field) = entry.get_field(*column_index) else {
+                    return;
+                };
+                if let Some(value) = Self::numeric_value(field) {
                     *sum += value;
                     *count += 1;
+                    *exact = Self::add_exact(*exact, field);
+                    *has_decimal |= matches!(field, Value::Decimal(_));
                 }
             }
-            Self::Min {
+            Self::Extremum {
                 column_index,
-                value,
+                arg_column_index,
+                wanted,
+                comparator,
+                key,
+                arg,
             } => {
                 let Some(candidate) = entry.get_field(*column_index) else {
                     return;
@@ -288,24 +383,21 @@ impl AggregateState {
                 if candidate.is_null() {
                     return;
                 }
-                match value {
-                    Some(current) if candidate >= current => {}
-                    slot => *slot = Some(candidate.clone()),
-                }
-            }
-            Self::Max {
-                column_index,
-                value,
-            } => {
-                let Some(candidate) = entry.get_field(*column_index) else {
-                    return;
-                };
-                if candidate.is_null() {
-                    return;
+                if let Some(current) = key {
+                    let ordering = match comparator {
+                        Some(comparator) => comparator.compare(candidate, current),
+                        None => candidate.cmp(current),
+                    };
+                    if ordering != *wanted {
+                        return;
+                    }
                 }
-                match value {
-                    Some(current) if candidate <= current => {}
-                    slot => *slot = Some(candidate.clone()),
+                *key = Some(candidate.clone());
+                if let Some(arg_column_index) = arg_column_index {
+                    *arg = entry
+                        .get_field(*arg_column_index)
+                        .cloned()
+                        .unwrap_or(Value::Null);
                 }
             }
             Self::Distinct { column_index, seen } => {
@@ -359,14 +451,27 @@ impl AggregateState {
                 output_mode: SumOutputMode::Float,
                 ..
             } => Value::Float64(sum),
-            Self::Avg { sum, count, .. } => {
-                if count == 0 {
-                    Value::Null
-                } else {
-                    Value::Float64(sum / count as f64)
-                }
-            }
-            Self::Min { value, .. } | Self::Max { value, .. } => value.unwrap_or(Value::Null),
+            Self::Sum {
+                exact,
+                output_mode: SumOutputMode::Decimal,
+                ..
+            } => exact.map_or(Value::Null, Value::Decimal),
+            Self::Avg { count: 0, .. } => Value::Null,
+            Self::Avg {
+                count,
+                exact: Some(exact),
+                has_decimal: true,
+                ..
+            } => exact
+                .checked_div(Decimal::from(count as i64))
+                .map_or(Value::Null, Value::Decimal),
+            Self::Avg { sum, count, .. } => Value::Float64(sum / count as f64),
+            Self::Extremum {
+                arg_column_index: Some(_),
+                arg,
+                ..
+            } => arg,
+            Self::Extremum { key, .. } => key.unwrap_or(Value::Null),
             Self::Distinct { seen, .. } => Value::Int64(seen.len() as i64),
             Self::StdDev { count, m2, .. } => {
                 if count == 0 {
@@ -391,9 +496,22 @@ impl AggregateState {
             Value::Int32(value) => Some(*value as f64),
             Value::Int64(value) => Some(*value as f64),
             Value::Float64(value) => Some(*value),
+            Value::Decimal(value) => Some(value.to_f64()),
             _ => None,
         }
     }
+
+    /// Adds an integer or decimal input to an exact running sum. Any other
+    /// input, or overflow, makes the sum unavailable.
+    fn add_exact(exact: Option<Decimal>, value: &Value) -> Option<Decimal> {
+        let value = match value {
+            Value::Int32(value) => Decimal::from(*value),
+            Value::Int64(value) => Decimal::from(*value),
+            Value::Decimal(value) => *value,
+            _ => return None,
+        };
+        exact?.checked_add(value)
+    }
 }
 
 impl GroupKeyValue {
@@ -408,6 +526,10 @@ impl GroupKeyValue {
             Value::DateTime(value) => Self::DateTime(*value),
             Value::Bytes(value) => Self::Bytes(value.clone()),
             Value::Jsonb(value) => Self::Jsonb(value.0.clone()),
+            Value::Decimal(value) => {
+                let value = value.normalize();
+                Self::Decimal(value.mantissa(), value.scale())
+            }
         }
     }
 
@@ -484,6 +606,29 @@ mod tests {
         assert_eq!(result.entries[0].get_field(0), Some(&Value::Float64(20.0)));
     }
 
+    #[test]
+    fn test_sum_and_avg_of_decimals_are_exact() {
+        let dec = |s: &str| Value::Decimal(s.parse().unwrap());
+        let rows = (0..10)
+            .map(|i| Row::new(i, vec![dec("0.1")]))
+            .chain([
+                Row::new(10, vec![dec("0.20")]),
+                Row::new(11, vec![Value::Null]),
+            ])
+            .collect();
+        let input = Relation::from_rows_owned(rows, vec!["t".into()]);
+
+        let executor = AggregateExecutor::no_group(vec![
+            (AggregateFunc::Sum, Some(0)),
+            (AggregateFunc::Avg, Some(0)),
+        ]);
+        let result = executor.execute(input);
+
+        // In f64 ten 0.1s sum to 0.9999999999999999.
+        assert_eq!(result.entries[0].get_field(0), Some(&dec("1.2")));
+        assert_eq!(result.entries[0].get_field(1), Some(&dec("0.10909091")));
+    }
+
     #[test]
     fn test_min_max() {
         let rows = vec![
@@ -503,6 +648,125 @@ mod tests {
         assert_eq!(result.entries[0].get_field(1), Some(&Value::Int64(30)));
     }
 
+    struct CaseInsensitive;
+
+    impl Comparator<Value> for CaseInsensitive {
+        fn compare(&self, a: &Value, b: &Value) -> Ordering {
+            match (a, b) {
+                (Value::String(a), Value::String(b)) => a.to_lowercase().cmp(&b.to_lowercase()),
+                _ => a.cmp(b),
+            }
+        }
+    }
+
+    #[test]
+    fn test_min_max_with_comparator() {
+        let rows = vec![
+            Row::new(0, vec![Value::String("banana".into())]),
+            Row::new(1, vec![Value::String("Cherry".into())]),
+            Row::new(2, vec![Value::String("apple".into())]),
+            Row::new(3, vec![Value::String("Avocado".into())]),
+        ];
+        let input = Relation::from_rows_owned(rows, vec!["t".into()]);
+
+        let comparator: Rc<dyn Comparator<Value>> = Rc::new(CaseInsensitive);
+        let executor = AggregateExecutor::with_specs(
+            Vec::new(),
+            vec![
+                AggregateSpec::new(AggregateFunc::Min, Some(0)),
+                AggregateSpec::new(AggregateFunc::Min, Some(0)).with_comparator(comparator.clone()),
+                AggregateSpec::new(AggregateFunc::Max, Some(0)).with_comparator(comparator),
+            ],
+        );
+        let result = executor.execute(input);
+
+        // Byte order puts upper case first; the comparator ignores case.
+        assert_eq!(
+            result.entries[0].get_field(0),
+            Some(&Value::String("Avocado".into()))
+        );
+        assert_eq!(
+            result.entries[0].get_field(1),
+            Some(&Value::String("apple".into()))
+        );
+        assert_eq!(
+            result.entries[0].get_field(2),
+            Some(&Value::String("Cherry".into()))
+        );
+    }
+
+    #[test]
+    fn test_arg_min_arg_max() {
+        let rows = vec![
+            Row::new(
+                0,
+                vec![
+                    Value::String("A".into()),
+                    Value::String("ann".into()),
+                    Value::Int64(50),
+                ],
+            ),
+            Row::new(
+                1,
+                vec![
+                    Value::String("A".into()),
+                    Value::String("bob".into()),
+                    Value::Int64(90),
+                ],
+            ),
+            Row::new(
+                2,
+                vec![
+                    Value::String("A".into()),
+                    Value::String("cat".into()),
+                    Value::Null,
+                ],
+            ),
+            Row::new(
+                3,
+                vec![
+                    Value::String("B".into()),
+                    Value::String("dan".into()),
+                    Value::Int64(70),
+                ],
+            ),
+            Row::new(
+                4,
+                vec![
+                    Value::String("B".into()),
+                    Value::String("eve".into()),
+                    Value::Int64(70),
+                ],
+            ),
+        ];
+        let input = Relation::from_rows_owned(rows, vec!["t".into()]);
+
+        let executor = AggregateExecutor::with_specs(
+            vec![0],
+            vec![AggregateSpec::arg_max(2, 1), AggregateSpec::arg_min(2, 1)],
+        );
+        let result = executor.execute(input);
+
+        assert_eq!(result.len(), 2);
+        let row_a = &result.entries[0];
+        assert_eq!(row_a.get_field(1), Some(&Value::String("bob".into())));
+        assert_eq!(row_a.get_field(2), Some(&Value::String("ann".into())));
+        // Ties keep the first row seen.
+        let row_b = &result.entries[1];
+        assert_eq!(row_b.get_field(1), Some(&Value::String("dan".into())));
+        assert_eq!(row_b.get_field(2), Some(&Value::String("dan".into())));
+    }
+
+    #[test]
+    fn test_arg_max_of_empty_input_is_null() {
+        let input = Relation::from_rows_owned(Vec::new(), vec!["t".into()]);
+        let executor =
+            AggregateExecutor::with_specs(Vec::new(), vec![AggregateSpec::arg_max(0, 1)]);
+        let result = executor.execute(input);
+
+        assert_eq!(result.entries[0].get_field(0), Some(&Value::Null));
+    }
+
     #[test]
     fn test_group_by() {
         let rows = vec![
@@ -0,0 +1,193 @@
+//! `DATE_TRUNC` and `EXTRACT` on DateTime values.
+//!
+//! DateTime values are milliseconds since the Unix epoch and are read as UTC.
+//! Units and fields are case-insensitive strings; an unknown unit, a NULL or a
+//! non-DateTime argument evaluates to NULL, as does a truncation whose result
+//! falls outside the DateTime range.
+
+use cynos_core::Value;
+
+const MS_PER_SECOND: i64 = 1000;
+const MS_PER_MINUTE: i64 = 60 * MS_PER_SECOND;
+const MS_PER_HOUR: i64 = 60 * MS_PER_MINUTE;
+const MS_PER_DAY: i64 = 24 * MS_PER_HOUR;
+
+/// Evaluates `DATE_TRUNC(unit, ts)`, rounding `ts` down to the start of its
+/// `second`, `minute`, `hour`, `day`, `week` (Monday), `month` or `year`.
+pub fn eval_date_trunc(unit: &Value, ts: &Value) -> Value {
+    let (Value::String(unit), Value::DateTime(ms)) = (unit, ts) else {
+        return Value::Null;
+    };
+    let days = ms.div_euclid(MS_PER_DAY);
+    let truncated = match unit.to_ascii_lowercase().as_str() {
+        "second" => ms.checked_sub(ms.rem_euclid(MS_PER_SECOND)),
+        "minute" => ms.checked_sub(ms.rem_euclid(MS_PER_MINUTE)),
+        "hour" => ms.checked_sub(ms.rem_euclid(MS_PER_HOUR)),
+        "day" => days.checked_mul(MS_PER_DAY),
+        "week" => {
+            // Day 0 (1970-01-01) was a Thursday, three days after a Monday.
+            let monday = days - (days + 3).rem_euclid(7);
+            monday.checked_mul(MS_PER_DAY)
+        }
+        "month" => {
+            let (year, month, _) = civil_from_days(days);
+            days_from_civil(year, month, 1).checked_mul(MS_PER_DAY)
+        }
+        "year" => {
+            let (year, _, _) = civil_from_days(days);
+            days_from_civil(year, 1, 1).checked_mul(MS_PER_DAY)
+        }
+        _ => None,
+    };
+    truncated.map_or(Value::Null, Value::DateTime)
+}
+
+/// Evaluates `EXTRACT(field, ts)` as an Int64. Fields are `year`, `month`,
+/// `day`, `hour`, `minute`, `second`, `millisecond`, `dow` (0 = Sunday) and
+/// `doy` (1-based day of the year).
+pub fn eval_extract(field: &Value, ts: &Value) -> Value {
+    let (Value::String(field), Value::DateTime(ms)) = (field, ts) else {
+        return Value::Null;
+    };
+    let days = ms.div_euclid(MS_PER_DAY);
+    let time = ms.rem_euclid(MS_PER_DAY);
+    let (year, month, day) = civil_from_days(days);
+    let value = match field.to_ascii_lowercase().as_str() {
+        "year" => year,
+        "month" => month,
+        "day" => day,
+        "hour" => time / MS_PER_HOUR,
+        "minute" => time % MS_PER_HOUR / MS_PER_MINUTE,
+        "second" => time % MS_PER_MINUTE / MS_PER_SECOND,
+        "millisecond" => time % MS_PER_SECOND,
+        "dow" => (days + 4).rem_euclid(7),
+        "doy" => days - days_from_civil(year, 1, 1) + 1,
+        _ => return Value::Null,
+    };
+    Value::Int64(value)
+}
+
+/// Converts days since 1970-01-01 to a proleptic Gregorian
+/// `(year, month, day)`.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Converts a proleptic Gregorian date to days since 1970-01-01.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-03-15T13:45:30.250Z
+    const TS: i64 = 1_710_510_330_250;
+
+    fn unit(name: &str) -> Value {
+        Value::String(name.into())
+    }
+
+    #[test]
+    fn test_date_trunc() {
+        let ts = Value::DateTime(TS);
+        // 2024-03-15T00:00:00Z
+        assert_eq!(
+            eval_date_trunc(&unit("day"), &ts),
+            Value::DateTime(1_710_460_800_000)
+        );
+        // 2024-03-15T13:00:00Z
+        assert_eq!(
+            eval_date_trunc(&unit("HOUR"), &ts),
+            Value::DateTime(1_710_507_600_000)
+        );
+        // Monday 2024-03-11
+        assert_eq!(
+            eval_date_trunc(&unit("week"), &ts),
+            Value::DateTime(1_710_115_200_000)
+        );
+        // 2024-03-01 and 2024-01-01
+        assert_eq!(
+            eval_date_trunc(&unit("month"), &ts),
+            Value::DateTime(1_709_251_200_000)
+        );
+        assert_eq!(
+            eval_date_trunc(&unit("year"), &ts),
+            Value::DateTime(1_704_067_200_000)
+        );
+
+        // Instants before the epoch round down, not toward zero.
+        // 1969-12-31T23:59:59.999Z -> 1969-12-31
+        assert_eq!(
+            eval_date_trunc(&unit("day"), &Value::DateTime(-1)),
+            Value::DateTime(-MS_PER_DAY)
+        );
+
+        // Rounding the earliest instants down leaves the DateTime range.
+        for name in ["second", "day", "week", "month", "year"] {
+            assert_eq!(
+                eval_date_trunc(&unit(name), &Value::DateTime(i64::MIN)),
+                Value::Null
+            );
+        }
+        assert_eq!(
+            eval_date_trunc(&unit("year"), &Value::DateTime(i64::MAX)),
+            Value::DateTime(days_from_civil(292_278_994, 1, 1) * MS_PER_DAY)
+        );
+
+        assert_eq!(eval_date_trunc(&unit("fortnight"), &ts), Value::Null);
+        assert_eq!(eval_date_trunc(&unit("day"), &Value::Null), Value::Null);
+        assert_eq!(eval_date_trunc(&Value::Null, &ts), Value::Null);
+    }
+
+    #[test]
+    fn test_extract() {
+        let ts = Value::DateTime(TS);
+        let extract = |field: &str| eval_extract(&unit(field), &ts);
+
+        assert_eq!(extract("year"), Value::Int64(2024));
+        assert_eq!(extract("Month"), Value::Int64(3));
+        assert_eq!(extract("day"), Value::Int64(15));
+        assert_eq!(extract("hour"), Value::Int64(13));
+        assert_eq!(extract("minute"), Value::Int64(45));
+        assert_eq!(extract("second"), Value::Int64(30));
+        assert_eq!(extract("millisecond"), Value::Int64(250));
+        // A Friday in a leap year.
+        assert_eq!(extract("dow"), Value::Int64(5));
+        assert_eq!(extract("doy"), Value::Int64(75));
+
+        assert_eq!(
+            eval_extract(&unit("year"), &Value::DateTime(-1)),
+            Value::Int64(1969)
+        );
+        assert_eq!(extract("decade"), Value::Null);
+        assert_eq!(eval_extract(&unit("year"), &Value::Null), Value::Null);
+        assert_eq!(eval_extract(&unit("year"), &Value::Int64(TS)), Value::Null);
+    }
+
+    #[test]
+    fn test_civil_round_trip() {
+        for days in [-719_468, -1, 0, 59, 11_016, 19_797, 2_932_896] {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+    }
+}
@@ -19,6 +19,8 @@ pub struct HashJoin {
     right_key_index: usize,
     /// Whether this is an outer join.
     is_outer_join: bool,
+    /// Whether NULL keys match each other.
+    null_safe: bool,
 }
 
 impl HashJoin {
@@ -28,9 +30,17 @@ impl HashJoin {
             left_key_index,
             right_key_index,
             is_outer_join,
+            null_safe: false,
         }
     }
 
+    /// Makes NULL keys match each other (`IS NOT DISTINCT FROM`) instead of
+    /// never matching.
+    pub fn with_null_safe_keys(mut self, null_safe: bool) -> Self {
+        self.null_safe = null_safe;
+        self
+    }
+
     /// Creates an inner hash join.
     pub fn inner(left_key_index: usize, right_key_index: usize) -> Self {
         Self::new(left_key_index, right_key_index, false)
@@ -77,7 +87,7 @@ impl HashJoin {
 
         for (idx, entry) in build_rel.entries.iter().enumerate() {
             if let Some(key_value) = entry.get_field(build_key_idx) {
-                if !key_value.is_null() {
+                if self.null_safe || !key_value.is_null() {
                     hash_table
                         .entry(SqlValueRef::new(key_value))
                         .or_default()
@@ -120,7 +130,7 @@ impl HashJoin {
             let mut matched = false;
 
             if let Some(kv) = key_value {
-                if !kv.is_null() {
+                if self.null_safe || !kv.is_null() {
                     if let Some(build_indices) = hash_table.get(&SqlValueRef::new(kv)) {
                         matched = true;
                         for &build_idx in build_indices {
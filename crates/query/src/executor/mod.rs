@@ -1,9 +1,11 @@
 //! Query executor module.
 
 mod aggregate;
+mod datetime;
 mod filter;
 pub mod join;
 mod limit;
+mod numeric;
 mod operator;
 mod project;
 mod relation;
@@ -11,18 +13,22 @@ mod runner;
 mod scan;
 mod sort;
 mod sql_value_ref;
+mod topn;
 
-pub use aggregate::AggregateExecutor;
+pub use aggregate::{AggregateExecutor, AggregateSpec};
+pub use datetime::{eval_date_trunc, eval_extract};
 pub use filter::FilterExecutor;
 pub use join::{HashJoin, NestedLoopJoin, SortMergeJoin};
 pub use limit::LimitExecutor;
+pub use numeric::{eval_bitwise, eval_decimal_arithmetic, eval_floor_div, eval_pow, eval_power};
 pub use operator::Operator;
 pub use project::ProjectExecutor;
 pub use relation::{Relation, RelationEntry, SharedTables};
 pub use runner::{
-    DataSource, ExecutionError, ExecutionResult, InMemoryDataSource, PhysicalPlanRunner,
-    PlanExecutionArtifact,
+    CoercionPolicy, DataSource, ExecutionError, ExecutionResult, InMemoryDataSource,
+    PhysicalPlanRunner, PlanExecutionArtifact,
 };
 pub use scan::{IndexScanExecutor, TableScanExecutor};
 pub use sort::SortExecutor;
 pub(crate) use sql_value_ref::SqlValueRef;
+pub use topn::TopNExecutor;
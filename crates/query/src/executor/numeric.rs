@@ -0,0 +1,423 @@
+//! Integer bit operations, exponentiation, floor division and decimal
+//! arithmetic shared by the evaluators.
+//!
+//! Like division by zero, results that have no value in the result type
+//! (overflow, out-of-range shifts, NaN) evaluate to NULL.
+
+use crate::ast::BinaryOp;
+use cynos_core::{Decimal, Value};
+
+/// Evaluates a bitwise operator on two integers.
+///
+/// Int32 operands yield Int32; any Int64 operand widens the result to Int64.
+/// A shift keeps the type of its left operand and returns NULL unless the
+/// shift amount is in `0..width`. Non-integer operands yield NULL.
+pub fn eval_bitwise(op: BinaryOp, left: &Value, right: &Value) -> Value {
+    match op {
+        BinaryOp::Shl | BinaryOp::Shr => {
+            let Some(amount) = integer(right) else {
+                return Value::Null;
+            };
+            match left {
+                Value::Int32(value) => match shift_amount(amount, i32::BITS) {
+                    Some(amount) if op == BinaryOp::Shl => Value::Int32(value << amount),
+                    Some(amount) => Value::Int32(value >> amount),
+                    None => Value::Null,
+                },
+                Value::Int64(value) => match shift_amount(amount, i64::BITS) {
+                    Some(amount) if op == BinaryOp::Shl => Value::Int64(value << amount),
+                    Some(amount) => Value::Int64(value >> amount),
+                    None => Value::Null,
+                },
+                _ => Value::Null,
+            }
+        }
+        BinaryOp::BitAnd | BinaryOp::BitOr | BinaryOp::BitXor => {
+            let apply = |a: i64, b: i64| match op {
+                BinaryOp::BitAnd => a & b,
+                BinaryOp::BitOr => a | b,
+                _ => a ^ b,
+            };
+            match (left, right) {
+                (Value::Int32(a), Value::Int32(b)) => {
+                    Value::Int32(apply(*a as i64, *b as i64) as i32)
+                }
+                _ => match (integer(left), integer(right)) {
+                    (Some(a), Some(b)) => Value::Int64(apply(a, b)),
+                    _ => Value::Null,
+                },
+            }
+        }
+        _ => Value::Null,
+    }
+}
+
+/// Evaluates `POWER(base, exponent)`.
+///
+/// Two Int32 arguments yield Int32 and other integer pairs Int64, with NULL
+/// on overflow; a negative exponent truncates toward zero like integer
+/// division. Any Float64 argument yields Float64, and NULL when the result is
+/// not finite (e.g. a negative base with a fractional exponent).
+pub fn eval_power(base: &Value, exponent: &Value) -> Value {
+    match (base, exponent) {
+        (Value::Int32(b), Value::Int32(e)) => integer_power(*b as i64, *e as i64)
+            .map_or(Value::Null, |result| {
+                i32::try_from(result).map_or(Value::Null, Value::Int32)
+            }),
+        _ => match (integer(base), integer(exponent)) {
+            (Some(b), Some(e)) => integer_power(b, e).map_or(Value::Null, Value::Int64),
+            _ => match (float(base), float(exponent)) {
+                (Some(b), Some(e)) => {
+                    let result = libm::pow(b, e);
+                    if result.is_finite() {
+                        Value::Float64(result)
+                    } else {
+                        Value::Null
+                    }
+                }
+                _ => Value::Null,
+            },
+        },
+    }
+}
+
+/// Evaluates the `^` operator: both operands as Float64, and NULL when the
+/// result is not finite.
+pub fn eval_pow(base: &Value, exponent: &Value) -> Value {
+    match (float(base), float(exponent)) {
+        (Some(b), Some(e)) => {
+            let result = libm::pow(b, e);
+            if result.is_finite() {
+                Value::Float64(result)
+            } else {
+                Value::Null
+            }
+        }
+        _ => Value::Null,
+    }
+}
+
+/// Evaluates `left // right`, rounding the quotient toward negative infinity.
+///
+/// Two Int32 operands yield Int32 and other integer pairs Int64, with NULL on
+/// overflow (`MIN // -1`). Any Float64 operand yields the floored Float64
+/// quotient. Division by zero yields NULL.
+pub fn eval_floor_div(left: &Value, right: &Value) -> Value {
+    match (left, right) {
+        (Value::Int32(a), Value::Int32(b)) => floor_div_i64(*a as i64, *b as i64)
+            .map_or(Value::Null, |result| {
+                i32::try_from(result).map_or(Value::Null, Value::Int32)
+            }),
+        _ => match (integer(left), integer(right)) {
+            (Some(a), Some(b)) => floor_div_i64(a, b).map_or(Value::Null, Value::Int64),
+            _ => match (float(left), float(right)) {
+                (Some(_), Some(0.0)) => Value::Null,
+                (Some(a), Some(b)) => {
+                    let result = libm::floor(a / b);
+                    if result.is_finite() {
+                        Value::Float64(result)
+                    } else {
+                        Value::Null
+                    }
+                }
+                _ => Value::Null,
+            },
+        },
+    }
+}
+
+/// Evaluates `+ - * / %` when either operand is a Decimal, or returns `None`
+/// otherwise.
+///
+/// A Decimal with a Decimal or integer operand is computed exactly and yields
+/// Decimal, with NULL on overflow or division by zero. A Float64 operand
+/// makes the result Float64.
+pub fn eval_decimal_arithmetic(op: BinaryOp, left: &Value, right: &Value) -> Option<Value> {
+    if !matches!(left, Value::Decimal(_)) && !matches!(right, Value::Decimal(_)) {
+        return None;
+    }
+    if !matches!(
+        op,
+        BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod
+    ) {
+        return None;
+    }
+    let result = match (decimal(left), decimal(right)) {
+        (Some(a), Some(b)) => match op {
+            BinaryOp::Add => a.checked_add(b),
+            BinaryOp::Sub => a.checked_sub(b),
+            BinaryOp::Mul => a.checked_mul(b),
+            BinaryOp::Div => a.checked_div(b),
+            _ => a.checked_rem(b),
+        }
+        .map_or(Value::Null, Value::Decimal),
+        _ => match (float(left), float(right)) {
+            (Some(_), Some(0.0)) if matches!(op, BinaryOp::Div | BinaryOp::Mod) => Value::Null,
+            (Some(a), Some(b)) => Value::Float64(match op {
+                BinaryOp::Add => a + b,
+                BinaryOp::Sub => a - b,
+                BinaryOp::Mul => a * b,
+                BinaryOp::Div => a / b,
+                _ => a % b,
+            }),
+            _ => Value::Null,
+        },
+    };
+    Some(result)
+}
+
+fn floor_div_i64(a: i64, b: i64) -> Option<i64> {
+    let quotient = a.checked_div(b)?;
+    if a % b != 0 && (a < 0) != (b < 0) {
+        Some(quotient - 1)
+    } else {
+        Some(quotient)
+    }
+}
+
+fn integer_power(base: i64, exponent: i64) -> Option<i64> {
+    if exponent >= 0 {
+        return base.checked_pow(u32::try_from(exponent).ok()?);
+    }
+    match base {
+        0 => None,
+        1 => Some(1),
+        -1 if exponent % 2 == 0 => Some(1),
+        -1 => Some(-1),
+        _ => Some(0),
+    }
+}
+
+fn shift_amount(amount: i64, width: u32) -> Option<u32> {
+    u32::try_from(amount).ok().filter(|&amount| amount < width)
+}
+
+fn integer(value: &Value) -> Option<i64> {
+    match value {
+        Value::Int32(v) => Some(*v as i64),
+        Value::Int64(v) => Some(*v),
+        _ => None,
+    }
+}
+
+fn float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Int32(v) => Some(*v as f64),
+        Value::Int64(v) => Some(*v as f64),
+        Value::Float64(v) => Some(*v),
+        Value::Decimal(v) => Some(v.to_f64()),
+        _ => None,
+    }
+}
+
+/// Returns an exact operand as a Decimal; Float64 is not exact.
+fn decimal(value: &Value) -> Option<Decimal> {
+    match value {
+        Value::Int32(v) => Some(Decimal::from(*v)),
+        Value::Int64(v) => Some(Decimal::from(*v)),
+        Value::Decimal(v) => Some(*v),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_arithmetic_is_exact() {
+        let dec = |s: &str| Value::Decimal(s.parse().unwrap());
+        let eval = |op, l: &Value, r: &Value| eval_decimal_arithmetic(op, l, r).unwrap();
+
+        assert_eq!(eval(BinaryOp::Add, &dec("0.1"), &dec("0.2")), dec("0.3"));
+        assert_eq!(
+            eval(BinaryOp::Sub, &dec("1.10"), &Value::Int32(2)),
+            dec("-0.9")
+        );
+        assert_eq!(
+            eval(BinaryOp::Mul, &Value::Int64(3), &dec("0.25")),
+            dec("0.75")
+        );
+        assert_eq!(eval(BinaryOp::Div, &dec("1"), &dec("8")), dec("0.125"));
+        assert_eq!(
+            eval(BinaryOp::Mod, &dec("5.5"), &Value::Int32(2)),
+            dec("1.5")
+        );
+        assert_eq!(
+            eval(BinaryOp::Div, &dec("1"), &Value::Int32(0)),
+            Value::Null
+        );
+        assert_eq!(
+            eval(BinaryOp::Add, &dec("0.5"), &Value::Float64(0.25)),
+            Value::Float64(0.75)
+        );
+        assert_eq!(
+            eval_decimal_arithmetic(BinaryOp::Add, &Value::Int32(1), &Value::Int32(2)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_power() {
+        assert_eq!(
+            eval_power(&Value::Int32(2), &Value::Int32(10)),
+            Value::Int32(1024)
+        );
+        assert_eq!(
+            eval_power(&Value::Int64(3), &Value::Int32(3)),
+            Value::Int64(27)
+        );
+        assert_eq!(
+            eval_power(&Value::Int32(2), &Value::Int32(-1)),
+            Value::Int32(0)
+        );
+        assert_eq!(
+            eval_power(&Value::Int32(-1), &Value::Int32(-3)),
+            Value::Int32(-1)
+        );
+        assert_eq!(eval_power(&Value::Int32(0), &Value::Int32(-1)), Value::Null);
+        // Overflow of the result type.
+        assert_eq!(eval_power(&Value::Int32(2), &Value::Int32(31)), Value::Null);
+        assert_eq!(eval_power(&Value::Int64(2), &Value::Int64(63)), Value::Null);
+        assert_eq!(
+            eval_power(&Value::Int64(2), &Value::Int64(62)),
+            Value::Int64(1 << 62)
+        );
+
+        // Fractional exponents return Float64.
+        assert_eq!(
+            eval_power(&Value::Int64(9), &Value::Float64(0.5)),
+            Value::Float64(3.0)
+        );
+        assert_eq!(
+            eval_power(&Value::Float64(2.0), &Value::Int32(-2)),
+            Value::Float64(0.25)
+        );
+        assert_eq!(
+            eval_power(&Value::Int32(-8), &Value::Float64(0.5)),
+            Value::Null
+        );
+        assert_eq!(
+            eval_power(&Value::String("2".into()), &Value::Int32(2)),
+            Value::Null
+        );
+    }
+
+    #[test]
+    fn test_pow_operator() {
+        assert_eq!(
+            eval_pow(&Value::Int32(2), &Value::Int32(10)),
+            Value::Float64(1024.0)
+        );
+        assert_eq!(
+            eval_pow(&Value::Int64(2), &Value::Int64(-1)),
+            Value::Float64(0.5)
+        );
+        assert_eq!(
+            eval_pow(&Value::Int32(-8), &Value::Float64(0.5)),
+            Value::Null
+        );
+        assert_eq!(
+            eval_pow(&Value::Float64(10.0), &Value::Int32(400)),
+            Value::Null
+        );
+    }
+
+    #[test]
+    fn test_floor_div() {
+        assert_eq!(
+            eval_floor_div(&Value::Int32(7), &Value::Int32(2)),
+            Value::Int32(3)
+        );
+        assert_eq!(
+            eval_floor_div(&Value::Int32(-7), &Value::Int32(2)),
+            Value::Int32(-4)
+        );
+        assert_eq!(
+            eval_floor_div(&Value::Int64(7), &Value::Int32(-2)),
+            Value::Int64(-4)
+        );
+        assert_eq!(
+            eval_floor_div(&Value::Int64(-8), &Value::Int64(2)),
+            Value::Int64(-4)
+        );
+        assert_eq!(
+            eval_floor_div(&Value::Float64(-7.5), &Value::Int32(2)),
+            Value::Float64(-4.0)
+        );
+
+        // Division by zero and overflow.
+        assert_eq!(
+            eval_floor_div(&Value::Int32(7), &Value::Int32(0)),
+            Value::Null
+        );
+        assert_eq!(
+            eval_floor_div(&Value::Float64(7.0), &Value::Float64(0.0)),
+            Value::Null
+        );
+        assert_eq!(
+            eval_floor_div(&Value::Int32(i32::MIN), &Value::Int32(-1)),
+            Value::Null
+        );
+        assert_eq!(
+            eval_floor_div(&Value::Int64(i64::MIN), &Value::Int64(-1)),
+            Value::Null
+        );
+    }
+
+    #[test]
+    fn test_bitwise_ops() {
+        let eval =
+            |op, left: i32, right: i32| eval_bitwise(op, &Value::Int32(left), &Value::Int32(right));
+
+        assert_eq!(eval(BinaryOp::BitAnd, 0b1100, 0b1010), Value::Int32(0b1000));
+        assert_eq!(eval(BinaryOp::BitOr, 0b1100, 0b1010), Value::Int32(0b1110));
+        assert_eq!(eval(BinaryOp::BitXor, 0b1100, 0b1010), Value::Int32(0b0110));
+        assert_eq!(eval(BinaryOp::Shl, 1, 4), Value::Int32(16));
+        assert_eq!(eval(BinaryOp::Shr, -16, 2), Value::Int32(-4));
+
+        // Mixed widths widen to Int64.
+        assert_eq!(
+            eval_bitwise(BinaryOp::BitOr, &Value::Int32(1), &Value::Int64(1 << 40)),
+            Value::Int64((1 << 40) | 1)
+        );
+        assert_eq!(
+            eval_bitwise(BinaryOp::BitAnd, &Value::Float64(1.0), &Value::Int32(1)),
+            Value::Null
+        );
+    }
+
+    #[test]
+    fn test_shift_bounds() {
+        // Bits shifted past the width are discarded.
+        assert_eq!(
+            eval_bitwise(BinaryOp::Shl, &Value::Int32(1), &Value::Int32(31)),
+            Value::Int32(i32::MIN)
+        );
+        assert_eq!(
+            eval_bitwise(BinaryOp::Shl, &Value::Int32(3), &Value::Int32(31)),
+            Value::Int32(i32::MIN)
+        );
+        // The shifted value's width bounds the amount, not the amount's type.
+        assert_eq!(
+            eval_bitwise(BinaryOp::Shl, &Value::Int32(1), &Value::Int64(32)),
+            Value::Null
+        );
+        assert_eq!(
+            eval_bitwise(BinaryOp::Shl, &Value::Int64(1), &Value::Int32(32)),
+            Value::Int64(1 << 32)
+        );
+        assert_eq!(
+            eval_bitwise(BinaryOp::Shr, &Value::Int64(-1), &Value::Int64(63)),
+            Value::Int64(-1)
+        );
+        assert_eq!(
+            eval_bitwise(BinaryOp::Shr, &Value::Int64(1), &Value::Int64(64)),
+            Value::Null
+        );
+        assert_eq!(
+            eval_bitwise(BinaryOp::Shl, &Value::Int64(1), &Value::Int32(-1)),
+            Value::Null
+        );
+    }
+}
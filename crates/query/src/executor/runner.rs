@@ -5,9 +5,11 @@
 //! execution operators.
 
 use crate::ast::{AggregateFunc, BinaryOp, ColumnRef, Expr, SortOrder, UnaryOp};
+use crate::executor::sort::{compare_key, JsonKeys};
 use crate::executor::{
-    AggregateExecutor, LimitExecutor, Relation, RelationEntry, SharedTables, SortExecutor,
-    SqlValueRef,
+    eval_bitwise, eval_date_trunc, eval_decimal_arithmetic, eval_extract, eval_floor_div, eval_pow,
+    eval_power, AggregateExecutor, AggregateSpec, LimitExecutor, Relation, RelationEntry,
+    SharedTables, SortExecutor, SqlValueRef, TopNExecutor,
 };
 use crate::planner::{IndexBounds, PhysicalPlan};
 use alloc::boxed::Box;
@@ -17,65 +19,12 @@ use alloc::string::String;
 use alloc::vec;
 use alloc::vec::Vec;
 use core::cmp::Ordering;
-use cynos_core::{Row, Value, DUMMY_ROW_ID};
+use cynos_core::{DataType, Row, RowId, Value, DUMMY_ROW_ID};
 use cynos_index::KeyRange;
 use cynos_jsonb::{JsonPath, JsonbObject, JsonbValue};
 
-// ========== TopN Heap Entry ==========
-
 const NULL_VALUE: Value = Value::Null;
 
-/// Entry wrapper for binary heap-based TopN execution.
-/// Stores a reference to the order_by indices to avoid cloning per entry.
-struct TopNHeapEntry<'a> {
-    entry: RelationEntry,
-    order_by: &'a [(usize, SortOrder)],
-}
-
-impl<'a> PartialEq for TopNHeapEntry<'a> {
-    fn eq(&self, other: &Self) -> bool {
-        self.cmp(other) == Ordering::Equal
-    }
-}
-
-impl<'a> Eq for TopNHeapEntry<'a> {}
-
-impl<'a> PartialOrd for TopNHeapEntry<'a> {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl<'a> Ord for TopNHeapEntry<'a> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        // For TopN with ASC order, we want to keep the k smallest elements.
-        // BinaryHeap is a max-heap, so the largest element is at the top.
-        // We need the heap to keep the k smallest, with the largest of those at top.
-        // So for ASC: normal comparison (larger values have higher priority, stay at top)
-        // For DESC: reversed comparison (smaller values have higher priority, stay at top)
-        for (idx, order) in self.order_by {
-            let a = self.entry.get_field(*idx);
-            let b = other.entry.get_field(*idx);
-            let cmp = match (a, b) {
-                (Some(va), Some(vb)) => va.partial_cmp(vb).unwrap_or(Ordering::Equal),
-                (Some(_), None) => Ordering::Greater,
-                (None, Some(_)) => Ordering::Less,
-                (None, None) => Ordering::Equal,
-            };
-            if cmp != Ordering::Equal {
-                // For ASC: keep smallest k, so larger values should be at heap top (normal order)
-                // For DESC: keep largest k, so smaller values should be at heap top (reversed)
-                let final_cmp = match order {
-                    SortOrder::Asc => cmp,
-                    SortOrder::Desc => cmp.reverse(),
-                };
-                return final_cmp;
-            }
-        }
-        Ordering::Equal
-    }
-}
-
 /// Context for expression evaluation in JOIN queries.
 /// Contains table metadata needed to compute correct column indices at runtime.
 #[derive(Clone, Debug)]
@@ -977,6 +926,10 @@ enum CompiledSourcePlan {
         index: String,
         keys: Vec<Value>,
     },
+    FetchByIds {
+        table: String,
+        ids: Vec<RowId>,
+    },
     GinIndexScan {
         table: String,
         index: String,
@@ -1001,6 +954,8 @@ enum CompiledProjection {
 struct CompiledEquiJoinKeys {
     left_key_idx: usize,
     right_key_idx: usize,
+    /// NULL keys match each other (`IS NOT DISTINCT FROM`).
+    null_safe: bool,
 }
 
 #[derive(Clone, Copy)]
@@ -1469,6 +1424,35 @@ pub trait DataSource {
         Ok(())
     }
 
+    /// Returns the number of rows an index scan over `range` would return.
+    /// `None` scans the whole index.
+    fn count_index_range(
+        &self,
+        table: &str,
+        index: &str,
+        range: Option<&KeyRange<Value>>,
+    ) -> ExecutionResult<usize> {
+        let (start, end, include_start, include_end) = match range {
+            None | Some(KeyRange::All) => (None, None, true, true),
+            Some(KeyRange::Only(value)) => (Some(value), Some(value), true, true),
+            Some(KeyRange::LowerBound { value, exclusive }) => {
+                (Some(value), None, !exclusive, true)
+            }
+            Some(KeyRange::UpperBound { value, exclusive }) => {
+                (None, Some(value), true, !exclusive)
+            }
+            Some(KeyRange::Bound {
+                lower,
+                upper,
+                lower_exclusive,
+                upper_exclusive,
+            }) => (Some(lower), Some(upper), !lower_exclusive, !upper_exclusive),
+        };
+        Ok(self
+            .get_index_range(table, index, start, end, include_start, include_end)?
+            .len())
+    }
+
     /// Returns rows from an index point lookup.
     fn get_index_point(
         &self,
@@ -1523,6 +1507,20 @@ pub trait DataSource {
         Ok(self.get_table_rows(table)?.len())
     }
 
+    /// Returns the rows with the given ids, in the order of `ids`.
+    /// Ids with no stored row are skipped.
+    fn fetch_rows_by_ids(&self, table: &str, ids: &[RowId]) -> ExecutionResult<Vec<Rc<Row>>> {
+        let wanted: alloc::collections::BTreeSet<RowId> = ids.iter().copied().collect();
+        let mut found = BTreeMap::new();
+        self.visit_table_rows(table, |row| {
+            if wanted.contains(&row.id()) {
+                found.insert(row.id(), Rc::clone(row));
+            }
+            found.len() < wanted.len()
+        })?;
+        Ok(ids.iter().filter_map(|id| found.get(id).cloned()).collect())
+    }
+
     /// Returns rows from a GIN index lookup by key-value pair.
     /// Used for JSONB path equality queries like `$.category = 'Electronics'`.
     fn get_gin_index_rows(
@@ -1625,18 +1623,44 @@ pub trait DataSource {
     }
 }
 
+/// How comparisons between a String and a number are evaluated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CoercionPolicy {
+    /// Values of different types never compare equal, so `'5' = 5` is false.
+    #[default]
+    Strict,
+    /// A String compared against a number is parsed as a number first, so
+    /// `'5' = 5` is true. Comparing a String that does not parse is NULL.
+    Lenient,
+}
+
 /// Physical plan runner - executes physical query plans.
 ///
 /// The runner recursively evaluates plan nodes, executing each operator
 /// and combining results according to the plan structure.
 pub struct PhysicalPlanRunner<'a, D: DataSource> {
     data_source: &'a D,
+    coercion: CoercionPolicy,
 }
 
 impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
     /// Creates a new physical plan runner with the given data source.
     pub fn new(data_source: &'a D) -> Self {
-        Self { data_source }
+        Self {
+            data_source,
+            coercion: CoercionPolicy::Strict,
+        }
+    }
+
+    /// Sets how String/number comparisons are evaluated.
+    pub fn with_coercion_policy(mut self, policy: CoercionPolicy) -> Self {
+        self.coercion = policy;
+        self
+    }
+
+    /// Returns the String/number coercion policy.
+    pub fn coercion_policy(&self) -> CoercionPolicy {
+        self.coercion
     }
 
     /// Compiles an execution artifact for repeated execution of a physical plan.
@@ -1863,6 +1887,14 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                     keys: keys.clone(),
                 }),
             }),
+            PhysicalPlan::FetchByIds { table, ids } => Ok(CompiledExecPlan {
+                meta: self.compile_single_table_meta(table)?,
+                estimated_rows: Some(ids.len()),
+                kind: CompiledExecPlanKind::Source(CompiledSourcePlan::FetchByIds {
+                    table: table.clone(),
+                    ids: unique_ids(ids),
+                }),
+            }),
             PhysicalPlan::GinIndexScan {
                 table,
                 index,
@@ -1985,7 +2017,9 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                 condition,
                 join_type,
                 output_tables,
+                ..
             } => {
+                Self::ensure_not_semi_or_anti_join(*join_type)?;
                 let left = self.compile_exec_plan(left)?;
                 let right = self.compile_exec_plan(right)?;
                 let keys = Self::extract_join_keys_from_meta(condition, &left.meta, &right.meta)?;
@@ -2017,6 +2051,8 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                 join_type,
                 output_tables,
             } => {
+                Self::ensure_not_semi_or_anti_join(*join_type)?;
+                Self::ensure_not_null_safe_join(condition)?;
                 let left = self.compile_exec_plan(left)?;
                 let right = self.compile_exec_plan(right)?;
                 let keys = Self::extract_join_keys_from_meta(condition, &left.meta, &right.meta)?;
@@ -2041,7 +2077,9 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                 condition,
                 join_type,
                 output_tables,
+                ..
             } => {
+                Self::ensure_not_semi_or_anti_join(*join_type)?;
                 let left = self.compile_exec_plan(left)?;
                 let right = self.compile_exec_plan(right)?;
                 let join_meta = Self::compiled_join_meta(&left.meta, &right.meta, output_tables);
@@ -2206,13 +2244,37 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
         }
     }
 
+    /// Semi- and anti-joins are only executed as index nested loop joins.
+    fn ensure_not_semi_or_anti_join(join_type: crate::ast::JoinType) -> ExecutionResult<()> {
+        if join_type.outputs_left_only() {
+            return Err(ExecutionError::InvalidOperation(alloc::format!(
+                "{:?} join requires an index on the inner join column",
+                join_type
+            )));
+        }
+        Ok(())
+    }
+
+    /// Sort-merge join skips NULL keys while merging, so it cannot match
+    /// them under `IS NOT DISTINCT FROM`.
+    fn ensure_not_null_safe_join(condition: &Expr) -> ExecutionResult<()> {
+        if condition.is_null_safe_equi_join() {
+            return Err(ExecutionError::InvalidOperation(
+                "sort-merge join does not support NULL-safe join keys".into(),
+            ));
+        }
+        Ok(())
+    }
+
     fn estimate_join_output_rows(
         left_rows: Option<usize>,
         right_rows: Option<usize>,
         join_type: crate::ast::JoinType,
     ) -> Option<usize> {
         match join_type {
-            crate::ast::JoinType::LeftOuter => left_rows,
+            crate::ast::JoinType::LeftOuter
+            | crate::ast::JoinType::Semi
+            | crate::ast::JoinType::Anti => left_rows,
             crate::ast::JoinType::RightOuter => right_rows,
             crate::ast::JoinType::FullOuter => match (left_rows, right_rows) {
                 (Some(left_rows), Some(right_rows)) => Some(left_rows.saturating_add(right_rows)),
@@ -2236,7 +2298,10 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
         join_type: crate::ast::JoinType,
     ) -> Option<usize> {
         match join_type {
-            crate::ast::JoinType::LeftOuter | crate::ast::JoinType::Inner => outer_rows,
+            crate::ast::JoinType::LeftOuter
+            | crate::ast::JoinType::Inner
+            | crate::ast::JoinType::Semi
+            | crate::ast::JoinType::Anti => outer_rows,
             crate::ast::JoinType::RightOuter
             | crate::ast::JoinType::FullOuter
             | crate::ast::JoinType::Cross => None,
@@ -2259,7 +2324,9 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                     HashJoinBuildSide::Right
                 }
             }
-            crate::ast::JoinType::Cross => HashJoinBuildSide::Right,
+            crate::ast::JoinType::Cross
+            | crate::ast::JoinType::Semi
+            | crate::ast::JoinType::Anti => HashJoinBuildSide::Right,
         }
     }
 
@@ -2286,7 +2353,9 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                     (None, None) => HashJoinBuildSide::Right,
                 }
             }
-            crate::ast::JoinType::Cross => HashJoinBuildSide::Right,
+            crate::ast::JoinType::Cross
+            | crate::ast::JoinType::Semi
+            | crate::ast::JoinType::Anti => HashJoinBuildSide::Right,
         }
     }
 
@@ -2299,7 +2368,10 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
             crate::ast::JoinType::LeftOuter => matches!(build_side, HashJoinBuildSide::Right),
             crate::ast::JoinType::RightOuter => matches!(build_side, HashJoinBuildSide::Left),
             crate::ast::JoinType::FullOuter => true,
-            crate::ast::JoinType::Inner | crate::ast::JoinType::Cross => false,
+            crate::ast::JoinType::Inner
+            | crate::ast::JoinType::Cross
+            | crate::ast::JoinType::Semi
+            | crate::ast::JoinType::Anti => false,
         }
     }
 
@@ -2312,7 +2384,10 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
             crate::ast::JoinType::LeftOuter => matches!(build_side, HashJoinBuildSide::Left),
             crate::ast::JoinType::RightOuter => matches!(build_side, HashJoinBuildSide::Right),
             crate::ast::JoinType::FullOuter => true,
-            crate::ast::JoinType::Inner | crate::ast::JoinType::Cross => false,
+            crate::ast::JoinType::Inner
+            | crate::ast::JoinType::Cross
+            | crate::ast::JoinType::Semi
+            | crate::ast::JoinType::Anti => false,
         }
     }
 
@@ -2323,10 +2398,11 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
     ) -> ExecutionResult<CompiledEquiJoinKeys> {
         if let Expr::BinaryOp {
             left: left_expr,
-            op: BinaryOp::Eq,
+            op: op @ (BinaryOp::Eq | BinaryOp::IsNotDistinctFrom),
             right: right_expr,
         } = condition
         {
+            let null_safe = *op == BinaryOp::IsNotDistinctFrom;
             let left_col = Self::extract_column_ref_static(left_expr)?;
             let right_col = Self::extract_column_ref_static(right_expr)?;
 
@@ -2334,6 +2410,7 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                 return Ok(CompiledEquiJoinKeys {
                     left_key_idx: left.resolve_column_index(&left_col.table, left_col.index),
                     right_key_idx: right.resolve_column_index(&right_col.table, right_col.index),
+                    null_safe,
                 });
             }
 
@@ -2341,6 +2418,7 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                 return Ok(CompiledEquiJoinKeys {
                     left_key_idx: left.resolve_column_index(&right_col.table, right_col.index),
                     right_key_idx: right.resolve_column_index(&left_col.table, left_col.index),
+                    null_safe,
                 });
             }
         }
@@ -2391,6 +2469,7 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                 index: meta.resolve_column_index(&column.table, column.index),
             }),
             Expr::Literal(value) => Expr::Literal(value.clone()),
+            Expr::TypedNull(data_type) => Expr::TypedNull(*data_type),
             Expr::BinaryOp { left, op, right } => Expr::BinaryOp {
                 left: Box::new(Self::bind_expr_to_meta(left, meta)),
                 op: *op,
@@ -2407,6 +2486,10 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                     .map(|arg| Self::bind_expr_to_meta(arg, meta))
                     .collect(),
             },
+            Expr::Cast { expr, target } => Expr::Cast {
+                expr: Box::new(Self::bind_expr_to_meta(expr, meta)),
+                target: *target,
+            },
             Expr::Aggregate {
                 func,
                 expr,
@@ -2491,6 +2574,18 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                 self.execute_index_in_get(table, index, keys)
             }
 
+            PhysicalPlan::FetchByIds { table, ids } => {
+                let rows = self
+                    .data_source
+                    .fetch_rows_by_ids(table, &unique_ids(ids))?;
+                let column_count = self.data_source.get_column_count(table)?;
+                Ok(Relation::from_rows_with_column_count(
+                    rows,
+                    alloc::vec![table.clone()],
+                    column_count,
+                ))
+            }
+
             PhysicalPlan::GinIndexScan {
                 table,
                 index,
@@ -2523,7 +2618,9 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                 condition,
                 join_type,
                 output_tables,
+                ..
             } => {
+                Self::ensure_not_semi_or_anti_join(*join_type)?;
                 let left_rel = self.execute(left)?;
                 let right_rel = self.execute(right)?;
                 self.execute_hash_join(left_rel, right_rel, condition, *join_type, output_tables)
@@ -2536,6 +2633,7 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                 join_type,
                 output_tables,
             } => {
+                Self::ensure_not_semi_or_anti_join(*join_type)?;
                 let left_rel = self.execute(left)?;
                 let right_rel = self.execute(right)?;
                 self.execute_sort_merge_join(
@@ -2553,7 +2651,9 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                 condition,
                 join_type,
                 output_tables,
+                ..
             } => {
+                Self::ensure_not_semi_or_anti_join(*join_type)?;
                 let left_rel = self.execute(left)?;
                 let right_rel = self.execute(right)?;
                 self.execute_nested_loop_join(
@@ -2591,6 +2691,21 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                 group_by,
                 aggregates,
             } => {
+                if let PhysicalPlan::IndexScan {
+                    table,
+                    index,
+                    bounds,
+                    limit: None,
+                    offset: None,
+                    ..
+                } = input.as_ref()
+                {
+                    if let Some(relation) =
+                        self.try_index_count(table, index, bounds, group_by, aggregates)?
+                    {
+                        return Ok(relation);
+                    }
+                }
                 let input_rel = self.execute(input)?;
                 self.execute_hash_aggregate(input_rel, group_by, aggregates)
             }
@@ -2818,14 +2933,31 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                 input,
                 group_by,
                 aggregates,
-            } => self.emit_relation_rows(
-                self.execute_hash_aggregate(
-                    self.execute_compiled_exec_plan(input)?,
-                    group_by,
-                    aggregates,
-                )?,
-                emit,
-            ),
+            } => {
+                if let CompiledExecPlanKind::Source(CompiledSourcePlan::IndexScan {
+                    table,
+                    index,
+                    bounds,
+                    limit: None,
+                    offset: 0,
+                    ..
+                }) = &input.kind
+                {
+                    if let Some(relation) =
+                        self.try_index_count(table, index, bounds, group_by, aggregates)?
+                    {
+                        return self.emit_relation_rows(relation, emit);
+                    }
+                }
+                self.emit_relation_rows(
+                    self.execute_hash_aggregate(
+                        self.execute_compiled_exec_plan(input)?,
+                        group_by,
+                        aggregates,
+                    )?,
+                    emit,
+                )
+            }
             CompiledExecPlanKind::Sort { input, order_by } => self.emit_relation_rows(
                 self.execute_sort(self.execute_compiled_exec_plan(input)?, order_by)?,
                 emit,
@@ -2869,6 +3001,10 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
             join_type,
             crate::ast::JoinType::LeftOuter | crate::ast::JoinType::FullOuter
         );
+        // A semi-join emits the outer row once and an anti-join drops it, so
+        // either lookup stops at the first match.
+        let is_semi = join_type == crate::ast::JoinType::Semi;
+        let is_anti = join_type == crate::ast::JoinType::Anti;
         let inner_col_count = self.data_source.get_column_count(inner_table)?;
         let layout = Self::index_join_output_layout_from_meta(
             &outer.meta,
@@ -2900,10 +3036,13 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                                 outer_is_left,
                             );
                             matched = true;
+                            if is_anti {
+                                return false;
+                            }
                             match emit(ExecRowRef::Joined(view)) {
                                 Ok(next) => {
                                     continue_scan = next;
-                                    next
+                                    next && !is_semi
                                 }
                                 Err(err) => {
                                     visit_error = Some(err);
@@ -2922,7 +3061,7 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                 return Ok(false);
             }
 
-            if is_outer && !matched {
+            if (is_outer || is_anti) && !matched {
                 let view = Self::index_nested_loop_view(&outer_row, None, &layout, outer_is_left);
                 if !emit(ExecRowRef::Joined(view))? {
                     return Ok(false);
@@ -2957,7 +3096,7 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
             hashbrown::HashMap::with_capacity(build_rel.len());
         for (index, entry) in build_rel.entries.iter().enumerate() {
             if let Some(key_value) = entry.get_field(build_key_idx) {
-                if !key_value.is_null() {
+                if keys.null_safe || !key_value.is_null() {
                     hash_table
                         .entry(SqlValueRef::new(key_value))
                         .or_default()
@@ -2976,7 +3115,7 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
             let mut matched = false;
 
             if let Some(key_value) = probe_row.get_value(probe_key_idx) {
-                if !key_value.is_null() {
+                if keys.null_safe || !key_value.is_null() {
                     if let Some(build_indices) = hash_table.get(&SqlValueRef::new(key_value)) {
                         matched = true;
                         for &build_index in build_indices {
@@ -3173,6 +3312,17 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                 }
                 Ok(true)
             }
+            CompiledSourcePlan::FetchByIds { table, ids } => {
+                let rows = self.data_source.fetch_rows_by_ids(table, ids)?;
+                self.visit_compiled_source_rows(emit, |visit| {
+                    for row in &rows {
+                        if !visit(row) {
+                            break;
+                        }
+                    }
+                    Ok(())
+                })
+            }
             CompiledSourcePlan::GinIndexScan {
                 table,
                 index,
@@ -4588,6 +4738,7 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                 Value::DateTime(_) => InListKernelFamily::DateTime,
                 Value::Bytes(_) => InListKernelFamily::Bytes,
                 Value::Jsonb(_) => InListKernelFamily::Jsonb,
+                Value::Decimal(_) => return InListPredicateKernel::Generic(literals),
             };
 
             match family {
@@ -4734,6 +4885,7 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                 op,
                 literal: value.0.clone(),
             },
+            Value::Decimal(_) => SimplePredicateKernel::Generic,
             Value::Null => SimplePredicateKernel::Generic,
         }
     }
@@ -5555,8 +5707,43 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
         }
     }
 
+    /// Evaluates a comparison between a String and a number by parsing the
+    /// String, when the runner's policy is `Lenient`. A String that does not
+    /// parse makes the comparison NULL. Returns `None` when no coercion applies.
+    fn eval_coerced_comparison(&self, op: BinaryOp, left: &Value, right: &Value) -> Option<Value> {
+        if self.coercion == CoercionPolicy::Strict {
+            return None;
+        }
+        let op = Self::normalize_comparison_op(op, true)?;
+        fn parse_number(text: &str) -> Option<Value> {
+            let text = text.trim();
+            text.parse::<i64>()
+                .map(Value::Int64)
+                .or_else(|_| text.parse::<f64>().map(Value::Float64))
+                .ok()
+        }
+        let is_number =
+            |value: &Value| matches!(value, Value::Int32(_) | Value::Int64(_) | Value::Float64(_));
+        let ordering = match (left, right) {
+            (Value::String(text), number) if is_number(number) => {
+                parse_number(text).map(|parsed| parsed.cmp(number))
+            }
+            (number, Value::String(text)) if is_number(number) => {
+                parse_number(text).map(|parsed| number.cmp(&parsed))
+            }
+            _ => return None,
+        };
+        Some(match ordering {
+            Some(ordering) => Value::Boolean(Self::eval_comparison_op(op, ordering)),
+            None => Value::Null,
+        })
+    }
+
     #[inline]
     fn eval_binary_op_bool(&self, op: BinaryOp, left: &Value, right: &Value) -> bool {
+        if let Some(result) = self.eval_coerced_comparison(op, left, right) {
+            return result == Value::Boolean(true);
+        }
         match op {
             BinaryOp::Eq => left.sql_eq(right),
             BinaryOp::Ne => !left.sql_eq(right),
@@ -5576,15 +5763,26 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
 
     // ========== Project Operation ==========
 
+    /// Evaluates `columns` against each input row.
+    ///
+    /// Only an identity projection, every column of a single table in
+    /// schema order as `SELECT *` over a scan produces, reuses the shared
+    /// input rows. A `Row` owns its values and has no view over another
+    /// row's, so any other projection, a column subset included, allocates
+    /// a new row per entry.
     fn execute_project(&self, input: Relation, columns: &[Expr]) -> ExecutionResult<Relation> {
         let tables = input.tables().to_vec();
         let table_column_counts = input.table_column_counts().to_vec();
         let shared_tables: SharedTables = tables.clone().into();
         let ctx = EvalContext::new(&tables, &table_column_counts);
+        let identity = Self::is_identity_projection(&table_column_counts, columns);
 
         let entries: Vec<RelationEntry> = input
             .into_iter()
             .map(|entry| {
+                if identity && entry.row.len() == columns.len() {
+                    return RelationEntry::new_combined(entry.row, shared_tables.clone());
+                }
                 let values: Vec<Value> = columns
                     .iter()
                     .map(|col| self.eval_expr_ctx(col, &entry, Some(&ctx)))
@@ -5608,22 +5806,31 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
         })
     }
 
+    /// Returns true if `columns` are the columns of a single-table input,
+    /// in order, so projecting leaves every row unchanged.
+    fn is_identity_projection(table_column_counts: &[usize], columns: &[Expr]) -> bool {
+        table_column_counts == [columns.len()]
+            && columns
+                .iter()
+                .enumerate()
+                .all(|(i, col)| matches!(col, Expr::Column(c) if c.index == i))
+    }
+
     // ========== Join Operations ==========
 
     fn emit_hash_join_entries(
         &self,
         left: &Relation,
         right: &Relation,
-        left_key_idx: usize,
-        right_key_idx: usize,
+        keys: CompiledEquiJoinKeys,
         join_type: crate::ast::JoinType,
         output_tables: &[String],
         emit: &mut dyn FnMut(RelationEntry) -> ExecutionResult<bool>,
     ) -> ExecutionResult<bool> {
         let build_side = Self::choose_hash_join_build_side(left.len(), right.len(), join_type);
         let (build_rel, probe_rel, build_key_idx, probe_key_idx) = match build_side {
-            HashJoinBuildSide::Left => (left, right, left_key_idx, right_key_idx),
-            HashJoinBuildSide::Right => (right, left, right_key_idx, left_key_idx),
+            HashJoinBuildSide::Left => (left, right, keys.left_key_idx, keys.right_key_idx),
+            HashJoinBuildSide::Right => (right, left, keys.right_key_idx, keys.left_key_idx),
         };
         let emit_unmatched_probe = Self::hash_join_emit_unmatched_probe(join_type, build_side);
         let emit_unmatched_build = Self::hash_join_emit_unmatched_build(join_type, build_side);
@@ -5642,7 +5849,7 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
 
         for (index, entry) in build_rel.entries.iter().enumerate() {
             if let Some(key_value) = entry.get_field(build_key_idx) {
-                if !key_value.is_null() {
+                if keys.null_safe || !key_value.is_null() {
                     hash_table
                         .entry(SqlValueRef::new(key_value))
                         .or_default()
@@ -5654,7 +5861,7 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
         for probe_entry in probe_rel.entries.iter() {
             let mut matched = false;
             if let Some(key_value) = probe_entry.get_field(probe_key_idx) {
-                if !key_value.is_null() {
+                if keys.null_safe || !key_value.is_null() {
                     if let Some(build_indices) = hash_table.get(&SqlValueRef::new(key_value)) {
                         matched = true;
                         for &build_index in build_indices {
@@ -5960,6 +6167,10 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
             join_type,
             crate::ast::JoinType::LeftOuter | crate::ast::JoinType::FullOuter
         );
+        // A semi-join emits the outer row once and an anti-join drops it, so
+        // either lookup stops at the first match.
+        let is_semi = join_type == crate::ast::JoinType::Semi;
+        let is_anti = join_type == crate::ast::JoinType::Anti;
         let outer_key_idx = self.extract_outer_key_index(condition, outer)?;
         let inner_col_count = self.data_source.get_column_count(inner_table)?;
         let layout = Self::index_join_output_layout(
@@ -5993,10 +6204,13 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                                 outer_is_left,
                             );
                             matched = true;
+                            if is_anti {
+                                return false;
+                            }
                             match self.emit_join_view(&view, &shared_tables, emit) {
                                 Ok(next) => {
                                     continue_scan = next;
-                                    next
+                                    next && !is_semi
                                 }
                                 Err(err) => {
                                     visit_error = Some(err);
@@ -6015,7 +6229,7 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                 return Ok(false);
             }
 
-            if is_outer && !matched {
+            if (is_outer || is_anti) && !matched {
                 let view = Self::index_nested_loop_view(outer_entry, None, &layout, outer_is_left);
                 if !self.emit_join_view(&view, &shared_tables, emit)? {
                     return Ok(false);
@@ -6061,11 +6275,15 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
         let (left_key_idx, right_key_idx) = self.extract_join_keys(condition, &left, &right)?;
         let layout = Self::join_output_layout(&left, &right, output_tables);
         let mut entries = Vec::new();
+        let keys = CompiledEquiJoinKeys {
+            left_key_idx,
+            right_key_idx,
+            null_safe: condition.is_null_safe_equi_join(),
+        };
         self.emit_hash_join_entries(
             &left,
             &right,
-            left_key_idx,
-            right_key_idx,
+            keys,
             join_type,
             output_tables,
             &mut |entry| {
@@ -6088,6 +6306,7 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
         join_type: crate::ast::JoinType,
         output_tables: &[String],
     ) -> ExecutionResult<Relation> {
+        Self::ensure_not_null_safe_join(condition)?;
         let (left_key_idx, right_key_idx) = self.extract_join_keys(condition, &left, &right)?;
         let layout = Self::join_output_layout(&left, &right, output_tables);
         let mut entries = Vec::new();
@@ -6232,6 +6451,55 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
 
     // ========== Aggregate Operation ==========
 
+    /// Answers an ungrouped aggregate made only of `COUNT(*)` over an index
+    /// scan by counting index entries, without fetching any rows. Returns
+    /// `None` for any other aggregate.
+    fn try_index_count(
+        &self,
+        table: &str,
+        index: &str,
+        bounds: &IndexBounds,
+        group_by: &[Expr],
+        aggregates: &[(AggregateFunc, Expr)],
+    ) -> ExecutionResult<Option<Relation>> {
+        let counts_rows = |expr: &Expr| match expr {
+            Expr::Aggregate {
+                expr: None,
+                distinct: false,
+                ..
+            } => true,
+            Expr::Literal(value) => !value.is_null(),
+            _ => false,
+        };
+        if !group_by.is_empty()
+            || aggregates.is_empty()
+            || !aggregates
+                .iter()
+                .all(|(func, expr)| *func == AggregateFunc::Count && counts_rows(expr))
+        {
+            return Ok(None);
+        }
+
+        let range = match bounds {
+            IndexBounds::Unbounded | IndexBounds::Scalar(KeyRange::All) => None,
+            IndexBounds::Scalar(range) => Some(range),
+            IndexBounds::Composite(_) => return Ok(None),
+        };
+        let count = self.data_source.count_index_range(table, index, range)?;
+
+        let tables = alloc::vec![String::from(table)];
+        let values = alloc::vec![Value::Int64(count as i64); aggregates.len()];
+        let entry = RelationEntry::new_combined(
+            Rc::new(Row::dummy_with_version(0, values)),
+            tables.clone().into(),
+        );
+        Ok(Some(Relation {
+            entries: alloc::vec![entry],
+            tables,
+            table_column_counts: alloc::vec![aggregates.len()],
+        }))
+    }
+
     fn execute_hash_aggregate(
         &self,
         input: Relation,
@@ -6242,43 +6510,103 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
         let table_column_counts = input.table_column_counts().to_vec();
         let ctx = EvalContext::new(&tables, &table_column_counts);
 
-        // Convert Expr group_by to column indices
-        let group_by_indices: Vec<usize> = group_by
-            .iter()
-            .filter_map(|expr| {
-                if let Expr::Column(col) = expr {
-                    Some(ctx.resolve_column_index(&col.table, col.index))
-                } else {
-                    None
-                }
-            })
-            .collect();
+        // Column keys group by their index. Computed keys, and aggregate
+        // arguments that are not plain columns, are evaluated per row and
+        // appended after the input columns, then read by position.
+        let input_width: usize = table_column_counts.iter().sum();
+        let mut computed: Vec<&Expr> = Vec::new();
+        let mut column_index = |expr| Self::input_slot(expr, &mut computed, &ctx, input_width);
+        let group_by_indices: Vec<usize> = group_by.iter().map(&mut column_index).collect();
 
-        // Convert aggregates to (func, Option<column_index>)
-        let agg_specs: Vec<(AggregateFunc, Option<usize>)> = aggregates
+        let agg_specs: Vec<AggregateSpec> = aggregates
             .iter()
             .map(|(func, expr)| {
-                let col_idx = match expr {
-                    Expr::Column(col) => Some(ctx.resolve_column_index(&col.table, col.index)),
+                let input = match expr {
+                    Expr::Aggregate { expr: None, .. } => {
+                        return Ok(AggregateSpec::new(*func, None));
+                    }
                     Expr::Aggregate {
                         expr: Some(inner), ..
-                    } => {
-                        if let Expr::Column(col) = inner.as_ref() {
-                            Some(ctx.resolve_column_index(&col.table, col.index))
-                        } else {
-                            None
-                        }
-                    }
-                    _ => None,
+                    } => inner.as_ref(),
+                    other => other,
                 };
-                (*func, col_idx)
+                // ARG_MIN/ARG_MAX carry (key, value) as function arguments.
+                if matches!(func, AggregateFunc::ArgMin | AggregateFunc::ArgMax) {
+                    let (key, value) = match input {
+                        Expr::Function { args, .. } if args.len() == 2 => (&args[0], &args[1]),
+                        _ => {
+                            return Err(ExecutionError::InvalidOperation(alloc::format!(
+                                "unsupported argument to {:?}: expected (key, value)",
+                                func
+                            )))
+                        }
+                    };
+                    let (key, value) = (column_index(key), column_index(value));
+                    return Ok(if *func == AggregateFunc::ArgMin {
+                        AggregateSpec::arg_min(key, value)
+                    } else {
+                        AggregateSpec::arg_max(key, value)
+                    });
+                }
+                Ok(AggregateSpec::new(*func, Some(column_index(input))))
             })
-            .collect();
+            .collect::<ExecutionResult<_>>()?;
+
+        let input = if computed.is_empty() {
+            input
+        } else {
+            self.append_computed_columns(input, &computed, &ctx)
+        };
 
-        let executor = AggregateExecutor::new(group_by_indices, agg_specs);
+        let executor = AggregateExecutor::with_specs(group_by_indices, agg_specs);
         Ok(executor.execute(input))
     }
 
+    /// Returns the input position of `expr`: its column index, or the slot
+    /// after `input_width` where its computed value will be appended.
+    fn input_slot<'e>(
+        expr: &'e Expr,
+        computed: &mut Vec<&'e Expr>,
+        ctx: &EvalContext<'_>,
+        input_width: usize,
+    ) -> usize {
+        if let Expr::Column(col) = expr {
+            ctx.resolve_column_index(&col.table, col.index)
+        } else {
+            computed.push(expr);
+            input_width + computed.len() - 1
+        }
+    }
+
+    /// Returns `input` with the values of `exprs` appended to every row.
+    fn append_computed_columns(
+        &self,
+        input: Relation,
+        exprs: &[&Expr],
+        ctx: &EvalContext<'_>,
+    ) -> Relation {
+        let tables = input.tables().to_vec();
+        let mut column_counts = input.table_column_counts().to_vec();
+        if let Some(last) = column_counts.last_mut() {
+            *last += exprs.len();
+        }
+        let shared_tables: SharedTables = tables.clone().into();
+        let entries = input
+            .iter()
+            .map(|entry| {
+                let mut values = entry.row.values().to_vec();
+                values.extend(
+                    exprs
+                        .iter()
+                        .map(|expr| self.eval_expr_ctx(expr, entry, Some(ctx))),
+                );
+                let row = Row::new_with_version(entry.row.id(), entry.row.version(), values);
+                RelationEntry::new_combined(Rc::new(row), shared_tables.clone())
+            })
+            .collect();
+        Relation::from_entries(entries, tables, column_counts)
+    }
+
     // ========== Sort Operation ==========
 
     fn execute_sort(
@@ -6286,25 +6614,71 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
         input: Relation,
         order_by: &[(Expr, SortOrder)],
     ) -> ExecutionResult<Relation> {
-        let tables = input.tables().to_vec();
-        let table_column_counts = input.table_column_counts().to_vec();
-        let ctx = EvalContext::new(&tables, &table_column_counts);
+        let Some(order_by_indices) = Self::order_by_column_indices(&input, order_by) else {
+            return Ok(self.sort_by_computed_keys(input, order_by));
+        };
 
-        // Convert Expr order_by to column indices using dynamic computation
-        let order_by_indices: Vec<(usize, SortOrder)> = order_by
+        let executor = SortExecutor::new(order_by_indices);
+        Ok(executor.execute(input))
+    }
+
+    /// Resolves ORDER BY keys to column indices in `input`, or returns `None`
+    /// when a key is a computed expression.
+    fn order_by_column_indices(
+        input: &Relation,
+        order_by: &[(Expr, SortOrder)],
+    ) -> Option<Vec<(usize, SortOrder)>> {
+        let ctx = EvalContext::new(input.tables(), input.table_column_counts());
+        order_by
             .iter()
-            .filter_map(|(expr, order)| {
-                if let Expr::Column(col) = expr {
-                    let actual_index = ctx.resolve_column_index(&col.table, col.index);
-                    Some((actual_index, *order))
-                } else {
-                    None
+            .map(|(expr, order)| match expr {
+                Expr::Column(col) => {
+                    Some((ctx.resolve_column_index(&col.table, col.index), *order))
                 }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Sorts by ORDER BY keys that include computed expressions. Each key is
+    /// evaluated once per entry; ties keep their input order.
+    fn sort_by_computed_keys(&self, input: Relation, order_by: &[(Expr, SortOrder)]) -> Relation {
+        let Relation {
+            entries,
+            tables,
+            table_column_counts,
+        } = input;
+        let ctx = EvalContext::new(&tables, &table_column_counts);
+
+        let mut keyed: Vec<(Vec<Value>, JsonKeys, RelationEntry)> = entries
+            .into_iter()
+            .map(|entry| {
+                let keys: Vec<Value> = order_by
+                    .iter()
+                    .map(|(expr, _)| self.eval_expr_ctx(expr, &entry, Some(&ctx)))
+                    .collect();
+                let json = JsonKeys::decode(keys.iter().map(Some));
+                (keys, json, entry)
             })
             .collect();
+        keyed.sort_by(|(a, a_json, _), (b, b_json, _)| {
+            for (key, (_, order)) in order_by.iter().enumerate() {
+                let cmp = compare_key(key, (a.get(key), a_json), (b.get(key), b_json));
+                if cmp != Ordering::Equal {
+                    return match order {
+                        SortOrder::Asc => cmp,
+                        SortOrder::Desc => cmp.reverse(),
+                    };
+                }
+            }
+            Ordering::Equal
+        });
 
-        let executor = SortExecutor::new(order_by_indices);
-        Ok(executor.execute(input))
+        Relation {
+            entries: keyed.into_iter().map(|(_, _, entry)| entry).collect(),
+            tables,
+            table_column_counts,
+        }
     }
 
     // ========== Limit Operation ==========
@@ -6321,8 +6695,8 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
 
     // ========== TopN Operation ==========
 
-    /// Executes TopN using a binary heap for O(n log k) performance.
-    /// This is more efficient than Sort + Limit when k << n.
+    /// Executes TopN with a bounded heap in O(n log k), which beats
+    /// Sort + Limit when k << n. Computed sort keys fall back to a full sort.
     fn execute_topn(
         &self,
         input: Relation,
@@ -6330,92 +6704,11 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
         limit: usize,
         offset: usize,
     ) -> ExecutionResult<Relation> {
-        use alloc::collections::BinaryHeap;
-
-        let tables = input.tables().to_vec();
-        let table_column_counts = input.table_column_counts().to_vec();
-        let ctx = EvalContext::new(&tables, &table_column_counts);
-
-        // Convert order_by to column indices
-        let order_by_indices: Vec<(usize, SortOrder)> = order_by
-            .iter()
-            .filter_map(|(expr, order)| {
-                if let Expr::Column(col) = expr {
-                    let actual_index = ctx.resolve_column_index(&col.table, col.index);
-                    Some((actual_index, *order))
-                } else {
-                    None
-                }
-            })
-            .collect();
-
-        let k = limit.saturating_add(offset);
-
-        // For small k or small input, just use sort
-        if k == 0 || input.len() <= k.saturating_mul(2) {
-            let executor = SortExecutor::new(order_by_indices);
-            let sorted = executor.execute(input);
-            let limit_executor = LimitExecutor::new(limit, offset);
-            return Ok(limit_executor.execute(sorted));
-        }
-
-        // Build heap with capacity k (using reference to avoid cloning order_by per entry)
-        let mut heap: BinaryHeap<TopNHeapEntry> = BinaryHeap::with_capacity(k.saturating_add(1));
-
-        for entry in input.into_iter() {
-            let heap_entry = TopNHeapEntry {
-                entry,
-                order_by: &order_by_indices,
-            };
-
-            if heap.len() < k {
-                heap.push(heap_entry);
-            } else if let Some(top) = heap.peek() {
-                // Replace if new entry should come before the worst (top) in final result.
-                // For ASC: heap keeps k smallest, top is largest of those. Replace if new < top.
-                // For DESC: heap keeps k largest, top is smallest of those. Replace if new > top.
-                // In our Ord impl: for ASC, larger values are Greater; for DESC, smaller are Greater.
-                // So we replace when heap_entry < top (Less), meaning heap_entry is better.
-                if heap_entry.cmp(top) == Ordering::Less {
-                    heap.pop();
-                    heap.push(heap_entry);
-                }
-            }
-        }
-
-        // Extract and sort the k elements
-        let mut result: Vec<RelationEntry> = heap.into_iter().map(|e| e.entry).collect();
-
-        // Sort the result (heap doesn't maintain full order)
-        result.sort_by(|a, b| {
-            for (idx, order) in &order_by_indices {
-                let va = a.get_field(*idx);
-                let vb = b.get_field(*idx);
-                let cmp = match (va, vb) {
-                    (Some(va), Some(vb)) => va.partial_cmp(vb).unwrap_or(Ordering::Equal),
-                    (Some(_), None) => Ordering::Greater,
-                    (None, Some(_)) => Ordering::Less,
-                    (None, None) => Ordering::Equal,
-                };
-                if cmp != Ordering::Equal {
-                    return match order {
-                        SortOrder::Asc => cmp,
-                        SortOrder::Desc => cmp.reverse(),
-                    };
-                }
-            }
-            Ordering::Equal
-        });
-
-        // Apply offset and limit
-        let final_result: Vec<RelationEntry> =
-            result.into_iter().skip(offset).take(limit).collect();
-
-        Ok(Relation::from_entries(
-            final_result,
-            tables,
-            table_column_counts,
-        ))
+        let Some(order_by_indices) = Self::order_by_column_indices(&input, order_by) else {
+            let sorted = self.sort_by_computed_keys(input, order_by);
+            return Ok(LimitExecutor::new(limit, offset).execute(sorted));
+        };
+        Ok(TopNExecutor::new(order_by_indices, limit, offset).execute(input))
     }
 
     // ========== Expression Evaluation ==========
@@ -6442,6 +6735,8 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
 
             Expr::Literal(value) => value.clone(),
 
+            Expr::TypedNull(_) => Value::Null,
+
             Expr::BinaryOp { left, op, right } => {
                 let left_val = self.eval_accessor_expr(left, accessor, ctx);
                 let right_val = self.eval_accessor_expr(right, accessor, ctx);
@@ -6453,6 +6748,11 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                 self.eval_unary_op(*op, &val)
             }
 
+            Expr::Cast { expr, target } => self
+                .eval_accessor_expr(expr, accessor, ctx)
+                .coerce_to(*target)
+                .unwrap_or(Value::Null),
+
             Expr::Aggregate { expr, .. } => {
                 if let Some(e) = expr {
                     self.eval_accessor_expr(e, accessor, ctx)
@@ -6527,6 +6827,21 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                 }
             }
 
+            // ROW_TO_JSONB keys its object by the argument column names,
+            // which are gone once the arguments are evaluated.
+            Expr::Function { name, args } if name.eq_ignore_ascii_case("ROW_TO_JSONB") => {
+                let mut object = JsonbObject::with_capacity(args.len());
+                for (i, arg) in args.iter().enumerate() {
+                    let key = match arg {
+                        Expr::Column(column) => column.column.clone(),
+                        _ => alloc::format!("f{}", i + 1),
+                    };
+                    let value = self.eval_accessor_expr(arg, accessor, ctx);
+                    object.insert(key, value_to_jsonb(&value));
+                }
+                jsonb_to_value(&JsonbValue::Object(object))
+            }
+
             Expr::Function { name, args } => {
                 let arg_values: Vec<Value> = args
                     .iter()
@@ -6537,6 +6852,22 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
         }
     }
 
+    /// Evaluates a scalar expression against a single row of `table`.
+    ///
+    /// Column references use table-relative indices, as they would in a
+    /// single-table plan.
+    pub fn eval_row(&self, expr: &Expr, row: Rc<Row>, table: &str) -> Value {
+        let entry = RelationEntry::new(row, vec![table.into()]);
+        self.eval_expr(expr, &entry)
+    }
+
+    /// Evaluates a scalar expression against borrowed column values, with
+    /// column references indexing into `values`. Unlike `eval_row` it
+    /// allocates nothing beyond the evaluation itself.
+    pub fn eval_values(&self, expr: &Expr, values: &[Value]) -> Value {
+        self.eval_accessor_expr(expr, &values, None)
+    }
+
     /// Evaluates an expression against a relation entry.
     /// If `ctx` is provided, column indices are dynamically computed based on table metadata.
     /// This is needed for JOIN queries where the optimizer may have reordered tables.
@@ -6614,12 +6945,20 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                     }
                     Value::Null
                 }
+                BinaryOp::IsNotDistinctFrom => Value::Boolean(left.is_null() && right.is_null()),
                 _ => Value::Null,
             };
         }
 
+        if let Some(result) = self.eval_coerced_comparison(op, left, right) {
+            return result;
+        }
+        if let Some(result) = eval_decimal_arithmetic(op, left, right) {
+            return result;
+        }
+
         match op {
-            BinaryOp::Eq => Value::Boolean(left.sql_eq(right)),
+            BinaryOp::Eq | BinaryOp::IsNotDistinctFrom => Value::Boolean(left.sql_eq(right)),
             BinaryOp::Ne => Value::Boolean(!left.sql_eq(right)),
             BinaryOp::Lt => Value::Boolean(left < right),
             BinaryOp::Le => Value::Boolean(left <= right),
@@ -6651,8 +6990,17 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
             BinaryOp::Mod => match (left, right) {
                 (Value::Int64(a), Value::Int64(b)) if *b != 0 => Value::Int64(a % b),
                 (Value::Int32(a), Value::Int32(b)) if *b != 0 => Value::Int32(a % b),
+                (Value::Int32(a), Value::Int64(b)) if *b != 0 => Value::Int64(*a as i64 % b),
+                (Value::Int64(a), Value::Int32(b)) if *b != 0 => Value::Int64(a % *b as i64),
                 _ => Value::Null,
             },
+            BinaryOp::Pow => eval_pow(left, right),
+            BinaryOp::FloorDiv => eval_floor_div(left, right),
+            BinaryOp::BitAnd
+            | BinaryOp::BitOr
+            | BinaryOp::BitXor
+            | BinaryOp::Shl
+            | BinaryOp::Shr => eval_bitwise(op, left, right),
             BinaryOp::Like | BinaryOp::In | BinaryOp::Between => {
                 // These are handled specially in eval_expr
                 Value::Null
@@ -6679,10 +7027,13 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
 
         let result = op(l, r);
 
-        // Preserve integer type if both inputs are integers
+        // Preserve integer type if both inputs are integers, widening mixed
+        // Int32/Int64 operands to Int64
         match (left, right) {
-            (Value::Int64(_), Value::Int64(_)) => Value::Int64(result as i64),
             (Value::Int32(_), Value::Int32(_)) => Value::Int32(result as i32),
+            (Value::Int32(_) | Value::Int64(_), Value::Int32(_) | Value::Int64(_)) => {
+                Value::Int64(result as i64)
+            }
             _ => Value::Float64(result),
         }
     }
@@ -6698,6 +7049,7 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                 Value::Int32(i) => Value::Int32(-i),
                 Value::Int64(i) => Value::Int64(-i),
                 Value::Float64(f) => Value::Float64(-f),
+                Value::Decimal(d) => d.checked_neg().map_or(Value::Null, Value::Decimal),
                 _ => Value::Null,
             },
             UnaryOp::IsNull => Value::Boolean(value.is_null()),
@@ -6713,12 +7065,25 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                         Value::Int32(i) => Value::Int32(i.abs()),
                         Value::Int64(i) => Value::Int64(i.abs()),
                         Value::Float64(f) => Value::Float64(f.abs()),
+                        Value::Decimal(d) => d.checked_abs().map_or(Value::Null, Value::Decimal),
                         _ => Value::Null,
                     }
                 } else {
                     Value::Null
                 }
             }
+            "POWER" | "POW" => match (args.first(), args.get(1)) {
+                (Some(base), Some(exponent)) => eval_power(base, exponent),
+                _ => Value::Null,
+            },
+            "DATE_TRUNC" => match (args.first(), args.get(1)) {
+                (Some(unit), Some(ts)) => eval_date_trunc(unit, ts),
+                _ => Value::Null,
+            },
+            "EXTRACT" => match (args.first(), args.get(1)) {
+                (Some(field), Some(ts)) => eval_extract(field, ts),
+                _ => Value::Null,
+            },
             "UPPER" => {
                 if let Some(Value::String(s)) = args.first() {
                     Value::String(s.to_uppercase().into())
@@ -6740,6 +7105,62 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
                     Value::Null
                 }
             }
+            "CONTAINS" => eval_substring_test(args, |s, part| s.contains(part)),
+            "STARTS_WITH" => eval_substring_test(args, |s, part| s.starts_with(part)),
+            "ENDS_WITH" => eval_substring_test(args, |s, part| s.ends_with(part)),
+            // Fields are 1-based; an index outside the fields yields ''.
+            "SPLIT_PART" => match (args.first(), args.get(1), args.get(2)) {
+                (Some(Value::String(s)), Some(Value::String(delim)), Some(n)) => {
+                    let field = match n {
+                        Value::Int32(n) => Some(*n as i64),
+                        Value::Int64(n) => Some(*n),
+                        _ => None,
+                    };
+                    match field {
+                        Some(n) if n >= 1 => {
+                            let part = if delim.is_empty() {
+                                (n == 1).then_some(s.as_str())
+                            } else {
+                                s.split(delim.as_str()).nth((n - 1) as usize)
+                            };
+                            Value::String(part.unwrap_or("").into())
+                        }
+                        Some(_) => Value::String(String::new()),
+                        None => Value::Null,
+                    }
+                }
+                _ => Value::Null,
+            },
+            // Unlike most functions, NULL converts to the JSON `null` document.
+            "TO_JSONB" => match args.first() {
+                Some(value) => jsonb_to_value(&value_to_jsonb(value)),
+                None => Value::Null,
+            },
+            // Behaves like `||`: any NULL argument makes the result NULL.
+            "CONCAT" => {
+                let mut out = String::new();
+                for arg in args {
+                    match arg.coerce_to(DataType::String) {
+                        Some(Value::String(s)) => out.push_str(&s),
+                        _ => return Value::Null,
+                    }
+                }
+                Value::String(out)
+            }
+            // NULL arguments are skipped; numbers of different types compare
+            // by value and the winner keeps its own type.
+            "GREATEST" => args
+                .iter()
+                .filter(|arg| !arg.is_null())
+                .max()
+                .cloned()
+                .unwrap_or(Value::Null),
+            "LEAST" => args
+                .iter()
+                .filter(|arg| !arg.is_null())
+                .min()
+                .cloned()
+                .unwrap_or(Value::Null),
             "COALESCE" => {
                 for arg in args {
                     if !arg.is_null() {
@@ -7191,179 +7612,7 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
     /// Parses JSON string bytes to JsonbValue.
     fn parse_json_bytes(&self, bytes: &[u8]) -> Option<JsonbValue> {
         let json_str = core::str::from_utf8(bytes).ok()?;
-        self.parse_json_str(json_str)
-    }
-
-    /// Parses a JSON string to JsonbValue.
-    fn parse_json_str(&self, s: &str) -> Option<JsonbValue> {
-        // Simple JSON parser for common types
-        let s = s.trim();
-        if s == "null" {
-            return Some(JsonbValue::Null);
-        }
-        if s == "true" {
-            return Some(JsonbValue::Bool(true));
-        }
-        if s == "false" {
-            return Some(JsonbValue::Bool(false));
-        }
-        // Try parsing as number
-        if let Ok(n) = s.parse::<f64>() {
-            return Some(JsonbValue::Number(n));
-        }
-        // Try parsing as string (quoted)
-        if s.starts_with('"') && s.ends_with('"') && s.len() >= 2 {
-            let inner = &s[1..s.len() - 1];
-            // Handle escape sequences
-            let unescaped = self.unescape_json_string(inner);
-            return Some(JsonbValue::String(unescaped));
-        }
-        // Try parsing as object
-        if s.starts_with('{') && s.ends_with('}') {
-            return self.parse_json_object(s);
-        }
-        // Try parsing as array
-        if s.starts_with('[') && s.ends_with(']') {
-            return self.parse_json_array(s);
-        }
-        None
-    }
-
-    /// Unescapes a JSON string.
-    fn unescape_json_string(&self, s: &str) -> String {
-        let mut result = String::new();
-        let mut chars = s.chars().peekable();
-        while let Some(c) = chars.next() {
-            if c == '\\' {
-                if let Some(&next) = chars.peek() {
-                    match next {
-                        '"' | '\\' | '/' => {
-                            result.push(next);
-                            chars.next();
-                        }
-                        'n' => {
-                            result.push('\n');
-                            chars.next();
-                        }
-                        'r' => {
-                            result.push('\r');
-                            chars.next();
-                        }
-                        't' => {
-                            result.push('\t');
-                            chars.next();
-                        }
-                        _ => result.push(c),
-                    }
-                } else {
-                    result.push(c);
-                }
-            } else {
-                result.push(c);
-            }
-        }
-        result
-    }
-
-    /// Parses a JSON object string.
-    fn parse_json_object(&self, s: &str) -> Option<JsonbValue> {
-        let inner = s[1..s.len() - 1].trim();
-        if inner.is_empty() {
-            return Some(JsonbValue::Object(JsonbObject::new()));
-        }
-
-        let mut obj = JsonbObject::new();
-        let mut depth = 0;
-        let mut in_string = false;
-        let mut escape = false;
-        let mut start = 0;
-
-        for (i, c) in inner.char_indices() {
-            if escape {
-                escape = false;
-                continue;
-            }
-            match c {
-                '\\' if in_string => escape = true,
-                '"' => in_string = !in_string,
-                '{' | '[' if !in_string => depth += 1,
-                '}' | ']' if !in_string => depth -= 1,
-                ',' if !in_string && depth == 0 => {
-                    if let Some((k, v)) = self.parse_json_kv(&inner[start..i]) {
-                        obj.insert(k, v);
-                    }
-                    start = i + 1;
-                }
-                _ => {}
-            }
-        }
-        // Parse last entry
-        if start < inner.len() {
-            if let Some((k, v)) = self.parse_json_kv(&inner[start..]) {
-                obj.insert(k, v);
-            }
-        }
-
-        Some(JsonbValue::Object(obj))
-    }
-
-    /// Parses a JSON key-value pair.
-    fn parse_json_kv(&self, s: &str) -> Option<(String, JsonbValue)> {
-        let s = s.trim();
-        let colon_pos = s.find(':')?;
-        let key_part = s[..colon_pos].trim();
-        let value_part = s[colon_pos + 1..].trim();
-
-        // Key should be a quoted string
-        if key_part.starts_with('"') && key_part.ends_with('"') && key_part.len() >= 2 {
-            let key = self.unescape_json_string(&key_part[1..key_part.len() - 1]);
-            let value = self.parse_json_str(value_part)?;
-            Some((key, value))
-        } else {
-            None
-        }
-    }
-
-    /// Parses a JSON array string.
-    fn parse_json_array(&self, s: &str) -> Option<JsonbValue> {
-        let inner = s[1..s.len() - 1].trim();
-        if inner.is_empty() {
-            return Some(JsonbValue::Array(Vec::new()));
-        }
-
-        let mut items = Vec::new();
-        let mut depth = 0;
-        let mut in_string = false;
-        let mut escape = false;
-        let mut start = 0;
-
-        for (i, c) in inner.char_indices() {
-            if escape {
-                escape = false;
-                continue;
-            }
-            match c {
-                '\\' if in_string => escape = true,
-                '"' => in_string = !in_string,
-                '{' | '[' if !in_string => depth += 1,
-                '}' | ']' if !in_string => depth -= 1,
-                ',' if !in_string && depth == 0 => {
-                    if let Some(v) = self.parse_json_str(inner[start..i].trim()) {
-                        items.push(v);
-                    }
-                    start = i + 1;
-                }
-                _ => {}
-            }
-        }
-        // Parse last item
-        if start < inner.len() {
-            if let Some(v) = self.parse_json_str(inner[start..].trim()) {
-                items.push(v);
-            }
-        }
-
-        Some(JsonbValue::Array(items))
+        cynos_jsonb::parse_json(json_str).ok()
     }
 
     /// Evaluates a JSONB path equality expression.
@@ -7471,7 +7720,7 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
     ) -> ExecutionResult<(usize, usize)> {
         if let Expr::BinaryOp {
             left: left_expr,
-            op: BinaryOp::Eq,
+            op: BinaryOp::Eq | BinaryOp::IsNotDistinctFrom,
             right: right_expr,
         } = condition
         {
@@ -7555,6 +7804,53 @@ impl<'a, D: DataSource> PhysicalPlanRunner<'a, D> {
     }
 }
 
+/// Drops repeated ids, keeping the first occurrence of each.
+fn unique_ids(ids: &[RowId]) -> Vec<RowId> {
+    let mut seen = alloc::collections::BTreeSet::new();
+    ids.iter().copied().filter(|id| seen.insert(*id)).collect()
+}
+
+/// Applies a substring test to `(string, part)` arguments; anything other
+/// than two strings yields NULL.
+fn eval_substring_test(args: &[Value], test: impl Fn(&str, &str) -> bool) -> Value {
+    match (args.first(), args.get(1)) {
+        (Some(Value::String(s)), Some(Value::String(part))) => Value::Boolean(test(s, part)),
+        _ => Value::Null,
+    }
+}
+
+/// Converts a value to its JSON document equivalent. Timestamps become
+/// millisecond numbers, bytes an array of byte values, and JSONB values are
+/// embedded as the documents they hold.
+fn value_to_jsonb(value: &Value) -> JsonbValue {
+    match value {
+        Value::Null => JsonbValue::Null,
+        Value::Boolean(b) => JsonbValue::Bool(*b),
+        Value::Int32(i) => JsonbValue::Number(*i as f64),
+        Value::Int64(i) | Value::DateTime(i) => JsonbValue::Number(*i as f64),
+        Value::Float64(f) => JsonbValue::Number(*f),
+        Value::Decimal(d) => JsonbValue::Number(d.to_f64()),
+        Value::String(s) => JsonbValue::String(s.clone()),
+        Value::Bytes(bytes) => JsonbValue::Array(
+            bytes
+                .iter()
+                .map(|b| JsonbValue::Number(*b as f64))
+                .collect(),
+        ),
+        Value::Jsonb(jsonb) => core::str::from_utf8(&jsonb.0)
+            .ok()
+            .and_then(|text| cynos_jsonb::parse_json(text).ok())
+            .unwrap_or(JsonbValue::Null),
+    }
+}
+
+/// Wraps a JSON document as a JSONB value.
+fn jsonb_to_value(doc: &JsonbValue) -> Value {
+    Value::Jsonb(cynos_core::JsonbValue::new(
+        cynos_jsonb::to_json_string(doc).into_bytes(),
+    ))
+}
+
 /// A simple in-memory data source for testing and simple use cases.
 #[derive(Default)]
 pub struct InMemoryDataSource {
@@ -7991,6 +8287,7 @@ mod tests {
     use alloc::rc::Rc;
     use alloc::vec;
     use core::cell::Cell;
+    use cynos_core::DataType;
 
     struct CountingDataSource {
         inner: InMemoryDataSource,
@@ -8257,6 +8554,33 @@ mod tests {
         assert_eq!(result.len(), 2);
     }
 
+    #[test]
+    fn test_union_all_of_disjoint_index_scans() {
+        let ds = create_test_data_source();
+        let runner = PhysicalPlanRunner::new(&ds);
+
+        let scan = |range| PhysicalPlan::IndexScan {
+            table: "users".into(),
+            index: "idx_id".into(),
+            bounds: IndexBounds::Scalar(range),
+            limit: None,
+            offset: None,
+            reverse: false,
+        };
+        // id < 2 OR id > 2
+        let plan = PhysicalPlan::union(
+            scan(KeyRange::upper_bound(Value::Int64(2), true)),
+            scan(KeyRange::lower_bound(Value::Int64(2), true)),
+            true,
+        );
+        let result = runner.execute(&plan).unwrap();
+
+        assert_eq!(result.tables(), &["users"]);
+        let ids: Vec<_> = result.iter().map(|e| e.get_field(0).cloned()).collect();
+        assert_eq!(ids, vec![Some(Value::Int64(1)), Some(Value::Int64(3))]);
+        assert_full_execution_artifact_matches(&plan);
+    }
+
     #[test]
     fn test_composite_index_scan_with_tuple_bounds() {
         let mut ds = InMemoryDataSource::new();
@@ -8600,6 +8924,44 @@ mod tests {
         assert_eq!(compiled.entries[0].get_field(0), Some(&Value::Int64(2)));
     }
 
+    #[test]
+    fn test_fetch_by_ids() {
+        let ds = create_test_data_source();
+        let runner = PhysicalPlanRunner::new(&ds);
+
+        // Rows follow the requested order; unknown and repeated ids are skipped.
+        let plan = PhysicalPlan::fetch_by_ids("users", vec![3, 1, 99, 3]);
+        let names = |relation: &Relation| -> Vec<Value> {
+            relation
+                .entries
+                .iter()
+                .map(|entry| entry.get_field(1).cloned().unwrap())
+                .collect()
+        };
+        let expected = vec![
+            Value::String("Charlie".into()),
+            Value::String("Alice".into()),
+        ];
+
+        let result = runner.execute(&plan).unwrap();
+        assert_eq!(names(&result), expected);
+
+        let artifact = runner.compile_execution_artifact_with_data_source(&plan);
+        let compiled = runner.execute_with_artifact(&plan, &artifact).unwrap();
+        assert_eq!(names(&compiled), expected);
+
+        // Predicates apply to the fetched rows only.
+        let filtered = PhysicalPlan::filter(
+            plan,
+            Expr::eq(
+                Expr::column("users", "dept_id", 2),
+                Expr::literal(Value::Int64(10)),
+            ),
+        );
+        let result = runner.execute(&filtered).unwrap();
+        assert_eq!(names(&result), expected);
+    }
+
     #[test]
     fn test_filter_literal_on_left() {
         let ds = create_test_data_source();
@@ -8698,6 +9060,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_identity_projection_over_index_scan_reuses_rows() {
+        let ds = create_test_data_source();
+        let runner = PhysicalPlanRunner::new(&ds);
+        let scan = || PhysicalPlan::index_scan("users", "idx_id", None, None);
+        let project = |columns: &[(&str, usize)]| {
+            PhysicalPlan::project(
+                scan(),
+                columns
+                    .iter()
+                    .map(|&(name, index)| Expr::column("users", name, index))
+                    .collect(),
+            )
+        };
+
+        let scanned = runner.execute(&scan()).unwrap();
+        let all = runner
+            .execute(&project(&[("id", 0), ("name", 1), ("dept_id", 2)]))
+            .unwrap();
+
+        assert_eq!(all.len(), scanned.len());
+        assert_eq!(all.table_column_counts(), &[3]);
+        for (projected, row) in all.entries.iter().zip(&scanned.entries) {
+            assert!(Rc::ptr_eq(&projected.row, &row.row));
+            assert_eq!(projected.row.values(), row.row.values());
+        }
+
+        // Any other column list still builds new rows.
+        let reordered = runner
+            .execute(&project(&[("name", 1), ("id", 0), ("dept_id", 2)]))
+            .unwrap();
+        assert!(!Rc::ptr_eq(
+            &reordered.entries[0].row,
+            &scanned.entries[0].row
+        ));
+        assert_eq!(
+            reordered.entries[0].row.values(),
+            &[
+                Value::String("Alice".into()),
+                Value::Int64(1),
+                Value::Int64(10)
+            ]
+        );
+    }
+
     #[test]
     fn test_single_table_pipeline_filter_project_limit() {
         let ds = create_test_data_source();
@@ -8764,67 +9171,741 @@ mod tests {
     }
 
     #[test]
-    fn test_execution_artifact_matches_single_table_pipeline() {
-        let plan = PhysicalPlan::limit(
-            PhysicalPlan::project(
-                PhysicalPlan::filter(
-                    PhysicalPlan::table_scan("users"),
-                    Expr::eq(
-                        Expr::column("users", "dept_id", 2),
-                        Expr::literal(Value::Int64(10)),
-                    ),
-                ),
-                vec![Expr::column("users", "name", 1)],
-            ),
-            1,
-            1,
-        );
-
-        assert_execution_artifact_matches(&plan);
-    }
-
-    #[test]
-    fn test_execution_artifact_matches_offset_beyond_input() {
-        let plan = PhysicalPlan::limit(PhysicalPlan::table_scan("users"), 1_000_000_000, 10);
-
-        assert_execution_artifact_matches(&plan);
-    }
-
-    #[test]
-    fn test_hash_join() {
+    fn test_typed_null_evaluates_as_null() {
         let ds = create_test_data_source();
         let runner = PhysicalPlanRunner::new(&ds);
 
-        // Join users and departments on dept_id = id
-        let plan = PhysicalPlan::hash_join(
+        let plan = PhysicalPlan::project(
+            PhysicalPlan::table_scan("users"),
+            vec![Expr::Function {
+                name: "COALESCE".into(),
+                args: vec![
+                    Expr::null(cynos_core::DataType::String),
+                    Expr::column("users", "name", 1),
+                ],
+            }],
+        );
+        let result = runner.execute(&plan).unwrap();
+        assert_eq!(
+            result.entries[0].get_field(0),
+            Some(&Value::String("Alice".into()))
+        );
+
+        // Comparing with NULL is never true, whatever its type.
+        let plan = PhysicalPlan::filter(
             PhysicalPlan::table_scan("users"),
-            PhysicalPlan::table_scan("departments"),
             Expr::eq(
                 Expr::column("users", "dept_id", 2),
-                Expr::column("departments", "id", 0),
+                Expr::null(cynos_core::DataType::Int64),
             ),
-            JoinType::Inner,
         );
-        let result = runner.execute(&plan).unwrap();
-
-        // Alice and Charlie match Engineering (10), Bob matches Sales (20)
-        assert_eq!(result.len(), 3);
-        assert_eq!(result.tables(), &["users", "departments"]);
+        assert_eq!(runner.execute(&plan).unwrap().len(), 0);
     }
 
     #[test]
-    fn test_hash_join_matches_across_integer_widths() {
-        let ds = create_cross_width_join_data_source();
+    fn test_greatest_and_least() {
+        let ds = create_test_data_source();
         let runner = PhysicalPlanRunner::new(&ds);
+        let call = |name: &str, args: Vec<Value>| Expr::Function {
+            name: name.into(),
+            args: args.into_iter().map(Expr::literal).collect(),
+        };
 
-        let plan = PhysicalPlan::hash_join(
-            PhysicalPlan::table_scan("users_mixed"),
-            PhysicalPlan::table_scan("departments_mixed"),
-            Expr::eq(
-                Expr::column("users_mixed", "dept_id", 2),
-                Expr::column("departments_mixed", "id", 0),
+        let plan = PhysicalPlan::project(
+            PhysicalPlan::filter(
+                PhysicalPlan::table_scan("users"),
+                Expr::eq(
+                    Expr::column("users", "id", 0),
+                    Expr::literal(Value::Int64(1)),
+                ),
             ),
-            JoinType::Inner,
+            vec![
+                call(
+                    "GREATEST",
+                    vec![Value::Int32(3), Value::Float64(2.5), Value::Int64(7)],
+                ),
+                call(
+                    "LEAST",
+                    vec![Value::Int32(3), Value::Float64(2.5), Value::Int64(7)],
+                ),
+                call("greatest", vec![Value::Null, Value::Int64(4), Value::Null]),
+                call(
+                    "LEAST",
+                    vec![Value::Float64(-1.5), Value::Null, Value::Int32(0)],
+                ),
+                call("GREATEST", vec![Value::Null, Value::Null]),
+                Expr::Function {
+                    name: "LEAST".into(),
+                    args: vec![
+                        Expr::column("users", "dept_id", 2),
+                        Expr::literal(Value::Float64(12.5)),
+                    ],
+                },
+            ],
+        );
+        let result = runner.execute(&plan).unwrap();
+        let fields: Vec<Value> = (0..6)
+            .map(|i| result.entries[0].get_field(i).cloned().unwrap())
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                Value::Int64(7),
+                Value::Float64(2.5),
+                Value::Int64(4),
+                Value::Float64(-1.5),
+                Value::Null,
+                Value::Int64(10),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_concat_function() {
+        let ds = create_test_data_source();
+        let runner = PhysicalPlanRunner::new(&ds);
+
+        let plan = PhysicalPlan::project(
+            PhysicalPlan::filter(
+                PhysicalPlan::table_scan("users"),
+                Expr::eq(
+                    Expr::column("users", "id", 0),
+                    Expr::literal(Value::Int64(1)),
+                ),
+            ),
+            vec![
+                Expr::concat(vec![
+                    Expr::column("users", "name", 1),
+                    Expr::literal(" #"),
+                    Expr::column("users", "dept_id", 2),
+                ]),
+                Expr::concat(vec![
+                    Expr::column("users", "name", 1),
+                    Expr::literal(Value::Null),
+                ]),
+            ],
+        );
+        let result = runner.execute(&plan).unwrap();
+        assert_eq!(
+            result.entries[0].get_field(0),
+            Some(&Value::String("Alice #10".into()))
+        );
+        assert_eq!(result.entries[0].get_field(1), Some(&Value::Null));
+    }
+
+    #[test]
+    fn test_substring_predicates() {
+        let ds = InMemoryDataSource::new();
+        let runner = PhysicalPlanRunner::new(&ds);
+        let row = Rc::new(Row::new(1, vec![Value::String("Alice".into())]));
+        let eval = |expr: Expr| runner.eval_row(&expr, row.clone(), "users");
+        let name = || Expr::column("users", "name", 0);
+
+        assert_eq!(
+            eval(Expr::contains(name(), Expr::literal("lic"))),
+            Value::Boolean(true)
+        );
+        assert_eq!(
+            eval(Expr::contains(name(), Expr::literal("bob"))),
+            Value::Boolean(false)
+        );
+        assert_eq!(
+            eval(Expr::starts_with(name(), Expr::literal("Al"))),
+            Value::Boolean(true)
+        );
+        assert_eq!(
+            eval(Expr::starts_with(name(), Expr::literal("al"))),
+            Value::Boolean(false)
+        );
+        assert_eq!(
+            eval(Expr::ends_with(name(), Expr::literal("ice"))),
+            Value::Boolean(true)
+        );
+        assert_eq!(
+            eval(Expr::ends_with(name(), Expr::literal("Al"))),
+            Value::Boolean(false)
+        );
+        assert_eq!(
+            eval(Expr::contains(name(), Expr::literal(Value::Int64(1)))),
+            Value::Null
+        );
+        assert_eq!(
+            eval(Expr::starts_with(
+                Expr::literal(Value::Null),
+                Expr::literal("A")
+            )),
+            Value::Null
+        );
+    }
+
+    #[test]
+    fn test_to_jsonb_scalars() {
+        let ds = InMemoryDataSource::new();
+        let runner = PhysicalPlanRunner::new(&ds);
+        let row = Rc::new(Row::new(1, vec![]));
+        let to_jsonb = |value: Value| match runner.eval_row(
+            &Expr::to_jsonb(Expr::literal(value)),
+            row.clone(),
+            "t",
+        ) {
+            Value::Jsonb(jsonb) => runner.parse_json_bytes(&jsonb.0).unwrap(),
+            other => panic!("expected JSONB, got {:?}", other),
+        };
+
+        assert_eq!(to_jsonb(Value::Null), JsonbValue::Null);
+        assert_eq!(to_jsonb(Value::Boolean(true)), JsonbValue::Bool(true));
+        assert_eq!(to_jsonb(Value::Int32(-3)), JsonbValue::Number(-3.0));
+        assert_eq!(to_jsonb(Value::Int64(42)), JsonbValue::Number(42.0));
+        assert_eq!(to_jsonb(Value::Float64(1.5)), JsonbValue::Number(1.5));
+        assert_eq!(to_jsonb(Value::DateTime(1000)), JsonbValue::Number(1000.0));
+        assert_eq!(
+            to_jsonb(Value::String("say \"hi\"".into())),
+            JsonbValue::String("say \"hi\"".into())
+        );
+        assert_eq!(
+            to_jsonb(Value::Bytes(vec![0, 255])),
+            cynos_jsonb::parse_json("[0, 255]").unwrap()
+        );
+        // JSONB values are embedded as documents, not re-encoded as strings.
+        assert_eq!(
+            to_jsonb(Value::Jsonb(cynos_core::JsonbValue::new(
+                br#"{"a": [1, 2]}"#.to_vec()
+            ))),
+            cynos_jsonb::parse_json(r#"{"a": [1, 2]}"#).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_row_to_jsonb() {
+        let ds = InMemoryDataSource::new();
+        let runner = PhysicalPlanRunner::new(&ds);
+        let row = Rc::new(Row::new(
+            1,
+            vec![
+                Value::Int64(7),
+                Value::String("Alice".into()),
+                Value::Null,
+                Value::Jsonb(cynos_core::JsonbValue::new(br#"["admin"]"#.to_vec())),
+            ],
+        ));
+        let expr = Expr::row_to_jsonb(vec![
+            Expr::column("users", "id", 0),
+            Expr::column("users", "name", 1),
+            Expr::column("users", "manager", 2),
+            Expr::column("users", "roles", 3),
+            Expr::literal(true),
+        ]);
+
+        let doc = match runner.eval_row(&expr, row, "users") {
+            Value::Jsonb(jsonb) => runner.parse_json_bytes(&jsonb.0).unwrap(),
+            other => panic!("expected JSONB, got {:?}", other),
+        };
+        assert_eq!(
+            doc,
+            cynos_jsonb::parse_json(
+                r#"{"id": 7, "name": "Alice", "manager": null, "roles": ["admin"], "f5": true}"#
+            )
+            .unwrap()
+        );
+        assert_eq!(expr.infer_type(&|_| None), Some(DataType::Jsonb));
+    }
+
+    #[test]
+    fn test_date_functions() {
+        let ds = InMemoryDataSource::new();
+        let runner = PhysicalPlanRunner::new(&ds);
+        // 2024-03-15T13:45:30Z, and a NULL timestamp.
+        let row = Rc::new(Row::new(
+            1,
+            vec![Value::DateTime(1_710_510_330_000), Value::Null],
+        ));
+        let eval = |expr: Expr| runner.eval_row(&expr, row.clone(), "events");
+        let ts = || Expr::column("events", "ts", 0);
+        let missing = || Expr::column("events", "missing", 1);
+
+        assert_eq!(
+            eval(Expr::date_trunc(Expr::literal("day"), ts())),
+            Value::DateTime(1_710_460_800_000)
+        );
+        assert_eq!(
+            eval(Expr::extract(Expr::literal("month"), ts())),
+            Value::Int64(3)
+        );
+        assert_eq!(
+            eval(Expr::date_trunc(Expr::literal("day"), missing())),
+            Value::Null
+        );
+        assert_eq!(eval(Expr::extract(Expr::literal("eon"), ts())), Value::Null);
+    }
+
+    #[test]
+    fn test_coercion_policy_for_string_number_comparisons() {
+        let mut ds = InMemoryDataSource::new();
+        ds.add_table(
+            "codes",
+            vec![
+                Row::new(1, vec![Value::String("5".into())]),
+                Row::new(2, vec![Value::String("12".into())]),
+                Row::new(3, vec![Value::String("abc".into())]),
+            ],
+            1,
+        );
+        let code = || Expr::column("codes", "code", 0);
+        let ids = |runner: &PhysicalPlanRunner<InMemoryDataSource>, plan: &PhysicalPlan| {
+            let artifact = runner.compile_execution_artifact_with_data_source(plan);
+            let compiled: Vec<RowId> = runner
+                .execute_with_artifact(plan, &artifact)
+                .unwrap()
+                .entries
+                .iter()
+                .map(|entry| entry.id())
+                .collect();
+            let interpreted: Vec<RowId> = runner
+                .execute(plan)
+                .unwrap()
+                .entries
+                .iter()
+                .map(|entry| entry.id())
+                .collect();
+            assert_eq!(compiled, interpreted);
+            interpreted
+        };
+        let eq_five = PhysicalPlan::filter(
+            PhysicalPlan::table_scan("codes"),
+            Expr::eq(code(), Expr::literal(Value::Int64(5))),
+        );
+        let above_six = PhysicalPlan::filter(
+            PhysicalPlan::table_scan("codes"),
+            Expr::lt(Expr::literal(Value::Float64(6.0)), code()),
+        );
+
+        let strict = PhysicalPlanRunner::new(&ds);
+        assert_eq!(strict.coercion_policy(), CoercionPolicy::Strict);
+        assert!(ids(&strict, &eq_five).is_empty());
+
+        let lenient = PhysicalPlanRunner::new(&ds).with_coercion_policy(CoercionPolicy::Lenient);
+        assert_eq!(ids(&lenient, &eq_five), vec![1]);
+        // "5" is not above six, and comparing "abc" against a number is NULL.
+        assert_eq!(ids(&lenient, &above_six), vec![2]);
+
+        let row = Rc::new(Row::new(1, vec![Value::String("5".into())]));
+        let five_eq = Expr::eq(Expr::literal(Value::Int32(5)), code());
+        assert_eq!(
+            strict.eval_row(&five_eq, row.clone(), "codes"),
+            Value::Boolean(false)
+        );
+        assert_eq!(
+            lenient.eval_row(&five_eq, row, "codes"),
+            Value::Boolean(true)
+        );
+    }
+
+    #[test]
+    fn test_sort_by_computed_expressions() {
+        let ds = create_test_data_source();
+        let runner = PhysicalPlanRunner::new(&ds);
+        let in_dept_10 = || {
+            Expr::eq(
+                Expr::column("users", "dept_id", 2),
+                Expr::literal(Value::Int64(10)),
+            )
+        };
+        let negated_id = || {
+            Expr::multiply(
+                Expr::column("users", "id", 0),
+                Expr::literal(Value::Int64(-1)),
+            )
+        };
+        let ids = |relation: Relation| -> Vec<RowId> {
+            relation.entries.iter().map(|entry| entry.id()).collect()
+        };
+
+        // Dept 10 first, then ids descending via the ascending negation.
+        let order_by = vec![
+            (in_dept_10(), SortOrder::Desc),
+            (negated_id(), SortOrder::Asc),
+        ];
+        let plan = PhysicalPlan::sort(PhysicalPlan::table_scan("users"), order_by.clone());
+        assert_eq!(ids(runner.execute(&plan).unwrap()), vec![3, 1, 2]);
+
+        let artifact = runner.compile_execution_artifact_with_data_source(&plan);
+        assert_eq!(
+            ids(runner.execute_with_artifact(&plan, &artifact).unwrap()),
+            vec![3, 1, 2]
+        );
+
+        let plan = PhysicalPlan::top_n(PhysicalPlan::table_scan("users"), order_by, 1, 1);
+        assert_eq!(ids(runner.execute(&plan).unwrap()), vec![1]);
+    }
+
+    #[test]
+    fn test_sort_by_jsonb_uses_logical_order() {
+        let docs = ["[2]", r#"{"k":0}"#, "12", r#""a""#, "false", "3"];
+        let mut ds = InMemoryDataSource::new();
+        ds.add_table(
+            "docs",
+            docs.iter()
+                .enumerate()
+                .map(|(i, doc)| {
+                    Row::new(
+                        i as RowId,
+                        vec![Value::Jsonb(cynos_core::JsonbValue(
+                            doc.as_bytes().to_vec(),
+                        ))],
+                    )
+                })
+                .collect(),
+            1,
+        );
+        let runner = PhysicalPlanRunner::new(&ds);
+        let ids = |relation: Relation| -> Vec<RowId> {
+            relation.entries.iter().map(|entry| entry.id()).collect()
+        };
+        let doc = || Expr::column("docs", "doc", 0);
+
+        // false < 3 < 12 < "a" < [2] < {"k":0}, descending.
+        let plan = PhysicalPlan::sort(
+            PhysicalPlan::table_scan("docs"),
+            vec![(doc(), SortOrder::Desc)],
+        );
+        assert_eq!(ids(runner.execute(&plan).unwrap()), vec![1, 0, 3, 2, 5, 4]);
+
+        // A computed JSONB key sorts the same way.
+        let to_jsonb = Expr::Function {
+            name: "TO_JSONB".into(),
+            args: vec![doc()],
+        };
+        let plan = PhysicalPlan::sort(
+            PhysicalPlan::table_scan("docs"),
+            vec![(to_jsonb, SortOrder::Asc)],
+        );
+        assert_eq!(ids(runner.execute(&plan).unwrap()), vec![4, 5, 2, 3, 0, 1]);
+    }
+
+    #[test]
+    fn test_split_part() {
+        let ds = InMemoryDataSource::new();
+        let runner = PhysicalPlanRunner::new(&ds);
+        let row = Rc::new(Row::new(1, vec![Value::String("a,b,,d".into())]));
+        let split = |s: Expr, delim: &str, n: i64| {
+            runner.eval_row(
+                &Expr::split_part(s, Expr::literal(delim), Expr::literal(n)),
+                row.clone(),
+                "csv",
+            )
+        };
+        let cell = || Expr::column("csv", "cell", 0);
+        let string = |s: &str| Value::String(s.into());
+
+        assert_eq!(split(cell(), ",", 1), string("a"));
+        assert_eq!(split(cell(), ",", 2), string("b"));
+        assert_eq!(split(cell(), ",", 3), string(""));
+        assert_eq!(split(cell(), ",", 4), string("d"));
+
+        // Out-of-range fields are empty.
+        assert_eq!(split(cell(), ",", 5), string(""));
+        assert_eq!(split(cell(), ",", 0), string(""));
+
+        // Multi-character delimiters split on the whole delimiter.
+        assert_eq!(
+            split(Expr::literal("one::two:three"), "::", 2),
+            string("two:three")
+        );
+        assert_eq!(split(Expr::literal("one::two"), "::", 3), string(""));
+
+        assert_eq!(split(Expr::literal(Value::Int64(12)), ",", 1), Value::Null);
+        assert_eq!(split(Expr::literal(Value::Null), ",", 1), Value::Null);
+    }
+
+    #[test]
+    fn test_eval_row() {
+        let ds = InMemoryDataSource::new();
+        let runner = PhysicalPlanRunner::new(&ds);
+        let row = Rc::new(Row::new(
+            1,
+            vec![
+                Value::String("Ada".into()),
+                Value::String("Lovelace".into()),
+            ],
+        ));
+        let full_name = Expr::concat(vec![
+            Expr::column("people", "first", 0),
+            Expr::literal(" "),
+            Expr::column("people", "last", 1),
+        ]);
+
+        assert_eq!(
+            runner.eval_row(&full_name, row, "people"),
+            Value::String("Ada Lovelace".into())
+        );
+    }
+
+    #[test]
+    fn test_project_power_and_bitwise_ops() {
+        let ds = create_test_data_source();
+        let runner = PhysicalPlanRunner::new(&ds);
+        let id = || Expr::column("users", "id", 0);
+        let dept_id = || Expr::column("users", "dept_id", 2);
+
+        let plan = PhysicalPlan::project(
+            PhysicalPlan::filter(
+                PhysicalPlan::table_scan("users"),
+                Expr::eq(id(), Expr::literal(Value::Int64(1))),
+            ),
+            vec![
+                Expr::power(dept_id(), Expr::literal(Value::Int64(2))),
+                Expr::power(dept_id(), Expr::literal(Value::Float64(0.5))),
+                Expr::bit_and(dept_id(), Expr::literal(Value::Int64(6))),
+                Expr::bit_or(dept_id(), Expr::literal(Value::Int64(5))),
+                Expr::bit_xor(dept_id(), Expr::literal(Value::Int64(15))),
+                Expr::shift_left(id(), Expr::literal(Value::Int64(3))),
+                Expr::shift_right(dept_id(), Expr::literal(Value::Int64(1))),
+                Expr::shift_left(id(), Expr::literal(Value::Int64(64))),
+            ],
+        );
+        let result = runner.execute(&plan).unwrap();
+        let fields: Vec<Value> = (0..8)
+            .map(|i| result.entries[0].get_field(i).cloned().unwrap())
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                Value::Int64(100),
+                Value::Float64(libm::sqrt(10.0)),
+                Value::Int64(2),
+                Value::Int64(15),
+                Value::Int64(5),
+                Value::Int64(8),
+                Value::Int64(5),
+                Value::Null,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_project_and_filter_cast() {
+        let ds = create_test_data_source();
+        let runner = PhysicalPlanRunner::new(&ds);
+        let name = || Expr::column("users", "name", 1);
+        let dept_id = || Expr::column("users", "dept_id", 2);
+
+        let plan = PhysicalPlan::project(
+            PhysicalPlan::filter(
+                PhysicalPlan::table_scan("users"),
+                Expr::eq(
+                    Expr::cast(dept_id(), DataType::String),
+                    Expr::literal(Value::String("20".into())),
+                ),
+            ),
+            vec![
+                Expr::cast(Expr::literal(Value::String("42".into())), DataType::Int64),
+                // Float-to-integer casts truncate toward zero.
+                Expr::cast(Expr::literal(Value::Float64(2.9)), DataType::Int64),
+                Expr::cast(Expr::literal(Value::Float64(-2.9)), DataType::Int32),
+                // "Bob" is not a number.
+                Expr::cast(name(), DataType::Int64),
+                Expr::cast(Expr::literal(Value::Float64(1e300)), DataType::Int64),
+            ],
+        );
+        let result = runner.execute(&plan).unwrap();
+        assert_eq!(result.entries.len(), 1);
+        let fields: Vec<Value> = (0..5)
+            .map(|i| result.entries[0].get_field(i).cloned().unwrap())
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                Value::Int64(42),
+                Value::Int64(2),
+                Value::Int32(-2),
+                Value::Null,
+                Value::Null,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cast_composes_with_arithmetic_and_comparisons() {
+        let ds = create_test_data_source();
+        let runner = PhysicalPlanRunner::new(&ds);
+        let id = || Expr::column("users", "id", 0);
+        let dept_id = || Expr::column("users", "dept_id", 2);
+
+        // WHERE CAST(dept_id AS Float64) > 15.5 keeps only Bob (dept 20).
+        let plan = PhysicalPlan::project(
+            PhysicalPlan::filter(
+                PhysicalPlan::table_scan("users"),
+                Expr::gt(
+                    Expr::cast(dept_id(), DataType::Float64),
+                    Expr::literal(Value::Float64(15.5)),
+                ),
+            ),
+            vec![
+                Expr::plus(
+                    Expr::cast(dept_id(), DataType::Int32),
+                    Expr::literal(Value::Int32(1)),
+                ),
+                Expr::cast(id(), DataType::String),
+                Expr::cast(Expr::literal(Value::Boolean(true)), DataType::Int64),
+                Expr::cast(Expr::literal(Value::Int64(0)), DataType::Boolean),
+                Expr::cast(Expr::literal(Value::Null), DataType::Int64),
+            ],
+        );
+        let result = runner.execute(&plan).unwrap();
+        assert_eq!(result.entries.len(), 1);
+        let fields: Vec<Value> = (0..5)
+            .map(|i| result.entries[0].get_field(i).cloned().unwrap())
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                Value::Int32(21),
+                Value::String("2".into()),
+                Value::Int64(1),
+                Value::Boolean(false),
+                Value::Null,
+            ]
+        );
+
+        assert_execution_artifact_matches(&plan);
+    }
+
+    #[test]
+    fn test_mixed_integer_arithmetic_promotes_to_int64() {
+        let ds = create_test_data_source();
+        let runner = PhysicalPlanRunner::new(&ds);
+        let id = || Expr::column("users", "id", 0);
+        let dept_id = || Expr::column("users", "dept_id", 2);
+
+        let plan = PhysicalPlan::project(
+            PhysicalPlan::filter(
+                PhysicalPlan::table_scan("users"),
+                Expr::eq(id(), Expr::literal(Value::Int64(1))),
+            ),
+            vec![
+                Expr::plus(dept_id(), Expr::literal(Value::Int32(5))),
+                Expr::multiply(Expr::literal(Value::Int32(3)), dept_id()),
+                Expr::modulo(dept_id(), Expr::literal(Value::Int32(3))),
+                Expr::plus(dept_id(), Expr::literal(Value::Float64(0.5))),
+                Expr::plus(
+                    Expr::literal(Value::Int32(1)),
+                    Expr::literal(Value::Int32(2)),
+                ),
+            ],
+        );
+        let result = runner.execute(&plan).unwrap();
+        let fields: Vec<Value> = (0..5)
+            .map(|i| result.entries[0].get_field(i).cloned().unwrap())
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                Value::Int64(15),
+                Value::Int64(30),
+                Value::Int64(1),
+                Value::Float64(10.5),
+                Value::Int32(3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_project_pow_and_floor_div() {
+        let ds = create_test_data_source();
+        let runner = PhysicalPlanRunner::new(&ds);
+        let id = || Expr::column("users", "id", 0);
+        let lit = |v: i64| Expr::literal(Value::Int64(v));
+
+        let plan = PhysicalPlan::project(
+            PhysicalPlan::filter(PhysicalPlan::table_scan("users"), Expr::eq(id(), lit(1))),
+            vec![
+                Expr::BinaryOp {
+                    left: Box::new(lit(2)),
+                    op: BinaryOp::Pow,
+                    right: Box::new(lit(10)),
+                },
+                Expr::floor_div(lit(-7), lit(2)),
+                Expr::floor_div(lit(7), lit(2)),
+                Expr::floor_div(id(), lit(0)),
+            ],
+        );
+        let result = runner.execute(&plan).unwrap();
+        let fields: Vec<Value> = (0..4)
+            .map(|i| result.entries[0].get_field(i).cloned().unwrap())
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                Value::Float64(1024.0),
+                Value::Int64(-4),
+                Value::Int64(3),
+                Value::Null,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_execution_artifact_matches_single_table_pipeline() {
+        let plan = PhysicalPlan::limit(
+            PhysicalPlan::project(
+                PhysicalPlan::filter(
+                    PhysicalPlan::table_scan("users"),
+                    Expr::eq(
+                        Expr::column("users", "dept_id", 2),
+                        Expr::literal(Value::Int64(10)),
+                    ),
+                ),
+                vec![Expr::column("users", "name", 1)],
+            ),
+            1,
+            1,
+        );
+
+        assert_execution_artifact_matches(&plan);
+    }
+
+    #[test]
+    fn test_execution_artifact_matches_offset_beyond_input() {
+        let plan = PhysicalPlan::limit(PhysicalPlan::table_scan("users"), 1_000_000_000, 10);
+
+        assert_execution_artifact_matches(&plan);
+    }
+
+    #[test]
+    fn test_hash_join() {
+        let ds = create_test_data_source();
+        let runner = PhysicalPlanRunner::new(&ds);
+
+        // Join users and departments on dept_id = id
+        let plan = PhysicalPlan::hash_join(
+            PhysicalPlan::table_scan("users"),
+            PhysicalPlan::table_scan("departments"),
+            Expr::eq(
+                Expr::column("users", "dept_id", 2),
+                Expr::column("departments", "id", 0),
+            ),
+            JoinType::Inner,
+        );
+        let result = runner.execute(&plan).unwrap();
+
+        // Alice and Charlie match Engineering (10), Bob matches Sales (20)
+        assert_eq!(result.len(), 3);
+        assert_eq!(result.tables(), &["users", "departments"]);
+    }
+
+    #[test]
+    fn test_hash_join_matches_across_integer_widths() {
+        let ds = create_cross_width_join_data_source();
+        let runner = PhysicalPlanRunner::new(&ds);
+
+        let plan = PhysicalPlan::hash_join(
+            PhysicalPlan::table_scan("users_mixed"),
+            PhysicalPlan::table_scan("departments_mixed"),
+            Expr::eq(
+                Expr::column("users_mixed", "dept_id", 2),
+                Expr::column("departments_mixed", "id", 0),
+            ),
+            JoinType::Inner,
         );
         let result = runner.execute(&plan).unwrap();
 
@@ -8832,6 +9913,68 @@ mod tests {
         assert_eq!(result.tables(), &["users_mixed", "departments_mixed"]);
     }
 
+    #[test]
+    fn test_null_safe_hash_join_matches_null_keys() {
+        let mut ds = InMemoryDataSource::new();
+        let key_rows = |keys: &[Option<i64>]| -> Vec<Row> {
+            keys.iter()
+                .enumerate()
+                .map(|(id, key)| {
+                    Row::new(
+                        id as u64,
+                        vec![
+                            Value::Int64(id as i64),
+                            key.map_or(Value::Null, Value::Int64),
+                        ],
+                    )
+                })
+                .collect()
+        };
+        ds.add_table("a", key_rows(&[Some(1), None, Some(2), None]), 2);
+        ds.add_table("b", key_rows(&[None, Some(1), Some(3)]), 2);
+        let runner = PhysicalPlanRunner::new(&ds);
+
+        let join = |condition: Expr, join_type: JoinType| {
+            PhysicalPlan::hash_join(
+                PhysicalPlan::table_scan("a"),
+                PhysicalPlan::table_scan("b"),
+                condition,
+                join_type,
+            )
+        };
+        let keys = || (Expr::column("a", "k", 1), Expr::column("b", "k", 1));
+
+        let (a, b) = keys();
+        let plain = join(Expr::eq(a, b), JoinType::Inner);
+        assert_eq!(runner.execute(&plain).unwrap().len(), 1);
+
+        // Both NULL-keyed rows of `a` pair with the NULL-keyed row of `b`.
+        let (a, b) = keys();
+        let null_safe = join(Expr::is_not_distinct_from(a, b), JoinType::Inner);
+        let expected = relation_snapshot(runner.execute(&null_safe).unwrap());
+        assert_eq!(expected.len(), 3);
+        let artifact = runner.compile_execution_artifact_with_data_source(&null_safe);
+        let compiled = runner.execute_with_artifact(&null_safe, &artifact).unwrap();
+        assert_eq!(relation_snapshot(compiled), expected);
+
+        let (a, b) = keys();
+        let nested = PhysicalPlan::nested_loop_join(
+            PhysicalPlan::table_scan("a"),
+            PhysicalPlan::table_scan("b"),
+            Expr::is_not_distinct_from(a, b),
+            JoinType::Inner,
+        );
+        assert_eq!(
+            relation_snapshot(runner.execute(&nested).unwrap()),
+            expected
+        );
+
+        // A left join keeps only the unmatched non-NULL key.
+        let (a, b) = keys();
+        let left = join(Expr::is_not_distinct_from(a, b), JoinType::LeftOuter);
+        assert_eq!(runner.execute(&left).unwrap().len(), 4);
+    }
+
     #[test]
     fn test_index_nested_loop_join_matches_across_integer_widths() {
         let mut ds = create_cross_width_join_data_source();
@@ -8857,6 +10000,154 @@ mod tests {
         assert_eq!(result.tables(), &["users_mixed", "departments_mixed"]);
     }
 
+    #[test]
+    fn test_index_nested_loop_semi_join_emits_each_parent_once() {
+        let ds = create_test_data_source();
+        let runner = PhysicalPlanRunner::new(&ds);
+
+        // Departments with at least one user; Engineering has two.
+        let plan = PhysicalPlan::IndexNestedLoopJoin {
+            outer: Box::new(PhysicalPlan::table_scan("departments")),
+            inner_table: "users".into(),
+            inner_index: "idx_dept".into(),
+            condition: Expr::eq(
+                Expr::column("departments", "id", 0),
+                Expr::column("users", "dept_id", 2),
+            ),
+            join_type: JoinType::Semi,
+            outer_is_left: true,
+            output_tables: alloc::vec!["departments".into()],
+        };
+
+        let result = runner.execute(&plan).unwrap();
+        assert_eq!(result.tables(), &["departments"]);
+        let names: Vec<&Value> = result
+            .entries
+            .iter()
+            .map(|entry| entry.get_field(1).unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            alloc::vec![
+                &Value::String("Engineering".into()),
+                &Value::String("Sales".into())
+            ]
+        );
+
+        assert_execution_artifact_matches(&plan);
+    }
+
+    #[test]
+    fn test_semi_join_without_index_is_rejected() {
+        let ds = create_test_data_source();
+        let runner = PhysicalPlanRunner::new(&ds);
+
+        let plan = PhysicalPlan::hash_join(
+            PhysicalPlan::table_scan("departments"),
+            PhysicalPlan::table_scan("users"),
+            Expr::eq(
+                Expr::column("departments", "id", 0),
+                Expr::column("users", "dept_id", 2),
+            ),
+            JoinType::Semi,
+        );
+
+        assert!(matches!(
+            runner.execute(&plan),
+            Err(ExecutionError::InvalidOperation(_))
+        ));
+    }
+
+    #[test]
+    fn test_zero_count_subquery_runs_as_anti_join() {
+        use crate::context::{ExecutionContext, IndexInfo, TableStats};
+        use crate::optimizer::Optimizer;
+        use crate::planner::{LogicalPlan, QueryPlanner};
+
+        let ds = create_test_data_source();
+        let runner = PhysicalPlanRunner::new(&ds);
+        let mut ctx = ExecutionContext::new();
+        ctx.register_table(
+            "departments",
+            TableStats {
+                row_count: 3,
+                is_sorted: true,
+                indexes: vec![IndexInfo::new("idx_id", vec!["id".into()], true)],
+            },
+        );
+        ctx.register_table(
+            "users",
+            TableStats {
+                row_count: 3,
+                is_sorted: true,
+                indexes: vec![IndexInfo::new("idx_dept", vec!["dept_id".into()], false)],
+            },
+        );
+
+        // SELECT d.id, d.name, 0 FROM departments d
+        // WHERE (SELECT COUNT(*) FROM users u WHERE u.dept_id = d.id) = 0,
+        // decorrelated into a grouped left join.
+        let plan = LogicalPlan::filter(
+            LogicalPlan::aggregate(
+                LogicalPlan::left_join(
+                    LogicalPlan::scan("departments"),
+                    LogicalPlan::scan("users"),
+                    Expr::eq(
+                        Expr::column("departments", "id", 0),
+                        Expr::column("users", "dept_id", 2),
+                    ),
+                ),
+                vec![
+                    Expr::column("departments", "id", 0),
+                    Expr::column("departments", "name", 1),
+                ],
+                vec![(
+                    AggregateFunc::Count,
+                    Expr::count(Expr::column("users", "dept_id", 2)),
+                )],
+            ),
+            Expr::eq(
+                Expr::column("departments", "count", 2),
+                Expr::literal(Value::Int64(0)),
+            ),
+        );
+
+        let physical = QueryPlanner::new(ctx).plan(plan.clone());
+        // No aggregate is left: the index lookup stops at the first user.
+        match &physical {
+            PhysicalPlan::Project { input, .. } => assert!(matches!(
+                input.as_ref(),
+                PhysicalPlan::IndexNestedLoopJoin {
+                    join_type: JoinType::Anti,
+                    inner_index,
+                    ..
+                } if inner_index == "idx_dept"
+            )),
+            other => panic!("expected Project over an anti-join, got {:?}", other),
+        }
+
+        let values = |relation: Relation| -> Vec<Vec<Value>> {
+            relation
+                .entries
+                .iter()
+                .map(|entry| entry.row.values().to_vec())
+                .collect()
+        };
+        let result = values(runner.execute(&physical).unwrap());
+        assert_eq!(
+            result,
+            vec![vec![
+                Value::Int64(30),
+                Value::String("Marketing".into()),
+                Value::Int64(0)
+            ]]
+        );
+        let counted = values(runner.execute(&Optimizer::new().to_physical(plan)).unwrap());
+        assert_eq!(result, counted);
+
+        assert_execution_artifact_matches(&physical);
+    }
+
     #[test]
     fn test_filter_column_equality_matches_across_integer_widths() {
         let ds = create_cross_width_filter_data_source();
@@ -9625,6 +10916,66 @@ mod tests {
         assert_eq!(result.entries[0].get_field(0), Some(&Value::Int64(3)));
     }
 
+    #[test]
+    fn test_count_star_over_index_scan_counts_index_entries() {
+        let ds = create_test_data_source();
+        let runner = PhysicalPlanRunner::new(&ds);
+        let count_over = |input: PhysicalPlan, aggregates| {
+            let plan = PhysicalPlan::hash_aggregate(input, vec![], aggregates);
+            assert_full_execution_artifact_matches(&plan);
+            runner.execute(&plan).unwrap().entries[0]
+                .row
+                .values()
+                .to_vec()
+        };
+        let dept_scan = |start: Option<i64>, end: Option<i64>| {
+            PhysicalPlan::index_scan(
+                "users",
+                "idx_dept",
+                start.map(Value::Int64),
+                end.map(Value::Int64),
+            )
+        };
+
+        // COUNT(*), COUNT(1) WHERE dept_id = 10
+        assert_eq!(
+            count_over(
+                dept_scan(Some(10), Some(10)),
+                vec![
+                    (AggregateFunc::Count, Expr::count_star()),
+                    (AggregateFunc::Count, Expr::literal(Value::Int64(1))),
+                ],
+            ),
+            vec![Value::Int64(2), Value::Int64(2)]
+        );
+        assert_eq!(
+            count_over(
+                dept_scan(None, None),
+                vec![(AggregateFunc::Count, Expr::count_star())]
+            ),
+            vec![Value::Int64(3)]
+        );
+        assert_eq!(
+            count_over(
+                dept_scan(Some(15), None),
+                vec![(AggregateFunc::Count, Expr::count_star())]
+            ),
+            vec![Value::Int64(1)]
+        );
+
+        // Other aggregates still read the rows.
+        assert_eq!(
+            count_over(
+                dept_scan(Some(10), Some(10)),
+                vec![
+                    (AggregateFunc::Count, Expr::count_star()),
+                    (AggregateFunc::Sum, Expr::column("users", "id", 0)),
+                ],
+            ),
+            vec![Value::Int64(2), Value::Int64(4)]
+        );
+    }
+
     #[test]
     fn test_aggregate_group_by() {
         let ds = create_test_data_source();
@@ -9642,6 +10993,177 @@ mod tests {
         assert_eq!(result.len(), 2);
     }
 
+    #[test]
+    fn test_aggregate_group_by_computed_expression() {
+        const DAY: i64 = 86_400_000;
+        let mut ds = InMemoryDataSource::new();
+        // (id, ts, amount): three events on day 0, one on day 1, two on day 3.
+        let events = [
+            (1, 10, 5),
+            (2, DAY - 1, 7),
+            (3, 3_600_000, 1),
+            (4, DAY + 5, 2),
+            (5, 3 * DAY, 4),
+            (6, 3 * DAY + 42, 6),
+        ]
+        .into_iter()
+        .map(|(id, ts, amount)| {
+            Row::new(
+                id,
+                vec![
+                    Value::Int64(id as i64),
+                    Value::DateTime(ts),
+                    Value::Int64(amount),
+                ],
+            )
+        })
+        .collect();
+        ds.add_table("events", events, 3);
+        let runner = PhysicalPlanRunner::new(&ds);
+
+        // GROUP BY DATE_TRUNC('day', ts), COUNT(*), SUM(amount)
+        let plan = PhysicalPlan::hash_aggregate(
+            PhysicalPlan::table_scan("events"),
+            vec![Expr::date_trunc(
+                Expr::literal("day"),
+                Expr::column("events", "ts", 1),
+            )],
+            vec![
+                (AggregateFunc::Count, Expr::column("events", "id", 0)),
+                (AggregateFunc::Sum, Expr::column("events", "amount", 2)),
+            ],
+        );
+
+        let mut buckets: Vec<_> = runner
+            .execute(&plan)
+            .unwrap()
+            .entries
+            .iter()
+            .map(|entry| {
+                (
+                    entry.get_field(0).cloned().unwrap(),
+                    entry.get_field(1).cloned().unwrap(),
+                )
+            })
+            .collect();
+        buckets.sort();
+        assert_eq!(
+            buckets,
+            vec![
+                (Value::DateTime(0), Value::Int64(3)),
+                (Value::DateTime(DAY), Value::Int64(1)),
+                (Value::DateTime(3 * DAY), Value::Int64(2)),
+            ]
+        );
+
+        // Computed keys mix with column keys, and the compiled path agrees.
+        let mixed = PhysicalPlan::hash_aggregate(
+            PhysicalPlan::table_scan("events"),
+            vec![
+                Expr::column("events", "amount", 2),
+                Expr::date_trunc(Expr::literal("day"), Expr::column("events", "ts", 1)),
+            ],
+            vec![(AggregateFunc::Count, Expr::column("events", "id", 0))],
+        );
+        let artifact = runner.compile_execution_artifact_with_data_source(&mixed);
+        let compiled = runner.execute_with_artifact(&mixed, &artifact).unwrap();
+        assert_eq!(compiled.len(), 6);
+        assert!(compiled.entries.iter().all(|entry| {
+            matches!(entry.get_field(1), Some(Value::DateTime(ts)) if ts % DAY == 0)
+                && entry.get_field(2) == Some(&Value::Int64(1))
+        }));
+    }
+
+    #[test]
+    fn test_aggregate_arg_max_returns_associated_column() {
+        let ds = create_test_data_source();
+        let runner = PhysicalPlanRunner::new(&ds);
+
+        // ARG_MAX(id, name), ARG_MIN(id, name) GROUP BY dept_id
+        let plan = PhysicalPlan::hash_aggregate(
+            PhysicalPlan::table_scan("users"),
+            vec![Expr::column("users", "dept_id", 2)],
+            vec![
+                (
+                    AggregateFunc::ArgMax,
+                    Expr::arg_max(
+                        Expr::column("users", "id", 0),
+                        Expr::column("users", "name", 1),
+                    ),
+                ),
+                (
+                    AggregateFunc::ArgMin,
+                    Expr::arg_min(
+                        Expr::column("users", "id", 0),
+                        Expr::column("users", "name", 1),
+                    ),
+                ),
+            ],
+        );
+        let result = runner.execute(&plan).unwrap();
+
+        let values: Vec<Vec<Value>> = result
+            .iter()
+            .map(|entry| {
+                (0..3)
+                    .map(|i| entry.get_field(i).unwrap().clone())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                vec![
+                    Value::Int64(10),
+                    Value::String("Charlie".into()),
+                    Value::String("Alice".into()),
+                ],
+                vec![
+                    Value::Int64(20),
+                    Value::String("Bob".into()),
+                    Value::String("Bob".into()),
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_aggregate_arg_max_evaluates_computed_arguments() {
+        let ds = create_test_data_source();
+        let runner = PhysicalPlanRunner::new(&ds);
+
+        // ARG_MAX(0 - id, name) picks the smallest id per department.
+        let negated_id = Expr::minus(Expr::literal(0i64), Expr::column("users", "id", 0));
+        let plan = PhysicalPlan::hash_aggregate(
+            PhysicalPlan::table_scan("users"),
+            vec![Expr::column("users", "dept_id", 2)],
+            vec![(
+                AggregateFunc::ArgMax,
+                Expr::arg_max(negated_id, Expr::column("users", "name", 1)),
+            )],
+        );
+        let result = runner.execute(&plan).unwrap();
+        let names: Vec<Value> = result
+            .iter()
+            .map(|entry| entry.get_field(1).unwrap().clone())
+            .collect();
+        assert_eq!(
+            names,
+            vec![Value::String("Alice".into()), Value::String("Bob".into())]
+        );
+
+        // Anything but a (key, value) pair is rejected, not read as column 0.
+        let malformed = PhysicalPlan::hash_aggregate(
+            PhysicalPlan::table_scan("users"),
+            vec![],
+            vec![(AggregateFunc::ArgMax, Expr::column("users", "name", 1))],
+        );
+        assert!(matches!(
+            runner.execute(&malformed),
+            Err(ExecutionError::InvalidOperation(_))
+        ));
+    }
+
     #[test]
     fn test_full_execution_artifact_matches_hash_aggregate() {
         let plan = PhysicalPlan::hash_aggregate(
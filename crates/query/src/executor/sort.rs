@@ -4,6 +4,8 @@ use crate::ast::SortOrder;
 use crate::executor::{Relation, RelationEntry};
 use alloc::vec::Vec;
 use core::cmp::Ordering;
+use cynos_core::Value;
+use cynos_jsonb::JsonSortKey;
 
 /// Sort executor - sorts rows by specified columns.
 pub struct SortExecutor {
@@ -19,31 +21,103 @@ impl SortExecutor {
 
     /// Executes the sort on the input relation.
     pub fn execute(&self, mut input: Relation) -> Relation {
-        input.entries.sort_by(|a, b| self.compare_entries(a, b));
+        let keys: Vec<JsonKeys> = input
+            .entries
+            .iter()
+            .map(|entry| JsonKeys::of_entry(&self.order_by, entry))
+            .collect();
+        if keys.iter().all(JsonKeys::is_empty) {
+            let none = JsonKeys::default();
+            input
+                .entries
+                .sort_by(|a, b| compare_entries(&self.order_by, (a, &none), (b, &none)));
+            return input;
+        }
+
+        let mut keyed: Vec<(JsonKeys, RelationEntry)> =
+            keys.into_iter().zip(input.entries).collect();
+        keyed.sort_by(|(a_keys, a), (b_keys, b)| {
+            compare_entries(&self.order_by, (a, a_keys), (b, b_keys))
+        });
+        input.entries = keyed.into_iter().map(|(_, entry)| entry).collect();
         input
     }
+}
 
-    fn compare_entries(&self, a: &RelationEntry, b: &RelationEntry) -> Ordering {
-        for (col_idx, order) in &self.order_by {
-            let a_val = a.get_field(*col_idx);
-            let b_val = b.get_field(*col_idx);
-
-            let cmp = match (a_val, b_val) {
-                (Some(av), Some(bv)) => av.cmp(bv),
-                (None, Some(_)) => Ordering::Less,
-                (Some(_), None) => Ordering::Greater,
-                (None, None) => Ordering::Equal,
-            };
+/// The JSONB documents among one row's sort keys, decoded once so that
+/// sorting compares them by content without re-parsing them on every
+/// comparison. Slot `i` holds the document of key `i`, if it is one; rows
+/// without JSONB keys allocate nothing.
+#[derive(Default)]
+pub(crate) struct JsonKeys(Vec<Option<JsonSortKey>>);
 
-            if cmp != Ordering::Equal {
-                return match order {
-                    SortOrder::Asc => cmp,
-                    SortOrder::Desc => cmp.reverse(),
-                };
+impl JsonKeys {
+    /// Decodes the JSONB values among `keys`.
+    pub(crate) fn decode<'a>(keys: impl IntoIterator<Item = Option<&'a Value>>) -> Self {
+        let mut decoded = Vec::new();
+        for (i, key) in keys.into_iter().enumerate() {
+            if let Some(Value::Jsonb(doc)) = key {
+                decoded.resize_with(i, || None);
+                decoded.push(Some(JsonSortKey::decode(&doc.0)));
             }
         }
-        Ordering::Equal
+        Self(decoded)
+    }
+
+    /// Decodes the JSONB values among `entry`'s `order_by` columns.
+    pub(crate) fn of_entry(order_by: &[(usize, SortOrder)], entry: &RelationEntry) -> Self {
+        Self::decode(order_by.iter().map(|(col, _)| entry.get_field(*col)))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn get(&self, key: usize) -> Option<&JsonSortKey> {
+        self.0.get(key).and_then(Option::as_ref)
+    }
+}
+
+/// Compares key number `key` of two rows. JSONB documents compare by their
+/// decoded content; all other values use `Value`'s own ordering.
+pub(crate) fn compare_key(
+    key: usize,
+    (a, a_json): (Option<&Value>, &JsonKeys),
+    (b, b_json): (Option<&Value>, &JsonKeys),
+) -> Ordering {
+    if let (Some(a), Some(b)) = (a_json.get(key), b_json.get(key)) {
+        return a.cmp(b);
+    }
+    match (a, b) {
+        (Some(av), Some(bv)) => av.cmp(bv),
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Compares two entries by the given column indices and sort orders, the
+/// ordering shared by `SortExecutor` and `TopNExecutor`. Each entry comes
+/// with its decoded JSONB keys.
+pub(crate) fn compare_entries(
+    order_by: &[(usize, SortOrder)],
+    (a, a_json): (&RelationEntry, &JsonKeys),
+    (b, b_json): (&RelationEntry, &JsonKeys),
+) -> Ordering {
+    for (key, (col_idx, order)) in order_by.iter().enumerate() {
+        let cmp = compare_key(
+            key,
+            (a.get_field(*col_idx), a_json),
+            (b.get_field(*col_idx), b_json),
+        );
+        if cmp != Ordering::Equal {
+            return match order {
+                SortOrder::Asc => cmp,
+                SortOrder::Desc => cmp.reverse(),
+            };
+        }
     }
+    Ordering::Equal
 }
 
 /// Sorts a relation by a key function.
@@ -108,6 +182,91 @@ mod tests {
         assert_eq!(result.entries[2].get_field(0), Some(&Value::Int64(10)));
     }
 
+    #[test]
+    fn test_sort_executor_orders_jsonb_logically() {
+        let docs = [
+            r#"{"a":1}"#,
+            "[1,2]",
+            r#""text""#,
+            "10",
+            "null",
+            "[1]",
+            "true",
+            "9",
+            r#"{"a":1,"b":2}"#,
+            "[]",
+        ];
+        let rows = docs
+            .iter()
+            .enumerate()
+            .map(|(i, doc)| {
+                Rc::new(Row::new(
+                    i as u64,
+                    vec![Value::Jsonb(cynos_core::JsonbValue::new(
+                        doc.as_bytes().to_vec(),
+                    ))],
+                ))
+            })
+            .collect();
+        let input = Relation::from_rows(rows, vec!["t".into()]);
+
+        let result = SortExecutor::new(vec![(0, SortOrder::Asc)]).execute(input);
+        let sorted: Vec<&str> = result
+            .entries
+            .iter()
+            .map(|entry| match entry.get_field(0) {
+                Some(Value::Jsonb(doc)) => core::str::from_utf8(&doc.0).unwrap(),
+                other => panic!("expected JSONB, got {:?}", other),
+            })
+            .collect();
+
+        // Byte order would put "10" before "9" and "[1,2]" before "true".
+        assert_eq!(
+            sorted,
+            vec![
+                "null",
+                "true",
+                "9",
+                "10",
+                r#""text""#,
+                "[]",
+                "[1]",
+                "[1,2]",
+                r#"{"a":1}"#,
+                r#"{"a":1,"b":2}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sort_executor_orders_text_and_binary_jsonb_together() {
+        let text = |doc: &str| doc.as_bytes().to_vec();
+        let binary = |value| cynos_jsonb::JsonbBinary::encode(&value).into_bytes();
+        let docs = [
+            text("10"),
+            binary(cynos_jsonb::JsonbValue::Number(9.5)),
+            text("{oops"),
+            text("9"),
+            binary(cynos_jsonb::JsonbValue::Bool(true)),
+        ];
+        let rows = docs
+            .iter()
+            .enumerate()
+            .map(|(i, doc)| {
+                Rc::new(Row::new(
+                    i as u64,
+                    vec![Value::Jsonb(cynos_core::JsonbValue::new(doc.clone()))],
+                ))
+            })
+            .collect();
+        let input = Relation::from_rows(rows, vec!["t".into()]);
+
+        let result = SortExecutor::new(vec![(0, SortOrder::Asc)]).execute(input);
+        let ids: Vec<u64> = result.entries.iter().map(|entry| entry.id()).collect();
+        // true < 9 < 9.5 < 10, then the undecodable document.
+        assert_eq!(ids, vec![4, 3, 1, 0, 2]);
+    }
+
     #[test]
     fn test_sort_executor_multi_column() {
         let rows = vec![
@@ -0,0 +1,191 @@
+//! TopN executor.
+
+use crate::ast::SortOrder;
+use crate::executor::sort::{compare_entries, JsonKeys};
+use crate::executor::{Relation, RelationEntry};
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// TopN executor - returns the first `limit` rows after `offset` in sort
+/// order without sorting the whole input.
+///
+/// Keeps a bounded max-heap of the best `limit + offset` rows seen so far,
+/// so the cost is O(n log k) time and O(k) space. Ties keep input order,
+/// making the output identical to a stable sort followed by a limit.
+pub struct TopNExecutor {
+    /// Column indices and sort orders.
+    order_by: Vec<(usize, SortOrder)>,
+    limit: usize,
+    offset: usize,
+}
+
+impl TopNExecutor {
+    /// Creates a new TopN executor.
+    pub fn new(order_by: Vec<(usize, SortOrder)>, limit: usize, offset: usize) -> Self {
+        Self {
+            order_by,
+            limit,
+            offset,
+        }
+    }
+
+    /// Executes the TopN on the input relation.
+    pub fn execute(&self, input: Relation) -> Relation {
+        let tables = input.tables().to_vec();
+        let table_column_counts = input.table_column_counts().to_vec();
+        let k = self.limit.saturating_add(self.offset);
+
+        if k == 0 || self.limit == 0 {
+            return Relation::from_entries(Vec::new(), tables, table_column_counts);
+        }
+
+        let mut heap: BinaryHeap<HeapEntry> =
+            BinaryHeap::with_capacity(k.min(input.len()).saturating_add(1));
+
+        for (seq, entry) in input.into_iter().enumerate() {
+            let candidate = HeapEntry {
+                json: JsonKeys::of_entry(&self.order_by, &entry),
+                entry,
+                seq,
+                order_by: &self.order_by,
+            };
+            if heap.len() < k {
+                heap.push(candidate);
+            } else if let Some(mut worst) = heap.peek_mut() {
+                // A later row never displaces an equal one, which keeps ties
+                // in input order.
+                if candidate < *worst {
+                    *worst = candidate;
+                }
+            }
+        }
+
+        let entries: Vec<RelationEntry> = heap
+            .into_sorted_vec()
+            .into_iter()
+            .skip(self.offset)
+            .map(|e| e.entry)
+            .collect();
+
+        Relation::from_entries(entries, tables, table_column_counts)
+    }
+}
+
+/// Heap entry ordered by the sort keys, then by input position, so the heap
+/// top is the row that would come last among those kept.
+struct HeapEntry<'a> {
+    entry: RelationEntry,
+    json: JsonKeys,
+    seq: usize,
+    order_by: &'a [(usize, SortOrder)],
+}
+
+impl PartialEq for HeapEntry<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for HeapEntry<'_> {}
+
+impl PartialOrd for HeapEntry<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeapEntry<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_entries(
+            self.order_by,
+            (&self.entry, &self.json),
+            (&other.entry, &other.json),
+        )
+        .then(self.seq.cmp(&other.seq))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::{LimitExecutor, SortExecutor};
+    use alloc::rc::Rc;
+    use alloc::vec;
+    use cynos_core::{Row, Value};
+
+    /// Rows with many duplicate keys in a scrambled order, plus some NULLs.
+    fn scrambled_rows(n: u64) -> Vec<Rc<Row>> {
+        (0..n)
+            .map(|i| {
+                let key = (i * 7919) % 37;
+                let group = if key % 11 == 0 {
+                    Value::Null
+                } else {
+                    Value::Int64((key % 5) as i64)
+                };
+                Rc::new(Row::new(i, vec![group, Value::Int64(key as i64)]))
+            })
+            .collect()
+    }
+
+    fn row_ids(relation: &Relation) -> Vec<u64> {
+        relation.entries.iter().map(|e| e.id()).collect()
+    }
+
+    #[test]
+    fn test_topn_matches_sort_then_limit() {
+        let rows = scrambled_rows(500);
+        let orders = [
+            vec![(0, SortOrder::Asc)],
+            vec![(0, SortOrder::Desc)],
+            vec![(0, SortOrder::Asc), (1, SortOrder::Desc)],
+            vec![(1, SortOrder::Desc), (0, SortOrder::Asc)],
+        ];
+
+        for order_by in orders {
+            for (limit, offset) in [(1, 0), (10, 0), (10, 25), (100, 450), (1000, 0), (0, 5)] {
+                let expected = LimitExecutor::new(limit, offset).execute(
+                    SortExecutor::new(order_by.clone())
+                        .execute(Relation::from_rows(rows.clone(), vec!["t".into()])),
+                );
+                let actual = TopNExecutor::new(order_by.clone(), limit, offset)
+                    .execute(Relation::from_rows(rows.clone(), vec!["t".into()]));
+
+                assert_eq!(
+                    row_ids(&actual),
+                    row_ids(&expected),
+                    "order_by={:?} limit={} offset={}",
+                    order_by,
+                    limit,
+                    offset
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_topn_keeps_ties_in_input_order() {
+        let rows = vec![
+            Rc::new(Row::new(1, vec![Value::Int64(2)])),
+            Rc::new(Row::new(2, vec![Value::Int64(1)])),
+            Rc::new(Row::new(3, vec![Value::Int64(2)])),
+            Rc::new(Row::new(4, vec![Value::Int64(1)])),
+            Rc::new(Row::new(5, vec![Value::Int64(2)])),
+        ];
+        let input = Relation::from_rows(rows, vec!["t".into()]);
+
+        let result = TopNExecutor::new(vec![(0, SortOrder::Desc)], 2, 0).execute(input);
+
+        assert_eq!(row_ids(&result), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_topn_offset_past_end() {
+        let input = Relation::from_rows(scrambled_rows(10), vec!["t".into()]);
+
+        let result = TopNExecutor::new(vec![(1, SortOrder::Asc)], 5, 20).execute(input);
+
+        assert!(result.is_empty());
+    }
+}
@@ -3,6 +3,7 @@
 //! This crate provides the query execution engine including:
 //!
 //! - `ast`: Expression and predicate AST definitions
+//! - `check`: CHECK constraint predicates built from expressions
 //! - `planner`: Logical and physical query plans
 //! - `optimizer`: Query optimization passes
 //! - `executor`: Query execution operators (scan, filter, project, join, aggregate, sort, limit)
@@ -14,6 +15,7 @@
 extern crate alloc;
 
 pub mod ast;
+pub mod check;
 pub mod context;
 pub mod executor;
 pub mod optimizer;
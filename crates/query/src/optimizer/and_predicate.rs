@@ -62,12 +62,14 @@ impl AndPredicatePass {
                 condition,
                 join_type,
                 output_tables,
+                algorithm,
             } => LogicalPlan::Join {
                 left: Box::new(self.traverse(*left)),
                 right: Box::new(self.traverse(*right)),
                 condition,
                 join_type,
                 output_tables,
+                algorithm,
             },
 
             LogicalPlan::Aggregate {
@@ -111,6 +113,7 @@ impl AndPredicatePass {
             | LogicalPlan::IndexScan { .. }
             | LogicalPlan::IndexGet { .. }
             | LogicalPlan::IndexInGet { .. }
+            | LogicalPlan::FetchByIds { .. }
             | LogicalPlan::GinIndexScan { .. }
             | LogicalPlan::GinIndexScanMulti { .. }
             | LogicalPlan::Empty) => plan,
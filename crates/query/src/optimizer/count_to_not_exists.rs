@@ -0,0 +1,437 @@
+//! COUNT-equals-zero to NOT EXISTS optimization pass.
+//!
+//! `WHERE (SELECT COUNT(*) FROM r WHERE r.k = l.k) = 0` decorrelates into a
+//! left outer join that counts every match per left row and then keeps the
+//! rows whose count is zero:
+//!
+//! ```text
+//! Filter(count = 0)                        Project(l.id, l.name, 0)
+//!        |                                          |
+//! Aggregate(GROUP BY l.id, l.name;    =>    AntiJoin(l, r, l.k = r.k)
+//!           COUNT(r.k))
+//!        |
+//! LeftJoin(l, r, l.k = r.k)
+//! ```
+//!
+//! The anti-join form asks the same question without counting: its index
+//! lookup stops at the first match.
+//!
+//! The rewrite only applies when it is exact and the anti-join can run:
+//! - the join is an un-hinted left outer equi-join whose right side is a
+//!   plain scan with an index on its join column
+//! - the count is over the right join column, which is non-NULL exactly
+//!   for matched rows
+//! - the groups are left columns covering the left table's primary key, so
+//!   each group is one left row
+//!
+//! The planner runs this pass on every query, but the JS query builder
+//! always filters below its aggregate and has no HAVING clause, so it never
+//! produces the `Filter` over `Aggregate` shape above. Only hand-built
+//! logical plans, such as those from embedders of `cynos-query`, reach the
+//! rewrite today.
+
+use crate::ast::{AggregateFunc, BinaryOp, ColumnRef, Expr, JoinType};
+use crate::context::ExecutionContext;
+use crate::optimizer::OptimizerPass;
+use crate::planner::LogicalPlan;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use cynos_core::Value;
+
+/// Rewrites `COUNT(matches) = 0` over a left outer join into an anti-join.
+pub struct CountToNotExists<'a> {
+    ctx: &'a ExecutionContext,
+}
+
+impl<'a> CountToNotExists<'a> {
+    /// Creates a new pass using `ctx` for key and index information.
+    pub fn new(ctx: &'a ExecutionContext) -> Self {
+        Self { ctx }
+    }
+}
+
+impl OptimizerPass for CountToNotExists<'_> {
+    fn optimize(&self, plan: LogicalPlan) -> LogicalPlan {
+        self.rewrite(plan)
+    }
+
+    fn name(&self) -> &'static str {
+        "count_to_not_exists"
+    }
+}
+
+impl CountToNotExists<'_> {
+    fn rewrite(&self, plan: LogicalPlan) -> LogicalPlan {
+        match plan {
+            LogicalPlan::Filter { input, predicate } => {
+                let input = self.rewrite(*input);
+                if self.is_zero_count_filter(&predicate, &input) {
+                    if let LogicalPlan::Aggregate {
+                        input: join,
+                        group_by,
+                        ..
+                    } = input
+                    {
+                        return Self::anti_join(*join, group_by);
+                    }
+                }
+                LogicalPlan::Filter {
+                    input: Box::new(input),
+                    predicate,
+                }
+            }
+
+            LogicalPlan::Project { input, columns } => LogicalPlan::Project {
+                input: Box::new(self.rewrite(*input)),
+                columns,
+            },
+
+            LogicalPlan::Join {
+                left,
+                right,
+                condition,
+                join_type,
+                output_tables,
+                algorithm,
+            } => LogicalPlan::Join {
+                left: Box::new(self.rewrite(*left)),
+                right: Box::new(self.rewrite(*right)),
+                condition,
+                join_type,
+                output_tables,
+                algorithm,
+            },
+
+            LogicalPlan::Aggregate {
+                input,
+                group_by,
+                aggregates,
+            } => LogicalPlan::Aggregate {
+                input: Box::new(self.rewrite(*input)),
+                group_by,
+                aggregates,
+            },
+
+            LogicalPlan::Sort { input, order_by } => LogicalPlan::Sort {
+                input: Box::new(self.rewrite(*input)),
+                order_by,
+            },
+
+            LogicalPlan::Limit {
+                input,
+                limit,
+                offset,
+            } => LogicalPlan::Limit {
+                input: Box::new(self.rewrite(*input)),
+                limit,
+                offset,
+            },
+
+            LogicalPlan::CrossProduct { left, right } => LogicalPlan::CrossProduct {
+                left: Box::new(self.rewrite(*left)),
+                right: Box::new(self.rewrite(*right)),
+            },
+
+            LogicalPlan::Union { left, right, all } => LogicalPlan::Union {
+                left: Box::new(self.rewrite(*left)),
+                right: Box::new(self.rewrite(*right)),
+                all,
+            },
+
+            // Leaf nodes
+            LogicalPlan::Scan { .. }
+            | LogicalPlan::IndexScan { .. }
+            | LogicalPlan::IndexGet { .. }
+            | LogicalPlan::IndexInGet { .. }
+            | LogicalPlan::FetchByIds { .. }
+            | LogicalPlan::GinIndexScan { .. }
+            | LogicalPlan::GinIndexScanMulti { .. }
+            | LogicalPlan::Empty => plan,
+        }
+    }
+
+    /// Returns true if `Filter(predicate, input)` keeps the left rows of a
+    /// left outer join that have no match, and can run as an anti-join.
+    /// The index on the right key is what lets the anti-join run:
+    /// `IndexJoinPass` turns it into an index nested loop join even when the
+    /// plan pins the algorithm of some other join.
+    fn is_zero_count_filter(&self, predicate: &Expr, input: &LogicalPlan) -> bool {
+        let LogicalPlan::Aggregate {
+            input: join,
+            group_by,
+            aggregates,
+        } = input
+        else {
+            return false;
+        };
+        let LogicalPlan::Join {
+            left,
+            right,
+            condition,
+            join_type: JoinType::LeftOuter,
+            algorithm: None,
+            ..
+        } = join.as_ref()
+        else {
+            return false;
+        };
+        let LogicalPlan::Scan { table: right_table } = right.as_ref() else {
+            return false;
+        };
+        let Some(left_table) = Self::single_table(left) else {
+            return false;
+        };
+        let Some(right_key) = Self::right_join_column(condition, &left_table, right_table) else {
+            return false;
+        };
+
+        Self::compares_count_to_zero(predicate, group_by.len())
+            && Self::counts_column(aggregates, right_key)
+            && self.groups_by_primary_key(&left_table, group_by)
+            && self
+                .ctx
+                .find_index(right_table, &[right_key.column.as_str()])
+                .is_some_and(|index| !index.is_gin())
+    }
+
+    /// Replaces the aggregate over `join` with an anti-join that outputs the
+    /// same group columns and a zero count.
+    fn anti_join(join: LogicalPlan, group_by: Vec<Expr>) -> LogicalPlan {
+        let LogicalPlan::Join {
+            left,
+            right,
+            condition,
+            ..
+        } = join
+        else {
+            unreachable!("checked by is_zero_count_filter");
+        };
+        let mut columns = group_by;
+        columns.push(Expr::literal(Value::Int64(0)));
+        LogicalPlan::project(
+            LogicalPlan::join(*left, *right, condition, JoinType::Anti),
+            columns,
+        )
+    }
+
+    /// Returns true if `predicate` is `count = 0` for the single aggregate
+    /// output column, which follows the `group_width` group columns.
+    fn compares_count_to_zero(predicate: &Expr, group_width: usize) -> bool {
+        let Expr::BinaryOp {
+            left,
+            op: BinaryOp::Eq,
+            right,
+        } = predicate
+        else {
+            return false;
+        };
+        let is_count = |expr: &Expr| matches!(expr, Expr::Column(col) if col.index == group_width);
+        let is_zero = |expr: &Expr| {
+            matches!(
+                expr,
+                Expr::Literal(Value::Int32(0)) | Expr::Literal(Value::Int64(0))
+            )
+        };
+        (is_count(left) && is_zero(right)) || (is_zero(left) && is_count(right))
+    }
+
+    /// Returns true if the only aggregate is `COUNT(column)`, which is
+    /// non-zero exactly when some row matched.
+    fn counts_column(aggregates: &[(AggregateFunc, Expr)], column: &ColumnRef) -> bool {
+        let [(AggregateFunc::Count, expr)] = aggregates else {
+            return false;
+        };
+        let counted = match expr {
+            Expr::Aggregate {
+                expr: Some(inner), ..
+            } => inner.as_ref(),
+            Expr::Aggregate { expr: None, .. } => return false,
+            other => other,
+        };
+        matches!(counted, Expr::Column(col) if col.table == column.table && col.index == column.index)
+    }
+
+    /// Returns true if the groups are columns of `table` covering its
+    /// primary key, so each group is exactly one row.
+    fn groups_by_primary_key(&self, table: &str, group_by: &[Expr]) -> bool {
+        let mut grouped = Vec::with_capacity(group_by.len());
+        for expr in group_by {
+            match expr {
+                Expr::Column(col) if col.table == table => grouped.push(col.column.as_str()),
+                _ => return false,
+            }
+        }
+        self.ctx
+            .find_primary_index(table)
+            .is_some_and(|pk| pk.columns.iter().all(|c| grouped.contains(&c.as_str())))
+    }
+
+    /// Returns the one table `plan` reads, if it is a single-table plan.
+    fn single_table(plan: &LogicalPlan) -> Option<String> {
+        match plan.collect_tables().as_slice() {
+            [table] => Some(table.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the right-table column of an equi-join condition between
+    /// `left_table` and `right_table`.
+    fn right_join_column<'e>(
+        condition: &'e Expr,
+        left_table: &str,
+        right_table: &str,
+    ) -> Option<&'e ColumnRef> {
+        let Expr::BinaryOp {
+            left,
+            op: BinaryOp::Eq,
+            right,
+        } = condition
+        else {
+            return None;
+        };
+        let (Expr::Column(a), Expr::Column(b)) = (left.as_ref(), right.as_ref()) else {
+            return None;
+        };
+        if a.table == left_table && b.table == right_table {
+            Some(b)
+        } else if b.table == left_table && a.table == right_table {
+            Some(a)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::{IndexInfo, TableStats};
+    use alloc::vec;
+
+    fn create_test_context() -> ExecutionContext {
+        let mut ctx = ExecutionContext::new();
+        ctx.register_table(
+            "departments",
+            TableStats {
+                row_count: 3,
+                is_sorted: true,
+                indexes: vec![IndexInfo::new("idx_id", vec!["id".into()], true)],
+            },
+        );
+        ctx.register_table(
+            "users",
+            TableStats {
+                row_count: 100,
+                is_sorted: false,
+                indexes: vec![IndexInfo::new("idx_dept", vec!["dept_id".into()], false)],
+            },
+        );
+        ctx.register_table(
+            "notes",
+            TableStats {
+                row_count: 100,
+                is_sorted: false,
+                indexes: vec![],
+            },
+        );
+        ctx
+    }
+
+    /// Departments with no rows in `right`, as the decorrelated form of
+    /// `(SELECT COUNT(*) FROM right WHERE right.dept_id = d.id) = 0`.
+    fn zero_count_plan(right: &str, count: Expr, group_by: Vec<Expr>) -> LogicalPlan {
+        let width = group_by.len();
+        LogicalPlan::filter(
+            LogicalPlan::aggregate(
+                LogicalPlan::left_join(
+                    LogicalPlan::scan("departments"),
+                    LogicalPlan::scan(right),
+                    Expr::eq(
+                        Expr::column("departments", "id", 0),
+                        Expr::column(right, "dept_id", 2),
+                    ),
+                ),
+                group_by,
+                vec![(AggregateFunc::Count, count)],
+            ),
+            Expr::eq(
+                Expr::column("departments", "count", width),
+                Expr::literal(Value::Int64(0)),
+            ),
+        )
+    }
+
+    fn dept_columns() -> Vec<Expr> {
+        vec![
+            Expr::column("departments", "id", 0),
+            Expr::column("departments", "name", 1),
+        ]
+    }
+
+    #[test]
+    fn test_zero_count_becomes_anti_join() {
+        let ctx = create_test_context();
+        let plan = zero_count_plan(
+            "users",
+            Expr::count(Expr::column("users", "dept_id", 2)),
+            dept_columns(),
+        );
+
+        match CountToNotExists::new(&ctx).optimize(plan) {
+            LogicalPlan::Project { input, columns } => {
+                assert_eq!(columns.len(), 3);
+                assert!(matches!(columns[2], Expr::Literal(Value::Int64(0))));
+                match *input {
+                    LogicalPlan::Join {
+                        join_type,
+                        output_tables,
+                        ..
+                    } => {
+                        assert_eq!(join_type, JoinType::Anti);
+                        assert_eq!(output_tables, vec![String::from("departments")]);
+                    }
+                    other => panic!("expected an anti-join, got {:?}", other),
+                }
+            }
+            other => panic!("expected Project over an anti-join, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_zero_count_kept_when_rewrite_is_inexact() {
+        let ctx = create_test_context();
+        let pass = CountToNotExists::new(&ctx);
+        let cases = [
+            // COUNT(*) counts the NULL-extended row of an unmatched department.
+            zero_count_plan("users", Expr::count_star(), dept_columns()),
+            // Counting a column that may be NULL in matched rows.
+            zero_count_plan(
+                "users",
+                Expr::count(Expr::column("users", "name", 1)),
+                dept_columns(),
+            ),
+            // Groups that may merge several departments.
+            zero_count_plan(
+                "users",
+                Expr::count(Expr::column("users", "dept_id", 2)),
+                vec![Expr::column("departments", "name", 1)],
+            ),
+            // No index to stop at the first match.
+            zero_count_plan(
+                "notes",
+                Expr::count(Expr::column("notes", "dept_id", 2)),
+                dept_columns(),
+            ),
+        ];
+
+        for plan in cases {
+            match pass.optimize(plan) {
+                LogicalPlan::Filter { input, .. } => {
+                    assert!(matches!(*input, LogicalPlan::Aggregate { .. }))
+                }
+                other => panic!("expected the plan unchanged, got {:?}", other),
+            }
+        }
+    }
+}
@@ -76,12 +76,14 @@ impl CrossProductPass {
                 condition,
                 join_type,
                 output_tables,
+                algorithm,
             } => LogicalPlan::Join {
                 left: Box::new(self.traverse(*left)),
                 right: Box::new(self.traverse(*right)),
                 condition,
                 join_type,
                 output_tables,
+                algorithm,
             },
 
             LogicalPlan::Aggregate {
@@ -120,6 +122,7 @@ impl CrossProductPass {
             | LogicalPlan::IndexScan { .. }
             | LogicalPlan::IndexGet { .. }
             | LogicalPlan::IndexInGet { .. }
+            | LogicalPlan::FetchByIds { .. }
             | LogicalPlan::GinIndexScan { .. }
             | LogicalPlan::GinIndexScanMulti { .. }
             | LogicalPlan::Empty) => plan,
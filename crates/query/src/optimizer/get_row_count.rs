@@ -131,6 +131,7 @@ impl<'a> GetRowCountPass<'a> {
                 condition,
                 join_type,
                 output_tables,
+                pinned,
             } => {
                 let (left_opt, _) = self.traverse(*left);
                 let (right_opt, _) = self.traverse(*right);
@@ -141,6 +142,7 @@ impl<'a> GetRowCountPass<'a> {
                         condition,
                         join_type,
                         output_tables,
+                        pinned,
                     },
                     None,
                 )
@@ -173,6 +175,7 @@ impl<'a> GetRowCountPass<'a> {
                 condition,
                 join_type,
                 output_tables,
+                pinned,
             } => {
                 let (left_opt, _) = self.traverse(*left);
                 let (right_opt, _) = self.traverse(*right);
@@ -183,6 +186,7 @@ impl<'a> GetRowCountPass<'a> {
                         condition,
                         join_type,
                         output_tables,
+                        pinned,
                     },
                     None,
                 )
@@ -270,6 +274,7 @@ impl<'a> GetRowCountPass<'a> {
             | PhysicalPlan::IndexScan { .. }
             | PhysicalPlan::IndexGet { .. }
             | PhysicalPlan::IndexInGet { .. }
+            | PhysicalPlan::FetchByIds { .. }
             | PhysicalPlan::GinIndexScan { .. }
             | PhysicalPlan::GinIndexScanMulti { .. }
             | PhysicalPlan::Empty) => (plan, None),
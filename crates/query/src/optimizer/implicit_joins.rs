@@ -15,6 +15,12 @@
 //! This transformation is important because:
 //! 1. Join nodes can use optimized join algorithms (hash join, merge join)
 //! 2. It enables further optimizations like index join selection
+//!
+//! The reverse also applies: an inner join whose condition does not relate
+//! its two sides (usually a mistyped join condition) pairs every row of one
+//! side with every row of the other, so it is rewritten to a Filter over a
+//! CrossProduct. Predicate pushdown can then move the condition to the side
+//! it actually references.
 
 use crate::ast::{Expr, JoinType};
 use crate::optimizer::OptimizerPass;
@@ -54,6 +60,7 @@ impl ImplicitJoinsPass {
                             condition: predicate,
                             join_type: JoinType::Inner,
                             output_tables,
+                            algorithm: None,
                         };
                     }
                 }
@@ -81,13 +88,31 @@ impl ImplicitJoinsPass {
                 condition,
                 join_type,
                 output_tables,
-            } => LogicalPlan::Join {
-                left: Box::new(self.traverse(*left)),
-                right: Box::new(self.traverse(*right)),
-                condition,
-                join_type,
-                output_tables,
-            },
+                algorithm,
+            } => {
+                let left = self.traverse(*left);
+                let right = self.traverse(*right);
+                if join_type == JoinType::Inner
+                    && !self.is_join_predicate(&condition, &left, &right)
+                {
+                    let mut cross_tables = left.output_tables();
+                    cross_tables.extend(right.output_tables());
+                    if cross_tables == output_tables {
+                        return LogicalPlan::filter(
+                            LogicalPlan::cross_product(left, right),
+                            condition,
+                        );
+                    }
+                }
+                LogicalPlan::Join {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    condition,
+                    join_type,
+                    output_tables,
+                    algorithm,
+                }
+            }
 
             LogicalPlan::Aggregate {
                 input,
@@ -125,6 +150,7 @@ impl ImplicitJoinsPass {
             | LogicalPlan::IndexScan { .. }
             | LogicalPlan::IndexGet { .. }
             | LogicalPlan::IndexInGet { .. }
+            | LogicalPlan::FetchByIds { .. }
             | LogicalPlan::GinIndexScan { .. }
             | LogicalPlan::GinIndexScanMulti { .. }
             | LogicalPlan::Empty) => plan,
@@ -133,7 +159,12 @@ impl ImplicitJoinsPass {
 
     /// Checks if the predicate is a join predicate that references both sides
     /// of the cross product.
-    fn is_join_predicate(&self, predicate: &Expr, left: &LogicalPlan, right: &LogicalPlan) -> bool {
+    pub fn is_join_predicate(
+        &self,
+        predicate: &Expr,
+        left: &LogicalPlan,
+        right: &LogicalPlan,
+    ) -> bool {
         let left_tables = self.collect_tables(left);
         let right_tables = self.collect_tables(right);
         let predicate_tables = self.collect_predicate_tables(predicate);
@@ -162,6 +193,7 @@ impl ImplicitJoinsPass {
             LogicalPlan::IndexScan { table, .. }
             | LogicalPlan::IndexGet { table, .. }
             | LogicalPlan::IndexInGet { table, .. }
+            | LogicalPlan::FetchByIds { table, .. }
             | LogicalPlan::GinIndexScan { table, .. }
             | LogicalPlan::GinIndexScanMulti { table, .. } => tables.push(table.clone()),
             LogicalPlan::Filter { input, .. }
@@ -203,7 +235,7 @@ impl ImplicitJoinsPass {
                 self.collect_expr_tables(left, tables);
                 self.collect_expr_tables(right, tables);
             }
-            Expr::UnaryOp { expr, .. } => {
+            Expr::UnaryOp { expr, .. } | Expr::Cast { expr, .. } => {
                 self.collect_expr_tables(expr, tables);
             }
             Expr::Aggregate { expr, .. } => {
@@ -211,7 +243,7 @@ impl ImplicitJoinsPass {
                     self.collect_expr_tables(e, tables);
                 }
             }
-            Expr::Literal(_) => {}
+            Expr::Literal(_) | Expr::TypedNull(_) => {}
             // Handle other expression types
             Expr::Function { args, .. } => {
                 for arg in args {
@@ -306,6 +338,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_inner_join_without_join_predicate_becomes_cross_product() {
+        let pass = ImplicitJoinsPass;
+
+        // The condition compares two columns of `a`, so nothing relates `b`.
+        let condition = Expr::eq(Expr::column("a", "id", 0), Expr::column("a", "b_id", 1));
+        let plan =
+            LogicalPlan::inner_join(LogicalPlan::scan("a"), LogicalPlan::scan("b"), condition);
+
+        let result = pass.optimize(plan);
+
+        let LogicalPlan::Filter { input, .. } = result else {
+            panic!("expected Filter, got {:?}", result);
+        };
+        assert!(matches!(*input, LogicalPlan::CrossProduct { .. }));
+
+        // Outer joins keep their unmatched rows, so they are left alone.
+        let left_join = LogicalPlan::left_join(
+            LogicalPlan::scan("a"),
+            LogicalPlan::scan("b"),
+            Expr::eq(Expr::column("a", "id", 0), Expr::literal(1i64)),
+        );
+        assert!(matches!(
+            pass.optimize(left_join),
+            LogicalPlan::Join {
+                join_type: JoinType::LeftOuter,
+                ..
+            }
+        ));
+    }
+
     #[test]
     fn test_filter_without_cross_product() {
         let pass = ImplicitJoinsPass;
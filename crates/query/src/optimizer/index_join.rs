@@ -14,6 +14,14 @@
 //! 1. One side has an index on the join column
 //! 2. The outer relation is small enough that index lookups are efficient
 //! 3. The join is an inner equi-join
+//!
+//! Semi-joins and anti-joins (the decorrelated forms of `EXISTS` and
+//! `NOT EXISTS`) are always converted when the right side has an index on
+//! its join column: the lookup stops at the first match, and no other join
+//! operator executes them.
+//!
+//! Inner joins whose algorithm a join hint pinned (see
+//! `PhysicalPlan::pin_join_algorithm`) are left as they are.
 
 use crate::ast::{BinaryOp, ColumnRef, Expr, JoinType};
 use crate::context::{ExecutionContext, IndexInfo};
@@ -52,18 +60,36 @@ impl<'a> IndexJoinPass<'a> {
                 condition,
                 join_type,
                 output_tables,
+                pinned,
             } => {
                 let left = self.traverse(*left, None);
                 let right = self.traverse(*right, None);
 
-                // Only optimize inner equi-joins
-                if join_type != JoinType::Inner || !condition.is_equi_join() {
+                if join_type.outputs_left_only() {
+                    if let Some((inner_table, inner_index)) =
+                        self.find_semi_join_index(&left, &right, &condition)
+                    {
+                        return PhysicalPlan::IndexNestedLoopJoin {
+                            outer: Box::new(left),
+                            inner_table,
+                            inner_index,
+                            condition,
+                            join_type,
+                            outer_is_left: true,
+                            output_tables,
+                        };
+                    }
+                }
+
+                // Only optimize inner equi-joins without a pinned algorithm
+                if pinned || join_type != JoinType::Inner || !condition.is_equi_join() {
                     return PhysicalPlan::HashJoin {
                         left: Box::new(left),
                         right: Box::new(right),
                         condition,
                         join_type,
                         output_tables,
+                        pinned,
                     };
                 }
 
@@ -88,6 +114,7 @@ impl<'a> IndexJoinPass<'a> {
                     condition,
                     join_type,
                     output_tables,
+                    pinned,
                 }
             }
 
@@ -98,18 +125,36 @@ impl<'a> IndexJoinPass<'a> {
                 condition,
                 join_type,
                 output_tables,
+                pinned,
             } => {
                 let left = self.traverse(*left, None);
                 let right = self.traverse(*right, None);
 
-                // Only optimize inner equi-joins
-                if join_type != JoinType::Inner || !condition.is_equi_join() {
+                if join_type.outputs_left_only() {
+                    if let Some((inner_table, inner_index)) =
+                        self.find_semi_join_index(&left, &right, &condition)
+                    {
+                        return PhysicalPlan::IndexNestedLoopJoin {
+                            outer: Box::new(left),
+                            inner_table,
+                            inner_index,
+                            condition,
+                            join_type,
+                            outer_is_left: true,
+                            output_tables,
+                        };
+                    }
+                }
+
+                // Only optimize inner equi-joins without a pinned algorithm
+                if pinned || join_type != JoinType::Inner || !condition.is_equi_join() {
                     return PhysicalPlan::NestedLoopJoin {
                         left: Box::new(left),
                         right: Box::new(right),
                         condition,
                         join_type,
                         output_tables,
+                        pinned,
                     };
                 }
 
@@ -134,6 +179,7 @@ impl<'a> IndexJoinPass<'a> {
                     condition,
                     join_type,
                     output_tables,
+                    pinned,
                 }
             }
 
@@ -225,6 +271,7 @@ impl<'a> IndexJoinPass<'a> {
             | PhysicalPlan::IndexScan { .. }
             | PhysicalPlan::IndexGet { .. }
             | PhysicalPlan::IndexInGet { .. }
+            | PhysicalPlan::FetchByIds { .. }
             | PhysicalPlan::IndexNestedLoopJoin { .. }
             | PhysicalPlan::Empty
             | PhysicalPlan::GinIndexScan { .. }
@@ -263,6 +310,22 @@ impl<'a> IndexJoinPass<'a> {
         None
     }
 
+    /// Finds the index used to probe the right side of a semi- or anti-join.
+    ///
+    /// Unlike inner joins there is no cost check: the join keeps the left
+    /// side as the outer relation and has no other executable form.
+    fn find_semi_join_index(
+        &self,
+        left: &PhysicalPlan,
+        right: &PhysicalPlan,
+        condition: &Expr,
+    ) -> Option<(String, String)> {
+        let (cond_left_col, cond_right_col) = self.extract_join_columns(condition)?;
+        let (_, right_col) = self.align_join_columns(left, right, cond_left_col, cond_right_col)?;
+        let (table, index) = self.get_indexed_table_scan(right, right_col)?;
+        Some((table, index.name))
+    }
+
     fn align_join_columns<'b>(
         &self,
         left: &PhysicalPlan,
@@ -393,6 +456,7 @@ impl<'a> IndexJoinPass<'a> {
                 .unwrap_or(1000),
             PhysicalPlan::IndexGet { .. } => 1,
             PhysicalPlan::IndexInGet { keys, .. } => keys.len(),
+            PhysicalPlan::FetchByIds { ids, .. } => ids.len(),
             PhysicalPlan::IndexScan { table, .. } | PhysicalPlan::GinIndexScan { table, .. } => {
                 self.ctx
                     .get_stats(table)
@@ -482,6 +546,56 @@ mod tests {
         ctx
     }
 
+    #[test]
+    fn test_semi_join_probes_right_index() {
+        let ctx = create_test_context();
+        let pass = IndexJoinPass::new(&ctx);
+
+        // EXISTS (SELECT 1 FROM b WHERE b.a_id = a.id), decorrelated
+        let plan = PhysicalPlan::hash_join(
+            PhysicalPlan::table_scan("a"),
+            PhysicalPlan::table_scan("b"),
+            Expr::eq(Expr::column("a", "id", 0), Expr::column("b", "a_id", 0)),
+            JoinType::Semi,
+        );
+
+        match pass.optimize(plan) {
+            PhysicalPlan::IndexNestedLoopJoin {
+                outer,
+                inner_table,
+                inner_index,
+                join_type,
+                outer_is_left,
+                output_tables,
+                ..
+            } => {
+                assert!(matches!(*outer, PhysicalPlan::TableScan { ref table } if table == "a"));
+                assert_eq!(inner_table, "b");
+                assert_eq!(inner_index, "idx_a_id");
+                assert_eq!(join_type, JoinType::Semi);
+                assert!(outer_is_left);
+                assert_eq!(output_tables, alloc::vec![String::from("a")]);
+            }
+            other => panic!("Expected semi index join, got {:?}", other),
+        }
+
+        // The outer side of a semi-join is fixed, so an index on the left
+        // side cannot be used.
+        let plan = PhysicalPlan::hash_join(
+            PhysicalPlan::table_scan("b"),
+            PhysicalPlan::table_scan("a"),
+            Expr::eq(Expr::column("b", "a_id", 0), Expr::column("a", "id", 0)),
+            JoinType::Semi,
+        );
+        assert!(matches!(
+            pass.optimize(plan),
+            PhysicalPlan::HashJoin {
+                join_type: JoinType::Semi,
+                ..
+            }
+        ));
+    }
+
     #[test]
     fn test_hash_join_to_index_join() {
         let ctx = create_test_context();
@@ -510,6 +624,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_pinned_join_keeps_its_algorithm() {
+        let ctx = create_test_context();
+        let pass = IndexJoinPass::new(&ctx);
+        let join = || {
+            PhysicalPlan::hash_join(
+                PhysicalPlan::table_scan("a"),
+                PhysicalPlan::table_scan("b"),
+                Expr::eq(Expr::column("a", "id", 0), Expr::column("b", "a_id", 0)),
+                JoinType::Inner,
+            )
+        };
+
+        assert!(matches!(
+            pass.optimize(join().pin_join_algorithm()),
+            PhysicalPlan::HashJoin { pinned: true, .. }
+        ));
+
+        // The pin belongs to the node, so it survives passes that rebuild
+        // the plan and does not leak into an unpinned join nested below it.
+        let plan = PhysicalPlan::nested_loop_join(
+            PhysicalPlan::limit(join(), 10, 0),
+            PhysicalPlan::table_scan("c"),
+            Expr::eq(Expr::column("a", "id", 0), Expr::column("c", "id", 0)),
+            JoinType::Inner,
+        )
+        .pin_join_algorithm();
+        let plan = crate::optimizer::LimitPushdown::new().optimize(plan);
+        match pass.optimize(plan) {
+            PhysicalPlan::NestedLoopJoin {
+                left, pinned: true, ..
+            } => assert!(matches!(
+                *left,
+                PhysicalPlan::Limit { ref input, .. }
+                    if matches!(**input, PhysicalPlan::IndexNestedLoopJoin { .. })
+            )),
+            other => panic!("Expected the pinned nested loop join, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_index_join_tracks_logical_left_when_outer_flips() {
         let mut ctx = ExecutionContext::new();
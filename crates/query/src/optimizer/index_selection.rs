@@ -1,6 +1,6 @@
 //! Index selection optimization pass.
 
-use crate::ast::{BinaryOp, Expr};
+use crate::ast::{BinaryOp, Expr, JoinType};
 use crate::context::{ExecutionContext, IndexInfo};
 use crate::optimizer::OptimizerPass;
 use crate::planner::{IndexBounds, LogicalPlan};
@@ -161,6 +161,93 @@ impl MergedRange {
         }
     }
 
+    /// Returns true if no value lies in the range: the lower bound is above
+    /// the upper bound, or both are the same value and one excludes it.
+    fn is_empty(&self) -> bool {
+        use core::cmp::Ordering;
+        let (Some(lower), Some(upper)) = (&self.lower_bound, &self.upper_bound) else {
+            return false;
+        };
+        match lower.cmp(upper) {
+            Ordering::Greater => true,
+            Ordering::Equal => !(self.lower_inclusive && self.upper_inclusive),
+            Ordering::Less => false,
+        }
+    }
+
+    /// Narrows the range by `column <op> value`. Returns false if `op` does
+    /// not describe a range.
+    fn constrain(&mut self, op: BinaryOp, value: Value) -> bool {
+        match op {
+            BinaryOp::Eq => {
+                self.update_lower(value.clone(), true);
+                self.update_upper(value, true);
+            }
+            BinaryOp::Gt => self.update_lower(value, false),
+            BinaryOp::Ge => self.update_lower(value, true),
+            BinaryOp::Lt => self.update_upper(value, false),
+            BinaryOp::Le => self.update_upper(value, true),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Orders ranges by where they start: -∞ first, and an inclusive bound
+    /// before an exclusive one on the same value.
+    fn cmp_lower(&self, other: &Self) -> core::cmp::Ordering {
+        use core::cmp::Ordering;
+        match (&self.lower_bound, &other.lower_bound) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Less,
+            (Some(_), None) => Ordering::Greater,
+            (Some(a), Some(b)) => a
+                .cmp(b)
+                .then(other.lower_inclusive.cmp(&self.lower_inclusive)),
+        }
+    }
+
+    /// Returns true if `next`, which starts no earlier than `self`, overlaps
+    /// or touches `self`, so that both form one contiguous range.
+    fn reaches(&self, next: &Self) -> bool {
+        match (&self.upper_bound, &next.lower_bound) {
+            (None, _) | (_, None) => true,
+            (Some(upper), Some(lower)) => {
+                lower < upper || (lower == upper && (self.upper_inclusive || next.lower_inclusive))
+            }
+        }
+    }
+
+    /// Extends the upper bound to cover `other`'s, keeping the larger one.
+    fn extend_upper(&mut self, other: &Self) {
+        use core::cmp::Ordering;
+        let (Some(upper), Some(other_upper)) = (&self.upper_bound, &other.upper_bound) else {
+            self.upper_bound = None;
+            self.upper_inclusive = true;
+            return;
+        };
+        match other_upper.cmp(upper) {
+            Ordering::Greater => {
+                self.upper_bound = other.upper_bound.clone();
+                self.upper_inclusive = other.upper_inclusive;
+            }
+            Ordering::Equal => self.upper_inclusive |= other.upper_inclusive,
+            Ordering::Less => {}
+        }
+    }
+
+    /// Unions ranges into the fewest disjoint ranges, in ascending order.
+    fn union_all(mut ranges: Vec<MergedRange>) -> Vec<MergedRange> {
+        ranges.sort_by(|a, b| a.cmp_lower(b));
+        let mut result: Vec<MergedRange> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match result.last_mut() {
+                Some(last) if last.reaches(&range) => last.extend_upper(&range),
+                _ => result.push(range),
+            }
+        }
+        result
+    }
+
     /// Converts to IndexScan range parameters.
     fn to_range_params(self) -> (Option<Value>, Option<Value>, bool, bool) {
         (
@@ -170,6 +257,54 @@ impl MergedRange {
             self.upper_inclusive,
         )
     }
+
+    /// Converts a non-empty range to a key range; a single value becomes
+    /// `KeyRange::Only`.
+    fn into_key_range(self) -> KeyRange<Value> {
+        if let (Some(lower), Some(upper)) = (&self.lower_bound, &self.upper_bound) {
+            if lower == upper {
+                return KeyRange::only(lower.clone());
+            }
+        }
+        let (start, end, include_start, include_end) = self.to_range_params();
+        match IndexBounds::from_scalar_range(start, end, include_start, include_end) {
+            IndexBounds::Scalar(range) => range,
+            _ => KeyRange::all(),
+        }
+    }
+}
+
+/// Converts a predicate on `column` into the key range it selects, for use
+/// outside the optimizer, e.g. by range deletes or cursor pagination.
+///
+/// The predicate must be a comparison (`=`, `<`, `<=`, `>`, `>=`) between
+/// `column` and a non-NULL literal, a `BETWEEN` with literal bounds, or an
+/// AND of such terms, whose ranges intersect. Returns `None` if any term is
+/// something else or no key satisfies the predicate.
+pub fn predicate_to_range(predicate: &Expr, column: &str) -> Option<KeyRange<Value>> {
+    let selection = IndexSelection::new();
+    let mut range = MergedRange::new();
+    for conjunct in selection.flatten_and_predicates(predicate) {
+        if let Expr::Between { expr, low, high } = &conjunct {
+            let (Expr::Column(col), Expr::Literal(low), Expr::Literal(high)) =
+                (expr.as_ref(), low.as_ref(), high.as_ref())
+            else {
+                return None;
+            };
+            if col.column != column || low.is_null() || high.is_null() {
+                return None;
+            }
+            range.update_lower(low.clone(), true);
+            range.update_upper(high.clone(), true);
+            continue;
+        }
+        let info = selection.analyze_predicate(&conjunct)?;
+        let value = info.value.filter(|value| !value.is_null())?;
+        if info.column != column || !range.constrain(info.op, value) {
+            return None;
+        }
+    }
+    (!range.is_empty()).then(|| range.into_key_range())
 }
 
 /// Information extracted from an IN predicate for index selection.
@@ -232,13 +367,32 @@ impl IndexSelection {
                 condition,
                 join_type,
                 output_tables,
-            } => LogicalPlan::Join {
-                left: Box::new(self.select_indexes(*left)),
-                right: Box::new(self.select_indexes(*right)),
-                condition,
-                join_type,
-                output_tables,
-            },
+                algorithm,
+            } => {
+                let (left, left_empty) = self.select_indexes_keeping_layout(*left);
+                let (right, right_empty) = self.select_indexes_keeping_layout(*right);
+                // An empty side empties the join unless the join preserves
+                // the other side's rows.
+                let empties_join = |preserves_other: bool| match join_type {
+                    JoinType::Inner | JoinType::Cross | JoinType::Semi => true,
+                    JoinType::FullOuter => false,
+                    _ => !preserves_other,
+                };
+                if (left_empty && empties_join(join_type == JoinType::RightOuter))
+                    || (right_empty
+                        && empties_join(matches!(join_type, JoinType::LeftOuter | JoinType::Anti)))
+                {
+                    return LogicalPlan::Empty;
+                }
+                LogicalPlan::Join {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    condition,
+                    join_type,
+                    output_tables,
+                    algorithm,
+                }
+            }
 
             LogicalPlan::Aggregate {
                 input,
@@ -265,14 +419,21 @@ impl IndexSelection {
                 offset,
             },
 
-            LogicalPlan::CrossProduct { left, right } => LogicalPlan::CrossProduct {
-                left: Box::new(self.select_indexes(*left)),
-                right: Box::new(self.select_indexes(*right)),
-            },
+            LogicalPlan::CrossProduct { left, right } => {
+                let left = self.select_indexes(*left);
+                let right = self.select_indexes(*right);
+                if matches!(left, LogicalPlan::Empty) || matches!(right, LogicalPlan::Empty) {
+                    return LogicalPlan::Empty;
+                }
+                LogicalPlan::CrossProduct {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                }
+            }
 
             LogicalPlan::Union { left, right, all } => LogicalPlan::Union {
-                left: Box::new(self.select_indexes(*left)),
-                right: Box::new(self.select_indexes(*right)),
+                left: Box::new(self.select_indexes_keeping_layout(*left).0),
+                right: Box::new(self.select_indexes_keeping_layout(*right).0),
                 all,
             },
 
@@ -281,12 +442,25 @@ impl IndexSelection {
             | LogicalPlan::IndexScan { .. }
             | LogicalPlan::IndexGet { .. }
             | LogicalPlan::IndexInGet { .. }
+            | LogicalPlan::FetchByIds { .. }
             | LogicalPlan::GinIndexScan { .. }
             | LogicalPlan::GinIndexScanMulti { .. }
             | LogicalPlan::Empty => plan,
         }
     }
 
+    /// Selects indexes for one input of a join or union. An input that
+    /// turns out to be Empty would lose the column layout its parent relies
+    /// on, so the original input is kept and the emptiness is reported
+    /// instead.
+    fn select_indexes_keeping_layout(&self, plan: LogicalPlan) -> (LogicalPlan, bool) {
+        let original = plan.clone();
+        match self.select_indexes(plan) {
+            LogicalPlan::Empty if !matches!(original, LogicalPlan::Empty) => (original, true),
+            optimized => (optimized, false),
+        }
+    }
+
     /// Attempts to use an index for the given predicate.
     fn try_use_index(
         &self,
@@ -317,6 +491,16 @@ impl IndexSelection {
             return Some(between_plan);
         }
 
+        // OR of ranges on one column becomes the union of those ranges
+        if let Some(or_plan) = self.try_use_btree_with_or(table, predicate, ctx) {
+            return Some(or_plan);
+        }
+
+        // STARTS_WITH on an ordered index becomes a prefix range scan
+        if let Some(prefix_plan) = self.try_use_prefix_index(table, predicate, ctx) {
+            return Some(prefix_plan);
+        }
+
         // Then, try to use GIN index for JSONB function queries
         if let Some(gin_plan) = self.try_use_gin_index(table, predicate, ctx) {
             return Some(gin_plan);
@@ -357,6 +541,15 @@ impl IndexSelection {
             // Use IndexScan for range predicates
             let (range_start, range_end, include_start, include_end) =
                 self.compute_range(&pred_info);
+            if Self::range_too_wide(
+                ctx,
+                table,
+                &pred_info.column,
+                range_start.as_ref(),
+                range_end.as_ref(),
+            ) {
+                return None;
+            }
             return Some(LogicalPlan::IndexScan {
                 table: table.into(),
                 index: index.name.clone(),
@@ -372,6 +565,21 @@ impl IndexSelection {
         None
     }
 
+    /// Returns true when the column histogram estimates that the range covers
+    /// more of `table` than the context's `max_index_selectivity`, in which
+    /// case a filtered scan avoids the index's random access. Without a
+    /// histogram the index is always considered worthwhile.
+    fn range_too_wide(
+        ctx: &ExecutionContext,
+        table: &str,
+        column: &str,
+        lower: Option<&Value>,
+        upper: Option<&Value>,
+    ) -> bool {
+        ctx.estimate_range_selectivity(table, column, lower, upper)
+            .is_some_and(|fraction| fraction > ctx.max_index_selectivity())
+    }
+
     /// Attempts to use an index for BETWEEN predicates.
     fn try_use_between_index(
         &self,
@@ -388,7 +596,15 @@ impl IndexSelection {
                 {
                     // Find an index for this column
                     if let Some(index) = ctx.find_index(table, &[col.column.as_str()]) {
-                        if !index.supports_range() {
+                        if !index.supports_range()
+                            || Self::range_too_wide(
+                                ctx,
+                                table,
+                                &col.column,
+                                Some(low_val),
+                                Some(high_val),
+                            )
+                        {
                             return None;
                         }
                         // Use IndexScan for BETWEEN
@@ -409,6 +625,167 @@ impl IndexSelection {
         None
     }
 
+    /// Attempts to use an ordered index for an OR of ranges on one column,
+    /// such as `a < 5 OR a > 10` or `(a >= 1 AND a < 3) OR a = 7`.
+    ///
+    /// Each branch is merged into a range and the ranges are unioned.
+    /// Overlapping or touching ranges collapse into a single IndexScan, so
+    /// `a > 5 OR a > 10` scans `a > 5`. Disjoint ranges become a UNION ALL of
+    /// IndexScans; the ranges do not overlap, so no row is scanned twice.
+    fn try_use_btree_with_or(
+        &self,
+        table: &str,
+        predicate: &Expr,
+        ctx: &ExecutionContext,
+    ) -> Option<LogicalPlan> {
+        if !matches!(
+            predicate,
+            Expr::BinaryOp {
+                op: BinaryOp::Or,
+                ..
+            }
+        ) {
+            return None;
+        }
+
+        let mut branches = Vec::new();
+        Self::flatten_or_predicates_into(predicate, &mut branches);
+
+        let mut column: Option<String> = None;
+        let mut ranges = Vec::with_capacity(branches.len());
+        for branch in &branches {
+            let mut range = MergedRange::new();
+            for conjunct in self.flatten_and_predicates(branch) {
+                let info = self.analyze_predicate(&conjunct)?;
+                let value = info.value.filter(|value| !value.is_null())?;
+                if info.table != table || column.as_ref().is_some_and(|c| *c != info.column) {
+                    return None;
+                }
+                if !range.constrain(info.op, value) {
+                    return None;
+                }
+                column = Some(info.column);
+            }
+            // A contradictory branch adds nothing to the union
+            if !range.is_empty() {
+                ranges.push(range);
+            }
+        }
+
+        let column = column?;
+        let index = ctx.find_index(table, &[column.as_str()])?;
+        if !index.supports_range() {
+            return None;
+        }
+
+        if ranges.is_empty() {
+            return Some(LogicalPlan::Empty);
+        }
+        let ranges = MergedRange::union_all(ranges);
+        if let [range] = ranges.as_slice() {
+            // The branches cover every key, so the index would not narrow the scan.
+            if range.lower_bound.is_none() && range.upper_bound.is_none() {
+                return None;
+            }
+        }
+        let selectivity = ranges.iter().try_fold(0.0, |total, range| {
+            ctx.estimate_range_selectivity(
+                table,
+                &column,
+                range.lower_bound.as_ref(),
+                range.upper_bound.as_ref(),
+            )
+            .map(|fraction| total + fraction)
+        });
+        if selectivity.is_some_and(|fraction| fraction > ctx.max_index_selectivity()) {
+            return None;
+        }
+
+        ranges
+            .into_iter()
+            .map(|range| {
+                let (range_start, range_end, include_start, include_end) = range.to_range_params();
+                LogicalPlan::IndexScan {
+                    table: table.into(),
+                    index: index.name.clone(),
+                    bounds: IndexBounds::from_scalar_range(
+                        range_start,
+                        range_end,
+                        include_start,
+                        include_end,
+                    ),
+                }
+            })
+            .reduce(|left, right| LogicalPlan::union(left, right, true))
+    }
+
+    fn flatten_or_predicates_into(predicate: &Expr, predicates: &mut Vec<Expr>) {
+        match predicate {
+            Expr::BinaryOp {
+                left,
+                op: BinaryOp::Or,
+                right,
+            } => {
+                Self::flatten_or_predicates_into(left, predicates);
+                Self::flatten_or_predicates_into(right, predicates);
+            }
+            _ => predicates.push(predicate.clone()),
+        }
+    }
+
+    /// Attempts to use an index for a STARTS_WITH predicate.
+    ///
+    /// The scan covers `[prefix, successor)`, which holds exactly the strings
+    /// starting with `prefix`. When the prefix has no successor the scan is
+    /// left open above and the predicate is kept as a filter.
+    fn try_use_prefix_index(
+        &self,
+        table: &str,
+        predicate: &Expr,
+        ctx: &ExecutionContext,
+    ) -> Option<LogicalPlan> {
+        let (column, prefix) = self.analyze_prefix_predicate(predicate)?;
+        let index = ctx.find_index(table, &[column.as_str()])?;
+        if !index.supports_range() {
+            return None;
+        }
+
+        let upper = prefix_successor(&prefix).map(Value::String);
+        let lower = Value::String(prefix);
+        if Self::range_too_wide(ctx, table, &column, Some(&lower), upper.as_ref()) {
+            return None;
+        }
+        let exact = upper.is_some();
+        let index_plan = LogicalPlan::IndexScan {
+            table: table.into(),
+            index: index.name.clone(),
+            bounds: IndexBounds::from_scalar_range(Some(lower), upper, true, false),
+        };
+        let remaining = if exact {
+            Vec::new()
+        } else {
+            alloc::vec![predicate.clone()]
+        };
+        Some(self.wrap_with_filter_if_needed(index_plan, remaining))
+    }
+
+    /// Matches `STARTS_WITH(column, 'literal')` and returns the column name and
+    /// prefix. An empty prefix matches every string, so it is not reported.
+    fn analyze_prefix_predicate(&self, predicate: &Expr) -> Option<(String, String)> {
+        let Expr::Function { name, args } = predicate else {
+            return None;
+        };
+        if !name.eq_ignore_ascii_case("starts_with") {
+            return None;
+        }
+        match (args.first()?, args.get(1)?) {
+            (Expr::Column(col), Expr::Literal(Value::String(prefix))) if !prefix.is_empty() => {
+                Some((col.column.clone(), prefix.clone()))
+            }
+            _ => None,
+        }
+    }
+
     /// Attempts to use a composite B-Tree index for AND predicates.
     ///
     /// This is intentionally conservative: it only generates tuple bounds when
@@ -521,6 +898,12 @@ impl IndexSelection {
             return None;
         }
 
+        // Contradictory ranges such as `a > 10 AND a < 5` match no row
+        let mut merged = self.merge_range_predicates_by_column(&indexable);
+        if merged.iter().any(|(_, _, range, _)| range.is_empty()) {
+            return Some(LogicalPlan::Empty);
+        }
+
         // Try to find a point lookup first (highest priority)
         for (pred, info, index) in &indexable {
             if info.is_point_lookup && info.value.is_some() {
@@ -534,7 +917,9 @@ impl IndexSelection {
                 // Collect remaining predicates (non-indexable + other indexable)
                 let mut all_remaining: Vec<Expr> = remaining;
                 for (other_pred, _, _) in &indexable {
-                    if !Self::expr_eq(other_pred, pred) {
+                    if !Self::expr_eq(other_pred, pred)
+                        && !all_remaining.iter().any(|p| Self::expr_eq(p, other_pred))
+                    {
                         all_remaining.push(other_pred.clone());
                     }
                 }
@@ -544,8 +929,17 @@ impl IndexSelection {
         }
 
         // No point lookup found, try to merge range predicates on the same column
-        // Group range predicates by (column, index)
-        let merged = self.merge_range_predicates_by_column(&indexable);
+        // Group range predicates by (column, index), dropping ranges the
+        // histogram expects to cover too much of the table
+        merged.retain(|(column, _, range, _)| {
+            !Self::range_too_wide(
+                ctx,
+                table,
+                column,
+                range.lower_bound.as_ref(),
+                range.upper_bound.as_ref(),
+            )
+        });
 
         if merged.is_empty() {
             return None;
@@ -579,6 +973,10 @@ impl IndexSelection {
             {
                 continue;
             }
+            // STARTS_WITH contributes two bounds but is one predicate.
+            if all_remaining.iter().any(|p| Self::expr_eq(p, pred)) {
+                continue;
+            }
             all_remaining.push(pred.clone());
         }
 
@@ -738,6 +1136,37 @@ impl IndexSelection {
                 self.extract_btree_and_remaining_recursive(right, table, ctx, indexable, remaining);
             }
             _ => {
+                // A STARTS_WITH with a bounded prefix range joins the range
+                // merge as `>= prefix` and `< successor`.
+                if let Some((column, prefix)) = self.analyze_prefix_predicate(predicate) {
+                    if let (Some(index), Some(upper)) = (
+                        ctx.find_index(table, &[column.as_str()]),
+                        prefix_successor(&prefix),
+                    ) {
+                        if index.supports_range() {
+                            let bound = |op, value| PredicateInfo {
+                                table: table.into(),
+                                column: column.clone(),
+                                op,
+                                value: Some(Value::String(value)),
+                                is_range: true,
+                                is_point_lookup: false,
+                            };
+                            indexable.push((
+                                predicate.clone(),
+                                bound(BinaryOp::Ge, prefix),
+                                index.clone(),
+                            ));
+                            indexable.push((
+                                predicate.clone(),
+                                bound(BinaryOp::Lt, upper),
+                                index.clone(),
+                            ));
+                            return;
+                        }
+                    }
+                }
+
                 // Try to analyze as a simple predicate
                 if let Some(pred_info) = self.analyze_predicate(predicate) {
                     // Check if there's a B-Tree index for this column
@@ -1464,6 +1893,20 @@ impl IndexSelection {
     }
 }
 
+/// Returns the smallest string greater than every string starting with
+/// `prefix`, or `None` when the prefix consists only of `char::MAX`.
+fn prefix_successor(prefix: &str) -> Option<String> {
+    let mut chars: Vec<char> = prefix.chars().collect();
+    while let Some(last) = chars.pop() {
+        // Skips the surrogate gap, which `char::from_u32` rejects.
+        if let Some(next) = (last as u32 + 1..=char::MAX as u32).find_map(char::from_u32) {
+            chars.push(next);
+            return Some(chars.into_iter().collect());
+        }
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1489,6 +1932,94 @@ mod tests {
         grams.into_iter().collect()
     }
 
+    #[test]
+    fn test_predicate_to_range() {
+        let age = || Expr::column("users", "age", 1);
+        let int = |n: i64| Expr::literal(Value::Int64(n));
+        let range = |predicate: Expr| predicate_to_range(&predicate, "age");
+
+        assert_eq!(
+            range(Expr::gt(age(), int(18))),
+            Some(KeyRange::lower_bound(Value::Int64(18), true))
+        );
+        assert_eq!(
+            range(Expr::gte(age(), int(18))),
+            Some(KeyRange::lower_bound(Value::Int64(18), false))
+        );
+        assert_eq!(
+            range(Expr::lt(age(), int(65))),
+            Some(KeyRange::upper_bound(Value::Int64(65), true))
+        );
+        assert_eq!(
+            range(Expr::lte(age(), int(65))),
+            Some(KeyRange::upper_bound(Value::Int64(65), false))
+        );
+        // A literal on the left flips the comparison.
+        assert_eq!(
+            range(Expr::lt(int(18), age())),
+            Some(KeyRange::lower_bound(Value::Int64(18), true))
+        );
+        assert_eq!(
+            range(Expr::between(age(), int(18), int(65))),
+            Some(KeyRange::bound(
+                Value::Int64(18),
+                Value::Int64(65),
+                false,
+                false
+            ))
+        );
+        // Conjuncts intersect; a single remaining value is a point.
+        assert_eq!(
+            range(Expr::and(
+                Expr::between(age(), int(18), int(65)),
+                Expr::lt(age(), int(30))
+            )),
+            Some(KeyRange::bound(
+                Value::Int64(18),
+                Value::Int64(30),
+                false,
+                true
+            ))
+        );
+        assert_eq!(
+            range(Expr::and(
+                Expr::gte(age(), int(30)),
+                Expr::lte(age(), int(30))
+            )),
+            Some(KeyRange::only(Value::Int64(30)))
+        );
+    }
+
+    #[test]
+    fn test_predicate_to_range_rejects_non_ranges() {
+        let age = || Expr::column("users", "age", 1);
+        let int = |n: i64| Expr::literal(Value::Int64(n));
+        let range = |predicate: Expr| predicate_to_range(&predicate, "age");
+
+        assert_eq!(range(Expr::ne(age(), int(18))), None);
+        assert_eq!(
+            range(Expr::or(Expr::lt(age(), int(18)), Expr::gt(age(), int(65)))),
+            None
+        );
+        assert_eq!(
+            range(Expr::like(Expr::column("users", "name", 0), "A%")),
+            None
+        );
+        // Another column, a NULL bound, or a contradiction.
+        assert_eq!(
+            range(Expr::gt(Expr::column("users", "id", 0), int(1))),
+            None
+        );
+        assert_eq!(range(Expr::gt(age(), Expr::literal(Value::Null))), None);
+        assert_eq!(
+            range(Expr::and(
+                Expr::gt(age(), int(65)),
+                Expr::lt(age(), int(18))
+            )),
+            None
+        );
+    }
+
     #[test]
     fn test_index_selection_basic() {
         let pass = IndexSelection::new();
@@ -2254,6 +2785,89 @@ mod tests {
         }
     }
 
+    fn users_name_index_ctx() -> ExecutionContext {
+        let mut ctx = ExecutionContext::new();
+        ctx.register_table(
+            "users",
+            TableStats {
+                row_count: 10000,
+                is_sorted: false,
+                indexes: alloc::vec![IndexInfo::new(
+                    "idx_name",
+                    alloc::vec!["name".into()],
+                    false
+                )],
+            },
+        );
+        ctx
+    }
+
+    #[test]
+    fn test_starts_with_uses_prefix_index_scan() {
+        let pass = IndexSelection::with_context(users_name_index_ctx());
+        let plan = LogicalPlan::filter(
+            LogicalPlan::scan("users"),
+            Expr::starts_with(Expr::column("users", "name", 1), Expr::literal("Al")),
+        );
+
+        match pass.optimize(plan) {
+            LogicalPlan::IndexScan { index, bounds, .. } => {
+                assert_eq!(index, "idx_name");
+                assert_eq!(
+                    bounds,
+                    IndexBounds::Scalar(KeyRange::bound(
+                        Value::String("Al".into()),
+                        Value::String("Am".into()),
+                        false,
+                        true,
+                    ))
+                );
+            }
+            other => panic!("Expected IndexScan, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_starts_with_merges_with_other_predicates() {
+        let pass = IndexSelection::with_context(users_name_index_ctx());
+        let predicate = Expr::and(
+            Expr::starts_with(Expr::column("users", "name", 1), Expr::literal("Al")),
+            Expr::gt(Expr::column("users", "age", 2), Expr::literal(30i64)),
+        );
+        let plan = LogicalPlan::filter(LogicalPlan::scan("users"), predicate);
+
+        match pass.optimize(plan) {
+            LogicalPlan::Filter { input, predicate } => {
+                assert!(matches!(*input, LogicalPlan::IndexScan { .. }));
+                assert!(IndexSelection::expr_eq(
+                    &predicate,
+                    &Expr::gt(Expr::column("users", "age", 2), Expr::literal(30i64))
+                ));
+            }
+            other => panic!("Expected Filter over IndexScan, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_starts_with_needs_literal_prefix() {
+        let pass = IndexSelection::with_context(users_name_index_ctx());
+        for prefix in [Expr::literal(""), Expr::column("users", "nick", 3)] {
+            let plan = LogicalPlan::filter(
+                LogicalPlan::scan("users"),
+                Expr::starts_with(Expr::column("users", "name", 1), prefix),
+            );
+            assert!(matches!(pass.optimize(plan), LogicalPlan::Filter { .. }));
+        }
+    }
+
+    #[test]
+    fn test_prefix_successor() {
+        assert_eq!(prefix_successor("abc"), Some("abd".into()));
+        assert_eq!(prefix_successor("a\u{d7ff}"), Some("a\u{e000}".into()));
+        assert_eq!(prefix_successor("a\u{10ffff}"), Some("b".into()));
+        assert_eq!(prefix_successor("\u{10ffff}"), None);
+    }
+
     /// Test: Multiple range predicates on the same column should be merged.
     ///
     /// Query: price > 10 AND price < 150
@@ -2688,4 +3302,176 @@ mod tests {
             "single-column predicates should not use a composite prefix as a scalar index lookup"
         );
     }
+
+    fn price_index_ctx() -> ExecutionContext {
+        let mut ctx = ExecutionContext::new();
+        ctx.register_table(
+            "stocks",
+            TableStats {
+                row_count: 10000,
+                is_sorted: false,
+                indexes: alloc::vec![IndexInfo::new(
+                    "idx_price",
+                    alloc::vec!["price".into()],
+                    false
+                )],
+            },
+        );
+        ctx
+    }
+
+    fn price(op: fn(Expr, Expr) -> Expr, value: i64) -> Expr {
+        op(
+            Expr::column("stocks", "price", 1),
+            Expr::literal(Value::Int64(value)),
+        )
+    }
+
+    #[test]
+    fn test_or_ranges_collapse_into_one_scan() {
+        let pass = IndexSelection::with_context(price_index_ctx());
+
+        // price > 5 OR price > 10 => price > 5
+        let plan = LogicalPlan::filter(
+            LogicalPlan::scan("stocks"),
+            Expr::or(price(Expr::gt, 5), price(Expr::gt, 10)),
+        );
+        match pass.optimize(plan) {
+            LogicalPlan::IndexScan { index, bounds, .. } => {
+                assert_eq!(index, "idx_price");
+                assert!(matches!(
+                    bounds,
+                    IndexBounds::Scalar(KeyRange::LowerBound {
+                        value: Value::Int64(5),
+                        exclusive: true,
+                    })
+                ));
+            }
+            other => panic!("Expected a single IndexScan, got {:?}", other),
+        }
+
+        // [1, 5) and [5, 8] touch at 5
+        let plan = LogicalPlan::filter(
+            LogicalPlan::scan("stocks"),
+            Expr::or(
+                Expr::and(price(Expr::ge, 1), price(Expr::lt, 5)),
+                Expr::and(price(Expr::ge, 5), price(Expr::le, 8)),
+            ),
+        );
+        match pass.optimize(plan) {
+            LogicalPlan::IndexScan { bounds, .. } => assert!(matches!(
+                bounds,
+                IndexBounds::Scalar(KeyRange::Bound {
+                    lower: Value::Int64(1),
+                    upper: Value::Int64(8),
+                    lower_exclusive: false,
+                    upper_exclusive: false,
+                })
+            )),
+            other => panic!("Expected a single IndexScan, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_disjoint_or_ranges_become_union_of_scans() {
+        let pass = IndexSelection::with_context(price_index_ctx());
+
+        // price > 10 OR price < 5 => UNION ALL of (-inf, 5) and (10, +inf)
+        let plan = LogicalPlan::filter(
+            LogicalPlan::scan("stocks"),
+            Expr::or(price(Expr::gt, 10), price(Expr::lt, 5)),
+        );
+        match pass.optimize(plan) {
+            LogicalPlan::Union { left, right, all } => {
+                assert!(all);
+                assert!(matches!(
+                    *left,
+                    LogicalPlan::IndexScan {
+                        bounds: IndexBounds::Scalar(KeyRange::UpperBound {
+                            value: Value::Int64(5),
+                            exclusive: true,
+                        }),
+                        ..
+                    }
+                ));
+                assert!(matches!(
+                    *right,
+                    LogicalPlan::IndexScan {
+                        bounds: IndexBounds::Scalar(KeyRange::LowerBound {
+                            value: Value::Int64(10),
+                            exclusive: true,
+                        }),
+                        ..
+                    }
+                ));
+            }
+            other => panic!("Expected a union of IndexScans, got {:?}", other),
+        }
+
+        // Exclusive bounds on the same value leave a gap at 5.
+        let plan = LogicalPlan::filter(
+            LogicalPlan::scan("stocks"),
+            Expr::or(price(Expr::lt, 5), price(Expr::gt, 5)),
+        );
+        assert!(matches!(pass.optimize(plan), LogicalPlan::Union { .. }));
+
+        // Branches on different columns are left to the filter.
+        let plan = LogicalPlan::filter(
+            LogicalPlan::scan("stocks"),
+            Expr::or(
+                price(Expr::lt, 5),
+                Expr::gt(
+                    Expr::column("stocks", "volume", 2),
+                    Expr::literal(Value::Int64(10)),
+                ),
+            ),
+        );
+        assert!(matches!(pass.optimize(plan), LogicalPlan::Filter { .. }));
+    }
+
+    #[test]
+    fn test_contradictory_ranges_become_empty() {
+        let pass = IndexSelection::with_context(price_index_ctx());
+        let filter =
+            |predicate| pass.optimize(LogicalPlan::filter(LogicalPlan::scan("stocks"), predicate));
+
+        // price > 10 AND price < 5
+        assert!(matches!(
+            filter(Expr::and(price(Expr::gt, 10), price(Expr::lt, 5))),
+            LogicalPlan::Empty
+        ));
+        // price >= 5 AND price < 5 excludes the only shared value
+        assert!(matches!(
+            filter(Expr::and(price(Expr::ge, 5), price(Expr::lt, 5))),
+            LogicalPlan::Empty
+        ));
+        // price >= 5 AND price <= 5 still matches 5
+        assert!(matches!(
+            filter(Expr::and(price(Expr::ge, 5), price(Expr::le, 5))),
+            LogicalPlan::IndexScan { .. }
+        ));
+        // A contradiction beside a point lookup still empties the plan
+        assert!(matches!(
+            filter(Expr::and(
+                Expr::and(price(Expr::gt, 10), price(Expr::eq, 20)),
+                price(Expr::le, 10),
+            )),
+            LogicalPlan::Empty
+        ));
+        // Contradictory OR branches drop out of the union
+        assert!(matches!(
+            filter(Expr::or(
+                Expr::and(price(Expr::gt, 10), price(Expr::lt, 5)),
+                price(Expr::gt, 20),
+            )),
+            LogicalPlan::IndexScan { .. }
+        ));
+        assert!(matches!(
+            filter(Expr::or(
+                Expr::and(price(Expr::gt, 10), price(Expr::lt, 5)),
+                Expr::and(price(Expr::gt, 3), price(Expr::lt, 2)),
+            )),
+            LogicalPlan::Empty
+        ));
+    }
 }
@@ -24,7 +24,7 @@
 use crate::ast::{Expr, JoinType};
 use crate::context::ExecutionContext;
 use crate::optimizer::OptimizerPass;
-use crate::planner::LogicalPlan;
+use crate::planner::{JoinAlgorithm, LogicalPlan};
 use alloc::boxed::Box;
 use alloc::string::String;
 use alloc::vec::Vec;
@@ -86,6 +86,8 @@ struct JoinCondition {
     left_tables: Vec<String>,
     /// Tables referenced by the right side.
     right_tables: Vec<String>,
+    /// Join algorithm requested for this condition's join.
+    algorithm: Option<JoinAlgorithm>,
 }
 
 impl JoinReorder {
@@ -97,6 +99,7 @@ impl JoinReorder {
                 condition,
                 join_type,
                 output_tables,
+                algorithm,
             } => {
                 let optimized_left = self.reorder(*left);
                 let optimized_right = self.reorder(*right);
@@ -106,7 +109,8 @@ impl JoinReorder {
                     condition,
                     join_type,
                     output_tables,
-                );
+                )
+                .with_join_algorithm(algorithm);
 
                 if join_type != JoinType::Inner {
                     return original_join;
@@ -120,13 +124,15 @@ impl JoinReorder {
                 if nodes.len() <= 2 {
                     if nodes.len() == 2 && !conditions.is_empty() {
                         let (left_node, right_node) = self.order_two_nodes(nodes);
+                        let join_condition = conditions.into_iter().next().unwrap();
                         return LogicalPlan::join_with_output_tables(
                             left_node.plan,
                             right_node.plan,
-                            conditions.into_iter().next().unwrap().condition,
+                            join_condition.condition,
                             JoinType::Inner,
                             original_output_tables,
-                        );
+                        )
+                        .with_join_algorithm(join_condition.algorithm);
                     }
                     if let Some(node) = nodes.into_iter().next() {
                         return node.plan;
@@ -188,6 +194,7 @@ impl JoinReorder {
             | LogicalPlan::IndexScan { .. }
             | LogicalPlan::IndexGet { .. }
             | LogicalPlan::IndexInGet { .. }
+            | LogicalPlan::FetchByIds { .. }
             | LogicalPlan::GinIndexScan { .. }
             | LogicalPlan::GinIndexScanMulti { .. }
             | LogicalPlan::Empty => plan,
@@ -207,6 +214,7 @@ impl JoinReorder {
                 right,
                 condition,
                 join_type: JoinType::Inner,
+                algorithm,
                 ..
             } => {
                 // Recursively collect from left and right
@@ -219,6 +227,7 @@ impl JoinReorder {
                     condition: condition.clone(),
                     left_tables,
                     right_tables,
+                    algorithm: *algorithm,
                 });
             }
 
@@ -330,7 +339,8 @@ impl JoinReorder {
                         conditions[cond_idx].condition.clone(),
                         JoinType::Inner,
                         output_tables.clone(),
-                    );
+                    )
+                    .with_join_algorithm(conditions[cond_idx].algorithm);
 
                     result_node = JoinNode {
                         plan: new_plan,
@@ -368,13 +378,16 @@ impl JoinReorder {
             let next_node = nodes.remove(best_idx);
 
             // Get the join condition
-            let condition = if let Some(cond_idx) = best_condition_idx {
+            let (condition, algorithm) = if let Some(cond_idx) = best_condition_idx {
                 used_conditions[cond_idx] = true;
-                conditions[cond_idx].condition.clone()
+                (
+                    conditions[cond_idx].condition.clone(),
+                    conditions[cond_idx].algorithm,
+                )
             } else {
                 // No specific condition found, use a cross product condition
                 // This shouldn't happen in well-formed queries
-                Expr::literal(true)
+                (Expr::literal(true), None)
             };
 
             // Create the join
@@ -388,7 +401,8 @@ impl JoinReorder {
                 condition,
                 JoinType::Inner,
                 output_tables.clone(),
-            );
+            )
+            .with_join_algorithm(algorithm);
 
             result_node = JoinNode {
                 plan: new_plan,
@@ -516,7 +530,7 @@ impl JoinReorder {
                 self.collect_expr_tables(left, tables);
                 self.collect_expr_tables(right, tables);
             }
-            Expr::UnaryOp { expr, .. } => {
+            Expr::UnaryOp { expr, .. } | Expr::Cast { expr, .. } => {
                 self.collect_expr_tables(expr, tables);
             }
             _ => {}
@@ -538,6 +552,7 @@ impl JoinReorder {
             LogicalPlan::IndexScan { table, .. }
             | LogicalPlan::IndexGet { table, .. }
             | LogicalPlan::IndexInGet { table, .. }
+            | LogicalPlan::FetchByIds { table, .. }
             | LogicalPlan::GinIndexScan { table, .. }
             | LogicalPlan::GinIndexScanMulti { table, .. } => {
                 tables.push(table.clone());
@@ -576,6 +591,7 @@ impl JoinReorder {
             LogicalPlan::Scan { .. } => 1000,  // Default table size
             LogicalPlan::IndexGet { .. } => 1, // Point lookup
             LogicalPlan::IndexInGet { keys, .. } => keys.len(), // Multi-point lookup
+            LogicalPlan::FetchByIds { ids, .. } => ids.len(), // Row id lookup
             LogicalPlan::IndexScan { .. } => 100, // Range scan
             LogicalPlan::Filter { input, .. } => {
                 // Assume 10% selectivity
@@ -744,6 +760,52 @@ mod tests {
         assert!(matches!(optimized, LogicalPlan::Join { .. }));
     }
 
+    #[test]
+    fn test_reorder_preserves_join_algorithm_hint() {
+        let ctx = create_test_context();
+        let pass = JoinReorder::with_context(ctx);
+
+        let plan = LogicalPlan::join(
+            LogicalPlan::join(
+                LogicalPlan::scan("large"),
+                LogicalPlan::scan("small"),
+                Expr::eq(
+                    Expr::column("large", "id", 0),
+                    Expr::column("small", "large_id", 0),
+                ),
+                JoinType::Inner,
+            )
+            .with_join_algorithm(Some(JoinAlgorithm::SortMerge)),
+            LogicalPlan::scan("medium"),
+            Expr::eq(
+                Expr::column("small", "id", 0),
+                Expr::column("medium", "small_id", 0),
+            ),
+            JoinType::Inner,
+        );
+
+        fn collect_hints(plan: &LogicalPlan, hints: &mut Vec<(String, JoinAlgorithm)>) {
+            if let LogicalPlan::Join {
+                condition,
+                algorithm: Some(algorithm),
+                ..
+            } = plan
+            {
+                hints.push((alloc::format!("{:?}", condition), *algorithm));
+            }
+            for input in plan.inputs() {
+                collect_hints(input, hints);
+            }
+        }
+
+        let optimized = pass.optimize(plan);
+        let mut hints = Vec::new();
+        collect_hints(&optimized, &mut hints);
+        assert_eq!(hints.len(), 1);
+        assert!(hints[0].0.contains("large_id"));
+        assert_eq!(hints[0].1, JoinAlgorithm::SortMerge);
+    }
+
     #[test]
     fn test_single_table_unchanged() {
         let pass = JoinReorder::new();
@@ -765,6 +827,7 @@ mod tests {
                 condition: Expr::eq(Expr::column("a", "id", 0), Expr::column("b", "a_id", 0)),
                 join_type: JoinType::Inner,
                 output_tables: alloc::vec!["a".into(), "b".into()],
+                algorithm: None,
             },
             Expr::gt(Expr::column("a", "value", 1), Expr::literal(100i64)),
         );
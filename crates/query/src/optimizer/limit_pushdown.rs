@@ -0,0 +1,365 @@
+//! Limit pushdown through projections.
+//!
+//! A projection maps each input row to exactly one output row, so a `Limit`
+//! above it can be applied to the projection's input instead. Fewer rows are
+//! then projected, and the `Limit` ends up next to the operator that produces
+//! the rows, where later passes can fold it further (e.g. `TopNPushdown` turns
+//! `Limit -> Sort` into `TopN`, `LimitSkipByIndexPass` pushes it into a scan).
+//!
+//! Example:
+//! ```text
+//! Limit(10, 5)                =>    Project(columns)
+//!      |                                  |
+//! Project(columns)                  Limit(10, 5)
+//!      |                                  |
+//!   input                               input
+//! ```
+//!
+//! DISTINCT is planned as an aggregate rather than as part of `Project`, so
+//! every projection preserves cardinality. Projections that evaluate
+//! aggregate expressions are left alone.
+
+use crate::ast::Expr;
+use crate::planner::PhysicalPlan;
+use alloc::boxed::Box;
+
+/// Pushes `Limit` below `Project`.
+pub struct LimitPushdown;
+
+impl LimitPushdown {
+    /// Creates a new LimitPushdown pass.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Optimizes the physical plan by moving limits below projections.
+    pub fn optimize(&self, plan: PhysicalPlan) -> PhysicalPlan {
+        self.traverse(plan)
+    }
+
+    fn traverse(&self, plan: PhysicalPlan) -> PhysicalPlan {
+        match plan {
+            PhysicalPlan::Limit {
+                input,
+                limit,
+                offset,
+            } => Self::push_limit(self.traverse(*input), limit, offset),
+
+            // Recursively process other nodes
+            PhysicalPlan::Filter { input, predicate } => PhysicalPlan::Filter {
+                input: Box::new(self.traverse(*input)),
+                predicate,
+            },
+
+            PhysicalPlan::Project { input, columns } => PhysicalPlan::Project {
+                input: Box::new(self.traverse(*input)),
+                columns,
+            },
+
+            PhysicalPlan::Sort { input, order_by } => PhysicalPlan::Sort {
+                input: Box::new(self.traverse(*input)),
+                order_by,
+            },
+
+            PhysicalPlan::TopN {
+                input,
+                order_by,
+                limit,
+                offset,
+            } => PhysicalPlan::TopN {
+                input: Box::new(self.traverse(*input)),
+                order_by,
+                limit,
+                offset,
+            },
+
+            PhysicalPlan::HashJoin {
+                left,
+                right,
+                condition,
+                join_type,
+                output_tables,
+                pinned,
+            } => PhysicalPlan::HashJoin {
+                left: Box::new(self.traverse(*left)),
+                right: Box::new(self.traverse(*right)),
+                condition,
+                join_type,
+                output_tables,
+                pinned,
+            },
+
+            PhysicalPlan::SortMergeJoin {
+                left,
+                right,
+                condition,
+                join_type,
+                output_tables,
+            } => PhysicalPlan::SortMergeJoin {
+                left: Box::new(self.traverse(*left)),
+                right: Box::new(self.traverse(*right)),
+                condition,
+                join_type,
+                output_tables,
+            },
+
+            PhysicalPlan::NestedLoopJoin {
+                left,
+                right,
+                condition,
+                join_type,
+                output_tables,
+                pinned,
+            } => PhysicalPlan::NestedLoopJoin {
+                left: Box::new(self.traverse(*left)),
+                right: Box::new(self.traverse(*right)),
+                condition,
+                join_type,
+                output_tables,
+                pinned,
+            },
+
+            PhysicalPlan::IndexNestedLoopJoin {
+                outer,
+                inner_table,
+                inner_index,
+                condition,
+                join_type,
+                outer_is_left,
+                output_tables,
+            } => PhysicalPlan::IndexNestedLoopJoin {
+                outer: Box::new(self.traverse(*outer)),
+                inner_table,
+                inner_index,
+                condition,
+                join_type,
+                outer_is_left,
+                output_tables,
+            },
+
+            PhysicalPlan::HashAggregate {
+                input,
+                group_by,
+                aggregates,
+            } => PhysicalPlan::HashAggregate {
+                input: Box::new(self.traverse(*input)),
+                group_by,
+                aggregates,
+            },
+
+            PhysicalPlan::CrossProduct { left, right } => PhysicalPlan::CrossProduct {
+                left: Box::new(self.traverse(*left)),
+                right: Box::new(self.traverse(*right)),
+            },
+
+            PhysicalPlan::Union { left, right, all } => PhysicalPlan::Union {
+                left: Box::new(self.traverse(*left)),
+                right: Box::new(self.traverse(*right)),
+                all,
+            },
+
+            PhysicalPlan::NoOp { input } => PhysicalPlan::NoOp {
+                input: Box::new(self.traverse(*input)),
+            },
+
+            // Leaf nodes - no transformation
+            plan @ (PhysicalPlan::TableScan { .. }
+            | PhysicalPlan::IndexScan { .. }
+            | PhysicalPlan::IndexGet { .. }
+            | PhysicalPlan::IndexInGet { .. }
+            | PhysicalPlan::FetchByIds { .. }
+            | PhysicalPlan::GinIndexScan { .. }
+            | PhysicalPlan::GinIndexScanMulti { .. }
+            | PhysicalPlan::Empty) => plan,
+        }
+    }
+
+    /// Places `Limit(limit, offset)` above `input`, as far below any
+    /// projections at the top of `input` as possible.
+    fn push_limit(input: PhysicalPlan, limit: usize, offset: usize) -> PhysicalPlan {
+        match input {
+            PhysicalPlan::Project { input, columns }
+                if !columns
+                    .iter()
+                    .any(|column| matches!(column, Expr::Aggregate { .. })) =>
+            {
+                PhysicalPlan::Project {
+                    input: Box::new(Self::push_limit(*input, limit, offset)),
+                    columns,
+                }
+            }
+            input => PhysicalPlan::Limit {
+                input: Box::new(input),
+                limit,
+                offset,
+            },
+        }
+    }
+}
+
+impl Default for LimitPushdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::SortOrder;
+    use crate::executor::{InMemoryDataSource, PhysicalPlanRunner};
+    use crate::optimizer::TopNPushdown;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use cynos_core::{Row, Value};
+
+    fn project_name(input: PhysicalPlan) -> PhysicalPlan {
+        PhysicalPlan::project(input, vec![Expr::column("users", "name", 1)])
+    }
+
+    #[test]
+    fn test_limit_pushed_below_project() {
+        let plan = PhysicalPlan::limit(project_name(PhysicalPlan::table_scan("users")), 10, 5);
+
+        let result = LimitPushdown::new().optimize(plan);
+
+        let PhysicalPlan::Project { input, columns } = result else {
+            panic!("Expected Project, got {:?}", result);
+        };
+        assert_eq!(columns.len(), 1);
+        assert!(matches!(
+            *input,
+            PhysicalPlan::Limit {
+                limit: 10,
+                offset: 5,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_limit_pushed_below_nested_projects_and_into_topn() {
+        let sorted = PhysicalPlan::sort(
+            PhysicalPlan::table_scan("users"),
+            vec![(Expr::column("users", "id", 0), SortOrder::Desc)],
+        );
+        let plan = PhysicalPlan::limit(
+            PhysicalPlan::project(project_name(sorted), vec![Expr::column("users", "name", 0)]),
+            3,
+            0,
+        );
+
+        let result = TopNPushdown::new().optimize(LimitPushdown::new().optimize(plan));
+
+        let PhysicalPlan::Project { input, .. } = result else {
+            panic!("Expected Project, got {:?}", result);
+        };
+        let PhysicalPlan::Project { input, .. } = *input else {
+            panic!("Expected inner Project, got {:?}", input);
+        };
+        assert!(matches!(*input, PhysicalPlan::TopN { limit: 3, .. }));
+    }
+
+    #[test]
+    fn test_limit_stops_above_filter() {
+        let mut ds = InMemoryDataSource::new();
+        let rows = (0..30)
+            .map(|id| {
+                Row::new(
+                    id,
+                    vec![
+                        Value::Int64(id as i64),
+                        Value::String(alloc::format!("u{}", id)),
+                    ],
+                )
+            })
+            .collect();
+        ds.add_table("users", rows, 2);
+        let runner = PhysicalPlanRunner::new(&ds);
+
+        let filtered = PhysicalPlan::filter(
+            PhysicalPlan::table_scan("users"),
+            Expr::gt(
+                Expr::column("users", "id", 0),
+                Expr::literal(Value::Int64(12)),
+            ),
+        );
+        let plan = PhysicalPlan::limit(project_name(filtered), 5, 2);
+        let optimized = LimitPushdown::new().optimize(plan.clone());
+
+        // Limiting before the filter would drop rows the filter keeps, so
+        // the limit lands directly above it.
+        let PhysicalPlan::Project { input, .. } = &optimized else {
+            panic!("Expected Project, got {:?}", optimized);
+        };
+        let PhysicalPlan::Limit { input, .. } = input.as_ref() else {
+            panic!("Expected Limit, got {:?}", input);
+        };
+        assert!(matches!(input.as_ref(), PhysicalPlan::Filter { .. }));
+
+        let names = |plan: &PhysicalPlan| -> Vec<Value> {
+            runner
+                .execute(plan)
+                .unwrap()
+                .entries
+                .iter()
+                .map(|entry| entry.row.values()[0].clone())
+                .collect()
+        };
+        let expected: Vec<Value> = (15..20)
+            .map(|id| Value::String(alloc::format!("u{}", id)))
+            .collect();
+        assert_eq!(names(&plan), expected);
+        assert_eq!(names(&optimized), expected);
+    }
+
+    #[test]
+    fn test_limit_kept_above_aggregate_projection() {
+        let plan = PhysicalPlan::limit(
+            PhysicalPlan::project(
+                PhysicalPlan::table_scan("users"),
+                vec![Expr::count(Expr::column("users", "id", 0))],
+            ),
+            1,
+            0,
+        );
+
+        let result = LimitPushdown::new().optimize(plan);
+        assert!(matches!(result, PhysicalPlan::Limit { .. }));
+    }
+
+    #[test]
+    fn test_limit_pushdown_preserves_results() {
+        let mut ds = InMemoryDataSource::new();
+        let rows = (0..20)
+            .map(|id| {
+                Row::new(
+                    id,
+                    vec![
+                        Value::Int64(id as i64),
+                        Value::String(alloc::format!("u{}", id)),
+                    ],
+                )
+            })
+            .collect();
+        ds.add_table("users", rows, 2);
+        let runner = PhysicalPlanRunner::new(&ds);
+
+        let plan = PhysicalPlan::limit(project_name(PhysicalPlan::table_scan("users")), 4, 7);
+        let optimized = LimitPushdown::new().optimize(plan.clone());
+        assert!(matches!(optimized, PhysicalPlan::Project { .. }));
+
+        let values = |plan: &PhysicalPlan| -> Vec<Vec<Value>> {
+            runner
+                .execute(plan)
+                .unwrap()
+                .entries
+                .iter()
+                .map(|entry| entry.row.values().to_vec())
+                .collect()
+        };
+        let expected = values(&plan);
+        assert_eq!(expected.len(), 4);
+        assert_eq!(values(&optimized), expected);
+    }
+}
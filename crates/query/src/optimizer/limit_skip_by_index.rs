@@ -106,12 +106,14 @@ impl<'a> LimitSkipByIndexPass<'a> {
                 condition,
                 join_type,
                 output_tables,
+                pinned,
             } => PhysicalPlan::HashJoin {
                 left: Box::new(self.traverse(*left)),
                 right: Box::new(self.traverse(*right)),
                 condition,
                 join_type,
                 output_tables,
+                pinned,
             },
 
             PhysicalPlan::SortMergeJoin {
@@ -134,12 +136,14 @@ impl<'a> LimitSkipByIndexPass<'a> {
                 condition,
                 join_type,
                 output_tables,
+                pinned,
             } => PhysicalPlan::NestedLoopJoin {
                 left: Box::new(self.traverse(*left)),
                 right: Box::new(self.traverse(*right)),
                 condition,
                 join_type,
                 output_tables,
+                pinned,
             },
 
             PhysicalPlan::IndexNestedLoopJoin {
@@ -184,6 +188,7 @@ impl<'a> LimitSkipByIndexPass<'a> {
             | PhysicalPlan::IndexScan { .. }
             | PhysicalPlan::IndexGet { .. }
             | PhysicalPlan::IndexInGet { .. }
+            | PhysicalPlan::FetchByIds { .. }
             | PhysicalPlan::GinIndexScan { .. }
             | PhysicalPlan::GinIndexScanMulti { .. }
             | PhysicalPlan::Empty) => plan,
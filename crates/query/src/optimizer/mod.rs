@@ -1,12 +1,14 @@
 //! Query optimizer module.
 
 mod and_predicate;
+mod count_to_not_exists;
 mod cross_product;
 mod get_row_count;
 mod implicit_joins;
 mod index_join;
 mod index_selection;
 mod join_reorder;
+mod limit_pushdown;
 mod limit_skip_by_index;
 mod multi_column_or;
 mod not_simplification;
@@ -14,15 +16,18 @@ mod order_by_index;
 mod outer_join_simplification;
 mod pass;
 mod predicate_pushdown;
+mod projection_elimination;
 mod topn_pushdown;
 
 pub use and_predicate::AndPredicatePass;
+pub use count_to_not_exists::CountToNotExists;
 pub use cross_product::CrossProductPass;
 pub use get_row_count::{GetRowCountPass, GetRowCountPlan};
 pub use implicit_joins::ImplicitJoinsPass;
 pub use index_join::IndexJoinPass;
-pub use index_selection::IndexSelection;
+pub use index_selection::{predicate_to_range, IndexSelection};
 pub use join_reorder::JoinReorder;
+pub use limit_pushdown::LimitPushdown;
 pub use limit_skip_by_index::LimitSkipByIndexPass;
 pub use multi_column_or::{MultiColumnOrConfig, MultiColumnOrPass};
 pub use not_simplification::NotSimplification;
@@ -30,6 +35,7 @@ pub use order_by_index::OrderByIndexPass;
 pub use outer_join_simplification::OuterJoinSimplification;
 pub use pass::OptimizerPass;
 pub use predicate_pushdown::PredicatePushdown;
+pub use projection_elimination::ProjectionElimination;
 pub use topn_pushdown::TopNPushdown;
 
 use crate::planner::{JoinAlgorithm, LogicalPlan, PhysicalPlan};
@@ -123,6 +129,8 @@ impl Optimizer {
                 PhysicalPlan::index_in_get(table, index, keys)
             }
 
+            LogicalPlan::FetchByIds { table, ids } => PhysicalPlan::fetch_by_ids(table, ids),
+
             LogicalPlan::GinIndexScan {
                 table,
                 index,
@@ -192,14 +200,15 @@ impl Optimizer {
                 condition,
                 join_type,
                 output_tables,
+                algorithm: hint,
             } => {
                 let left_physical = self.logical_to_physical(*left);
                 let right_physical = self.logical_to_physical(*right);
 
                 // Choose join algorithm based on condition
-                let algorithm = self.choose_join_algorithm(&condition);
+                let algorithm = self.choose_join_algorithm(&condition, hint);
 
-                match algorithm {
+                let join = match algorithm {
                     JoinAlgorithm::Hash => PhysicalPlan::hash_join_with_output_tables(
                         left_physical,
                         right_physical,
@@ -223,6 +232,13 @@ impl Optimizer {
                             output_tables,
                         )
                     }
+                };
+                // A hinted join keeps its algorithm. An index join hint plans a
+                // nested loop join that the index join pass may still convert.
+                if hint.is_some_and(|hint| hint != JoinAlgorithm::IndexNestedLoop) {
+                    join.pin_join_algorithm()
+                } else {
+                    join
                 }
             }
 
@@ -268,9 +284,18 @@ impl Optimizer {
         }
     }
 
-    fn choose_join_algorithm(&self, condition: &crate::ast::Expr) -> JoinAlgorithm {
+    fn choose_join_algorithm(
+        &self,
+        condition: &crate::ast::Expr,
+        hint: Option<JoinAlgorithm>,
+    ) -> JoinAlgorithm {
+        // An explicit hint wins when the algorithm can evaluate the condition
+        if let Some(hint) = hint.filter(|hint| hint.supports_condition(condition)) {
+            return hint;
+        }
+
         // For equi-joins, prefer hash join
-        if condition.is_equi_join() {
+        if condition.is_equi_join() || condition.is_null_safe_equi_join() {
             return JoinAlgorithm::Hash;
         }
 
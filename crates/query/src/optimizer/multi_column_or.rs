@@ -139,12 +139,14 @@ impl<'a> MultiColumnOrPass<'a> {
                 condition,
                 join_type,
                 output_tables,
+                pinned,
             } => PhysicalPlan::HashJoin {
                 left: Box::new(self.traverse(*left)),
                 right: Box::new(self.traverse(*right)),
                 condition,
                 join_type,
                 output_tables,
+                pinned,
             },
 
             PhysicalPlan::SortMergeJoin {
@@ -167,12 +169,14 @@ impl<'a> MultiColumnOrPass<'a> {
                 condition,
                 join_type,
                 output_tables,
+                pinned,
             } => PhysicalPlan::NestedLoopJoin {
                 left: Box::new(self.traverse(*left)),
                 right: Box::new(self.traverse(*right)),
                 condition,
                 join_type,
                 output_tables,
+                pinned,
             },
 
             PhysicalPlan::IndexNestedLoopJoin {
@@ -235,6 +239,7 @@ impl<'a> MultiColumnOrPass<'a> {
             | PhysicalPlan::IndexScan { .. }
             | PhysicalPlan::IndexGet { .. }
             | PhysicalPlan::IndexInGet { .. }
+            | PhysicalPlan::FetchByIds { .. }
             | PhysicalPlan::GinIndexScan { .. }
             | PhysicalPlan::GinIndexScanMulti { .. }
             | PhysicalPlan::Empty) => plan,
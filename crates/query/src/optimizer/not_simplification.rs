@@ -39,12 +39,14 @@ impl NotSimplification {
                 condition,
                 join_type,
                 output_tables,
+                algorithm,
             } => LogicalPlan::Join {
                 left: Box::new(self.simplify_plan(*left)),
                 right: Box::new(self.simplify_plan(*right)),
                 condition: self.simplify_expr(condition),
                 join_type,
                 output_tables,
+                algorithm,
             },
             LogicalPlan::Project { input, columns } => LogicalPlan::Project {
                 input: Box::new(self.simplify_plan(*input)),
@@ -156,6 +158,11 @@ impl NotSimplification {
                 args: args.into_iter().map(|e| self.simplify_expr(e)).collect(),
             },
 
+            Expr::Cast { expr, target } => Expr::Cast {
+                expr: Box::new(self.simplify_expr(*expr)),
+                target,
+            },
+
             // Recursively simplify aggregates
             Expr::Aggregate {
                 func,
@@ -102,12 +102,14 @@ impl<'a> OrderByIndexPass<'a> {
                 condition,
                 join_type,
                 output_tables,
+                pinned,
             } => PhysicalPlan::HashJoin {
                 left: Box::new(self.traverse(*left)),
                 right: Box::new(self.traverse(*right)),
                 condition,
                 join_type,
                 output_tables,
+                pinned,
             },
             PhysicalPlan::SortMergeJoin {
                 left,
@@ -128,12 +130,14 @@ impl<'a> OrderByIndexPass<'a> {
                 condition,
                 join_type,
                 output_tables,
+                pinned,
             } => PhysicalPlan::NestedLoopJoin {
                 left: Box::new(self.traverse(*left)),
                 right: Box::new(self.traverse(*right)),
                 condition,
                 join_type,
                 output_tables,
+                pinned,
             },
             PhysicalPlan::IndexNestedLoopJoin {
                 outer,
@@ -186,6 +190,7 @@ impl<'a> OrderByIndexPass<'a> {
             | PhysicalPlan::IndexScan { .. }
             | PhysicalPlan::IndexGet { .. }
             | PhysicalPlan::IndexInGet { .. }
+            | PhysicalPlan::FetchByIds { .. }
             | PhysicalPlan::GinIndexScan { .. }
             | PhysicalPlan::GinIndexScanMulti { .. }
             | PhysicalPlan::Empty) => plan,
@@ -630,6 +635,7 @@ mod tests {
                 ),
                 join_type: JoinType::Inner,
                 output_tables: alloc::vec!["users".into(), "orders".into()],
+                pinned: false,
             }),
             order_by: alloc::vec![(Expr::column("users", "id", 0), SortOrder::Asc)],
         };
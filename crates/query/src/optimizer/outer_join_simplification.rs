@@ -59,6 +59,7 @@ impl OuterJoinSimplification {
                     condition,
                     join_type,
                     output_tables,
+                    algorithm,
                 } = optimized_input
                 {
                     if let Some(new_join_type) =
@@ -71,6 +72,7 @@ impl OuterJoinSimplification {
                                 condition,
                                 join_type: new_join_type,
                                 output_tables: output_tables.clone(),
+                                algorithm,
                             }),
                             predicate,
                         };
@@ -84,6 +86,7 @@ impl OuterJoinSimplification {
                             condition,
                             join_type,
                             output_tables,
+                            algorithm,
                         }),
                         predicate,
                     };
@@ -106,12 +109,14 @@ impl OuterJoinSimplification {
                 condition,
                 join_type,
                 output_tables,
+                algorithm,
             } => LogicalPlan::Join {
                 left: Box::new(self.simplify(*left)),
                 right: Box::new(self.simplify(*right)),
                 condition,
                 join_type,
                 output_tables,
+                algorithm,
             },
 
             LogicalPlan::Aggregate {
@@ -155,6 +160,7 @@ impl OuterJoinSimplification {
             | LogicalPlan::IndexScan { .. }
             | LogicalPlan::IndexGet { .. }
             | LogicalPlan::IndexInGet { .. }
+            | LogicalPlan::FetchByIds { .. }
             | LogicalPlan::GinIndexScan { .. }
             | LogicalPlan::GinIndexScanMulti { .. }
             | LogicalPlan::Empty => plan,
@@ -206,8 +212,8 @@ impl OuterJoinSimplification {
                 None
             }
 
-            // Inner and Cross joins don't need simplification
-            JoinType::Inner | JoinType::Cross => None,
+            // Inner, Cross, Semi and Anti joins don't need simplification
+            JoinType::Inner | JoinType::Cross | JoinType::Semi | JoinType::Anti => None,
         }
     }
 
@@ -312,7 +318,9 @@ impl OuterJoinSimplification {
                 self.expr_references_tables(left, tables)
                     || self.expr_references_tables(right, tables)
             }
-            Expr::UnaryOp { expr, .. } => self.expr_references_tables(expr, tables),
+            Expr::UnaryOp { expr, .. } | Expr::Cast { expr, .. } => {
+                self.expr_references_tables(expr, tables)
+            }
             Expr::Function { args, .. } => args
                 .iter()
                 .any(|arg| self.expr_references_tables(arg, tables)),
@@ -342,7 +350,7 @@ impl OuterJoinSimplification {
             Expr::NotLike { expr, .. } => self.expr_references_tables(expr, tables),
             Expr::Match { expr, .. } => self.expr_references_tables(expr, tables),
             Expr::NotMatch { expr, .. } => self.expr_references_tables(expr, tables),
-            Expr::Literal(_) => false,
+            Expr::Literal(_) | Expr::TypedNull(_) => false,
         }
     }
 
@@ -361,6 +369,7 @@ impl OuterJoinSimplification {
             LogicalPlan::IndexScan { table, .. }
             | LogicalPlan::IndexGet { table, .. }
             | LogicalPlan::IndexInGet { table, .. }
+            | LogicalPlan::FetchByIds { table, .. }
             | LogicalPlan::GinIndexScan { table, .. }
             | LogicalPlan::GinIndexScanMulti { table, .. } => {
                 tables.insert(table.clone());
@@ -559,6 +568,7 @@ mod tests {
                 ),
                 join_type: JoinType::RightOuter,
                 output_tables: alloc::vec!["users".into(), "orders".into()],
+                algorithm: None,
             },
             Expr::eq(Expr::column("users", "active", 1), Expr::literal(true)),
         );
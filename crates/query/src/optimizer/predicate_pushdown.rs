@@ -14,7 +14,6 @@ use crate::optimizer::OptimizerPass;
 use crate::planner::LogicalPlan;
 use alloc::boxed::Box;
 use alloc::string::String;
-use alloc::vec::Vec;
 use hashbrown::HashSet;
 
 /// Predicate pushdown optimization.
@@ -49,12 +48,14 @@ impl PredicatePushdown {
                 condition,
                 join_type,
                 output_tables,
+                algorithm,
             } => LogicalPlan::Join {
                 left: Box::new(self.pushdown(*left)),
                 right: Box::new(self.pushdown(*right)),
                 condition,
                 join_type,
                 output_tables,
+                algorithm,
             },
 
             LogicalPlan::Aggregate {
@@ -98,6 +99,7 @@ impl PredicatePushdown {
             | LogicalPlan::IndexScan { .. }
             | LogicalPlan::IndexGet { .. }
             | LogicalPlan::IndexInGet { .. }
+            | LogicalPlan::FetchByIds { .. }
             | LogicalPlan::GinIndexScan { .. }
             | LogicalPlan::GinIndexScanMulti { .. }
             | LogicalPlan::Empty => plan,
@@ -123,20 +125,32 @@ impl PredicatePushdown {
             }
 
             // Push filter into join if predicate references only one side
-            LogicalPlan::Join {
-                left,
-                right,
-                condition,
-                join_type,
-                output_tables,
-            } => self.push_filter_into_join(
-                *left,
-                *right,
-                condition,
-                join_type,
-                output_tables,
-                predicate,
-            ),
+            join @ LogicalPlan::Join { .. } => self.push_filter_into_join(join, predicate),
+
+            // Likewise for a cross product, which keeps every row of both sides
+            LogicalPlan::CrossProduct { left, right } => {
+                let left_tables = self.extract_tables(&left);
+                let right_tables = self.extract_tables(&right);
+                let pred_tables = self.extract_predicate_tables(&predicate);
+                let refs_left = pred_tables.iter().any(|t| left_tables.contains(t));
+                let refs_right = pred_tables.iter().any(|t| right_tables.contains(t));
+                if refs_left && !refs_right {
+                    LogicalPlan::CrossProduct {
+                        left: Box::new(self.try_push_filter(*left, predicate)),
+                        right,
+                    }
+                } else if refs_right && !refs_left {
+                    LogicalPlan::CrossProduct {
+                        left,
+                        right: Box::new(self.try_push_filter(*right, predicate)),
+                    }
+                } else {
+                    LogicalPlan::Filter {
+                        input: Box::new(LogicalPlan::CrossProduct { left, right }),
+                        predicate,
+                    }
+                }
+            }
 
             // Can't push filter below aggregate
             LogicalPlan::Aggregate { .. } => LogicalPlan::Filter {
@@ -165,6 +179,7 @@ impl PredicatePushdown {
             | LogicalPlan::IndexScan { .. }
             | LogicalPlan::IndexGet { .. }
             | LogicalPlan::IndexInGet { .. }
+            | LogicalPlan::FetchByIds { .. }
             | LogicalPlan::GinIndexScan { .. }
             | LogicalPlan::GinIndexScanMulti { .. } => LogicalPlan::Filter {
                 input: Box::new(input),
@@ -188,15 +203,20 @@ impl PredicatePushdown {
     }
 
     /// Push filter into join based on which tables the predicate references.
-    fn push_filter_into_join(
-        &self,
-        left: LogicalPlan,
-        right: LogicalPlan,
-        condition: Expr,
-        join_type: JoinType,
-        output_tables: Vec<String>,
-        predicate: Expr,
-    ) -> LogicalPlan {
+    fn push_filter_into_join(&self, join: LogicalPlan, predicate: Expr) -> LogicalPlan {
+        let LogicalPlan::Join {
+            left,
+            right,
+            condition,
+            join_type,
+            output_tables,
+            algorithm,
+        } = join
+        else {
+            return LogicalPlan::filter(join, predicate);
+        };
+        let (left, right) = (*left, *right);
+
         // Extract tables referenced by each side of the join
         let left_tables = self.extract_tables(&left);
         let right_tables = self.extract_tables(&right);
@@ -219,6 +239,7 @@ impl PredicatePushdown {
                         condition,
                         join_type,
                         output_tables,
+                        algorithm,
                     }
                 } else if refs_right && !refs_left {
                     // Push to right side
@@ -228,6 +249,7 @@ impl PredicatePushdown {
                         condition,
                         join_type,
                         output_tables,
+                        algorithm,
                     }
                 } else {
                     // References both sides or neither - keep above join
@@ -238,16 +260,19 @@ impl PredicatePushdown {
                             condition,
                             join_type,
                             output_tables,
+                            algorithm,
                         }),
                         predicate,
                     }
                 }
             }
 
-            JoinType::LeftOuter => {
+            JoinType::LeftOuter | JoinType::Semi | JoinType::Anti => {
                 // For left outer join:
                 // - Can push predicates on LEFT side down (preserves NULL extension)
                 // - Cannot push predicates on RIGHT side (would filter out NULLs incorrectly)
+                // Semi- and anti-joins output only left columns, so the same
+                // split applies.
                 if refs_left && !refs_right {
                     LogicalPlan::Join {
                         left: Box::new(self.try_push_filter(left, predicate)),
@@ -255,6 +280,7 @@ impl PredicatePushdown {
                         condition,
                         join_type,
                         output_tables,
+                        algorithm,
                     }
                 } else {
                     // Keep above join
@@ -265,6 +291,7 @@ impl PredicatePushdown {
                             condition,
                             join_type,
                             output_tables,
+                            algorithm,
                         }),
                         predicate,
                     }
@@ -282,6 +309,7 @@ impl PredicatePushdown {
                         condition,
                         join_type,
                         output_tables,
+                        algorithm,
                     }
                 } else {
                     LogicalPlan::Filter {
@@ -291,6 +319,7 @@ impl PredicatePushdown {
                             condition,
                             join_type,
                             output_tables,
+                            algorithm,
                         }),
                         predicate,
                     }
@@ -306,6 +335,7 @@ impl PredicatePushdown {
                         condition,
                         join_type,
                         output_tables,
+                        algorithm,
                     }),
                     predicate,
                 }
@@ -328,6 +358,7 @@ impl PredicatePushdown {
             LogicalPlan::IndexScan { table, .. }
             | LogicalPlan::IndexGet { table, .. }
             | LogicalPlan::IndexInGet { table, .. }
+            | LogicalPlan::FetchByIds { table, .. }
             | LogicalPlan::GinIndexScan { table, .. }
             | LogicalPlan::GinIndexScanMulti { table, .. } => {
                 tables.insert(table.clone());
@@ -365,7 +396,7 @@ impl PredicatePushdown {
                 self.collect_expr_tables(left, tables);
                 self.collect_expr_tables(right, tables);
             }
-            Expr::UnaryOp { expr, .. } => {
+            Expr::UnaryOp { expr, .. } | Expr::Cast { expr, .. } => {
                 self.collect_expr_tables(expr, tables);
             }
             Expr::Function { args, .. } => {
@@ -412,7 +443,7 @@ impl PredicatePushdown {
             Expr::NotMatch { expr, .. } => {
                 self.collect_expr_tables(expr, tables);
             }
-            Expr::Literal(_) => {}
+            Expr::Literal(_) | Expr::TypedNull(_) => {}
         }
     }
 }
@@ -567,6 +598,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_push_filter_into_cross_product_side() {
+        let pass = PredicatePushdown;
+
+        let plan = LogicalPlan::filter(
+            LogicalPlan::cross_product(LogicalPlan::scan("users"), LogicalPlan::scan("orders")),
+            Expr::gt(Expr::column("orders", "amount", 2), Expr::literal(100i64)),
+        );
+
+        let optimized = pass.optimize(plan);
+
+        // Should be CrossProduct(Scan(users), Filter(Scan(orders)))
+        if let LogicalPlan::CrossProduct { left, right } = optimized {
+            assert!(matches!(*left, LogicalPlan::Scan { .. }));
+            assert!(matches!(*right, LogicalPlan::Filter { .. }));
+        } else {
+            panic!("Expected CrossProduct, got {:?}", optimized);
+        }
+    }
+
     #[test]
     fn test_left_join_push_to_left_only() {
         let pass = PredicatePushdown;
@@ -0,0 +1,298 @@
+//! Projection elimination optimization pass.
+//!
+//! This pass removes projections that do no work:
+//! - A projection of exactly its input's columns, in order, is dropped:
+//!   `Project([t.0, t.1, t.2], Scan(t))` → `Scan(t)` when `t` has three columns
+//! - A projection that only selects columns of another projection is merged
+//!   into it: `Project([p.1], Project([a, b], input))` → `Project([b], input)`
+//!
+//! Input widths come from the column counts in the execution context; without
+//! them only projections over other projections are simplified.
+
+use crate::ast::{ColumnRef, Expr};
+use crate::context::ExecutionContext;
+use crate::optimizer::OptimizerPass;
+use crate::planner::LogicalPlan;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Projection elimination pass.
+pub struct ProjectionElimination {
+    /// Execution context with table column counts.
+    context: Option<ExecutionContext>,
+}
+
+impl Default for ProjectionElimination {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProjectionElimination {
+    /// Creates a new projection elimination pass without context.
+    pub fn new() -> Self {
+        Self { context: None }
+    }
+
+    /// Creates a new projection elimination pass with execution context.
+    pub fn with_context(context: ExecutionContext) -> Self {
+        Self {
+            context: Some(context),
+        }
+    }
+}
+
+impl OptimizerPass for ProjectionElimination {
+    fn optimize(&self, plan: LogicalPlan) -> LogicalPlan {
+        self.eliminate(plan)
+    }
+
+    fn name(&self) -> &'static str {
+        "projection_elimination"
+    }
+}
+
+impl ProjectionElimination {
+    fn eliminate(&self, plan: LogicalPlan) -> LogicalPlan {
+        match plan {
+            LogicalPlan::Project { input, columns } => {
+                let input = self.eliminate(*input);
+                self.simplify_project(input, columns)
+            }
+
+            LogicalPlan::Filter { input, predicate } => LogicalPlan::Filter {
+                input: Box::new(self.eliminate(*input)),
+                predicate,
+            },
+
+            LogicalPlan::Join {
+                left,
+                right,
+                condition,
+                join_type,
+                output_tables,
+                algorithm,
+            } => LogicalPlan::Join {
+                left: Box::new(self.eliminate(*left)),
+                right: Box::new(self.eliminate(*right)),
+                condition,
+                join_type,
+                output_tables,
+                algorithm,
+            },
+
+            LogicalPlan::Aggregate {
+                input,
+                group_by,
+                aggregates,
+            } => LogicalPlan::Aggregate {
+                input: Box::new(self.eliminate(*input)),
+                group_by,
+                aggregates,
+            },
+
+            LogicalPlan::Sort { input, order_by } => LogicalPlan::Sort {
+                input: Box::new(self.eliminate(*input)),
+                order_by,
+            },
+
+            LogicalPlan::Limit {
+                input,
+                limit,
+                offset,
+            } => LogicalPlan::Limit {
+                input: Box::new(self.eliminate(*input)),
+                limit,
+                offset,
+            },
+
+            LogicalPlan::CrossProduct { left, right } => LogicalPlan::CrossProduct {
+                left: Box::new(self.eliminate(*left)),
+                right: Box::new(self.eliminate(*right)),
+            },
+
+            LogicalPlan::Union { left, right, all } => LogicalPlan::Union {
+                left: Box::new(self.eliminate(*left)),
+                right: Box::new(self.eliminate(*right)),
+                all,
+            },
+
+            // Leaf nodes
+            LogicalPlan::Scan { .. }
+            | LogicalPlan::IndexScan { .. }
+            | LogicalPlan::IndexGet { .. }
+            | LogicalPlan::IndexInGet { .. }
+            | LogicalPlan::FetchByIds { .. }
+            | LogicalPlan::GinIndexScan { .. }
+            | LogicalPlan::GinIndexScanMulti { .. }
+            | LogicalPlan::Empty => plan,
+        }
+    }
+
+    /// Simplifies `Project(columns)` over an already simplified `input`.
+    fn simplify_project(&self, input: LogicalPlan, columns: Vec<Expr>) -> LogicalPlan {
+        let tables = input.output_tables();
+        let Some(positions) = Self::column_positions(&columns, &tables) else {
+            return LogicalPlan::project(input, columns);
+        };
+
+        let is_identity = |width: usize| {
+            positions.len() == width && positions.iter().enumerate().all(|(i, &p)| i == p)
+        };
+
+        match input {
+            LogicalPlan::Project {
+                input: inner_input,
+                columns: inner_columns,
+            } => {
+                if is_identity(inner_columns.len()) {
+                    return LogicalPlan::Project {
+                        input: inner_input,
+                        columns: inner_columns,
+                    };
+                }
+                match positions
+                    .iter()
+                    .map(|&p| inner_columns.get(p).cloned())
+                    .collect::<Option<Vec<_>>>()
+                {
+                    Some(composed) => LogicalPlan::project(*inner_input, composed),
+                    None => LogicalPlan::project(
+                        LogicalPlan::project(*inner_input, inner_columns),
+                        columns,
+                    ),
+                }
+            }
+            input => {
+                if tables.len() == 1 && self.output_width(&input).is_some_and(is_identity) {
+                    input
+                } else {
+                    LogicalPlan::project(input, columns)
+                }
+            }
+        }
+    }
+
+    /// Returns the position each column reads in a row of a relation with
+    /// `tables`, or `None` if any column is not a plain column reference.
+    ///
+    /// Only positions within the first table are reported, which is where a
+    /// projection's output columns resolve.
+    fn column_positions(columns: &[Expr], tables: &[String]) -> Option<Vec<usize>> {
+        columns
+            .iter()
+            .map(|column| match column {
+                Expr::Column(ColumnRef { table, index, .. })
+                    if table.is_empty()
+                        || tables.first() == Some(table)
+                        || !tables.contains(table) =>
+                {
+                    Some(*index)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the number of columns in each row `plan` produces, if known.
+    fn output_width(&self, plan: &LogicalPlan) -> Option<usize> {
+        match plan {
+            LogicalPlan::Scan { table }
+            | LogicalPlan::IndexScan { table, .. }
+            | LogicalPlan::IndexGet { table, .. }
+            | LogicalPlan::IndexInGet { table, .. }
+            | LogicalPlan::FetchByIds { table, .. }
+            | LogicalPlan::GinIndexScan { table, .. }
+            | LogicalPlan::GinIndexScanMulti { table, .. } => {
+                self.context.as_ref()?.column_count(table)
+            }
+            LogicalPlan::Filter { input, .. }
+            | LogicalPlan::Sort { input, .. }
+            | LogicalPlan::Limit { input, .. } => self.output_width(input),
+            LogicalPlan::Project { columns, .. } => Some(columns.len()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn users_ctx() -> ExecutionContext {
+        let mut ctx = ExecutionContext::new();
+        ctx.register_column_count("users", 3);
+        ctx
+    }
+
+    fn col(index: usize) -> Expr {
+        Expr::column("users", ["id", "name", "dept_id"][index], index)
+    }
+
+    #[test]
+    fn test_identity_projection_is_removed() {
+        let pass = ProjectionElimination::with_context(users_ctx());
+        let plan = LogicalPlan::project(
+            LogicalPlan::filter(
+                LogicalPlan::scan("users"),
+                Expr::gt(col(0), Expr::literal(cynos_core::Value::Int64(1))),
+            ),
+            vec![col(0), col(1), col(2)],
+        );
+
+        assert!(matches!(pass.optimize(plan), LogicalPlan::Filter { .. }));
+
+        // Reordered or partial projections stay.
+        for columns in [vec![col(1), col(0), col(2)], vec![col(0), col(1)]] {
+            let plan = LogicalPlan::project(LogicalPlan::scan("users"), columns);
+            assert!(matches!(pass.optimize(plan), LogicalPlan::Project { .. }));
+        }
+
+        // Without the table width the projection cannot be proven identity.
+        let plan = LogicalPlan::project(LogicalPlan::scan("users"), vec![col(0), col(1), col(2)]);
+        assert!(matches!(
+            ProjectionElimination::new().optimize(plan),
+            LogicalPlan::Project { .. }
+        ));
+    }
+
+    #[test]
+    fn test_stacked_projections_collapse() {
+        let pass = ProjectionElimination::new();
+        let upper_name = Expr::Function {
+            name: "upper".into(),
+            args: vec![col(1)],
+        };
+        // SELECT dept_id, UPPER(name) ... then pick the second, then the first
+        let plan = LogicalPlan::project(
+            LogicalPlan::project(LogicalPlan::scan("users"), vec![col(2), upper_name.clone()]),
+            vec![Expr::column("users", "upper_name", 1), col(0)],
+        );
+
+        match pass.optimize(plan) {
+            LogicalPlan::Project { input, columns } => {
+                assert!(matches!(*input, LogicalPlan::Scan { .. }));
+                assert_eq!(columns.len(), 2);
+                assert!(matches!(&columns[0], Expr::Function { name, .. } if name == "upper"));
+                assert!(matches!(&columns[1], Expr::Column(c) if c.index == 2));
+            }
+            other => panic!("expected one Project, got {:?}", other),
+        }
+
+        // An identity projection over a projection leaves just the inner one.
+        let plan = LogicalPlan::project(
+            LogicalPlan::project(LogicalPlan::scan("users"), vec![col(2), col(0)]),
+            vec![col(0), col(1)],
+        );
+        match pass.optimize(plan) {
+            LogicalPlan::Project { input, columns } => {
+                assert!(matches!(*input, LogicalPlan::Scan { .. }));
+                assert!(matches!(&columns[0], Expr::Column(c) if c.index == 2));
+                assert!(matches!(&columns[1], Expr::Column(c) if c.index == 0));
+            }
+            other => panic!("expected one Project, got {:?}", other),
+        }
+    }
+}
@@ -160,12 +160,14 @@ impl TopNPushdown {
                 condition,
                 join_type,
                 output_tables,
+                pinned,
             } => PhysicalPlan::HashJoin {
                 left: Box::new(self.traverse(*left)),
                 right: Box::new(self.traverse(*right)),
                 condition,
                 join_type,
                 output_tables,
+                pinned,
             },
 
             PhysicalPlan::SortMergeJoin {
@@ -188,12 +190,14 @@ impl TopNPushdown {
                 condition,
                 join_type,
                 output_tables,
+                pinned,
             } => PhysicalPlan::NestedLoopJoin {
                 left: Box::new(self.traverse(*left)),
                 right: Box::new(self.traverse(*right)),
                 condition,
                 join_type,
                 output_tables,
+                pinned,
             },
 
             PhysicalPlan::IndexNestedLoopJoin {
@@ -244,6 +248,7 @@ impl TopNPushdown {
             | PhysicalPlan::IndexScan { .. }
             | PhysicalPlan::IndexGet { .. }
             | PhysicalPlan::IndexInGet { .. }
+            | PhysicalPlan::FetchByIds { .. }
             | PhysicalPlan::GinIndexScan { .. }
             | PhysicalPlan::GinIndexScanMulti { .. }
             | PhysicalPlan::Empty) => plan,
@@ -433,6 +438,7 @@ mod tests {
             ),
             join_type: crate::ast::JoinType::Inner,
             output_tables: alloc::vec!["orders".into(), "users".into()],
+            pinned: false,
         };
 
         let result = pass.optimize(plan);
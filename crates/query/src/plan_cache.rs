@@ -83,6 +83,13 @@ fn hash_logical_plan<H: Hasher>(plan: &LogicalPlan, hasher: &mut H) {
                 hash_value(key, hasher);
             }
         }
+        LogicalPlan::FetchByIds { table, ids } => {
+            hasher.write(b"fetch_by_ids");
+            hasher.write(table.as_bytes());
+            for id in ids {
+                hasher.write_u64(*id);
+            }
+        }
         LogicalPlan::GinIndexScan {
             table,
             index,
@@ -144,6 +151,7 @@ fn hash_logical_plan<H: Hasher>(plan: &LogicalPlan, hasher: &mut H) {
             condition,
             join_type,
             output_tables,
+            algorithm,
         } => {
             hasher.write(b"join");
             hash_logical_plan(left, hasher);
@@ -153,6 +161,10 @@ fn hash_logical_plan<H: Hasher>(plan: &LogicalPlan, hasher: &mut H) {
             for table in output_tables {
                 hasher.write(table.as_bytes());
             }
+            match algorithm {
+                Some(algorithm) => hasher.write(&[1, *algorithm as u8]),
+                None => hasher.write(&[0]),
+            }
         }
         LogicalPlan::Aggregate {
             input,
@@ -304,6 +316,10 @@ fn hash_expr<H: Hasher>(expr: &Expr, hasher: &mut H) {
             hasher.write(b"lit");
             hash_value(v, hasher);
         }
+        Expr::TypedNull(data_type) => {
+            hasher.write(b"null");
+            hasher.write(&[*data_type as u8]);
+        }
         Expr::BinaryOp { left, op, right } => {
             hasher.write(b"binop");
             hasher.write(&[*op as u8]);
@@ -315,6 +331,11 @@ fn hash_expr<H: Hasher>(expr: &Expr, hasher: &mut H) {
             hasher.write(&[*op as u8]);
             hash_expr(expr, hasher);
         }
+        Expr::Cast { expr, target } => {
+            hasher.write(b"cast");
+            hasher.write(&[*target as u8]);
+            hash_expr(expr, hasher);
+        }
         Expr::Function { name, args } => {
             hasher.write(b"func");
             hasher.write(name.as_bytes());
@@ -422,6 +443,12 @@ fn hash_value<H: Hasher>(value: &cynos_core::Value, hasher: &mut H) {
             let s = format!("{:?}", j);
             hasher.write(s.as_bytes());
         }
+        Value::Decimal(d) => {
+            hasher.write(b"dec");
+            let d = d.normalize();
+            hasher.write(&d.mantissa().to_le_bytes());
+            hasher.write(&d.scale().to_le_bytes());
+        }
     }
 }
 
@@ -798,6 +825,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_plan_fingerprint_different_join_algorithms() {
+        let join = LogicalPlan::inner_join(
+            LogicalPlan::scan("users"),
+            LogicalPlan::scan("orders"),
+            Expr::eq(
+                Expr::column("users", "id", 0),
+                Expr::column("orders", "user_id", 1),
+            ),
+        );
+        let hinted = join
+            .clone()
+            .with_join_algorithm(Some(crate::planner::JoinAlgorithm::SortMerge));
+
+        assert_ne!(
+            compute_plan_fingerprint(&join),
+            compute_plan_fingerprint(&hinted)
+        );
+    }
+
     #[test]
     fn test_cache_basic() {
         let mut cache = PlanCache::new(10);
@@ -1102,6 +1149,7 @@ mod tests {
             condition: Expr::Literal(Value::Boolean(true)),
             join_type: JoinType::Inner,
             output_tables: alloc::vec!["users".into(), "orders".into()],
+            pinned: false,
         };
 
         let products_plan = PhysicalPlan::table_scan("products");
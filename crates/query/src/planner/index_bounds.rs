@@ -6,6 +6,7 @@ use cynos_index::KeyRange;
 
 /// Bounds for an index scan.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum IndexBounds {
     /// Full index scan.
     Unbounded,
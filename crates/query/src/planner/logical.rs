@@ -2,11 +2,11 @@
 
 use crate::ast::JoinType;
 use crate::ast::{AggregateFunc, Expr, SortOrder};
-use crate::planner::IndexBounds;
+use crate::planner::{IndexBounds, JoinAlgorithm};
 use alloc::boxed::Box;
 use alloc::string::String;
 use alloc::vec::Vec;
-use cynos_core::Value;
+use cynos_core::{RowId, Value};
 
 /// Logical query plan node.
 #[derive(Clone, Debug)]
@@ -36,6 +36,9 @@ pub enum LogicalPlan {
         keys: Vec<Value>,
     },
 
+    /// Direct lookup of specific row ids, returned in the order given.
+    FetchByIds { table: String, ids: Vec<RowId> },
+
     /// GIN index scan for JSONB queries.
     GinIndexScan {
         table: String,
@@ -86,6 +89,8 @@ pub enum LogicalPlan {
         condition: Expr,
         join_type: JoinType,
         output_tables: Vec<String>,
+        /// Join algorithm requested by the caller, overriding the planner's choice.
+        algorithm: Option<JoinAlgorithm>,
     },
 
     /// Aggregation (GROUP BY).
@@ -133,6 +138,14 @@ impl LogicalPlan {
         }
     }
 
+    /// Creates a row id lookup plan.
+    pub fn fetch_by_ids(table: impl Into<String>, ids: Vec<RowId>) -> Self {
+        LogicalPlan::FetchByIds {
+            table: table.into(),
+            ids,
+        }
+    }
+
     /// Creates a filter plan.
     pub fn filter(input: LogicalPlan, predicate: Expr) -> Self {
         LogicalPlan::Filter {
@@ -156,7 +169,7 @@ impl LogicalPlan {
         condition: Expr,
         join_type: JoinType,
     ) -> Self {
-        let output_tables = Self::combined_output_tables(&left, &right);
+        let output_tables = Self::join_output_tables(&left, &right, join_type);
         Self::join_with_output_tables(left, right, condition, join_type, output_tables)
     }
 
@@ -174,7 +187,19 @@ impl LogicalPlan {
             condition,
             join_type,
             output_tables,
+            algorithm: None,
+        }
+    }
+
+    /// Sets the join algorithm requested for this join node.
+    ///
+    /// Has no effect on non-join plans. The planner falls back to its own
+    /// choice if the algorithm does not support the join condition.
+    pub fn with_join_algorithm(mut self, hint: Option<JoinAlgorithm>) -> Self {
+        if let LogicalPlan::Join { algorithm, .. } = &mut self {
+            *algorithm = hint;
         }
+        self
     }
 
     /// Creates an inner join plan.
@@ -234,6 +259,19 @@ impl LogicalPlan {
         }
     }
 
+    /// Semi- and anti-joins output only their left side.
+    fn join_output_tables(
+        left: &LogicalPlan,
+        right: &LogicalPlan,
+        join_type: JoinType,
+    ) -> Vec<String> {
+        if join_type.outputs_left_only() {
+            left.output_tables()
+        } else {
+            Self::combined_output_tables(left, right)
+        }
+    }
+
     fn combined_output_tables(left: &LogicalPlan, right: &LogicalPlan) -> Vec<String> {
         let mut tables = left.output_tables();
         tables.extend(right.output_tables());
@@ -247,6 +285,7 @@ impl LogicalPlan {
             | LogicalPlan::IndexScan { table, .. }
             | LogicalPlan::IndexGet { table, .. }
             | LogicalPlan::IndexInGet { table, .. }
+            | LogicalPlan::FetchByIds { table, .. }
             | LogicalPlan::GinIndexScan { table, .. }
             | LogicalPlan::GinIndexScanMulti { table, .. } => alloc::vec![table.clone()],
             LogicalPlan::Filter { input, .. }
@@ -269,6 +308,7 @@ impl LogicalPlan {
             | LogicalPlan::IndexScan { .. }
             | LogicalPlan::IndexGet { .. }
             | LogicalPlan::IndexInGet { .. }
+            | LogicalPlan::FetchByIds { .. }
             | LogicalPlan::GinIndexScan { .. }
             | LogicalPlan::GinIndexScanMulti { .. }
             | LogicalPlan::Empty => alloc::vec![],
@@ -283,6 +323,19 @@ impl LogicalPlan {
         }
     }
 
+    /// Returns true if any join in this plan carries an explicit algorithm hint.
+    pub fn has_join_algorithm_hint(&self) -> bool {
+        match self {
+            LogicalPlan::Join {
+                algorithm: Some(_), ..
+            } => true,
+            _ => self
+                .inputs()
+                .into_iter()
+                .any(LogicalPlan::has_join_algorithm_hint),
+        }
+    }
+
     /// Collects all table names referenced by this plan.
     pub fn collect_tables(&self) -> Vec<String> {
         let mut tables = Vec::new();
@@ -296,6 +349,7 @@ impl LogicalPlan {
             | LogicalPlan::IndexScan { table, .. }
             | LogicalPlan::IndexGet { table, .. }
             | LogicalPlan::IndexInGet { table, .. }
+            | LogicalPlan::FetchByIds { table, .. }
             | LogicalPlan::GinIndexScan { table, .. }
             | LogicalPlan::GinIndexScanMulti { table, .. } => {
                 if !tables.contains(table) {
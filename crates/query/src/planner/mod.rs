@@ -8,6 +8,6 @@ mod query_planner;
 
 pub use index_bounds::IndexBounds;
 pub use logical::LogicalPlan;
-pub use physical::{JoinAlgorithm, PhysicalPlan};
+pub use physical::{IndexScanType, IndexUsage, JoinAlgorithm, PhysicalPlan};
 pub use properties::{OrderingColumn, OrderingProperty, PhysicalProperties};
 pub use query_planner::QueryPlanner;
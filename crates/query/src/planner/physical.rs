@@ -6,7 +6,7 @@ use crate::planner::IndexBounds;
 use alloc::boxed::Box;
 use alloc::string::String;
 use alloc::vec::Vec;
-use cynos_core::Value;
+use cynos_core::{RowId, Value};
 
 /// Join algorithm selection.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -21,8 +21,63 @@ pub enum JoinAlgorithm {
     IndexNestedLoop,
 }
 
+impl JoinAlgorithm {
+    /// Returns true if this algorithm can evaluate the given join condition.
+    ///
+    /// Hash, sort-merge, and index joins match on key equality, so they
+    /// require an equi-join; nested loop handles any condition. Only hash
+    /// join also matches NULL-safe (`IS NOT DISTINCT FROM`) keys.
+    pub fn supports_condition(&self, condition: &Expr) -> bool {
+        match self {
+            JoinAlgorithm::Hash => condition.is_equi_join() || condition.is_null_safe_equi_join(),
+            JoinAlgorithm::SortMerge | JoinAlgorithm::IndexNestedLoop => condition.is_equi_join(),
+            JoinAlgorithm::NestedLoop => true,
+        }
+    }
+}
+
+/// How a physical plan reads an index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IndexScanType {
+    /// Key range scan (`IndexScan`).
+    Range,
+    /// Single key lookup (`IndexGet`).
+    Point,
+    /// One lookup per key of an IN list (`IndexInGet`).
+    MultiPoint,
+    /// GIN lookup of one JSONB predicate (`GinIndexScan`).
+    Gin,
+    /// GIN lookup of several ANDed JSONB predicates (`GinIndexScanMulti`).
+    GinMulti,
+    /// Inner-side lookups of an index nested loop join.
+    JoinLookup,
+}
+
+impl IndexScanType {
+    /// Returns the name used for this scan type in APIs.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IndexScanType::Range => "range",
+            IndexScanType::Point => "point",
+            IndexScanType::MultiPoint => "multiPoint",
+            IndexScanType::Gin => "gin",
+            IndexScanType::GinMulti => "ginMulti",
+            IndexScanType::JoinLookup => "joinLookup",
+        }
+    }
+}
+
+/// An index read by a physical plan.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndexUsage {
+    pub table: String,
+    pub index: String,
+    pub scan_type: IndexScanType,
+}
+
 /// Physical query plan node.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PhysicalPlan {
     /// Full table scan.
     TableScan { table: String },
@@ -57,6 +112,10 @@ pub enum PhysicalPlan {
         keys: Vec<Value>,
     },
 
+    /// Direct lookup of specific row ids, returned in the order given.
+    /// Ids with no stored row are skipped.
+    FetchByIds { table: String, ids: Vec<RowId> },
+
     /// GIN index scan for JSONB queries.
     GinIndexScan {
         table: String,
@@ -101,6 +160,9 @@ pub enum PhysicalPlan {
         condition: Expr,
         join_type: JoinType,
         output_tables: Vec<String>,
+        /// Set when a join hint chose this algorithm, which keeps the
+        /// index join pass from replacing it.
+        pinned: bool,
     },
 
     /// Sort-merge join.
@@ -119,6 +181,9 @@ pub enum PhysicalPlan {
         condition: Expr,
         join_type: JoinType,
         output_tables: Vec<String>,
+        /// Set when a join hint chose this algorithm, which keeps the
+        /// index join pass from replacing it.
+        pinned: bool,
     },
 
     /// Index nested loop join.
@@ -302,6 +367,14 @@ impl PhysicalPlan {
         }
     }
 
+    /// Creates a row id lookup plan.
+    pub fn fetch_by_ids(table: impl Into<String>, ids: Vec<RowId>) -> Self {
+        PhysicalPlan::FetchByIds {
+            table: table.into(),
+            ids,
+        }
+    }
+
     /// Creates a GIN index scan plan.
     pub fn gin_index_scan(
         table: impl Into<String>,
@@ -359,7 +432,7 @@ impl PhysicalPlan {
         condition: Expr,
         join_type: JoinType,
     ) -> Self {
-        let output_tables = Self::combined_output_tables(&left, &right);
+        let output_tables = Self::join_output_tables(&left, &right, join_type);
         Self::hash_join_with_output_tables(left, right, condition, join_type, output_tables)
     }
 
@@ -377,9 +450,21 @@ impl PhysicalPlan {
             condition,
             join_type,
             output_tables,
+            pinned: false,
         }
     }
 
+    /// Marks a hash or nested loop join as chosen by a join hint, so the
+    /// index join pass keeps its algorithm. Other plans are returned as is.
+    pub fn pin_join_algorithm(mut self) -> Self {
+        if let PhysicalPlan::HashJoin { pinned, .. } | PhysicalPlan::NestedLoopJoin { pinned, .. } =
+            &mut self
+        {
+            *pinned = true;
+        }
+        self
+    }
+
     /// Creates a sort-merge join plan.
     pub fn sort_merge_join(
         left: PhysicalPlan,
@@ -387,7 +472,7 @@ impl PhysicalPlan {
         condition: Expr,
         join_type: JoinType,
     ) -> Self {
-        let output_tables = Self::combined_output_tables(&left, &right);
+        let output_tables = Self::join_output_tables(&left, &right, join_type);
         Self::sort_merge_join_with_output_tables(left, right, condition, join_type, output_tables)
     }
 
@@ -415,7 +500,7 @@ impl PhysicalPlan {
         condition: Expr,
         join_type: JoinType,
     ) -> Self {
-        let output_tables = Self::combined_output_tables(&left, &right);
+        let output_tables = Self::join_output_tables(&left, &right, join_type);
         Self::nested_loop_join_with_output_tables(left, right, condition, join_type, output_tables)
     }
 
@@ -433,6 +518,7 @@ impl PhysicalPlan {
             condition,
             join_type,
             output_tables,
+            pinned: false,
         }
     }
 
@@ -490,6 +576,19 @@ impl PhysicalPlan {
         }
     }
 
+    /// Semi- and anti-joins output only their left side.
+    fn join_output_tables(
+        left: &PhysicalPlan,
+        right: &PhysicalPlan,
+        join_type: JoinType,
+    ) -> Vec<String> {
+        if join_type.outputs_left_only() {
+            left.output_tables()
+        } else {
+            Self::combined_output_tables(left, right)
+        }
+    }
+
     fn combined_output_tables(left: &PhysicalPlan, right: &PhysicalPlan) -> Vec<String> {
         let mut tables = left.output_tables();
         tables.extend(right.output_tables());
@@ -503,6 +602,7 @@ impl PhysicalPlan {
             | PhysicalPlan::IndexScan { table, .. }
             | PhysicalPlan::IndexGet { table, .. }
             | PhysicalPlan::IndexInGet { table, .. }
+            | PhysicalPlan::FetchByIds { table, .. }
             | PhysicalPlan::GinIndexScan { table, .. }
             | PhysicalPlan::GinIndexScanMulti { table, .. } => alloc::vec![table.clone()],
             PhysicalPlan::Filter { input, .. }
@@ -529,13 +629,19 @@ impl PhysicalPlan {
             | PhysicalPlan::IndexScan { .. }
             | PhysicalPlan::IndexGet { .. }
             | PhysicalPlan::IndexInGet { .. }
+            | PhysicalPlan::FetchByIds { .. }
             | PhysicalPlan::Filter { .. }
             | PhysicalPlan::Project { .. }
             | PhysicalPlan::HashJoin { .. }
             | PhysicalPlan::HashAggregate { .. } => true,
-            PhysicalPlan::Sort { .. } | PhysicalPlan::Limit { .. } | PhysicalPlan::TopN { .. } => {
-                false
-            }
+            // A LIMIT window is maintained incrementally over its input; an
+            // unbounded Sort is not.
+            PhysicalPlan::TopN { input, .. } => input.is_incrementalizable(),
+            PhysicalPlan::Limit { input, .. } => match input.as_ref() {
+                PhysicalPlan::Sort { input, .. } => input.is_incrementalizable(),
+                other => other.is_incrementalizable(),
+            },
+            PhysicalPlan::Sort { .. } => false,
             PhysicalPlan::SortMergeJoin { .. }
             | PhysicalPlan::NestedLoopJoin { .. }
             | PhysicalPlan::IndexNestedLoopJoin { .. } => true,
@@ -554,6 +660,7 @@ impl PhysicalPlan {
             | PhysicalPlan::IndexScan { .. }
             | PhysicalPlan::IndexGet { .. }
             | PhysicalPlan::IndexInGet { .. }
+            | PhysicalPlan::FetchByIds { .. }
             | PhysicalPlan::GinIndexScan { .. }
             | PhysicalPlan::GinIndexScanMulti { .. }
             | PhysicalPlan::Empty => alloc::vec![],
@@ -582,12 +689,55 @@ impl PhysicalPlan {
         tables
     }
 
+    /// Collects the indexes this plan reads, in plan order (outer inputs
+    /// before inner ones, left before right).
+    pub fn collect_index_usages(&self) -> Vec<IndexUsage> {
+        let mut usages = Vec::new();
+        self.collect_index_usages_into(&mut usages);
+        usages
+    }
+
+    fn collect_index_usages_into(&self, usages: &mut Vec<IndexUsage>) {
+        let (table, index, scan_type) = match self {
+            PhysicalPlan::IndexScan { table, index, .. } => (table, index, IndexScanType::Range),
+            PhysicalPlan::IndexGet { table, index, .. } => (table, index, IndexScanType::Point),
+            PhysicalPlan::IndexInGet { table, index, .. } => {
+                (table, index, IndexScanType::MultiPoint)
+            }
+            PhysicalPlan::GinIndexScan { table, index, .. } => (table, index, IndexScanType::Gin),
+            PhysicalPlan::GinIndexScanMulti { table, index, .. } => {
+                (table, index, IndexScanType::GinMulti)
+            }
+            PhysicalPlan::IndexNestedLoopJoin {
+                outer,
+                inner_table,
+                inner_index,
+                ..
+            } => {
+                outer.collect_index_usages_into(usages);
+                (inner_table, inner_index, IndexScanType::JoinLookup)
+            }
+            _ => {
+                for input in self.inputs() {
+                    input.collect_index_usages_into(usages);
+                }
+                return;
+            }
+        };
+        usages.push(IndexUsage {
+            table: table.clone(),
+            index: index.clone(),
+            scan_type,
+        });
+    }
+
     fn collect_tables_into(&self, tables: &mut Vec<String>) {
         match self {
             PhysicalPlan::TableScan { table }
             | PhysicalPlan::IndexScan { table, .. }
             | PhysicalPlan::IndexGet { table, .. }
             | PhysicalPlan::IndexInGet { table, .. }
+            | PhysicalPlan::FetchByIds { table, .. }
             | PhysicalPlan::GinIndexScan { table, .. }
             | PhysicalPlan::GinIndexScanMulti { table, .. } => {
                 if !tables.contains(table) {
@@ -654,6 +804,9 @@ mod tests {
         assert!(!sort.is_incrementalizable());
 
         let limit = PhysicalPlan::limit(PhysicalPlan::table_scan("users"), 10, 0);
-        assert!(!limit.is_incrementalizable());
+        assert!(limit.is_incrementalizable());
+
+        let top_n = PhysicalPlan::limit(sort, 10, 0);
+        assert!(top_n.is_incrementalizable());
     }
 }
@@ -169,6 +169,10 @@ impl PhysicalProperties {
                 estimated_rows: Some(keys.len()),
                 ordering: None,
             },
+            PhysicalPlan::FetchByIds { ids, .. } => Self {
+                estimated_rows: Some(ids.len()),
+                ordering: None,
+            },
             PhysicalPlan::Filter { input, .. } => {
                 let input = Self::derive(input, ctx);
                 Self {
@@ -17,11 +17,14 @@
 //!    - JoinReorder
 //!
 //! 2. **Context-Aware Logical Optimization** - Requires ExecutionContext:
+//!    - CountToNotExists (turns `COUNT(matches) = 0` into an anti-join)
 //!    - IndexSelection (converts Filter+Scan to IndexScan/IndexGet)
+//!    - ProjectionElimination (drops identity projections, merges stacked ones)
 //!
 //! 3. **Physical Plan Conversion** - Converts logical to physical plan
 //!
 //! 4. **Physical Optimization** - Context-aware physical transformations:
+//!    - LimitPushdown (moves Limit below Project)
 //!    - TopNPushdown (converts Sort+Limit to TopN)
 //!    - OrderByIndexPass (leverages indexes for sorting)
 //!    - IndexJoinPass (uses indexed inner lookups for bounded joins)
@@ -37,9 +40,10 @@
 
 use crate::context::ExecutionContext;
 use crate::optimizer::{
-    AndPredicatePass, CrossProductPass, ImplicitJoinsPass, IndexJoinPass, IndexSelection,
-    JoinReorder, LimitSkipByIndexPass, NotSimplification, OptimizerPass, OrderByIndexPass,
-    OuterJoinSimplification, PredicatePushdown, TopNPushdown,
+    AndPredicatePass, CountToNotExists, CrossProductPass, ImplicitJoinsPass, IndexJoinPass,
+    IndexSelection, JoinReorder, LimitPushdown, LimitSkipByIndexPass, NotSimplification,
+    OptimizerPass, OrderByIndexPass, OuterJoinSimplification, PredicatePushdown,
+    ProjectionElimination, TopNPushdown,
 };
 use crate::planner::{LogicalPlan, PhysicalPlan};
 use alloc::boxed::Box;
@@ -62,8 +66,9 @@ impl QueryPlanner {
     /// The planner is initialized with default optimization passes:
     /// - Logical: NotSimplification, AndPredicatePass, CrossProductPass,
     ///   ImplicitJoinsPass, OuterJoinSimplification, PredicatePushdown, JoinReorder
-    /// - Context-aware logical: IndexSelection
-    /// - Physical: TopNPushdown, OrderByIndexPass, LimitSkipByIndexPass
+    /// - Context-aware logical: CountToNotExists, IndexSelection,
+    ///   ProjectionElimination
+    /// - Physical: LimitPushdown, TopNPushdown, OrderByIndexPass, LimitSkipByIndexPass
     pub fn new(ctx: ExecutionContext) -> Self {
         Self {
             ctx: ctx.clone(),
@@ -99,9 +104,11 @@ impl QueryPlanner {
     ///
     /// This is the main entry point that runs the complete optimization pipeline:
     /// 1. Apply context-free logical optimizations
-    /// 2. Apply context-aware logical optimizations (IndexSelection)
+    /// 2. Apply context-aware logical optimizations (CountToNotExists,
+    ///    IndexSelection, ProjectionElimination)
     /// 3. Convert to physical plan
-    /// 4. Apply physical optimizations (TopNPushdown, OrderByIndexPass, LimitSkipByIndexPass)
+    /// 4. Apply physical optimizations (LimitPushdown, TopNPushdown, OrderByIndexPass,
+    ///    LimitSkipByIndexPass)
     pub fn plan(&self, plan: LogicalPlan) -> PhysicalPlan {
         // Phase 1: Context-free logical optimizations
         let mut logical = plan;
@@ -110,11 +117,13 @@ impl QueryPlanner {
         }
 
         // Phase 2: Context-aware logical optimizations
+        logical = CountToNotExists::new(&self.ctx).optimize(logical);
         let index_selection = IndexSelection::with_context(self.ctx.clone());
         logical = index_selection.optimize(logical);
+        logical = ProjectionElimination::with_context(self.ctx.clone()).optimize(logical);
 
         // Phase 3: Convert to physical plan
-        self.optimize_physical(self.logical_to_physical(logical))
+        self.to_physical(logical)
     }
 
     /// Optimizes only the logical plan without converting to physical.
@@ -129,8 +138,10 @@ impl QueryPlanner {
         }
 
         // Context-aware passes
+        logical = CountToNotExists::new(&self.ctx).optimize(logical);
         let index_selection = IndexSelection::with_context(self.ctx.clone());
         logical = index_selection.optimize(logical);
+        logical = ProjectionElimination::with_context(self.ctx.clone()).optimize(logical);
 
         logical
     }
@@ -138,6 +149,10 @@ impl QueryPlanner {
     /// Converts a logical plan to physical and applies physical optimizations.
     ///
     /// Assumes the logical plan has already been optimized.
+    ///
+    /// Joins with an explicit algorithm hint skip the index join rewrite so
+    /// the requested algorithm is kept; other joins in the plan can still be
+    /// rewritten.
     pub fn to_physical(&self, plan: LogicalPlan) -> PhysicalPlan {
         self.optimize_physical(self.logical_to_physical(plan))
     }
@@ -170,6 +185,8 @@ impl QueryPlanner {
                 PhysicalPlan::index_in_get(table, index, keys)
             }
 
+            LogicalPlan::FetchByIds { table, ids } => PhysicalPlan::fetch_by_ids(table, ids),
+
             LogicalPlan::GinIndexScan {
                 table,
                 index,
@@ -233,12 +250,13 @@ impl QueryPlanner {
                 condition,
                 join_type,
                 output_tables,
+                algorithm: hint,
             } => {
                 let left_physical = self.logical_to_physical(*left);
                 let right_physical = self.logical_to_physical(*right);
-                let algorithm = self.choose_join_algorithm(&condition);
+                let algorithm = self.choose_join_algorithm(&condition, hint);
 
-                match algorithm {
+                let join = match algorithm {
                     JoinAlgorithm::Hash => PhysicalPlan::hash_join_with_output_tables(
                         left_physical,
                         right_physical,
@@ -262,6 +280,13 @@ impl QueryPlanner {
                             output_tables,
                         )
                     }
+                };
+                // A hinted join keeps its algorithm. An index join hint plans a
+                // nested loop join that the index join pass may still convert.
+                if hint.is_some_and(|hint| hint != JoinAlgorithm::IndexNestedLoop) {
+                    join.pin_join_algorithm()
+                } else {
+                    join
                 }
             }
 
@@ -307,8 +332,15 @@ impl QueryPlanner {
         }
     }
 
-    fn choose_join_algorithm(&self, condition: &crate::ast::Expr) -> crate::planner::JoinAlgorithm {
-        if condition.is_equi_join() {
+    fn choose_join_algorithm(
+        &self,
+        condition: &crate::ast::Expr,
+        hint: Option<crate::planner::JoinAlgorithm>,
+    ) -> crate::planner::JoinAlgorithm {
+        if let Some(hint) = hint.filter(|hint| hint.supports_condition(condition)) {
+            return hint;
+        }
+        if condition.is_equi_join() || condition.is_null_safe_equi_join() {
             return crate::planner::JoinAlgorithm::Hash;
         }
         if condition.is_range_join() {
@@ -318,6 +350,7 @@ impl QueryPlanner {
     }
 
     fn optimize_physical(&self, mut physical: PhysicalPlan) -> PhysicalPlan {
+        physical = LimitPushdown::new().optimize(physical);
         physical = TopNPushdown::new().optimize(physical);
         physical = OrderByIndexPass::new(&self.ctx).optimize(physical);
         physical = IndexJoinPass::new(&self.ctx).optimize(physical);
@@ -328,9 +361,12 @@ impl QueryPlanner {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ast::{Expr, SortOrder};
+    use crate::ast::{Expr, JoinType, SortOrder};
     use crate::context::{IndexInfo, TableStats};
+    use crate::planner::JoinAlgorithm;
     use alloc::string::String;
+    use cynos_core::Value;
+    use cynos_index::KeyHistogram;
 
     fn create_test_context() -> ExecutionContext {
         let mut ctx = ExecutionContext::new();
@@ -354,6 +390,7 @@ mod tests {
             | LogicalPlan::IndexScan { table, .. }
             | LogicalPlan::IndexGet { table, .. }
             | LogicalPlan::IndexInGet { table, .. }
+            | LogicalPlan::FetchByIds { table, .. }
             | LogicalPlan::GinIndexScan { table, .. }
             | LogicalPlan::GinIndexScanMulti { table, .. } => order.push(table.clone()),
             LogicalPlan::Filter { input, .. }
@@ -399,6 +436,42 @@ mod tests {
         assert!(matches!(physical, PhysicalPlan::IndexGet { .. }));
     }
 
+    #[test]
+    fn test_query_planner_skips_index_for_wide_ranges() {
+        let mut ctx = create_test_context();
+        let ids: Vec<Value> = (0..1000).map(Value::Int64).collect();
+        ctx.register_histogram(
+            "users",
+            "id",
+            KeyHistogram::from_sorted(16, ids.iter().map(|id| (id, 1))),
+        );
+        let id_filter = |expr: fn(Expr, Expr) -> Expr, value: i64| {
+            LogicalPlan::filter(
+                LogicalPlan::scan("users"),
+                expr(Expr::column("users", "id", 0), Expr::literal(value)),
+            )
+        };
+
+        // id > 100 matches ~90% of rows: a filtered scan is cheaper.
+        let planner = QueryPlanner::new(ctx.clone());
+        let physical = planner.plan(id_filter(Expr::gt, 100));
+        match &physical {
+            PhysicalPlan::Filter { input, .. } => {
+                assert!(matches!(input.as_ref(), PhysicalPlan::TableScan { .. }))
+            }
+            other => panic!("Expected Filter over TableScan, got {:?}", other),
+        }
+
+        // id < 10 matches ~1% of rows: the index wins.
+        let physical = planner.plan(id_filter(Expr::lt, 10));
+        assert!(matches!(physical, PhysicalPlan::IndexScan { .. }));
+
+        // Raising the threshold brings the index back for the wide range.
+        ctx.set_max_index_selectivity(0.95);
+        let physical = QueryPlanner::new(ctx).plan(id_filter(Expr::gt, 100));
+        assert!(matches!(physical, PhysicalPlan::IndexScan { .. }));
+    }
+
     #[test]
     fn test_query_planner_union_lowers_to_physical_union() {
         let ctx = create_test_context();
@@ -499,6 +572,7 @@ mod tests {
                 ),
                 join_type: crate::ast::JoinType::Inner,
                 output_tables: alloc::vec!["users".into(), "departments".into()],
+                algorithm: None,
             }),
             limit: 1_000,
             offset: 0,
@@ -522,6 +596,141 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_query_planner_join_hint_overrides_index_join() {
+        let mut ctx = ExecutionContext::new();
+        ctx.register_table(
+            "users",
+            TableStats {
+                row_count: 10,
+                is_sorted: false,
+                indexes: alloc::vec![],
+            },
+        );
+        ctx.register_table(
+            "departments",
+            TableStats {
+                row_count: 100,
+                is_sorted: false,
+                indexes: alloc::vec![IndexInfo::new(
+                    "pk_departments_id",
+                    alloc::vec!["id".into()],
+                    true,
+                )],
+            },
+        );
+
+        let planner = QueryPlanner::new(ctx);
+        let join = || {
+            LogicalPlan::inner_join(
+                LogicalPlan::scan("users"),
+                LogicalPlan::scan("departments"),
+                Expr::eq(
+                    Expr::column("users", "dept_id", 2),
+                    Expr::column("departments", "id", 0),
+                ),
+            )
+        };
+
+        assert!(matches!(
+            planner.plan(join()),
+            PhysicalPlan::IndexNestedLoopJoin { .. }
+        ));
+        assert!(matches!(
+            planner.plan(join().with_join_algorithm(Some(JoinAlgorithm::SortMerge))),
+            PhysicalPlan::SortMergeJoin { .. }
+        ));
+        assert!(matches!(
+            planner.plan(join().with_join_algorithm(Some(JoinAlgorithm::NestedLoop))),
+            PhysicalPlan::NestedLoopJoin { .. }
+        ));
+    }
+
+    #[test]
+    fn test_query_planner_join_hint_only_pins_its_own_join() {
+        let mut ctx = ExecutionContext::new();
+        for (table, row_count, indexes) in [
+            ("users", 10, alloc::vec![]),
+            (
+                "departments",
+                100,
+                alloc::vec![IndexInfo::new(
+                    "pk_departments_id",
+                    alloc::vec!["id".into()],
+                    true,
+                )],
+            ),
+            ("orders", 100, alloc::vec![]),
+        ] {
+            ctx.register_table(
+                table,
+                TableStats {
+                    row_count,
+                    is_sorted: false,
+                    indexes,
+                },
+            );
+        }
+
+        let planner = QueryPlanner::new(ctx);
+        let with_departments = |join_type| {
+            LogicalPlan::join(
+                LogicalPlan::scan("users"),
+                LogicalPlan::scan("departments"),
+                Expr::eq(
+                    Expr::column("users", "dept_id", 2),
+                    Expr::column("departments", "id", 0),
+                ),
+                join_type,
+            )
+        };
+        let with_orders = |left| {
+            LogicalPlan::inner_join(
+                left,
+                LogicalPlan::scan("orders"),
+                Expr::eq(
+                    Expr::column("users", "id", 0),
+                    Expr::column("orders", "user_id", 1),
+                ),
+            )
+            .with_join_algorithm(Some(JoinAlgorithm::Hash))
+        };
+
+        // The hinted join keeps its hash join while the unhinted inner join
+        // and semi-join below it still use the index.
+        for join_type in [JoinType::Inner, JoinType::Semi, JoinType::Anti] {
+            match planner.plan(with_orders(with_departments(join_type))) {
+                PhysicalPlan::HashJoin { left, right, .. } => assert!(
+                    [left, right]
+                        .iter()
+                        .any(|side| matches!(**side, PhysicalPlan::IndexNestedLoopJoin { .. })),
+                    "{:?} join below a hinted join was not converted",
+                    join_type
+                ),
+                other => panic!("expected the hinted hash join, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_query_planner_ignores_hash_hint_for_non_equi_join() {
+        let planner = QueryPlanner::new(create_test_context());
+        let plan = LogicalPlan::inner_join(
+            LogicalPlan::scan("users"),
+            LogicalPlan::scan("orders"),
+            Expr::lt(
+                Expr::column("users", "id", 0),
+                Expr::column("orders", "user_id", 1),
+            ),
+        )
+        .with_join_algorithm(Some(JoinAlgorithm::Hash));
+
+        assert!(matches!(
+            planner.plan(plan),
+            PhysicalPlan::NestedLoopJoin { .. }
+        ));
+    }
+
     #[test]
     fn test_query_planner_optimize_logical() {
         let ctx = create_test_context();
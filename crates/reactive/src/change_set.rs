@@ -4,8 +4,9 @@
 //! containing added, removed, and modified rows.
 
 use alloc::vec::Vec;
-use cynos_core::Row;
+use cynos_core::{Row, RowId};
 use cynos_incremental::Delta;
+use hashbrown::HashMap;
 
 /// A set of changes to query results.
 ///
@@ -65,6 +66,29 @@ impl ChangeSet {
         changes
     }
 
+    /// Creates a change set taking result set `old` to `new`.
+    ///
+    /// Rows are matched by ID. A row whose values differ is reported as a
+    /// removal of the old row plus an addition of the new one, the same way
+    /// an update arrives as deltas.
+    pub fn between(old: Vec<Row>, new: Vec<Row>) -> Self {
+        let mut old_by_id: HashMap<RowId, Row> = old.into_iter().map(|r| (r.id(), r)).collect();
+        let mut changes = Self::new();
+        for row in &new {
+            match old_by_id.remove(&row.id()) {
+                Some(previous) if previous == *row => {}
+                Some(previous) => {
+                    changes.removed.push(previous);
+                    changes.added.push(row.clone());
+                }
+                None => changes.added.push(row.clone()),
+            }
+        }
+        changes.removed.extend(old_by_id.into_values());
+        changes.current_result = new;
+        changes
+    }
+
     /// Creates a change set representing an initial result set.
     ///
     /// All rows are treated as additions.
@@ -153,6 +177,21 @@ mod tests {
         assert_eq!(cs.len(), 0);
     }
 
+    #[test]
+    fn test_change_set_between() {
+        let old = vec![make_row(1, 10), make_row(2, 20), make_row(3, 30)];
+        let new = vec![make_row(1, 10), make_row(2, 25), make_row(4, 40)];
+
+        let cs = ChangeSet::between(old, new);
+        let mut removed: Vec<u64> = cs.removed.iter().map(|r| r.id()).collect();
+        removed.sort();
+        let mut added: Vec<u64> = cs.added.iter().map(|r| r.id()).collect();
+        added.sort();
+        assert_eq!(removed, vec![2, 3]);
+        assert_eq!(added, vec![2, 4]);
+        assert_eq!(cs.current_result.len(), 3);
+    }
+
     #[test]
     fn test_change_set_from_deltas() {
         let deltas = vec![
@@ -0,0 +1,201 @@
+//! Combining several observable queries into one derived stream.
+//!
+//! `combine_observables` subscribes to each input, keeps a copy of each
+//! input's latest result and re-runs a reducer over all of them whenever any
+//! input changes.
+
+use crate::change_set::ChangeSet;
+use crate::observable::ObservableQuery;
+use crate::subscription::SubscriptionId;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::{Ref, RefCell};
+use cynos_core::Row;
+
+type Reducer<T> = Box<dyn Fn(&[Vec<Row>]) -> T>;
+type ValueCallback<T> = Box<dyn Fn(&T)>;
+
+/// A value derived from the latest results of several observable queries.
+///
+/// Dropping it unsubscribes from the inputs.
+pub struct CombinedObservable<T> {
+    inputs: Vec<(Rc<RefCell<ObservableQuery>>, SubscriptionId)>,
+    state: Rc<RefCell<CombineState<T>>>,
+}
+
+struct CombineState<T> {
+    /// Latest result of each input, in input order.
+    latest: Vec<Vec<Row>>,
+    reducer: Reducer<T>,
+    value: T,
+    subscriptions: Vec<(SubscriptionId, ValueCallback<T>)>,
+    next_id: SubscriptionId,
+}
+
+/// Combines `inputs` into a single observable whose value is
+/// `reducer(&[latest result of each input])`.
+///
+/// The value is computed once up front from the inputs' current results and
+/// again after every change to any input, and subscribers receive each new
+/// value.
+pub fn combine_observables<T, F>(
+    inputs: &[Rc<RefCell<ObservableQuery>>],
+    reducer: F,
+) -> CombinedObservable<T>
+where
+    T: 'static,
+    F: Fn(&[Vec<Row>]) -> T + 'static,
+{
+    let latest: Vec<Vec<Row>> = inputs.iter().map(|query| query.borrow().result()).collect();
+    let value = reducer(&latest);
+    let state = Rc::new(RefCell::new(CombineState {
+        latest,
+        reducer: Box::new(reducer),
+        value,
+        subscriptions: Vec::new(),
+        next_id: 1,
+    }));
+
+    let inputs = inputs
+        .iter()
+        .enumerate()
+        .map(|(index, query)| {
+            let state = state.clone();
+            let id = query
+                .borrow_mut()
+                .subscribe(move |changes| CombineState::on_input_change(&state, index, changes));
+            (query.clone(), id)
+        })
+        .collect();
+
+    CombinedObservable { inputs, state }
+}
+
+impl<T> CombineState<T> {
+    fn on_input_change(state: &RefCell<Self>, index: usize, changes: &ChangeSet) {
+        {
+            let mut state = state.borrow_mut();
+            apply_changes(&mut state.latest[index], changes);
+            let value = (state.reducer)(&state.latest);
+            state.value = value;
+        }
+
+        // Subscribers may read `value()` again, so only a shared borrow is
+        // held while they run.
+        let state = state.borrow();
+        for (_, callback) in &state.subscriptions {
+            callback(&state.value);
+        }
+    }
+}
+
+/// Applies a change set to a cached result. Removed rows are matched by
+/// value, since derived rows may share ids.
+fn apply_changes(rows: &mut Vec<Row>, changes: &ChangeSet) {
+    for removed in &changes.removed {
+        if let Some(pos) = rows.iter().position(|row| row == removed) {
+            rows.swap_remove(pos);
+        }
+    }
+    for (old, new) in &changes.modified {
+        match rows.iter_mut().find(|row| *row == old) {
+            Some(row) => *row = new.clone(),
+            None => rows.push(new.clone()),
+        }
+    }
+    rows.extend(changes.added.iter().cloned());
+}
+
+impl<T> CombinedObservable<T> {
+    /// Returns the latest combined value.
+    pub fn value(&self) -> Ref<'_, T> {
+        Ref::map(self.state.borrow(), |state| &state.value)
+    }
+
+    /// Subscribes to new combined values.
+    pub fn subscribe<F>(&self, callback: F) -> SubscriptionId
+    where
+        F: Fn(&T) + 'static,
+    {
+        let mut state = self.state.borrow_mut();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.subscriptions.push((id, Box::new(callback)));
+        id
+    }
+
+    /// Unsubscribes by ID. Returns true if the subscription was found.
+    pub fn unsubscribe(&self, id: SubscriptionId) -> bool {
+        let mut state = self.state.borrow_mut();
+        let before = state.subscriptions.len();
+        state.subscriptions.retain(|(sub_id, _)| *sub_id != id);
+        state.subscriptions.len() != before
+    }
+
+    /// Returns the number of subscriptions.
+    pub fn subscription_count(&self) -> usize {
+        self.state.borrow().subscriptions.len()
+    }
+}
+
+impl<T> Drop for CombinedObservable<T> {
+    fn drop(&mut self) {
+        for (query, id) in &self.inputs {
+            query.borrow_mut().unsubscribe(*id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use cynos_core::Value;
+    use cynos_incremental::{DataflowNode, Delta};
+
+    fn make_row(id: u64, value: i64) -> Row {
+        Row::new(id, vec![Value::Int64(id as i64), Value::Int64(value)])
+    }
+
+    #[test]
+    fn test_combined_value_tracks_latest_of_each_input() {
+        let users = Rc::new(RefCell::new(ObservableQuery::with_initial(
+            DataflowNode::source(1),
+            vec![make_row(1, 10), make_row(2, 20)],
+        )));
+        let orders = Rc::new(RefCell::new(ObservableQuery::with_initial(
+            DataflowNode::source(2),
+            vec![make_row(100, 5)],
+        )));
+
+        let combined = combine_observables(&[users.clone(), orders.clone()], |results| {
+            (results[0].len(), results[1].len())
+        });
+        assert_eq!(*combined.value(), (2, 1));
+
+        let emitted = Rc::new(RefCell::new(Vec::new()));
+        let emitted_clone = emitted.clone();
+        combined.subscribe(move |value| emitted_clone.borrow_mut().push(*value));
+
+        orders.borrow_mut().on_table_change(
+            2,
+            vec![
+                Delta::insert(make_row(101, 7)),
+                Delta::insert(make_row(102, 9)),
+            ],
+        );
+        // The users count is the cached latest value, not recomputed.
+        assert_eq!(*emitted.borrow(), vec![(2, 3)]);
+
+        users
+            .borrow_mut()
+            .on_table_change(1, vec![Delta::delete(make_row(1, 10))]);
+        assert_eq!(*emitted.borrow(), vec![(2, 3), (1, 3)]);
+        assert_eq!(*combined.value(), (1, 3));
+
+        drop(combined);
+        assert_eq!(users.borrow().subscription_count(), 0);
+        assert_eq!(orders.borrow().subscription_count(), 0);
+    }
+}
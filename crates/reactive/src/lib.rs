@@ -10,6 +10,7 @@
 //! - `ObservableQuery`: A query that tracks changes and notifies subscribers
 //! - `SubscriptionManager`: Manages subscriptions to query changes
 //! - `QueryRegistry`: Routes table changes to dependent queries
+//! - `CombinedObservable`: A value derived from the latest results of several queries
 //!
 //! # Key Features
 //!
@@ -46,11 +47,13 @@
 extern crate alloc;
 
 pub mod change_set;
+pub mod combine;
 pub mod notify;
 pub mod observable;
 pub mod subscription;
 
 pub use change_set::ChangeSet;
+pub use combine::{combine_observables, CombinedObservable};
 pub use notify::{QueryId, QueryRegistry};
 pub use observable::{Changes, ObservableQuery};
 pub use subscription::{ChangeCallback, Subscription, SubscriptionId, SubscriptionManager};
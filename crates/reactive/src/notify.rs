@@ -2,8 +2,14 @@
 //!
 //! This module provides `QueryRegistry` which tracks observable queries
 //! and routes table changes to the appropriate queries.
+//!
+//! In batching mode the registry queues changes instead of routing them, and
+//! `flush` delivers each table's queued deltas in one call. A host scheduler
+//! (typically `queueMicrotask`) is invoked when the first change is queued so
+//! a synchronous burst of mutations produces one notification per query.
 
 use crate::observable::ObservableQuery;
+use alloc::boxed::Box;
 use alloc::rc::{Rc, Weak};
 use alloc::vec::Vec;
 use core::cell::RefCell;
@@ -14,6 +20,9 @@ use hashbrown::HashMap;
 /// Unique identifier for a registered query.
 pub type QueryId = u64;
 
+/// Callback asked to arrange a later `flush`, e.g. via `queueMicrotask`.
+type FlushScheduler = Box<dyn Fn() + 'static>;
+
 /// A registry that tracks observable queries and routes changes to them.
 ///
 /// The registry maintains a mapping from table IDs to the queries that
@@ -46,6 +55,12 @@ pub struct QueryRegistry {
     queries: HashMap<QueryId, Weak<RefCell<ObservableQuery>>>,
     /// Next query ID to assign
     next_id: QueryId,
+    /// Queue changes until `flush` instead of routing them immediately
+    batching: bool,
+    /// Queued deltas per table, in the order tables first changed
+    pending: RefCell<Vec<(TableId, Vec<Delta<Row>>)>>,
+    /// Called when the queue goes from empty to non-empty
+    flush_scheduler: Option<FlushScheduler>,
 }
 
 impl Default for QueryRegistry {
@@ -61,6 +76,9 @@ impl QueryRegistry {
             table_queries: HashMap::new(),
             queries: HashMap::new(),
             next_id: 1,
+            batching: false,
+            pending: RefCell::new(Vec::new()),
+            flush_scheduler: None,
         }
     }
 
@@ -102,8 +120,72 @@ impl QueryRegistry {
         }
     }
 
+    /// Turns batching mode on or off.
+    ///
+    /// While batching, `on_table_change` only queues deltas. Turning batching
+    /// off flushes anything still queued.
+    pub fn set_batching(&mut self, enabled: bool) {
+        self.batching = enabled;
+        if !enabled {
+            self.flush();
+        }
+    }
+
+    /// Returns true if changes are queued until `flush`.
+    pub fn is_batching(&self) -> bool {
+        self.batching
+    }
+
+    /// Sets the callback invoked when a batch starts, so the host can call
+    /// `flush` at the end of the current task.
+    pub fn set_flush_scheduler(&mut self, scheduler: impl Fn() + 'static) {
+        self.flush_scheduler = Some(Box::new(scheduler));
+    }
+
+    /// Returns true if queued changes are waiting for `flush`.
+    pub fn has_pending_changes(&self) -> bool {
+        !self.pending.borrow().is_empty()
+    }
+
+    /// Delivers all queued changes, one call per changed table with its
+    /// deltas in mutation order.
+    pub fn flush(&self) {
+        let pending = core::mem::take(&mut *self.pending.borrow_mut());
+        for (table_id, deltas) in pending {
+            self.notify(table_id, deltas);
+        }
+    }
+
     /// Notifies all queries that depend on the given table of changes.
+    ///
+    /// In batching mode the deltas are queued and merged with earlier
+    /// changes to the same table until `flush`.
     pub fn on_table_change(&self, table_id: TableId, deltas: Vec<Delta<Row>>) {
+        if !self.batching {
+            self.notify(table_id, deltas);
+            return;
+        }
+        if deltas.is_empty() {
+            return;
+        }
+
+        let schedule = {
+            let mut pending = self.pending.borrow_mut();
+            let schedule = pending.is_empty();
+            match pending.iter_mut().find(|(id, _)| *id == table_id) {
+                Some((_, queued)) => queued.extend(deltas),
+                None => pending.push((table_id, deltas)),
+            }
+            schedule
+        };
+        if schedule {
+            if let Some(scheduler) = &self.flush_scheduler {
+                scheduler();
+            }
+        }
+    }
+
+    fn notify(&self, table_id: TableId, deltas: Vec<Delta<Row>>) {
         if let Some(queries) = self.table_queries.get(&table_id) {
             for query_ref in queries {
                 if let Some(query) = query_ref.upgrade() {
@@ -149,6 +231,7 @@ impl QueryRegistry {
     pub fn clear(&mut self) {
         self.table_queries.clear();
         self.queries.clear();
+        self.pending.borrow_mut().clear();
     }
 }
 
@@ -506,4 +589,45 @@ mod tests {
 
         drop(query);
     }
+
+    #[test]
+    fn test_batched_changes_notify_once_on_flush() {
+        let mut registry = QueryRegistry::new();
+        let scheduled = Rc::new(RefCell::new(0));
+        let scheduled_clone = scheduled.clone();
+        registry.set_flush_scheduler(move || *scheduled_clone.borrow_mut() += 1);
+        registry.set_batching(true);
+
+        let query = Rc::new(RefCell::new(ObservableQuery::new(DataflowNode::source(1))));
+        let notifications = Rc::new(RefCell::new(Vec::new()));
+        let notifications_clone = notifications.clone();
+        query.borrow_mut().subscribe(move |changes| {
+            notifications_clone.borrow_mut().push(changes.added.len());
+        });
+        registry.register(query.clone());
+
+        for id in 1..=10 {
+            registry.on_table_change(1, vec![Delta::insert(make_row(id, 0))]);
+        }
+        // A change to a table no query reads joins the same batch.
+        registry.on_table_change(2, vec![Delta::insert(make_row(99, 0))]);
+
+        assert!(notifications.borrow().is_empty());
+        assert!(registry.has_pending_changes());
+        assert_eq!(*scheduled.borrow(), 1);
+
+        registry.flush();
+
+        assert_eq!(*notifications.borrow(), vec![10]);
+        assert_eq!(query.borrow().len(), 10);
+        assert!(!registry.has_pending_changes());
+
+        // The next burst schedules a new flush; leaving batching mode
+        // delivers it.
+        registry.on_table_change(1, vec![Delta::delete(make_row(1, 0))]);
+        assert_eq!(*scheduled.borrow(), 2);
+        registry.set_batching(false);
+        assert_eq!(notifications.borrow().len(), 2);
+        assert_eq!(query.borrow().len(), 9);
+    }
 }
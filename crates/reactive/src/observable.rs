@@ -79,12 +79,68 @@ impl ObservableQuery {
             .initialize_join_state(left_rows, right_rows, left_key_fn, right_key_fn);
     }
 
+    /// Initializes TopN state from the full (unlimited) input of the TopN node.
+    /// This must be called for LIMIT queries so deleted rows can be backfilled.
+    pub fn initialize_top_n_state(&mut self, input_rows: Vec<Row>) {
+        self.view.initialize_top_n_state(input_rows);
+    }
+
+    /// Rebuilds the view from the full contents of its source tables,
+    /// discarding all incremental state. Subscribers are not notified.
+    pub fn rebuild_from(&mut self, sources: impl IntoIterator<Item = (TableId, Vec<Row>)>) {
+        self.view.rebuild_from(sources);
+    }
+
+    /// Rebuilds the view like `rebuild_from` and notifies subscribers of the
+    /// difference between the old and rebuilt results. Use this to catch up
+    /// after changes the view never saw.
+    pub fn resync_from(&mut self, sources: impl IntoIterator<Item = (TableId, Vec<Row>)>) {
+        let before = self.view.result();
+        self.view.rebuild_from(sources);
+        if self.subscriptions.is_empty() {
+            return;
+        }
+
+        let changes = ChangeSet::between(before, self.view.result());
+        if !changes.is_empty() {
+            self.subscriptions.notify_all(&changes);
+        }
+    }
+
     /// Returns the current result as a Vec.
     #[inline]
     pub fn result(&self) -> Vec<Row> {
         self.view.result()
     }
 
+    /// Checks the incrementally maintained result against `full_result`, the
+    /// output of re-executing the query from scratch.
+    ///
+    /// The two must be equal as multisets of rows. A row of `full_result`
+    /// with a real row id must match a row with the same id and values; join
+    /// and aggregate rows carry dummy ids there and are matched by values.
+    #[cfg(feature = "debug-verify")]
+    pub fn verify_against(&self, full_result: &[Row]) -> bool {
+        let mut remaining = self.view.result();
+        if remaining.len() != full_result.len() {
+            return false;
+        }
+
+        for expected in full_result {
+            let position = remaining.iter().position(|actual| {
+                actual.values() == expected.values()
+                    && (expected.is_dummy() || actual.id() == expected.id())
+            });
+            match position {
+                Some(position) => {
+                    remaining.swap_remove(position);
+                }
+                None => return false,
+            }
+        }
+        true
+    }
+
     /// Returns the number of rows in the result.
     #[inline]
     pub fn len(&self) -> usize {
@@ -327,6 +383,29 @@ mod tests {
         assert_eq!(changes_received.borrow()[0].added.len(), 2);
     }
 
+    #[test]
+    fn test_observable_query_resync_from() {
+        let dataflow = DataflowNode::source(1);
+        let mut query = ObservableQuery::with_initial(dataflow, vec![make_row(1, 25)]);
+
+        let changes_received = Rc::new(RefCell::new(Vec::new()));
+        let changes_clone = changes_received.clone();
+        query.subscribe(move |changes| {
+            changes_clone.borrow_mut().push(changes.clone());
+        });
+
+        // The view missed an insert and an update; resyncing reports both.
+        query.resync_from(vec![(1, vec![make_row(1, 26), make_row(2, 30)])]);
+        assert_eq!(query.len(), 2);
+        assert_eq!(changes_received.borrow().len(), 1);
+        assert_eq!(changes_received.borrow()[0].added.len(), 2);
+        assert_eq!(changes_received.borrow()[0].removed, vec![make_row(1, 25)]);
+
+        // Nothing changed since: no notification.
+        query.resync_from(vec![(1, vec![make_row(1, 26), make_row(2, 30)])]);
+        assert_eq!(changes_received.borrow().len(), 1);
+    }
+
     #[test]
     fn test_observable_query_filter() {
         let dataflow = DataflowNode::filter(DataflowNode::source(1), |row| {
@@ -746,4 +825,25 @@ mod tests {
         query.on_table_change(1, vec![Delta::insert(make_row(3, 35))]);
         assert_eq!(*call_count.borrow(), 1);
     }
+
+    #[cfg(feature = "debug-verify")]
+    #[test]
+    fn test_verify_against() {
+        let initial = vec![make_row(1, 25), make_row(2, 30)];
+        let query = ObservableQuery::with_initial(DataflowNode::source(1), initial);
+
+        assert!(query.verify_against(&[make_row(2, 30), make_row(1, 25)]));
+        // Same values under a different row id.
+        assert!(!query.verify_against(&[
+            make_row(1, 25),
+            Row::new(7, make_row(2, 30).values().to_vec())
+        ]));
+        // Dummy ids are matched by values only.
+        assert!(query.verify_against(&[
+            make_row(1, 25),
+            Row::dummy(make_row(2, 30).values().to_vec())
+        ]));
+        assert!(!query.verify_against(&[make_row(1, 25)]));
+        assert!(!query.verify_against(&[make_row(1, 25), make_row(2, 31)]));
+    }
 }
@@ -1,4 +1,4 @@
-//! Benchmarks for RowStore delete operations.
+//! Benchmarks for RowStore inserts, delete operations and primary key lookups.
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 use cynos_core::schema::TableBuilder;
@@ -44,6 +44,44 @@ fn populate_store(store: &mut RowStore, count: u64) {
     }
 }
 
+/// Benchmark: bulk inserts of sequential keys into a table with two indexed
+/// Int64 columns, whose B-tree indexes keep key histograms.
+fn row_store_insert_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("row_store_insert");
+    group.sample_size(10);
+    let schema = TableBuilder::new("events")
+        .unwrap()
+        .add_column("id", DataType::Int64)
+        .unwrap()
+        .add_column("ts", DataType::Int64)
+        .unwrap()
+        .add_primary_key(&["id"], false)
+        .unwrap()
+        .add_index("idx_ts", &["ts"], false)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    for total_rows in [10000u64, 100000] {
+        group.bench_with_input(
+            BenchmarkId::new("sequential", total_rows),
+            &total_rows,
+            |b, &total_rows| {
+                b.iter(|| {
+                    let mut store = RowStore::new(schema.clone());
+                    for i in 1..=total_rows {
+                        let row = Row::new(i, vec![Value::Int64(i as i64), Value::Int64(i as i64)]);
+                        store.insert(row).unwrap();
+                    }
+                    black_box(store)
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
 /// Benchmark: individual delete() calls vs delete_batch()
 fn row_store_delete_benchmark(c: &mut Criterion) {
     let mut group = c.benchmark_group("row_store_delete");
@@ -174,10 +212,40 @@ fn row_store_delete_all_benchmark(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark: repeated primary key lookups of a few hot rows, with and
+/// without the point lookup cache.
+fn row_store_point_lookup_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("row_store_point_lookup");
+    let total_rows = 100000u64;
+    let hot_keys: Vec<Value> = (0..8).map(|i| Value::Int64(1 + i * 9973)).collect();
+
+    for capacity in [0usize, 16] {
+        let mut store = RowStore::new(create_test_schema_with_indices());
+        populate_store(&mut store, total_rows);
+        store.set_point_cache_capacity(capacity);
+
+        group.bench_with_input(
+            BenchmarkId::new("cache_capacity", capacity),
+            &capacity,
+            |b, _| {
+                b.iter(|| {
+                    for key in &hot_keys {
+                        black_box(store.get_by_pk(key));
+                    }
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
+    row_store_insert_benchmark,
     row_store_delete_benchmark,
     row_store_delete_all_benchmark,
+    row_store_point_lookup_benchmark,
 );
 
 criterion_main!(benches);
@@ -15,6 +15,8 @@ use cynos_core::{Error, Result, Row, RowId};
 pub struct TableCache {
     /// Table name → RowStore mapping.
     tables: BTreeMap<String, RowStore>,
+    /// Planner threshold for index range scans, if overridden.
+    max_index_selectivity: Option<f64>,
 }
 
 impl TableCache {
@@ -22,9 +24,22 @@ impl TableCache {
     pub fn new() -> Self {
         Self {
             tables: BTreeMap::new(),
+            max_index_selectivity: None,
         }
     }
 
+    /// Returns the largest estimated fraction of a table the planner should
+    /// still read through an index range scan, if one was set.
+    pub fn max_index_selectivity(&self) -> Option<f64> {
+        self.max_index_selectivity
+    }
+
+    /// Overrides the planner's index range scan threshold; `None` restores
+    /// the planner default.
+    pub fn set_max_index_selectivity(&mut self, fraction: Option<f64>) {
+        self.max_index_selectivity = fraction;
+    }
+
     /// Creates a table in the cache.
     pub fn create_table(&mut self, schema: Table) -> Result<()> {
         let name = schema.name().to_string();
@@ -1,12 +1,13 @@
 //! Constraint checking for Cynos database.
 //!
 //! This module provides constraint validation including primary key,
-//! unique, not-null, and foreign key constraints.
+//! unique, not-null, domain, check, and foreign key constraints.
 
 use crate::cache::TableCache;
 use crate::row_store::RowStore;
 use alloc::format;
-use cynos_core::schema::{ConstraintTiming, Table};
+use alloc::vec::Vec;
+use cynos_core::schema::{CheckConstraint, ConstraintTiming, Table};
 use cynos_core::{Error, Result, Row, RowId};
 
 /// Constraint checker for validating database constraints.
@@ -41,6 +42,64 @@ impl ConstraintChecker {
         Ok(())
     }
 
+    /// Checks that every value of a domain-restricted column is allowed.
+    pub fn check_domain(schema: &Table, row: &Row) -> Result<()> {
+        for col in schema.columns() {
+            if col.get_allowed_values().is_none() {
+                continue;
+            }
+            if let Some(value) = row.get(col.index()) {
+                if !col.allows(value) {
+                    return Err(Error::domain_constraint(col.name(), value.clone()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the row satisfies every CHECK constraint of the table.
+    pub fn check_checks(schema: &Table, row: &Row) -> Result<()> {
+        Self::first_failed_check(schema, schema.constraints().get_checks().iter(), row)
+    }
+
+    /// Checks the CHECK constraints an update can affect: those that read a
+    /// column whose value differs between `old_row` and `new_row`, and those
+    /// that report no columns at all, whose result cannot be tied to the row.
+    /// The others held for `old_row` and still see the same values.
+    pub fn check_checks_for_update(schema: &Table, old_row: &Row, new_row: &Row) -> Result<()> {
+        let changed = |index: usize| old_row.get(index) != new_row.get(index);
+        let affected = schema.constraints().get_checks().iter().filter(|check| {
+            let columns = Self::check_columns(check);
+            columns.is_empty() || columns.into_iter().any(changed)
+        });
+        Self::first_failed_check(schema, affected, new_row)
+    }
+
+    /// Returns the positions of the columns a CHECK constraint reads.
+    fn check_columns(check: &CheckConstraint) -> Vec<usize> {
+        check
+            .predicate
+            .columns()
+            .into_iter()
+            .map(|(_, index)| index)
+            .collect()
+    }
+
+    fn first_failed_check<'a>(
+        schema: &Table,
+        mut checks: impl Iterator<Item = &'a CheckConstraint>,
+        row: &Row,
+    ) -> Result<()> {
+        match checks.find(|check| !check.predicate.check(row)) {
+            Some(check) => Err(Error::CheckViolation {
+                table: schema.name().into(),
+                constraint: check.name.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
     /// Checks foreign key constraints for insert.
     pub fn check_foreign_keys_for_insert(
         cache: &TableCache,
@@ -216,7 +275,11 @@ impl ConstraintChecker {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::rc::Rc;
+    use alloc::string::String;
     use alloc::vec;
+    use alloc::vec::Vec;
+    use core::cell::Cell;
     use cynos_core::schema::TableBuilder;
     use cynos_core::{DataType, Value};
 
@@ -252,6 +315,162 @@ mod tests {
         assert!(result.is_err());
     }
 
+    /// Requires the Int64 column at `.0` to be non-negative.
+    #[derive(Debug)]
+    struct NonNegative(usize);
+
+    impl cynos_core::schema::CheckPredicate for NonNegative {
+        fn columns(&self) -> Vec<(String, usize)> {
+            vec![("balance".into(), self.0)]
+        }
+
+        fn check(&self, row: &Row) -> bool {
+            !matches!(row.get(self.0), Some(Value::Int64(n)) if *n < 0)
+        }
+    }
+
+    #[test]
+    fn test_check_checks() {
+        let schema = TableBuilder::new("accounts")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("balance", DataType::Int64)
+            .unwrap()
+            .add_check("balance_non_negative", NonNegative(1))
+            .unwrap()
+            .build()
+            .unwrap();
+        let row = |balance: i64| Row::new(1, vec![Value::Int64(1), Value::Int64(balance)]);
+
+        assert!(ConstraintChecker::check_checks(&schema, &row(0)).is_ok());
+        assert!(matches!(
+            ConstraintChecker::check_checks(&schema, &row(-5)),
+            Err(Error::CheckViolation { table, constraint })
+                if table == "accounts" && constraint == "balance_non_negative"
+        ));
+    }
+
+    /// Requires the Int64 column at `.0` to be at most `.1`, counting how
+    /// often it is evaluated.
+    #[derive(Debug)]
+    struct AtMost(usize, i64, Rc<Cell<usize>>);
+
+    impl cynos_core::schema::CheckPredicate for AtMost {
+        fn columns(&self) -> Vec<(String, usize)> {
+            vec![(["id", "balance", "limit"][self.0].into(), self.0)]
+        }
+
+        fn check(&self, row: &Row) -> bool {
+            self.2.set(self.2.get() + 1);
+            !matches!(row.get(self.0), Some(Value::Int64(n)) if *n > self.1)
+        }
+    }
+
+    #[test]
+    fn test_check_checks_for_update_runs_affected_checks() {
+        let balance_checks = Rc::new(Cell::new(0));
+        let limit_checks = Rc::new(Cell::new(0));
+        let schema = TableBuilder::new("accounts")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("balance", DataType::Int64)
+            .unwrap()
+            .add_column("limit", DataType::Int64)
+            .unwrap()
+            .add_check("balance_cap", AtMost(1, 100, balance_checks.clone()))
+            .unwrap()
+            .add_check("limit_cap", AtMost(2, 1000, limit_checks.clone()))
+            .unwrap()
+            .build()
+            .unwrap();
+        let row = |balance: i64, limit: i64| {
+            Row::new(
+                1,
+                vec![Value::Int64(1), Value::Int64(balance), Value::Int64(limit)],
+            )
+        };
+        let counts = || (balance_checks.get(), limit_checks.get());
+
+        let old = row(10, 500);
+        assert!(ConstraintChecker::check_checks_for_update(&schema, &old, &row(20, 500)).is_ok());
+        assert_eq!(counts(), (1, 0));
+        assert!(ConstraintChecker::check_checks_for_update(&schema, &old, &row(10, 900)).is_ok());
+        assert_eq!(counts(), (1, 1));
+        assert!(ConstraintChecker::check_checks_for_update(&schema, &old, &old).is_ok());
+        assert_eq!(counts(), (1, 1));
+
+        assert!(matches!(
+            ConstraintChecker::check_checks_for_update(&schema, &old, &row(10, 2000)),
+            Err(Error::CheckViolation { constraint, .. }) if constraint == "limit_cap"
+        ));
+        assert_eq!(counts(), (1, 2));
+
+        // Inserts still run every check.
+        assert!(ConstraintChecker::check_checks(&schema, &old).is_ok());
+        assert_eq!(counts(), (2, 3));
+    }
+
+    /// Reads no columns, counting how often it is evaluated.
+    #[derive(Debug)]
+    struct Unbound(Rc<Cell<usize>>);
+
+    impl cynos_core::schema::CheckPredicate for Unbound {
+        fn columns(&self) -> Vec<(String, usize)> {
+            Vec::new()
+        }
+
+        fn check(&self, _row: &Row) -> bool {
+            self.0.set(self.0.get() + 1);
+            true
+        }
+    }
+
+    #[test]
+    fn test_check_checks_for_update_runs_checks_without_columns() {
+        let evaluations = Rc::new(Cell::new(0));
+        let schema = TableBuilder::new("accounts")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("balance", DataType::Int64)
+            .unwrap()
+            .add_check("unbound", Unbound(evaluations.clone()))
+            .unwrap()
+            .build()
+            .unwrap();
+        let row = |balance: i64| Row::new(1, vec![Value::Int64(1), Value::Int64(balance)]);
+
+        assert!(ConstraintChecker::check_checks_for_update(&schema, &row(1), &row(2)).is_ok());
+        assert_eq!(evaluations.get(), 1);
+        assert!(ConstraintChecker::check_checks_for_update(&schema, &row(1), &row(1)).is_ok());
+        assert_eq!(evaluations.get(), 2);
+    }
+
+    #[test]
+    fn test_check_domain() {
+        let schema = TableBuilder::new("accounts")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_enum_column("status", &["active", "inactive"])
+            .unwrap()
+            .add_nullable(&["status"])
+            .build()
+            .unwrap();
+        let row = |status: Value| Row::new(1, vec![Value::Int64(1), status]);
+
+        assert!(
+            ConstraintChecker::check_domain(&schema, &row(Value::String("active".into()))).is_ok()
+        );
+        assert!(ConstraintChecker::check_domain(&schema, &row(Value::Null)).is_ok());
+        assert!(matches!(
+            ConstraintChecker::check_domain(&schema, &row(Value::String("deleted".into()))),
+            Err(Error::DomainConstraint { column, .. }) if column == "status"
+        ));
+    }
+
     #[test]
     fn test_find_existing_row_id() {
         let schema = test_schema_with_not_null();
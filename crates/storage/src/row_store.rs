@@ -3,18 +3,21 @@
 //! This module provides the `RowStore` struct which manages rows for a single table,
 //! including primary key and secondary index maintenance.
 
+use crate::constraint::ConstraintChecker;
 use alloc::collections::BTreeMap;
 use alloc::format;
 use alloc::rc::Rc;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
+use core::cell::RefCell;
 use cynos_core::schema::{IndexType, Table};
 use cynos_core::{Error, Result, Row, RowId, Value};
 use cynos_incremental::Delta;
 use cynos_index::{
-    contains_trigram_pairs, BTreeIndex, GinIndex, HashIndex, Index, KeyRange, RangeIndex,
+    contains_trigram_pairs, BTreeIndex, GinIndex, HashIndex, Index, KeyHistogram, KeyRange,
+    RangeIndex,
 };
-use cynos_jsonb::{JsonbObject, JsonbValue as ParsedJsonbValue};
+use cynos_jsonb::JsonbValue as ParsedJsonbValue;
 
 /// Row ID lookup backend: HashMap (O(1) lookup) or BTreeMap (O(log n) lookup).
 #[cfg(feature = "hash-store")]
@@ -22,6 +25,9 @@ type RowMap = hashbrown::HashMap<RowId, usize>;
 #[cfg(not(feature = "hash-store"))]
 type RowMap = BTreeMap<RowId, usize>;
 
+/// Buckets in the key histograms kept on single-column B-tree indexes.
+const INDEX_HISTOGRAM_BUCKETS: usize = 32;
+
 #[derive(Clone)]
 struct RowSlot {
     row_id: RowId,
@@ -179,6 +185,8 @@ pub trait IndexStore {
 /// Wrapper for BTreeIndex that implements IndexStore.
 pub struct BTreeIndexStore {
     inner: BTreeIndex<IndexKey>,
+    /// Key changes since the histogram was last checked for skew.
+    histogram_changes: usize,
 }
 
 impl BTreeIndexStore {
@@ -186,6 +194,43 @@ impl BTreeIndexStore {
     pub fn new(unique: bool) -> Self {
         Self {
             inner: BTreeIndex::new(64, unique),
+            histogram_changes: 0,
+        }
+    }
+
+    /// Creates a BTree index store that keeps an equi-depth histogram of
+    /// its keys, rebuilt once enough updates have skewed it.
+    pub fn with_histogram(unique: bool) -> Self {
+        let mut store = Self::new(unique);
+        store.inner.enable_histogram(INDEX_HISTOGRAM_BUCKETS);
+        store
+    }
+
+    /// Returns the key histogram of a single-column index, if kept.
+    pub fn histogram(&self) -> Option<KeyHistogram<Value>> {
+        let histogram = self.inner.stats().histogram()?;
+        Some(histogram.map_keys(|key| match key {
+            IndexKey::Scalar(value) => value.clone(),
+            IndexKey::Composite(_) => Value::Null,
+        }))
+    }
+
+    /// Records `changes` key changes, checking the histogram for skew once
+    /// they add up to an eighth of the index. Rebuilds then cost amortized
+    /// O(1) per change, even when sequential keys skew the histogram all the
+    /// time.
+    fn rebalance_histogram(&mut self, changes: usize) {
+        let stats = self.inner.stats();
+        let Some(histogram) = stats.histogram() else {
+            return;
+        };
+        self.histogram_changes += changes;
+        if self.histogram_changes < (stats.total_rows() / 8).max(1) {
+            return;
+        }
+        self.histogram_changes = 0;
+        if histogram.is_skewed() {
+            self.inner.rebuild_histogram();
         }
     }
 
@@ -194,11 +239,14 @@ impl BTreeIndexStore {
         key: IndexKey,
         row_id: RowId,
     ) -> core::result::Result<(), cynos_index::IndexError> {
-        self.inner.add(key, row_id)
+        self.inner.add(key, row_id)?;
+        self.rebalance_histogram(1);
+        Ok(())
     }
 
     fn set_index_key(&mut self, key: IndexKey, row_id: RowId) {
         self.inner.set(key, row_id);
+        self.rebalance_histogram(1);
     }
 
     fn get_index_key(&self, key: &IndexKey) -> Vec<RowId> {
@@ -207,10 +255,12 @@ impl BTreeIndexStore {
 
     fn remove_index_key(&mut self, key: &IndexKey, row_id: Option<RowId>) {
         self.inner.remove(key, row_id);
+        self.rebalance_histogram(1);
     }
 
     fn remove_batch_index_keys(&mut self, entries: &[(IndexKey, RowId)]) {
         self.inner.remove_batch(entries);
+        self.rebalance_histogram(entries.len());
     }
 
     fn contains_index_key(&self, key: &IndexKey) -> bool {
@@ -227,6 +277,10 @@ impl BTreeIndexStore {
         self.inner.get_range(range, reverse, limit, skip)
     }
 
+    fn count_range_index_keys(&self, range: Option<&KeyRange<IndexKey>>) -> usize {
+        self.inner.count_range(range, true)
+    }
+
     fn visit_range_index_keys<F>(
         &self,
         range: Option<&KeyRange<IndexKey>>,
@@ -367,6 +421,10 @@ impl HashIndexStore {
         self.inner.get_range(range, reverse, limit, skip)
     }
 
+    fn count_range_index_keys(&self, range: Option<&KeyRange<IndexKey>>) -> usize {
+        self.inner.count_range(range, true)
+    }
+
     fn visit_range_index_keys<F>(
         &self,
         range: Option<&KeyRange<IndexKey>>,
@@ -467,13 +525,23 @@ enum SecondaryIndexStore {
 }
 
 impl SecondaryIndexStore {
-    fn new(index_type: IndexType, unique: bool) -> Self {
+    fn new(index_type: IndexType, unique: bool, with_histogram: bool) -> Self {
         match index_type {
             IndexType::Hash => Self::Hash(HashIndexStore::new(unique)),
+            IndexType::BTree | IndexType::Gin if with_histogram => {
+                Self::BTree(BTreeIndexStore::with_histogram(unique))
+            }
             IndexType::BTree | IndexType::Gin => Self::BTree(BTreeIndexStore::new(unique)),
         }
     }
 
+    fn histogram(&self) -> Option<KeyHistogram<Value>> {
+        match self {
+            Self::BTree(index) => index.histogram(),
+            Self::Hash(_) => None,
+        }
+    }
+
     fn add_index_key(
         &mut self,
         key: IndexKey,
@@ -520,6 +588,13 @@ impl SecondaryIndexStore {
         }
     }
 
+    fn count_range_index_keys(&self, range: Option<&KeyRange<IndexKey>>) -> usize {
+        match self {
+            Self::BTree(index) => index.count_range_index_keys(range),
+            Self::Hash(index) => index.count_range_index_keys(range),
+        }
+    }
+
     fn visit_range_index_keys<F>(
         &self,
         range: Option<&KeyRange<IndexKey>>,
@@ -544,6 +619,15 @@ fn extract_key(row: &Row, col_indices: &[usize]) -> IndexKey {
     IndexKey::from_row(row, col_indices)
 }
 
+/// Returns whether `row` belongs in an index gated by `condition_col`.
+///
+/// Unconditional indexes hold every row; conditional ones only rows whose
+/// flag column is `true`.
+#[inline]
+fn row_in_index(row: &Row, condition_col: Option<usize>) -> bool {
+    condition_col.is_none_or(|col| matches!(row.get(col), Some(Value::Boolean(true))))
+}
+
 fn extract_key_from_values(values: &[Value]) -> IndexKey {
     IndexKey::from_values(values.to_vec())
 }
@@ -558,6 +642,52 @@ fn composite_range_has_expected_arity(range: &KeyRange<Vec<Value>>, expected: us
     }
 }
 
+/// Small LRU cache of primary key lookups.
+///
+/// Entries are kept in recency order (most recent last) and found by a linear
+/// scan, which beats an index descent for the small capacities this is meant
+/// for. Each entry records the row id it resolved to, so a write to that row
+/// evicts it.
+#[derive(Default)]
+struct PointLookupCache {
+    capacity: usize,
+    entries: Vec<(IndexKey, RowId, Rc<Row>)>,
+}
+
+impl PointLookupCache {
+    fn get(&mut self, key: &IndexKey) -> Option<Rc<Row>> {
+        let position = self
+            .entries
+            .iter()
+            .position(|(cached_key, _, _)| cached_key == key)?;
+        let entry = self.entries.remove(position);
+        let row = entry.2.clone();
+        self.entries.push(entry);
+        Some(row)
+    }
+
+    fn put(&mut self, key: IndexKey, row: Rc<Row>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() >= self.capacity {
+            self.entries.remove(0);
+        }
+        self.entries.push((key, row.id(), row));
+    }
+
+    fn invalidate(&mut self, row_id: RowId) {
+        self.entries
+            .retain(|(_, cached_row_id, _)| *cached_row_id != row_id);
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        let excess = self.entries.len().saturating_sub(capacity);
+        self.entries.drain(..excess);
+    }
+}
+
 /// Row storage for a single table.
 pub struct RowStore {
     schema: Table,
@@ -572,10 +702,14 @@ pub struct RowStore {
     pk_columns: Vec<usize>,
     secondary_indices: BTreeMap<String, SecondaryIndexStore>,
     index_columns: BTreeMap<String, Vec<usize>>,
+    /// Flag column indices for conditional secondary indexes
+    index_conditions: BTreeMap<String, usize>,
     /// GIN indexes for JSONB columns
     gin_indices: BTreeMap<String, GinIndex>,
     /// Column indices for GIN indexes
     gin_index_columns: BTreeMap<String, usize>,
+    /// Read-through cache for primary key lookups; disabled by default.
+    point_cache: RefCell<PointLookupCache>,
 }
 
 impl RowStore {
@@ -591,8 +725,10 @@ impl RowStore {
             pk_columns: Vec::new(),
             secondary_indices: BTreeMap::new(),
             index_columns: BTreeMap::new(),
+            index_conditions: BTreeMap::new(),
             gin_indices: BTreeMap::new(),
             gin_index_columns: BTreeMap::new(),
+            point_cache: RefCell::new(PointLookupCache::default()),
         };
 
         if let Some(pk) = schema.primary_key() {
@@ -622,11 +758,22 @@ impl RowStore {
                         .insert(idx.name().to_string(), col_idx);
                 }
             } else {
+                // Histograms feed the planner's range estimates, which only
+                // cover single-column indexes over every row.
+                let with_histogram = cols.len() == 1 && idx.get_condition_column().is_none();
                 store.secondary_indices.insert(
                     idx.name().to_string(),
-                    SecondaryIndexStore::new(idx.get_index_type(), idx.is_unique()),
+                    SecondaryIndexStore::new(idx.get_index_type(), idx.is_unique(), with_histogram),
                 );
                 store.index_columns.insert(idx.name().to_string(), cols);
+                if let Some(col_idx) = idx
+                    .get_condition_column()
+                    .and_then(|name| schema.get_column_index(name))
+                {
+                    store
+                        .index_conditions
+                        .insert(idx.name().to_string(), col_idx);
+                }
             }
         }
 
@@ -664,6 +811,7 @@ impl RowStore {
 
     #[inline]
     fn row_mut_by_id(&mut self, row_id: RowId) -> Option<&mut Rc<Row>> {
+        self.point_cache.get_mut().invalidate(row_id);
         let slot_idx = *self.rows.get(&row_id)?;
         self.row_slots.get_mut(slot_idx).map(|slot| &mut slot.row)
     }
@@ -684,6 +832,7 @@ impl RowStore {
     }
 
     fn remove_row_slot(&mut self, row_id: RowId) -> Option<Rc<Row>> {
+        self.point_cache.get_mut().invalidate(row_id);
         let slot_idx = self.rows.remove(&row_id)?;
         let removed_scan_pos = self.scan_position(row_id).ok();
         let moved_slot_meta = if slot_idx + 1 < self.row_slots.len() {
@@ -717,6 +866,8 @@ impl RowStore {
     /// Inserts a row into the store.
     pub fn insert(&mut self, row: Row) -> Result<RowId> {
         let row_id = row.id();
+        ConstraintChecker::check_domain(&self.schema, &row)?;
+        ConstraintChecker::check_checks(&self.schema, &row)?;
 
         if self.rows.contains_key(&row_id) {
             return Err(Error::invalid_operation("Row ID already exists"));
@@ -759,6 +910,9 @@ impl RowStore {
         // Collect index names first to avoid borrow conflict
         let index_names: Vec<String> = self.index_columns.keys().cloned().collect();
         for idx_name in &index_names {
+            if !row_in_index(&row, self.index_conditions.get(idx_name).copied()) {
+                continue;
+            }
             let cols = &self.index_columns[idx_name];
             let key = extract_key(&row, cols);
             if let Some(idx) = self.secondary_indices.get_mut(idx_name) {
@@ -798,6 +952,9 @@ impl RowStore {
 
         let index_names: Vec<String> = self.index_columns.keys().cloned().collect();
         for idx_name in &index_names {
+            if !row_in_index(row, self.index_conditions.get(idx_name).copied()) {
+                continue;
+            }
             let cols = &self.index_columns[idx_name];
             let key = extract_key(row, cols);
             if let Some(idx) = self.secondary_indices.get_mut(idx_name) {
@@ -823,6 +980,8 @@ impl RowStore {
             .row_ref_by_id(row_id)
             .cloned()
             .ok_or_else(|| Error::not_found(self.schema.name(), Value::Int64(row_id as i64)))?;
+        ConstraintChecker::check_domain(&self.schema, &new_row)?;
+        ConstraintChecker::check_checks_for_update(&self.schema, &old_row, &new_row)?;
 
         // Check primary key uniqueness if PK changed
         if !self.pk_columns.is_empty() {
@@ -840,10 +999,18 @@ impl RowStore {
 
         // Check secondary index uniqueness (only for unique indexes)
         for (idx_name, cols) in &self.index_columns {
+            let condition = self.index_conditions.get(idx_name).copied();
+            if !row_in_index(&new_row, condition) {
+                continue;
+            }
+            let was_indexed = row_in_index(&old_row, condition);
             let old_key = extract_key(&old_row, cols);
             let new_key = extract_key(&new_row, cols);
             if let Some(idx) = self.secondary_indices.get(idx_name) {
-                if idx.is_unique() && old_key != new_key && idx.contains_index_key(&new_key) {
+                if idx.is_unique()
+                    && (!was_indexed || old_key != new_key)
+                    && idx.contains_index_key(&new_key)
+                {
                     return Err(Error::UniqueConstraint {
                         column: idx_name.clone(),
                         value: new_key.to_error_value(),
@@ -867,13 +1034,20 @@ impl RowStore {
         // Update secondary indices
         let index_names: Vec<String> = self.index_columns.keys().cloned().collect();
         for idx_name in &index_names {
+            let condition = self.index_conditions.get(idx_name).copied();
+            let was_indexed = row_in_index(&old_row, condition);
+            let is_indexed = row_in_index(&new_row, condition);
             let cols = &self.index_columns[idx_name];
             let old_key = extract_key(&old_row, cols);
             let new_key = extract_key(&new_row, cols);
             if let Some(idx) = self.secondary_indices.get_mut(idx_name) {
-                if old_key != new_key {
-                    idx.remove_index_key(&old_key, Some(row_id));
-                    let _ = idx.add_index_key(new_key, row_id);
+                if was_indexed != is_indexed || old_key != new_key {
+                    if was_indexed {
+                        idx.remove_index_key(&old_key, Some(row_id));
+                    }
+                    if is_indexed {
+                        let _ = idx.add_index_key(new_key, row_id);
+                    }
                 }
             }
         }
@@ -919,6 +1093,9 @@ impl RowStore {
 
         let index_names: Vec<String> = self.index_columns.keys().cloned().collect();
         for idx_name in &index_names {
+            if !row_in_index(&row, self.index_conditions.get(idx_name).copied()) {
+                continue;
+            }
             let cols = &self.index_columns[idx_name];
             let key = extract_key(&row, cols);
             if let Some(idx) = self.secondary_indices.get_mut(idx_name) {
@@ -982,9 +1159,11 @@ impl RowStore {
 
         // Prepare batch entries for each secondary index
         for (idx_name, cols) in &self.index_columns {
+            let condition = self.index_conditions.get(idx_name).copied();
             if let Some(idx) = self.secondary_indices.get_mut(idx_name) {
                 let entries: Vec<(IndexKey, RowId)> = deleted_rows
                     .iter()
+                    .filter(|row| row_in_index(row, condition))
                     .map(|row| (extract_key(row, cols), row.id()))
                     .collect();
                 idx.remove_batch_index_keys(&entries);
@@ -1066,16 +1245,49 @@ impl RowStore {
 
         if let Some(ref pk_index) = self.primary_index {
             let pk_key = extract_key_from_values(pk_values);
-            pk_index
+            let mut cache = self.point_cache.borrow_mut();
+            if cache.capacity == 0 {
+                drop(cache);
+                return pk_index
+                    .get_index_key(&pk_key)
+                    .iter()
+                    .filter_map(|&id| self.row_ref_by_id(id).cloned())
+                    .collect();
+            }
+            if let Some(row) = cache.get(&pk_key) {
+                return alloc::vec![row];
+            }
+
+            let rows: Vec<Rc<Row>> = pk_index
                 .get_index_key(&pk_key)
                 .iter()
                 .filter_map(|&id| self.row_ref_by_id(id).cloned())
-                .collect()
+                .collect();
+            if let [row] = rows.as_slice() {
+                cache.put(pk_key, row.clone());
+            }
+            rows
         } else {
             Vec::new()
         }
     }
 
+    /// Enables an LRU cache of up to `capacity` primary key lookups, or
+    /// disables it with a capacity of 0 (the default).
+    ///
+    /// `get_by_pk` and `get_by_pk_values` are served from the cache when they
+    /// hit. Updating or deleting a row evicts it, so a hit always returns the
+    /// current row. The cache holds `Rc` handles, so its memory cost is one
+    /// key per entry.
+    pub fn set_point_cache_capacity(&mut self, capacity: usize) {
+        self.point_cache.get_mut().set_capacity(capacity);
+    }
+
+    /// Returns the capacity of the primary key lookup cache (0 if disabled).
+    pub fn point_cache_capacity(&self) -> usize {
+        self.point_cache.borrow().capacity
+    }
+
     /// Finds existing row ID by primary key.
     pub fn find_row_id_by_pk(&self, row: &Row) -> Option<RowId> {
         if let Some(ref pk_index) = self.primary_index {
@@ -1194,6 +1406,26 @@ impl RowStore {
         }
     }
 
+    /// Counts the rows an index scan over `range` would return, without
+    /// fetching them.
+    pub fn index_range_count(&self, index_name: &str, range: Option<&KeyRange<Value>>) -> usize {
+        let Some(idx) = self.secondary_indices.get(index_name) else {
+            return 0;
+        };
+        let Some(columns) = self.index_columns.get(index_name) else {
+            return 0;
+        };
+
+        if columns.len() == 1 {
+            let normalized_range = IndexKey::from_scalar_range(range);
+            idx.count_range_index_keys(normalized_range.as_ref())
+        } else if range.is_none() {
+            idx.count_range_index_keys(None)
+        } else {
+            0
+        }
+    }
+
     /// Gets rows by composite index scan.
     /// Use this for multi-column indexes where the bounds are real tuple keys.
     pub fn index_scan_composite(
@@ -1294,6 +1526,7 @@ impl RowStore {
 
     /// Clears all rows and indices.
     pub fn clear(&mut self) {
+        self.point_cache.get_mut().entries.clear();
         self.rows.clear();
         self.row_slots.clear();
         self.scan_order.clear();
@@ -1333,6 +1566,12 @@ impl RowStore {
         }
     }
 
+    /// Returns the key histogram of a single-column B-tree index, used to
+    /// estimate how many rows a range on its column matches.
+    pub fn index_histogram(&self, index_name: &str) -> Option<KeyHistogram<Value>> {
+        self.secondary_indices.get(index_name)?.histogram()
+    }
+
     /// Checks if a secondary index contains a key (for unique constraint checking).
     pub fn secondary_index_contains(&self, index_name: &str, key: &Value) -> bool {
         self.secondary_index_contains_values(index_name, core::slice::from_ref(key))
@@ -1555,7 +1794,7 @@ impl RowStore {
             return None;
         };
         let json_str = core::str::from_utf8(&jsonb.0).ok()?;
-        parse_json_text(json_str)
+        cynos_jsonb::parse_json(json_str).ok()
     }
 
     fn jsonb_scalar_to_index_value(value: &ParsedJsonbValue) -> Option<String> {
@@ -1734,170 +1973,6 @@ impl RowStore {
     }
 }
 
-fn parse_json_text(s: &str) -> Option<ParsedJsonbValue> {
-    let s = s.trim();
-    if s == "null" {
-        return Some(ParsedJsonbValue::Null);
-    }
-    if s == "true" {
-        return Some(ParsedJsonbValue::Bool(true));
-    }
-    if s == "false" {
-        return Some(ParsedJsonbValue::Bool(false));
-    }
-    if let Ok(n) = s.parse::<f64>() {
-        return Some(ParsedJsonbValue::Number(n));
-    }
-    if s.starts_with('"') && s.ends_with('"') && s.len() >= 2 {
-        return Some(ParsedJsonbValue::String(unescape_json(&s[1..s.len() - 1])));
-    }
-    if s.starts_with('{') {
-        return parse_json_object(s);
-    }
-    if s.starts_with('[') {
-        return parse_json_array(s);
-    }
-    None
-}
-
-fn unescape_json(s: &str) -> String {
-    let mut result = String::new();
-    let mut chars = s.chars();
-    while let Some(c) = chars.next() {
-        if c == '\\' {
-            match chars.next() {
-                Some('n') => result.push('\n'),
-                Some('t') => result.push('\t'),
-                Some('r') => result.push('\r'),
-                Some('"') => result.push('"'),
-                Some('\\') => result.push('\\'),
-                Some('/') => result.push('/'),
-                Some(other) => {
-                    result.push('\\');
-                    result.push(other);
-                }
-                None => result.push('\\'),
-            }
-        } else {
-            result.push(c);
-        }
-    }
-    result
-}
-
-fn parse_json_object(s: &str) -> Option<ParsedJsonbValue> {
-    let s = s.trim();
-    if !s.starts_with('{') || !s.ends_with('}') {
-        return None;
-    }
-
-    let inner = s[1..s.len() - 1].trim();
-    if inner.is_empty() {
-        return Some(ParsedJsonbValue::Object(JsonbObject::new()));
-    }
-
-    let mut obj = JsonbObject::new();
-    for pair in split_json_top_level(inner, ',') {
-        let pair = pair.trim();
-        let colon_pos = find_json_colon(pair)?;
-        let key_str = pair[..colon_pos].trim();
-        let value_str = pair[colon_pos + 1..].trim();
-
-        if !(key_str.starts_with('"') && key_str.ends_with('"') && key_str.len() >= 2) {
-            return None;
-        }
-
-        let key = unescape_json(&key_str[1..key_str.len() - 1]);
-        obj.insert(key, parse_json_text(value_str)?);
-    }
-
-    Some(ParsedJsonbValue::Object(obj))
-}
-
-fn parse_json_array(s: &str) -> Option<ParsedJsonbValue> {
-    let s = s.trim();
-    if !s.starts_with('[') || !s.ends_with(']') {
-        return None;
-    }
-
-    let inner = s[1..s.len() - 1].trim();
-    if inner.is_empty() {
-        return Some(ParsedJsonbValue::Array(Vec::new()));
-    }
-
-    let mut values = Vec::new();
-    for value in split_json_top_level(inner, ',') {
-        values.push(parse_json_text(value.trim())?);
-    }
-
-    Some(ParsedJsonbValue::Array(values))
-}
-
-fn split_json_top_level(s: &str, separator: char) -> Vec<&str> {
-    let mut parts = Vec::new();
-    let mut depth = 0i32;
-    let mut in_string = false;
-    let mut escape = false;
-    let mut start = 0usize;
-
-    for (i, c) in s.char_indices() {
-        if escape {
-            escape = false;
-            continue;
-        }
-        if c == '\\' && in_string {
-            escape = true;
-            continue;
-        }
-        if c == '"' {
-            in_string = !in_string;
-            continue;
-        }
-        if in_string {
-            continue;
-        }
-        if c == '{' || c == '[' {
-            depth += 1;
-        } else if c == '}' || c == ']' {
-            depth -= 1;
-        } else if c == separator && depth == 0 {
-            parts.push(&s[start..i]);
-            start = i + c.len_utf8();
-        }
-    }
-
-    if start <= s.len() {
-        parts.push(&s[start..]);
-    }
-
-    parts
-}
-
-fn find_json_colon(s: &str) -> Option<usize> {
-    let mut in_string = false;
-    let mut escape = false;
-
-    for (i, c) in s.char_indices() {
-        if escape {
-            escape = false;
-            continue;
-        }
-        if c == '\\' && in_string {
-            escape = true;
-            continue;
-        }
-        if c == '"' {
-            in_string = !in_string;
-            continue;
-        }
-        if !in_string && c == ':' {
-            return Some(i);
-        }
-    }
-
-    None
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1957,6 +2032,46 @@ mod tests {
         assert_eq!(store.len(), 1);
     }
 
+    #[test]
+    fn test_index_histogram_tracks_inserts_and_deletes() {
+        let mut store = RowStore::new(test_schema_with_index());
+        for i in 0..1000 {
+            store
+                .insert(Row::new(
+                    i,
+                    vec![Value::Int64(i as i64), Value::Int64(i as i64)],
+                ))
+                .unwrap();
+        }
+        let estimate = |store: &RowStore, range: KeyRange<Value>| {
+            store
+                .index_histogram("idx_value")
+                .unwrap()
+                .estimate_selectivity(&range)
+        };
+
+        // Appended keys keep the buckets balanced through rebuilds.
+        let histogram = store.index_histogram("idx_value").unwrap();
+        assert_eq!(histogram.total(), 1000);
+        assert!(histogram.bucket_count() > 16);
+        let wide = estimate(&store, KeyRange::lower_bound(Value::Int64(100), false));
+        assert!((wide - 0.9).abs() < 0.05, "{wide}");
+        let narrow = estimate(&store, KeyRange::upper_bound(Value::Int64(10), false));
+        assert!(narrow < 0.05, "{narrow}");
+
+        for i in 0..500 {
+            store.delete(i).unwrap();
+        }
+        let upper_half = estimate(&store, KeyRange::lower_bound(Value::Int64(750), false));
+        assert!((upper_half - 0.5).abs() < 0.1, "{upper_half}");
+
+        // The primary key is indexed like any other column; hash indexes
+        // keep no histogram.
+        assert_eq!(store.index_histogram("pkTest").unwrap().total(), 500);
+        let hashed = RowStore::new(test_schema_with_hash_index());
+        assert!(hashed.index_histogram("idx_value_hash").is_none());
+    }
+
     #[test]
     fn test_row_store_get() {
         let mut store = RowStore::new(test_schema());
@@ -2547,6 +2662,107 @@ mod tests {
         assert_eq!(results.len(), 1);
     }
 
+    #[test]
+    fn test_enum_column_rejects_out_of_domain_writes() {
+        let schema = TableBuilder::new("test")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_enum_column("status", &["active", "inactive"])
+            .unwrap()
+            .add_primary_key(&["id"], false)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut store = RowStore::new(schema);
+        let row = |id: i64, status: &str| {
+            Row::new(
+                id as u64,
+                vec![Value::Int64(id), Value::String(status.into())],
+            )
+        };
+
+        store.insert(row(1, "active")).unwrap();
+        assert!(matches!(
+            store.insert(row(2, "archived")),
+            Err(Error::DomainConstraint { .. })
+        ));
+        assert_eq!(store.len(), 1);
+
+        store.update(1, row(1, "inactive")).unwrap();
+        assert!(matches!(
+            store.update(1, row(1, "archived")),
+            Err(Error::DomainConstraint { .. })
+        ));
+        assert_eq!(
+            store.get(1).unwrap().get(1),
+            Some(&Value::String("inactive".into()))
+        );
+    }
+
+    #[test]
+    fn test_conditional_index_tracks_flag() {
+        let schema = TableBuilder::new("test")
+            .unwrap()
+            .add_column("id", DataType::Int64)
+            .unwrap()
+            .add_column("value", DataType::Int64)
+            .unwrap()
+            .add_column("active", DataType::Boolean)
+            .unwrap()
+            .add_primary_key(&["id"], false)
+            .unwrap()
+            .add_conditional_index("idx_active_value", &["value"], true, "active")
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut store = RowStore::new(schema);
+        let row = |id: i64, value: i64, active: bool| {
+            Row::new(
+                id as u64,
+                vec![
+                    Value::Int64(id),
+                    Value::Int64(value),
+                    Value::Boolean(active),
+                ],
+            )
+        };
+        let indexed_ids = |store: &RowStore| -> Vec<u64> {
+            store
+                .index_scan("idx_active_value", None)
+                .iter()
+                .map(|r| r.id())
+                .collect()
+        };
+
+        store.insert(row(1, 10, true)).unwrap();
+        store.insert(row(2, 20, false)).unwrap();
+        assert_eq!(indexed_ids(&store), vec![1]);
+
+        // Uniqueness only applies among flagged rows.
+        store.insert(row(3, 10, false)).unwrap();
+        assert!(store.insert(row(4, 10, true)).is_err());
+
+        // Setting the flag moves the row into the index...
+        store.update(2, row(2, 20, true)).unwrap();
+        assert_eq!(indexed_ids(&store), vec![1, 2]);
+        // ...and clearing it moves the row out again.
+        store.update(1, row(1, 10, false)).unwrap();
+        assert_eq!(indexed_ids(&store), vec![2]);
+        assert!(store
+            .index_scan("idx_active_value", Some(&KeyRange::only(Value::Int64(10))))
+            .is_empty());
+
+        // The value freed by row 1 can now be claimed by row 3.
+        store.update(3, row(3, 10, true)).unwrap();
+        assert_eq!(indexed_ids(&store), vec![3, 2]);
+        assert!(store.update(1, row(1, 10, true)).is_err());
+
+        store.delete(3).unwrap();
+        store.delete_batch(&[1, 2]);
+        assert!(indexed_ids(&store).is_empty());
+    }
+
     #[test]
     fn test_visit_index_scan_matches_materialized_scan() {
         let mut store = RowStore::new(test_schema_with_index());
@@ -2575,6 +2791,38 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_index_range_count_matches_index_scan() {
+        let mut store = RowStore::new(test_schema_with_index());
+        for i in 1..=20 {
+            store
+                .insert(Row::new(
+                    i,
+                    vec![Value::Int64(i as i64), Value::Int64((i % 7 * 100) as i64)],
+                ))
+                .unwrap();
+        }
+
+        let ranges = [
+            None,
+            Some(KeyRange::only(Value::Int64(300))),
+            Some(KeyRange::bound(
+                Value::Int64(200),
+                Value::Int64(500),
+                true,
+                false,
+            )),
+            Some(KeyRange::upper_bound(Value::Int64(400), true)),
+        ];
+        for range in &ranges {
+            assert_eq!(
+                store.index_range_count("idx_value", range.as_ref()),
+                store.index_scan("idx_value", range.as_ref()).len()
+            );
+        }
+        assert_eq!(store.index_range_count("missing", None), 0);
+    }
+
     #[test]
     fn test_visit_composite_index_scan_matches_materialized_scan() {
         let mut store = RowStore::new(test_schema_with_composite_index());
@@ -3338,4 +3586,53 @@ mod tests {
             "Different composite key should succeed"
         );
     }
+
+    #[test]
+    fn test_point_cache_returns_current_row_after_writes() {
+        let mut store = RowStore::new(test_schema());
+        store.set_point_cache_capacity(2);
+        for id in 1..=3 {
+            store
+                .insert(Row::new(
+                    id,
+                    vec![Value::Int64(id as i64), Value::String("old".into())],
+                ))
+                .unwrap();
+        }
+
+        let first = store.get_by_pk(&Value::Int64(1));
+        let hit = store.get_by_pk(&Value::Int64(1));
+        assert!(Rc::ptr_eq(&first[0], &hit[0]));
+
+        store
+            .update(
+                1,
+                Row::new(1, vec![Value::Int64(1), Value::String("new".into())]),
+            )
+            .unwrap();
+        assert_eq!(
+            store.get_by_pk(&Value::Int64(1))[0].get(1),
+            Some(&Value::String("new".into()))
+        );
+
+        // Capacity 2: looking up 2 and 3 evicts 1, which still resolves.
+        store.get_by_pk(&Value::Int64(2));
+        store.get_by_pk(&Value::Int64(3));
+        assert_eq!(store.point_cache.borrow().entries.len(), 2);
+        assert_eq!(store.get_by_pk(&Value::Int64(1)).len(), 1);
+
+        store.delete(2).unwrap();
+        assert!(store.get_by_pk(&Value::Int64(2)).is_empty());
+        store
+            .insert(Row::new(
+                9,
+                vec![Value::Int64(2), Value::String("again".into())],
+            ))
+            .unwrap();
+        assert_eq!(store.get_by_pk(&Value::Int64(2))[0].id(), 9);
+
+        store.set_point_cache_capacity(0);
+        assert!(store.point_cache.borrow().entries.is_empty());
+        assert_eq!(store.get_by_pk(&Value::Int64(3)).len(), 1);
+    }
 }
@@ -17,11 +17,19 @@ const DataType = {
   DateTime: 5,
   Bytes: 6,
   Jsonb: 7,
+  // Encoded as decimal text and returned as a string to keep every digit
+  Decimal: 8,
 } as const;
 
 // Header size in bytes
 const HEADER_SIZE = 16;
 
+// Header flag bit set when any row contains a NULL
+const FLAG_HAS_NULLS = 1;
+
+// Header flag bit set when Int64 columns hold 64-bit integers instead of f64
+const FLAG_INT64_AS_BIGINT = 1 << 2;
+
 // Shared TextDecoder instance for performance
 const textDecoder = new TextDecoder();
 
@@ -120,7 +128,7 @@ export class ResultSet<T = Record<string, unknown>> implements Iterable<T> {
    * Check if a value is null.
    */
   isNull(rowIndex: number, columnIndex: number): boolean {
-    if (this._flags === 0) return false; // No nulls in result
+    if ((this._flags & FLAG_HAS_NULLS) === 0) return false; // No nulls in result
 
     const rowOffset = HEADER_SIZE + rowIndex * this._rowStride;
     const byteIndex = Math.floor(columnIndex / 8);
@@ -156,6 +164,15 @@ export class ResultSet<T = Record<string, unknown>> implements Iterable<T> {
     return this.dataView.getFloat64(offset, true);
   }
 
+  /**
+   * Read an Int64 column written in BigInt mode as an exact bigint.
+   */
+  getBigInt(rowIndex: number, columnIndex: number): bigint | null {
+    if (this.isNull(rowIndex, columnIndex)) return null;
+    const offset = this.getValueOffset(rowIndex, columnIndex);
+    return this.dataView.getBigInt64(offset, true);
+  }
+
   getString(rowIndex: number, columnIndex: number): string | null {
     if (this.isNull(rowIndex, columnIndex)) return null;
     const offset = this.getValueOffset(rowIndex, columnIndex);
@@ -222,11 +239,15 @@ export class ResultSet<T = Record<string, unknown>> implements Iterable<T> {
       case DataType.Int32:
         return this.getInt32(rowIndex, columnIndex);
       case DataType.Int64:
+        return (this._flags & FLAG_INT64_AS_BIGINT) !== 0
+          ? this.getBigInt(rowIndex, columnIndex)
+          : this.getNumber(rowIndex, columnIndex);
       case DataType.Float64:
         return this.getNumber(rowIndex, columnIndex);
       case DataType.DateTime:
         return this.getDate(rowIndex, columnIndex);
       case DataType.String:
+      case DataType.Decimal:
         return this.getString(rowIndex, columnIndex);
       case DataType.Bytes:
         return this.getBytes(rowIndex, columnIndex);
@@ -243,7 +264,8 @@ export class ResultSet<T = Record<string, unknown>> implements Iterable<T> {
    * so V8 creates a single hidden class for all row objects.
    */
   private _compileRowDecoder(): (rowIndex: number) => T {
-    const hasNulls = this._flags !== 0;
+    const hasNulls = (this._flags & FLAG_HAS_NULLS) !== 0;
+    const int64AsBigInt = (this._flags & FLAG_INT64_AS_BIGINT) !== 0;
     const colCount = this._columnNames.length;
     const nullMaskSize = this._nullMaskSize;
     const varOffset = this._varOffset;
@@ -280,6 +302,12 @@ export class ResultSet<T = Record<string, unknown>> implements Iterable<T> {
           lines.push(`${vName} = dv.getInt32(${off}, true);`);
           break;
         case DataType.Int64:
+          lines.push(
+            int64AsBigInt
+              ? `${vName} = dv.getBigInt64(${off}, true);`
+              : `${vName} = dv.getFloat64(${off}, true);`
+          );
+          break;
         case DataType.Float64:
           lines.push(`${vName} = dv.getFloat64(${off}, true);`);
           break;
@@ -287,6 +315,7 @@ export class ResultSet<T = Record<string, unknown>> implements Iterable<T> {
           lines.push(`${vName} = new Date(dv.getFloat64(${off}, true));`);
           break;
         case DataType.String:
+        case DataType.Decimal:
           lines.push(`{ var so = dv.getUint32(${off}, true), sl = dv.getUint32(${off} + 4, true);`);
           lines.push(`${vName} = sl === 0 ? '' : td.decode(u8.subarray(${varOffset} + so, ${varOffset} + so + sl)); }`);
           break;
@@ -339,7 +368,8 @@ export class ResultSet<T = Record<string, unknown>> implements Iterable<T> {
     const row: Record<string, unknown> = {};
     const rowOffset = HEADER_SIZE + rowIndex * this._rowStride;
     const dataOffset = rowOffset + this._nullMaskSize;
-    const hasNulls = this._flags !== 0;
+    const hasNulls = (this._flags & FLAG_HAS_NULLS) !== 0;
+    const int64AsBigInt = (this._flags & FLAG_INT64_AS_BIGINT) !== 0;
     const colCount = this._columnNames.length;
 
     for (let i = 0; i < colCount; i++) {
@@ -363,13 +393,18 @@ export class ResultSet<T = Record<string, unknown>> implements Iterable<T> {
           row[this._columnNames[i]] = this.dataView.getInt32(offset, true);
           break;
         case DataType.Int64:
+          row[this._columnNames[i]] = int64AsBigInt
+            ? this.dataView.getBigInt64(offset, true)
+            : this.dataView.getFloat64(offset, true);
+          break;
         case DataType.Float64:
           row[this._columnNames[i]] = this.dataView.getFloat64(offset, true);
           break;
         case DataType.DateTime:
           row[this._columnNames[i]] = new Date(this.dataView.getFloat64(offset, true));
           break;
-        case DataType.String: {
+        case DataType.String:
+        case DataType.Decimal: {
           const strOffset = this.dataView.getUint32(offset, true);
           const strLength = this.dataView.getUint32(offset + 4, true);
           if (strLength === 0) {
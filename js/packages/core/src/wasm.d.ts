@@ -373,6 +373,7 @@ export enum JsDataType {
     DateTime = 5,
     Bytes = 6,
     Jsonb = 7,
+    Decimal = 8,
 }
 
 /**